@@ -0,0 +1,61 @@
+//! Minimal screen plugin. Build it with
+//! `cargo build -p example_screen_plugin` and list the library, e.g.
+//! `target/debug/libexample_screen_plugin.so`, in the `plugin_paths` of
+//! `mcg-frontend.toml` or in `MCG_PLUGIN_PATHS` to find a "Hello Plugin"
+//! entry in the main menu of a native build.
+
+use std::ffi::{c_char, c_void};
+
+use frontend::game::screens::{AppInterface, ScreenWidget};
+use frontend::plugin_loader::{into_plugin_screen, PluginScreenDef, PLUGIN_ABI_VERSION};
+
+#[derive(Default)]
+struct HelloScreen {
+    clicks: u32,
+}
+
+impl ScreenWidget for HelloScreen {
+    fn ui(
+        &mut self,
+        _app_interface: &mut AppInterface,
+        ui: &mut egui::Ui,
+        _frame: &mut eframe::Frame,
+    ) {
+        ui.heading("Hello from a plugin");
+        if ui.button("Click me").clicked() {
+            self.clicks += 1;
+        }
+        ui.label(format!("Clicked {} times", self.clicks));
+    }
+}
+
+extern "C" fn create_hello_screen() -> *mut c_void {
+    into_plugin_screen(Box::new(HelloScreen::default()))
+}
+
+struct ScreenList([PluginScreenDef; 2]);
+
+// SAFETY: the definitions only point to string literals
+unsafe impl Sync for ScreenList {}
+
+static SCREENS: ScreenList = ScreenList([
+    PluginScreenDef {
+        path: c"/hello".as_ptr(),
+        display_name: c"Hello Plugin".as_ptr(),
+        factory: Some(create_hello_screen),
+    },
+    PluginScreenDef::END,
+]);
+
+/// Compiler and `frontend` version this plugin was built with, compared
+/// with the app's before the screens are read
+#[no_mangle]
+pub extern "C" fn mcg_plugin_abi_version() -> *const c_char {
+    PLUGIN_ABI_VERSION.as_ptr()
+}
+
+/// Screens of this plugin, read by the app when it loads the library
+#[no_mangle]
+pub extern "C" fn mcg_plugin_screens() -> *const PluginScreenDef {
+    SCREENS.0.as_ptr()
+}
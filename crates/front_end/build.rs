@@ -171,14 +171,13 @@ fn get_first_terminals(
             }
 
             // 3. Recurse into the rule definition
-            if !visited.contains(name) {
-                if let Some(sub_expr) = rule_map.get(name) {
+            if !visited.contains(name)
+                && let Some(sub_expr) = rule_map.get(name) {
                     visited.push(name.clone());
                     let res = get_first_terminals(sub_expr, rule_map, visited);
                     visited.pop();
                     return res;
                 }
-            }
             vec![]
         }
 
@@ -216,12 +215,11 @@ fn can_be_empty(expr: &Expr, rule_map: &HashMap<String, &Expr>, visited: &mut Ve
     match expr {
         Expr::Opt(_) | Expr::Rep(_) => true,
         Expr::Ident(name) => {
-            if !visited.contains(name) {
-                if let Some(sub_expr) = rule_map.get(name) {
+            if !visited.contains(name)
+                && let Some(sub_expr) = rule_map.get(name) {
                     visited.push(name.clone());
                     return can_be_empty(sub_expr, rule_map, visited);
                 }
-            }
             false
         },
         Expr::Seq(a, b) => can_be_empty(a, rule_map, visited) && can_be_empty(b, rule_map, visited),
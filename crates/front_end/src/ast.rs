@@ -1,28 +1,32 @@
-///    You can define more AST-structures in here.
-///    It will automatically generate a Spanned-Version of the new AST
-///    and the corresponding Lowering/Walking logic for it.
-///
-///    Be careful with naming certain structs and enums:
-///    For example the Spanned versions have always a 'S' at the front
-///    of the original naming:
-///    IntExpr -> SIntExpr
-///
-///    Do not do naming like this (in this file):
-///    enum Template {
-///        ...
-///    } 
-///
-///    enum STemplate {
-///        ...
-///    }
-///
-///    This will cause an error.
-///
-///    For more information look at code_gen.
+//    You can define more AST-structures in here.
+//    It will automatically generate a Spanned-Version of the new AST
+//    and the corresponding Lowering/Walking logic for it.
+//
+//    Be careful with naming certain structs and enums:
+//    For example the Spanned versions have always a 'S' at the front
+//    of the original naming:
+//    IntExpr -> SIntExpr
+//
+//    Do not do naming like this (in this file):
+//    enum Template {
+//        ...
+//    } 
+//
+//    enum STemplate {
+//        ...
+//    }
+//
+//    This will cause an error.
+//
+//    For more information look at code_gen.
 
 
 use code_gen::*;
 
+// The AST is built once per game and only walked afterwards, so the size of
+// the larger nodes does not matter; boxing them would also change the
+// generated spanned AST
+#[allow(clippy::large_enum_variant)]
 #[spanned_ast]
 pub mod ast {
     use arbitrary::Arbitrary;
@@ -1,4 +1,4 @@
-/// Has all format-logic of the AST.
+// Has all format-logic of the AST.
 
 use core::fmt;
 
@@ -189,7 +189,7 @@ impl fmt::Display for EndType {
         let s = match self {
             EndType::Turn => "turn",
             EndType::CurrentStage => "stage",
-            EndType::Stage { stage } => &format!("{}", stage),
+            EndType::Stage { stage } => &stage.to_string(),
             EndType::GameWithWinner { players } => &format!("game with winner {}", players),
         };
         f.write_str(s)
@@ -280,7 +280,7 @@ impl fmt::Display for SingleOwner {
                 &format!("{}", team)
             }
             SingleOwner::Table => {
-                &format!("table")
+                &"table".to_string()
             }
         };
         f.write_str(s)
@@ -705,7 +705,7 @@ impl fmt::Display for AggregateFilter {
                 key,
                 string: string_expr,
             } => &format!("{} is not {}", key, string_expr),
-            AggregateFilter::Combo { combo } => &format!("{}", combo),
+            AggregateFilter::Combo { combo } => &combo.to_string(),
             AggregateFilter::NotCombo { combo } => &format!("not {}", combo),
         };
         f.write_str(s)
@@ -1069,7 +1069,7 @@ impl fmt::Display for ActionRule {
 impl fmt::Display for UseMemory {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
-            UseMemory::Memory { memory } => &format!("{}", memory),
+            UseMemory::Memory { memory } => &memory.to_string(),
             UseMemory::WithOwner { memory, owner } => &format!("{} of {}", memory, owner),
         };
         f.write_str(s)
@@ -1407,7 +1407,7 @@ impl fmt::Display for Game {
             .collect::<Vec<_>>() // collect into Vec<String>
             .join("\n");
 
-        let s = &format!("{}", fs);
+        let s = &fs.to_string();
         f.write_str(s)
     }
 }
@@ -17,15 +17,15 @@ use crate::ir::*;
 // Layout crate imports for pure-rust SVG generation
 use layout::backends::svg::SVGWriter;
 use layout::core::base::Orientation;
-use layout::topo::layout::VisualGraph;
 use layout::std_shapes::shapes::{Arrow, Element, ShapeKind};
+use layout::topo::layout::VisualGraph;
 
-pub fn fsm_to_svg<Ctx: AstContext>(
+pub fn fsm_to_svg<Ctx>(
     fsm: &Ir<Payload<Ctx>>,
     path: &Path,
 ) -> Result<(), Box<dyn std::error::Error>>
 where
-    Ctx: Serialize + DeserializeOwned,
+    Ctx: AstContext + Serialize + DeserializeOwned,
 {
     // 1. Create visual graph
     let mut vg = VisualGraph::new(Orientation::TopToBottom);
@@ -84,8 +84,10 @@ where
         let &source_handle = nodes.get(&state_id.raw()).unwrap();
         for edge in edges.iter() {
             if let Some(&target_handle) = nodes.get(&edge.to.raw()) {
-                let mut arrow = Arrow::default();
-                arrow.text = edge.payload.to_string();
+                let arrow = Arrow {
+                    text: edge.payload.to_string(),
+                    ..Arrow::default()
+                };
                 vg.add_edge(arrow, source_handle, target_handle);
             }
         }
@@ -105,21 +107,30 @@ where
     Ok(())
 }
 
-/// Generates a standard .dot file. 
+/// Generates a standard .dot file.
 /// Useful if the user HAS Graphviz or for use in the VS Code Webview.
-pub fn fsm_to_dot<Ctx: AstContext>(
+pub fn fsm_to_dot<Ctx>(
     fsm: &Ir<Payload<Ctx>>,
     path: &Path,
 ) -> Result<(), Box<dyn std::error::Error>>
 where
-    Ctx: Serialize + DeserializeOwned,
+    Ctx: AstContext + Serialize + DeserializeOwned,
 {
     let mut file = File::create(path)?;
 
     writeln!(file, "digraph CFG {{")?;
-    writeln!(file, "  graph [splines=ortho, nodesep=1.0, ranksep=1.0, concentrate=true];")?;
-    writeln!(file, "  node [shape=box, fontname=\"Arial\", style=filled, fillcolor=\"#ffffff\", color=\"#333333\"];")?;
-    writeln!(file, "  edge [fontname=\"Arial\", fontsize=9, arrowsize=0.8];")?;
+    writeln!(
+        file,
+        "  graph [splines=ortho, nodesep=1.0, ranksep=1.0, concentrate=true];"
+    )?;
+    writeln!(
+        file,
+        "  node [shape=box, fontname=\"Arial\", style=filled, fillcolor=\"#ffffff\", color=\"#333333\"];"
+    )?;
+    writeln!(
+        file,
+        "  edge [fontname=\"Arial\", fontsize=9, arrowsize=0.8];"
+    )?;
 
     writeln!(file, "  entry [shape=point];")?;
     writeln!(file, "  entry -> {:?};", fsm.entry.raw())?;
@@ -139,4 +150,4 @@ where
 
     writeln!(file, "}}")?;
     Ok(())
-}
\ No newline at end of file
+}
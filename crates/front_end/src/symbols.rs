@@ -1,9 +1,8 @@
-///    We check the Symbols via two main concepts: Initialization and Usage.
-///    If a Variable is used we give the Variable a NoType. If it is initialized
-///    then we give it the specific initialization Type (e.g. GameType::Player).
-///    We can catch if a Symbol is never initialized and/or if it is defined
-///    multiple times.
-
+//    We check the Symbols via two main concepts: Initialization and Usage.
+//    If a Variable is used we give the Variable a NoType. If it is initialized
+//    then we give it the specific initialization Type (e.g. GameType::Player).
+//    We can catch if a Symbol is never initialized and/or if it is defined
+//    multiple times.
 
 use std::collections::HashMap;
 
@@ -62,6 +61,12 @@ pub struct SymbolVisitor {
     symbols: HashMap<SID, GameType>,
 }
 
+impl Default for SymbolVisitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SymbolVisitor {
     pub fn new() -> Self {
         SymbolVisitor {
@@ -155,8 +160,8 @@ impl SymbolVisitor {
             .collect();
 
         self.symbols
-            .iter()
-            .map(|(k, _)| {
+            .keys()
+            .map(|k| {
                 let ty = string_to_type
                     .get(&k.node)
                     .cloned()
@@ -173,7 +178,7 @@ impl SymbolVisitor {
 
         for (var, game_type) in typed_vars {
             // .entry() handles the case where the GameType isn't in the map yet
-            map.entry(game_type).or_insert_with(Vec::new).push(var.id); // Assuming var is the String name
+            map.entry(game_type).or_default().push(var.id); // Assuming var is the String name
         }
 
         map
@@ -188,90 +193,69 @@ impl AstPass for SymbolVisitor {
     {
         if let Some(unwrapped) = node.kind() {
             match unwrapped {
-                NodeKind::OutOf(o) => match o {
-                    OutOf::Stage { name: spanned } => {
-                        self.use_id(&spanned);
-                    }
-                    _ => {}
-                },
-                NodeKind::Groupable(g) => match g {
-                    Groupable::Location { name: spanned } => {
-                        self.use_id(&spanned);
-                    }
-                    _ => {}
-                },
+                NodeKind::OutOf(OutOf::Stage { name: spanned }) => {
+                    self.use_id(spanned);
+                }
+                NodeKind::Groupable(Groupable::Location { name: spanned }) => {
+                    self.use_id(spanned);
+                }
                 NodeKind::Types(t) => {
                     for (k, vs) in t.types.iter() {
-                        self.init_id(&k, GameType::Key);
+                        self.init_id(k, GameType::Key);
                         for v in vs.iter() {
-                            self.init_id(&v, GameType::Value);
+                            self.init_id(v, GameType::Value);
                         }
                     }
                 }
-                NodeKind::AggregatePlayer(a) => match a {
-                    AggregatePlayer::OwnerOfMemory {
-                        extrema: _,
-                        memory: spanned1,
-                    } => {
-                        self.use_id(&spanned1);
-                    }
-                    _ => {}
-                },
-                NodeKind::PlayerExpr(p) => match p {
-                    PlayerExpr::Literal { name: spanned } => {
-                        self.use_id(&spanned);
-                    }
-                    _ => {}
-                },
+                NodeKind::AggregatePlayer(AggregatePlayer::OwnerOfMemory {
+                    extrema: _,
+                    memory: spanned1,
+                }) => {
+                    self.use_id(spanned1);
+                }
+                NodeKind::PlayerExpr(PlayerExpr::Literal { name: spanned }) => {
+                    self.use_id(spanned);
+                }
                 NodeKind::AggregateInt(a) => match a {
                     AggregateInt::SumOfCardSet {
                         card_set: _,
                         pointmap: spanned1,
                     } => {
-                        self.use_id(&spanned1);
+                        self.use_id(spanned1);
                     }
                     AggregateInt::ExtremaCardset {
                         extrema: _,
                         card_set: _,
                         pointmap: spanned2,
                     } => {
-                        self.use_id(&spanned2);
-                    }
-                    _ => {}
-                },
-                NodeKind::QueryString(q) => match q {
-                    QueryString::KeyOf {
-                        key: spanned,
-                        card_position: _,
-                    } => {
-                        self.use_id(&spanned);
-                    }
-                    _ => {}
-                },
-                NodeKind::StringExpr(s) => match s {
-                    StringExpr::Literal { value: spanned } => {
-                        self.use_id(&spanned);
-                    }
-                    _ => {}
-                },
-                NodeKind::TeamExpr(t) => match t {
-                    TeamExpr::Literal { name: spanned } => {
-                        self.use_id(&spanned);
+                        self.use_id(spanned2);
                     }
                     _ => {}
                 },
+                NodeKind::QueryString(QueryString::KeyOf {
+                    key: spanned,
+                    card_position: _,
+                }) => {
+                    self.use_id(spanned);
+                }
+                NodeKind::StringExpr(StringExpr::Literal { value: spanned }) => {
+                    self.use_id(spanned);
+                }
+                NodeKind::TeamExpr(TeamExpr::Literal { name: spanned }) => {
+                    self.use_id(spanned);
+                }
                 NodeKind::QueryCardPosition(q) => match q {
                     QueryCardPosition::At {
                         location: spanned,
                         int_expr: _,
                     } => {
-                        self.use_id(&spanned);
+                        self.use_id(spanned);
                     }
                     QueryCardPosition::Top { location: spanned } => {
-                        self.use_id(&spanned);
+                        self.use_id(spanned);
                     }
                     QueryCardPosition::Bottom { location: spanned } => {
-                        self.use_id(&spanned);
+                        self.use_id(spanned);
                     }
                 },
                 NodeKind::AggregateCardPosition(a) => match a {
@@ -280,98 +264,95 @@ impl AstPass for SymbolVisitor {
                         card_set: _,
                         pointmap: spanned2,
                     } => {
-                        self.use_id(&spanned2);
+                        self.use_id(spanned2);
                     }
                     AggregateCardPosition::ExtremaPrecedence {
                         extrema: _,
                         card_set: _,
                         precedence: spanned2,
                     } => {
-                        self.use_id(&spanned2);
+                        self.use_id(spanned2);
                     }
                 },
-                NodeKind::LocationCollection(l) => match l {
-                    LocationCollection::Literal { locations } => {
-                        for location in locations.iter() {
-                            self.use_id(&location);
-                        }
+                NodeKind::LocationCollection(LocationCollection::Literal { locations }) => {
+                    for location in locations.iter() {
+                        self.use_id(location);
                     }
-                    _ => {}
-                },
+                }
                 NodeKind::Group(g) => match g {
                     Group::NotCombo {
                         combo: spanned,
                         groupable: _,
                     } => {
-                        self.use_id(&spanned);
+                        self.use_id(spanned);
                     }
                     Group::Combo {
                         combo: spanned,
                         groupable: _,
                     } => {
-                        self.use_id(&spanned);
+                        self.use_id(spanned);
                     }
                     _ => {}
                 },
                 NodeKind::AggregateFilter(a) => match a {
                     AggregateFilter::Same { key: spanned } => {
-                        self.use_id(&spanned);
+                        self.use_id(spanned);
                     }
                     AggregateFilter::Distinct { key: spanned } => {
-                        self.use_id(&spanned);
+                        self.use_id(spanned);
                     }
                     AggregateFilter::Adjacent {
                         key: spanned,
                         precedence: spanned1,
                     } => {
-                        self.use_id(&spanned);
-                        self.use_id(&spanned1);
+                        self.use_id(spanned);
+                        self.use_id(spanned1);
                     }
                     AggregateFilter::Higher {
                         key: spanned,
                         value: _,
                         precedence: spanned1,
                     } => {
-                        self.use_id(&spanned);
-                        self.use_id(&spanned1);
+                        self.use_id(spanned);
+                        self.use_id(spanned1);
                     }
                     AggregateFilter::Lower {
                         key: spanned,
                         value: _,
                         precedence: spanned1,
                     } => {
-                        self.use_id(&spanned);
-                        self.use_id(&spanned1);
+                        self.use_id(spanned);
+                        self.use_id(spanned1);
                     }
                     AggregateFilter::KeyIsString {
                         key: spanned,
                         string: _,
                     } => {
-                        self.use_id(&spanned);
+                        self.use_id(spanned);
                     }
                     AggregateFilter::KeyIsNotString {
                         key: spanned,
                         string: _,
                     } => {
-                        self.use_id(&spanned);
+                        self.use_id(spanned);
                     }
                     AggregateFilter::Combo { combo: spanned } => {
-                        self.use_id(&spanned);
+                        self.use_id(spanned);
                     }
                     AggregateFilter::NotCombo { combo: spanned } => {
-                        self.use_id(&spanned);
+                        self.use_id(spanned);
                     }
                     _ => {}
                 },
                 NodeKind::SetUpRule(s) => match s {
                     SetUpRule::CreatePlayer { players: spanneds } => {
                         for s in spanneds.iter() {
-                            self.init_id(&s, GameType::Player);
+                            self.init_id(s, GameType::Player);
                         }
                     }
                     SetUpRule::CreateTeams { teams: items } => {
                         for (t, _) in items.iter() {
-                            self.init_id(&t, GameType::Team);
+                            self.init_id(t, GameType::Team);
                         }
                     }
                     SetUpRule::CreateLocation {
@@ -379,60 +360,60 @@ impl AstPass for SymbolVisitor {
                         owner: _,
                     } => {
                         for s in spanneds.iter() {
-                            self.init_id(&s, GameType::Location);
+                            self.init_id(s, GameType::Location);
                         }
                     }
                     SetUpRule::CreateCardOnLocation {
                         location: spanned,
                         cards: _,
                     } => {
-                        self.use_id(&spanned);
+                        self.use_id(spanned);
                     }
                     SetUpRule::CreateTokenOnLocation {
                         int: _,
                         token: spanned1,
                         location: spanned2,
                     } => {
-                        self.init_id(&spanned1, GameType::Token);
-                        self.use_id(&spanned2);
+                        self.init_id(spanned1, GameType::Token);
+                        self.use_id(spanned2);
                     }
                     SetUpRule::CreateCombo {
                         combo: spanned,
                         filter: _,
                     } => {
-                        self.init_id(&spanned, GameType::Combo);
+                        self.init_id(spanned, GameType::Combo);
                     }
                     SetUpRule::CreateMemory {
                         memory: spanned,
                         owner: _,
                     } => {
-                        self.init_id(&spanned, GameType::Memory);
+                        self.init_id(spanned, GameType::Memory);
                     }
                     SetUpRule::CreateMemoryWithMemoryType {
                         memory: spanned,
                         memory_type: _,
                         owner: _,
                     } => {
-                        self.init_id(&spanned, GameType::Memory);
+                        self.init_id(spanned, GameType::Memory);
                     }
                     SetUpRule::CreatePrecedence {
                         precedence: spanned,
                         kvs: items,
                     } => {
-                        self.init_id(&spanned, GameType::Precedence);
+                        self.init_id(spanned, GameType::Precedence);
                         for (k, v) in items.iter() {
-                            self.use_id(&k);
-                            self.use_id(&v);
+                            self.use_id(k);
+                            self.use_id(v);
                         }
                     }
                     SetUpRule::CreatePointMap {
                         pointmap: spanned,
                         kvis: items,
                     } => {
-                        self.init_id(&spanned, GameType::PointMap);
+                        self.init_id(spanned, GameType::PointMap);
                         for (k, v, _) in items.iter() {
-                            self.use_id(&k);
-                            self.use_id(&v);
+                            self.use_id(k);
+                            self.use_id(v);
                         }
                     }
                     _ => {}
@@ -442,17 +423,17 @@ impl AstPass for SymbolVisitor {
                         memory: spanned,
                         memory_type: _,
                     } => {
-                        self.use_id(&spanned);
+                        self.use_id(spanned);
                     }
                     ActionRule::ResetMemory { memory: spanned } => {
-                        self.use_id(&spanned);
+                        self.use_id(spanned);
                     }
                     ActionRule::BidMemoryAction {
                         memory: spanned,
                         quantity: _,
                         owner: _,
                     } => {
-                        self.use_id(&spanned);
+                        self.use_id(spanned);
                     }
                     _ => {}
                 },
@@ -465,7 +446,7 @@ impl AstPass for SymbolVisitor {
                         from_loc: _,
                         to_loc: _,
                     } => {
-                        self.use_id(&spanned);
+                        self.use_id(spanned);
                     }
                     TokenMove::PlaceQuantity {
                         quantity: _,
@@ -473,53 +454,42 @@ impl AstPass for SymbolVisitor {
                         from_loc: _,
                         to_loc: _,
                     } => {
-                        self.use_id(&spanned1);
+                        self.use_id(spanned1);
                     }
                 },
-                NodeKind::ScoreRule(s) => match s {
-                    ScoreRule::ScoreMemory {
-                        int: _,
-                        memory: spanned1,
-                        players: _,
-                    } => {
-                        self.use_id(&spanned1);
-                    }
-                    _ => {}
-                },
-                NodeKind::WinnerType(w) => match w {
-                    WinnerType::Memory { memory: spanned } => {
-                        self.use_id(&spanned);
-                    }
-                    _ => {}
-                },
+                NodeKind::ScoreRule(ScoreRule::ScoreMemory {
+                    int: _,
+                    memory: spanned1,
+                    players: _,
+                }) => {
+                    self.use_id(spanned1);
+                }
+                NodeKind::WinnerType(WinnerType::Memory { memory: spanned }) => {
+                    self.use_id(spanned);
+                }
                 NodeKind::UseMemory(m) => match m {
-                    UseMemory::Memory { memory } => self.use_id(&memory),
-                    UseMemory::WithOwner { memory, owner: _ } => self.use_id(&memory),
+                    UseMemory::Memory { memory } => self.use_id(memory),
+                    UseMemory::WithOwner { memory, owner: _ } => self.use_id(memory),
                 },
                 NodeKind::UseSingleMemory(m) => match m {
-                    UseSingleMemory::Memory { memory } => self.use_id(&memory),
-                    UseSingleMemory::WithOwner { memory, owner: _ } => self.use_id(&memory),
-                },
-                NodeKind::IntCollection(ic) => match ic {
-                    IntCollection::AggregateMemory { memory, multi: _ } => self.use_id(&memory),
-                    _ => {},
-                },
-                NodeKind::StringCollection(sc) => match sc {
-                    StringCollection::AggregateMemory { memory, multi: _ } => self.use_id(&memory),
-                    _ => {},
-                },
-                NodeKind::TeamCollection(tc) => match tc {
-                    TeamCollection::AggregateMemory { memory, multi: _ } => self.use_id(&memory),
-                    _ => {},
-                },
-                NodeKind::PlayerCollection(pc) => match pc {
-                    PlayerCollection::AggregateMemory { memory, multi: _ } => self.use_id(&memory),
-                    _ => {},
-                },
-                NodeKind::RuntimeInt(ri) => match ri {
-                    RuntimeInt::StageRoundCounter { stage } => self.use_id(stage),
-                    _ => {},
+                    UseSingleMemory::Memory { memory } => self.use_id(memory),
+                    UseSingleMemory::WithOwner { memory, owner: _ } => self.use_id(memory),
                 },
+                NodeKind::IntCollection(IntCollection::AggregateMemory { memory, multi: _ }) => {
+                    self.use_id(memory)
+                }
+                NodeKind::StringCollection(StringCollection::AggregateMemory {
+                    memory,
+                    multi: _,
+                }) => self.use_id(memory),
+                NodeKind::TeamCollection(TeamCollection::AggregateMemory { memory, multi: _ }) => {
+                    self.use_id(memory)
+                }
+                NodeKind::PlayerCollection(PlayerCollection::AggregateMemory {
+                    memory,
+                    multi: _,
+                }) => self.use_id(memory),
+                NodeKind::RuntimeInt(RuntimeInt::StageRoundCounter { stage }) => self.use_id(stage),
                 _ => {}
             }
         }
@@ -1,18 +1,17 @@
-/// The testing consists of two types of testing:
-/// - Unit-Tests -> for special cases
-/// - Generated-Tests -> Arbitrary-style tests (Proptests)
-/// 
-/// We need a lot of generated tests because pest is a recursive descent Parser.
-/// 
-/// In addition to this:
-/// - Each AST-struct/-enum must have a fmt::Display trait that is the corresponding
-/// Rule in the grammar.pest (e.g. PlayerExpr::Current => "current").
-/// 
-/// This way we can check for mistakes in the AST-declaration and further Parsing errors
-/// by doing: Generate AST -> String-Represenation -> Parse -> assert_eq Generated-AST and Parse-Output
+// The testing consists of two types of testing:
+// - Unit-Tests -> for special cases
+// - Generated-Tests -> Arbitrary-style tests (Proptests)
+// 
+// We need a lot of generated tests because pest is a recursive descent Parser.
+// 
+// In addition to this:
+// - Each AST-struct/-enum must have a fmt::Display trait that is the corresponding
+// Rule in the grammar.pest (e.g. PlayerExpr::Current => "current").
+// 
+// This way we can check for mistakes in the AST-declaration and further Parsing errors
+// by doing: Generate AST -> String-Represenation -> Parse -> assert_eq Generated-AST and Parse-Output
 
 use std::fs;
-use std::path::Path;
 use std::process::Command;
 
 use crate::fsm_to_dot::fsm_to_dot;
@@ -99,19 +98,19 @@ fn parse_ast_parse(input: &str) {
 
 fn show_graph(fsm: &Ir<SpannedPayload>, name: &str) {
     // Make sure the output folder exists
-    let out_dir = Path::new("../tests_out");
+    let out_dir = std::env::temp_dir().join("cgdsl_tests_out");
     if !out_dir.exists() {
-        fs::create_dir_all(out_dir).expect("Failed to create output folder");
+        fs::create_dir_all(&out_dir).expect("Failed to create output folder");
     }
 
     let dot_path = out_dir.join(format!("{}.dot", name));
     let png_path = out_dir.join(format!("{}.png", name));
 
     // Generate .dot file
-    fsm_to_dot(&fsm, &dot_path).unwrap();
+    fsm_to_dot(fsm, &dot_path).unwrap();
 
-    // Call Graphviz to generate PNG
-    let status = Command::new("dot")
+    // Call Graphviz to generate PNG, rendering is skipped where it isn't installed
+    let status = match Command::new("dot")
         .args([
             "-Tpng",
             dot_path.to_str().unwrap(),
@@ -119,7 +118,10 @@ fn show_graph(fsm: &Ir<SpannedPayload>, name: &str) {
             png_path.to_str().unwrap(),
         ])
         .status()
-        .expect("failed to run dot");
+    {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+        status => status.expect("failed to run dot"),
+    };
 
     assert!(status.success());
 }
@@ -132,7 +134,7 @@ fn test_rule_ir() {
     ",
     );
 
-    assert_eq!(true, fsm.is_connected());
+    assert!(fsm.is_connected());
 
     show_graph(&fsm, "rule");
 }
@@ -147,7 +149,7 @@ fn test_optional_ir() {
     ",
     );
 
-    assert_eq!(true, fsm.is_connected());
+    assert!(fsm.is_connected());
 
     show_graph(&fsm, "optional");
 }
@@ -162,7 +164,7 @@ fn test_if_ir() {
     ",
     );
 
-    assert_eq!(true, fsm.is_connected());
+    assert!(fsm.is_connected());
 
     show_graph(&fsm, "if");
 }
@@ -183,7 +185,7 @@ fn test_stage_ir() {
     ",
     );
 
-    assert_eq!(true, fsm.is_connected());
+    assert!(fsm.is_connected());
 
     show_graph(&fsm, "stage");
 }
@@ -200,7 +202,7 @@ fn test_choose_ir() {
     ",
     );
 
-    assert_eq!(true, fsm.is_connected());
+    assert!(fsm.is_connected());
 
     show_graph(&fsm, "choose");
 }
@@ -220,7 +222,7 @@ fn test_conditional_ir() {
     ",
     );
 
-    assert_eq!(true, fsm.is_connected());
+    assert!(fsm.is_connected());
 
     show_graph(&fsm, "conditional");
 }
@@ -289,7 +291,7 @@ fn test_game_ir() {
       "
   );
 
-    assert_eq!(true, fsm.is_connected());
+    assert!(fsm.is_connected());
 
     show_graph(&fsm, "game");
 }
@@ -428,7 +430,7 @@ fn test_reparse_game() {
 #[test]
 fn test_specific_rule() {
     let input = "(&IC:Id608 of (&T:Id100 of playersin))";
-    let _ = match test_rule_consume(input, Rule::int_collection, CGDSLParser::int_collection) {
+    match test_rule_consume(input, Rule::int_collection, CGDSLParser::int_collection) {
         Ok(a) => {
             println!("{:?}", a);
         }
@@ -10,7 +10,7 @@ use std::sync::LazyLock;
 
 /// Generated Snippet completion rules
 static SNIPPET_LOOKUP: LazyLock<HashMap<&'static str, Vec<&'static str>>> =
-    LazyLock::new(|| get_all_snippets());
+    LazyLock::new(get_all_snippets);
 
 
 /// If the parser throws a parser error then we can try to use auto-completion logic.
@@ -49,11 +49,8 @@ pub fn get_completions(
                     vec
                 });
 
-                match items {
-                    Some(completion) => {
-                        completion_response.extend(completion);
-                    }
-                    None => {}
+                if let Some(completion) = items {
+                    completion_response.extend(completion);
                 }
             }
 
@@ -20,26 +20,26 @@ use tower_lsp::lsp_types::Diagnostic;
 /// * `Err(Vec<Diagnostic>)`: A collection of LSP-compatible errors found 
 ///   during either stage.
 pub fn validate_document(ast: &SGame) -> Result<HashMap<GameType, Vec<String>>, Vec<Diagnostic>> {
-    let symbol_table;
+    
 
-    match symbol_validation(&ast) {
+    let symbol_table = match symbol_validation(ast) {
         Err(errs) => {
             return Err(errs
                 .iter()
-                .map(|s| symbol_error_to_diagnostics(s))
+                .map(symbol_error_to_diagnostics)
                 .collect());
         }
-        Ok(table) => symbol_table = table,
-    }
+        Ok(table) => table,
+    };
 
-    if let Some(errs) = semantic_validation(&ast) {
+    if let Some(errs) = semantic_validation(ast) {
         return Err(errs
             .iter()
-            .map(|s| semantic_error_to_diagnostics(s))
+            .map(semantic_error_to_diagnostics)
             .collect());
     }
 
-    return Ok(symbol_table);
+    Ok(symbol_table)
 }
 
 /// Runs high-level program/game-logic validation on the AST.
@@ -51,15 +51,15 @@ pub fn validate_document(ast: &SGame) -> Result<HashMap<GameType, Vec<String>>,
 /// * `Some(Vec<Diagnostic>)` if errors are found.
 /// * `None` if the game logic is valid.
 pub fn validate_game(ast: &SGame) -> Option<Vec<Diagnostic>> {
-    if let Some(errs) = program_validation(&ast) {
+    if let Some(errs) = program_validation(ast) {
         return Some(
             errs.iter()
-                .map(|g| program_error_to_diagnostics(g))
+                .map(program_error_to_diagnostics)
                 .collect(),
         );
     }
 
-    return None;
+    None
 }
 
 /// Converts a [`Rope`] to a string and attempts to parse it into an [`SGame`] AST.
@@ -76,5 +76,5 @@ pub fn validate_parsing(doc: &Rope) -> Result<SGame, Vec<Diagnostic>> {
         return Err(vec![pest_error_to_diagnostic(err)]);
     }
 
-    return Ok(result.unwrap());
+    Ok(result.unwrap())
 }
@@ -0,0 +1,69 @@
+//! Gauss-Jordan elimination of coded equations, as an epoch decodes them.
+//!
+//! Each system has N equations in N unknowns with dense random coefficients
+//! and random fragments. Rows are built by mixing a unit upper triangle, so
+//! every system has full rank and eliminates down to the identity.
+//!
+//! Usage:
+//!   cargo bench -p `mcg_qr_comm` --bench `matrix_elimination`
+
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use mcg_qr_comm::data_structures::{Fragment, SparseFactor};
+use mcg_qr_comm::matrix::Matrix;
+use mcg_qr_comm::network_coding::{Equation, GaloisField2p4};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const SIZES: [usize; 3] = [50, 100, 200];
+
+// Systems have a few hundred unknowns
+#[allow(clippy::cast_possible_truncation)]
+fn random_system(n: usize, rng: &mut StdRng) -> Matrix {
+    let upper: Vec<Equation> = (0..n)
+        .map(|row| {
+            let mut factors = SparseFactor::default();
+            factors.inner.push((row as u32, GaloisField2p4::ONE));
+            for column in row + 1..n {
+                factors.inner.push((column as u32, rng.random()));
+            }
+            let mut fragment = Fragment::default();
+            rng.fill(&mut fragment.inner[..]);
+            Equation::new(factors, fragment)
+        })
+        .collect();
+    let inner = (0..n)
+        .map(|row| {
+            upper[..row].iter().fold(upper[row].clone(), |acc, eq| {
+                acc + eq.clone() * rng.random::<GaloisField2p4>()
+            })
+        })
+        .collect();
+    Matrix { inner }
+}
+
+fn matrix_elimination(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut group = c.benchmark_group("matrix_elimination");
+    group.sample_size(10);
+    for n in SIZES {
+        let matrix = random_system(n, &mut rng);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{n}x{n}")),
+            &matrix,
+            |b, m| {
+                b.iter_batched(
+                    || m.clone(),
+                    |mut m| {
+                        m.matrix_elimination();
+                        m
+                    },
+                    BatchSize::LargeInput,
+                );
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, matrix_elimination);
+criterion_main!(benches);
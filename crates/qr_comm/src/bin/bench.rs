@@ -0,0 +1,323 @@
+//! Encode/decode throughput of the network coding.
+//!
+//! Writes N random packages into an epoch, one per participant, and pushes
+//! coded frames into a fresh receiving epoch until every package is decoded.
+//! Prints a Markdown table, followed by the memory and row operation cost of
+//! dense (`WideFactor`) and sparse (`SparseFactor`) equation factors, and the
+//! cost of a GF(16) multiplication by table lookup and by polynomial arithmetic.
+//!
+//! Usage:
+//!   cargo run --release -p `mcg_qr_comm` --bin bench -- --packages 4 --size-bytes 1024 --runs 10
+
+use mcg_qr_comm::data_structures::{Factor, Package, SparseFactor, WideFactor};
+use mcg_qr_comm::network_coding::{Epoch, GaloisField2p4};
+use mcg_qr_comm::{
+    AP_LENGTH_INDEX_SIZE_BYTES, CODING_FACTORS_PER_FRAME, CODING_FACTORS_PER_PARTICIPANT_PER_FRAME,
+    FRAGMENT_SIZE_BYTES, FRAGMENTS_PER_EPOCH, FRAGMENTS_PER_PARTICIPANT_PER_EPOCH,
+    MAX_PARTICIPANTS,
+};
+use std::hint::black_box;
+use std::mem::size_of;
+use std::process::exit;
+use std::time::{Duration, Instant};
+
+/// Largest package whose fragments still fit into one participant's share of a frame
+const MAX_PACKAGE_SIZE_BYTES: usize =
+    CODING_FACTORS_PER_PARTICIPANT_PER_FRAME * FRAGMENT_SIZE_BYTES - AP_LENGTH_INDEX_SIZE_BYTES;
+
+const USAGE: &str = "usage: bench [--packages N] [--size-bytes B] [--runs R]";
+
+struct Args {
+    packages: usize,
+    size_bytes: usize,
+    runs: usize,
+}
+
+impl Args {
+    fn parse() -> Result<Self, String> {
+        let mut args = Self {
+            packages: 4,
+            size_bytes: 1024,
+            runs: 10,
+        };
+        let mut it = std::env::args().skip(1);
+        while let Some(flag) = it.next() {
+            let target = match flag.as_str() {
+                "--packages" => &mut args.packages,
+                "--size-bytes" => &mut args.size_bytes,
+                "--runs" => &mut args.runs,
+                "-h" | "--help" => return Err(USAGE.to_string()),
+                other => return Err(format!("unknown argument '{other}'\n{USAGE}")),
+            };
+            let value = it.next().ok_or_else(|| format!("{flag} needs a value"))?;
+            *target = value
+                .parse()
+                .map_err(|_| format!("{flag} expects a number, got '{value}'"))?;
+        }
+
+        // One participant is left over for the receiving epoch
+        if !(1..MAX_PARTICIPANTS).contains(&args.packages) {
+            return Err(format!(
+                "--packages must be between 1 and {}",
+                MAX_PARTICIPANTS - 1
+            ));
+        }
+        if !(1..=MAX_PACKAGE_SIZE_BYTES).contains(&args.size_bytes) {
+            return Err(format!(
+                "--size-bytes must be between 1 and {MAX_PACKAGE_SIZE_BYTES}"
+            ));
+        }
+        if fragments_per_package(args.size_bytes) * args.packages > CODING_FACTORS_PER_FRAME {
+            return Err(format!(
+                "{} packages of {} bytes do not fit into one frame",
+                args.packages, args.size_bytes
+            ));
+        }
+        if args.runs == 0 {
+            return Err("--runs must be at least 1".to_string());
+        }
+        Ok(args)
+    }
+}
+
+const fn fragments_per_package(size_bytes: usize) -> usize {
+    (size_bytes + AP_LENGTH_INDEX_SIZE_BYTES).div_ceil(FRAGMENT_SIZE_BYTES)
+}
+
+/// Measurements of a single run
+struct Run {
+    /// Fragments the receiver has to solve for
+    width: usize,
+    frames: usize,
+    encode: Duration,
+    decode: Duration,
+}
+
+// Counts and sizes in the report are far below the 52 bits of an `f64` mantissa
+#[allow(clippy::cast_precision_loss)]
+impl Run {
+    fn ratio(&self) -> f64 {
+        self.frames as f64 / self.width as f64
+    }
+
+    fn throughput_kbs(&self, payload_bytes: usize) -> f64 {
+        payload_bytes as f64 / 1024.0 / (self.encode + self.decode).as_secs_f64()
+    }
+}
+
+// Participant ids are below `MAX_PARTICIPANTS`, see `Args::parse`
+#[allow(clippy::cast_possible_truncation)]
+fn run(args: &Args) -> Result<Run, String> {
+    let packages: Vec<Package> = (0..args.packages)
+        .map(|_| {
+            let data: Vec<u8> = (0..args.size_bytes).map(|_| rand::random()).collect();
+            Package::new(&data)
+        })
+        .collect();
+
+    let mut encode = Duration::ZERO;
+    let start = Instant::now();
+    let mut e_out = Epoch::default();
+    for (participant, package) in packages.iter().enumerate() {
+        e_out.header.participant = participant as u8;
+        e_out.write(package.clone());
+    }
+    encode += start.elapsed();
+
+    let mut e_in = Epoch::default();
+    e_in.header.participant = args.packages as u8;
+    let width = fragments_per_package(args.size_bytes) * args.packages;
+    let max_frames = 10 * width + 100;
+
+    let mut decode = Duration::ZERO;
+    let mut frames = 0;
+    while (0..args.packages).any(|p| e_in.decoded_fragments[p].len() < width / args.packages) {
+        if frames == max_frames {
+            return Err(format!("not decoded after {max_frames} frames"));
+        }
+        let start = Instant::now();
+        let frame = e_out
+            .pop_recent_frame()
+            .ok_or("packages do not fit into a frame")?;
+        encode += start.elapsed();
+
+        let start = Instant::now();
+        e_in.push_frame(frame);
+        decode += start.elapsed();
+        frames += 1;
+    }
+
+    for (participant, package) in packages.iter().enumerate() {
+        if e_in.get_package(participant, 0).as_ref() != Some(package) {
+            return Err(format!(
+                "package of participant {participant} decoded wrongly"
+            ));
+        }
+    }
+    Ok(Run {
+        width,
+        frames,
+        encode,
+        decode,
+    })
+}
+
+/// Cost of one equation's factors in a given representation
+struct FactorCost {
+    name: &'static str,
+    bytes_per_equation: usize,
+    row_op: Duration,
+}
+
+/// Random coding factors over the fragments of all packages, like a frame carries
+fn random_equation(args: &Args) -> SparseFactor {
+    let per_package = fragments_per_package(args.size_bytes);
+    let inner = (0..args.packages)
+        .flat_map(|p| {
+            let start = p * FRAGMENTS_PER_PARTICIPANT_PER_EPOCH;
+            // `check_const_parameters` keeps fragment indices within `u32`
+            #[allow(clippy::cast_possible_truncation)]
+            (start..start + per_package).map(|idx| (idx as u32, rand::random()))
+        })
+        .collect();
+    SparseFactor { inner }
+}
+
+/// Time `ops` eliminations of one equation by another, the step the matrix
+/// sweeps repeat for every pivot.
+fn time_row_ops(pivot: &Factor, target: Factor, ops: usize) -> Duration {
+    let mut target = target;
+    let start = Instant::now();
+    for _ in 0..ops {
+        let factor: GaloisField2p4 = rand::random();
+        target -= pivot.clone() * factor;
+    }
+    start.elapsed()
+}
+
+fn factor_costs(args: &Args, ops: usize) -> [FactorCost; 2] {
+    let pivot = random_equation(args);
+    let target = random_equation(args);
+    let sparse_bytes =
+        size_of::<SparseFactor>() + pivot.inner.len() * size_of::<(u32, GaloisField2p4)>();
+    let dense = FactorCost {
+        name: "dense",
+        bytes_per_equation: size_of::<WideFactor>()
+            + FRAGMENTS_PER_EPOCH * size_of::<GaloisField2p4>(),
+        row_op: time_row_ops(
+            &Factor::Wide(pivot.clone().into()),
+            Factor::Wide(target.clone().into()),
+            ops,
+        ),
+    };
+    let sparse = FactorCost {
+        name: "sparse",
+        bytes_per_equation: sparse_bytes,
+        row_op: time_row_ops(&Factor::Sparse(pivot), Factor::Sparse(target), ops),
+    };
+    [dense, sparse]
+}
+
+/// Products per GF(16) multiplication timing
+const GF_PRODUCTS: usize = 1 << 20;
+
+type GfMul = fn(GaloisField2p4, GaloisField2p4) -> GaloisField2p4;
+
+/// Time `GF_PRODUCTS` multiplications with `mul`, chained so none can be skipped.
+fn time_gf_mul(mul: GfMul) -> Duration {
+    let factors: Vec<GaloisField2p4> = (0..256).map(|_| rand::random()).collect();
+    let mut acc = GaloisField2p4::ONE;
+    let start = Instant::now();
+    for i in 0..GF_PRODUCTS {
+        acc = black_box(mul(acc, factors[i % factors.len()]));
+    }
+    let elapsed = start.elapsed();
+    black_box(acc);
+    elapsed
+}
+
+// Counts and sizes in the report are far below the 52 bits of an `f64` mantissa
+#[allow(clippy::cast_precision_loss)]
+fn main() {
+    let args = match Args::parse() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}");
+            exit(2);
+        }
+    };
+    let payload_bytes = args.packages * args.size_bytes;
+
+    let mut runs = Vec::with_capacity(args.runs);
+    for _ in 0..args.runs {
+        match run(&args) {
+            Ok(r) => runs.push(r),
+            Err(e) => {
+                eprintln!("benchmark failed: {e}");
+                exit(1);
+            }
+        }
+    }
+
+    println!(
+        "{} packages × {} bytes, {} runs\n",
+        args.packages, args.size_bytes, args.runs
+    );
+    println!("| run | width | frames | frames/width | encode (ms) | decode (ms) | KB/s |");
+    println!("|----:|------:|-------:|-------------:|------------:|------------:|-----:|");
+    for (idx, r) in runs.iter().enumerate() {
+        println!(
+            "| {} | {} | {} | {:.3} | {:.3} | {:.3} | {:.1} |",
+            idx + 1,
+            r.width,
+            r.frames,
+            r.ratio(),
+            r.encode.as_secs_f64() * 1000.0,
+            r.decode.as_secs_f64() * 1000.0,
+            r.throughput_kbs(payload_bytes),
+        );
+    }
+
+    let n = runs.len() as f64;
+    let mean = |f: &dyn Fn(&Run) -> f64| runs.iter().map(f).sum::<f64>() / n;
+    println!(
+        "| **mean** | {} | {:.1} | {:.3} | {:.3} | {:.3} | {:.1} |",
+        runs[0].width,
+        mean(&|r| r.frames as f64),
+        mean(&|r| r.ratio()),
+        mean(&|r| r.encode.as_secs_f64() * 1000.0),
+        mean(&|r| r.decode.as_secs_f64() * 1000.0),
+        mean(&|r| r.throughput_kbs(payload_bytes)),
+    );
+
+    let width = runs[0].width;
+    let ops = width * args.runs;
+    println!("\nFactors of {width} equations, {ops} row operations\n");
+    println!("| factors | bytes/equation | bytes for {width} equations | row op (µs) |");
+    println!("|:--------|---------------:|------------------------:|------------:|");
+    for cost in factor_costs(&args, ops) {
+        println!(
+            "| {} | {} | {} | {:.3} |",
+            cost.name,
+            cost.bytes_per_equation,
+            cost.bytes_per_equation * width,
+            cost.row_op.as_secs_f64() * 1e6 / ops as f64,
+        );
+    }
+
+    println!("\nGF(16) multiplication, {GF_PRODUCTS} products\n");
+    println!("| method | ns/product |");
+    println!("|:-------|-----------:|");
+    let methods: [(&str, GfMul); 2] = [
+        ("table lookup", |a, b| a * b),
+        ("polynomial", GaloisField2p4::mul_polynomial),
+    ];
+    for (name, mul) in methods {
+        let elapsed = time_gf_mul(mul);
+        println!(
+            "| {} | {:.3} |",
+            name,
+            elapsed.as_secs_f64() * 1e9 / GF_PRODUCTS as f64
+        );
+    }
+}
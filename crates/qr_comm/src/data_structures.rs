@@ -3,6 +3,7 @@ pub use crate::data_structures::fragment::Fragment;
 
 mod frame;
 pub use crate::data_structures::frame::Frame;
+pub(crate) use crate::data_structures::frame::encode_qr_bytes;
 
 mod header;
 pub use crate::data_structures::header::FrameHeader;
@@ -3,32 +3,40 @@ use crate::{AP_LENGTH_INDEX_SIZE_BYTES, AP_MAX_SIZE_BYTES, FRAGMENT_SIZE_BYTES};
 use std::cmp::min;
 use std::io::Read;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Package {
     pub size: u32,
     pub data: Vec<u8>,
 }
 
 impl Package {
+    /// # Panics
+    ///
+    /// If the package is larger than `AP_MAX_SIZE_BYTES`.
+    #[must_use]
     pub fn new(package: &[u8]) -> Self {
-        if package.len() > AP_MAX_SIZE_BYTES {
-            panic!("AP is too large");
-        }
+        assert!(package.len() <= AP_MAX_SIZE_BYTES, "AP is too large");
         let mut data = package.to_vec();
         data.shrink_to_fit();
+        // Checked against `AP_MAX_SIZE_BYTES` above
+        #[allow(clippy::cast_possible_truncation)]
         let size = data.len() as u32;
-        Package { size, data }
+        Self { size, data }
     }
+    /// # Panics
+    ///
+    /// If reading fails or the package is larger than `AP_MAX_SIZE_BYTES`.
     pub fn from_read(mut package: impl Read) -> Self {
         let mut buf = Vec::new();
         package
             .read_to_end(&mut buf)
             .expect("Unable to read package!");
-        Package::new(&buf)
+        Self::new(&buf)
     }
+    #[must_use]
     pub fn into_fragments(self) -> Vec<Fragment> {
         debug_assert!(AP_LENGTH_INDEX_SIZE_BYTES <= size_of::<u32>());
-        let Package { size, mut data } = self;
+        let Self { size, mut data } = self;
         let mut fragments = Vec::new();
         let mut first_fragment = [0u8; FRAGMENT_SIZE_BYTES];
         first_fragment[..size_of::<u32>()].copy_from_slice(&size.to_le_bytes());
@@ -46,6 +54,11 @@ impl Package {
         }
         fragments
     }
+    /// # Panics
+    ///
+    /// If there are no fragments or the first one announces a package larger
+    /// than `AP_MAX_SIZE_BYTES`.
+    #[must_use]
     pub fn from_fragments(fragments: &[Fragment]) -> Self {
         debug_assert!(AP_LENGTH_INDEX_SIZE_BYTES <= size_of::<u32>());
         assert!(!fragments.is_empty());
@@ -54,7 +67,7 @@ impl Package {
             .copy_from_slice(&fragments[0][..AP_LENGTH_INDEX_SIZE_BYTES]);
         let size = u32::from_le_bytes(size);
         assert!(
-            size <= AP_MAX_SIZE_BYTES as u32,
+            size as usize <= AP_MAX_SIZE_BYTES,
             "AP size greater than maximum"
         );
         let mut data = Vec::with_capacity(size as usize);
@@ -71,11 +84,13 @@ impl Package {
             data.extend_from_slice(&fragment[..end]);
             fragment_idx += 1;
         }
-        Package::new(&data)
+        Self::new(&data)
     }
 }
 
 #[cfg(test)]
+// Test data wraps around the byte values on purpose
+#[allow(clippy::cast_possible_truncation)]
 mod tests {
     use crate::data_structures::Package;
     use crate::{AP_LENGTH_INDEX_SIZE_BYTES, FRAGMENT_SIZE_BYTES};
@@ -104,7 +119,7 @@ mod tests {
     }
     #[test]
     fn into_fragments_test_1() {
-        const DATA_LEN: usize = 1021;
+        const DATA_LEN: usize = FRAGMENT_SIZE_BYTES - AP_LENGTH_INDEX_SIZE_BYTES;
         let data: [u8; DATA_LEN] = from_fn(|x| x as u8);
         let package = Package::new(&data);
         assert_eq!(package.size, DATA_LEN as u32);
@@ -114,14 +129,10 @@ mod tests {
             fragments[0][AP_LENGTH_INDEX_SIZE_BYTES..DATA_LEN + AP_LENGTH_INDEX_SIZE_BYTES],
             data
         );
-        // assert_eq!(
-        //     fragments[0][DATA_LEN + AP_LENGTH_INDEX_SIZE_BYTES..],
-        //     [0; FRAGMENT_SIZE_BYTES - DATA_LEN - AP_LENGTH_INDEX_SIZE_BYTES]
-        // );
     }
     #[test]
     fn into_fragments_test_2() {
-        const DATA_LEN: usize = 1024;
+        const DATA_LEN: usize = FRAGMENT_SIZE_BYTES;
         let data: [u8; DATA_LEN] = from_fn(|x| x as u8);
         let package = Package::new(&data);
         assert_eq!(package.size, DATA_LEN as u32);
@@ -147,8 +158,11 @@ mod tests {
         let package = Package::new(&data);
         assert_eq!(package.size, DATA_LEN as u32);
         let fragments = package.into_fragments();
-        assert_eq!(fragments.len(), 9);
-        let new = Package::from_fragments(fragments.clone().as_ref()).into_fragments();
+        assert_eq!(
+            fragments.len(),
+            (DATA_LEN + AP_LENGTH_INDEX_SIZE_BYTES).div_ceil(FRAGMENT_SIZE_BYTES)
+        );
+        let new = Package::from_fragments(fragments.as_ref()).into_fragments();
         for (idx, frag) in fragments.iter().enumerate() {
             assert_eq!(frag, &new[idx]);
         }
@@ -28,7 +28,7 @@ impl From<[u8; FRAME_SIZE_BYTES]> for Frame {
         let header = header.into();
         let coding_factors = coding_factors.into();
         let fragment = fragment.into();
-        Frame {
+        Self {
             header,
             factors: coding_factors,
             fragment,
@@ -42,10 +42,16 @@ impl From<[u8; HEADER_SIZE_BYTES]> for FrameHeader {
         let participant = value[0];
         let is_overflowing = value[1] != 0;
         let epoch = value[2];
-        FrameHeader {
+        let seq = u32::from_le_bytes([value[3], value[4], value[5], value[6]]);
+        let epoch_id = u16::from_le_bytes([value[7], value[8]]);
+        let participant_count = value[9];
+        Self {
             participant,
             is_overflowing,
             epoch,
+            seq,
+            epoch_id,
+            participant_count,
         }
     }
 }
@@ -73,13 +79,13 @@ impl From<[u8; NETWORK_CODING_SIZE_BYTES]> for FrameFactor {
             })
             .collect();
         coding_factors[..factors.len()].copy_from_slice(factors.as_slice());
-        FrameFactor::new(coding_factors, width, offsets)
+        Self::new(coding_factors, width, offsets)
             .expect("Seems like the provided values are illegal!")
     }
 }
 impl From<[u8; FRAGMENT_SIZE_BYTES]> for Fragment {
     fn from(value: [u8; FRAGMENT_SIZE_BYTES]) -> Self {
         let inner = value.into();
-        Fragment { inner }
+        Self { inner }
     }
 }
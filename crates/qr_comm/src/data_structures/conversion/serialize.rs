@@ -45,10 +45,16 @@ impl From<FrameHeader> for [u8; HEADER_SIZE_BYTES] {
             participant: sender_id,
             is_overflowing,
             epoch,
+            seq,
+            epoch_id,
+            participant_count,
         } = val;
         result[0] = sender_id;
-        result[1] = is_overflowing as u8;
+        result[1] = u8::from(is_overflowing);
         result[2] = epoch;
+        result[3..7].copy_from_slice(&seq.to_le_bytes());
+        result[7..9].copy_from_slice(&epoch_id.to_le_bytes());
+        result[9] = participant_count;
         result
     }
 }
@@ -1,4 +1,4 @@
-use crate::network_coding::GaloisField2p4;
+use crate::network_coding::{GaloisField2p4, xor_assign_slice};
 use crate::{
     CODING_FACTORS_PER_FRAME, FRAGMENTS_PER_EPOCH, FRAGMENTS_PER_PARTICIPANT_PER_EPOCH,
     MAX_PARTICIPANTS,
@@ -8,7 +8,7 @@ use std::ops::{Deref, DerefMut};
 
 mod ops;
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct FrameFactor {
     pub widths: [u8; MAX_PARTICIPANTS],
     pub offsets: [u16; MAX_PARTICIPANTS],
@@ -29,21 +29,25 @@ impl Default for FrameFactor {
 }
 
 impl FrameFactor {
+    /// # Errors
+    ///
+    /// If the widths add up to more than 512 factors.
     pub fn new(
         factors: [GaloisField2p4; CODING_FACTORS_PER_FRAME],
         widths: [u8; MAX_PARTICIPANTS],
         offsets: [u16; MAX_PARTICIPANTS],
     ) -> Result<Self, &'static str> {
-        if widths.iter().fold(0u16, |acc, w| acc + 2 * (*w as u16)) > 512 {
+        if widths.iter().fold(0u16, |acc, w| acc + 2 * u16::from(*w)) > 512 {
             Err("Illegal widths data. You can't have more than 512 factors!")
         } else {
-            Ok(FrameFactor {
+            Ok(Self {
                 widths,
                 offsets,
                 factors,
             })
         }
     }
+    #[must_use]
     pub fn get_factor_at(&self, idx: usize) -> Option<GaloisField2p4> {
         let participant_idx = idx / FRAGMENTS_PER_PARTICIPANT_PER_EPOCH;
         let factor_idx = idx % FRAGMENTS_PER_PARTICIPANT_PER_EPOCH;
@@ -62,38 +66,43 @@ impl FrameFactor {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Factor {
     Sparse(SparseFactor),
     Wide(WideFactor),
 }
 
 impl Factor {
+    #[must_use]
     pub fn get(&self, idx: usize) -> Option<GaloisField2p4> {
         match self {
-            Factor::Sparse(this) => {
-                if let Ok(idx) = this.inner.binary_search_by_key(&idx, |(idx_f, _)| *idx_f) {
-                    Some(this.inner[idx].1)
-                } else {
-                    None
-                }
-            }
-            Factor::Wide(this) => Some(this.inner[idx]),
+            Self::Sparse(this) => this
+                .inner
+                .binary_search_by_key(&idx, |(idx_f, _)| *idx_f as usize)
+                .ok()
+                .map(|idx| this.inner[idx].1),
+            Self::Wide(this) => Some(this.inner[idx]),
         }
     }
+    #[must_use]
     pub fn utilized_fragments(&self) -> Box<[bool; FRAGMENTS_PER_EPOCH]> {
         match self {
-            Factor::Sparse(this) => this.utilized_fragments(),
-            Factor::Wide(this) => this.utilized_fragments(),
+            Self::Sparse(this) => this.utilized_fragments(),
+            Self::Wide(this) => this.utilized_fragments(),
         }
     }
+    /// # Panics
+    ///
+    /// If the factor is all zeros.
+    #[must_use]
     pub fn first_factor(&self) -> (usize, GaloisField2p4) {
         match self {
-            Factor::Sparse(lhs) => *lhs
+            Self::Sparse(lhs) => lhs
                 .inner
                 .first()
+                .map(|(idx, f)| (*idx as usize, *f))
                 .expect("It's impossible to have equations without factors"),
-            Factor::Wide(rhs) => rhs
+            Self::Wide(rhs) => rhs
                 .inner
                 .iter()
                 .enumerate()
@@ -107,25 +116,36 @@ impl Factor {
                 .expect("It's impossible to have equations without factors"),
         }
     }
+    #[must_use]
     pub fn is_plain(&self) -> bool {
         match self {
-            Factor::Sparse(this) => this.inner.len() == 1,
-            Factor::Wide(this) => this.is_plain(),
+            Self::Sparse(this) => this.inner.len() == 1,
+            Self::Wide(this) => this.is_plain(),
         }
     }
+    #[must_use]
     pub fn is_zero(&self) -> bool {
         match self {
-            Factor::Sparse(this) => this.inner.is_empty(),
-            Factor::Wide(_) => {
-                todo!()
-            }
+            Self::Sparse(this) => this.inner.is_empty(),
+            Self::Wide(this) => this.inner.iter().all(|f| *f == GaloisField2p4::ZERO),
         }
     }
-    pub fn is_wide(&self) -> bool {
-        matches!(self, Factor::Wide(_))
+    /// `self -= rhs * factor`. Rows of the same kind are combined without
+    /// cloning `rhs`.
+    pub fn sub_assign_scaled(&mut self, rhs: &Self, factor: GaloisField2p4) {
+        match (&mut *self, rhs) {
+            (Self::Wide(lhs), Self::Wide(rhs)) => xor_assign_slice(lhs, &(&rhs[..] * factor)),
+            (Self::Sparse(lhs), Self::Sparse(rhs)) => lhs.sub_assign_scaled(rhs, factor),
+            _ => *self -= rhs.clone() * factor,
+        }
+    }
+    #[must_use]
+    pub const fn is_wide(&self) -> bool {
+        matches!(self, Self::Wide(_))
     }
-    pub fn is_sparse(&self) -> bool {
-        matches!(self, Factor::Sparse(_))
+    #[must_use]
+    pub const fn is_sparse(&self) -> bool {
+        matches!(self, Self::Sparse(_))
     }
     pub(crate) fn print_matrix_row(&self, idx: impl IntoIterator<Item = usize>) -> String {
         let mut matrix = Vec::new();
@@ -139,27 +159,31 @@ impl Factor {
             }
         }
         writeln!(&mut matrix).unwrap();
-        String::try_from(matrix.to_vec()).unwrap()
+        String::try_from(matrix.clone()).unwrap()
     }
 }
 
 impl From<SparseFactor> for Factor {
     fn from(value: SparseFactor) -> Self {
-        Factor::Sparse(value)
+        Self::Sparse(value)
     }
 }
 impl From<WideFactor> for Factor {
     fn from(value: WideFactor) -> Self {
-        Factor::Wide(value)
+        Self::Wide(value)
     }
 }
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(PartialEq, Eq, Clone, Debug)]
 pub struct WideFactor {
     pub inner: Box<[GaloisField2p4; FRAGMENTS_PER_EPOCH]>,
 }
 
 impl WideFactor {
+    // Offsets and widths of the participants' factors fit the 16 and 8 bits
+    // the frame header has for them
+    #[allow(clippy::cast_possible_truncation)]
+    #[must_use]
     pub fn get_width_and_offsets(&self) -> ([u8; MAX_PARTICIPANTS], [u16; MAX_PARTICIPANTS]) {
         let mut width = [0; MAX_PARTICIPANTS];
         let mut offsets = [0; MAX_PARTICIPANTS];
@@ -189,10 +213,21 @@ impl WideFactor {
         }
         (width, offsets)
     }
+    // The conversion cannot fail, there is one entry per fragment
+    #[allow(clippy::missing_panics_doc)]
+    #[must_use]
     pub fn utilized_fragments(&self) -> Box<[bool; FRAGMENTS_PER_EPOCH]> {
         // TODO change into sparse layout
-        self.inner.clone().map(|f| f != GaloisField2p4::ZERO).into()
+        let utilized: Box<[bool]> = self
+            .inner
+            .iter()
+            .map(|f| *f != GaloisField2p4::ZERO)
+            .collect();
+        utilized
+            .try_into()
+            .expect("a wide factor has one entry per fragment")
     }
+    #[must_use]
     pub fn is_plain(&self) -> bool {
         self.inner
             .iter()
@@ -207,7 +242,7 @@ impl Default for WideFactor {
         let inner = vec![GaloisField2p4::ZERO; FRAGMENTS_PER_EPOCH]
             .try_into()
             .expect("Error allocating memory!");
-        WideFactor { inner }
+        Self { inner }
     }
 }
 impl Deref for WideFactor {
@@ -224,7 +259,7 @@ impl DerefMut for WideFactor {
 }
 impl From<FrameFactor> for WideFactor {
     fn from(value: FrameFactor) -> Self {
-        let mut wide = WideFactor::default();
+        let mut wide = Self::default();
         let mut start: usize = 0;
         let mut stop: usize = 0;
         for (participant, (width, offset)) in
@@ -252,46 +287,72 @@ impl From<FrameFactor> for SparseFactor {
             let width = (*width_u8 as usize) * 2;
             let idx = participant * FRAGMENTS_PER_PARTICIPANT_PER_EPOCH + (*offset as usize);
             for w in 0..width {
-                let factor = factors.next().unwrap();
+                // The widths of a corrupted frame may add up to more factors than it has
+                let Some(factor) = factors.next() else {
+                    return Self { inner };
+                };
                 if *factor != GaloisField2p4::ZERO {
-                    inner.push((idx + w, *factor));
+                    // `check_const_parameters` keeps fragment indices within `u32`
+                    #[allow(clippy::cast_possible_truncation)]
+                    let idx = (idx + w) as u32;
+                    inner.push((idx, *factor));
                 }
             }
         }
-        SparseFactor { inner }
+        Self { inner }
     }
 }
 impl From<WideFactor> for SparseFactor {
     fn from(value: WideFactor) -> Self {
-        let sparse: Vec<(usize, GaloisField2p4)> = value
+        let sparse: Vec<(u32, GaloisField2p4)> = value
             .inner
             .iter()
             .enumerate()
             .filter_map(|(idx, f)| {
-                if *f != GaloisField2p4::ZERO {
-                    Some((idx, *f))
-                } else {
+                if *f == GaloisField2p4::ZERO {
                     None
+                } else {
+                    // `check_const_parameters` keeps fragment indices within `u32`
+                    #[allow(clippy::cast_possible_truncation)]
+                    let idx = idx as u32;
+                    Some((idx, *f))
                 }
             })
             .collect();
-        SparseFactor { inner: sparse }
+        Self { inner: sparse }
+    }
+}
+impl From<SparseFactor> for WideFactor {
+    fn from(value: SparseFactor) -> Self {
+        let mut wide = Self::default();
+        for (idx, f) in value.inner {
+            wide.inner[idx as usize] = f;
+        }
+        wide
     }
 }
 
-#[allow(dead_code)]
-#[derive(PartialEq, Clone, Debug)]
+/// Only the non-zero factors of an equation, sorted by fragment index.
+///
+/// A coded frame touches at most `CODING_FACTORS_PER_FRAME` of the
+/// `FRAGMENTS_PER_EPOCH` fragments, so rows stay a few hundred bytes instead of
+/// the megabyte a `WideFactor` takes. Indices are `u32`, which halves the size
+/// of each entry compared to `usize`.
+#[derive(PartialEq, Eq, Clone, Debug)]
 pub struct SparseFactor {
-    pub inner: Vec<(usize, GaloisField2p4)>,
+    pub inner: Vec<(u32, GaloisField2p4)>,
 }
 
 impl SparseFactor {
+    // The conversion cannot fail, there is one entry per fragment
+    #[allow(clippy::missing_panics_doc)]
+    #[must_use]
     pub fn utilized_fragments(&self) -> Box<[bool; FRAGMENTS_PER_EPOCH]> {
         let mut util: Box<[bool; FRAGMENTS_PER_EPOCH]> = vec![false; FRAGMENTS_PER_EPOCH]
             .try_into()
             .expect("Error allocating memory!");
-        for (idx, f) in self.inner.iter() {
-            util[*idx] = *f != GaloisField2p4::ZERO;
+        for (idx, f) in &self.inner {
+            util[*idx as usize] = *f != GaloisField2p4::ZERO;
         }
         util
     }
@@ -300,11 +361,13 @@ impl SparseFactor {
 impl Default for SparseFactor {
     fn default() -> Self {
         let inner = Vec::new();
-        SparseFactor { inner }
+        Self { inner }
     }
 }
 
 #[cfg(test)]
+// Test factors wrap around the field on purpose
+#[allow(clippy::cast_possible_truncation)]
 mod tests {
     use crate::data_structures::FrameFactor;
     use crate::network_coding::GaloisField2p4;
@@ -329,9 +392,7 @@ mod tests {
                 let expected = GaloisField2p4::from((factor + participant) as u8 & 0xF);
                 assert!(
                     f.is_some(),
-                    "Missing for participant {} at factor {}",
-                    participant,
-                    factor
+                    "Missing for participant {participant} at factor {factor}"
                 );
                 assert_eq!(f.unwrap(), expected);
             }
@@ -349,9 +410,7 @@ mod tests {
                 let expected = GaloisField2p4::from((factor + participant - 1) as u8 & 0xF);
                 assert!(
                     f.is_some(),
-                    "Missing for participant {} at factor {}",
-                    participant,
-                    factor
+                    "Missing for participant {participant} at factor {factor}"
                 );
                 assert_eq!(f.unwrap(), expected);
             }
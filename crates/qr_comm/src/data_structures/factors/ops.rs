@@ -1,10 +1,11 @@
 use crate::data_structures::{Factor, SparseFactor, WideFactor};
 use crate::network_coding::GaloisField2p4;
+use std::cmp::Ordering;
 use std::mem;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
 
 // std::ops for WideFactor
-impl SubAssign<WideFactor> for WideFactor {
+impl SubAssign<Self> for WideFactor {
     fn sub_assign(&mut self, rhs: Self) {
         self.inner
             .iter_mut()
@@ -14,8 +15,8 @@ impl SubAssign<WideFactor> for WideFactor {
             });
     }
 }
-impl Sub<WideFactor> for WideFactor {
-    type Output = WideFactor;
+impl Sub<Self> for WideFactor {
+    type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
         let mut lhs = self;
@@ -23,7 +24,7 @@ impl Sub<WideFactor> for WideFactor {
         lhs
     }
 }
-impl AddAssign<WideFactor> for WideFactor {
+impl AddAssign<Self> for WideFactor {
     fn add_assign(&mut self, rhs: Self) {
         self.inner
             .iter_mut()
@@ -33,8 +34,8 @@ impl AddAssign<WideFactor> for WideFactor {
             });
     }
 }
-impl Add<WideFactor> for WideFactor {
-    type Output = WideFactor;
+impl Add<Self> for WideFactor {
+    type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
         let mut lhs = self;
@@ -54,7 +55,7 @@ impl MulAssign<u8> for WideFactor {
     }
 }
 impl Mul<GaloisField2p4> for WideFactor {
-    type Output = WideFactor;
+    type Output = Self;
 
     fn mul(self, rhs: GaloisField2p4) -> Self::Output {
         let mut lhs = self;
@@ -63,7 +64,7 @@ impl Mul<GaloisField2p4> for WideFactor {
     }
 }
 impl Mul<u8> for WideFactor {
-    type Output = WideFactor;
+    type Output = Self;
 
     fn mul(self, rhs: u8) -> Self::Output {
         let mut lhs = self;
@@ -83,7 +84,7 @@ impl DivAssign<u8> for WideFactor {
     }
 }
 impl Div<GaloisField2p4> for WideFactor {
-    type Output = WideFactor;
+    type Output = Self;
 
     fn div(self, rhs: GaloisField2p4) -> Self::Output {
         let mut lhs = self;
@@ -92,7 +93,7 @@ impl Div<GaloisField2p4> for WideFactor {
     }
 }
 impl Div<u8> for WideFactor {
-    type Output = WideFactor;
+    type Output = Self;
 
     fn div(self, rhs: u8) -> Self::Output {
         let mut lhs = self;
@@ -102,40 +103,57 @@ impl Div<u8> for WideFactor {
 }
 
 // std::ops for SparseFactor
-impl SubAssign<SparseFactor> for SparseFactor {
+impl SubAssign<Self> for SparseFactor {
     fn sub_assign(&mut self, rhs: Self) {
-        let mut idx_lhs = 0;
-        let mut idx_rhs = 0;
-
-        let len_rhs = rhs.inner.len();
-        while idx_lhs < self.inner.len() && idx_rhs < len_rhs {
-            let (factor_idx_lhs, factor_lhs) = self.inner[idx_lhs];
-            let (factor_idx_rhs, factor_rhs) = rhs.inner[idx_rhs];
-
-            if factor_idx_lhs == factor_idx_rhs {
-                if factor_lhs == factor_rhs {
-                    // Remove union element
-                    self.inner.remove(idx_lhs);
-                } else {
-                    self.inner[idx_lhs].1 -= factor_rhs;
-                    idx_lhs += 1;
+        self.sub_assign_scaled(&rhs, GaloisField2p4::ONE);
+    }
+}
+impl SparseFactor {
+    /// `self -= rhs * factor`, scaling `rhs` while merging instead of cloning it.
+    // Only unwraps the entries `peek` just returned
+    #[allow(clippy::missing_panics_doc)]
+    pub fn sub_assign_scaled(&mut self, rhs: &Self, factor: GaloisField2p4) {
+        if factor == GaloisField2p4::ZERO {
+            return;
+        }
+        // Merge both sorted rows in one pass instead of inserting into the middle
+        let lhs = mem::take(&mut self.inner);
+        let mut merged = Vec::with_capacity(lhs.len() + rhs.inner.len());
+        let mut lhs = lhs.into_iter().peekable();
+        let mut rhs = rhs
+            .inner
+            .iter()
+            .map(|&(idx, f)| (idx, f * factor))
+            .peekable();
+        loop {
+            let next = match (lhs.peek(), rhs.peek()) {
+                (Some(&(idx_lhs, factor_lhs)), Some(&(idx_rhs, factor_rhs))) => {
+                    match idx_lhs.cmp(&idx_rhs) {
+                        Ordering::Equal => {
+                            lhs.next();
+                            rhs.next();
+                            // Cancelled factors are dropped to keep the row sparse
+                            let factor = factor_lhs - factor_rhs;
+                            if factor == GaloisField2p4::ZERO {
+                                continue;
+                            }
+                            (idx_lhs, factor)
+                        }
+                        Ordering::Less => lhs.next().unwrap(),
+                        Ordering::Greater => rhs.next().unwrap(),
+                    }
                 }
-                idx_rhs += 1;
-            } else if factor_idx_rhs < factor_idx_lhs {
-                self.inner.insert(idx_lhs, (factor_idx_rhs, factor_rhs));
-                idx_rhs += 1;
-                idx_lhs += 1;
-            } else {
-                idx_lhs += 1;
-            }
+                (Some(_), None) => lhs.next().unwrap(),
+                (None, Some(_)) => rhs.next().unwrap(),
+                (None, None) => break,
+            };
+            merged.push(next);
         }
-
-        // Add remaining elements
-        self.inner.extend(&rhs.inner[idx_rhs..]);
+        self.inner = merged;
     }
 }
-impl Sub<SparseFactor> for SparseFactor {
-    type Output = SparseFactor;
+impl Sub<Self> for SparseFactor {
+    type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
         let mut lhs = self;
@@ -144,13 +162,13 @@ impl Sub<SparseFactor> for SparseFactor {
     }
 }
 
-impl AddAssign<SparseFactor> for SparseFactor {
+impl AddAssign<Self> for SparseFactor {
     fn add_assign(&mut self, rhs: Self) {
         self.sub_assign(rhs);
     }
 }
-impl Add<SparseFactor> for SparseFactor {
-    type Output = SparseFactor;
+impl Add<Self> for SparseFactor {
+    type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
         let mut lhs = self;
@@ -174,7 +192,7 @@ impl MulAssign<u8> for SparseFactor {
     }
 }
 impl Mul<GaloisField2p4> for SparseFactor {
-    type Output = SparseFactor;
+    type Output = Self;
 
     fn mul(self, rhs: GaloisField2p4) -> Self::Output {
         let mut lhs = self;
@@ -183,7 +201,7 @@ impl Mul<GaloisField2p4> for SparseFactor {
     }
 }
 impl Mul<u8> for SparseFactor {
-    type Output = SparseFactor;
+    type Output = Self;
 
     fn mul(self, rhs: u8) -> Self::Output {
         let mut lhs = self;
@@ -207,7 +225,7 @@ impl DivAssign<u8> for SparseFactor {
     }
 }
 impl Div<GaloisField2p4> for SparseFactor {
-    type Output = SparseFactor;
+    type Output = Self;
 
     fn div(self, rhs: GaloisField2p4) -> Self::Output {
         let mut lhs = self;
@@ -216,7 +234,7 @@ impl Div<GaloisField2p4> for SparseFactor {
     }
 }
 impl Div<u8> for SparseFactor {
-    type Output = SparseFactor;
+    type Output = Self;
 
     fn div(self, rhs: u8) -> Self::Output {
         let mut lhs = self;
@@ -226,24 +244,26 @@ impl Div<u8> for SparseFactor {
 }
 
 // std::ops for Factor
-impl SubAssign<Factor> for Factor {
+impl SubAssign<Self> for Factor {
     fn sub_assign(&mut self, rhs: Self) {
-        match (self, rhs) {
-            (Factor::Wide(lhs), Factor::Wide(rhs)) => lhs.sub_assign(rhs),
-            (Factor::Sparse(lhs), Factor::Sparse(rhs)) => lhs.sub_assign(rhs),
-            (Factor::Wide(lhs), Factor::Sparse(rhs)) => {
-                let mut lhs: SparseFactor = lhs.to_owned().into();
+        match (&mut *self, rhs) {
+            (Self::Wide(lhs), Self::Wide(rhs)) => lhs.sub_assign(rhs),
+            (Self::Sparse(lhs), Self::Sparse(rhs)) => lhs.sub_assign(rhs),
+            // Mixed rows end up sparse, which is what the epochs store
+            (Self::Wide(lhs), Self::Sparse(rhs)) => {
+                let mut lhs: SparseFactor = mem::take(lhs).into();
                 lhs.sub_assign(rhs);
+                *self = Self::Sparse(lhs);
             }
-            (Factor::Sparse(lhs), Factor::Wide(rhs)) => {
+            (Self::Sparse(lhs), Self::Wide(rhs)) => {
                 let rhs: SparseFactor = rhs.into();
                 lhs.sub_assign(rhs);
             }
         }
     }
 }
-impl Sub<Factor> for Factor {
-    type Output = Factor;
+impl Sub<Self> for Factor {
+    type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
         let mut lhs = self;
@@ -251,25 +271,25 @@ impl Sub<Factor> for Factor {
         lhs
     }
 }
-impl AddAssign<Factor> for Factor {
+impl AddAssign<Self> for Factor {
     fn add_assign(&mut self, rhs: Self) {
-        match (self, rhs) {
-            (Factor::Wide(lhs), Factor::Wide(rhs)) => lhs.add_assign(rhs),
-            (Factor::Sparse(lhs), Factor::Sparse(rhs)) => lhs.add_assign(rhs),
-            (Factor::Wide(lhs), Factor::Sparse(rhs)) => {
-                let mut lhs: SparseFactor = lhs.to_owned().into();
+        match (&mut *self, rhs) {
+            (Self::Wide(lhs), Self::Wide(rhs)) => lhs.add_assign(rhs),
+            (Self::Sparse(lhs), Self::Sparse(rhs)) => lhs.add_assign(rhs),
+            (Self::Wide(lhs), Self::Sparse(rhs)) => {
+                let mut lhs: SparseFactor = mem::take(lhs).into();
                 lhs.add_assign(rhs);
-                // *self = Factor::Sparse(lhs);
+                *self = Self::Sparse(lhs);
             }
-            (Factor::Sparse(lhs), Factor::Wide(rhs)) => {
-                let rhs: SparseFactor = rhs.to_owned().into();
+            (Self::Sparse(lhs), Self::Wide(rhs)) => {
+                let rhs: SparseFactor = rhs.into();
                 lhs.add_assign(rhs);
             }
         }
     }
 }
-impl Add<Factor> for Factor {
-    type Output = Factor;
+impl Add<Self> for Factor {
+    type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
         let mut lhs = self;
@@ -280,8 +300,8 @@ impl Add<Factor> for Factor {
 impl MulAssign<GaloisField2p4> for Factor {
     fn mul_assign(&mut self, rhs: GaloisField2p4) {
         match self {
-            Factor::Sparse(lhs) => lhs.mul_assign(rhs),
-            Factor::Wide(lhs) => lhs.mul_assign(rhs),
+            Self::Sparse(lhs) => lhs.mul_assign(rhs),
+            Self::Wide(lhs) => lhs.mul_assign(rhs),
         }
     }
 }
@@ -292,7 +312,7 @@ impl MulAssign<u8> for Factor {
     }
 }
 impl Mul<GaloisField2p4> for Factor {
-    type Output = Factor;
+    type Output = Self;
 
     fn mul(self, rhs: GaloisField2p4) -> Self::Output {
         let mut lhs = self;
@@ -301,7 +321,7 @@ impl Mul<GaloisField2p4> for Factor {
     }
 }
 impl Mul<u8> for Factor {
-    type Output = Factor;
+    type Output = Self;
 
     fn mul(self, rhs: u8) -> Self::Output {
         let mut lhs = self;
@@ -312,8 +332,8 @@ impl Mul<u8> for Factor {
 impl DivAssign<GaloisField2p4> for Factor {
     fn div_assign(&mut self, rhs: GaloisField2p4) {
         match self {
-            Factor::Sparse(lhs) => lhs.div_assign(rhs),
-            Factor::Wide(lhs) => lhs.div_assign(rhs),
+            Self::Sparse(lhs) => lhs.div_assign(rhs),
+            Self::Wide(lhs) => lhs.div_assign(rhs),
         }
     }
 }
@@ -324,7 +344,7 @@ impl DivAssign<u8> for Factor {
     }
 }
 impl Div<GaloisField2p4> for Factor {
-    type Output = Factor;
+    type Output = Self;
 
     fn div(self, rhs: GaloisField2p4) -> Self::Output {
         let mut lhs = self;
@@ -333,7 +353,7 @@ impl Div<GaloisField2p4> for Factor {
     }
 }
 impl Div<u8> for Factor {
-    type Output = Factor;
+    type Output = Self;
 
     fn div(self, rhs: u8) -> Self::Output {
         let mut lhs = self;
@@ -344,10 +364,51 @@ impl Div<u8> for Factor {
 
 #[cfg(test)]
 mod tests {
+    use crate::data_structures::{Factor, SparseFactor, WideFactor};
+    use crate::network_coding::GaloisField2p4;
+
+    fn sparse(factors: &[(u32, u8)]) -> SparseFactor {
+        let inner = factors
+            .iter()
+            .map(|(idx, f)| (*idx, GaloisField2p4::from(*f)))
+            .collect();
+        SparseFactor { inner }
+    }
+
+    #[test]
+    fn add_wide() {
+        let lhs: WideFactor = sparse(&[(0, 3), (7, 5)]).into();
+        let rhs: WideFactor = sparse(&[(7, 5), (9, 1)]).into();
+        assert_eq!(lhs + rhs, sparse(&[(0, 3), (9, 1)]).into());
+    }
     #[test]
-    fn add_wide() {}
+    fn add_sparse() {
+        let lhs = sparse(&[(1, 2), (4, 6), (8, 1)]);
+        let rhs = sparse(&[(0, 7), (4, 6), (8, 3), (12, 9)]);
+        // Equal factors cancel and leave no zero entry behind
+        assert_eq!(lhs + rhs, sparse(&[(0, 7), (1, 2), (8, 2), (12, 9)]));
+        assert_eq!(sparse(&[(5, 4)]) - sparse(&[(5, 4)]), sparse(&[]));
+    }
     #[test]
-    fn add_sparse() {}
+    fn add_factor() {
+        let wide = Factor::Wide(sparse(&[(2, 1), (3, 4)]).into());
+        let result = wide + Factor::Sparse(sparse(&[(3, 4), (5, 5)]));
+        assert_eq!(result, Factor::Sparse(sparse(&[(2, 1), (5, 5)])));
+
+        let result = Factor::Sparse(sparse(&[(2, 1)])) - Factor::Wide(sparse(&[(2, 1)]).into());
+        assert!(result.is_zero());
+    }
     #[test]
-    fn add_factor() {}
+    fn sparse_wide_round_trip() {
+        let factors = sparse(&[(0, 1), (65_567, 15), (1_049_071, 8)]);
+        let wide: WideFactor = factors.clone().into();
+        assert_eq!(
+            wide.inner
+                .iter()
+                .filter(|f| **f != GaloisField2p4::ZERO)
+                .count(),
+            3
+        );
+        assert_eq!(SparseFactor::from(wide), factors);
+    }
 }
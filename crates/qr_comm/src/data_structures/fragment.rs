@@ -1,8 +1,8 @@
 use crate::FRAGMENT_SIZE_BYTES;
-use crate::network_coding::GaloisField2p4;
+use crate::network_coding::{GaloisField2p4, xor_assign_scaled_bytes};
 use std::ops::{AddAssign, Deref, DerefMut, DivAssign, Mul, MulAssign, SubAssign};
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Fragment {
     pub inner: Box<[u8; FRAGMENT_SIZE_BYTES]>,
 }
@@ -12,7 +12,7 @@ impl Default for Fragment {
         let inner = vec![0u8; FRAGMENT_SIZE_BYTES]
             .try_into()
             .expect("Error allocating memory!");
-        Fragment { inner }
+        Self { inner }
     }
 }
 impl Deref for Fragment {
@@ -35,7 +35,7 @@ impl DivAssign<GaloisField2p4> for Fragment {
                 inner: (*f & 0xF0) >> 4,
             } / rhs;
             let lower = GaloisField2p4 { inner: *f & 0xF } / rhs;
-            *f = (upper.inner << 4) | lower.inner
+            *f = (upper.inner << 4) | lower.inner;
         });
     }
 }
@@ -46,12 +46,12 @@ impl MulAssign<GaloisField2p4> for Fragment {
                 inner: (*f & 0xF0) >> 4,
             } * rhs;
             let lower = GaloisField2p4 { inner: *f & 0xF } * rhs;
-            *f = (upper.inner << 4) | lower.inner
+            *f = (upper.inner << 4) | lower.inner;
         });
     }
 }
 impl Mul<GaloisField2p4> for Fragment {
-    type Output = Fragment;
+    type Output = Self;
 
     fn mul(self, rhs: GaloisField2p4) -> Self::Output {
         let mut lhs = self;
@@ -59,8 +59,8 @@ impl Mul<GaloisField2p4> for Fragment {
         lhs
     }
 }
-impl AddAssign<Fragment> for Fragment {
-    fn add_assign(&mut self, rhs: Fragment) {
+impl AddAssign<Self> for Fragment {
+    fn add_assign(&mut self, rhs: Self) {
         self.inner
             .iter_mut()
             .zip(rhs.inner.iter())
@@ -69,8 +69,8 @@ impl AddAssign<Fragment> for Fragment {
             });
     }
 }
-impl SubAssign<Fragment> for Fragment {
-    fn sub_assign(&mut self, rhs: Fragment) {
+impl SubAssign<Self> for Fragment {
+    fn sub_assign(&mut self, rhs: Self) {
         self.inner
             .iter_mut()
             .zip(rhs.inner.iter())
@@ -82,7 +82,12 @@ impl SubAssign<Fragment> for Fragment {
 
 impl Fragment {
     #[inline]
+    #[must_use]
     pub fn is_zero(&self) -> bool {
         self.iter().all(|&x| x == 0)
     }
+    /// `self -= rhs * factor` without cloning `rhs`.
+    pub fn sub_assign_scaled(&mut self, rhs: &Self, factor: GaloisField2p4) {
+        xor_assign_scaled_bytes(&mut self.inner[..], &rhs.inner[..], factor);
+    }
 }
@@ -1,5 +1,6 @@
 use crate::data_structures::{Fragment, FrameFactor, FrameHeader};
 use crate::{FRAME_SIZE_BYTES, QR_CODE_ECC, QR_CODE_VERSION};
+use qrcode::bits::Bits;
 use qrcode::types::QrError;
 use qrcode::{QrCode, QrResult};
 
@@ -13,17 +14,35 @@ pub struct Frame {
 impl TryFrom<Frame> for QrCode {
     type Error = QrError;
 
-    fn try_from(value: Frame) -> QrResult<QrCode> {
-        QrCode::with_version::<[u8; FRAME_SIZE_BYTES]>(value.into(), QR_CODE_VERSION, QR_CODE_ECC)
+    fn try_from(value: Frame) -> QrResult<Self> {
+        let data: [u8; FRAME_SIZE_BYTES] = value.into();
+        encode_qr_bytes(&data)
     }
 }
 
+/// Encode `data` as a single byte segment. The mixed segments picked by
+/// `QrCode::with_version` can need more room than plain bytes, so some
+/// frames would not fit into the configured version.
+pub fn encode_qr_bytes(data: &[u8]) -> QrResult<QrCode> {
+    let mut bits = Bits::new(QR_CODE_VERSION);
+    bits.push_byte_data(data)?;
+    bits.push_terminator(QR_CODE_ECC)?;
+    QrCode::with_bits(bits, QR_CODE_ECC)
+}
+
 impl Frame {
-    pub fn new(factors: FrameFactor, fragment: Fragment, header: FrameHeader) -> Self {
+    /// Build a frame carrying `header` with its sequence number set to `seq`.
+    #[must_use]
+    pub const fn new(
+        factors: FrameFactor,
+        fragment: Fragment,
+        header: FrameHeader,
+        seq: u32,
+    ) -> Self {
         Self {
             factors,
             fragment,
-            header,
+            header: FrameHeader { seq, ..header },
         }
     }
 }
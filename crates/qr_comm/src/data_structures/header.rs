@@ -1,6 +1,11 @@
-#[derive(Copy, Clone, Debug, Default)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct FrameHeader {
     pub participant: u8,
     pub is_overflowing: bool,
     pub epoch: u8,
+    /// Sequence number of the frame within its epoch, used to detect missed frames
+    pub seq: u32,
+    /// Incremented with every new epoch; frames from other epochs are ignored
+    pub epoch_id: u16,
+    pub participant_count: u8,
 }
@@ -10,6 +10,9 @@ pub const MAX_PARTICIPANTS_SIZE_BYTES: usize = MAX_PARTICIPANTS_SIZE_BITS.div_ce
 
 pub const HEADER_SIZE_BYTES: usize = 39;
 pub const HEADER_SIZE_BITS: usize = HEADER_SIZE_BYTES * 8;
+/// Bytes of the header used by `FrameHeader` fields; the rest pads the frame
+/// to the capacity of the QR code
+pub const HEADER_FIELDS_SIZE_BYTES: usize = 10;
 pub const CODING_FACTORS_PER_FRAME: usize =
     CODING_FACTORS_PER_PARTICIPANT_PER_FRAME * MAX_PARTICIPANTS;
 pub const CODING_FACTORS_PER_PARTICIPANT_PER_FRAME: usize = 32;
@@ -22,6 +25,8 @@ pub const CODING_FACTOR_WIDTH_SIZE_BYTES: usize = 16;
 pub const NETWORK_CODING_SIZE_BYTES: usize =
     CODING_FACTOR_WIDTH_SIZE_BYTES + CODING_FACTOR_OFFSET_SIZE_BYTES + CODING_FACTORS_SIZE_BYTES;
 pub const FRAGMENT_SIZE_BYTES: usize = 515;
+// Exponents are single digits; `u32::try_from` is not const
+#[allow(clippy::cast_possible_truncation)]
 pub const FRAGMENTS_PER_PARTICIPANT_PER_EPOCH: usize =
     2usize.pow(CODING_FACTOR_OFFSET_SIZE_BITS as u32) + CODING_FACTORS_PER_PARTICIPANT_PER_FRAME
         - 1;
@@ -30,6 +35,8 @@ pub const FRAME_SIZE_BYTES: usize =
     HEADER_SIZE_BYTES + NETWORK_CODING_SIZE_BYTES + FRAGMENT_SIZE_BYTES;
 
 pub const GALOIS_FIELD_POWER: usize = 4;
+// Exponents are single digits; `u32::try_from` is not const
+#[allow(clippy::cast_possible_truncation)]
 pub const GALOIS_FIELD: usize = 2usize.pow(GALOIS_FIELD_POWER as u32);
 
 pub const EPOCH_SIZE_BYTES: usize = BYTES_PER_PARTICIPANT * MAX_PARTICIPANTS;
@@ -45,6 +52,167 @@ pub const AP_LENGTH_INDEX_SIZE_BYTES: usize = AP_LENGTH_INDEX_SIZE_BITS.div_ceil
 pub const AP_MAX_SIZE_BYTES: usize =
     FRAGMENT_SIZE_BYTES * CODING_FACTORS_PER_FRAME - AP_LENGTH_INDEX_SIZE_BYTES;
 
+/// A violated consistency constraint between the protocol constants above.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterError {
+    /// A frame is not exactly header, network coding and fragment
+    FrameSize,
+    /// The network coding section is not exactly widths, offsets and factors
+    NetworkCodingSize,
+    /// Widths are serialized as one byte per participant
+    WidthSize,
+    /// Offsets are serialized as one little-endian `u16` per participant
+    OffsetSize,
+    /// The coding factors do not fill a whole number of bytes
+    CodingFactorsNotByteAligned,
+    /// Galois field elements must pack evenly into bytes
+    GaloisFieldPower,
+    /// Participant indices do not fit into `MAX_PARTICIPANTS_SIZE_BITS`
+    ParticipantBits,
+    /// Participant ids are serialized as a single byte
+    ParticipantIdOverflow,
+    /// The header cannot hold all `FrameHeader` fields
+    HeaderTooSmall,
+    /// An epoch is not exactly the data of all participants
+    EpochSize,
+    /// Sparse factors store fragment indices as `u32`
+    SparseIndexOverflow,
+    /// The application package length index cannot address every byte
+    ApLengthIndex,
+    /// The application package payload and length index exceed the frame fragments
+    ApMaxSize,
+    /// A serialized frame does not fit into a QR code of the configured version
+    FrameExceedsQrCapacity,
+}
+
+impl std::fmt::Display for ParameterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            Self::FrameSize => {
+                "FRAME_SIZE_BYTES != HEADER_SIZE_BYTES + NETWORK_CODING_SIZE_BYTES + FRAGMENT_SIZE_BYTES"
+            }
+            Self::NetworkCodingSize => {
+                "NETWORK_CODING_SIZE_BYTES does not match widths, offsets and factors"
+            }
+            Self::WidthSize => "CODING_FACTOR_WIDTH_SIZE_BYTES != MAX_PARTICIPANTS",
+            Self::OffsetSize => {
+                "coding factor offsets must be 16 bits for each of MAX_PARTICIPANTS"
+            }
+            Self::CodingFactorsNotByteAligned => "coding factors per frame do not fill whole bytes",
+            Self::GaloisFieldPower => "GALOIS_FIELD_POWER must divide 8",
+            Self::ParticipantBits => {
+                "MAX_PARTICIPANTS does not fit into MAX_PARTICIPANTS_SIZE_BITS"
+            }
+            Self::ParticipantIdOverflow => "MAX_PARTICIPANTS exceeds 256",
+            Self::HeaderTooSmall => "HEADER_SIZE_BYTES is smaller than HEADER_FIELDS_SIZE_BYTES",
+            Self::EpochSize => "EPOCH_SIZE_BYTES != FRAGMENT_SIZE_BYTES * FRAGMENTS_PER_EPOCH",
+            Self::SparseIndexOverflow => "FRAGMENTS_PER_EPOCH exceeds u32::MAX",
+            Self::ApLengthIndex => {
+                "AP_LENGTH_INDEX_SIZE_BITS cannot address a full application package"
+            }
+            Self::ApMaxSize => {
+                "AP_MAX_SIZE_BYTES + AP_LENGTH_INDEX_SIZE_BYTES exceeds the frame fragments"
+            }
+            Self::FrameExceedsQrCapacity => {
+                "FRAME_SIZE_BYTES exceeds the capacity of QR_CODE_VERSION at QR_CODE_ECC"
+            }
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for ParameterError {}
+
+/// Check the invariants between the constants that can be evaluated at compile time.
+///
+/// # Errors
+///
+/// Names the first invariant that does not hold.
+pub const fn check_const_parameters() -> Result<(), ParameterError> {
+    if FRAME_SIZE_BYTES != HEADER_SIZE_BYTES + NETWORK_CODING_SIZE_BYTES + FRAGMENT_SIZE_BYTES {
+        return Err(ParameterError::FrameSize);
+    }
+    if NETWORK_CODING_SIZE_BYTES
+        != CODING_FACTOR_WIDTH_SIZE_BYTES
+            + CODING_FACTOR_OFFSET_SIZE_BYTES
+            + CODING_FACTORS_SIZE_BYTES
+    {
+        return Err(ParameterError::NetworkCodingSize);
+    }
+    if CODING_FACTOR_WIDTH_SIZE_BYTES != MAX_PARTICIPANTS {
+        return Err(ParameterError::WidthSize);
+    }
+    if CODING_FACTOR_OFFSET_SIZE_BITS != 16
+        || CODING_FACTOR_OFFSET_SIZE_BYTES != 2 * MAX_PARTICIPANTS
+    {
+        return Err(ParameterError::OffsetSize);
+    }
+    if !(CODING_FACTORS_PER_FRAME * GALOIS_FIELD_POWER).is_multiple_of(8) {
+        return Err(ParameterError::CodingFactorsNotByteAligned);
+    }
+    if GALOIS_FIELD_POWER == 0 || !8usize.is_multiple_of(GALOIS_FIELD_POWER) {
+        return Err(ParameterError::GaloisFieldPower);
+    }
+    if MAX_PARTICIPANTS > 1 << MAX_PARTICIPANTS_SIZE_BITS {
+        return Err(ParameterError::ParticipantBits);
+    }
+    if MAX_PARTICIPANTS > u8::MAX as usize + 1 {
+        return Err(ParameterError::ParticipantIdOverflow);
+    }
+    if HEADER_SIZE_BYTES < HEADER_FIELDS_SIZE_BYTES {
+        return Err(ParameterError::HeaderTooSmall);
+    }
+    if EPOCH_SIZE_BYTES != FRAGMENT_SIZE_BYTES * FRAGMENTS_PER_EPOCH {
+        return Err(ParameterError::EpochSize);
+    }
+    if FRAGMENTS_PER_EPOCH > u32::MAX as usize {
+        return Err(ParameterError::SparseIndexOverflow);
+    }
+    if FRAGMENT_SIZE_BYTES * CODING_FACTORS_PER_FRAME > 1 << AP_LENGTH_INDEX_SIZE_BITS {
+        return Err(ParameterError::ApLengthIndex);
+    }
+    if AP_MAX_SIZE_BYTES + AP_LENGTH_INDEX_SIZE_BYTES
+        > FRAGMENT_SIZE_BYTES * CODING_FACTORS_PER_FRAME
+    {
+        return Err(ParameterError::ApMaxSize);
+    }
+    Ok(())
+}
+
+// Reject inconsistent constants at compile time
+const _: () = assert!(
+    check_const_parameters().is_ok(),
+    "inconsistent QR network coding parameters"
+);
+
+/// Check all invariants between the protocol constants, including those that
+/// need the QR encoder at runtime.
+///
+/// # Errors
+///
+/// Names the first invariant that does not hold.
+pub fn validate_parameters() -> Result<(), ParameterError> {
+    check_const_parameters()?;
+    data_structures::encode_qr_bytes(&[0u8; FRAME_SIZE_BYTES])
+        .map_err(|_| ParameterError::FrameExceedsQrCapacity)?;
+    Ok(())
+}
+
+/// Validate the parameters once per process and panic on misconfiguration.
+///
+/// Called when the first epoch is created, so a broken build fails as soon as
+/// QR communication starts instead of producing undecodable frames.
+///
+/// # Panics
+///
+/// If `validate_parameters` fails.
+pub fn ensure_valid_parameters() {
+    static VALIDATED: std::sync::OnceLock<Result<(), ParameterError>> = std::sync::OnceLock::new();
+    if let Err(e) = VALIDATED.get_or_init(validate_parameters) {
+        panic!("invalid QR communication parameters: {e}");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,6 +223,7 @@ mod tests {
         assert_eq!(MAX_PARTICIPANTS_SIZE_BYTES, 1);
         assert_eq!(HEADER_SIZE_BYTES, 39);
         assert_eq!(HEADER_SIZE_BITS, 312);
+        assert_eq!(HEADER_FIELDS_SIZE_BYTES, 10);
         assert_eq!(CODING_FACTORS_PER_FRAME, 512);
         assert_eq!(CODING_FACTORS_PER_PARTICIPANT_PER_FRAME, 32);
         assert_eq!(CODING_FACTORS_SIZE_BYTES, 256);
@@ -65,21 +234,26 @@ mod tests {
         // assert_eq!(FRAGMENT_SIZE_BYTES, 1024);
         assert_eq!(FRAGMENT_SIZE_BYTES, 515);
         assert_eq!(FRAGMENTS_PER_PARTICIPANT_PER_EPOCH, 65567);
-        assert_eq!(FRAGMENTS_PER_EPOCH, 1049072);
+        assert_eq!(FRAGMENTS_PER_EPOCH, 1_049_072);
         // assert_eq!(FRAME_SIZE_BYTES, 1367);
         assert_eq!(FRAME_SIZE_BYTES, 858);
         assert_eq!(GALOIS_FIELD_POWER, 4);
         assert_eq!(GALOIS_FIELD, 16);
         // assert_eq!(EPOCH_SIZE_BYTES, 1074249728);
-        assert_eq!(EPOCH_SIZE_BYTES, 540272080);
+        assert_eq!(EPOCH_SIZE_BYTES, 540_272_080);
         // assert_eq!(BYTES_PER_PARTICIPANT, 67140608);
-        assert_eq!(BYTES_PER_PARTICIPANT, 33767005);
+        assert_eq!(BYTES_PER_PARTICIPANT, 33_767_005);
         // assert_eq!(QR_CODE_VERSION, Version::Normal(26));
         assert_eq!(QR_CODE_VERSION, Version::Normal(20));
         assert_eq!(QR_CODE_ECC, EcLevel::L);
         assert_eq!(AP_LENGTH_INDEX_SIZE_BITS, 19);
         assert_eq!(AP_LENGTH_INDEX_SIZE_BYTES, 3);
         // assert_eq!(AP_MAX_SIZE_BYTES, 524285);
-        assert_eq!(AP_MAX_SIZE_BYTES, 263677);
+        assert_eq!(AP_MAX_SIZE_BYTES, 263_677);
+    }
+
+    #[test]
+    fn parameters_are_consistent() {
+        assert_eq!(validate_parameters(), Ok(()));
     }
 }
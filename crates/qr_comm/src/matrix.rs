@@ -7,18 +7,17 @@ pub struct Matrix {
 }
 
 impl Matrix {
+    #[must_use]
     pub fn find_pivot(&self, column: usize, start: usize) -> Option<usize> {
         (start..self.inner.len()).find(|&i| {
-            let f = self.inner[i].factors.get(column);
-            if let Some(f) = f {
-                f != GaloisField2p4::ZERO
-            } else {
-                false
-            }
+            self.inner[i]
+                .factors
+                .get(column)
+                .is_some_and(|f| f != GaloisField2p4::ZERO)
         })
     }
 
-    /// Matrix elimination from https://docs.rs/gauss_jordan_elimination/0.2.0/src/gauss_jordan_elimination/lib.rs.html#23
+    /// Matrix elimination from <https://docs.rs/gauss_jordan_elimination/0.2.0/src/gauss_jordan_elimination/lib.rs.html#23>
     pub fn matrix_elimination(&mut self) {
         self.sweep_downwards();
         self.sweep_upwards();
@@ -50,7 +49,7 @@ impl Matrix {
                             continue;
                         }
                         let (pivot_slice, destination_slice) = self.inner.split_at_mut(row);
-                        destination_slice[0] -= pivot_slice[pivot_row_idx].clone() * factor;
+                        destination_slice[0].sub_assign_scaled(&pivot_slice[pivot_row_idx], factor);
                         if self.inner[row].factors.is_zero() {
                             self.inner.remove(row);
                         }
@@ -67,6 +66,8 @@ impl Matrix {
         }
     }
     /// Eliminate only the lowest equation
+    // The pivot row was just found, so it exists
+    #[allow(clippy::missing_panics_doc)]
     pub fn single_sweep_down(&mut self) {
         let mut pivot_counter: usize = 0;
         for column_idx in 0..FRAGMENTS_PER_EPOCH {
@@ -115,7 +116,7 @@ impl Matrix {
                     {
                         let (destination_slice, pivot_slice) =
                             self.inner.split_at_mut(pivot_row_idx);
-                        destination_slice[row_idx] -= pivot_slice[0].clone() * factor;
+                        destination_slice[row_idx].sub_assign_scaled(&pivot_slice[0], factor);
                     }
                 }
             }
@@ -1,8 +1,18 @@
 mod galois;
-pub use crate::network_coding::galois::GaloisField2p4;
+pub use crate::network_coding::galois::{
+    GaloisField2p4, xor_assign_scaled_bytes, xor_assign_slice,
+};
 
 mod epoch;
 pub use crate::network_coding::epoch::Epoch;
 
 pub mod equation;
 pub use crate::network_coding::equation::Equation;
+
+mod epoch_manager;
+pub use crate::network_coding::epoch_manager::EpochManager;
+
+#[cfg(feature = "image-io")]
+mod qr_image;
+#[cfg(feature = "image-io")]
+pub use crate::network_coding::qr_image::DecodeError;
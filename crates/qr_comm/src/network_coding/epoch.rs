@@ -7,13 +7,13 @@ use crate::{
     FRAGMENT_SIZE_BYTES, FRAGMENTS_PER_EPOCH, FRAGMENTS_PER_PARTICIPANT_PER_EPOCH,
     MAX_PARTICIPANTS,
 };
-use rand::random;
+use rand::{Rng, random};
 use std::array::from_fn;
 use std::io::Write;
 use std::num::NonZeroUsize;
 use std::ops::Range;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Utilization {
     Some(NonZeroUsize),
     Decoded,
@@ -29,6 +29,8 @@ pub struct Epoch {
     pub elimination_flag: bool,
     pub header: FrameHeader,
     pub needed_eqs: usize,
+    /// Sequence number given to the next frame popped from this epoch
+    pub next_seq: u32,
 }
 
 impl Default for Epoch {
@@ -46,7 +48,7 @@ impl Default for Epoch {
                 .expect("Error allocating memory!");
         let elimination_flag = false;
         let header = FrameHeader::default();
-        Epoch {
+        Self {
             equations,
             matrix,
             decoded_fragments,
@@ -55,35 +57,53 @@ impl Default for Epoch {
             elimination_flag,
             header,
             needed_eqs: 0,
+            next_seq: 0,
         }
     }
 }
 
 impl Epoch {
+    #[must_use]
     pub fn new(header: FrameHeader) -> Self {
+        crate::ensure_valid_parameters();
         Self {
             header,
             ..Default::default()
         }
     }
+    /// Start the following epoch: same participant, `epoch_id` incremented and
+    /// all decoding state and sequence numbers reset.
+    #[must_use]
+    pub fn next_epoch(&self) -> Self {
+        Self::new(FrameHeader {
+            epoch_id: self.header.epoch_id.wrapping_add(1),
+            seq: 0,
+            ..self.header
+        })
+    }
+
+    /// Add a received frame. Frames from another epoch are ignored.
+    // The utilization vector has one entry per fragment, so it always
+    // converts into the boxed array
+    #[allow(clippy::missing_panics_doc)]
     pub fn push_frame(&mut self, frame: Frame) {
         let Frame {
             factors,
             fragment,
-            // TODO Think about how the header should be used e.g. implement starting a new epoch
-            header: _header,
+            header,
         } = frame;
+        if header.epoch_id != self.header.epoch_id {
+            return;
+        }
         let factors: SparseFactor = factors.into();
         let utilization: Box<[bool; FRAGMENTS_PER_EPOCH]> = factors.utilized_fragments();
 
         // Check for new fragments this frame
         for (current, utilized) in self.current_utilization.iter_mut().zip(utilization.iter()) {
-            let u = if *utilized { 1 } else { 0 };
-            if let Utilization::None = current
-                && u == 1
-            {
+            let u = usize::from(*utilized);
+            if matches!(current, Utilization::None) && u == 1 {
                 self.elimination_flag = true;
-                *current = Utilization::Some(1.try_into().unwrap());
+                *current = Utilization::Some(NonZeroUsize::MIN);
             } else if let Utilization::Some(c) = current {
                 *c = c.saturating_add(u);
             }
@@ -124,23 +144,15 @@ impl Epoch {
             self.needed_eqs = number_equations;
 
             // Add already decoded fragments, that aren't in the matrix
-            if !plain_equations.is_empty() {
-                for (idx, eq) in plain_equations {
-                    let insert_idx = self
-                        .matrix
-                        .inner
-                        .partition_point(|eq| eq.factors.first_factor().0 < idx);
-                    self.matrix.inner.insert(insert_idx, eq);
-                    self.current_utilization[idx] = Decoded;
-                }
-                self.matrix.sweep_downwards();
-            } else {
-                self.matrix.sweep_downwards();
-                // self.matrix.single_sweep_down();
-                // if self.matrix.inner.last().unwrap().factors.is_zero() {
-                //     self.matrix.inner.pop();
-                // }
+            for (idx, eq) in plain_equations {
+                let insert_idx = self
+                    .matrix
+                    .inner
+                    .partition_point(|eq| eq.factors.first_factor().0 < idx);
+                self.matrix.inner.insert(insert_idx, eq);
+                self.current_utilization[idx] = Decoded;
             }
+            self.matrix.sweep_downwards();
             // #[cfg(debug_assertions)]
             // println!("{}", self.print_matrix());
 
@@ -177,6 +189,22 @@ impl Epoch {
             }
         }
     }
+    /// Add a received frame, unless it is dropped with probability
+    /// `loss_probability` to simulate an unreliable channel in tests.
+    pub fn push_frame_with_loss(&mut self, frame: Frame, loss_probability: f64) {
+        self.push_frame_with_loss_using(frame, loss_probability, &mut rand::rng());
+    }
+    /// Like `push_frame_with_loss`, drawing the losses from `rng`.
+    pub fn push_frame_with_loss_using(
+        &mut self,
+        frame: Frame,
+        loss_probability: f64,
+        rng: &mut impl Rng,
+    ) {
+        if !rng.random_bool(loss_probability) {
+            self.push_frame(frame);
+        }
+    }
     // pub fn pop_frame(&self) -> Frame {
     //     // TODO think about how frames should pick their window widths
     //     let _width = [16; MAX_PARTICIPANTS];
@@ -221,7 +249,11 @@ impl Epoch {
     //         .expect("Looks like I did something wrong!");
     //     Frame::new(coding_factors, fragment, header)
     // }
-    pub fn pop_recent_frame(&self) -> Option<Frame> {
+    // The widths are checked against `CODING_FACTORS_PER_FRAME`, which is
+    // all `FrameFactor::new` rejects, and they and the offsets fit the 8 and
+    // 16 bits of the frame header
+    #[allow(clippy::missing_panics_doc, clippy::cast_possible_truncation)]
+    pub fn pop_recent_frame(&mut self) -> Option<Frame> {
         let mut widths = [0u8; MAX_PARTICIPANTS];
         let mut sum_width = 0;
         let mut offsets = [0u16; MAX_PARTICIPANTS];
@@ -231,12 +263,13 @@ impl Epoch {
         for participant in 0..MAX_PARTICIPANTS {
             if let Some(Range { start, end }) = self.find_range_of_most_recent_package(participant)
             {
-                let width = end - start;
+                // Widths are sent in pairs, an odd package leaves its last factor zero
+                let width = (end - start).next_multiple_of(2);
                 sum_width += width;
                 if sum_width > CODING_FACTORS_PER_FRAME {
                     return None;
                 }
-                widths[participant] = width.div_ceil(2) as u8;
+                widths[participant] = (width / 2) as u8;
                 offsets[participant] = start as u16;
                 // TODO move this after participant loop to fill widths up to maximum
                 for frag in &self.decoded_fragments[participant][start..end] {
@@ -245,11 +278,12 @@ impl Epoch {
                     coding_factor_idx += 1;
                     fragment += frag.clone() * factor;
                 }
+                coding_factor_idx += (end - start) % 2;
             }
         }
         let factors = FrameFactor::new(factors, widths, offsets).unwrap();
-        let header = self.header;
-        let frame = Frame::new(factors, fragment, header);
+        let frame = Frame::new(factors, fragment, self.header, self.next_seq);
+        self.next_seq = self.next_seq.wrapping_add(1);
         Some(frame)
     }
     pub fn write(&mut self, ap: Package) {
@@ -265,6 +299,7 @@ impl Epoch {
             self.meta_ap_fragments[self.header.participant as usize].push(ap_info);
         }
     }
+    #[must_use]
     pub fn get_package(&self, participant: usize, index: usize) -> Option<Package> {
         if self.decoded_fragments[participant].is_empty() {
             return None;
@@ -278,7 +313,10 @@ impl Epoch {
         let mut fragment_index = 0;
         let mut package_index = -1;
         let mut number_used_fragments = 0;
-        while package_index < index as isize {
+        // An epoch holds far fewer packages than `isize::MAX`
+        #[allow(clippy::cast_possible_wrap)]
+        let index = index as isize;
+        while package_index < index {
             // TODO use last element from before index from self.meta_ap_fragments[participant] to start
             let mut size = [0; 4];
             let fragment = self.decoded_fragments[participant].get(fragment_index)?;
@@ -301,6 +339,7 @@ impl Epoch {
         }
         package
     }
+    #[must_use]
     pub fn find_range_of_most_recent_package(&self, participant: usize) -> Option<Range<usize>> {
         if self.decoded_fragments[participant].is_empty() {
             return None;
@@ -322,6 +361,9 @@ impl Epoch {
         }
         range
     }
+    // Writing to a `Vec` cannot fail, and only UTF-8 is written
+    #[allow(clippy::missing_panics_doc)]
+    #[must_use]
     pub fn print_matrix(&self) -> String {
         let mut matrix = Vec::new();
         let idx: Vec<usize> = self
@@ -329,10 +371,10 @@ impl Epoch {
             .iter()
             .enumerate()
             .filter_map(|(i, util)| {
-                if !matches!(util, Utilization::None) {
-                    Some(i)
-                } else {
+                if matches!(util, Utilization::None) {
                     None
+                } else {
+                    Some(i)
                 }
             })
             .collect();
@@ -349,11 +391,13 @@ impl Epoch {
             // f.print_matrix_row(idx.clone());
         }
         writeln!(&mut matrix).unwrap();
-        String::from_utf8(matrix.to_vec()).unwrap()
+        String::from_utf8(matrix.clone()).unwrap()
     }
 }
 
 #[cfg(test)]
+// Participant ids are below `MAX_PARTICIPANTS`
+#[allow(clippy::cast_possible_truncation)]
 mod tests {
     use crate::data_structures::{Fragment, Frame, Package, SparseFactor, WideFactor};
     use crate::matrix::Matrix;
@@ -361,18 +405,33 @@ mod tests {
     use crate::{FRAGMENTS_PER_PARTICIPANT_PER_EPOCH, FRAME_SIZE_BYTES};
     use image::{ImageBuffer, Luma};
     use qrcode::QrCode;
-    use rand::random;
+    use rand::rngs::StdRng;
+    use rand::{SeedableRng, random};
     use std::array::from_fn;
-    use std::fs::File;
-    use std::io::Write;
+    use std::path::PathBuf;
+
+    const FILES: [&[u8]; 3] = [
+        include_bytes!("../../tests/fixtures/data_0.txt"),
+        include_bytes!("../../tests/fixtures/data_1.txt"),
+        include_bytes!("../../tests/fixtures/homepage.md"),
+    ];
+    const NUM_FRAMES: usize = 220;
+
+    fn fixture(idx: usize) -> Package {
+        Package::new(FILES[idx])
+    }
+    /// Where the ignored QR image tests exchange their PNGs
+    fn qr_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join("mcg_qr_comm_tests");
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
 
     #[test]
     fn get_package_test_0() {
         let mut e = Epoch::default();
-        let file_0 = File::open("../../media/qr_test/data_0.txt").unwrap();
-        let file_1 = File::open("../../media/qr_test/data_1.txt").unwrap();
-        let package_0 = Package::from_read(&file_0);
-        let package_1 = Package::from_read(&file_1);
+        let package_0 = fixture(0);
+        let package_1 = fixture(1);
         e.write(package_0.clone());
         e.write(package_1.clone());
         assert_eq!(e.get_package(0, 0).unwrap(), package_0);
@@ -382,8 +441,7 @@ mod tests {
     }
     #[test]
     fn matrix_elimination_test_0() {
-        let file_0 = File::open("../../media/qr_test/data_0.txt").unwrap();
-        let fragments = Package::from_read(file_0).into_fragments();
+        let fragments = fixture(0).into_fragments();
         let equations: Vec<Equation> = fragments
             .iter()
             .enumerate()
@@ -409,38 +467,54 @@ mod tests {
     }
     #[test]
     fn push_frame_test_0() {
-        let mut e = Epoch::default();
-        let package: Package =
-            Package::from_read(File::open("../../media/qr_test/data_0.txt").unwrap());
-        e.write(package);
-        assert!(e.get_package(0, 0).is_some());
-        let mut frames = Vec::new();
-        for _ in 0..4 {
-            let frame = e.pop_recent_frame();
-            assert!(frame.is_some());
-            frames.push(frame.unwrap());
-        }
+        let mut e_out = Epoch::default();
+        let package = fixture(0);
+        let fragments = package.clone().into_fragments().len();
+        e_out.write(package.clone());
+        assert!(e_out.get_package(0, 0).is_some());
         let mut e = Epoch::default();
         e.header.participant = 1;
-        for (idx, frame) in frames.iter().enumerate() {
-            if idx > 0 {
-                assert!(!e.equations.is_empty());
-            } else {
-                assert_eq!(e.equations.len(), idx);
+        for idx in 0..fragments {
+            assert_eq!(e.equations.is_empty(), idx == 0);
+            e.push_frame(e_out.pop_recent_frame().unwrap());
+        }
+        // Random coding factors are occasionally dependent, so allow a few more frames
+        for _ in 0..10 {
+            if e.equations.is_empty() {
+                break;
             }
-            e.push_frame(frame.clone());
+            e.push_frame(e_out.pop_recent_frame().unwrap());
         }
         assert!(e.equations.is_empty());
+        assert_eq!(e.get_package(0, 0), Some(package));
+    }
+    #[test]
+    fn frames_are_sequenced_and_stale_epochs_ignored() {
+        let mut e_out = Epoch::default();
+        e_out.write(fixture(0));
+        let first = e_out.pop_recent_frame().unwrap();
+        let second = e_out.pop_recent_frame().unwrap();
+        assert_eq!((first.header.seq, second.header.seq), (0, 1));
+        let bytes: [u8; FRAME_SIZE_BYTES] = second.clone().into();
+        let decoded: Frame = bytes.into();
+        assert_eq!(decoded.header, second.header);
+
+        let mut e_in = Epoch::default().next_epoch();
+        e_in.header.participant = 1;
+        assert_eq!(e_in.header.epoch_id, 1);
+        e_in.push_frame(first.clone());
+        assert!(e_in.equations.is_empty());
+
+        let mut e_in = Epoch::default();
+        e_in.header.participant = 1;
+        e_in.push_frame(first);
+        assert!(!e_in.equations.is_empty());
     }
     #[test]
     fn push_frame_test_1() {
         let mut e_out = Epoch::default();
-        assert_eq!(FILES.len(), 4);
-        for (idx, file_name) in FILES.iter().enumerate() {
-            let ap = Package::from_read(
-                File::open(format!("../../media/qr_test/{}", file_name)).unwrap(),
-            );
-            e_out.write(ap);
+        for idx in 0..FILES.len() {
+            e_out.write(fixture(idx));
             e_out.header.participant += 1;
             assert!(e_out.get_package(idx, 0).is_some());
         }
@@ -452,7 +526,7 @@ mod tests {
             .factors
             .widths
             .iter()
-            .fold(0u16, |acc, w| acc + (*w as u16 * 2));
+            .fold(0u16, |acc, w| acc + (u16::from(*w) * 2));
         let times = width + 20;
         for idx in 0..times {
             let frame = e_out.pop_recent_frame();
@@ -469,12 +543,8 @@ mod tests {
     #[test]
     fn push_frame_test_2() {
         let mut e_out = Epoch::default();
-        assert_eq!(FILES.len(), 4);
-        for (idx, file_name) in FILES.iter().enumerate() {
-            let ap = Package::from_read(
-                File::open(format!("../../media/qr_test/{}", file_name)).unwrap(),
-            );
-            e_out.write(ap);
+        for idx in 0..FILES.len() {
+            e_out.write(fixture(idx));
             e_out.header.participant += 1;
             assert!(e_out.get_package(idx, 0).is_some());
         }
@@ -494,46 +564,62 @@ mod tests {
             e_in.push_frame(frame.unwrap());
             idx += 1;
         }
-        for (idx, file_name) in FILES.iter().enumerate() {
-            let mut ap = e_in.get_package(idx, 0).unwrap();
-            let mut file = File::create(format!("tests/out_dir/{}", file_name)).unwrap();
-            file.write_all(ap.data.as_mut_slice()).unwrap();
+        for idx in 0..FILES.len() {
+            assert_eq!(e_in.get_package(idx, 0), Some(fixture(idx)));
         }
     }
-    const NUM_FRAMES: usize = 220;
-    const FILES: [&str; 1] = ["data_0.txt"];
-    // const FILES: [&str; 2] = ["data_0.txt", "data_1.txt"];
-    // const FILES: [&str; 4] = [
-    //     "data_0.txt",
-    //     "data_1.txt",
-    //     "dataset-card.png",
-    //     "homepage.md",
-    // ];
+    /// Send frames of two packages over a channel losing 20% of them and
+    /// report whether both decode within 1.5 times the frames sent.
+    fn decodes_with_packet_loss(seed: u64) -> bool {
+        const FRAMES: usize = 200;
+        const LOSS: f64 = 0.2;
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut e_out = Epoch::default();
+        for idx in 0..2 {
+            e_out.write(fixture(idx));
+            e_out.header.participant += 1;
+        }
+        let mut e_in = Epoch::default();
+        e_in.header.participant = 2;
+        for _ in 0..FRAMES * 3 / 2 {
+            let frame = e_out.pop_recent_frame().unwrap();
+            e_in.push_frame_with_loss_using(frame, LOSS, &mut rng);
+            if (0..2).all(|idx| e_in.get_package(idx, 0) == Some(fixture(idx))) {
+                return true;
+            }
+        }
+        false
+    }
+    #[test]
+    fn test_decode_with_packet_loss() {
+        let decoded = (0..10)
+            .filter(|&seed| decodes_with_packet_loss(seed))
+            .count();
+        assert!(decoded >= 9, "decoded in only {decoded} of 10 runs");
+    }
     #[test]
-    #[ignore]
+    #[ignore = "writes PNGs to the temp directory for scan_qr_codes"]
     fn generate_qr_codes() {
         let mut e = Epoch::default();
-        for (idx, file_name) in FILES.iter().enumerate() {
-            let file = File::open(format!("../../media/qr_test/{}", file_name)).unwrap();
-            let package = Package::from_read(&file);
+        for idx in 0..FILES.len() {
             e.header.participant = idx as u8;
-            e.write(package);
+            e.write(fixture(idx));
         }
         for idx in 0..NUM_FRAMES {
             let frame: Frame = e.pop_recent_frame().unwrap();
             let code: Result<QrCode, _> = frame.try_into();
             if let Ok(qr) = code {
                 let image: ImageBuffer<Luma<u8>, Vec<u8>> = qr.render::<Luma<u8>>().build();
-                image.save(format!("tests/out_dir/qr_{idx}.png")).unwrap();
+                image.save(qr_dir().join(format!("qr_{idx}.png"))).unwrap();
             }
         }
     }
     #[test]
-    #[ignore]
+    #[ignore = "needs the PNGs of generate_qr_codes"]
     fn scan_qr_codes() {
         let mut e = Epoch::default();
         for (idx, code) in (0..NUM_FRAMES)
-            .filter_map(|file_idx| image::open(format!("tests/out_dir/qr_{}.png", file_idx)).ok())
+            .filter_map(|file_idx| image::open(qr_dir().join(format!("qr_{file_idx}.png"))).ok())
             .enumerate()
         {
             let img = code.to_luma8();
@@ -552,26 +638,15 @@ mod tests {
                 }
             }
         }
-        for (participant_idx, file) in FILES.iter().enumerate() {
-            let maybe_ap = e.get_package(participant_idx, 0);
-            assert!(maybe_ap.is_some());
-            let Package {
-                data,
-                size: _size,
-            } = maybe_ap.unwrap();
-            if let Ok(mut file) = File::create(format!("tests/out_dir/{}", file)) {
-                let _ = file.write_all(&data);
-            }
+        for idx in 0..FILES.len() {
+            assert_eq!(e.get_package(idx, 0), Some(fixture(idx)));
         }
     }
     #[test]
     fn coding_test_0() {
         let mut e_out = Epoch::default();
-        for (idx, file_name) in FILES.iter().enumerate() {
-            let ap = Package::from_read(
-                File::open(format!("../../media/qr_test/{}", file_name)).unwrap(),
-            );
-            e_out.write(ap);
+        for idx in 0..FILES.len() {
+            e_out.write(fixture(idx));
             e_out.header.participant += 1;
             assert!(e_out.get_package(idx, 0).is_some());
         }
@@ -607,10 +682,8 @@ mod tests {
             //     }
             // }
         }
-        for (idx, file_name) in FILES.iter().enumerate() {
-            let mut ap = e_in.get_package(idx, 0).unwrap();
-            let mut file = File::create(format!("tests/out_dir/{}", file_name)).unwrap();
-            file.write_all(ap.data.as_mut_slice()).unwrap();
+        for idx in 0..FILES.len() {
+            assert_eq!(e_in.get_package(idx, 0), Some(fixture(idx)));
         }
     }
 }
@@ -0,0 +1,176 @@
+use crate::MAX_PARTICIPANTS;
+use crate::data_structures::{Frame, FrameHeader, Package};
+use crate::network_coding::Epoch;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// Epochs at most this many ids behind the newest one are still decoded
+pub const EPOCH_RETENTION: u16 = 3;
+
+/// Decodes several epochs at once.
+///
+/// Late frames of an old epoch may still
+/// arrive after the senders started the next one, so every epoch keeps its
+/// own decoder until it falls more than [`EPOCH_RETENTION`] ids behind the
+/// newest epoch seen. Epoch ids wrap around like [`Epoch::next_epoch`].
+pub struct EpochManager {
+    /// Header of the local participant, used for the epochs created here
+    pub header: FrameHeader,
+    pub active_epochs: HashMap<u16, Epoch>,
+    latest_epoch_id: Option<u16>,
+    /// Number of packages already handed out, by epoch and participant
+    delivered: HashMap<u16, [usize; MAX_PARTICIPANTS]>,
+}
+
+/// How many ids `epoch_id` is behind `latest`, or `None` if it is newer
+fn epochs_behind(latest: u16, epoch_id: u16) -> Option<u16> {
+    let behind = latest.wrapping_sub(epoch_id);
+    (behind <= u16::MAX / 2).then_some(behind)
+}
+
+impl EpochManager {
+    #[must_use]
+    pub fn new(header: FrameHeader) -> Self {
+        Self {
+            header,
+            active_epochs: HashMap::new(),
+            latest_epoch_id: None,
+            delivered: HashMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub const fn latest_epoch_id(&self) -> Option<u16> {
+        self.latest_epoch_id
+    }
+
+    /// Route a received frame to the epoch named in its header, starting a
+    /// new epoch if needed. Frames of collected epochs are ignored.
+    pub fn push_frame(&mut self, frame: Frame) {
+        let epoch_id = frame.header.epoch_id;
+        let behind = self
+            .latest_epoch_id
+            .and_then(|l| epochs_behind(l, epoch_id));
+        match behind {
+            Some(behind) if behind > EPOCH_RETENTION => return,
+            Some(_) => {}
+            None => {
+                self.latest_epoch_id = Some(epoch_id);
+                self.collect_garbage(epoch_id);
+            }
+        }
+        let header = FrameHeader {
+            epoch_id,
+            seq: 0,
+            ..self.header
+        };
+        self.active_epochs
+            .entry(epoch_id)
+            .or_insert_with(|| Epoch::new(header))
+            .push_frame(frame);
+    }
+
+    fn collect_garbage(&mut self, latest: u16) {
+        let keep = |id: &u16| epochs_behind(latest, *id).is_some_and(|b| b <= EPOCH_RETENTION);
+        self.active_epochs.retain(|id, _| keep(id));
+        self.delivered.retain(|id, _| keep(id));
+    }
+
+    /// Packages decoded since the last call as `(epoch_id, participant,
+    /// package)`, oldest epoch first and in the order each participant
+    /// wrote them.
+    pub fn ready_packages(&mut self) -> Vec<(u16, usize, Package)> {
+        let mut ready = Vec::new();
+        for (&epoch_id, epoch) in &self.active_epochs {
+            let delivered = self
+                .delivered
+                .entry(epoch_id)
+                .or_insert([0; MAX_PARTICIPANTS]);
+            for (participant, count) in delivered.iter_mut().enumerate() {
+                while let Some(package) = epoch.get_package(participant, *count) {
+                    ready.push((epoch_id, participant, package));
+                    *count += 1;
+                }
+            }
+        }
+        let latest = self.latest_epoch_id.unwrap_or_default();
+        ready.sort_by_key(|&(epoch_id, participant, _)| {
+            (Reverse(latest.wrapping_sub(epoch_id)), participant)
+        });
+        ready
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sender(epoch_id: u16, data: &[u8]) -> Epoch {
+        let mut epoch = Epoch::new(FrameHeader {
+            epoch_id,
+            ..Default::default()
+        });
+        epoch.write(Package::new(data));
+        epoch
+    }
+
+    fn receiver() -> EpochManager {
+        EpochManager::new(FrameHeader {
+            participant: 1,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn overlapping_epochs_are_decoded_separately() {
+        let mut old = sender(7, b"late frames of the old epoch");
+        let mut new = old.next_epoch();
+        new.write(Package::new(b"first package of the new epoch"));
+        let mut manager = receiver();
+
+        // The new epoch starts before all frames of the old one arrived
+        let mut ready = Vec::new();
+        for _ in 0..8 {
+            manager.push_frame(new.pop_recent_frame().unwrap());
+            manager.push_frame(old.pop_recent_frame().unwrap());
+            ready.extend(manager.ready_packages());
+        }
+        assert_eq!(manager.latest_epoch_id(), Some(8));
+        assert_eq!(manager.active_epochs.len(), 2);
+        let ready: Vec<(u16, usize, Vec<u8>)> = ready
+            .into_iter()
+            .map(|(id, participant, package)| (id, participant, package.data))
+            .collect();
+        assert_eq!(
+            ready,
+            vec![
+                (7, 0, b"late frames of the old epoch".to_vec()),
+                (8, 0, b"first package of the new epoch".to_vec()),
+            ]
+        );
+        assert!(manager.ready_packages().is_empty());
+    }
+
+    #[test]
+    fn epochs_more_than_three_behind_are_dropped() {
+        let mut manager = receiver();
+        let frames: Vec<Frame> = [65534, 65535, 0, 1]
+            .into_iter()
+            .map(|id| sender(id, b"data").pop_recent_frame().unwrap())
+            .collect();
+        for frame in frames {
+            manager.push_frame(frame);
+        }
+        assert_eq!(manager.latest_epoch_id(), Some(1));
+        assert_eq!(manager.active_epochs.len(), 4);
+
+        // Epoch 2 pushes 65534 out, and its late frames are not routed anymore
+        manager.push_frame(sender(2, b"data").pop_recent_frame().unwrap());
+        let mut ids: Vec<u16> = manager.active_epochs.keys().copied().collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec![0, 1, 2, 65535]);
+        manager.push_frame(sender(65534, b"data").pop_recent_frame().unwrap());
+        assert!(!manager.active_epochs.contains_key(&65534));
+        assert_eq!(manager.latest_epoch_id(), Some(2));
+    }
+}
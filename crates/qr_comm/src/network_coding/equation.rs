@@ -11,24 +11,33 @@ pub struct Equation {
 impl Equation {
     pub fn new(factors: impl Into<Factor>, fragment: Fragment) -> Self {
         let factors = factors.into();
-        Equation { factors, fragment }
+        Self { factors, fragment }
     }
+    #[must_use]
     pub fn plain_at_index(index: usize, fragment: Fragment) -> Self {
         let mut sparse = SparseFactor::default();
+        // `check_const_parameters` keeps fragment indices within `u32`
+        #[allow(clippy::cast_possible_truncation)]
+        let index = index as u32;
         sparse.inner.push((index, GaloisField2p4::ONE));
         let factors = Factor::Sparse(sparse);
-        Equation { factors, fragment }
+        Self { factors, fragment }
+    }
+    /// `self -= rhs * factor`, the row operation of the matrix elimination.
+    pub fn sub_assign_scaled(&mut self, rhs: &Self, factor: GaloisField2p4) {
+        self.factors.sub_assign_scaled(&rhs.factors, factor);
+        self.fragment.sub_assign_scaled(&rhs.fragment, factor);
     }
 }
 
-impl SubAssign<Equation> for Equation {
+impl SubAssign<Self> for Equation {
     fn sub_assign(&mut self, rhs: Self) {
         self.factors -= rhs.factors;
         self.fragment -= rhs.fragment;
     }
 }
-impl Sub<Equation> for Equation {
-    type Output = Equation;
+impl Sub<Self> for Equation {
+    type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
         let mut lhs = self;
@@ -36,14 +45,14 @@ impl Sub<Equation> for Equation {
         lhs
     }
 }
-impl AddAssign<Equation> for Equation {
+impl AddAssign<Self> for Equation {
     fn add_assign(&mut self, rhs: Self) {
         self.factors += rhs.factors;
         self.fragment += rhs.fragment;
     }
 }
-impl Add<Equation> for Equation {
-    type Output = Equation;
+impl Add<Self> for Equation {
+    type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
         let mut lhs = self;
@@ -64,7 +73,7 @@ impl MulAssign<u8> for Equation {
     }
 }
 impl Mul<GaloisField2p4> for Equation {
-    type Output = Equation;
+    type Output = Self;
 
     fn mul(self, rhs: GaloisField2p4) -> Self::Output {
         let mut lhs = self;
@@ -73,7 +82,7 @@ impl Mul<GaloisField2p4> for Equation {
     }
 }
 impl Mul<u8> for Equation {
-    type Output = Equation;
+    type Output = Self;
 
     fn mul(self, rhs: u8) -> Self::Output {
         let mut lhs = self;
@@ -94,7 +103,7 @@ impl DivAssign<u8> for Equation {
     }
 }
 impl Div<GaloisField2p4> for Equation {
-    type Output = Equation;
+    type Output = Self;
 
     fn div(self, rhs: GaloisField2p4) -> Self::Output {
         let mut lhs = self;
@@ -103,7 +112,7 @@ impl Div<GaloisField2p4> for Equation {
     }
 }
 impl Div<u8> for Equation {
-    type Output = Equation;
+    type Output = Self;
 
     fn div(self, rhs: u8) -> Self::Output {
         let mut lhs = self;
@@ -4,42 +4,135 @@ use rand::seq::IndexedRandom;
 use std::array::from_fn;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
 
-const MUL_TABLE_2D: [[u8; 16]; 16] = [
-    [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
-    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
-    [0, 2, 4, 6, 8, 10, 12, 14, 3, 1, 7, 5, 11, 9, 15, 13],
-    [0, 3, 6, 5, 12, 15, 10, 9, 11, 8, 13, 14, 7, 4, 1, 2],
-    [0, 4, 8, 12, 3, 7, 11, 15, 6, 2, 14, 10, 5, 1, 13, 9],
-    [0, 5, 10, 15, 7, 2, 13, 8, 14, 11, 4, 1, 9, 12, 3, 6],
-    [0, 6, 12, 10, 11, 13, 7, 1, 5, 3, 9, 15, 14, 8, 2, 4],
-    [0, 7, 14, 9, 15, 8, 1, 6, 13, 10, 3, 4, 2, 5, 12, 11],
-    [0, 8, 3, 11, 6, 14, 5, 13, 12, 4, 15, 7, 10, 2, 9, 1],
-    [0, 9, 1, 8, 2, 11, 3, 10, 4, 13, 5, 12, 6, 15, 7, 14],
-    [0, 10, 7, 13, 14, 4, 9, 3, 15, 5, 8, 2, 1, 11, 6, 12],
-    [0, 11, 5, 14, 10, 1, 15, 4, 7, 12, 2, 9, 13, 6, 8, 3],
-    [0, 12, 11, 7, 5, 9, 14, 2, 10, 6, 1, 13, 15, 3, 4, 8],
-    [0, 13, 9, 4, 1, 12, 8, 5, 2, 15, 11, 6, 3, 14, 10, 7],
-    [0, 14, 15, 1, 13, 3, 2, 12, 9, 7, 6, 8, 4, 10, 11, 5],
-    [0, 15, 13, 2, 9, 6, 4, 11, 1, 14, 12, 3, 8, 7, 5, 10],
-];
-const DIV_TABLE_2D: [[u8; 16]; 16] = [
-    [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
-    [0, 1, 9, 14, 13, 11, 7, 6, 15, 2, 12, 5, 10, 4, 3, 8],
-    [0, 2, 1, 15, 9, 5, 14, 12, 13, 4, 11, 10, 7, 8, 6, 3],
-    [0, 3, 8, 1, 4, 14, 9, 10, 2, 6, 7, 15, 13, 12, 5, 11],
-    [0, 4, 2, 13, 1, 10, 15, 11, 9, 8, 5, 7, 14, 3, 12, 6],
-    [0, 5, 11, 3, 12, 1, 8, 13, 6, 10, 9, 2, 4, 7, 15, 14],
-    [0, 6, 3, 2, 8, 15, 1, 7, 4, 12, 14, 13, 9, 11, 10, 5],
-    [0, 7, 10, 12, 5, 4, 6, 1, 11, 14, 2, 8, 3, 15, 9, 13],
-    [0, 8, 4, 9, 2, 7, 13, 5, 1, 3, 10, 14, 15, 6, 11, 12],
-    [0, 9, 13, 7, 15, 12, 10, 3, 14, 1, 6, 11, 5, 2, 8, 4],
-    [0, 10, 5, 6, 11, 2, 3, 9, 12, 7, 1, 4, 8, 14, 13, 15],
-    [0, 11, 12, 8, 6, 9, 4, 15, 3, 5, 13, 1, 2, 10, 14, 7],
-    [0, 12, 6, 4, 3, 13, 2, 14, 8, 11, 15, 9, 1, 5, 7, 10],
-    [0, 13, 15, 10, 14, 6, 5, 8, 7, 9, 3, 12, 11, 1, 4, 2],
-    [0, 14, 7, 11, 10, 8, 12, 2, 5, 15, 4, 3, 6, 13, 1, 9],
-    [0, 15, 14, 5, 7, 3, 11, 4, 10, 13, 8, 6, 12, 9, 2, 1],
-];
+/// Product of `a` and `b` modulo P(x) = x4 + x + 1, by shift-and-add.
+///
+/// Only used to build `MUL_TABLE_2D`; at runtime the table lookup is faster.
+const fn mul_polynomial(a: u8, b: u8) -> u8 {
+    let mut a = a & 0xF;
+    let mut b = b & 0xF;
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        b >>= 1;
+        a <<= 1;
+        // Reduce x4 to x + 1
+        if a & 0x10 != 0 {
+            a ^= 0b1_0011;
+        }
+    }
+    product
+}
+
+// Field elements are below 16; `u8::try_from` is not const
+#[allow(clippy::cast_possible_truncation)]
+const fn mul_table() -> [[u8; 16]; 16] {
+    let mut table = [[0; 16]; 16];
+    let mut a = 0;
+    while a < 16 {
+        let mut b = 0;
+        while b < 16 {
+            table[a][b] = mul_polynomial(a as u8, b as u8);
+            b += 1;
+        }
+        a += 1;
+    }
+    table
+}
+
+/// Multiplicative inverses; zero has none and maps to zero.
+// Field elements are below 16; `u8::try_from` is not const
+#[allow(clippy::cast_possible_truncation)]
+const fn inv_table() -> [u8; 16] {
+    let mut table = [0; 16];
+    let mut a = 1;
+    while a < 16 {
+        let mut b = 1;
+        while MUL_TABLE_2D[a][b] != 1 {
+            b += 1;
+        }
+        table[a] = b as u8;
+        a += 1;
+    }
+    table
+}
+
+/// `a / b` as `a * b^-1`; dividing by zero yields zero.
+const fn div_table() -> [[u8; 16]; 16] {
+    let mut table = [[0; 16]; 16];
+    let mut a = 0;
+    while a < 16 {
+        let mut b = 0;
+        while b < 16 {
+            table[a][b] = MUL_TABLE_2D[a][INV_TABLE[b] as usize];
+            b += 1;
+        }
+        a += 1;
+    }
+    table
+}
+
+/// Products of each element with both elements packed into a byte.
+const fn mul_packed_table() -> [[u8; 256]; 16] {
+    let mut table = [[0; 256]; 16];
+    let mut a = 0;
+    while a < 16 {
+        let mut byte = 0;
+        while byte < 256 {
+            let upper = MUL_TABLE_2D[a][byte >> 4];
+            let lower = MUL_TABLE_2D[a][byte & 0xF];
+            table[a][byte] = (upper << 4) | lower;
+            byte += 1;
+        }
+        a += 1;
+    }
+    table
+}
+
+const MUL_TABLE_2D: [[u8; 16]; 16] = mul_table();
+const INV_TABLE: [u8; 16] = inv_table();
+const DIV_TABLE_2D: [[u8; 16]; 16] = div_table();
+const MUL_PACKED_TABLE: [[u8; 256]; 16] = mul_packed_table();
+
+/// Check the field axioms on the generated tables for all elements.
+// Field elements are below 16; `u8::try_from` is not const
+#[allow(clippy::cast_possible_truncation)]
+const fn verify_multiplication_table() {
+    let mut a = 0;
+    while a < 16 {
+        assert!(MUL_TABLE_2D[a][0] == 0, "zero is not absorbing");
+        assert!(MUL_TABLE_2D[a][1] == a as u8, "one is not the identity");
+        if a != 0 {
+            assert!(
+                MUL_TABLE_2D[a][INV_TABLE[a] as usize] == 1,
+                "wrong multiplicative inverse"
+            );
+        }
+        let mut b = 0;
+        while b < 16 {
+            let ab = MUL_TABLE_2D[a][b] as usize;
+            assert!(ab == MUL_TABLE_2D[b][a] as usize, "not commutative");
+            let mut c = 0;
+            while c < 16 {
+                assert!(
+                    MUL_TABLE_2D[ab][c] == MUL_TABLE_2D[a][MUL_TABLE_2D[b][c] as usize],
+                    "not associative"
+                );
+                assert!(
+                    MUL_TABLE_2D[a][b ^ c] == MUL_TABLE_2D[a][b] ^ MUL_TABLE_2D[a][c],
+                    "not distributive"
+                );
+                c += 1;
+            }
+            b += 1;
+        }
+        a += 1;
+    }
+}
+
+const _: () = verify_multiplication_table();
+
 const POW_TABLE_2D: [[u8; 16]; 16] = [
     [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
     [0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1],
@@ -65,18 +158,18 @@ const LOG_TABLE: [u8; 16] = [15, 0, 1, 4, 2, 8, 5, 10, 3, 14, 9, 7, 6, 13, 11, 1
 
 /// Implementation of GF(16) with P(x) = x4 + x + 1
 /// The `inner` attribute only hold a single 4-bit number, even though it could hold two.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct GaloisField2p4 {
     pub inner: u8,
 }
 
 impl Add for GaloisField2p4 {
-    type Output = GaloisField2p4;
+    type Output = Self;
 
     #[allow(clippy::suspicious_arithmetic_impl)]
     fn add(self, rhs: Self) -> Self::Output {
         let inner = self.inner ^ rhs.inner;
-        GaloisField2p4 { inner }
+        Self { inner }
     }
 }
 impl AddAssign for GaloisField2p4 {
@@ -86,12 +179,12 @@ impl AddAssign for GaloisField2p4 {
     }
 }
 impl Sub for GaloisField2p4 {
-    type Output = GaloisField2p4;
+    type Output = Self;
 
     #[allow(clippy::suspicious_arithmetic_impl)]
     fn sub(self, rhs: Self) -> Self::Output {
         let inner = self.inner ^ rhs.inner;
-        GaloisField2p4 { inner }
+        Self { inner }
     }
 }
 impl SubAssign for GaloisField2p4 {
@@ -101,11 +194,11 @@ impl SubAssign for GaloisField2p4 {
     }
 }
 impl Mul for GaloisField2p4 {
-    type Output = GaloisField2p4;
+    type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
         let inner = MUL_TABLE_2D[self.inner as usize][rhs.inner as usize];
-        GaloisField2p4 { inner }
+        Self { inner }
     }
 }
 impl MulAssign for GaloisField2p4 {
@@ -114,11 +207,11 @@ impl MulAssign for GaloisField2p4 {
     }
 }
 impl Div for GaloisField2p4 {
-    type Output = GaloisField2p4;
+    type Output = Self;
 
     fn div(self, rhs: Self) -> Self::Output {
         let inner = DIV_TABLE_2D[self.inner as usize][rhs.inner as usize];
-        GaloisField2p4 { inner }
+        Self { inner }
     }
 }
 impl DivAssign for GaloisField2p4 {
@@ -126,13 +219,27 @@ impl DivAssign for GaloisField2p4 {
         self.inner = DIV_TABLE_2D[self.inner as usize][rhs.inner as usize];
     }
 }
+/// Scalar multiplication of a whole row, looking up the row of the
+/// multiplication table only once.
+impl Mul<GaloisField2p4> for &[GaloisField2p4] {
+    type Output = Vec<GaloisField2p4>;
+
+    fn mul(self, rhs: GaloisField2p4) -> Self::Output {
+        let products = &MUL_TABLE_2D[rhs.inner as usize];
+        self.iter()
+            .map(|f| GaloisField2p4 {
+                inner: products[f.inner as usize],
+            })
+            .collect()
+    }
+}
 impl From<u8> for GaloisField2p4 {
-    /// Creating GaloisField2p4 from u8 shifts the value down by 4 bits if it is too big to fit in.
+    /// Creating `GaloisField2p4` from u8 shifts the value down by 4 bits if it is too big to fit in.
     fn from(value: u8) -> Self {
         if value <= 0xF {
-            GaloisField2p4 { inner: value }
+            Self { inner: value }
         } else {
-            GaloisField2p4 {
+            Self {
                 inner: (value & 0xF0) >> 4,
             }
         }
@@ -142,29 +249,159 @@ impl From<u8> for GaloisField2p4 {
 // Trait `Distribution<GaloisField2p4>` is not implemented for `StandardUniform`
 impl Distribution<GaloisField2p4> for StandardUniform {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> GaloisField2p4 {
+        // The non-zero elements, 1 to 15
+        #[allow(clippy::cast_possible_truncation)]
         let range: [GaloisField2p4; 15] = from_fn(|idx| GaloisField2p4::from((idx + 1) as u8));
         *range.choose(rng).unwrap()
     }
 }
 
 impl GaloisField2p4 {
-    pub fn pow(self, exp: GaloisField2p4) -> GaloisField2p4 {
+    #[must_use]
+    pub const fn pow(self, exp: Self) -> Self {
         let inner = POW_TABLE_2D[self.inner as usize][exp.inner as usize];
-        GaloisField2p4 { inner }
+        Self { inner }
     }
-    pub fn pow_assign(&mut self, exp: GaloisField2p4) {
+    pub const fn pow_assign(&mut self, exp: Self) {
         self.inner = POW_TABLE_2D[self.inner as usize][exp.inner as usize];
     }
-    pub const ZERO: GaloisField2p4 = GaloisField2p4 { inner: 0 };
-    pub const ONE: GaloisField2p4 = GaloisField2p4 { inner: 1 };
+    /// Multiplicative inverse. Zero has none and stays zero, like division by zero.
+    #[must_use]
+    pub const fn inv(self) -> Self {
+        let inner = INV_TABLE[self.inner as usize];
+        Self { inner }
+    }
+    /// Multiply without the lookup table, for comparison in benchmarks.
+    #[must_use]
+    pub const fn mul_polynomial(self, rhs: Self) -> Self {
+        let inner = mul_polynomial(self.inner, rhs.inner);
+        Self { inner }
+    }
+    pub const ZERO: Self = Self { inner: 0 };
+    pub const ONE: Self = Self { inner: 1 };
+}
+
+/// Element-wise `dst += src`, which is XOR in GF(16), eight elements at a time.
+///
+/// # Panics
+///
+/// If the slices differ in length.
+pub fn xor_assign_slice(dst: &mut [GaloisField2p4], src: &[GaloisField2p4]) {
+    assert_eq!(dst.len(), src.len(), "rows of different length");
+    let mut dst_chunks = dst.chunks_exact_mut(8);
+    let mut src_chunks = src.chunks_exact(8);
+    for (dst, src) in (&mut dst_chunks).zip(&mut src_chunks) {
+        let sum = pack(dst) ^ pack(src);
+        for (f, inner) in dst.iter_mut().zip(sum.to_le_bytes()) {
+            f.inner = inner;
+        }
+    }
+    for (dst, src) in dst_chunks
+        .into_remainder()
+        .iter_mut()
+        .zip(src_chunks.remainder())
+    {
+        *dst += *src;
+    }
+}
+
+/// Eight elements as one word, one per byte.
+fn pack(chunk: &[GaloisField2p4]) -> u64 {
+    u64::from_le_bytes(from_fn(|i| chunk[i].inner))
+}
+
+/// `dst += src * factor` on bytes that pack two elements each, like the
+/// bytes of a `Fragment`.
+///
+/// # Panics
+///
+/// If the slices differ in length.
+pub fn xor_assign_scaled_bytes(dst: &mut [u8], src: &[u8], factor: GaloisField2p4) {
+    assert_eq!(dst.len(), src.len(), "rows of different length");
+    let products = &MUL_PACKED_TABLE[factor.inner as usize];
+    let mut dst_chunks = dst.chunks_exact_mut(8);
+    let mut src_chunks = src.chunks_exact(8);
+    for (dst, src) in (&mut dst_chunks).zip(&mut src_chunks) {
+        let scaled = u64::from_le_bytes(from_fn(|i| products[src[i] as usize]));
+        let sum = u64::from_le_bytes(from_fn(|i| dst[i])) ^ scaled;
+        dst.copy_from_slice(&sum.to_le_bytes());
+    }
+    for (dst, src) in dst_chunks
+        .into_remainder()
+        .iter_mut()
+        .zip(src_chunks.remainder())
+    {
+        *dst ^= products[*src as usize];
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::iter::repeat_n;
+
+    #[test]
+    fn mul_table_matches_exp_log_tables() {
+        for a in 1..16usize {
+            for b in 1..16usize {
+                let log = (LOG_TABLE[a] as usize + LOG_TABLE[b] as usize) % 15;
+                assert_eq!(MUL_TABLE_2D[a][b], EXP_TABLE[log], "{a} * {b}");
+            }
+        }
+        assert_eq!(
+            MUL_TABLE_2D[2],
+            [0, 2, 4, 6, 8, 10, 12, 14, 3, 1, 7, 5, 11, 9, 15, 13]
+        );
+    }
+    #[test]
+    fn inverse_and_division() {
+        assert_eq!(
+            INV_TABLE,
+            [0, 1, 9, 14, 13, 11, 7, 6, 15, 2, 12, 5, 10, 4, 3, 8]
+        );
+        for a in 0..16u8 {
+            let a = GaloisField2p4::from(a);
+            assert_eq!(a / GaloisField2p4::ZERO, GaloisField2p4::ZERO);
+            for b in 1..16u8 {
+                let b = GaloisField2p4::from(b);
+                assert_eq!(a / b, a * b.inv());
+                assert_eq!((a / b) * b, a);
+            }
+        }
+        assert_eq!(GaloisField2p4::ZERO.inv(), GaloisField2p4::ZERO);
+    }
+    #[test]
+    fn batch_operations_match_single_elements() {
+        // 19 elements leave a remainder after the chunks of eight
+        let row: Vec<GaloisField2p4> = (0..19u8).map(|i| GaloisField2p4::from(i % 16)).collect();
+        let factor = GaloisField2p4::from(7);
+        let scaled = &row[..] * factor;
+        for (f, s) in row.iter().zip(&scaled) {
+            assert_eq!(*f * factor, *s);
+        }
+
+        let mut sum = scaled.clone();
+        xor_assign_slice(&mut sum, &row);
+        for ((f, s), sum) in row.iter().zip(&scaled).zip(&sum) {
+            assert_eq!(*f + *s, *sum);
+        }
+
+        let src: Vec<u8> = (0..19u8).map(|i| i.wrapping_mul(37)).collect();
+        let mut dst: Vec<u8> = (0..19u8).map(|i| i.wrapping_mul(101)).collect();
+        let expected: Vec<u8> = src
+            .iter()
+            .zip(&dst)
+            .map(|(s, d)| {
+                let upper = GaloisField2p4::from(s >> 4) * factor;
+                let lower = GaloisField2p4::from(s & 0xF) * factor;
+                d ^ ((upper.inner << 4) | lower.inner)
+            })
+            .collect();
+        xor_assign_scaled_bytes(&mut dst, &src, factor);
+        assert_eq!(dst, expected);
+    }
     #[test]
-    #[ignore]
+    #[ignore = "prints the lookup table, run by hand after changing the field"]
     fn generate_pow_table() {
         let mut table = [[0u8; 16]; 16];
         for x in 1..16u8 {
@@ -0,0 +1,133 @@
+use crate::FRAME_SIZE_BYTES;
+use crate::data_structures::Frame;
+use crate::network_coding::Epoch;
+use image::{ImageBuffer, ImageFormat, Luma};
+use qrcode::QrCode;
+use std::array::from_fn;
+use std::io::Cursor;
+
+/// Why a PNG could not be turned into a frame.
+#[derive(Debug)]
+pub enum DecodeError {
+    /// The bytes at `index` are not a readable image
+    Image {
+        index: usize,
+        source: image::ImageError,
+    },
+    /// No QR code was found in the image at `index`
+    NoQrCode { index: usize },
+    /// The QR code in the image at `index` could not be read
+    Qr {
+        index: usize,
+        source: rqrr::DeQRError,
+    },
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Image { index, source } => write!(f, "frame {index}: {source}"),
+            Self::NoQrCode { index } => write!(f, "frame {index}: no QR code found"),
+            Self::Qr { index, source } => write!(f, "frame {index}: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Image { source, .. } => Some(source),
+            Self::NoQrCode { .. } => None,
+            Self::Qr { source, .. } => Some(source),
+        }
+    }
+}
+
+impl Epoch {
+    /// Pop up to `count` frames and render each as a QR code PNG. Fewer are
+    /// returned once the epoch has no more frames to send.
+    // The frame size is checked against the QR capacity when the epoch is
+    // created, and writing to memory cannot fail
+    #[allow(clippy::missing_panics_doc)]
+    pub fn encode_to_qr_png_bytes(&mut self, count: usize) -> Vec<Vec<u8>> {
+        (0..count)
+            .map_while(|_| self.pop_recent_frame())
+            .map(|frame| {
+                // The frame size is checked against the QR capacity when the epoch is created
+                let code = QrCode::try_from(frame).expect("frame exceeds the QR code capacity");
+                let image: ImageBuffer<Luma<u8>, Vec<u8>> = code.render::<Luma<u8>>().build();
+                let mut png = Vec::new();
+                image
+                    .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+                    .expect("writing a PNG to memory cannot fail");
+                png
+            })
+            .collect()
+    }
+
+    /// Scan the QR code of each PNG and push its frame. Stops at the first
+    /// image that cannot be read; the frames before it have been pushed.
+    ///
+    /// # Errors
+    ///
+    /// If an image is not a PNG or shows no readable QR code.
+    pub fn decode_from_png_bytes(&mut self, frames: &[&[u8]]) -> Result<(), DecodeError> {
+        for (index, png) in frames.iter().enumerate() {
+            let image = image::load_from_memory_with_format(png, ImageFormat::Png)
+                .map_err(|source| DecodeError::Image { index, source })?;
+            let mut prepared = rqrr::PreparedImage::prepare(image.to_luma8());
+            let grids = prepared.detect_grids();
+            let grid = grids.first().ok_or(DecodeError::NoQrCode { index })?;
+            let mut buf = Vec::new();
+            grid.decode_to(&mut buf)
+                .map_err(|source| DecodeError::Qr { index, source })?;
+            let data: [u8; FRAME_SIZE_BYTES] = from_fn(|idx| *buf.get(idx).unwrap_or(&0));
+            self.push_frame(Frame::from(data));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_structures::Package;
+
+    #[test]
+    fn packages_round_trip_through_qr_pngs() {
+        let package = Package::new(b"mental card games over QR codes");
+        let mut sender = Epoch::default();
+        sender.write(package.clone());
+        let mut receiver = Epoch::default();
+        receiver.header.participant = 1;
+
+        for _ in 0..10 {
+            let pngs = sender.encode_to_qr_png_bytes(4);
+            assert_eq!(pngs.len(), 4);
+            let frames: Vec<&[u8]> = pngs.iter().map(Vec::as_slice).collect();
+            receiver.decode_from_png_bytes(&frames).unwrap();
+            if receiver.get_package(0, 0).is_some() {
+                break;
+            }
+        }
+        assert_eq!(receiver.get_package(0, 0), Some(package));
+    }
+
+    #[test]
+    fn images_without_a_qr_code_are_rejected() {
+        let blank: ImageBuffer<Luma<u8>, Vec<u8>> = ImageBuffer::from_pixel(64, 64, Luma([255]));
+        let mut png = Vec::new();
+        blank
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        let mut e = Epoch::default();
+        assert!(matches!(
+            e.decode_from_png_bytes(&[&png]),
+            Err(DecodeError::NoQrCode { index: 0 })
+        ));
+        assert!(matches!(
+            e.decode_from_png_bytes(&[b"not a png"]),
+            Err(DecodeError::Image { index: 0, .. })
+        ));
+    }
+}
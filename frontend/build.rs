@@ -0,0 +1,19 @@
+// Records the compiler and target of the build for
+// `plugin_loader::PLUGIN_ABI_VERSION`.
+
+use std::process::Command;
+
+fn main() {
+    // Cargo rebuilds the crate anyway when the compiler changes
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .unwrap_or_default();
+    let target = std::env::var("TARGET").unwrap_or_default();
+    println!("cargo:rustc-env=MCG_PLUGIN_ABI={} {target}", version.trim());
+}
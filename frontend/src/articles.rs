@@ -1,30 +1,181 @@
-use serde::{Deserialize, Serialize};
+use egui::{RichText, Ui};
+use mcg_shared::ArticleMeta;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Post {
-    #[serde(rename = "userId")]
-    pub user_id: u32,
-    pub id: u32,
-    pub title: String,
-    pub body: String,
+/// Fetch the list of articles published by the server.
+pub async fn fetch_article_list(server_address: &str) -> Result<Vec<ArticleMeta>, String> {
+    let url = format!("http://{}/articles", server_address);
+
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| format!("Failed to fetch articles: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse JSON: {}", e))
 }
 
-pub async fn fetch_posts() -> Result<Vec<Post>, String> {
-    let url = "https://jsonplaceholder.typicode.com/posts/";
+/// Fetch the markdown body of one article.
+pub async fn fetch_article(server_address: &str, id: &str) -> Result<String, String> {
+    let url = format!("http://{}/articles/{}", server_address, id);
 
-    let response = reqwest::get(url)
+    let response = reqwest::get(&url)
         .await
-        .map_err(|e| format!("Failed to fetch posts: {}", e))?;
+        .map_err(|e| format!("Failed to fetch article: {}", e))?;
 
     if !response.status().is_success() {
         return Err(format!("HTTP error: {}", response.status()));
     }
 
-    let posts: Vec<Post> = response
-        .json()
+    response
+        .text()
         .await
-        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+        .map_err(|e| format!("Failed to read article: {}", e))
+}
+
+/// Markdown subset understood by [`render_markdown`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkdownBlock {
+    /// `#`, `##` or `###` heading with its level
+    Heading(usize, String),
+    /// Paragraph or list item as `(text, bold)` spans
+    Paragraph(Vec<(String, bool)>),
+    /// Fenced code block
+    Code(String),
+}
+
+/// Split markdown into headings, paragraphs and fenced code blocks.
+/// List items become paragraphs of their own; `**bold**` is the only inline style.
+pub fn parse_markdown(text: &str) -> Vec<MarkdownBlock> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut code: Option<Vec<&str>> = None;
+
+    fn flush(blocks: &mut Vec<MarkdownBlock>, paragraph: &mut Vec<&str>) {
+        if !paragraph.is_empty() {
+            blocks.push(MarkdownBlock::Paragraph(parse_inline(&paragraph.join(" "))));
+            paragraph.clear();
+        }
+    }
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            match code.take() {
+                Some(lines) => blocks.push(MarkdownBlock::Code(lines.join("\n"))),
+                None => {
+                    flush(&mut blocks, &mut paragraph);
+                    code = Some(Vec::new());
+                }
+            }
+        } else if let Some(lines) = code.as_mut() {
+            lines.push(line);
+        } else if trimmed.is_empty() {
+            flush(&mut blocks, &mut paragraph);
+        } else if let Some(heading) = parse_heading(trimmed) {
+            flush(&mut blocks, &mut paragraph);
+            blocks.push(heading);
+        } else if is_list_item(trimmed) {
+            flush(&mut blocks, &mut paragraph);
+            paragraph.push(trimmed);
+            flush(&mut blocks, &mut paragraph);
+        } else {
+            paragraph.push(trimmed);
+        }
+    }
+    if let Some(lines) = code {
+        blocks.push(MarkdownBlock::Code(lines.join("\n")));
+    }
+    flush(&mut blocks, &mut paragraph);
+    blocks
+}
+
+fn parse_heading(line: &str) -> Option<MarkdownBlock> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let title = line[level..].strip_prefix(' ')?;
+    (1..=3)
+        .contains(&level)
+        .then(|| MarkdownBlock::Heading(level, title.trim().to_string()))
+}
 
-    Ok(posts)
+fn is_list_item(line: &str) -> bool {
+    let numbered = line
+        .split_once(". ")
+        .is_some_and(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+    numbered || line.starts_with("- ") || line.starts_with("* ")
 }
 
+/// Every other `**`-delimited segment is bold.
+fn parse_inline(text: &str) -> Vec<(String, bool)> {
+    text.split("**")
+        .enumerate()
+        .filter(|(_, s)| !s.is_empty())
+        .map(|(i, s)| (s.to_string(), i % 2 == 1))
+        .collect()
+}
+
+/// Draw markdown parsed with [`parse_markdown`].
+pub fn render_markdown(ui: &mut Ui, text: &str) {
+    for block in parse_markdown(text) {
+        match block {
+            MarkdownBlock::Heading(1, title) => {
+                ui.label(RichText::new(title).heading().strong());
+            }
+            MarkdownBlock::Heading(2, title) => {
+                ui.heading(title);
+            }
+            MarkdownBlock::Heading(_, title) => {
+                ui.label(RichText::new(title).strong());
+            }
+            MarkdownBlock::Paragraph(spans) => {
+                ui.horizontal_wrapped(|ui| {
+                    ui.spacing_mut().item_spacing.x = 0.0;
+                    for (text, bold) in spans {
+                        if bold {
+                            ui.label(RichText::new(text).strong());
+                        } else {
+                            ui.label(text);
+                        }
+                    }
+                });
+            }
+            MarkdownBlock::Code(code) => {
+                egui::Frame::group(ui.style()).show(ui, |ui| {
+                    ui.monospace(code);
+                });
+            }
+        }
+        ui.add_space(6.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_headings_bold_lists_and_code() {
+        let blocks = parse_markdown(
+            "# Title\n\nSome **bold**\ntext.\n\n## Part\n- one\n- two\n\n```\nAs Ks\n```\n",
+        );
+        assert_eq!(
+            blocks,
+            vec![
+                MarkdownBlock::Heading(1, "Title".into()),
+                MarkdownBlock::Paragraph(vec![
+                    ("Some ".into(), false),
+                    ("bold".into(), true),
+                    (" text.".into(), false),
+                ]),
+                MarkdownBlock::Heading(2, "Part".into()),
+                MarkdownBlock::Paragraph(vec![("- one".into(), false)]),
+                MarkdownBlock::Paragraph(vec![("- two".into(), false)]),
+                MarkdownBlock::Code("As Ks".into()),
+            ]
+        );
+    }
+}
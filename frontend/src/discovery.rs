@@ -0,0 +1,89 @@
+//! Listener for LAN server announcements (see `mcg_shared::ServerAd`).
+//!
+//! Browsers cannot open UDP sockets, so discovery only works in native builds;
+//! on wasm the list of servers always stays empty.
+
+use std::sync::{Arc, Mutex};
+
+use mcg_shared::ServerAd;
+
+/// Servers that have not announced themselves for this long are dropped.
+const SERVER_TIMEOUT_MS: f64 = 15_000.0;
+
+/// A server heard on the local network.
+#[derive(Clone, Debug)]
+pub struct DiscoveredServer {
+    pub ad: ServerAd,
+    /// `ip:port` to connect to
+    pub address: String,
+    pub last_seen_ms: f64,
+}
+
+/// Collects [`ServerAd`]s received by a background listener thread.
+#[derive(Default)]
+pub struct ServerDiscovery {
+    servers: Arc<Mutex<Vec<DiscoveredServer>>>,
+    started: bool,
+}
+
+impl ServerDiscovery {
+    /// Whether this build can receive announcements at all.
+    pub const SUPPORTED: bool = cfg!(not(target_arch = "wasm32"));
+
+    /// Start listening once; later calls do nothing.
+    pub fn start(&mut self) {
+        if self.started {
+            return;
+        }
+        self.started = true;
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let servers = self.servers.clone();
+            std::thread::spawn(move || {
+                if let Err(e) = listen(&servers) {
+                    crate::sprintln!("LAN discovery stopped: {}", e);
+                }
+            });
+        }
+    }
+
+    /// Servers heard recently, most recently seen first.
+    pub fn servers(&self) -> Vec<DiscoveredServer> {
+        let Ok(mut servers) = self.servers.lock() else {
+            return Vec::new();
+        };
+        let now = crate::utils::WasmTimer::now_ms();
+        servers.retain(|s| now - s.last_seen_ms < SERVER_TIMEOUT_MS);
+        let mut list = servers.clone();
+        list.sort_by(|a, b| b.last_seen_ms.total_cmp(&a.last_seen_ms));
+        list
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn listen(servers: &Mutex<Vec<DiscoveredServer>>) -> std::io::Result<()> {
+    use mcg_shared::{DISCOVERY_GROUP, DISCOVERY_PORT};
+    use std::net::{Ipv4Addr, UdpSocket};
+
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, DISCOVERY_PORT))?;
+    socket.join_multicast_v4(&DISCOVERY_GROUP, &Ipv4Addr::UNSPECIFIED)?;
+    let mut buf = [0u8; 1024];
+    loop {
+        let (len, from) = socket.recv_from(&mut buf)?;
+        let Ok(ad) = serde_json::from_slice::<ServerAd>(&buf[..len]) else {
+            continue;
+        };
+        let server = DiscoveredServer {
+            address: format!("{}:{}", from.ip(), ad.port),
+            ad,
+            last_seen_ms: crate::utils::WasmTimer::now_ms(),
+        };
+        let Ok(mut servers) = servers.lock() else {
+            return Ok(());
+        };
+        match servers.iter_mut().find(|s| s.address == server.address) {
+            Some(existing) => *existing = server,
+            None => servers.push(server),
+        }
+    }
+}
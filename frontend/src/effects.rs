@@ -1,15 +1,174 @@
-use crate::articles::{fetch_posts, Post};
+pub mod sound;
+
+use crate::articles::{fetch_article, fetch_article_list};
+use crate::i18n::{fetch_locale, Locale};
 use crate::store::{ArticlesLoading, ClientState};
+use mcg_shared::{
+    ArticleMeta, Backend2FrontendMsg, LeaderboardEntry, PairingInvite, PeerInfo, TableId,
+};
 use wasm_bindgen_futures::spawn_local;
 
+/// Fetch the article list from the preferred server.
 pub fn fetch_articles_effect(
     state: &mut ClientState,
-    on_done: impl FnOnce(Result<Vec<Post>, String>) + 'static,
+    on_done: impl FnOnce(Result<Vec<ArticleMeta>, String>) + 'static,
 ) {
     state.ui.articles = ArticlesLoading::Loading;
+    let server_address = state.profile.preferred_server.clone();
+
+    spawn_local(async move {
+        let result = fetch_article_list(&server_address).await;
+        on_done(result);
+    });
+}
+
+/// Fetch the markdown body of one article from the preferred server.
+pub fn fetch_article_effect(
+    state: &ClientState,
+    id: String,
+    on_done: impl FnOnce(Result<String, String>) + 'static,
+) {
+    let server_address = state.profile.preferred_server.clone();
+
+    spawn_local(async move {
+        let result = fetch_article(&server_address, &id).await;
+        on_done(result);
+    });
+}
+
+/// Fetch a locale the frontend does not have built in from the preferred server.
+pub fn fetch_locale_effect(
+    state: &ClientState,
+    lang: String,
+    on_done: impl FnOnce(Result<Locale, String>) + 'static,
+) {
+    let server_address = state.profile.preferred_server.clone();
 
     spawn_local(async move {
-        let result = fetch_posts().await;
+        let result = fetch_locale(&server_address, &lang).await;
         on_done(result);
     });
 }
+
+/// Fetch the all-time standings from the preferred server.
+pub fn fetch_leaderboard_effect(
+    state: &ClientState,
+    on_done: impl FnOnce(Result<Vec<LeaderboardEntry>, String>) + 'static,
+) {
+    let server_address = state.profile.preferred_server.clone();
+
+    spawn_local(async move {
+        let result = fetch_leaderboard(&server_address).await;
+        on_done(result);
+    });
+}
+
+async fn fetch_leaderboard(server_address: &str) -> Result<Vec<LeaderboardEntry>, String> {
+    let url = format!("http://{}/leaderboard", server_address);
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| format!("Failed to fetch leaderboard: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse JSON: {}", e))
+}
+
+/// Fetch the iroh nodes the preferred server knows of.
+pub fn fetch_iroh_peers_effect(
+    state: &ClientState,
+    on_done: impl FnOnce(Result<Vec<PeerInfo>, String>) + 'static,
+) {
+    let server_address = state.profile.preferred_server.clone();
+
+    spawn_local(async move {
+        let result = fetch_iroh_peers(&server_address).await;
+        on_done(result);
+    });
+}
+
+async fn fetch_iroh_peers(server_address: &str) -> Result<Vec<PeerInfo>, String> {
+    let url = format!("http://{}/iroh/peers", server_address);
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| format!("Failed to fetch iroh peers: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse JSON: {}", e))
+}
+
+/// Fetch the session hand history of a table from the server and offer it as a download.
+pub fn export_history_effect(
+    server_address: String,
+    table_id: TableId,
+    on_done: impl FnOnce(Result<(), String>) + 'static,
+) {
+    spawn_local(async move {
+        let result = fetch_history(&server_address, table_id)
+            .await
+            .and_then(|text| crate::utils::download_text_file("mcg-hand-history.txt", &text));
+        on_done(result);
+    });
+}
+
+async fn fetch_history(server_address: &str, table_id: TableId) -> Result<String, String> {
+    let url = format!("http://{}/history.txt?table={}", server_address, table_id);
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| format!("Failed to fetch hand history: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read hand history: {}", e))
+}
+
+/// Ask the server for an invite to a table, as shown on the pairing screen.
+pub fn fetch_pairing_invite_effect(
+    server_address: String,
+    table_id: TableId,
+    on_done: impl FnOnce(Result<PairingInvite, String>) + 'static,
+) {
+    spawn_local(async move {
+        let result = fetch_pairing_invite(&server_address, table_id).await;
+        on_done(result);
+    });
+}
+
+async fn fetch_pairing_invite(
+    server_address: &str,
+    table_id: TableId,
+) -> Result<PairingInvite, String> {
+    let url = format!("http://{}/pairing-code?table={}", server_address, table_id);
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| format!("Failed to fetch invite: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    match response.json().await {
+        Ok(Backend2FrontendMsg::PairingCode { invite_code, .. }) => {
+            PairingInvite::parse(&invite_code)
+                .ok_or_else(|| format!("Invalid invite code '{}'", invite_code))
+        }
+        Ok(other) => Err(format!("Unexpected response: {:?}", other)),
+        Err(e) => Err(format!("Failed to parse JSON: {}", e)),
+    }
+}
@@ -0,0 +1,233 @@
+//! Procedurally synthesized sound effects played through the Web Audio API.
+//! Native builds have no audio backend and stay silent.
+
+use mcg_shared::{ActionEvent, ActionKind, GameAction, GameStatePublic, PlayerPublic};
+
+/// Table events that have a sound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundEvent {
+    CardDealt,
+    ChipPlaced,
+    FoldMade,
+    WonHand,
+}
+
+/// Plays [`SoundEvent`]s and tracks which log entries were already heard.
+#[derive(Default)]
+pub struct SoundManager {
+    /// 0.0 mutes, 1.0 is full volume
+    volume: f32,
+    /// Length of the action log at the previous frame; `None` before the first state
+    seen_log_len: Option<usize>,
+    #[cfg(target_arch = "wasm32")]
+    context: Option<web_sys::AudioContext>,
+}
+
+impl SoundManager {
+    pub fn set_volume(&mut self, volume: f32) {
+        self.volume = volume.clamp(0.0, 1.0);
+    }
+
+    /// Play the sounds for log entries added since the last call. The log is
+    /// cleared on every new hand, so a shorter log counts as entirely new.
+    /// Entries present when the first state arrives are not replayed.
+    pub fn play_for_state(&mut self, state: Option<&GameStatePublic>, local_name: &str) {
+        let Some(state) = state else {
+            self.seen_log_len = None;
+            return;
+        };
+        let log = &state.action_log;
+        let new_entries = match self.seen_log_len {
+            None => &log[log.len()..],
+            Some(seen) if seen <= log.len() => &log[seen..],
+            Some(_) => &log[..],
+        };
+        self.seen_log_len = Some(log.len());
+        for event in sound_events_for(new_entries, &state.players, local_name) {
+            self.play(event);
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn play(&mut self, event: SoundEvent) {
+        if self.volume <= 0.0 {
+            return;
+        }
+        if let Err(e) = self.synthesize(event) {
+            tracing::warn!(?e, ?event, "failed to play sound");
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn play(&mut self, _event: SoundEvent) {}
+
+    #[cfg(target_arch = "wasm32")]
+    fn synthesize(&mut self, event: SoundEvent) -> Result<(), wasm_bindgen::JsValue> {
+        use web_sys::OscillatorType;
+
+        if self.context.is_none() {
+            self.context = Some(web_sys::AudioContext::new()?);
+        }
+        let Some(ctx) = self.context.as_ref() else {
+            return Ok(());
+        };
+        // Browsers start the context suspended until the user interacts with the page
+        let _ = ctx.resume();
+        let now = ctx.current_time();
+        let gain = 0.25 * self.volume;
+        match event {
+            SoundEvent::ChipPlaced => tone(
+                ctx,
+                now,
+                0.08,
+                (1800.0, 1200.0),
+                OscillatorType::Square,
+                gain,
+            ),
+            SoundEvent::CardDealt => tone(
+                ctx,
+                now,
+                0.2,
+                (600.0, 250.0),
+                OscillatorType::Triangle,
+                gain,
+            ),
+            SoundEvent::FoldMade => {
+                tone(ctx, now, 0.15, (300.0, 150.0), OscillatorType::Sine, gain)
+            }
+            SoundEvent::WonHand => {
+                // C major arpeggio, four notes over 500ms
+                for (i, hz) in [523.25, 659.25, 783.99, 1046.5].into_iter().enumerate() {
+                    let start = now + 0.125 * i as f64;
+                    tone(ctx, start, 0.125, (hz, hz), OscillatorType::Triangle, gain)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// One oscillator gliding between two frequencies with a decaying envelope.
+#[cfg(target_arch = "wasm32")]
+fn tone(
+    ctx: &web_sys::AudioContext,
+    start: f64,
+    duration: f64,
+    (from_hz, to_hz): (f32, f32),
+    kind: web_sys::OscillatorType,
+    gain: f32,
+) -> Result<(), wasm_bindgen::JsValue> {
+    let osc = ctx.create_oscillator()?;
+    let envelope = ctx.create_gain()?;
+    osc.set_type(kind);
+    osc.frequency().set_value_at_time(from_hz, start)?;
+    osc.frequency()
+        .exponential_ramp_to_value_at_time(to_hz, start + duration)?;
+    envelope.gain().set_value_at_time(gain, start)?;
+    envelope
+        .gain()
+        .exponential_ramp_to_value_at_time(0.001, start + duration)?;
+    osc.connect_with_audio_node(&envelope)?;
+    envelope.connect_with_audio_node(&ctx.destination())?;
+    osc.start_with_when(start)?;
+    osc.stop_with_when(start + duration)?;
+    Ok(())
+}
+
+/// Sounds for a batch of new log entries. Repeats are collapsed so dealing
+/// hole cards to a full table plays a single deal sound. Winning only plays
+/// when the player named `local_name` is among the winners.
+pub fn sound_events_for(
+    entries: &[ActionEvent],
+    players: &[PlayerPublic],
+    local_name: &str,
+) -> Vec<SoundEvent> {
+    let mut events = Vec::new();
+    for entry in entries {
+        let event = match entry {
+            ActionEvent::GameAction(GameAction::DealtHole { .. })
+            | ActionEvent::GameAction(GameAction::DealtCommunity { .. }) => SoundEvent::CardDealt,
+            ActionEvent::GameAction(GameAction::PotAwarded { winners, .. }) => {
+                let local_won = players
+                    .iter()
+                    .any(|p| p.name == local_name && winners.contains(&p.id));
+                if !local_won {
+                    continue;
+                }
+                SoundEvent::WonHand
+            }
+            ActionEvent::PlayerAction { action, .. } => match action {
+                ActionKind::Fold => SoundEvent::FoldMade,
+                ActionKind::Check => continue,
+                ActionKind::Call(_)
+                | ActionKind::Bet(_)
+                | ActionKind::Raise { .. }
+                | ActionKind::AllIn { .. }
+                | ActionKind::PostBlind { .. } => SoundEvent::ChipPlaced,
+            },
+            ActionEvent::GameAction(_) => continue,
+        };
+        if !events.contains(&event) {
+            events.push(event);
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::{PlayerId, Stage};
+
+    fn player(id: usize, name: &str) -> PlayerPublic {
+        PlayerPublic {
+            id: PlayerId(id),
+            name: name.to_string(),
+            stack: 1000,
+            cards: None,
+            has_folded: false,
+            all_in: false,
+            bet_this_round: 0,
+            avatar_color: None,
+            bounty: None,
+            seat_number: id as u8,
+            must_post_blind: false,
+        }
+    }
+
+    #[test]
+    fn log_entries_map_to_deduplicated_sounds() {
+        let players = [player(0, "Alice"), player(1, "Bob")];
+        let entries = [
+            ActionEvent::GameAction(GameAction::DealtHole {
+                player_id: PlayerId(0),
+            }),
+            ActionEvent::GameAction(GameAction::DealtHole {
+                player_id: PlayerId(1),
+            }),
+            ActionEvent::PlayerAction {
+                player_id: PlayerId(0),
+                action: ActionKind::Call(10),
+            },
+            ActionEvent::GameAction(GameAction::StageChanged(Stage::Flop)),
+            ActionEvent::PlayerAction {
+                player_id: PlayerId(1),
+                action: ActionKind::Fold,
+            },
+            ActionEvent::GameAction(GameAction::PotAwarded {
+                winners: vec![PlayerId(0)],
+                amount: 20,
+            }),
+        ];
+        assert_eq!(
+            sound_events_for(&entries, &players, "Alice"),
+            vec![
+                SoundEvent::CardDealt,
+                SoundEvent::ChipPlaced,
+                SoundEvent::FoldMade,
+                SoundEvent::WonHand
+            ]
+        );
+        assert!(!sound_events_for(&entries, &players, "Bob").contains(&SoundEvent::WonHand));
+    }
+}
@@ -0,0 +1,100 @@
+//! Hand equity estimator exported to JavaScript, so betting aids, companion
+//! apps or browser extensions can use it without starting the egui app.
+
+use mcg_shared::Card;
+use wasm_bindgen::prelude::*;
+
+use crate::game::hand_strength::estimate_equity;
+
+/// Returned by [`evaluate_hand_equity`] for invalid input
+pub const INVALID_EQUITY: f32 = -1.0;
+/// Most opponents an estimate can be asked for
+pub const MAX_OPPONENTS: u32 = 8;
+
+/// Cards by index `0..52`, or `None` if an index is out of range or a card
+/// appears twice.
+fn parse_cards(hole_cards: &[u8], board_cards: &[u8]) -> Option<([Card; 2], Vec<Card>)> {
+    let all: Vec<u8> = hole_cards.iter().chain(board_cards).copied().collect();
+    let distinct = all.iter().enumerate().all(|(i, c)| !all[..i].contains(c));
+    if !distinct || all.iter().any(|&c| c >= 52) {
+        return None;
+    }
+    let hole = [
+        Card::from(*hole_cards.first()?),
+        Card::from(*hole_cards.get(1)?),
+    ];
+    Some((hole, board_cards.iter().copied().map(Card::from).collect()))
+}
+
+/// Share of the pot `hole_cards` win on average against `num_opponents`
+/// random hands, estimated from `iterations` random deals.
+///
+/// Cards are indices `0..52` as used by `mcg_shared::Card`. Returns `-1.0`
+/// unless there are exactly 2 hole cards, 0 to 5 board cards, 1 to 8
+/// opponents, at least one iteration and no card twice.
+#[wasm_bindgen]
+pub fn evaluate_hand_equity(
+    hole_cards: &[u8],
+    board_cards: &[u8],
+    num_opponents: u32,
+    iterations: u32,
+) -> f32 {
+    if hole_cards.len() != 2
+        || board_cards.len() > 5
+        || !(1..=MAX_OPPONENTS).contains(&num_opponents)
+        || iterations == 0
+    {
+        return INVALID_EQUITY;
+    }
+    let Some((hole, board)) = parse_cards(hole_cards, board_cards) else {
+        return INVALID_EQUITY;
+    };
+    estimate_equity(
+        hole,
+        &board,
+        num_opponents as usize,
+        iterations,
+        &mut rand::rng(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::{CardRank, CardSuit};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn card(rank: CardRank, suit: CardSuit) -> u8 {
+        Card::new(rank, suit).0
+    }
+
+    /// Run with `wasm-pack test --node frontend`; also runs as a native test.
+    #[wasm_bindgen_test(unsupported = test)]
+    fn pocket_aces_win_about_85_percent_heads_up() {
+        let aces = [
+            card(CardRank::Ace, CardSuit::Spades),
+            card(CardRank::Ace, CardSuit::Hearts),
+        ];
+        let equity = evaluate_hand_equity(&aces, &[], 1, 20_000);
+        assert!((0.82..0.88).contains(&equity), "equity {}", equity);
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn invalid_input_returns_minus_one() {
+        let hole = [0, 13];
+        assert_eq!(
+            evaluate_hand_equity(&hole[..1], &[], 1, 100),
+            INVALID_EQUITY
+        );
+        assert_eq!(
+            evaluate_hand_equity(&hole, &[1, 2, 3, 4, 5, 6], 1, 100),
+            INVALID_EQUITY
+        );
+        assert_eq!(evaluate_hand_equity(&hole, &[], 0, 100), INVALID_EQUITY);
+        assert_eq!(evaluate_hand_equity(&hole, &[], 9, 100), INVALID_EQUITY);
+        assert_eq!(evaluate_hand_equity(&hole, &[], 1, 0), INVALID_EQUITY);
+        assert_eq!(evaluate_hand_equity(&hole, &[52], 1, 100), INVALID_EQUITY);
+        assert_eq!(evaluate_hand_equity(&hole, &[13], 1, 100), INVALID_EQUITY);
+        assert!(evaluate_hand_equity(&hole, &[1, 2, 3], 8, 100) >= 0.0);
+    }
+}
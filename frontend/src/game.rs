@@ -1,5 +1,9 @@
 pub mod card;
+#[cfg(debug_assertions)]
+pub mod debug_panel;
 pub mod field;
+pub mod hand_strength;
+pub mod notifications;
 pub mod screens;
 pub mod theme;
 pub mod websocket;
@@ -9,7 +13,10 @@ use crate::{
     store::ClientState,
 };
 use egui::Context;
+use notifications::{Notification, NotificationLevel, Notifications};
 use screens::{AppInterface, MainMenu, ScreenWidget};
+use std::cell::RefCell;
+use std::rc::Rc;
 use theme::*;
 
 /// Events that can be sent between screens
@@ -18,6 +25,12 @@ pub enum AppEvent {
     ChangeRoute(String),
     StartGame(screens::GameState<screens::DirectoryCardType>),
     ExitGame,
+    /// Show a toast in the bottom-left corner for `duration_ms`
+    ShowNotification {
+        message: String,
+        level: NotificationLevel,
+        duration_ms: u64,
+    },
 }
 
 /// Global settings for the application
@@ -25,13 +38,44 @@ pub enum AppEvent {
 pub struct Settings {
     pub dpi: f32,
     pub applied_dpi: f32,
+    /// Scale fitting the canvas size, see `App::fit_canvas`. The applied
+    /// scale follows it until the player picks another one.
+    pub auto_dpi: f32,
     pub dark_mode: bool,
+    /// Sound effect volume, 0.0 mutes
+    pub volume: f32,
+}
+
+/// Seconds a screen transition takes
+const TRANSITION_SECS: f32 = 0.3;
+
+/// How the next screen enters the view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionDirection {
+    /// The next screen slides in from the right
+    SlideLeft,
+    /// The next screen slides in from the left
+    SlideRight,
+    FadeIn,
+}
+
+/// Animation between two screens. The route only changes once `progress`
+/// reaches 1.0.
+#[derive(Debug, Clone)]
+pub struct TransitionState {
+    pub from_path: String,
+    pub to_path: String,
+    /// 0.0 at the start, 1.0 once finished
+    pub progress: f32,
+    pub direction: TransitionDirection,
 }
 
 /// Application UI/Screen manager
 pub struct App {
     // current route path ("/", "/game-setup", etc.)
     current_screen_path: String,
+    // screen change in progress, if any
+    transition: Option<TransitionState>,
     // lazily-created screens by path
     screens: std::collections::HashMap<String, Box<dyn ScreenWidget>>,
     // single shared screen registry
@@ -44,6 +88,19 @@ pub struct App {
 
     // Router for URL handling
     router: Option<Router>,
+
+    sound: crate::effects::sound::SoundManager,
+    achievement_toasts: screens::AchievementToasts,
+    notifications: Notifications,
+    // language code typed into the settings, and its fetch in flight
+    locale_input: String,
+    #[allow(clippy::type_complexity)]
+    pending_locale: Rc<RefCell<Option<Result<crate::i18n::Locale, String>>>>,
+
+    #[cfg(debug_assertions)]
+    debug_panel: debug_panel::DebugPanel,
+    // context of the last frame, to repaint after calls from JavaScript
+    egui_ctx: Option<Context>,
 }
 
 impl Default for App {
@@ -62,53 +119,135 @@ impl App {
         );
         crate::hardcoded_cards::set_deck_by_theme(&mut game_setup.card_config, "alt_cards");
 
-        let router = Router::new().ok();
+        let mut router = Router::new().ok();
 
-        let current_path = router
-            .as_ref()
-            .map(|r| r.current_path().to_string())
-            .unwrap_or_else(|| "/".to_string());
+        // The embedding page may deep-link to a screen other than the URL's
+        let current_path = crate::get_initial_screen();
+        if let Some(ref mut router) = router {
+            let _ = router.navigate_to_path(&current_path);
+        }
 
         let app_state = ClientState::new();
+        let dpi = crate::calculate_dpi_scale();
         Self {
             current_screen_path: current_path,
+            transition: None,
             screens: std::collections::HashMap::new(),
             screen_registry: screens::ScreenRegistry::new(),
             settings_open: false,
             pending_settings: Settings {
-                dpi: crate::calculate_dpi_scale(),
-                applied_dpi: crate::calculate_dpi_scale(),
+                dpi,
+                applied_dpi: dpi,
+                auto_dpi: dpi,
                 dark_mode: true,
+                volume: 0.5,
             },
             app_state,
             router,
+            sound: crate::effects::sound::SoundManager::default(),
+            achievement_toasts: screens::AchievementToasts::default(),
+            notifications: Notifications::default(),
+            locale_input: String::new(),
+            pending_locale: Rc::default(),
+            #[cfg(debug_assertions)]
+            debug_panel: debug_panel::DebugPanel::default(),
+            egui_ctx: None,
+        }
+    }
+
+    /// Switch to the UI scale `scale` of a resized canvas, unless the player
+    /// chose a scale of their own in the settings.
+    pub fn fit_canvas(&mut self, scale: f32) {
+        let settings = &mut self.pending_settings;
+        let follows_canvas = settings.applied_dpi == settings.auto_dpi;
+        settings.auto_dpi = scale;
+        if !follows_canvas {
+            return;
+        }
+        // A scale picked in the open settings but not applied yet stays
+        if settings.dpi == settings.applied_dpi {
+            settings.dpi = scale;
+        }
+        settings.applied_dpi = scale;
+        if let Some(ctx) = &self.egui_ctx {
+            ctx.set_pixels_per_point(scale);
+            ctx.request_repaint();
         }
     }
 
     /// Change route by path and update URL
     fn change_route(&mut self, path: &str) {
         let new_path = self.screen_registry.path_from_path(path).unwrap_or("/");
-        if self.current_screen_path != new_path {
-            self.current_screen_path = new_path.to_string();
+        if self.start_transition(new_path) {
             if let Some(ref mut router) = self.router {
                 let _ = router.navigate_to_path(new_path);
             }
         }
     }
 
+    /// Start animating towards `to_path`. A transition that is still running
+    /// is finished first. Returns false if `to_path` is already the target.
+    fn start_transition(&mut self, to_path: &str) -> bool {
+        let target = self
+            .transition
+            .as_ref()
+            .map_or(&self.current_screen_path, |t| &t.to_path);
+        if target == to_path {
+            return false;
+        }
+        if let Some(t) = self.transition.take() {
+            self.current_screen_path = t.to_path;
+        }
+
+        let from = self
+            .screen_registry
+            .menu_position(&self.current_screen_path);
+        let to = self.screen_registry.menu_position(to_path);
+        let direction = match (from, to) {
+            (Some(from), Some(to)) if to > from => TransitionDirection::SlideLeft,
+            (Some(_), Some(_)) => TransitionDirection::SlideRight,
+            _ => TransitionDirection::FadeIn,
+        };
+        self.transition = Some(TransitionState {
+            from_path: self.current_screen_path.clone(),
+            to_path: to_path.to_string(),
+            progress: 0.0,
+            direction,
+        });
+        true
+    }
+
+    /// Advance the running transition by this frame's time and commit the
+    /// screen change once it has finished.
+    fn advance_transition(&mut self, ctx: &Context) {
+        let Some(t) = self.transition.as_mut() else {
+            return;
+        };
+        t.progress += ctx.input(|i| i.unstable_dt).min(0.05) / TRANSITION_SECS;
+        if t.progress >= 1.0 {
+            self.current_screen_path = std::mem::take(&mut t.to_path);
+            self.transition = None;
+        }
+        ctx.request_repaint();
+    }
+
+    /// Create the screen for `path` if it has not been opened yet.
+    fn ensure_screen(&mut self, path: &str) {
+        if !self.screens.contains_key(path) {
+            if let Some(factory) = self.screen_registry.factory_by_path(path) {
+                self.screens.insert(path.to_string(), factory.create());
+            }
+        }
+    }
+
     /// Check for URL changes and update current path
     fn check_url_changes(&mut self) {
-        if let Some(ref mut router) = self.router {
-            if let Ok(changed) = router.check_for_url_changes() {
-                if changed {
-                    if let Some(new_path) =
-                        self.screen_registry.path_from_path(router.current_path())
-                    {
-                        if new_path != self.current_screen_path {
-                            self.current_screen_path = new_path.to_string();
-                        }
-                    }
-                }
+        let Some(ref mut router) = self.router else {
+            return;
+        };
+        if let Ok(true) = router.check_for_url_changes() {
+            if let Some(new_path) = self.screen_registry.path_from_path(router.current_path()) {
+                self.start_transition(new_path);
             }
         }
     }
@@ -116,6 +255,13 @@ impl App {
     pub fn current_path(&self) -> &str {
         &self.current_screen_path
     }
+
+    /// Path being navigated to, or the current path when no transition runs.
+    fn target_path(&self) -> &str {
+        self.transition
+            .as_ref()
+            .map_or(&self.current_screen_path, |t| &t.to_path)
+    }
 }
 
 impl App {
@@ -151,7 +297,7 @@ impl App {
                         egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
                         |ui| {
                             if let Some(meta) =
-                                self.screen_registry.meta_by_path(&self.current_screen_path)
+                                self.screen_registry.meta_by_path(self.target_path())
                             {
                                 ui.strong(meta.display_name);
                             }
@@ -191,12 +337,33 @@ impl App {
                             .text("UI scale (DPI)"),
                     );
                     if ui.button("Reset to default").clicked() {
-                        self.pending_settings.dpi = crate::calculate_dpi_scale();
+                        self.pending_settings.dpi = self.pending_settings.auto_dpi;
                     }
                     ui.checkbox(&mut self.pending_settings.dark_mode, "Dark mode");
+                    ui.add(
+                        egui::Slider::new(&mut self.pending_settings.volume, 0.0..=1.0)
+                            .text("Sound volume"),
+                    );
+                    ui.add_space(MARGIN_SM);
+                    ui.separator();
+                    self.render_theme_settings(ui);
+                    ui.add_space(MARGIN_SM);
+                    self.render_card_back_settings(ui);
+                    ui.add_space(MARGIN_SM);
+                    self.render_language_settings(ui);
+                    ui.add_space(MARGIN_SM);
+                    ui.checkbox(
+                        &mut self.app_state.settings.show_hand_strength,
+                        "Show hand strength meter",
+                    )
+                    .on_hover_text("Rate your own hand while it is played; makes the game easier");
+                    ui.add_space(MARGIN_SM);
+                    ui.separator();
+                    Self::render_profile_settings(ui, &mut self.app_state.profile);
                     ui.add_space(MARGIN_SM);
                     ui.horizontal(|ui| {
                         if ui.button("Apply").clicked() {
+                            self.save_profile();
                             self.pending_settings.applied_dpi = self.pending_settings.dpi;
                             ctx.set_pixels_per_point(self.pending_settings.applied_dpi);
                             if self.pending_settings.dark_mode {
@@ -206,6 +373,7 @@ impl App {
                             }
                         }
                         if ui.button("OK").clicked() {
+                            self.save_profile();
                             self.pending_settings.applied_dpi = self.pending_settings.dpi;
                             ctx.set_pixels_per_point(self.pending_settings.applied_dpi);
                             if self.pending_settings.dark_mode {
@@ -217,22 +385,234 @@ impl App {
                         }
                         if ui.button("Cancel").clicked() {
                             self.pending_settings.dpi = self.pending_settings.applied_dpi;
+                            self.app_state.profile =
+                                crate::store::PlayerProfile::load().unwrap_or_default();
                             self.settings_open = false;
                         }
                     });
                 });
             if !open {
                 self.pending_settings.dpi = self.pending_settings.applied_dpi;
+                self.app_state.profile = crate::store::PlayerProfile::load().unwrap_or_default();
                 self.settings_open = false;
             }
         }
     }
+
+    /// Card theme selector with a preview card per theme. Selecting a theme
+    /// applies it right away.
+    fn render_theme_settings(&mut self, ui: &mut egui::Ui) {
+        ui.label(egui::RichText::new("Card theme").strong());
+        let origin = crate::game::card::get_origin();
+        let mut picked = None;
+        ui.horizontal(|ui| {
+            for theme in crate::hardcoded_cards::ThemeRegistry::list_themes() {
+                let selected = self.app_state.settings.card_theme == theme.id;
+                ui.vertical(|ui| {
+                    let preview =
+                        egui::Image::new(format!("{}/{}", origin, theme.preview_card_path))
+                            .max_height(72.0)
+                            .show_loading_spinner(true);
+                    let clicked = ui
+                        .add(egui::Button::image(preview).selected(selected))
+                        .on_hover_text(theme.description)
+                        .clicked();
+                    if ui.selectable_label(selected, theme.display_name).clicked() || clicked {
+                        picked = Some(theme.id);
+                    }
+                });
+            }
+        });
+        if let Some(theme) = picked {
+            self.app_state.settings.card_theme = theme.to_string();
+            for screen in self.screens.values_mut() {
+                if let Some(setup) = screen.downcast_mut::<screens::GameSetupScreen>() {
+                    setup.apply_theme(theme);
+                }
+            }
+            ui.ctx().request_repaint();
+        }
+    }
+
+    /// Preview and pick the design of face-down cards.
+    fn render_card_back_settings(&mut self, ui: &mut egui::Ui) {
+        use crate::store::CardBackDesign;
+        use screens::poker::ui_components::CardRenderer;
+        ui.label(egui::RichText::new("Card back").strong());
+        let settings = &mut self.app_state.settings;
+        let designs = [
+            (CardBackDesign::Default, "Plain"),
+            (CardBackDesign::Striped, "Striped"),
+            (CardBackDesign::Dots, "Dots"),
+            (
+                CardBackDesign::Custom(settings.card_theme.clone()),
+                "From theme",
+            ),
+        ];
+        ui.horizontal(|ui| {
+            for (design, label) in designs {
+                ui.vertical(|ui| {
+                    CardRenderer::render_back(ui, &design);
+                    let selected = settings.card_back == design;
+                    if ui.selectable_label(selected, label).clicked() {
+                        settings.card_back = design;
+                    }
+                });
+            }
+        });
+    }
+
+    /// Pick one of the built-in languages, or load another from the server.
+    fn render_language_settings(&mut self, ui: &mut egui::Ui) {
+        use crate::i18n::{Locale, BUILT_IN};
+        ui.label(egui::RichText::new("Language").strong());
+        let current = self.app_state.settings.locale.lang.clone();
+        let current_name = BUILT_IN
+            .iter()
+            .find(|(lang, _)| *lang == current)
+            .map_or(current.as_str(), |(_, name)| name);
+        let mut picked = None;
+        egui::ComboBox::from_id_salt("language_select")
+            .selected_text(current_name)
+            .show_ui(ui, |ui| {
+                for (lang, name) in BUILT_IN {
+                    if ui.selectable_label(current == lang, name).clicked() {
+                        picked = Some(lang);
+                    }
+                }
+            });
+        if let Some(locale) = picked.and_then(Locale::built_in) {
+            self.app_state.settings.locale = locale;
+        }
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.locale_input)
+                    .hint_text("Language code, e.g. fr")
+                    .desired_width(140.0),
+            );
+            let lang = self.locale_input.trim().to_string();
+            if ui
+                .add_enabled(!lang.is_empty(), egui::Button::new("Load"))
+                .on_hover_text("Load this language from the server")
+                .clicked()
+            {
+                let slot = self.pending_locale.clone();
+                crate::effects::fetch_locale_effect(&self.app_state, lang, move |result| {
+                    *slot.borrow_mut() = Some(result);
+                });
+            }
+        });
+    }
+
+    /// Switch to a locale loaded from the server once it arrives.
+    fn apply_fetched_locale(&mut self) {
+        let Some(result) = self.pending_locale.borrow_mut().take() else {
+            return;
+        };
+        match result {
+            Ok(locale) => {
+                let message = format!("Switched language to {}", locale.lang);
+                self.app_state.settings.locale = locale;
+                self.app_state.notify(NotificationLevel::Success, message);
+            }
+            Err(e) => self.app_state.notify(NotificationLevel::Error, e),
+        }
+    }
+
+    /// Player name and a grid of preset avatar colors.
+    fn render_profile_settings(ui: &mut egui::Ui, profile: &mut crate::store::PlayerProfile) {
+        ui.label(egui::RichText::new("Profile").strong());
+        ui.horizontal(|ui| {
+            ui.label("Name:");
+            ui.text_edit_singleline(&mut profile.name);
+        });
+        ui.label("Avatar color:");
+        egui::Grid::new("avatar_color_grid")
+            .spacing([4.0, 4.0])
+            .show(ui, |ui| {
+                for (i, color) in crate::store::AVATAR_COLORS.iter().enumerate() {
+                    let [r, g, b] = *color;
+                    let selected = profile.avatar_color == *color;
+                    let button = egui::Button::new("")
+                        .fill(egui::Color32::from_rgb(r, g, b))
+                        .min_size(egui::vec2(24.0, 24.0))
+                        .stroke(if selected {
+                            egui::Stroke::new(2.0, ui.visuals().strong_text_color())
+                        } else {
+                            egui::Stroke::NONE
+                        });
+                    if ui.add(button).clicked() {
+                        profile.avatar_color = *color;
+                    }
+                    if i % 8 == 7 {
+                        ui.end_row();
+                    }
+                }
+            });
+    }
+
+    fn save_profile(&mut self) {
+        self.app_state.settings.name = self.app_state.profile.name.clone();
+        if let Err(e) = self.app_state.profile.save() {
+            self.app_state.notify(NotificationLevel::Error, e);
+        }
+    }
+
+    /// Warn that the server speaks another major protocol version. The game
+    /// stays usable, the player may try their luck after dismissing it.
+    fn show_version_warning(&mut self, ctx: &Context) {
+        let Some(server_version) = &self.app_state.connection.incompatible_server else {
+            return;
+        };
+        let mut open = true;
+        egui::Window::new("Incompatible server")
+            .open(&mut open)
+            .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 48.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Server version {} is incompatible with client version {}. Please update.",
+                        server_version,
+                        crate::store::CLIENT_VERSION
+                    ))
+                    .strong()
+                    .color(ui.visuals().warn_fg_color),
+                );
+            });
+        if !open {
+            self.app_state.connection.incompatible_server = None;
+        }
+    }
+
+    /// Draw the screen for `path` into `rect`, clipped to the parent `ui`.
+    fn show_screen(
+        screens: &mut std::collections::HashMap<String, Box<dyn ScreenWidget>>,
+        path: &str,
+        rect: egui::Rect,
+        app_interface: &mut AppInterface,
+        ui: &mut egui::Ui,
+        frame: &mut eframe::Frame,
+    ) {
+        let clip = ui.clip_rect();
+        let mut child = ui.new_child(egui::UiBuilder::new().max_rect(rect).id_salt(path));
+        child.set_clip_rect(clip.intersect(rect));
+        if let Some(screen) = screens.get_mut(path) {
+            screen.ui(app_interface, &mut child, frame);
+        } else {
+            // fallback: main menu
+            let mut mm = MainMenu::default();
+            mm.ui(app_interface, &mut child, frame);
+        }
+    }
 }
 
 impl eframe::App for App {
     fn update(&mut self, ctx: &Context, frame: &mut eframe::Frame) {
         // Process any pending messages from WebSocket callbacks
         self.app_state.dispatch_pending_messages();
+        self.egui_ctx.get_or_insert_with(|| ctx.clone());
 
         ctx.set_pixels_per_point(self.pending_settings.applied_dpi);
         if self.pending_settings.dark_mode {
@@ -241,39 +621,109 @@ impl eframe::App for App {
             ctx.set_visuals(egui::Visuals::light());
         }
         self.check_url_changes();
+        self.apply_fetched_locale();
+        if crate::i18n::current_lang() != self.app_state.settings.locale.lang {
+            crate::i18n::set_locale(self.app_state.settings.locale.clone());
+        }
+
+        self.sound.set_volume(self.pending_settings.volume);
+        self.sound.play_for_state(
+            self.app_state.session.game_state.as_ref(),
+            &self.app_state.profile.name,
+        );
 
         let mut events = Vec::new();
 
+        self.advance_transition(ctx);
+
         // show top bar unless root
-        if self.current_screen_path != "/" {
+        if self.target_path() != "/" {
             self.render_top_bar(ctx, &mut events);
         }
 
+        // Ensure the screens on display exist
+        let current_path = self.current_screen_path.clone();
+        self.ensure_screen(&current_path);
+        if let Some(t) = self.transition.clone() {
+            self.ensure_screen(&t.to_path);
+        }
+
         let mut app_interface = AppInterface {
             events: &mut events,
             app_state: &mut self.app_state,
         };
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            // Ensure screen exists
-            if !self.screens.contains_key(&self.current_screen_path) {
-                if let Some(factory) = self
-                    .screen_registry
-                    .factory_by_path(&self.current_screen_path)
-                {
-                    let boxed = factory();
-                    self.screens.insert(self.current_screen_path.clone(), boxed);
+            let rect = ui.max_rect();
+            let Some(t) = self.transition.as_ref() else {
+                Self::show_screen(
+                    &mut self.screens,
+                    &current_path,
+                    rect,
+                    &mut app_interface,
+                    ui,
+                    frame,
+                );
+                return;
+            };
+            // ease out so the new screen settles gently
+            let eased = 1.0 - (1.0 - t.progress.clamp(0.0, 1.0)).powi(2);
+            match t.direction {
+                TransitionDirection::SlideLeft | TransitionDirection::SlideRight => {
+                    let sign = if t.direction == TransitionDirection::SlideLeft {
+                        -1.0
+                    } else {
+                        1.0
+                    };
+                    let from_rect = rect.translate(egui::vec2(sign * rect.width() * eased, 0.0));
+                    let to_rect =
+                        rect.translate(egui::vec2(sign * rect.width() * (eased - 1.0), 0.0));
+                    ui.scope(|ui| {
+                        ui.disable();
+                        Self::show_screen(
+                            &mut self.screens,
+                            &t.from_path,
+                            from_rect,
+                            &mut app_interface,
+                            ui,
+                            frame,
+                        );
+                    });
+                    Self::show_screen(
+                        &mut self.screens,
+                        &t.to_path,
+                        to_rect,
+                        &mut app_interface,
+                        ui,
+                        frame,
+                    );
+                }
+                TransitionDirection::FadeIn => {
+                    Self::show_screen(
+                        &mut self.screens,
+                        &t.to_path,
+                        rect,
+                        &mut app_interface,
+                        ui,
+                        frame,
+                    );
+                    let veil = ui.visuals().panel_fill.gamma_multiply(1.0 - eased);
+                    ui.painter().rect_filled(rect, 0.0, veil);
                 }
-            }
-            if let Some(screen) = self.screens.get_mut(&self.current_screen_path) {
-                screen.ui(&mut app_interface, ui, frame);
-            } else {
-                // fallback: main menu
-                let mut mm = MainMenu::default();
-                mm.ui(&mut app_interface, ui, frame);
             }
         });
         let events = std::mem::take(app_interface.events);
+        self.achievement_toasts
+            .show(ctx, &mut self.app_state.achievements);
+
+        #[cfg(debug_assertions)]
+        {
+            self.debug_panel.record_events(&events);
+            self.debug_panel.handle_toggle(ctx);
+            self.debug_panel
+                .show(ctx, &self.current_screen_path, &self.app_state);
+        }
+
         for event in events {
             match event {
                 AppEvent::ChangeRoute(path) => {
@@ -282,7 +732,7 @@ impl eframe::App for App {
                 AppEvent::StartGame(config) => {
                     if !self.screens.contains_key("/game") {
                         if let Some(factory) = self.screen_registry.factory_by_path("/game") {
-                            let boxed = factory();
+                            let boxed = factory.create();
                             self.screens.insert("/game".to_string(), boxed);
                         }
                     }
@@ -296,11 +746,33 @@ impl eframe::App for App {
                 AppEvent::ExitGame => {
                     self.change_route("/");
                 }
+                AppEvent::ShowNotification {
+                    message,
+                    level,
+                    duration_ms,
+                } => {
+                    self.notifications.push(Notification {
+                        duration_ms,
+                        ..Notification::new(level, message)
+                    });
+                }
             }
         }
+        // Notifications raised while handling server messages
+        for notification in self.app_state.ui.notifications.drain(..) {
+            self.notifications.push(notification);
+        }
+        self.notifications.show(ctx);
+        self.show_version_warning(ctx);
 
         // Request continuous repaints for real-time updates (WebSocket messages, animations, etc.)
         // This is the standard approach for egui applications that need real-time updates
         ctx.request_repaint();
     }
+
+    /// Lets `resize_canvas` reach the app through the `WebRunner`
+    #[cfg(target_arch = "wasm32")]
+    fn as_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
+        Some(&mut *self)
+    }
 }
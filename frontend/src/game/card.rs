@@ -50,7 +50,7 @@ pub enum SimpleCard {
     Masked(Option<usize>),
 }
 
-fn get_origin() -> String {
+pub(crate) fn get_origin() -> String {
     let window = web_sys::window().expect("should have a window in this context");
     let location = window.location();
     location
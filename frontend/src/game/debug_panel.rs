@@ -0,0 +1,92 @@
+//! Development-only overlay with internal app state, toggled with Ctrl+D.
+//! The whole module is compiled out of release builds.
+
+use std::collections::VecDeque;
+
+use egui::Context;
+
+use super::AppEvent;
+use crate::store::ClientState;
+
+/// Number of queued events kept for display
+const EVENT_HISTORY: usize = 10;
+
+#[derive(Default)]
+pub struct DebugPanel {
+    open: bool,
+    recent_events: VecDeque<AppEvent>,
+}
+
+impl DebugPanel {
+    /// Flip visibility when Ctrl+D is pressed this frame.
+    pub fn handle_toggle(&mut self, ctx: &Context) {
+        if ctx.input(|i| i.key_pressed(egui::Key::D) && i.modifiers.ctrl) {
+            self.open = !self.open;
+        }
+    }
+
+    /// Remember the events queued this frame, keeping only the most recent ones.
+    pub fn record_events(&mut self, events: &[AppEvent]) {
+        for event in events {
+            if self.recent_events.len() == EVENT_HISTORY {
+                self.recent_events.pop_front();
+            }
+            self.recent_events.push_back(event.clone());
+        }
+    }
+
+    pub fn show(&mut self, ctx: &Context, screen_path: &str, app_state: &ClientState) {
+        if !self.open {
+            return;
+        }
+        let frame_ms = ctx.input(|i| i.unstable_dt) * 1000.0;
+        egui::Window::new("Debug")
+            .open(&mut self.open)
+            .collapsible(true)
+            .default_pos([12.0, 64.0])
+            .show(ctx, |ui| {
+                egui::Grid::new("debug_panel_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Screen:");
+                        ui.monospace(screen_path);
+                        ui.end_row();
+                        ui.label("Connection:");
+                        ui.monospace(format!("{:?}", app_state.connection.connection_status));
+                        ui.end_row();
+                        ui.label("WebSocket:");
+                        ui.monospace(app_state.connection_pool.ready_state_name());
+                        ui.end_row();
+                        ui.label("Frame time:");
+                        ui.monospace(format!("{:.1} ms", frame_ms));
+                        ui.end_row();
+                        ui.label("Pixels per point:");
+                        ui.monospace(format!("{:.2}", ctx.pixels_per_point()));
+                        ui.end_row();
+                    });
+                ui.separator();
+                egui::CollapsingHeader::new(format!(
+                    "Recent events ({})",
+                    self.recent_events.len()
+                ))
+                .default_open(true)
+                .show(ui, |ui| {
+                    for event in self.recent_events.iter().rev() {
+                        ui.monospace(event_summary(event));
+                    }
+                });
+            });
+    }
+}
+
+/// One-line description of an event; game states are too large to print whole.
+fn event_summary(event: &AppEvent) -> String {
+    match event {
+        AppEvent::ChangeRoute(path) => format!("ChangeRoute({})", path),
+        AppEvent::StartGame(_) => "StartGame(..)".to_string(),
+        AppEvent::ExitGame => "ExitGame".to_string(),
+        AppEvent::ShowNotification { level, message, .. } => {
+            format!("ShowNotification({:?}: {})", level, message)
+        }
+    }
+}
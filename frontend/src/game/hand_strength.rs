@@ -0,0 +1,225 @@
+//! Rough strength of the player's own hand: the Chen formula before the flop,
+//! a Monte Carlo equity estimate against random hands afterwards.
+
+use mcg_shared::evaluation::simulate_equity;
+use mcg_shared::{Card, CardRank};
+use rand::Rng;
+
+/// Chen score of the best starting hand, pocket aces
+pub const MAX_CHEN_SCORE: f32 = 20.0;
+
+/// Coarse rating shown next to the strength meter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StrengthLabel {
+    Weak,
+    Fair,
+    Good,
+    Premium,
+}
+
+impl StrengthLabel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StrengthLabel::Weak => "Weak",
+            StrengthLabel::Fair => "Fair",
+            StrengthLabel::Good => "Good",
+            StrengthLabel::Premium => "Premium",
+        }
+    }
+
+    /// Rating of a starting hand by its Chen score.
+    pub fn from_chen(score: f32) -> Self {
+        match score {
+            s if s >= 10.0 => StrengthLabel::Premium,
+            s if s >= 8.0 => StrengthLabel::Good,
+            s if s >= 6.0 => StrengthLabel::Fair,
+            _ => StrengthLabel::Weak,
+        }
+    }
+
+    /// Rating of an equity in `0.0..=1.0` compared to the fair share of a
+    /// pot contested by `opponents` other players.
+    pub fn from_equity(equity: f32, opponents: usize) -> Self {
+        let share = equity * (opponents + 1) as f32;
+        match share {
+            s if s >= 1.7 => StrengthLabel::Premium,
+            s if s >= 1.2 => StrengthLabel::Good,
+            s if s >= 0.8 => StrengthLabel::Fair,
+            _ => StrengthLabel::Weak,
+        }
+    }
+}
+
+/// Points of the higher card in the Chen formula
+fn chen_card_points(rank: CardRank) -> f32 {
+    match rank {
+        CardRank::Ace => 10.0,
+        CardRank::King => 8.0,
+        CardRank::Queen => 7.0,
+        CardRank::Jack => 6.0,
+        _ => rank.high_card_value() as f32 / 2.0,
+    }
+}
+
+/// Bill Chen's quick rating of a hold'em starting hand, from -1 (72 offsuit)
+/// to 20 (pocket aces). Half points are rounded up.
+pub fn chen_score(card1: Card, card2: Card) -> f32 {
+    let (high, low) = if card1.rank().high_card_value() >= card2.rank().high_card_value() {
+        (card1, card2)
+    } else {
+        (card2, card1)
+    };
+    let mut score = chen_card_points(high.rank());
+    if high.rank() == low.rank() {
+        return (score * 2.0).max(5.0).ceil();
+    }
+    if high.suit() == low.suit() {
+        score += 2.0;
+    }
+    let gap = high.rank().high_card_value() - low.rank().high_card_value() - 1;
+    score -= match gap {
+        0 => 0.0,
+        1 => 1.0,
+        2 => 2.0,
+        3 => 4.0,
+        _ => 5.0,
+    };
+    // Connected low cards can still make the top straights
+    if gap <= 1 && high.rank().high_card_value() < CardRank::Queen.high_card_value() {
+        score += 1.0;
+    }
+    score.ceil()
+}
+
+/// Share of the pot `hole` wins on average against `opponents` random hands,
+/// estimated from `runouts` random deals of the unknown cards. Invalid input,
+/// like a card dealt twice, rates `0.0`.
+pub fn estimate_equity<R: Rng + ?Sized>(
+    hole: [Card; 2],
+    board: &[Card],
+    opponents: usize,
+    runouts: u32,
+    rng: &mut R,
+) -> f32 {
+    if opponents == 0 || runouts == 0 {
+        return 1.0;
+    }
+    match simulate_equity(&[hole], opponents, board, runouts, rng) {
+        Ok(results) => (results[0].equity_pct() / 100.0) as f32,
+        Err(_) => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::{parse_cards, CardSuit};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn score(cards: &str) -> f32 {
+        let cards = parse_cards(cards).unwrap();
+        chen_score(cards[0], cards[1])
+    }
+
+    /// One representative of each of the 169 starting hands: 13 pairs, 78
+    /// suited and 78 offsuit combinations.
+    fn starting_hands() -> Vec<(Card, Card)> {
+        let mut hands = Vec::new();
+        for a in 0..13u8 {
+            for b in 0..=a {
+                let (a, b) = (CardRank::from_u8(a), CardRank::from_u8(b));
+                hands.push((
+                    Card::new(a, CardSuit::Spades),
+                    Card::new(b, CardSuit::Hearts),
+                ));
+                if a != b {
+                    hands.push((
+                        Card::new(a, CardSuit::Spades),
+                        Card::new(b, CardSuit::Spades),
+                    ));
+                }
+            }
+        }
+        hands
+    }
+
+    #[test]
+    fn known_scores() {
+        assert_eq!(score("Ah As"), 20.0);
+        assert_eq!(score("Kh Ks"), 16.0);
+        assert_eq!(score("2h 2s"), 5.0);
+        assert_eq!(score("Ah Kh"), 12.0);
+        assert_eq!(score("Ah Kd"), 10.0);
+        assert_eq!(score("Th 9h"), 8.0);
+        assert_eq!(score("5s 4s"), 6.0);
+        assert_eq!(score("Jd 8c"), 4.0);
+        assert_eq!(score("7h 2d"), -1.0);
+    }
+
+    #[test]
+    fn card_order_does_not_matter() {
+        for (a, b) in starting_hands() {
+            assert_eq!(chen_score(a, b), chen_score(b, a), "{} {}", a, b);
+        }
+    }
+
+    #[test]
+    fn all_169_starting_hands() {
+        let hands = starting_hands();
+        assert_eq!(hands.len(), 169);
+        for &(a, b) in &hands {
+            let s = chen_score(a, b);
+            assert!((-1.0..=MAX_CHEN_SCORE).contains(&s), "{} {}: {}", a, b, s);
+            assert_eq!(s, s.ceil(), "{} {} is not rounded up", a, b);
+            if a.rank() == b.rank() {
+                assert!(s >= 5.0, "{} {}: pairs score at least 5", a, b);
+            } else if a.suit() == b.suit() {
+                let offsuit = chen_score(a, Card::new(b.rank(), CardSuit::Hearts));
+                assert_eq!(s, offsuit + 2.0, "{} {}: suited adds 2", a, b);
+            }
+        }
+        let best = hands.iter().map(|&(a, b)| chen_score(a, b));
+        assert_eq!(best.clone().fold(f32::MIN, f32::max), MAX_CHEN_SCORE);
+        assert_eq!(best.fold(f32::MAX, f32::min), -1.0);
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq!(
+            StrengthLabel::from_chen(score("Ah As")),
+            StrengthLabel::Premium
+        );
+        assert_eq!(
+            StrengthLabel::from_chen(score("Th 9h")),
+            StrengthLabel::Good
+        );
+        assert_eq!(
+            StrengthLabel::from_chen(score("5s 4s")),
+            StrengthLabel::Fair
+        );
+        assert_eq!(
+            StrengthLabel::from_chen(score("7h 2d")),
+            StrengthLabel::Weak
+        );
+        assert_eq!(StrengthLabel::from_equity(0.5, 1), StrengthLabel::Fair);
+        assert_eq!(StrengthLabel::from_equity(0.5, 3), StrengthLabel::Premium);
+    }
+
+    #[test]
+    fn equity_of_made_hands() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let hole: Vec<Card> = parse_cards("Ah Ad").unwrap();
+        let hole = [hole[0], hole[1]];
+        // Quad aces lose to nothing but a straight flush
+        let board = parse_cards("Ac As 7d").unwrap();
+        assert!(estimate_equity(hole, &board, 2, 500, &mut rng) > 0.95);
+        // The nuts on the river always win
+        let board = parse_cards("Ac As Kd Kh 2c").unwrap();
+        assert_eq!(estimate_equity(hole, &board, 3, 100, &mut rng), 1.0);
+
+        let weak: Vec<Card> = parse_cards("7h 2d").unwrap();
+        let equity = estimate_equity([weak[0], weak[1]], &[], 1, 2_000, &mut rng);
+        assert!((0.25..0.45).contains(&equity), "72o equity {}", equity);
+    }
+}
@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use egui::{vec2, Align2, Color32, Context, RichText, Stroke};
+
+use crate::utils::WasmTimer;
+
+/// Toasts on screen at once; older ones make room for new ones
+const MAX_TOASTS: usize = 5;
+
+/// Severity of a notification, which sets its color and default lifetime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl NotificationLevel {
+    pub fn color(self) -> Color32 {
+        match self {
+            Self::Info => Color32::from_rgb(100, 160, 230),
+            Self::Success => Color32::from_rgb(80, 200, 120),
+            Self::Warning => Color32::from_rgb(240, 190, 40),
+            Self::Error => Color32::from_rgb(220, 80, 80),
+        }
+    }
+
+    fn icon(self) -> &'static str {
+        match self {
+            Self::Info => "ℹ",
+            Self::Success => "✔",
+            Self::Warning => "⚠",
+            Self::Error => "❌",
+        }
+    }
+
+    /// How long a toast stays up unless dismissed; errors stay longest so
+    /// there is time to read them.
+    pub fn default_duration_ms(self) -> u64 {
+        match self {
+            Self::Info | Self::Success => 3_000,
+            Self::Warning => 5_000,
+            Self::Error => 8_000,
+        }
+    }
+}
+
+/// A message shown as a toast until it expires or is dismissed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub message: String,
+    pub level: NotificationLevel,
+    /// When the notification was raised, see `WasmTimer::now_ms`
+    pub created_ms: f64,
+    pub duration_ms: u64,
+}
+
+impl Notification {
+    /// A notification raised now, shown for its level's default duration.
+    pub fn new(level: NotificationLevel, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            level,
+            created_ms: WasmTimer::now_ms(),
+            duration_ms: level.default_duration_ms(),
+        }
+    }
+
+    fn remaining_ms(&self, now_ms: f64) -> f64 {
+        self.created_ms + self.duration_ms as f64 - now_ms
+    }
+}
+
+/// Toasts stacked in the bottom-left corner, oldest on top.
+#[derive(Debug, Default)]
+pub struct Notifications {
+    active: Vec<Notification>,
+}
+
+impl Notifications {
+    pub fn push(&mut self, notification: Notification) {
+        self.active.push(notification);
+        if self.active.len() > MAX_TOASTS {
+            self.active.remove(0);
+        }
+    }
+
+    /// Drop the notifications whose time is up at `now_ms`.
+    pub fn expire(&mut self, now_ms: f64) {
+        self.active.retain(|n| n.remaining_ms(now_ms) > 0.0);
+    }
+
+    pub fn active(&self) -> &[Notification] {
+        &self.active
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let now = WasmTimer::now_ms();
+        self.expire(now);
+        if self.active.is_empty() {
+            return;
+        }
+        let mut dismissed = None;
+        egui::Area::new(egui::Id::new("notification_toasts"))
+            .anchor(Align2::LEFT_BOTTOM, vec2(16.0, -16.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                for (i, notification) in self.active.iter().enumerate() {
+                    let color = notification.level.color();
+                    egui::Frame::popup(ui.style())
+                        .stroke(Stroke::new(1.5, color))
+                        .show(ui, |ui| {
+                            ui.set_max_width(320.0);
+                            ui.horizontal(|ui| {
+                                ui.label(RichText::new(notification.level.icon()).color(color));
+                                ui.label(&notification.message);
+                                if ui.small_button("✖").on_hover_text("Dismiss").clicked() {
+                                    dismissed = Some(i);
+                                }
+                            });
+                        });
+                    ui.add_space(4.0);
+                }
+            });
+        if let Some(i) = dismissed {
+            self.active.remove(i);
+        }
+        let next_expiry = self
+            .active
+            .iter()
+            .map(|n| n.remaining_ms(now))
+            .fold(f64::INFINITY, f64::min);
+        if next_expiry.is_finite() {
+            ctx.request_repaint_after(Duration::from_millis(next_expiry.max(0.0) as u64));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(created_ms: f64, duration_ms: u64) -> Notification {
+        Notification {
+            message: format!("raised at {}", created_ms),
+            level: NotificationLevel::Info,
+            created_ms,
+            duration_ms,
+        }
+    }
+
+    #[test]
+    fn notifications_expire_after_their_duration() {
+        let mut notifications = Notifications::default();
+        notifications.push(at(0.0, 3_000));
+        notifications.push(at(1_000.0, 8_000));
+        notifications.expire(2_999.0);
+        assert_eq!(notifications.active().len(), 2);
+        notifications.expire(3_000.0);
+        assert_eq!(notifications.active(), &[at(1_000.0, 8_000)]);
+        notifications.expire(9_000.0);
+        assert!(notifications.active().is_empty());
+    }
+
+    #[test]
+    fn the_oldest_toast_makes_room() {
+        let mut notifications = Notifications::default();
+        for i in 0..=MAX_TOASTS {
+            notifications.push(at(i as f64, 3_000));
+        }
+        assert_eq!(notifications.active().len(), MAX_TOASTS);
+        assert_eq!(notifications.active()[0].created_ms, 1.0);
+    }
+}
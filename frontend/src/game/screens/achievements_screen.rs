@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use eframe::Frame;
+use egui::{vec2, Align2, Color32, Context, RichText};
+
+use super::{AppInterface, ScreenDef, ScreenMetadata, ScreenWidget};
+use crate::store::{AchievementId, AchievementManager};
+use crate::utils::WasmTimer;
+
+/// How long an unlocked achievement is announced
+const TOAST_MS: f64 = 3_000.0;
+
+#[derive(Default)]
+pub struct AchievementsScreen;
+
+impl ScreenWidget for AchievementsScreen {
+    fn ui(&mut self, app_interface: &mut AppInterface, ui: &mut egui::Ui, _frame: &mut Frame) {
+        let achievements = &app_interface.app_state.achievements.achievements;
+        ui.vertical_centered(|ui| {
+            ui.add_space(20.0);
+            ui.heading("Achievements");
+            let unlocked = achievements.iter().filter(|a| a.unlocked).count();
+            ui.label(format!("{} of {} unlocked", unlocked, achievements.len()));
+            ui.add_space(20.0);
+        });
+        egui::Grid::new("achievements_grid")
+            .num_columns(3)
+            .spacing(vec2(16.0, 12.0))
+            .striped(true)
+            .show(ui, |ui| {
+                for a in achievements {
+                    let color = if a.unlocked {
+                        Color32::from_rgb(240, 190, 40)
+                    } else {
+                        Color32::GRAY
+                    };
+                    let icon = if a.unlocked { "🏆" } else { "🔒" };
+                    ui.label(RichText::new(icon).size(20.0).color(color));
+                    ui.vertical(|ui| {
+                        ui.label(RichText::new(a.title).strong().color(color));
+                        ui.label(RichText::new(a.description).weak());
+                    });
+                    match a.unlocked_at_hand {
+                        Some(hand) => ui.label(format!("Hand {}", hand)),
+                        None => ui.label(""),
+                    };
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+crate::impl_screen_def!(
+    AchievementsScreen,
+    "/achievements",
+    "Achievements",
+    "🏅",
+    "Poker milestones you have reached",
+    true
+);
+
+/// Announces unlocked achievements one at a time in the bottom-right corner.
+#[derive(Default)]
+pub struct AchievementToasts {
+    /// Achievement on display and when it appeared
+    current: Option<(AchievementId, f64)>,
+}
+
+impl AchievementToasts {
+    pub fn show(&mut self, ctx: &Context, achievements: &mut AchievementManager) {
+        let now = WasmTimer::now_ms();
+        if self
+            .current
+            .is_some_and(|(_, since)| now - since >= TOAST_MS)
+        {
+            self.current = None;
+        }
+        if self.current.is_none() {
+            self.current = achievements.toasts.pop_front().map(|id| (id, now));
+        }
+        let Some((id, since)) = self.current else {
+            return;
+        };
+        let Some(achievement) = achievements.achievements.iter().find(|a| a.id == id) else {
+            return;
+        };
+        egui::Window::new("Achievement unlocked")
+            .id(egui::Id::new("achievement_toast"))
+            .anchor(Align2::RIGHT_BOTTOM, vec2(-16.0, -16.0))
+            .title_bar(false)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(format!("🏆 {}", achievement.title))
+                        .strong()
+                        .color(Color32::from_rgb(240, 190, 40)),
+                );
+                ui.label(achievement.description);
+            });
+        let left = (since + TOAST_MS - now).max(0.0);
+        ctx.request_repaint_after(Duration::from_millis(left as u64));
+    }
+}
@@ -1,9 +1,10 @@
 use eframe::Frame;
 use egui::{vec2, Color32, RichText, ScrollArea};
+use mcg_shared::ArticleMeta;
 
 use super::{AppInterface, ScreenDef, ScreenMetadata, ScreenWidget};
-use crate::articles::Post;
-use crate::effects::fetch_articles_effect;
+use crate::articles::render_markdown;
+use crate::effects::{fetch_article_effect, fetch_articles_effect};
 use crate::store::{ArticlesLoading, ClientState};
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -11,64 +12,114 @@ use std::rc::Rc;
 #[derive(Default)]
 pub struct ArticlesScreen {
     #[allow(clippy::type_complexity)]
-    pending_result: Rc<RefCell<Option<Result<Vec<Post>, String>>>>,
+    pending_list: Rc<RefCell<Option<Result<Vec<ArticleMeta>, String>>>>,
+    #[allow(clippy::type_complexity)]
+    pending_body: Rc<RefCell<Option<(String, Result<String, String>)>>>,
+    /// Article currently shown; `None` shows the list
+    selected: Option<String>,
+    /// Article whose body is being fetched
+    loading_body: Option<String>,
+    body_error: Option<String>,
 }
 
 impl ArticlesScreen {
-    fn render_loading_ui(&self, ui: &mut egui::Ui) {
+    fn fetch_list(&self, app_state: &mut ClientState) {
+        let pending_list = self.pending_list.clone();
+        fetch_articles_effect(app_state, move |result| {
+            *pending_list.borrow_mut() = Some(result);
+        });
+    }
+
+    /// Show an article, fetching its body unless it is already cached.
+    fn select(&mut self, app_state: &ClientState, id: &str) {
+        self.selected = Some(id.to_string());
+        self.body_error = None;
+        if app_state.ui.article_bodies.contains_key(id) || self.loading_body.is_some() {
+            return;
+        }
+        self.loading_body = Some(id.to_string());
+        let pending_body = self.pending_body.clone();
+        let article_id = id.to_string();
+        fetch_article_effect(app_state, id.to_string(), move |result| {
+            *pending_body.borrow_mut() = Some((article_id, result));
+        });
+    }
+
+    fn apply_pending_results(&mut self, app_state: &mut ClientState) {
+        if let Some(result) = self.pending_list.borrow_mut().take() {
+            app_state.ui.articles = match result {
+                Ok(articles) => ArticlesLoading::Loaded(articles),
+                Err(e) => ArticlesLoading::Error(e),
+            };
+        }
+        if let Some((id, result)) = self.pending_body.borrow_mut().take() {
+            self.loading_body = None;
+            match result {
+                Ok(body) => {
+                    app_state.ui.article_bodies.insert(id, body);
+                }
+                Err(e) => self.body_error = Some(e),
+            }
+        }
+    }
+
+    fn render_loading_ui(&self, ui: &mut egui::Ui, what: &str) {
         ui.spinner();
-        ui.label("Loading posts...");
-        ui.add_space(10.0);
-        ui.label(RichText::new("Fetching data from remote API...").color(Color32::GRAY));
+        ui.label(format!("Loading {}...", what));
     }
 
     fn render_error_ui(&self, ui: &mut egui::Ui, error: &str) {
-        ui.label(RichText::new("❌ Error loading posts").color(Color32::RED));
+        ui.label(RichText::new("❌ Error loading articles").color(Color32::RED));
         ui.label(RichText::new(error).color(Color32::GRAY));
         ui.add_space(20.0);
     }
 
-    fn render_posts_list(
+    fn render_article_list(
         &mut self,
         app_state: &mut ClientState,
         ui: &mut egui::Ui,
-        posts: &[Post],
-        _ctx: &egui::Context,
+        articles: &[ArticleMeta],
     ) {
         if ui
             .add_sized(vec2(150.0, 30.0), egui::Button::new("Refresh"))
             .clicked()
         {
-            let pending_result = self.pending_result.clone();
-            fetch_articles_effect(app_state, move |result| {
-                *pending_result.borrow_mut() = Some(result);
-            });
+            app_state.ui.article_bodies.clear();
+            self.fetch_list(app_state);
         }
-        ui.add_space(10.0);
-        ui.label(RichText::new(format!("Found {} posts", posts.len())).color(Color32::GREEN));
         ui.add_space(20.0);
         ScrollArea::vertical().max_height(500.0).show(ui, |ui| {
-            for post in posts {
+            for article in articles {
                 ui.group(|ui| {
                     ui.set_min_width(ui.available_width());
-                    ui.heading(&post.title);
-                    ui.horizontal(|ui| {
-                        ui.label(
-                            RichText::new(format!("Post ID: {}", post.id)).color(Color32::GRAY),
-                        );
-                        ui.separator();
-                        ui.label(
-                            RichText::new(format!("User ID: {}", post.user_id))
-                                .color(Color32::GRAY),
-                        );
-                    });
-                    ui.add_space(10.0);
-                    ui.label(&post.body);
+                    if ui.link(RichText::new(&article.title).heading()).clicked() {
+                        self.select(app_state, &article.id);
+                    }
+                    ui.label(RichText::new(&article.summary).color(Color32::GRAY));
                 });
                 ui.add_space(15.0);
             }
         });
     }
+
+    fn render_article(&mut self, app_state: &ClientState, ui: &mut egui::Ui, id: &str) {
+        if ui.button("⬅ All articles").clicked() {
+            self.selected = None;
+            return;
+        }
+        ui.add_space(10.0);
+        if let Some(body) = app_state.ui.article_bodies.get(id) {
+            ScrollArea::vertical().show(ui, |ui| {
+                ui.set_max_width(720.0);
+                render_markdown(ui, body);
+            });
+        } else if let Some(error) = &self.body_error {
+            self.render_error_ui(ui, error);
+        } else {
+            self.render_loading_ui(ui, "article");
+            ui.ctx().request_repaint();
+        }
+    }
 }
 
 impl ScreenWidget for ArticlesScreen {
@@ -76,51 +127,29 @@ impl ScreenWidget for ArticlesScreen {
         let ctx = ui.ctx().clone();
         let app_state = &mut app_interface.app_state;
 
-        if let Some(result) = self.pending_result.borrow_mut().take() {
-            match result {
-                Ok(posts) => app_state.ui.articles = ArticlesLoading::Loaded(posts),
-                Err(e) => {
-                    app_state.ui.articles = ArticlesLoading::Error(e);
-                }
-            }
+        self.apply_pending_results(app_state);
+        if matches!(app_state.ui.articles, ArticlesLoading::NotStarted) {
+            self.fetch_list(app_state);
+        }
+
+        if let Some(id) = self.selected.clone() {
+            self.render_article(app_state, ui, &id);
+            return;
         }
 
         ui.vertical_centered(|ui| {
             ui.add_space(20.0);
-            ui.heading("Posts from JSONPlaceholder");
-            ui.add_space(10.0);
-            ui.label(
-                RichText::new("Real REST API: https://jsonplaceholder.typicode.com/posts/")
-                    .color(Color32::GRAY),
-            );
+            ui.heading("Articles");
             ui.add_space(20.0);
 
             match &app_state.ui.articles {
-                ArticlesLoading::NotStarted => {
-                    if ui
-                        .add_sized(vec2(150.0, 40.0), egui::Button::new("Fetch Posts"))
-                        .clicked()
-                    {
-                        let pending_result = self.pending_result.clone();
-                        fetch_articles_effect(app_state, move |result| {
-                            *pending_result.borrow_mut() = Some(result);
-                        });
-                    }
-                    ui.add_space(20.0);
-                    ui.label("Click the button to fetch posts from JSONPlaceholder API.");
-                    ui.add_space(10.0);
-                    ui.label(
-                        RichText::new("This demonstrates async HTTP requests with egui and WASM.")
-                            .color(Color32::GRAY),
-                    );
-                }
-                ArticlesLoading::Loading => {
-                    self.render_loading_ui(ui);
+                ArticlesLoading::NotStarted | ArticlesLoading::Loading => {
+                    self.render_loading_ui(ui, "articles");
                     ctx.request_repaint();
                 }
-                ArticlesLoading::Loaded(posts) => {
-                    let posts = posts.clone();
-                    self.render_posts_list(app_state, ui, &posts, &ctx);
+                ArticlesLoading::Loaded(articles) => {
+                    let articles = articles.clone();
+                    self.render_article_list(app_state, ui, &articles);
                 }
                 ArticlesLoading::Error(err) => {
                     let err = err.clone();
@@ -129,10 +158,7 @@ impl ScreenWidget for ArticlesScreen {
                         .add_sized(vec2(150.0, 40.0), egui::Button::new("Retry"))
                         .clicked()
                     {
-                        let pending_result = self.pending_result.clone();
-                        fetch_articles_effect(app_state, move |result| {
-                            *pending_result.borrow_mut() = Some(result);
-                        });
+                        self.fetch_list(app_state);
                     }
                 }
             }
@@ -147,6 +173,6 @@ crate::impl_screen_def!(
     "/articles",
     "Articles",
     "📰",
-    "Fetch posts from a demo API",
+    "Read articles published by the server",
     true
 );
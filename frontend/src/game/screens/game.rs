@@ -1,5 +1,5 @@
 use eframe::Frame;
-use egui::{Align, Layout, Rect, UiBuilder};
+use egui::{Align, Color32, Layout, Rect, Stroke, StrokeKind, UiBuilder};
 
 use super::{AppInterface, ScreenDef, ScreenMetadata, ScreenWidget};
 use crate::game::card::{CardConfig, SimpleCard};
@@ -25,10 +25,21 @@ pub struct GameState<C: CardConfig> {
     pub stack: SimpleField<SimpleCard, C>,
 }
 impl<C: CardConfig> GameState<C> {
+    /// Move a card between fields. Dropping a card onto another card of the
+    /// same hand swaps the two instead.
     pub fn move_card(&mut self, src: DNDSelector, dst: DNDSelector) {
         if src == dst {
             return;
         }
+        if let (DNDSelector::Player(src_p, src_c), DNDSelector::Player(dst_p, dst_c)) = (src, dst) {
+            if src_p == dst_p && src_p < self.players.len() {
+                let cards = &mut self.players[src_p].1.cards;
+                if src_c < cards.len() && dst_c < cards.len() {
+                    cards.swap(src_c, dst_c);
+                }
+                return;
+            }
+        }
         let card = match src {
             DNDSelector::Player(p_idx, c_idx) => {
                 if p_idx < self.players.len() {
@@ -62,6 +73,21 @@ impl<C: CardConfig> GameState<C> {
 
 pub type DirectoryCardType = crate::game::card::DirectoryCardType;
 
+/// Outline a field while a card is dragged over it.
+fn highlight_drop_target(ui: &egui::Ui, rect: Rect, dragging: bool) {
+    let hovered = ui
+        .input(|i| i.pointer.latest_pos())
+        .is_some_and(|pos| rect.contains(pos));
+    if dragging && hovered {
+        ui.painter().rect_stroke(
+            rect,
+            4.0,
+            Stroke::new(3.0, Color32::GOLD),
+            StrokeKind::Outside,
+        );
+    }
+}
+
 impl ScreenWidget for Game<DirectoryCardType> {
     fn ui(&mut self, _app_interface: &mut AppInterface, ui: &mut egui::Ui, _frame: &mut Frame) {
         let mut rect = ui.max_rect();
@@ -104,7 +130,9 @@ impl ScreenWidget for Game<DirectoryCardType> {
                 ui.add_space(5.0);
                 ui.label("Stack");
                 let stack = &cfg.stack;
-                if let Some(_payload) = ui.add(stack.draw()).dnd_release_payload::<DNDSelector>() {
+                let response = ui.add(stack.draw());
+                highlight_drop_target(ui, response.rect, self.drag.is_some());
+                if let Some(_payload) = response.dnd_release_payload::<DNDSelector>() {
                     self.drop = Some(DNDSelector::Stack)
                 }
                 match stack.get_payload() {
@@ -119,8 +147,9 @@ impl ScreenWidget for Game<DirectoryCardType> {
                 let (name_0, field_0) = &cfg.players[self.player0_idx];
                 ui.add_space(5.0);
                 ui.label(name_0);
-                if let Some(_payload) = ui.add(field_0.draw()).dnd_release_payload::<DNDSelector>()
-                {
+                let response = ui.add(field_0.draw());
+                highlight_drop_target(ui, response.rect, self.drag.is_some());
+                if let Some(_payload) = response.dnd_release_payload::<DNDSelector>() {
                     self.drop = Some(DNDSelector::Player(self.player0_idx, field_0.cards.len()))
                 }
                 match field_0.get_payload() {
@@ -135,8 +164,9 @@ impl ScreenWidget for Game<DirectoryCardType> {
                 let (name_1, field_1) = &cfg.players[self.player1_idx];
                 ui.add_space(5.0);
                 ui.label(name_1);
-                if let Some(_payload) = ui.add(field_1.draw()).dnd_release_payload::<DNDSelector>()
-                {
+                let response = ui.add(field_1.draw());
+                highlight_drop_target(ui, response.rect, self.drag.is_some());
+                if let Some(_payload) = response.dnd_release_payload::<DNDSelector>() {
                     self.drop = Some(DNDSelector::Player(self.player1_idx, field_1.cards.len()))
                 }
                 match field_1.get_payload() {
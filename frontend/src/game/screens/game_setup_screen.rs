@@ -5,32 +5,31 @@ use std::rc::Rc;
 use super::{AppInterface, DirectoryCardType, GameState, ScreenDef, ScreenMetadata, ScreenWidget};
 use crate::game::card::{CardConfig, SimpleCard};
 use crate::game::field::{SimpleField, SimpleFieldKind::Stack};
+use crate::hardcoded_cards::ThemeRegistry;
 
 pub struct GameSetupScreen {
     pub card_config: Option<DirectoryCardType>,
     pub players: usize,
-    pub theme_index: usize,
+    pub theme: &'static str,
 }
 impl GameSetupScreen {
     pub fn new() -> Self {
         let card_config = None;
         let players = 2;
-        let theme_index = crate::hardcoded_cards::AVAILABLE_THEMES
-            .iter()
-            .position(|&t| t == crate::hardcoded_cards::DEFAULT_THEME)
-            .unwrap_or(0);
         let mut screen = Self {
             card_config,
             players,
-            theme_index,
+            theme: crate::hardcoded_cards::DEFAULT_THEME,
         };
         // Ensure a default deck is set for runtime-created screens
-        crate::hardcoded_cards::set_deck_by_theme(
-            &mut screen.card_config,
-            crate::hardcoded_cards::DEFAULT_THEME,
-        );
+        screen.apply_theme(crate::hardcoded_cards::DEFAULT_THEME);
         screen
     }
+    /// Switch the deck to another card theme.
+    pub fn apply_theme(&mut self, theme: &'static str) {
+        self.theme = theme;
+        crate::hardcoded_cards::set_deck_by_theme(&mut self.card_config, theme);
+    }
     pub fn generate_config(&self) -> Option<GameState<DirectoryCardType>> {
         let card_config = self.card_config.as_ref()?.clone();
         let mut players: Vec<(String, SimpleField<SimpleCard, DirectoryCardType>)> = (0..self
@@ -63,6 +62,12 @@ impl Default for GameSetupScreen {
 }
 impl ScreenWidget for GameSetupScreen {
     fn ui(&mut self, app_interface: &mut AppInterface, ui: &mut egui::Ui, _frame: &mut Frame) {
+        let card_theme = &app_interface.app_state.settings.card_theme;
+        if card_theme != self.theme {
+            if let Some(theme) = ThemeRegistry::get(card_theme) {
+                self.apply_theme(theme.id);
+            }
+        }
         let mut rect = ui.max_rect();
         let width = rect.width() / 3.0;
         rect.set_left(width);
@@ -85,30 +90,23 @@ impl ScreenWidget for GameSetupScreen {
                     ui.add_space(5.0);
                     ui.horizontal(|ui| {
                         ui.label("Theme:");
+                        let selected =
+                            ThemeRegistry::get(self.theme).map_or(self.theme, |t| t.display_name);
                         egui::ComboBox::new("theme_selector", "Theme")
-                            .selected_text(
-                                crate::hardcoded_cards::AVAILABLE_THEMES[self.theme_index],
-                            )
+                            .selected_text(selected)
                             .show_ui(ui, |ui| {
-                                for (i, theme) in
-                                    crate::hardcoded_cards::AVAILABLE_THEMES.iter().enumerate()
-                                {
-                                    let theme_name = match *theme {
-                                        "img_cards" => "Standard Cards",
-                                        "alt_cards" => "Alternative Cards",
-                                        _ => theme,
-                                    };
+                                for theme in ThemeRegistry::list_themes() {
                                     if ui
-                                        .selectable_label(self.theme_index == i, theme_name)
+                                        .selectable_label(
+                                            self.theme == theme.id,
+                                            theme.display_name,
+                                        )
+                                        .on_hover_text(theme.description)
                                         .clicked()
                                     {
-                                        self.theme_index = i;
-                                        let theme_str = crate::hardcoded_cards::AVAILABLE_THEMES
-                                            [self.theme_index];
-                                        crate::hardcoded_cards::set_deck_by_theme(
-                                            &mut self.card_config,
-                                            theme_str,
-                                        );
+                                        self.apply_theme(theme.id);
+                                        app_interface.app_state.settings.card_theme =
+                                            theme.id.to_string();
                                     }
                                 }
                             });
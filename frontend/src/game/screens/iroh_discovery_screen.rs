@@ -0,0 +1,158 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use eframe::Frame;
+use egui::{vec2, Color32, RichText};
+use mcg_shared::PeerInfo;
+
+use super::{AppInterface, ScreenDef, ScreenMetadata, ScreenWidget};
+use crate::effects::fetch_iroh_peers_effect;
+use crate::game::websocket::IROH_SCHEME;
+use crate::game::AppEvent;
+use crate::utils::WasmTimer;
+
+/// How often the peer list is fetched again while the screen is open
+const AUTO_REFRESH_MS: f64 = 10_000.0;
+
+/// "Xm ago" for a peer last seen at `last_seen_ms`, both in Unix milliseconds.
+pub fn format_last_seen(now_ms: f64, last_seen_ms: u64) -> String {
+    let minutes = ((now_ms - last_seen_ms as f64).max(0.0) / 60_000.0) as u64;
+    format!("{}m ago", minutes)
+}
+
+/// Iroh nodes known to the preferred server: the server itself and the peers
+/// that connected to it. Connecting to one fills its `iroh://` address into
+/// the poker screen.
+#[derive(Default)]
+pub struct IrohDiscoveryScreen {
+    #[allow(clippy::type_complexity)]
+    pending: Rc<RefCell<Option<Result<Vec<PeerInfo>, String>>>>,
+    peers: Option<Result<Vec<PeerInfo>, String>>,
+    loading: bool,
+    /// When the last fetch was started
+    last_fetch_ms: Option<f64>,
+}
+
+impl IrohDiscoveryScreen {
+    fn fetch(&mut self, app_interface: &AppInterface) {
+        self.loading = true;
+        self.last_fetch_ms = Some(WasmTimer::now_ms());
+        let pending = self.pending.clone();
+        fetch_iroh_peers_effect(app_interface.app_state, move |result| {
+            *pending.borrow_mut() = Some(result);
+        });
+    }
+
+    fn render_peers(app_interface: &mut AppInterface, ui: &mut egui::Ui, peers: &[PeerInfo]) {
+        let now = WasmTimer::now_ms();
+        egui::Grid::new("iroh_peers_grid")
+            .num_columns(4)
+            .spacing([12.0, 6.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label(RichText::new("Node").strong());
+                ui.label(RichText::new("Relay").strong());
+                ui.label(RichText::new("Last seen").strong());
+                ui.label("");
+                ui.end_row();
+                for peer in peers {
+                    ui.label(RichText::new(&peer.node_id).monospace())
+                        .on_hover_text("Endpoint id");
+                    ui.label(RichText::new(peer.relay_url.as_deref().unwrap_or("-")).weak());
+                    ui.label(format_last_seen(now, peer.last_seen_ms));
+                    if ui.button("Connect").clicked() {
+                        app_interface.app_state.session.pending_server_address =
+                            Some(format!("{}{}", IROH_SCHEME, peer.node_id));
+                        app_interface
+                            .queue_event(AppEvent::ChangeRoute("/poker-online".to_string()));
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+impl ScreenWidget for IrohDiscoveryScreen {
+    fn ui(&mut self, app_interface: &mut AppInterface, ui: &mut egui::Ui, _frame: &mut Frame) {
+        if let Some(result) = self.pending.borrow_mut().take() {
+            self.loading = false;
+            self.peers = Some(result);
+        }
+        let due = self
+            .last_fetch_ms
+            .is_none_or(|ms| WasmTimer::elapsed_since(ms) >= AUTO_REFRESH_MS);
+        if due && !self.loading {
+            self.fetch(app_interface);
+        }
+        if self.loading {
+            ui.ctx().request_repaint_after(Duration::from_millis(100));
+        } else if let Some(ms) = self.last_fetch_ms {
+            let wait = (AUTO_REFRESH_MS - WasmTimer::elapsed_since(ms)).max(0.0);
+            ui.ctx()
+                .request_repaint_after(Duration::from_millis(wait as u64));
+        }
+
+        ui.vertical_centered(|ui| {
+            ui.add_space(20.0);
+            ui.heading("Iroh Discovery");
+            ui.label(RichText::new("Iroh nodes seen by the server in the last hour").weak());
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                let refresh = ui.add_enabled(
+                    !self.loading,
+                    egui::Button::new("Refresh").min_size(vec2(120.0, 30.0)),
+                );
+                if refresh.clicked() {
+                    self.fetch(app_interface);
+                }
+                if self.loading {
+                    ui.spinner();
+                }
+            });
+            ui.add_space(10.0);
+        });
+
+        match self.peers.take() {
+            None => {
+                ui.label("Looking for iroh nodes...");
+            }
+            Some(Err(e)) => {
+                ui.label(RichText::new("❌ Error loading iroh nodes").color(Color32::RED));
+                ui.label(RichText::new(&e).color(Color32::GRAY));
+                self.peers = Some(Err(e));
+            }
+            Some(Ok(peers)) => {
+                if peers.is_empty() {
+                    ui.label("No iroh nodes seen yet.");
+                } else {
+                    Self::render_peers(app_interface, ui, &peers);
+                }
+                self.peers = Some(Ok(peers));
+            }
+        }
+    }
+}
+
+crate::impl_screen_def!(
+    IrohDiscoveryScreen,
+    "/iroh-discovery",
+    "Iroh Discovery",
+    "📡",
+    "Iroh nodes seen by the server",
+    true
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_seen_is_shown_in_minutes() {
+        let now = 10.0 * 60_000.0;
+        assert_eq!(format_last_seen(now, 600_000), "0m ago");
+        assert_eq!(format_last_seen(now, 420_000), "3m ago");
+        // Clocks of the server and browser may disagree a little
+        assert_eq!(format_last_seen(now, 605_000), "0m ago");
+    }
+}
@@ -0,0 +1,291 @@
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::rc::Rc;
+use std::time::Duration;
+
+use eframe::Frame;
+use egui::{vec2, Color32, RichText};
+use egui_extras::{Column, TableBuilder};
+use mcg_shared::LeaderboardEntry;
+
+use super::{AppInterface, ScreenDef, ScreenMetadata, ScreenWidget};
+use crate::effects::fetch_leaderboard_effect;
+use crate::utils::WasmTimer;
+
+/// How often the standings are fetched again while the screen is open
+const AUTO_REFRESH_MS: f64 = 30_000.0;
+/// Number of leading players shown
+const TOP_COUNT: u32 = 10;
+const YOU_COLOR: Color32 = Color32::from_rgb(100, 200, 255);
+const HEADER_HEIGHT: f32 = 22.0;
+const ROW_HEIGHT: f32 = 20.0;
+
+/// Column the table is sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LeaderboardSort {
+    /// Best rank first
+    #[default]
+    Rank,
+    Player,
+    /// Most hands won first
+    HandsWon,
+    /// Most chips won first
+    ChipsWon,
+    /// Most sessions first
+    Sessions,
+    /// Strongest hand first, players without a shown hand last
+    BestHand,
+}
+
+impl LeaderboardSort {
+    const COLUMNS: [(LeaderboardSort, &'static str); 6] = [
+        (LeaderboardSort::Rank, "#"),
+        (LeaderboardSort::Player, "Player"),
+        (LeaderboardSort::HandsWon, "Hands won"),
+        (LeaderboardSort::ChipsWon, "Chips won"),
+        (LeaderboardSort::Sessions, "Sessions"),
+        (LeaderboardSort::BestHand, "Best hand"),
+    ];
+}
+
+/// The top players sorted by `sort`, followed by the player named `you` if
+/// they are not among them.
+pub fn visible_entries<'a>(
+    entries: &'a [LeaderboardEntry],
+    sort: LeaderboardSort,
+    you: &str,
+) -> Vec<&'a LeaderboardEntry> {
+    let mut rows: Vec<_> = entries.iter().filter(|e| e.rank <= TOP_COUNT).collect();
+    match sort {
+        LeaderboardSort::Rank => rows.sort_by_key(|e| e.rank),
+        LeaderboardSort::Player => rows.sort_by(|a, b| a.player_name.cmp(&b.player_name)),
+        LeaderboardSort::HandsWon => rows.sort_by_key(|e| Reverse(e.hands_won)),
+        LeaderboardSort::ChipsWon => rows.sort_by_key(|e| Reverse(e.total_chips_won)),
+        LeaderboardSort::Sessions => rows.sort_by_key(|e| Reverse(e.sessions_played)),
+        LeaderboardSort::BestHand => rows.sort_by_key(|e| Reverse(e.best_hand)),
+    }
+    if let Some(own) = entries
+        .iter()
+        .find(|e| e.rank > TOP_COUNT && e.player_name == you)
+    {
+        rows.push(own);
+    }
+    rows
+}
+
+/// All-time standings of the preferred server, fetched again every
+/// `AUTO_REFRESH_MS` while the screen is shown.
+#[derive(Default)]
+pub struct LeaderboardScreen {
+    #[allow(clippy::type_complexity)]
+    pending: Rc<RefCell<Option<Result<Vec<LeaderboardEntry>, String>>>>,
+    entries: Option<Result<Vec<LeaderboardEntry>, String>>,
+    loading: bool,
+    /// When the last fetch was started
+    last_fetch_ms: Option<f64>,
+    sort: LeaderboardSort,
+}
+
+impl LeaderboardScreen {
+    fn fetch(&mut self, app_interface: &AppInterface) {
+        self.loading = true;
+        self.last_fetch_ms = Some(WasmTimer::now_ms());
+        let pending = self.pending.clone();
+        fetch_leaderboard_effect(app_interface.app_state, move |result| {
+            *pending.borrow_mut() = Some(result);
+        });
+    }
+
+    fn render_table(&mut self, ui: &mut egui::Ui, entries: &[LeaderboardEntry], you: &str) {
+        let rows = visible_entries(entries, self.sort, you);
+        TableBuilder::new(ui)
+            .id_salt("leaderboard_table")
+            .striped(true)
+            .column(Column::auto().at_least(30.0))
+            .column(Column::remainder().at_least(120.0))
+            .columns(Column::auto().at_least(70.0), 3)
+            .column(Column::auto().at_least(100.0))
+            .header(HEADER_HEIGHT, |mut header| {
+                for (sort, title) in LeaderboardSort::COLUMNS {
+                    header.col(|ui| {
+                        let selected = self.sort == sort;
+                        let text = if selected {
+                            format!("{} ⏷", title)
+                        } else {
+                            title.to_owned()
+                        };
+                        if ui
+                            .selectable_label(selected, RichText::new(text).strong())
+                            .on_hover_text(format!("Sort by {}", title.to_lowercase()))
+                            .clicked()
+                        {
+                            self.sort = sort;
+                        }
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(ROW_HEIGHT, rows.len(), |mut row| {
+                    let e = rows[row.index()];
+                    let is_you = e.player_name == you;
+                    row.set_selected(is_you);
+                    let text = |s: String| {
+                        let text = RichText::new(s);
+                        if is_you {
+                            text.strong().color(YOU_COLOR)
+                        } else {
+                            text
+                        }
+                    };
+                    row.col(|ui| {
+                        ui.label(text(e.rank.to_string()));
+                    });
+                    row.col(|ui| {
+                        ui.label(text(e.player_name.clone()));
+                    });
+                    row.col(|ui| {
+                        ui.label(text(e.hands_won.to_string()).monospace());
+                    });
+                    row.col(|ui| {
+                        ui.label(text(format!("{:+}", e.total_chips_won)).monospace());
+                    });
+                    row.col(|ui| {
+                        ui.label(text(e.sessions_played.to_string()).monospace());
+                    });
+                    row.col(|ui| {
+                        let best = e.best_hand.map(|c| c.to_string()).unwrap_or_default();
+                        ui.label(text(best));
+                    });
+                });
+            });
+    }
+}
+
+impl ScreenWidget for LeaderboardScreen {
+    fn ui(&mut self, app_interface: &mut AppInterface, ui: &mut egui::Ui, _frame: &mut Frame) {
+        if let Some(result) = self.pending.borrow_mut().take() {
+            self.loading = false;
+            self.entries = Some(result);
+        }
+        let due = self
+            .last_fetch_ms
+            .is_none_or(|ms| WasmTimer::elapsed_since(ms) >= AUTO_REFRESH_MS);
+        if due && !self.loading {
+            self.fetch(app_interface);
+        }
+        if self.loading {
+            ui.ctx().request_repaint_after(Duration::from_millis(100));
+        } else if let Some(ms) = self.last_fetch_ms {
+            let wait = (AUTO_REFRESH_MS - WasmTimer::elapsed_since(ms)).max(0.0);
+            ui.ctx()
+                .request_repaint_after(Duration::from_millis(wait as u64));
+        }
+
+        let you = app_interface.app_state.profile.name.clone();
+        ui.vertical_centered(|ui| {
+            ui.add_space(20.0);
+            ui.heading("Leaderboard");
+            ui.label(RichText::new("All-time standings since the server started").weak());
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                let refresh = ui.add_enabled(
+                    !self.loading,
+                    egui::Button::new("Refresh").min_size(vec2(120.0, 30.0)),
+                );
+                if refresh.clicked() {
+                    self.fetch(app_interface);
+                }
+                if self.loading {
+                    ui.spinner();
+                }
+            });
+            ui.add_space(10.0);
+        });
+
+        match self.entries.take() {
+            None => {
+                ui.label("Loading leaderboard...");
+            }
+            Some(Err(e)) => {
+                ui.label(RichText::new("❌ Error loading leaderboard").color(Color32::RED));
+                ui.label(RichText::new(&e).color(Color32::GRAY));
+                self.entries = Some(Err(e));
+            }
+            Some(Ok(entries)) => {
+                if entries.is_empty() {
+                    ui.label("No hands have been played yet.");
+                } else {
+                    self.render_table(ui, &entries, &you);
+                }
+                self.entries = Some(Ok(entries));
+            }
+        }
+    }
+}
+
+crate::impl_screen_def!(
+    LeaderboardScreen,
+    "/leaderboard",
+    "Leaderboard",
+    "🏆",
+    "All-time standings of the players on the server",
+    true
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::HandRankCategory;
+
+    fn entry(rank: u32, name: &str, hands_won: u32) -> LeaderboardEntry {
+        LeaderboardEntry {
+            rank,
+            player_name: name.to_owned(),
+            hands_won,
+            total_chips_won: 1000 - rank as i64 * 100,
+            sessions_played: 1,
+            best_hand: (rank == 3).then_some(HandRankCategory::Flush),
+        }
+    }
+
+    fn names(rows: &[&LeaderboardEntry]) -> Vec<String> {
+        rows.iter().map(|e| e.player_name.clone()).collect()
+    }
+
+    fn standings(count: u32) -> Vec<LeaderboardEntry> {
+        (1..=count)
+            .map(|rank| entry(rank, &format!("P{}", rank), rank % 4))
+            .collect()
+    }
+
+    #[test]
+    fn only_the_top_players_are_shown() {
+        let entries = standings(15);
+        let rows = visible_entries(&entries, LeaderboardSort::Rank, "Nobody");
+        assert_eq!(rows.len(), TOP_COUNT as usize);
+        assert_eq!(rows[0].player_name, "P1");
+    }
+
+    #[test]
+    fn own_row_is_added_below_the_top_players() {
+        let entries = standings(15);
+        let rows = visible_entries(&entries, LeaderboardSort::HandsWon, "P13");
+        assert_eq!(rows.len(), TOP_COUNT as usize + 1);
+        assert_eq!(rows.last().unwrap().player_name, "P13");
+
+        // Already among the top players, so not shown twice
+        let rows = visible_entries(&entries, LeaderboardSort::Rank, "P2");
+        assert_eq!(rows.len(), TOP_COUNT as usize);
+    }
+
+    #[test]
+    fn rows_are_sorted_by_the_selected_column() {
+        let entries = standings(4);
+        let rows = visible_entries(&entries, LeaderboardSort::HandsWon, "");
+        assert_eq!(names(&rows), ["P3", "P2", "P1", "P4"]);
+        let rows = visible_entries(&entries, LeaderboardSort::BestHand, "");
+        assert_eq!(rows[0].player_name, "P3");
+        let rows = visible_entries(&entries, LeaderboardSort::ChipsWon, "");
+        assert_eq!(names(&rows), ["P1", "P2", "P3", "P4"]);
+    }
+}
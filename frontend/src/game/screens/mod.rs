@@ -1,9 +1,12 @@
 use eframe::Frame;
 
+pub mod achievements_screen;
 pub mod articles_screen;
 pub mod example_screen;
 pub mod game;
 pub mod game_setup_screen;
+pub mod iroh_discovery_screen;
+pub mod leaderboard_screen;
 pub mod main_menu;
 pub mod pairing_screen;
 
@@ -11,18 +14,23 @@ pub mod poker;
 pub mod qr_test;
 pub mod qr_test_receive;
 pub mod qr_test_transmit;
+pub mod tournament_bracket;
 
 use crate::game::screens::qr_test_receive::QrTestReceive;
 use crate::game::screens::qr_test_transmit::QrTestTransmit;
+pub use achievements_screen::{AchievementToasts, AchievementsScreen};
 pub use articles_screen::ArticlesScreen;
 use downcast_rs::{impl_downcast, Downcast};
 pub use example_screen::ExampleScreen;
 pub use game::{DNDSelector, DirectoryCardType, Game, GameState};
 pub use game_setup_screen::GameSetupScreen;
+pub use iroh_discovery_screen::IrohDiscoveryScreen;
+pub use leaderboard_screen::LeaderboardScreen;
 pub use main_menu::MainMenu;
 pub use pairing_screen::PairingScreen;
 pub use poker::PokerOnlineScreen;
 pub use qr_test::QrScreen;
+pub use tournament_bracket::TournamentBracketScreen;
 
 pub struct AppInterface<'a> {
     pub events: &'a mut Vec<crate::game::AppEvent>,
@@ -68,10 +76,29 @@ pub struct ScreenMetadata {
     pub show_in_menu: bool,
 }
 
+/// Constructor of a screen
+#[derive(Clone, Copy)]
+pub enum ScreenFactory {
+    BuiltIn(fn() -> Box<dyn ScreenWidget>),
+    /// Screen of a plugin library, see `crate::plugin_loader`
+    #[cfg(not(target_arch = "wasm32"))]
+    Plugin(&'static crate::plugin_loader::PluginScreen),
+}
+
+impl ScreenFactory {
+    pub fn create(self) -> Box<dyn ScreenWidget> {
+        match self {
+            Self::BuiltIn(create) => create(),
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Plugin(screen) => screen.create(),
+        }
+    }
+}
+
 /// A registered screen entry holding metadata and a factory
 pub struct RegisteredScreen {
     pub meta: ScreenMetadata,
-    pub factory: fn() -> Box<dyn ScreenWidget>,
+    pub factory: ScreenFactory,
 }
 
 /// Screen registry for managing screen instances and metadata
@@ -87,11 +114,30 @@ impl ScreenRegistry {
             meta.path,
             RegisteredScreen {
                 meta,
-                factory: T::create,
+                factory: ScreenFactory::BuiltIn(T::create),
             },
         );
     }
 
+    /// Register the screens of the loaded plugins. They cannot replace
+    /// built-in screens.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn register_plugins(&mut self) {
+        for screen in crate::plugin_loader::plugin_screens() {
+            if self.by_path.contains_key(screen.meta.path) {
+                crate::sprintln!("Plugin screen {} is taken already", screen.meta.path);
+                continue;
+            }
+            self.by_path.insert(
+                screen.meta.path,
+                RegisteredScreen {
+                    meta: screen.meta,
+                    factory: ScreenFactory::Plugin(screen),
+                },
+            );
+        }
+    }
+
     pub fn new() -> Self {
         let mut reg = Self {
             by_path: std::collections::HashMap::new(),
@@ -103,11 +149,17 @@ impl ScreenRegistry {
         reg.register::<Game<DirectoryCardType>>();
         reg.register::<PairingScreen>();
         reg.register::<ArticlesScreen>();
+        reg.register::<AchievementsScreen>();
+        reg.register::<LeaderboardScreen>();
+        reg.register::<IrohDiscoveryScreen>();
         reg.register::<QrScreen>();
         reg.register::<QrTestTransmit>();
         reg.register::<QrTestReceive>();
         reg.register::<PokerOnlineScreen>();
+        reg.register::<TournamentBracketScreen>();
         reg.register::<ExampleScreen>();
+        #[cfg(not(target_arch = "wasm32"))]
+        reg.register_plugins();
 
         reg
     }
@@ -124,7 +176,7 @@ impl ScreenRegistry {
     }
 
     /// Get a screen factory by path
-    pub fn factory_by_path(&self, path: &str) -> Option<fn() -> Box<dyn ScreenWidget>> {
+    pub fn factory_by_path(&self, path: &str) -> Option<ScreenFactory> {
         let key = if path.is_empty() { "/" } else { path };
         self.by_path.get(key).map(|r| r.factory)
     }
@@ -141,6 +193,18 @@ impl ScreenRegistry {
         v.sort_by_key(|m| m.path);
         v
     }
+
+    /// Position of a screen in the menu ordering. The main menu comes before
+    /// every entry; screens hidden from the menu have no position.
+    pub fn menu_position(&self, path: &str) -> Option<usize> {
+        if path.is_empty() || path == "/" {
+            return Some(0);
+        }
+        self.menu_metas()
+            .iter()
+            .position(|m| m.path == path)
+            .map(|i| i + 1)
+    }
 }
 
 impl Default for ScreenRegistry {
@@ -1,124 +1,201 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use eframe::Frame;
-use egui::{vec2, Align, Button, Color32, Grid, Layout, RichText, ScrollArea};
+use egui::{vec2, Color32, ColorImage, Image, RichText, TextureHandle, TextureOptions};
+use image::Luma;
+use mcg_shared::{PairingInvite, TableId, DEFAULT_TABLE_ID};
+use qrcode::QrCode;
 
 use super::{AppInterface, ScreenDef, ScreenMetadata, ScreenWidget};
-use crate::sprintln;
+use crate::game::AppEvent;
+use crate::qr_scanner::QrScannerPopup;
 use crate::utils::emoji_hash;
 
+/// Edge length of the invite QR code on screen
+const QR_SIZE: f32 = 320.0;
+
+/// Which side of the pairing the local player is on
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum PairingRole {
+    /// Player A shows an invite to their table
+    #[default]
+    Host,
+    /// Player B scans or pastes the invite
+    Join,
+}
+
+/// Pair two players through a QR code: the host shows an invite holding the
+/// server's address, iroh node id and table, the other player scans it, and
+/// both continue at that table on the poker screen.
 #[derive(Default)]
-pub struct PairingScreen;
+pub struct QrCodePairingFlow {
+    role: PairingRole,
+    /// Table to invite to; starts at the table the client is seated at
+    table_id: Option<TableId>,
+    invite: Option<PairingInvite>,
+    qr_texture: Option<TextureHandle>,
+    fetch_result: Rc<RefCell<Option<Result<PairingInvite, String>>>>,
+    loading: bool,
+    error: Option<String>,
+    scanner: QrScannerPopup,
+    scanned: String,
+    scanned_raw: Vec<u8>,
+    code_input: String,
+}
+
+impl QrCodePairingFlow {
+    fn request_invite(&mut self, server_address: String) {
+        let result_slot = self.fetch_result.clone();
+        self.loading = true;
+        self.error = None;
+        let table_id = self.table_id.unwrap_or(DEFAULT_TABLE_ID);
+        crate::effects::fetch_pairing_invite_effect(server_address, table_id, move |result| {
+            *result_slot.borrow_mut() = Some(result);
+        });
+    }
+
+    /// Pick up a finished invite request and render its QR code.
+    fn apply_fetch_result(&mut self, ctx: &egui::Context) {
+        let Some(result) = self.fetch_result.borrow_mut().take() else {
+            return;
+        };
+        self.loading = false;
+        match result {
+            Ok(invite) => {
+                self.qr_texture = qr_texture(ctx, &invite.to_code());
+                self.invite = Some(invite);
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    /// Hand the invite to the poker screen, which connects and joins the table.
+    fn join(app_interface: &mut AppInterface, invite: PairingInvite) {
+        app_interface.state().session.pending_invite = Some(invite);
+        app_interface.queue_event(AppEvent::ChangeRoute("/poker-online".to_string()));
+    }
+
+    fn host_ui(&mut self, app_interface: &mut AppInterface, ui: &mut egui::Ui) {
+        ui.label("Show this code to the player you want to invite.");
+        let seated_at = app_interface.state().session.table_id;
+        let mut table_id = self
+            .table_id
+            .unwrap_or(seated_at.unwrap_or(DEFAULT_TABLE_ID));
+        let mut request = false;
+        ui.horizontal(|ui| {
+            ui.label("Table:");
+            ui.add(egui::DragValue::new(&mut table_id));
+            let label = if self.invite.is_some() {
+                "Refresh invite"
+            } else {
+                "Create invite"
+            };
+            if ui
+                .add_enabled(!self.loading, egui::Button::new(label))
+                .clicked()
+            {
+                request = true;
+            }
+            if self.loading {
+                ui.spinner();
+            }
+        });
+        self.table_id = Some(table_id);
+        if request {
+            let server_address = app_interface.state().settings.server_address.clone();
+            self.request_invite(server_address);
+        }
+        ui.add_space(8.0);
+
+        let Some(invite) = self.invite.clone() else {
+            return;
+        };
+        let code = invite.to_code();
+        ui.vertical_centered(|ui| {
+            if let Some(texture) = &self.qr_texture {
+                ui.add(Image::from_texture(texture).fit_to_exact_size(vec2(QR_SIZE, QR_SIZE)));
+            }
+            ui.add_space(8.0);
+            ui.label(RichText::new(&code).monospace());
+            ui.label(format!(
+                "Server {}  {}",
+                invite.server_address,
+                emoji_hash(invite.node_id.as_bytes(), ui.ctx())
+            ))
+            .on_hover_text("Both players should see the same symbols");
+            ui.horizontal(|ui| {
+                if ui.button("📋 Copy invite code").clicked() {
+                    ui.ctx().copy_text(code.clone());
+                }
+                if ui.button("▶ Go to table").clicked() {
+                    Self::join(app_interface, invite.clone());
+                }
+            });
+        });
+        ui.add_space(8.0);
+        ui.label("1. The other player opens Pairing → Join and scans this code.");
+        ui.label("2. Click \"Go to table\" to take your own seat.");
+    }
+
+    fn join_ui(&mut self, app_interface: &mut AppInterface, ui: &mut egui::Ui) {
+        ui.label("Scan the QR code on the host's screen, or paste their invite code.");
+        ui.horizontal(|ui| {
+            let ctx = ui.ctx().clone();
+            self.scanner
+                .button_and_popup(ui, &ctx, &mut self.scanned, &mut self.scanned_raw);
+            ui.text_edit_singleline(&mut self.code_input);
+            if ui.button("Join").clicked() {
+                self.scanned = std::mem::take(&mut self.code_input);
+            }
+        });
+
+        if self.scanned.is_empty() {
+            return;
+        }
+        let code = std::mem::take(&mut self.scanned);
+        match PairingInvite::parse(&code) {
+            Some(invite) => Self::join(app_interface, invite),
+            None => self.error = Some(format!("Not an invite code: {}", code)),
+        }
+    }
+}
+
+/// Render `text` as a QR code texture.
+fn qr_texture(ctx: &egui::Context, text: &str) -> Option<TextureHandle> {
+    let qr = QrCode::new(text.as_bytes()).ok()?;
+    let img = qr.render::<Luma<u8>>().build();
+    let size = [img.width() as usize, img.height() as usize];
+    let color_img = ColorImage::from_gray(size, img.as_raw());
+    // Nearest filtering keeps the modules crisp when scaled up
+    Some(ctx.load_texture("pairing_qr", color_img, TextureOptions::NEAREST))
+}
+
+#[derive(Default)]
+pub struct PairingScreen {
+    flow: QrCodePairingFlow,
+}
 
 impl ScreenWidget for PairingScreen {
     fn ui(&mut self, app_interface: &mut AppInterface, ui: &mut egui::Ui, _frame: &mut Frame) {
-        let app_state = &mut app_interface.app_state;
+        let flow = &mut self.flow;
+        flow.apply_fetch_result(ui.ctx());
+
         ui.heading("Player Pairing");
-        ui.with_layout(Layout::right_to_left(Align::TOP), |ui| {
-            // Global Back button is provided by the layout
-            ui.add_space(0.0);
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut flow.role, PairingRole::Host, "📱 Invite");
+            ui.selectable_value(&mut flow.role, PairingRole::Join, "📷 Join");
         });
+        ui.separator();
 
-        let players = app_state.ui.pairing_players.clone();
-
-        ScrollArea::vertical()
-            .auto_shrink([false, false])
-            .show(ui, |ui| {
-                Grid::new("player_grid")
-                    .num_columns(3)
-                    .spacing([20.0, 10.0])
-                    .striped(true)
-                    .show(ui, |ui| {
-                        ui.strong("Player");
-                        ui.strong("Status");
-                        ui.strong("Action");
-                        ui.end_row();
-                        for player in players.iter() {
-                            ui.horizontal(|ui| {
-                                let icon = if player.paired { "👥" } else { "👤" };
-                                ui.label(RichText::new(icon).size(24.0));
-                                ui.label(&player.name);
-                            });
-                            let status_text = if player.paired {
-                                RichText::new("Paired").color(Color32::GREEN)
-                            } else {
-                                RichText::new("Not Paired").color(Color32::RED)
-                            };
-                            ui.label(status_text);
-                            let button_text = if player.paired { "Unpair" } else { "Pair" };
-                            let button_color = if player.paired {
-                                Color32::from_rgb(255, 180, 180)
-                            } else {
-                                Color32::from_rgb(180, 255, 180)
-                            };
-                            if ui
-                                .add(
-                                    Button::new(RichText::new(button_text).color(Color32::BLACK))
-                                        .fill(button_color)
-                                        .min_size(vec2(80.0, 24.0)),
-                                )
-                                .clicked()
-                            {
-                                let pname = player.name.clone();
-                                let action = !player.paired;
-                                app_state.ui.pairing_confirm_player = Some(pname.clone());
-                                app_state.ui.pairing_confirm_action = Some(action);
-                            }
-                            ui.end_row();
-                        }
-                    });
-            });
+        match flow.role {
+            PairingRole::Host => flow.host_ui(app_interface, ui),
+            PairingRole::Join => flow.join_ui(app_interface, ui),
+        }
 
-        // Render confirmation window if requested in shared state
-        if let (Some(player_name), Some(pair_action)) = (
-            app_state.ui.pairing_confirm_player.clone(),
-            app_state.ui.pairing_confirm_action,
-        ) {
-            let action_text = if pair_action { "pair" } else { "unpair" };
-            let player_name_clone = player_name.clone();
-            let player_hash = emoji_hash(player_name_clone.as_bytes(), ui.ctx());
-            egui::Window::new(format!("Confirm {}", action_text))
-                .collapsible(false)
-                .resizable(false)
-                .min_width(300.0)
-                .show(ui.ctx(), |ui| {
-                    ui.vertical_centered(|ui| {
-                        ui.add_space(8.0);
-                        ui.label(RichText::new(&player_hash).size(48.0).strong());
-                        ui.add_space(8.0);
-                    });
-                    ui.separator();
-                    ui.label(format!(
-                        "Are you sure you want to {} {}?",
-                        action_text, player_name_clone
-                    ));
-                    ui.horizontal(|ui| {
-                        if ui.button("Cancel").clicked() {
-                            app_state.ui.pairing_confirm_player = None;
-                            app_state.ui.pairing_confirm_action = None;
-                        }
-                        let mut perform_action = false;
-                        if ui.button("Confirm").clicked() {
-                            perform_action = true;
-                        }
-                        if perform_action {
-                            let target = player_name_clone.clone();
-                            let _pair_action_local = pair_action;
-                            for p in app_state.ui.pairing_players.iter_mut() {
-                                if p.name == target {
-                                    p.paired = pair_action;
-                                    sprintln!(
-                                        "Player {} ({}) is now {}",
-                                        p.name,
-                                        emoji_hash(p.name.as_bytes(), ui.ctx()),
-                                        if p.paired { "paired" } else { "unpaired" }
-                                    );
-                                    break;
-                                }
-                            }
-                            app_state.ui.pairing_confirm_player = None;
-                            app_state.ui.pairing_confirm_action = None;
-                        }
-                    });
-                });
+        if let Some(err) = &flow.error {
+            ui.add_space(8.0);
+            ui.label(RichText::new(err).color(Color32::RED));
         }
     }
 }
@@ -128,6 +205,6 @@ crate::impl_screen_def!(
     "/pairing",
     "Pairing",
     "🔗",
-    "Player pairing demo",
+    "Invite another player to your table with a QR code",
     true
 );
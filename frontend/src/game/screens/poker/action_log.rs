@@ -0,0 +1,473 @@
+//! Action log of the current hand as a filterable, sortable table.
+
+use std::cmp::Reverse;
+
+use egui::{Color32, RichText, Ui};
+use egui_extras::{Column, TableBuilder};
+use mcg_shared::{
+    ActionEvent, ActionKind, BlindKind, Card, GameAction, GameStatePublic, PlayerId, Stage,
+};
+
+use super::ui_components::{
+    action_kind_text, card_text, category_text, name_of, stage_to_str, PlayerColorScheme,
+};
+use crate::i18n::*;
+use crate::utils::Clock;
+
+const ROW_HEIGHT: f32 = 18.0;
+const HEADER_HEIGHT: f32 = 20.0;
+const DEALER_COLOR: Color32 = Color32::from_rgb(150, 150, 150);
+const ALL_IN_BACKGROUND: Color32 = Color32::from_rgb(90, 20, 20);
+
+/// Column the table is sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogSort {
+    /// Newest entry first
+    #[default]
+    Time,
+    Player,
+    Action,
+    /// Largest amount first, entries without an amount last
+    Amount,
+    Stage,
+}
+
+impl LogSort {
+    /// Columns with the i18n key of their title
+    const COLUMNS: [(LogSort, &'static str); 5] = [
+        (LogSort::Time, I18N_LOG_COLUMN_TIME),
+        (LogSort::Player, I18N_LOG_COLUMN_PLAYER),
+        (LogSort::Action, I18N_LOG_COLUMN_ACTION),
+        (LogSort::Amount, I18N_LOG_COLUMN_AMOUNT),
+        (LogSort::Stage, I18N_LOG_COLUMN_STAGE),
+    ];
+}
+
+/// One table row, built from one entry of the action log
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayRow {
+    /// Seconds since the hand started, as seen by this client
+    pub secs: f64,
+    /// Acting player, empty for dealer events
+    pub player: String,
+    /// Color of the acting player, see `PlayerColorScheme`
+    pub player_color: Color32,
+    pub action: String,
+    pub amount: Option<u32>,
+    pub stage: Stage,
+    pub color: Color32,
+    /// Whether the row is an action of the local player
+    pub is_you: bool,
+    /// Explicit all-in, drawn to stand out from the other actions
+    pub all_in: bool,
+}
+
+/// Table of the action log with a filter above it. The server does not
+/// timestamp log entries, so the time column shows when this client first
+/// saw each entry, relative to the first entry of the hand.
+#[derive(Debug, Default)]
+pub struct ActionLogTable {
+    /// Substring matched against player names and actions
+    pub filter: String,
+    pub sort: LogSort,
+    /// When each log entry was first seen, by index
+    seen_ms: Vec<f64>,
+}
+
+fn stage_index(stage: Stage) -> u8 {
+    match stage {
+        Stage::Preflop => 0,
+        Stage::Flop => 1,
+        Stage::Turn => 2,
+        Stage::River => 3,
+        Stage::Showdown => 4,
+    }
+}
+
+/// Action type and amount of a player action; raises show the new bet.
+fn player_action(action: &ActionKind) -> (&'static str, Option<u32>) {
+    match *action {
+        ActionKind::Fold => (t(I18N_LOG_FOLD), None),
+        ActionKind::Check => (t(I18N_LOG_CHECK), None),
+        ActionKind::Call(n) => (t(I18N_LOG_CALL), Some(n)),
+        ActionKind::Bet(n) => (t(I18N_LOG_BET), Some(n)),
+        ActionKind::Raise { to, .. } => (t(I18N_LOG_RAISE), Some(to)),
+        ActionKind::AllIn { amount } => (t(I18N_LOG_ALL_IN), Some(amount)),
+        ActionKind::PostBlind {
+            kind: BlindKind::SmallBlind,
+            amount,
+        } => (t(I18N_LOG_SMALL_BLIND), Some(amount)),
+        ActionKind::PostBlind {
+            kind: BlindKind::BigBlind,
+            amount,
+        } => (t(I18N_LOG_BIG_BLIND), Some(amount)),
+    }
+}
+
+fn board_text(cards: &[Card]) -> String {
+    let name = match cards.len() {
+        3 => t(I18N_LOG_FLOP),
+        4 => t(I18N_LOG_TURN),
+        5 => t(I18N_LOG_RIVER),
+        _ => t(I18N_LOG_BOARD),
+    };
+    let shown = if cards.len() > 3 {
+        &cards[cards.len() - 1..]
+    } else {
+        cards
+    };
+    let cards: Vec<String> = shown.iter().map(|&c| card_text(c)).collect();
+    format!("{} {}", name, cards.join(" "))
+}
+
+impl ActionLogTable {
+    /// Record when new log entries arrive; call once per frame with the
+    /// latest state.
+    pub fn update(&mut self, gs: &GameStatePublic, clock: &impl Clock) {
+        // The log restarts with every hand
+        if gs.action_log.len() < self.seen_ms.len() {
+            self.seen_ms.clear();
+        }
+        let now = clock.now_ms();
+        self.seen_ms.resize(gs.action_log.len(), now);
+    }
+
+    /// One row per log entry in log order. Stage changes only set the stage
+    /// column of the rows after them.
+    pub fn rows(&self, gs: &GameStatePublic, you: PlayerId) -> Vec<DisplayRow> {
+        let players = &gs.players;
+        let colors = PlayerColorScheme::new(gs, you);
+        let start = self.seen_ms.first().copied();
+        let mut stage = Stage::Preflop;
+        let mut rows = Vec::with_capacity(gs.action_log.len());
+        for (index, entry) in gs.action_log.iter().enumerate() {
+            let secs = match (start, self.seen_ms.get(index)) {
+                (Some(start), Some(&seen)) => ((seen - start) / 1000.0).max(0.0),
+                _ => 0.0,
+            };
+            let mut push = |player: Option<PlayerId>, action: String, amount, color| {
+                rows.push(DisplayRow {
+                    secs,
+                    player: player.map(|id| name_of(players, id)).unwrap_or_default(),
+                    player_color: player.map_or(DEALER_COLOR, |id| colors.color(id)),
+                    action,
+                    amount,
+                    stage,
+                    color,
+                    is_you: player == Some(you),
+                    all_in: matches!(
+                        entry,
+                        ActionEvent::PlayerAction {
+                            action: ActionKind::AllIn { .. },
+                            ..
+                        }
+                    ),
+                })
+            };
+            match entry {
+                ActionEvent::PlayerAction { player_id, action } => {
+                    let (text, amount) = player_action(action);
+                    let (_, color) = action_kind_text(action);
+                    push(Some(*player_id), text.to_owned(), amount, color);
+                }
+                ActionEvent::GameAction(GameAction::StageChanged(s)) => stage = *s,
+                ActionEvent::GameAction(GameAction::DealtHole { player_id }) => {
+                    let action = t(I18N_LOG_DEALT_HOLE).to_owned();
+                    push(Some(*player_id), action, None, DEALER_COLOR);
+                }
+                ActionEvent::GameAction(GameAction::DealtCommunity { cards }) => {
+                    push(None, board_text(cards), None, DEALER_COLOR);
+                }
+                ActionEvent::GameAction(GameAction::Showdown { hand_results }) => {
+                    for hr in hand_results {
+                        let action = tf(I18N_LOG_SHOWS, category_text(hr.rank.category));
+                        let color = Color32::from_rgb(180, 100, 220);
+                        push(Some(hr.player_id), action, None, color);
+                    }
+                }
+                ActionEvent::GameAction(GameAction::PotAwarded { winners, amount }) => {
+                    let color = Color32::from_rgb(240, 200, 80);
+                    for &winner in winners {
+                        let action = t(I18N_LOG_WINS_POT).to_owned();
+                        push(Some(winner), action, Some(*amount), color);
+                    }
+                }
+                ActionEvent::GameAction(GameAction::PlayerEliminated {
+                    player_id,
+                    bounty_won_by,
+                    bounty,
+                }) => {
+                    let action = match bounty_won_by {
+                        Some(by) => tf(I18N_LOG_ELIMINATED_BY, name_of(players, *by)),
+                        None => t(I18N_LOG_ELIMINATED).to_owned(),
+                    };
+                    let amount = (*bounty > 0).then_some(*bounty);
+                    push(Some(*player_id), action, amount, Color32::LIGHT_RED);
+                }
+                ActionEvent::GameAction(GameAction::PlayerKicked { player_id }) => {
+                    let action = t(I18N_LOG_KICKED).to_owned();
+                    push(Some(*player_id), action, None, Color32::LIGHT_RED);
+                }
+            }
+        }
+        rows
+    }
+
+    /// Rows matching the filter, in the selected order.
+    pub fn visible_rows(&self, gs: &GameStatePublic, you: PlayerId) -> Vec<DisplayRow> {
+        let needle = self.filter.trim().to_lowercase();
+        let mut rows = self.rows(gs, you);
+        rows.retain(|r| {
+            needle.is_empty()
+                || r.player.to_lowercase().contains(&needle)
+                || r.action.to_lowercase().contains(&needle)
+        });
+        // Newest first breaks ties of every other order
+        rows.reverse();
+        match self.sort {
+            LogSort::Time => {}
+            LogSort::Player => rows.sort_by(|a, b| a.player.cmp(&b.player)),
+            LogSort::Action => rows.sort_by(|a, b| a.action.cmp(&b.action)),
+            LogSort::Amount => rows.sort_by_key(|r| Reverse(r.amount)),
+            LogSort::Stage => rows.sort_by_key(|r| stage_index(r.stage)),
+        }
+        rows
+    }
+
+    pub fn show(&mut self, ui: &mut Ui, gs: &GameStatePublic, you: PlayerId) {
+        ui.horizontal(|ui| {
+            ui.label("🔍");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.filter)
+                    .hint_text(t(I18N_LOG_FILTER_HINT))
+                    .desired_width(180.0),
+            );
+            if !self.filter.is_empty() && ui.small_button("✖").clicked() {
+                self.filter.clear();
+            }
+        });
+        let rows = self.visible_rows(gs, you);
+        TableBuilder::new(ui)
+            .id_salt("action_log_table")
+            .striped(true)
+            .max_scroll_height(200.0)
+            .column(Column::auto().at_least(40.0))
+            .column(Column::auto().at_least(70.0))
+            .column(Column::remainder().at_least(90.0))
+            .column(Column::auto().at_least(50.0))
+            .column(Column::auto().at_least(50.0))
+            .header(HEADER_HEIGHT, |mut header| {
+                for (sort, key) in LogSort::COLUMNS {
+                    let title = t(key);
+                    header.col(|ui| {
+                        let selected = self.sort == sort;
+                        let text = if selected {
+                            format!("{} ⏷", title)
+                        } else {
+                            title.to_owned()
+                        };
+                        if ui
+                            .selectable_label(selected, RichText::new(text).strong())
+                            .on_hover_text(tf(I18N_LOG_SORT_HINT, title.to_lowercase()))
+                            .clicked()
+                        {
+                            self.sort = sort;
+                        }
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(ROW_HEIGHT, rows.len(), |mut row| {
+                    let r = &rows[row.index()];
+                    row.col(|ui| {
+                        let secs = r.secs as u64;
+                        ui.monospace(format!("{}:{:02}", secs / 60, secs % 60));
+                    });
+                    row.col(|ui| {
+                        let name = RichText::new(&r.player).color(r.player_color);
+                        ui.label(if r.is_you { name.strong() } else { name });
+                    });
+                    row.col(|ui| {
+                        let action = RichText::new(&r.action).color(r.color);
+                        ui.label(if r.all_in {
+                            action.strong().background_color(ALL_IN_BACKGROUND)
+                        } else {
+                            action
+                        });
+                    });
+                    row.col(|ui| {
+                        if let Some(amount) = r.amount {
+                            let amount = RichText::new(amount.to_string()).monospace();
+                            ui.label(if r.all_in {
+                                amount.strong().color(r.color)
+                            } else {
+                                amount
+                            });
+                        }
+                    });
+                    row.col(|ui| {
+                        ui.label(stage_to_str(r.stage));
+                    });
+                });
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::{BettingLimit, PlayerPublic};
+    use std::cell::Cell;
+
+    struct MockClock(Cell<f64>);
+
+    impl Clock for MockClock {
+        fn now_ms(&self) -> f64 {
+            self.0.get()
+        }
+    }
+
+    fn state(action_log: Vec<ActionEvent>) -> GameStatePublic {
+        let player = |id: usize, name: &str| PlayerPublic {
+            id: PlayerId(id),
+            name: name.to_owned(),
+            stack: 1000,
+            cards: None,
+            has_folded: false,
+            all_in: false,
+            bet_this_round: 0,
+            avatar_color: None,
+            bounty: None,
+            seat_number: id as u8,
+            must_post_blind: false,
+        };
+        GameStatePublic {
+            players: vec![player(0, "Alice"), player(1, "Bob")],
+            community: Vec::new(),
+            pot: 0,
+            sb: 5,
+            bb: 10,
+            to_act: PlayerId(0),
+            stage: Stage::Flop,
+            winner_ids: Vec::new(),
+            action_log,
+            current_bet: 0,
+            min_raise: 10,
+            betting_limit: BettingLimit::NoLimit,
+            blind_level: None,
+            pot_equities: None,
+            pot_breakdown: Vec::new(),
+            dealer: None,
+            hand_number: 0,
+            hands_remaining: None,
+        }
+    }
+
+    fn hand() -> Vec<ActionEvent> {
+        let blind = |id, kind, amount| {
+            ActionEvent::player(PlayerId(id), ActionKind::PostBlind { kind, amount })
+        };
+        vec![
+            blind(0, BlindKind::SmallBlind, 5),
+            blind(1, BlindKind::BigBlind, 10),
+            ActionEvent::player(PlayerId(0), ActionKind::Call(5)),
+            ActionEvent::player(PlayerId(1), ActionKind::Check),
+            ActionEvent::game(GameAction::StageChanged(Stage::Flop)),
+            ActionEvent::player(PlayerId(0), ActionKind::Bet(40)),
+            ActionEvent::player(PlayerId(1), ActionKind::Fold),
+        ]
+    }
+
+    #[test]
+    fn rows_carry_player_action_amount_and_stage() {
+        let table = ActionLogTable::default();
+        let rows = table.rows(&state(hand()), PlayerId(0));
+        let summary: Vec<_> = rows
+            .iter()
+            .map(|r| (r.player.as_str(), r.action.as_str(), r.amount, r.stage))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Alice", "Small blind", Some(5), Stage::Preflop),
+                ("Bob", "Big blind", Some(10), Stage::Preflop),
+                ("Alice", "Call", Some(5), Stage::Preflop),
+                ("Bob", "Check", None, Stage::Preflop),
+                ("Alice", "Bet", Some(40), Stage::Flop),
+                ("Bob", "Fold", None, Stage::Flop),
+            ]
+        );
+        assert!(rows[0].is_you && !rows[1].is_you);
+    }
+
+    #[test]
+    fn all_in_rows_stand_out() {
+        let mut log = hand();
+        log.pop();
+        log.push(ActionEvent::player(
+            PlayerId(1),
+            ActionKind::AllIn { amount: 990 },
+        ));
+        let rows = ActionLogTable::default().rows(&state(log), PlayerId(0));
+        let (last, rest) = rows.split_last().unwrap();
+        assert_eq!(
+            (last.action.as_str(), last.amount, last.all_in),
+            ("ALL-IN!", Some(990), true)
+        );
+        assert!(rest.iter().all(|r| !r.all_in));
+    }
+
+    #[test]
+    fn filter_matches_player_or_action_and_amount_sorts_descending() {
+        let gs = state(hand());
+        let mut table = ActionLogTable {
+            filter: "bob".into(),
+            ..Default::default()
+        };
+        let actions: Vec<_> = table
+            .visible_rows(&gs, PlayerId(0))
+            .into_iter()
+            .map(|r| r.action)
+            .collect();
+        assert_eq!(actions, vec!["Fold", "Check", "Big blind"]);
+
+        table.filter = "BLIND".into();
+        assert_eq!(table.visible_rows(&gs, PlayerId(0)).len(), 2);
+
+        table.filter.clear();
+        table.sort = LogSort::Amount;
+        let amounts: Vec<_> = table
+            .visible_rows(&gs, PlayerId(0))
+            .into_iter()
+            .map(|r| r.amount)
+            .collect();
+        assert_eq!(
+            amounts,
+            vec![Some(40), Some(10), Some(5), Some(5), None, None]
+        );
+    }
+
+    #[test]
+    fn times_are_relative_to_the_first_entry_of_the_hand() {
+        let clock = MockClock(Cell::new(5_000.0));
+        let mut table = ActionLogTable::default();
+        let mut log = hand();
+        log.truncate(2);
+        table.update(&state(log.clone()), &clock);
+
+        clock.0.set(12_500.0);
+        log.push(ActionEvent::player(PlayerId(0), ActionKind::Call(5)));
+        table.update(&state(log.clone()), &clock);
+        let secs: Vec<_> = table
+            .rows(&state(log), PlayerId(0))
+            .iter()
+            .map(|r| r.secs)
+            .collect();
+        assert_eq!(secs, vec![0.0, 0.0, 7.5]);
+
+        // A shorter log is a new hand
+        clock.0.set(20_000.0);
+        table.update(&state(hand()[..1].to_vec()), &clock);
+        assert_eq!(table.seen_ms, vec![20_000.0]);
+    }
+}
@@ -0,0 +1,210 @@
+use std::cell::Cell;
+use std::time::Duration;
+
+use egui::{Align2, Color32, Context, RichText};
+use mcg_shared::{ActionEvent, Frontend2BackendMsg, GameStatePublic, PlayerId, Stage};
+
+use crate::game::websocket::MessageSender;
+use crate::utils::Clock;
+
+/// How long the local player may take before they are folded while away
+pub const AUTO_FOLD_AFTER_MS: f64 = 10_000.0;
+/// How long the "Auto-folded" toast stays up
+const TOAST_MS: f64 = 3_000.0;
+
+/// Identifies a turn: the log as it was when the turn started. A turn ends
+/// with an action, so the next turn of the same player has a different log
+/// even when the server skips the states in between.
+type TurnKey = (usize, Option<ActionEvent>);
+
+fn turn_key(gs: &GameStatePublic) -> TurnKey {
+    (gs.action_log.len(), gs.action_log.last().cloned())
+}
+
+/// Countdown that folds the local player when they do not act in time.
+#[derive(Debug, Default)]
+pub struct AutoAct {
+    /// Current turn of the local player and when it started
+    turn: Option<(TurnKey, f64)>,
+    /// The countdown of the current turn was stopped by acting, or has fired
+    stopped: Cell<bool>,
+    /// When the countdown last fired, for the toast
+    fired_ms: Option<f64>,
+}
+
+impl AutoAct {
+    /// Follow the turns of `you`; call once per frame with the latest state.
+    /// Returns true once per turn when the countdown runs out and the player
+    /// should be folded.
+    pub fn update(
+        &mut self,
+        gs: &GameStatePublic,
+        you: PlayerId,
+        enabled: bool,
+        clock: &impl Clock,
+    ) -> bool {
+        let now = clock.now_ms();
+        if !enabled || gs.to_act != you || gs.stage == Stage::Showdown {
+            self.turn = None;
+            return false;
+        }
+        let key = turn_key(gs);
+        match &self.turn {
+            Some((turn, _)) if *turn == key => {}
+            _ => {
+                self.turn = Some((key, now));
+                self.stopped.set(false);
+            }
+        }
+        let Some((_, started)) = self.turn else {
+            return false;
+        };
+        if self.stopped.get() || now - started < AUTO_FOLD_AFTER_MS {
+            return false;
+        }
+        self.stopped.set(true);
+        self.fired_ms = Some(now);
+        true
+    }
+
+    /// Stop the countdown of the current turn; the player acted themselves.
+    pub fn cancel(&self) {
+        self.stopped.set(true);
+    }
+
+    /// Seconds left before the player is folded, while the countdown runs.
+    pub fn remaining_secs(&self, clock: &impl Clock) -> Option<f64> {
+        let (_, started) = self.turn.as_ref().filter(|_| !self.stopped.get())?;
+        Some(((started + AUTO_FOLD_AFTER_MS - clock.now_ms()) / 1000.0).max(0.0))
+    }
+
+    /// Countdown label for the action row; repaints while it runs.
+    pub fn show_countdown(&self, ui: &mut egui::Ui, clock: &impl Clock) {
+        if let Some(secs) = self.remaining_secs(clock) {
+            ui.label(
+                RichText::new(format!("⏳ Auto-fold in {:.0}s", secs.ceil()))
+                    .color(Color32::from_rgb(230, 160, 60)),
+            );
+            ui.ctx().request_repaint_after(Duration::from_millis(250));
+        }
+    }
+
+    /// "Auto-folded" toast at the bottom of the screen, for a few seconds
+    /// after the countdown fired.
+    pub fn show_toast(&self, ctx: &Context, clock: &impl Clock) {
+        let Some(fired) = self.fired_ms else {
+            return;
+        };
+        let left = fired + TOAST_MS - clock.now_ms();
+        if left <= 0.0 {
+            return;
+        }
+        egui::Area::new(egui::Id::new("auto_fold_toast"))
+            .anchor(Align2::CENTER_BOTTOM, egui::vec2(0.0, -24.0))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.label(RichText::new("Auto-folded").strong());
+                });
+            });
+        ctx.request_repaint_after(Duration::from_millis(left as u64));
+    }
+}
+
+/// Sender that stops the countdown whenever the player sends an action.
+pub struct CancelOnAction<'a> {
+    pub sender: &'a dyn MessageSender,
+    pub auto_act: &'a AutoAct,
+}
+
+impl MessageSender for CancelOnAction<'_> {
+    fn send(&self, msg: &Frontend2BackendMsg) {
+        if matches!(msg, Frontend2BackendMsg::Action { .. }) {
+            self.auto_act.cancel();
+        }
+        self.sender.send(msg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::{ActionKind, BettingLimit};
+
+    struct MockClock(Cell<f64>);
+
+    impl Clock for MockClock {
+        fn now_ms(&self) -> f64 {
+            self.0.get()
+        }
+    }
+
+    fn state(to_act: usize, log: Vec<ActionEvent>) -> GameStatePublic {
+        GameStatePublic {
+            players: Vec::new(),
+            community: Vec::new(),
+            pot: 0,
+            sb: 5,
+            bb: 10,
+            to_act: PlayerId(to_act),
+            stage: Stage::Preflop,
+            winner_ids: Vec::new(),
+            action_log: log,
+            current_bet: 0,
+            min_raise: 0,
+            betting_limit: BettingLimit::NoLimit,
+            blind_level: None,
+            pot_equities: None,
+            pot_breakdown: Vec::new(),
+            dealer: None,
+            hand_number: 0,
+            hands_remaining: None,
+        }
+    }
+
+    fn check(player: usize) -> ActionEvent {
+        ActionEvent::player(PlayerId(player), ActionKind::Check)
+    }
+
+    #[test]
+    fn folds_once_per_turn_after_the_countdown() {
+        let clock = MockClock(Cell::new(0.0));
+        let mut auto_act = AutoAct::default();
+        let you = PlayerId(0);
+        let turn = state(0, vec![check(1)]);
+
+        assert!(!auto_act.update(&turn, you, true, &clock));
+        clock.0.set(6_500.0);
+        assert!(!auto_act.update(&turn, you, true, &clock));
+        assert_eq!(auto_act.remaining_secs(&clock), Some(3.5));
+        clock.0.set(10_000.0);
+        assert!(auto_act.update(&turn, you, true, &clock));
+        assert!(!auto_act.update(&turn, you, true, &clock));
+
+        // The next turn starts a new countdown, even without a state in between
+        let next_turn = state(0, vec![check(1), check(0), check(1)]);
+        assert!(!auto_act.update(&next_turn, you, true, &clock));
+        clock.0.set(20_000.0);
+        assert!(auto_act.update(&next_turn, you, true, &clock));
+    }
+
+    #[test]
+    fn acting_or_disabling_stops_the_countdown() {
+        let clock = MockClock(Cell::new(0.0));
+        let mut auto_act = AutoAct::default();
+        let you = PlayerId(0);
+        let turn = state(0, vec![check(1)]);
+
+        auto_act.update(&turn, you, true, &clock);
+        auto_act.cancel();
+        assert_eq!(auto_act.remaining_secs(&clock), None);
+        clock.0.set(15_000.0);
+        assert!(!auto_act.update(&turn, you, true, &clock));
+
+        let other_turn = state(1, vec![check(0)]);
+        assert!(!auto_act.update(&other_turn, you, true, &clock));
+        let turn = state(0, vec![check(0), check(1)]);
+        assert!(!auto_act.update(&turn, you, false, &clock));
+        clock.0.set(30_000.0);
+        assert!(!auto_act.update(&turn, you, false, &clock));
+    }
+}
@@ -1,8 +1,12 @@
 //! Betting controls and interface for poker games.
 
+use super::shortcuts::Shortcut;
 use crate::game::websocket::MessageSender;
 use egui::{RichText, Ui};
-use mcg_shared::{Frontend2BackendMsg, GameStatePublic, PlayerAction, PlayerId, PlayerPublic};
+use mcg_shared::{
+    BettingLimit, Frontend2BackendMsg, GameStatePublic, PlayerAction, PlayerId, PlayerPublic,
+    Stage, TableId,
+};
 
 /// Manages betting controls state for the poker interface
 #[derive(Clone, Debug, Default)]
@@ -17,6 +21,10 @@ pub struct BettingControls {
     pub max_raise: u32,
     /// Whether to show the betting controls
     pub show_betting_controls: bool,
+    /// Table the actions are sent to
+    pub table_id: TableId,
+    /// Give the amount slider keyboard focus the next time it is drawn
+    pub focus_slider: bool,
 }
 
 impl BettingControls {
@@ -52,6 +60,33 @@ impl BettingControls {
         }
     }
 
+    /// Smallest and largest bet (or raise increment) the betting limit allows.
+    fn bet_range(
+        state: &GameStatePublic,
+        player_id: PlayerId,
+        player: &PlayerPublic,
+    ) -> (u32, u32) {
+        let min_bet = if state.current_bet == 0 {
+            state.bb
+        } else {
+            state.min_raise
+        };
+        match state.betting_limit {
+            BettingLimit::NoLimit => (min_bet, player.stack),
+            BettingLimit::PotLimit => {
+                let pot_after_call = state.pot + Self::calculate_call_amount(state, player_id);
+                (min_bet, player.stack.min(pot_after_call.max(min_bet)))
+            }
+            BettingLimit::FixedLimit { small_bet, big_bet } => {
+                let bet = match state.stage {
+                    Stage::Turn | Stage::River => big_bet,
+                    _ => small_bet,
+                };
+                (bet, bet)
+            }
+        }
+    }
+
     /// Render betting/raising controls with slider and preset buttons
     pub fn render_betting_controls(
         &mut self,
@@ -65,12 +100,7 @@ impl BettingControls {
             ui.label(RichText::new("Betting Options:").strong());
             ui.add_space(4.0);
 
-            let min_bet = if state.current_bet == 0 {
-                state.bb
-            } else {
-                state.min_raise
-            };
-            let max_bet = player.stack;
+            let (min_bet, max_bet) = Self::bet_range(state, player_id, player);
 
             if state.current_bet == 0 {
                 // No current bet - can open bet
@@ -95,21 +125,23 @@ impl BettingControls {
 
         // Slider for custom bet amount
         ui.horizontal(|ui| {
-            ui.label("Bet:");
+            ui.label(Shortcut::Bet.label("Bet:"));
             let mut bet_amount = self.bet_amount as f32;
-            if ui
-                .add(
-                    egui::Slider::new(&mut bet_amount, min_bet as f32..=max_bet as f32)
-                        .suffix(" chips")
-                        .smart_aim(false),
-                )
-                .changed()
-            {
+            let slider = ui.add(
+                egui::Slider::new(&mut bet_amount, min_bet as f32..=max_bet as f32)
+                    .suffix(" chips")
+                    .smart_aim(false),
+            );
+            if std::mem::take(&mut self.focus_slider) {
+                slider.request_focus();
+            }
+            if slider.changed() {
                 self.bet_amount = bet_amount as u32;
             }
 
             if ui.button("Bet").clicked() {
                 conn.send(&Frontend2BackendMsg::Action {
+                    table_id: self.table_id,
                     player_id,
                     action: PlayerAction::Bet(self.bet_amount),
                 });
@@ -123,6 +155,7 @@ impl BettingControls {
         ui.horizontal(|ui| {
             if ui.button("Min Bet").clicked() {
                 conn.send(&Frontend2BackendMsg::Action {
+                    table_id: self.table_id,
                     player_id,
                     action: PlayerAction::Bet(state.bb),
                 });
@@ -131,6 +164,7 @@ impl BettingControls {
             let pot_third = (state.pot / 3).max(state.bb);
             if ui.button("1/3 Pot").clicked() {
                 conn.send(&Frontend2BackendMsg::Action {
+                    table_id: self.table_id,
                     player_id,
                     action: PlayerAction::Bet(pot_third.min(max_bet)),
                 });
@@ -139,6 +173,7 @@ impl BettingControls {
             let pot_half = (state.pot / 2).max(state.bb);
             if ui.button("1/2 Pot").clicked() {
                 conn.send(&Frontend2BackendMsg::Action {
+                    table_id: self.table_id,
                     player_id,
                     action: PlayerAction::Bet(pot_half.min(max_bet)),
                 });
@@ -148,17 +183,11 @@ impl BettingControls {
         ui.horizontal(|ui| {
             if ui.button("Pot Size").clicked() {
                 conn.send(&Frontend2BackendMsg::Action {
+                    table_id: self.table_id,
                     player_id,
                     action: PlayerAction::Bet(state.pot.max(state.bb).min(max_bet)),
                 });
             }
-
-            if max_bet > 0 && ui.button("All-in").clicked() {
-                conn.send(&Frontend2BackendMsg::Action {
-                    player_id,
-                    action: PlayerAction::Bet(max_bet),
-                });
-            }
         });
     }
 
@@ -175,21 +204,23 @@ impl BettingControls {
 
         // Slider for custom raise amount
         ui.horizontal(|ui| {
-            ui.label("Raise:");
+            ui.label(Shortcut::Bet.label("Raise:"));
             let mut raise_amount = self.raise_amount as f32;
-            if ui
-                .add(
-                    egui::Slider::new(&mut raise_amount, min_bet as f32..=max_bet as f32)
-                        .suffix(" chips")
-                        .smart_aim(false),
-                )
-                .changed()
-            {
+            let slider = ui.add(
+                egui::Slider::new(&mut raise_amount, min_bet as f32..=max_bet as f32)
+                    .suffix(" chips")
+                    .smart_aim(false),
+            );
+            if std::mem::take(&mut self.focus_slider) {
+                slider.request_focus();
+            }
+            if slider.changed() {
                 self.raise_amount = raise_amount as u32;
             }
 
             if ui.button("Raise").clicked() {
                 conn.send(&Frontend2BackendMsg::Action {
+                    table_id: self.table_id,
                     player_id,
                     action: PlayerAction::Bet(self.raise_amount),
                 });
@@ -203,6 +234,7 @@ impl BettingControls {
         ui.horizontal(|ui| {
             if min_bet <= max_bet && ui.button("Min Raise").clicked() {
                 conn.send(&Frontend2BackendMsg::Action {
+                    table_id: self.table_id,
                     player_id,
                     action: PlayerAction::Bet(min_bet),
                 });
@@ -211,6 +243,7 @@ impl BettingControls {
             let pot_third = (state.pot / 3).max(min_bet);
             if pot_third <= max_bet && ui.button("Raise 1/3 Pot").clicked() {
                 conn.send(&Frontend2BackendMsg::Action {
+                    table_id: self.table_id,
                     player_id,
                     action: PlayerAction::Bet(pot_third),
                 });
@@ -219,6 +252,7 @@ impl BettingControls {
             let pot_half = (state.pot / 2).max(min_bet);
             if pot_half <= max_bet && ui.button("Raise 1/2 Pot").clicked() {
                 conn.send(&Frontend2BackendMsg::Action {
+                    table_id: self.table_id,
                     player_id,
                     action: PlayerAction::Bet(pot_half),
                 });
@@ -229,17 +263,11 @@ impl BettingControls {
             let pot_size = state.pot.max(min_bet);
             if pot_size <= max_bet && ui.button("Raise Pot").clicked() {
                 conn.send(&Frontend2BackendMsg::Action {
+                    table_id: self.table_id,
                     player_id,
                     action: PlayerAction::Bet(pot_size),
                 });
             }
-
-            if max_bet > 0 && ui.button("All-in").clicked() {
-                conn.send(&Frontend2BackendMsg::Action {
-                    player_id,
-                    action: PlayerAction::Bet(max_bet),
-                });
-            }
         });
     }
 }
@@ -1,16 +1,15 @@
-use crate::game::websocket::WebSocketConnection;
+use crate::game::notifications::NotificationLevel;
+use crate::i18n::{
+    t, I18N_CONNECT_BUTTON, I18N_DISCONNECT_BUTTON, I18N_SERVER_ADDRESS_HINT, I18N_SERVER_LABEL,
+};
 use crate::qr_scanner::QrScannerPopup;
 use crate::store::{ClientState, ConnectionStatus};
-use egui::{Color32, Context, RichText, Ui};
-use mcg_shared::{PlayerConfig, Backend2FrontendMsg};
-use std::collections::VecDeque;
+use egui::{Context, Ui};
 
 pub struct ConnectionManager {
     edit_server_address: String,
     qr_result_raw: Vec<u8>,
     scanner: QrScannerPopup,
-    message_queue: Option<std::rc::Rc<std::cell::RefCell<VecDeque<Backend2FrontendMsg>>>>,
-    error_queue: Option<std::rc::Rc<std::cell::RefCell<VecDeque<String>>>>,
 }
 
 impl ConnectionManager {
@@ -19,89 +18,33 @@ impl ConnectionManager {
             edit_server_address: server_address,
             qr_result_raw: Vec::new(),
             scanner: QrScannerPopup::default(),
-            message_queue: None,
-            error_queue: None,
         }
     }
 
-    pub fn connect(
-        &mut self,
-        conn: &mut WebSocketConnection,
-        app_state: &mut ClientState,
-        ctx: &Context,
-        players: Vec<PlayerConfig>,
-    ) {
+    pub fn connect(&mut self, app_state: &mut ClientState, ctx: &Context) {
         app_state.connection.connection_status = ConnectionStatus::Connecting;
-        app_state.ui.last_error = None;
-        app_state.ui.last_info = Some(format!("Connecting to {}...", self.edit_server_address));
-        app_state.settings.server_address = self.edit_server_address.clone();
-
-        // Create a shared message queue using Rc<RefCell<VecDeque<ServerMsg>>>
-        let message_queue =
-            std::rc::Rc::new(std::cell::RefCell::new(std::collections::VecDeque::<
-                mcg_shared::Backend2FrontendMsg,
-            >::new()));
-        let error_queue = std::rc::Rc::new(std::cell::RefCell::new(std::collections::VecDeque::<
-            String,
-        >::new()));
-
-        // Clone queues and context for each closure
-        let msg_queue_for_msg = message_queue.clone();
-        let error_queue_for_error = error_queue.clone();
-        let error_queue_for_close = error_queue.clone();
-        let ctx_for_msg = ctx.clone();
-        let ctx_for_error = ctx.clone();
-        let ctx_for_close = ctx.clone();
-
-        conn.connect(
-            &self.edit_server_address,
-            players,
-            move |msg: mcg_shared::Backend2FrontendMsg| {
-                // Queue the message safely
-                if let Ok(mut queue) = msg_queue_for_msg.try_borrow_mut() {
-                    queue.push_back(msg);
-                    ctx_for_msg.request_repaint();
-                }
-            },
-            move |error: String| {
-                // Queue the error safely
-                if let Ok(mut queue) = error_queue_for_error.try_borrow_mut() {
-                    queue.push_back(error);
-                    ctx_for_error.request_repaint();
-                }
-            },
-            move |reason: String| {
-                // Queue the close reason safely
-                if let Ok(mut queue) = error_queue_for_close.try_borrow_mut() {
-                    queue.push_back(reason);
-                    ctx_for_close.request_repaint();
-                }
-            },
+        // The heartbeat timeout runs from the start of the connection
+        app_state.connection.last_heartbeat_ms = Some(crate::utils::WasmTimer::now_ms());
+        app_state.session.table_id = None;
+        app_state.notify(
+            NotificationLevel::Info,
+            format!("Connecting to {}...", self.edit_server_address),
         );
-
-        // Store the queues for processing in the update loop
-        self.message_queue = Some(message_queue);
-        self.error_queue = Some(error_queue);
-    }
-
-    /// Process any queued messages from WebSocket callbacks
-    pub fn dispatch_queued_messages(&mut self, app_state: &mut ClientState) {
-        if let Some(queue) = &self.message_queue {
-            if let Ok(mut q) = queue.try_borrow_mut() {
-                while let Some(msg) = q.pop_front() {
-                    app_state.apply_server_msg(msg);
-                }
+        app_state.settings.server_address = self.edit_server_address.clone();
+        if app_state.profile.preferred_server != self.edit_server_address {
+            app_state.profile.preferred_server = self.edit_server_address.clone();
+            if let Err(e) = app_state.profile.save() {
+                crate::sprintln!("{}", e);
             }
         }
 
-        if let Some(queue) = &self.error_queue {
-            if let Ok(mut q) = queue.try_borrow_mut() {
-                while let Some(error) = q.pop_front() {
-                    app_state.ui.last_error = Some(error);
-                    app_state.connection.connection_status = ConnectionStatus::Disconnected;
-                }
-            }
-        }
+        // Always open a new socket, the old one may be dead without having been closed
+        app_state.connection_pool.close();
+        app_state.get_or_create_connection(&self.edit_server_address, ctx);
+    }
+
+    pub fn set_server_address(&mut self, server_address: String) {
+        self.edit_server_address = server_address;
     }
 
     pub fn render_header(&mut self, app_state: &mut ClientState, ui: &mut Ui, ctx: &Context) {
@@ -134,13 +77,6 @@ impl ConnectionManager {
             .show(ui, |ui| {
                 super::player_manager::render_player_setup(ui, ctx);
             });
-
-        if let Some(err) = &app_state.ui.last_error {
-            ui.colored_label(Color32::RED, err);
-        }
-        if let Some(info) = &app_state.ui.last_info {
-            ui.label(RichText::new(info));
-        }
         ui.separator();
     }
 
@@ -156,17 +92,17 @@ impl ConnectionManager {
         if narrow {
             ui.vertical(|ui| {
                 ui.horizontal(|ui| {
-                    if ui.button("Connect").clicked() {
+                    if ui.button(t(I18N_CONNECT_BUTTON)).clicked() {
                         *connect_clicked = true;
                     }
-                    if ui.button("Disconnect").clicked() {
+                    if ui.button(t(I18N_DISCONNECT_BUTTON)).clicked() {
                         *disconnect_clicked = true;
                     }
                 });
                 ui.horizontal(|ui| {
-                    ui.label("Server:");
+                    ui.label(t(I18N_SERVER_LABEL));
                     ui.text_edit_singleline(&mut self.edit_server_address)
-                        .on_hover_text("Server address (IP:PORT)");
+                        .on_hover_text(t(I18N_SERVER_ADDRESS_HINT));
                     self.scanner.button_and_popup(
                         ui,
                         ctx,
@@ -177,9 +113,9 @@ impl ConnectionManager {
             });
         } else {
             ui.horizontal(|ui| {
-                ui.label("Server:");
+                ui.label(t(I18N_SERVER_LABEL));
                 ui.text_edit_singleline(&mut self.edit_server_address)
-                    .on_hover_text("Server address (IP:PORT)");
+                    .on_hover_text(t(I18N_SERVER_ADDRESS_HINT));
                 self.scanner.button_and_popup(
                     ui,
                     ctx,
@@ -187,10 +123,10 @@ impl ConnectionManager {
                     &mut self.qr_result_raw,
                 );
                 ui.add_space(12.0);
-                if ui.button("Connect").clicked() {
+                if ui.button(t(I18N_CONNECT_BUTTON)).clicked() {
                     *connect_clicked = true;
                 }
-                if ui.button("Disconnect").clicked() {
+                if ui.button(t(I18N_DISCONNECT_BUTTON)).clicked() {
                     *disconnect_clicked = true;
                 }
             });
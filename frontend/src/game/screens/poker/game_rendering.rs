@@ -1,5 +1,18 @@
 use egui::{Color32, Ui};
-use mcg_shared::{GameStatePublic, PlayerId, PlayerPublic};
+use mcg_shared::{
+    ActionEvent, Card, GameAction, GameStatePublic, HandResult, PlayerId, PlayerPublic,
+};
+
+use super::ui_components::{
+    card_chip, category_text, chip_stack, name_of, CardRenderer, PlayerColorScheme, YOU_COLOR,
+};
+use crate::i18n::{t, I18N_ACTION_LOG_TITLE, I18N_COPY_GAME_HINT, I18N_COPY_TO_CLIPBOARD};
+use crate::store::CardBackDesign;
+
+fn render_card(ui: &mut Ui, card: Card, theme: Option<&str>) {
+    let renderer = CardRenderer::for_card(ui.ctx(), card, theme);
+    super::ui_components::render_card(ui, card, &renderer);
+}
 
 pub fn render_showdown_banner(ui: &mut Ui, state: &GameStatePublic, preferred_player: PlayerId) {
     if state.stage == mcg_shared::Stage::Showdown {
@@ -18,60 +31,183 @@ pub fn render_showdown_banner(ui: &mut Ui, state: &GameStatePublic, preferred_pl
         if !winners.is_empty() {
             ui.label(format!("Winners: {}", winners.join(", ")));
         }
+        render_showdown_hands(ui, state, preferred_player, &winners);
         ui.add_space(8.0);
     }
 }
 
-pub fn render_table_panel(ui: &mut Ui, state: &GameStatePublic, preferred_player: PlayerId) {
-    ui.group(|ui| {
-        ui.horizontal(|ui| {
-            ui.label(egui::RichText::new("Pot:").strong());
-            ui.monospace(format!(" {}", state.pot));
-        });
-        ui.add_space(8.0);
+/// Hand results of the players who did not fold, best hand first.
+fn showdown_results(state: &GameStatePublic) -> Vec<HandResult> {
+    let mut results = state
+        .action_log
+        .iter()
+        .rev()
+        .find_map(|e| match e {
+            ActionEvent::GameAction(GameAction::Showdown { hand_results }) => {
+                Some(hand_results.clone())
+            }
+            _ => None,
+        })
+        .unwrap_or_default();
+    results.retain(|hr| {
+        state
+            .players
+            .iter()
+            .any(|p| p.id == hr.player_id && !p.has_folded)
+    });
+    results.sort_by(|a, b| b.rank.cmp(&a.rank));
+    results
+}
+
+/// Hand category and best five cards of each player at showdown. With several
+/// players left they are compared in a table, collapsed by default.
+fn render_showdown_hands(
+    ui: &mut Ui,
+    state: &GameStatePublic,
+    preferred_player: PlayerId,
+    winners: &[String],
+) {
+    let results = showdown_results(state);
+    let [best, rest @ ..] = results.as_slice() else {
+        return;
+    };
+    if rest.is_empty() {
         ui.horizontal(|ui| {
-            ui.label(egui::RichText::new("Board:").strong());
-            if state.community.is_empty() {
-                ui.label("—");
+            ui.label(category_text(best.rank.category));
+            for &c in &best.best_five {
+                card_chip(ui, c);
             }
-            for &c in &state.community {
-                super::ui_components::card_chip(ui, c);
+        });
+        return;
+    }
+
+    let header = format!(
+        "🏆 {} — {}",
+        winners.join(", "),
+        category_text(best.rank.category)
+    );
+    egui::CollapsingHeader::new(header)
+        .id_salt("showdown_hands")
+        .default_open(false)
+        .show(ui, |ui| {
+            egui::Grid::new("showdown_hands_grid")
+                .striped(true)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    ui.strong("Player");
+                    ui.strong("Hand");
+                    ui.strong("Best five");
+                    ui.end_row();
+                    for hr in &results {
+                        let name = name_of(&state.players, hr.player_id);
+                        if hr.player_id == preferred_player {
+                            ui.colored_label(Color32::LIGHT_GREEN, format!("{} (you)", name));
+                        } else {
+                            ui.label(name);
+                        }
+                        ui.label(category_text(hr.rank.category));
+                        ui.horizontal(|ui| {
+                            for &c in &hr.best_five {
+                                card_chip(ui, c);
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+            ui.add_space(4.0);
+            if ui
+                .button("Copy to Clipboard")
+                .on_hover_text("Copy a structured summary of the hand and full action log")
+                .clicked()
+            {
+                let clip = super::ui_components::format_game_for_clipboard(state, preferred_player);
+                ui.ctx().copy_text(clip);
             }
         });
+}
+
+pub fn render_table_panel(
+    ui: &mut Ui,
+    state: &GameStatePublic,
+    preferred_player: PlayerId,
+    poker_screen: &mut dyn PokerScreenActions,
+) {
+    ui.group(|ui| {
+        render_pot_and_board(ui, state, preferred_player, poker_screen.card_theme());
         ui.add_space(8.0);
         ui.separator();
-        ui.horizontal(|ui| {
-            ui.label(egui::RichText::new("Action log:").strong());
-            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                if ui
-                    .add(egui::Button::new("Copy to clipboard"))
-                    .on_hover_text(
-                        "Copy a structured summary of the current game and full action log",
-                    )
-                    .clicked()
-                {
-                    let clip =
-                        super::ui_components::format_game_for_clipboard(state, preferred_player);
-                    ui.ctx().copy_text(clip);
-                }
-            });
+        render_action_log_section(ui, state, preferred_player, poker_screen);
+    });
+}
+
+/// Pot with its breakdown and the community cards
+pub fn render_pot_and_board(
+    ui: &mut Ui,
+    state: &GameStatePublic,
+    preferred_player: PlayerId,
+    card_theme: Option<&str>,
+) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("Pot:").strong());
+        let pot_text = egui::RichText::new(format!(" {}", state.pot)).monospace();
+        if state.stage == mcg_shared::Stage::Showdown {
+            chip_stack(ui, state.pot, egui::Sense::hover());
+            ui.label(pot_text);
+        } else {
+            let chips = chip_stack(ui, state.pot, egui::Sense::click());
+            let pot = ui
+                .add(egui::Label::new(pot_text).sense(egui::Sense::click()))
+                .union(chips)
+                .on_hover_text("Click to see each player's share of the pot");
+            super::pot_equity::pot_equity_popup(&pot, state, preferred_player);
+        }
+    });
+    // A lone "Pot" entry would only repeat the total above
+    if state.pot_breakdown.len() > 1 {
+        ui.indent("pot_breakdown", |ui| {
+            for (label, amount) in &state.pot_breakdown {
+                ui.horizontal(|ui| {
+                    ui.weak(format!("{}:", label));
+                    ui.monospace(amount.to_string());
+                });
+            }
         });
-        egui::ScrollArea::vertical()
-            .id_salt("action_log_scroll")
-            .max_height(200.0)
-            .show(ui, |ui| {
-                for entry in state.action_log.iter().rev().take(100) {
-                    super::ui_components::log_entry_row(
-                        ui,
-                        entry,
-                        &state.players,
-                        preferred_player,
-                    );
-                }
-            });
+    }
+    ui.add_space(8.0);
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("Board:").strong());
+        if state.community.is_empty() {
+            ui.label("—");
+        }
+        for &c in &state.community {
+            render_card(ui, c, card_theme);
+        }
     });
 }
 
+/// Action log with its copy button
+pub fn render_action_log_section(
+    ui: &mut Ui,
+    state: &GameStatePublic,
+    preferred_player: PlayerId,
+    poker_screen: &mut dyn PokerScreenActions,
+) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(t(I18N_ACTION_LOG_TITLE)).strong());
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui
+                .add(egui::Button::new(t(I18N_COPY_TO_CLIPBOARD)))
+                .on_hover_text(t(I18N_COPY_GAME_HINT))
+                .clicked()
+            {
+                let clip = super::ui_components::format_game_for_clipboard(state, preferred_player);
+                ui.ctx().copy_text(clip);
+            }
+        });
+    });
+    poker_screen.render_action_log(ui, state, preferred_player);
+}
+
 pub fn render_player_status_and_bet(
     ui: &mut Ui,
     state: &GameStatePublic,
@@ -84,19 +220,35 @@ pub fn render_player_status_and_bet(
         ui.label("  ");
     }
 
+    let color = PlayerColorScheme::new(state, preferred_player).color_of(p);
     if p.id == preferred_player {
-        ui.colored_label(Color32::LIGHT_GREEN, "You");
+        ui.colored_label(YOU_COLOR, "You");
+    }
+    ui.label(egui::RichText::new(&p.name).strong().color(color));
+    if state.dealer == Some(p.id) {
+        super::ui_components::dealer_button(ui);
     }
-    ui.label(egui::RichText::new(&p.name).strong());
 
     if p.bet_this_round > 0 {
-        ui.label(format!("Bet: {}", p.bet_this_round));
+        chip_stack(ui, p.bet_this_round, egui::Sense::hover());
+        let bet = format!(" Bet: {} ", p.bet_this_round);
+        ui.label(
+            egui::RichText::new(bet)
+                .monospace()
+                .color(Color32::BLACK)
+                .background_color(color),
+        );
     }
 
     if p.has_folded {
         ui.colored_label(Color32::LIGHT_RED, "(folded)");
     }
 
+    if let Some(bounty) = p.bounty {
+        ui.label(format!("💀 {}", bounty))
+            .on_hover_text("Bounty paid to whoever eliminates this player");
+    }
+
     if state.stage == mcg_shared::Stage::Showdown && state.winner_ids.contains(&p.id) {
         ui.colored_label(Color32::YELLOW, "WINNER");
     }
@@ -115,11 +267,20 @@ pub fn render_my_cards_and_actions(
 ) {
     ui.vertical(|ui| {
         if let Some(cards) = p.cards {
+            let theme = poker_screen.card_theme();
+            let color = PlayerColorScheme::new(state, preferred_player).color_of(p);
             ui.horizontal(|ui| {
                 ui.add_space(12.0);
-                super::ui_components::card_chip(ui, cards[0]);
-                super::ui_components::card_chip(ui, cards[1]);
+                hole_card_frame(color).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        render_card(ui, cards[0], theme);
+                        render_card(ui, cards[1], theme);
+                    });
+                });
             });
+            if state.stage != mcg_shared::Stage::Showdown && !p.has_folded {
+                poker_screen.render_hand_strength(ui, state, p.id, cards);
+            }
             ui.add_space(6.0);
             ui.separator();
             ui.add_space(6.0);
@@ -158,18 +319,39 @@ pub fn render_player(
 
     if p.id == preferred_player {
         render_my_cards_and_actions(ui, state, p, preferred_player, poker_screen);
-    } else if state.stage == mcg_shared::Stage::Showdown {
-        if let Some(cards) = p.cards {
-            ui.horizontal(|ui| {
-                ui.add_space(12.0);
-                super::ui_components::card_chip(ui, cards[0]);
-                super::ui_components::card_chip(ui, cards[1]);
+    } else if !p.has_folded {
+        let theme = poker_screen.card_theme();
+        let color = PlayerColorScheme::new(state, preferred_player).color_of(p);
+        ui.horizontal(|ui| {
+            ui.add_space(12.0);
+            hole_card_frame(color).show(ui, |ui| {
+                ui.horizontal(|ui| match p.cards {
+                    Some(cards) if state.stage == mcg_shared::Stage::Showdown => {
+                        for card in cards {
+                            let renderer = CardRenderer::for_card(ui.ctx(), card, theme);
+                            super::ui_components::render_revealed_card(ui, card, &renderer);
+                        }
+                    }
+                    _ => {
+                        let back = poker_screen.card_back();
+                        CardRenderer::render_back(ui, back);
+                        CardRenderer::render_back(ui, back);
+                    }
+                });
             });
-        }
+        });
     }
     ui.add_space(8.0);
 }
 
+/// Border around a player's hole cards in the player's color
+fn hole_card_frame(color: Color32) -> egui::Frame {
+    egui::Frame::new()
+        .stroke(egui::Stroke::new(2.0, color))
+        .corner_radius(4.0)
+        .inner_margin(3.0)
+}
+
 pub fn render_players_panel(
     ui: &mut Ui,
     state: &GameStatePublic,
@@ -189,16 +371,66 @@ pub fn render_panels(
     preferred_player: PlayerId,
     poker_screen: &mut dyn PokerScreenActions,
 ) {
-    let narrow = ui.available_width() < 900.0;
-    if narrow {
-        render_players_panel(ui, state, preferred_player, poker_screen);
-        ui.add_space(8.0);
-        render_table_panel(ui, state, preferred_player);
-    } else {
-        ui.columns(2, |cols| {
-            render_table_panel(&mut cols[0], state, preferred_player);
-            render_players_panel(&mut cols[1], state, preferred_player, poker_screen);
-        });
+    // Decided every frame so resizing the window switches layouts right away
+    let layout = PanelLayout::for_width(ui.available_width());
+    match layout {
+        PanelLayout::Single => {
+            render_players_panel(ui, state, preferred_player, poker_screen);
+            ui.add_space(8.0);
+            render_table_panel(ui, state, preferred_player, poker_screen);
+        }
+        PanelLayout::TwoColumns => {
+            ui.columns(2, |cols| {
+                render_table_panel(&mut cols[0], state, preferred_player, poker_screen);
+                render_players_panel(&mut cols[1], state, preferred_player, poker_screen);
+            });
+        }
+        PanelLayout::ThreeColumns => {
+            // Stretch the columns' frames to the bottom of the screen
+            let margin = egui::Frame::group(ui.style()).total_margin().sum().y;
+            let height = (ui.available_height() - margin).max(0.0);
+            ui.columns(3, |cols| {
+                cols[0].group(|ui| {
+                    ui.set_min_height(height);
+                    for p in state.players.iter() {
+                        render_player(ui, state, p, preferred_player, poker_screen);
+                    }
+                });
+                cols[1].group(|ui| {
+                    ui.set_min_height(height);
+                    render_pot_and_board(ui, state, preferred_player, poker_screen.card_theme());
+                    ui.add_space(8.0);
+                    super::table_view::render_table_view(ui, state, preferred_player);
+                });
+                cols[2].group(|ui| {
+                    ui.set_min_height(height);
+                    render_action_log_section(ui, state, preferred_player, poker_screen);
+                });
+            });
+        }
+    }
+}
+
+/// How the player, table and action log panels are arranged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanelLayout {
+    /// Everything stacked, for phones and narrow windows
+    Single,
+    /// Table and action log next to the players
+    TwoColumns,
+    /// Players, table and action log side by side, for ultrawide monitors
+    ThreeColumns,
+}
+
+impl PanelLayout {
+    pub fn for_width(width: f32) -> Self {
+        if width < 700.0 {
+            Self::Single
+        } else if width < 1600.0 {
+            Self::TwoColumns
+        } else {
+            Self::ThreeColumns
+        }
     }
 }
 
@@ -220,4 +452,38 @@ pub trait PokerScreenActions {
         show_next: bool,
     );
     fn send(&self, msg: &mcg_shared::Frontend2BackendMsg);
+    /// Card image theme to draw cards with; `None` draws text chips.
+    fn card_theme(&self) -> Option<&'static str>;
+    /// Design of opponents' face-down cards.
+    fn card_back(&self) -> &CardBackDesign;
+    /// Rating of the player's own hole cards, if enabled in the settings.
+    fn render_hand_strength(
+        &mut self,
+        ui: &mut Ui,
+        state: &GameStatePublic,
+        player_id: mcg_shared::PlayerId,
+        cards: [Card; 2],
+    );
+    /// Filterable, sortable table of the current hand's action log.
+    fn render_action_log(
+        &mut self,
+        ui: &mut Ui,
+        state: &GameStatePublic,
+        preferred_player: PlayerId,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panel_layout_thresholds() {
+        assert_eq!(PanelLayout::for_width(400.0), PanelLayout::Single);
+        assert_eq!(PanelLayout::for_width(699.0), PanelLayout::Single);
+        assert_eq!(PanelLayout::for_width(700.0), PanelLayout::TwoColumns);
+        assert_eq!(PanelLayout::for_width(1599.0), PanelLayout::TwoColumns);
+        assert_eq!(PanelLayout::for_width(1600.0), PanelLayout::ThreeColumns);
+        assert_eq!(PanelLayout::for_width(3440.0), PanelLayout::ThreeColumns);
+    }
 }
@@ -0,0 +1,119 @@
+use egui::{Color32, Context, RichText};
+use mcg_shared::{ActionKind, GtoAnnotation, PlayerId, PlayerPublic};
+
+use super::ui_components::{name_of, stage_to_str};
+
+const OPTIMAL_COLOR: Color32 = Color32::from_rgb(80, 200, 120);
+const SUBOPTIMAL_COLOR: Color32 = Color32::from_rgb(220, 80, 80);
+
+fn action_phrase(kind: &ActionKind) -> String {
+    match kind {
+        ActionKind::Fold => "fold".into(),
+        ActionKind::Check => "check".into(),
+        ActionKind::Call(n) => format!("call {}", n),
+        ActionKind::Bet(n) => format!("bet {}", n),
+        ActionKind::Raise { to, .. } => format!("raise to {}", to),
+        ActionKind::AllIn { amount } => format!("go all-in for {}", amount),
+        ActionKind::PostBlind { amount, .. } => format!("post {}", amount),
+    }
+}
+
+fn annotation_row(
+    ui: &mut egui::Ui,
+    note: &GtoAnnotation,
+    players: &[PlayerPublic],
+    you: PlayerId,
+) {
+    let who = if note.player_id == you {
+        "You".to_string()
+    } else {
+        name_of(players, note.player_id)
+    };
+    let actual = action_phrase(&note.actual);
+    let text = if note.is_optimal() {
+        format!("{} {} (optimal)", who, actual)
+    } else {
+        format!(
+            "{} {} (suboptimal), GTO suggests {}, about {:.1} chips",
+            who,
+            actual,
+            action_phrase(&note.suggested),
+            note.ev_diff
+        )
+    };
+    let color = if note.is_optimal() {
+        OPTIMAL_COLOR
+    } else {
+        SUBOPTIMAL_COLOR
+    };
+    ui.horizontal(|ui| {
+        ui.label(RichText::new(stage_to_str(note.stage)).weak());
+        ui.label(RichText::new(text).color(color));
+    });
+}
+
+/// Estimated chips lost from non-GTO plays by each player, in seat order.
+fn ev_lost_by_player(
+    annotations: &[GtoAnnotation],
+    players: &[PlayerPublic],
+) -> Vec<(PlayerId, f32)> {
+    players
+        .iter()
+        .map(|p| {
+            let lost = annotations
+                .iter()
+                .filter(|a| a.player_id == p.id)
+                .map(|a| a.ev_diff)
+                .sum();
+            (p.id, lost)
+        })
+        .collect()
+}
+
+/// Window listing every action of the reviewed hand next to the GTO
+/// suggestion, with the total EV lost per player. Returns `false` once closed.
+pub fn draw_hand_review(
+    ctx: &Context,
+    annotations: &[GtoAnnotation],
+    players: &[PlayerPublic],
+    you: PlayerId,
+) -> bool {
+    let mut open = true;
+    egui::Window::new("Hand review")
+        .collapsible(false)
+        .default_width(460.0)
+        .open(&mut open)
+        .show(ctx, |ui| {
+            ui.label(
+                RichText::new(
+                    "Each decision compared with an equity based strategy that knows all hole cards.",
+                )
+                .weak(),
+            );
+            ui.add_space(6.0);
+            egui::ScrollArea::vertical()
+                .max_height(320.0)
+                .show(ui, |ui| {
+                    for note in annotations {
+                        annotation_row(ui, note, players, you);
+                    }
+                });
+            ui.separator();
+            ui.label(RichText::new("Estimated EV lost").strong());
+            egui::Grid::new("hand_review_summary")
+                .striped(true)
+                .show(ui, |ui| {
+                    for (id, lost) in ev_lost_by_player(annotations, players) {
+                        ui.label(name_of(players, id));
+                        let color = if lost > 0.0 {
+                            SUBOPTIMAL_COLOR
+                        } else {
+                            OPTIMAL_COLOR
+                        };
+                        ui.label(RichText::new(format!("{:.1}", lost)).color(color));
+                        ui.end_row();
+                    }
+                });
+        });
+    open
+}
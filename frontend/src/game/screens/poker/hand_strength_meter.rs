@@ -0,0 +1,72 @@
+use egui::{ProgressBar, Ui};
+use mcg_shared::{Card, GameStatePublic, PlayerId};
+
+use crate::game::hand_strength::{chen_score, estimate_equity, StrengthLabel, MAX_CHEN_SCORE};
+
+/// Random deals per equity estimate; enough for a stable label
+const EQUITY_RUNOUTS: u32 = 400;
+
+/// Progress bar rating the player's own hand. Opt-in in the settings, since
+/// it makes the game easier.
+#[derive(Debug, Default)]
+pub struct HandStrengthMeter {
+    /// Hole cards, board and opponent count the cached rating belongs to
+    rated: Option<([Card; 2], Vec<Card>, usize)>,
+    /// Fill of the bar in `0.0..=1.0`, label and hover explanation
+    rating: Option<(f32, StrengthLabel, String)>,
+}
+
+impl HandStrengthMeter {
+    /// Rate `hole` before the flop by the Chen formula and afterwards by
+    /// equity against the opponents still in the hand. Only recomputed when
+    /// the cards or the number of opponents change.
+    fn rate(&mut self, state: &GameStatePublic, you: PlayerId, hole: [Card; 2]) {
+        let opponents = state
+            .players
+            .iter()
+            .filter(|p| p.id != you && !p.has_folded)
+            .count();
+        let key = (hole, state.community.clone(), opponents);
+        if self.rated.as_ref() == Some(&key) {
+            return;
+        }
+        let rating = if state.community.is_empty() {
+            let score = chen_score(hole[0], hole[1]);
+            let fill = (score + 1.0) / (MAX_CHEN_SCORE + 1.0);
+            let hover = format!("Chen score {} of {}", score, MAX_CHEN_SCORE);
+            (fill, StrengthLabel::from_chen(score), hover)
+        } else {
+            let equity = estimate_equity(
+                hole,
+                &state.community,
+                opponents,
+                EQUITY_RUNOUTS,
+                &mut rand::rng(),
+            );
+            let hover = format!(
+                "About {:.0}% equity against {} random hand(s)",
+                equity * 100.0,
+                opponents
+            );
+            (equity, StrengthLabel::from_equity(equity, opponents), hover)
+        };
+        self.rated = Some(key);
+        self.rating = Some(rating);
+    }
+
+    pub fn show(&mut self, ui: &mut Ui, state: &GameStatePublic, you: PlayerId, hole: [Card; 2]) {
+        self.rate(state, you, hole);
+        let Some((fill, label, hover)) = &self.rating else {
+            return;
+        };
+        ui.horizontal(|ui| {
+            ui.add_space(12.0);
+            ui.add(
+                ProgressBar::new(fill.clamp(0.0, 1.0))
+                    .desired_width(220.0)
+                    .text(format!("Hand Strength: {}", label.as_str())),
+            )
+            .on_hover_text(hover.as_str());
+        });
+    }
+}
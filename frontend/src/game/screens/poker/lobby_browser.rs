@@ -0,0 +1,47 @@
+//! List of poker servers discovered on the local network.
+
+use egui::{Color32, RichText, Ui};
+
+use crate::discovery::ServerDiscovery;
+
+#[derive(Default)]
+pub struct LobbyBrowser {
+    discovery: ServerDiscovery,
+}
+
+impl LobbyBrowser {
+    /// Render the discovered servers. Returns the address of the server the
+    /// user clicked, to be filled into the server address field.
+    pub fn render(&mut self, ui: &mut Ui) -> Option<String> {
+        self.discovery.start();
+        let mut picked = None;
+        ui.label(RichText::new("Servers on your network").strong());
+        if !ServerDiscovery::SUPPORTED {
+            ui.label(
+                RichText::new("Server discovery needs the native client").color(Color32::GRAY),
+            );
+            return None;
+        }
+        let servers = self.discovery.servers();
+        if servers.is_empty() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(RichText::new("Searching...").color(Color32::GRAY));
+            });
+        }
+        for server in servers {
+            let text = format!(
+                "{}  {}  ({}/{} players)",
+                server.ad.name, server.address, server.ad.players, server.ad.max
+            );
+            if ui
+                .button(text)
+                .on_hover_text("Use this server address")
+                .clicked()
+            {
+                picked = Some(server.address);
+            }
+        }
+        picked
+    }
+}
@@ -1,9 +1,22 @@
+pub mod action_log;
+pub mod auto_act;
 pub mod betting_controls;
 pub mod connection_manager;
 pub mod game_rendering;
+pub mod hand_review;
+pub mod hand_strength_meter;
+pub mod lobby_browser;
 pub mod name_generator;
 pub mod player_manager;
+pub mod poker_clock;
+pub mod pot_equity;
 pub mod screen;
+pub mod session_restore;
+pub mod session_stats;
+pub mod shortcuts;
+pub mod table_browser;
+pub mod table_info_bar;
+pub mod table_view;
 pub mod ui_components;
 
 pub use screen::PokerOnlineScreen;
@@ -1,3 +1,5 @@
+use crate::utils::WasmTimer;
+
 pub struct NameGenerator;
 
 impl NameGenerator {
@@ -59,12 +61,6 @@ impl NameGenerator {
     }
 
     fn generate_timestamp_name() -> String {
-        format!(
-            "Player {}",
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs()
-        )
+        format!("Player {}", (WasmTimer::now_ms() / 1000.0) as u64)
     }
 }
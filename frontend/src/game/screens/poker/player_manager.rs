@@ -1,18 +1,40 @@
 use egui::{Context, Ui};
 use mcg_shared::{PlayerConfig, PlayerId};
 
+use crate::utils::WasmTimer;
+
 pub fn render_player_setup(ui: &mut Ui, _ctx: &Context) {
     ui.heading("Player Setup");
     ui.add_space(8.0);
 }
 
+/// Longest player name accepted by the rename dialog, in characters
+pub const MAX_NAME_LEN: usize = 20;
+
+/// Player being renamed and the edited name
+pub struct RenameState {
+    pub player_id: PlayerId,
+    pub buffer: String,
+}
+
+/// Check a new player name, returning it trimmed.
+pub fn validate_name(name: &str) -> Result<&str, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        Err("Name must not be empty".to_string())
+    } else if name.chars().count() > MAX_NAME_LEN {
+        Err(format!("Name must be at most {} characters", MAX_NAME_LEN))
+    } else {
+        Ok(name)
+    }
+}
+
 pub struct PlayerManager {
     players: Vec<PlayerConfig>,
     next_player_id: usize,
     new_player_name: String,
     preferred_player: PlayerId,
-    renaming_player_id: Option<PlayerId>,
-    rename_buffer: String,
+    rename_state: Option<RenameState>,
 }
 
 impl Default for PlayerManager {
@@ -29,28 +51,44 @@ impl PlayerManager {
                     id: mcg_shared::PlayerId(0),
                     name: "You".to_string(),
                     is_bot: false,
+                    avatar_color: None,
+                    preferred_seat: None,
                 },
                 PlayerConfig {
                     id: mcg_shared::PlayerId(1),
                     name: "Bot 1".to_string(),
                     is_bot: true,
+                    avatar_color: None,
+                    preferred_seat: None,
                 },
                 PlayerConfig {
                     id: mcg_shared::PlayerId(2),
                     name: "Bot 2".to_string(),
                     is_bot: true,
+                    avatar_color: None,
+                    preferred_seat: None,
                 },
                 PlayerConfig {
                     id: mcg_shared::PlayerId(3),
                     name: "Bot 3".to_string(),
                     is_bot: true,
+                    avatar_color: None,
+                    preferred_seat: None,
                 },
             ],
             next_player_id: 4,
             new_player_name: String::new(),
             preferred_player: PlayerId(0),
-            renaming_player_id: None,
-            rename_buffer: String::new(),
+            rename_state: None,
+        }
+    }
+
+    /// Seat the local profile as the player this client controls.
+    pub fn apply_profile(&mut self, profile: &crate::store::PlayerProfile) {
+        let preferred = self.preferred_player;
+        if let Some(player) = self.players.iter_mut().find(|p| p.id == preferred) {
+            player.name = profile.name.clone();
+            player.avatar_color = Some(profile.avatar_color);
         }
     }
 
@@ -76,33 +114,34 @@ impl PlayerManager {
 
     pub fn start_renaming(&mut self, player_id: PlayerId) {
         if let Some(player) = self.players.iter().find(|p| p.id == player_id) {
-            self.renaming_player_id = Some(player_id);
-            self.rename_buffer = player.name.clone();
+            self.rename_state = Some(RenameState {
+                player_id,
+                buffer: player.name.clone(),
+            });
         }
     }
 
-    pub fn is_renaming(&self, player_id: PlayerId) -> bool {
-        self.renaming_player_id == Some(player_id)
-    }
-
-    pub fn get_rename_buffer_mut(&mut self) -> &mut String {
-        &mut self.rename_buffer
+    pub fn get_rename_state_mut(&mut self) -> Option<&mut RenameState> {
+        self.rename_state.as_mut()
     }
 
-    pub fn apply_rename(&mut self) {
-        if let Some(player_id) = self.renaming_player_id {
-            if !self.rename_buffer.trim().is_empty() {
-                if let Some(player) = self.players.iter_mut().find(|p| p.id == player_id) {
-                    player.name = self.rename_buffer.trim().to_string();
-                }
-            }
-            self.cancel_rename();
+    /// Rename the player if the edited name is valid and end renaming.
+    /// An invalid name keeps the dialog open and returns the reason.
+    pub fn apply_rename(&mut self) -> Result<(), String> {
+        let Some(state) = &self.rename_state else {
+            return Ok(());
+        };
+        let name = validate_name(&state.buffer)?.to_string();
+        let player_id = state.player_id;
+        if let Some(player) = self.players.iter_mut().find(|p| p.id == player_id) {
+            player.name = name;
         }
+        self.cancel_rename();
+        Ok(())
     }
 
     pub fn cancel_rename(&mut self) {
-        self.renaming_player_id = None;
-        self.rename_buffer.clear();
+        self.rename_state = None;
     }
 
     pub fn add_new_player(&mut self) {
@@ -116,6 +155,8 @@ impl PlayerManager {
             id: mcg_shared::PlayerId(self.next_player_id),
             name: player_name,
             is_bot: true, // New players start as bots by default
+            avatar_color: None,
+            preferred_seat: None,
         });
         self.next_player_id += 1;
         self.new_player_name.clear();
@@ -185,12 +226,33 @@ impl PlayerManager {
     }
 
     fn generate_timestamp_name(&self) -> String {
-        format!(
-            "Player {}",
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs()
-        )
+        format!("Player {}", (WasmTimer::now_ms() / 1000.0) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rename_applies_only_valid_names() {
+        let mut manager = PlayerManager::new();
+        manager.start_renaming(PlayerId(1));
+        if let Some(state) = manager.get_rename_state_mut() {
+            state.buffer = "   ".to_string();
+        }
+        assert!(manager.apply_rename().is_err());
+        if let Some(state) = manager.get_rename_state_mut() {
+            state.buffer = "x".repeat(MAX_NAME_LEN + 1);
+        }
+        assert!(manager.apply_rename().is_err());
+        assert_eq!(manager.get_players()[1].name, "Bot 1");
+
+        if let Some(state) = manager.get_rename_state_mut() {
+            state.buffer = "  Shark ".to_string();
+        }
+        assert_eq!(manager.apply_rename(), Ok(()));
+        assert_eq!(manager.get_players()[1].name, "Shark");
+        assert!(manager.get_rename_state_mut().is_none());
     }
 }
@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use egui::{RichText, Ui};
+use mcg_shared::{ActionEvent, GameAction, GameStatePublic, Stage};
+
+use crate::utils::{Clock, WasmTimer};
+
+/// Table clock in the header, like the physical clocks of home games: how
+/// long the current hand has been running and where the game is in its blind
+/// schedule.
+#[derive(Debug, Default)]
+pub struct PokerClock {
+    /// When the hole cards of the current hand were first seen
+    hand_started_ms: Option<f64>,
+    /// When the current hand reached showdown, which stops the hand timer
+    hand_ended_ms: Option<f64>,
+    last_stage: Option<Stage>,
+    last_log_len: usize,
+}
+
+fn dealt_hole_cards(gs: &GameStatePublic) -> bool {
+    gs.action_log
+        .iter()
+        .any(|e| matches!(e, ActionEvent::GameAction(GameAction::DealtHole { .. })))
+}
+
+/// "m:ss", or "h:mm:ss" for hands that run over an hour.
+fn format_duration(secs: u64) -> String {
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
+
+impl PokerClock {
+    /// Follow the hand in `gs`; call once per frame with the latest state.
+    pub fn update(&mut self, gs: &GameStatePublic, clock: &impl Clock) {
+        let now = clock.now_ms();
+        // The log restarts with every hand, and states in between may be skipped
+        let new_hand = gs.action_log.len() < self.last_log_len
+            || (self.last_stage == Some(Stage::Showdown) && gs.stage != Stage::Showdown);
+        if new_hand {
+            self.hand_started_ms = None;
+            self.hand_ended_ms = None;
+        }
+        if self.hand_started_ms.is_none() && dealt_hole_cards(gs) {
+            self.hand_started_ms = Some(now);
+        }
+        if gs.stage == Stage::Showdown && self.hand_ended_ms.is_none() {
+            self.hand_ended_ms = Some(now);
+        }
+        self.last_stage = Some(gs.stage);
+        self.last_log_len = gs.action_log.len();
+    }
+
+    /// Whole seconds the current hand has been running, if it has started.
+    pub fn hand_elapsed_secs(&self, clock: &impl Clock) -> Option<u64> {
+        let start = self.hand_started_ms?;
+        let end = self.hand_ended_ms.unwrap_or_else(|| clock.now_ms());
+        Some(((end - start).max(0.0) / 1000.0) as u64)
+    }
+
+    /// Compact horizontal bar with the hand timer and the countdown to the
+    /// next blind level. Repaints every second while shown.
+    pub fn show(&self, ui: &mut Ui, gs: &GameStatePublic) {
+        ui.horizontal(|ui| {
+            let elapsed = self
+                .hand_elapsed_secs(&WasmTimer)
+                .map_or_else(|| "-:--".to_string(), format_duration);
+            ui.label(RichText::new(format!("⏱ {}", elapsed)).monospace())
+                .on_hover_text("Time since the hole cards were dealt");
+            if let Some(level) = gs.blind_level {
+                ui.separator();
+                ui.label(format!("Level {}", level.level));
+                let next = match level.hands_left {
+                    Some(1) => "next level after this hand".to_string(),
+                    Some(n) => format!("next level in {} hands", n),
+                    None => "final level".to_string(),
+                };
+                ui.label(RichText::new(next).weak());
+            }
+        });
+        ui.ctx().request_repaint_after(Duration::from_secs(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::{BettingLimit, PlayerId};
+    use std::cell::Cell;
+
+    struct MockClock(Cell<f64>);
+
+    impl Clock for MockClock {
+        fn now_ms(&self) -> f64 {
+            self.0.get()
+        }
+    }
+
+    fn state(stage: Stage, log_len: usize) -> GameStatePublic {
+        let dealt = ActionEvent::game(GameAction::DealtHole {
+            player_id: PlayerId(0),
+        });
+        GameStatePublic {
+            players: Vec::new(),
+            community: Vec::new(),
+            pot: 0,
+            sb: 5,
+            bb: 10,
+            to_act: PlayerId(0),
+            stage,
+            winner_ids: Vec::new(),
+            action_log: vec![dealt; log_len],
+            current_bet: 0,
+            min_raise: 0,
+            betting_limit: BettingLimit::NoLimit,
+            blind_level: None,
+            pot_equities: None,
+            pot_breakdown: Vec::new(),
+            dealer: None,
+            hand_number: 0,
+            hands_remaining: None,
+        }
+    }
+
+    #[test]
+    fn hand_timer_runs_from_the_deal_until_showdown() {
+        let clock = MockClock(Cell::new(10_000.0));
+        let mut poker_clock = PokerClock::default();
+        assert_eq!(poker_clock.hand_elapsed_secs(&clock), None);
+
+        poker_clock.update(&state(Stage::Preflop, 3), &clock);
+        clock.0.set(75_500.0);
+        poker_clock.update(&state(Stage::Flop, 6), &clock);
+        assert_eq!(poker_clock.hand_elapsed_secs(&clock), Some(65));
+
+        poker_clock.update(&state(Stage::Showdown, 12), &clock);
+        clock.0.set(90_000.0);
+        assert_eq!(poker_clock.hand_elapsed_secs(&clock), Some(65));
+
+        // The next hand starts a fresh timer
+        poker_clock.update(&state(Stage::Preflop, 3), &clock);
+        clock.0.set(92_000.0);
+        assert_eq!(poker_clock.hand_elapsed_secs(&clock), Some(2));
+    }
+
+    #[test]
+    fn durations_are_shown_as_minutes_and_seconds() {
+        assert_eq!(format_duration(0), "0:00");
+        assert_eq!(format_duration(65), "1:05");
+        assert_eq!(format_duration(3_725), "1:02:05");
+    }
+}
@@ -0,0 +1,251 @@
+//! Popup on the pot amount showing each player's expected share of the pot.
+
+use egui::{pos2, vec2, Color32, Id, Popup, Rect, Response, Sense, Stroke, StrokeKind, Ui};
+use mcg_shared::{Card, GameStatePublic, PlayerId};
+
+use crate::game::hand_strength::estimate_equity;
+
+/// Random deals for the client's own equity estimate
+const EQUITY_RUNOUTS: u32 = 400;
+const BAR_WIDTH: f32 = 240.0;
+const BAR_HEIGHT: f32 = 18.0;
+
+/// Slice colors of players without an avatar color, by seat
+const SEAT_COLORS: [Color32; 6] = [
+    Color32::from_rgb(66, 133, 244),
+    Color32::from_rgb(219, 68, 55),
+    Color32::from_rgb(244, 180, 0),
+    Color32::from_rgb(15, 157, 88),
+    Color32::from_rgb(171, 71, 188),
+    Color32::from_rgb(0, 172, 193),
+];
+const OPPONENTS_COLOR: Color32 = Color32::GRAY;
+
+/// One part of the equity bar
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquitySlice {
+    pub label: String,
+    /// Share of the pot in `0.0..=1.0`
+    pub equity: f32,
+    pub color: Color32,
+}
+
+/// Expected share of the pot of the players still in the hand
+#[derive(Debug, Clone, PartialEq)]
+pub struct PotEquity {
+    pub slices: Vec<EquitySlice>,
+    /// How the equities were obtained
+    pub note: &'static str,
+}
+
+fn seat_color(state: &GameStatePublic, seat: usize) -> Color32 {
+    match state.players[seat].avatar_color {
+        Some([r, g, b]) => Color32::from_rgb(r, g, b),
+        None => SEAT_COLORS[seat % SEAT_COLORS.len()],
+    }
+}
+
+impl PotEquity {
+    /// Equities the server simulated once everyone left is all-in.
+    pub fn all_in(state: &GameStatePublic) -> Option<Self> {
+        let equities = state.pot_equities.as_ref()?;
+        let slices = equities
+            .iter()
+            .filter_map(|&(id, equity)| {
+                let seat = state.players.iter().position(|p| p.id == id)?;
+                Some(EquitySlice {
+                    label: state.players[seat].name.clone(),
+                    equity,
+                    color: seat_color(state, seat),
+                })
+            })
+            .collect();
+        Some(Self {
+            slices,
+            note: "All-in: simulated by the server",
+        })
+    }
+
+    /// Equity of `you` against random hands of the other players still in
+    /// the hand, whose cards the client does not know.
+    pub fn estimate(state: &GameStatePublic, you: PlayerId) -> Option<Self> {
+        let seat = state.players.iter().position(|p| p.id == you)?;
+        let me = &state.players[seat];
+        let hole = me.cards.filter(|_| !me.has_folded)?;
+        let opponents = state
+            .players
+            .iter()
+            .filter(|p| p.id != you && !p.has_folded)
+            .count();
+        let equity = estimate_equity(
+            hole,
+            &state.community,
+            opponents,
+            EQUITY_RUNOUTS,
+            &mut rand::rng(),
+        );
+        let mut slices = vec![EquitySlice {
+            label: "You".to_owned(),
+            equity,
+            color: seat_color(state, seat),
+        }];
+        if opponents > 0 {
+            slices.push(EquitySlice {
+                label: format!("Opponents ({})", opponents),
+                equity: 1.0 - equity,
+                color: OPPONENTS_COLOR,
+            });
+        }
+        Some(Self {
+            slices,
+            note: "Your estimate against random hands",
+        })
+    }
+
+    /// Stacked bar with one slice per player and a legend below.
+    pub fn show(&self, ui: &mut Ui) {
+        let (rect, _) = ui.allocate_exact_size(vec2(BAR_WIDTH, BAR_HEIGHT), Sense::hover());
+        let painter = ui.painter();
+        let mut left = rect.left();
+        for slice in &self.slices {
+            let right = (left + rect.width() * slice.equity.clamp(0.0, 1.0)).min(rect.right());
+            let part = Rect::from_min_max(pos2(left, rect.top()), pos2(right, rect.bottom()));
+            painter.rect_filled(part, 0.0, slice.color);
+            left = right;
+        }
+        painter.rect_stroke(
+            rect,
+            2.0,
+            Stroke::new(1.0, ui.visuals().weak_text_color()),
+            StrokeKind::Inside,
+        );
+        for slice in &self.slices {
+            ui.horizontal(|ui| {
+                ui.colored_label(slice.color, "■");
+                ui.label(&slice.label);
+                ui.monospace(format!("{:.1}%", slice.equity * 100.0));
+            });
+        }
+        ui.weak(self.note);
+    }
+}
+
+/// Toggle the equity popup when `pot` is clicked. The client-side estimate
+/// is kept until the cards or the players in the hand change.
+pub fn pot_equity_popup(pot: &Response, state: &GameStatePublic, you: PlayerId) {
+    type Cached = (
+        Option<[Card; 2]>,
+        Vec<Card>,
+        Vec<PlayerId>,
+        Option<PotEquity>,
+    );
+
+    Popup::from_toggle_button_response(pot).show(|ui| {
+        ui.label(egui::RichText::new("Pot equity").strong());
+        let equity = PotEquity::all_in(state).or_else(|| {
+            let cache_id = Id::new("pot_equity_estimate");
+            let hole = state
+                .players
+                .iter()
+                .find(|p| p.id == you)
+                .and_then(|p| p.cards);
+            let in_hand: Vec<PlayerId> = state
+                .players
+                .iter()
+                .filter(|p| !p.has_folded)
+                .map(|p| p.id)
+                .collect();
+            let cached = ui.ctx().data(|d| d.get_temp::<Cached>(cache_id));
+            match cached {
+                Some((h, board, players, equity))
+                    if h == hole && board == state.community && players == in_hand =>
+                {
+                    equity
+                }
+                _ => {
+                    let equity = PotEquity::estimate(state, you);
+                    let entry = (hole, state.community.clone(), in_hand, equity.clone());
+                    ui.ctx()
+                        .data_mut(|d| d.insert_temp::<Cached>(cache_id, entry));
+                    equity
+                }
+            }
+        });
+        match equity {
+            Some(equity) => equity.show(ui),
+            None => {
+                ui.weak("Shown once everyone left is all-in");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::{BettingLimit, PlayerPublic, Stage};
+
+    fn state(pot_equities: Option<Vec<(PlayerId, f32)>>) -> GameStatePublic {
+        let player = |id: usize, name: &str, cards: Option<[Card; 2]>| PlayerPublic {
+            id: PlayerId(id),
+            name: name.to_owned(),
+            stack: 0,
+            cards,
+            has_folded: false,
+            all_in: true,
+            bet_this_round: 0,
+            avatar_color: None,
+            bounty: None,
+            seat_number: id as u8,
+            must_post_blind: false,
+        };
+        GameStatePublic {
+            players: vec![
+                player(0, "Alice", Some([Card(0), Card(13)])),
+                player(1, "Bob", None),
+            ],
+            community: Vec::new(),
+            pot: 200,
+            sb: 5,
+            bb: 10,
+            to_act: PlayerId(0),
+            stage: Stage::Flop,
+            winner_ids: Vec::new(),
+            action_log: Vec::new(),
+            current_bet: 0,
+            min_raise: 10,
+            betting_limit: BettingLimit::NoLimit,
+            blind_level: None,
+            pot_equities,
+            pot_breakdown: Vec::new(),
+            dealer: None,
+            hand_number: 0,
+            hands_remaining: None,
+        }
+    }
+
+    #[test]
+    fn server_equities_are_labelled_by_player() {
+        let gs = state(Some(vec![(PlayerId(1), 0.2), (PlayerId(0), 0.8)]));
+        let equity = PotEquity::all_in(&gs).unwrap();
+        let labels: Vec<_> = equity
+            .slices
+            .iter()
+            .map(|s| (s.label.as_str(), s.equity))
+            .collect();
+        assert_eq!(labels, vec![("Bob", 0.2), ("Alice", 0.8)]);
+        assert_ne!(equity.slices[0].color, equity.slices[1].color);
+        assert!(PotEquity::all_in(&state(None)).is_none());
+    }
+
+    #[test]
+    fn estimate_splits_the_pot_with_the_opponents() {
+        let gs = state(None);
+        let equity = PotEquity::estimate(&gs, PlayerId(0)).unwrap();
+        assert_eq!(equity.slices.len(), 2);
+        let total: f32 = equity.slices.iter().map(|s| s.equity).sum();
+        assert!((total - 1.0).abs() < 1e-6);
+        // Without visible hole cards there is nothing to estimate
+        assert!(PotEquity::estimate(&gs, PlayerId(1)).is_none());
+    }
+}
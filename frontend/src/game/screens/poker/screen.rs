@@ -1,85 +1,284 @@
+use crate::game::notifications::NotificationLevel;
 use crate::game::screens::{ScreenDef, ScreenMetadata};
-use crate::game::websocket::{MessageSender, WebSocketConnection};
+use crate::game::websocket::MessageSender;
 use crate::game::{AppInterface, ScreenWidget};
-use crate::store::ClientState;
+use crate::i18n::{
+    t, tf, I18N_ALL_IN_BUTTON, I18N_ALL_IN_HINT, I18N_CALL_BUTTON, I18N_CHECK_BUTTON,
+    I18N_FOLD_BUTTON, I18N_SHORTCUTS_HINT,
+};
+use crate::store::{CardBackDesign, ClientState, StateSerializer};
 use eframe::Frame;
 use egui::{Context, RichText, Ui};
-use mcg_shared::{PlayerAction, PlayerConfig};
+use mcg_shared::{PlayerAction, PlayerConfig, TableConfig, TableId, DEFAULT_TABLE_ID};
+use std::cell::RefCell;
+use std::rc::Rc;
 
+use super::action_log::ActionLogTable;
+use super::auto_act::{AutoAct, CancelOnAction};
 use super::betting_controls::BettingControls;
 use super::connection_manager::ConnectionManager;
-use super::player_manager::{render_player_setup, PlayerManager};
+use super::hand_strength_meter::HandStrengthMeter;
+use super::lobby_browser::LobbyBrowser;
+use super::player_manager::{render_player_setup, validate_name, PlayerManager, MAX_NAME_LEN};
+use super::poker_clock::PokerClock;
+use super::session_restore::{RestoreStep, SessionRestore};
+use super::shortcuts::Shortcut;
+use super::table_browser::TableBrowser;
+use super::table_info_bar::TableInfoBar;
 
 #[derive(Default)]
 struct PlayerTableEdits {
     bot_updates: Vec<(usize, bool)>,
+    seat_updates: Vec<(usize, Option<u8>)>,
     to_remove: Option<usize>,
     to_rename: Option<usize>,
-    apply_rename: bool,
-    cancel_rename: bool,
+}
+
+/// Messages sent while rendering, handed to the shared connection at the end
+/// of the frame; most of the screen renders without the client state at hand.
+#[derive(Default)]
+struct Outbox(RefCell<Vec<mcg_shared::Frontend2BackendMsg>>);
+
+impl MessageSender for Outbox {
+    fn send(&self, msg: &mcg_shared::Frontend2BackendMsg) {
+        self.0.borrow_mut().push(msg.clone());
+    }
 }
 
 pub struct PokerOnlineScreen {
-    conn: WebSocketConnection,
+    outbox: Outbox,
     connection_manager: ConnectionManager,
     player_manager: PlayerManager,
     betting_controls: BettingControls,
+    table_browser: TableBrowser,
+    lobby_browser: LobbyBrowser,
+    poker_clock: PokerClock,
+    table_info_bar: TableInfoBar,
+    action_log: ActionLogTable,
+    hand_strength: HandStrengthMeter,
+    /// Hand strength meter setting, mirrored from the client settings each frame
+    show_hand_strength: bool,
+    /// Table the client is seated at, mirrored from the client state each frame
+    table_id: TableId,
+    /// Card image theme; `None` draws cards as text chips
+    card_theme: Option<&'static str>,
+    /// Face-down card design, mirrored from the client settings each frame
+    card_back: CardBackDesign,
+    /// Profile name and avatar color last copied into the player setup
+    applied_profile: Option<(String, [u8; 3])>,
+    /// Card theme setting last copied into `card_theme`
+    applied_card_theme: Option<String>,
+    /// Rebuy (min, max) offered to the preferred player, mirrored from the client state
+    rebuy_offer: Option<(u32, u32)>,
+    /// Chips selected in the open rebuy dialog
+    rebuy_amount: Option<u32>,
+    export_result: Rc<RefCell<Option<Result<(), String>>>>,
+    /// Table from a pairing invite, joined once the connection is up
+    pending_join: Option<TableId>,
+    /// Keyboard shortcut pressed this frame, see `shortcuts`
+    shortcut: Option<Shortcut>,
+    /// Fold the preferred player when they do not act within ten seconds
+    auto_act_enabled: bool,
+    auto_act: AutoAct,
+    /// Session cached before the page was refreshed, until it is restored
+    /// or given up
+    restore: Option<SessionRestore>,
 }
 
 impl PokerOnlineScreen {
-    /// Default server address for the poker client.
-    const DEFAULT_SERVER_ADDRESS: &'static str = "127.0.0.1:3000";
-
     pub fn new() -> Self {
         Self {
-            conn: WebSocketConnection::new(),
-            connection_manager: ConnectionManager::new(Self::DEFAULT_SERVER_ADDRESS.to_string()),
+            outbox: Outbox::default(),
+            connection_manager: ConnectionManager::new(crate::get_server_address()),
             player_manager: PlayerManager::new(),
             betting_controls: BettingControls::default(),
+            table_browser: TableBrowser::default(),
+            lobby_browser: LobbyBrowser::default(),
+            poker_clock: PokerClock::default(),
+            table_info_bar: TableInfoBar::default(),
+            action_log: ActionLogTable::default(),
+            hand_strength: HandStrengthMeter::default(),
+            show_hand_strength: false,
+            table_id: DEFAULT_TABLE_ID,
+            card_theme: Some(crate::hardcoded_cards::DEFAULT_THEME),
+            card_back: CardBackDesign::Default,
+            applied_profile: None,
+            applied_card_theme: None,
+            rebuy_offer: None,
+            rebuy_amount: None,
+            export_result: Rc::new(RefCell::new(None)),
+            pending_join: None,
+            shortcut: None,
+            auto_act_enabled: false,
+            auto_act: AutoAct::default(),
+            restore: StateSerializer::load().map(SessionRestore::new),
         }
     }
 
-    fn draw_error_popup(&mut self, app_state: &mut ClientState, ctx: &Context) {
-        if app_state.ui.last_error.is_none() {
+    /// Copy the local profile into the player setup whenever it changes.
+    /// The preferred server is only used to pre-fill the address on startup,
+    /// and not at all when the embedding page configures one.
+    fn sync_profile(&mut self, app_state: &ClientState) {
+        let profile = &app_state.profile;
+        let current = (profile.name.clone(), profile.avatar_color);
+        if self.applied_profile.as_ref() == Some(&current) {
             return;
         }
+        if self.applied_profile.is_none() && crate::mcg_config("serverAddress").is_none() {
+            self.connection_manager
+                .set_server_address(profile.preferred_server.clone());
+        }
+        self.player_manager.apply_profile(profile);
+        self.applied_profile = Some(current);
+    }
 
-        let mut open = true;
-        let mut close_popup = false;
-        egui::Window::new("Connection error")
-            .collapsible(false)
-            .resizable(false)
-            .open(&mut open)
-            .show(ctx, |ui| {
-                if let Some(err) = &app_state.ui.last_error {
-                    ui.label(err);
-                }
-                ui.add_space(8.0);
-                if ui.button("Close").clicked() {
-                    close_popup = true;
-                }
-            });
+    /// Follow the global card theme setting; picking a style in the header
+    /// only overrides it until the setting changes again.
+    fn sync_card_theme(&mut self, app_state: &ClientState) {
+        let theme = &app_state.settings.card_theme;
+        if self.applied_card_theme.as_ref() == Some(theme) {
+            return;
+        }
+        if let Some(info) = crate::hardcoded_cards::ThemeRegistry::get(theme) {
+            self.card_theme = Some(info.id);
+        }
+        self.applied_card_theme = Some(theme.clone());
+    }
+
+    fn export_history(&self, app_state: &ClientState) {
+        let result_slot = self.export_result.clone();
+        crate::effects::export_history_effect(
+            app_state.settings.server_address.clone(),
+            self.table_id,
+            move |result| {
+                *result_slot.borrow_mut() = Some(result);
+            },
+        );
+    }
 
-        if !open || close_popup {
-            app_state.ui.last_error = None;
+    /// Surface the outcome of a finished history export in the UI state.
+    fn apply_export_result(&mut self, app_state: &mut ClientState) {
+        if let Some(result) = self.export_result.borrow_mut().take() {
+            match result {
+                Ok(()) => app_state.notify(NotificationLevel::Success, "Hand history exported"),
+                Err(e) => app_state.notify(NotificationLevel::Error, e),
+            }
         }
     }
 
-    fn connect(&mut self, app_state: &mut ClientState, ctx: &Context) {
-        self.connection_manager.connect(
-            &mut self.conn,
-            app_state,
-            ctx,
-            self.player_manager.get_players().clone(),
+    /// Connect to the server of an invite accepted on the pairing screen and
+    /// join its table as soon as the connection is open.
+    fn follow_invite(&mut self, app_state: &mut ClientState, ctx: &Context) {
+        if let Some(address) = app_state.session.pending_server_address.take() {
+            self.connection_manager.set_server_address(address);
+        }
+        if let Some(invite) = app_state.session.pending_invite.take() {
+            self.connection_manager
+                .set_server_address(invite.server_address);
+            self.connect(app_state, ctx);
+            self.pending_join = Some(invite.table_id);
+        }
+        if let Some(table_id) = app_state.session.pending_table.take() {
+            if !app_state.connection_pool.is_connected() {
+                self.connect(app_state, ctx);
+            }
+            self.pending_join = Some(table_id);
+        }
+        if app_state.connection_pool.is_connected() {
+            if let Some(table_id) = self.pending_join.take() {
+                self.send(&mcg_shared::Frontend2BackendMsg::JoinTable { table_id });
+            }
+        }
+    }
+
+    /// Reconnect, and rejoin the current table, when the server stopped
+    /// sending heartbeats; the socket may be dead without having been closed.
+    fn reconnect_if_silent(&mut self, app_state: &mut ClientState, ctx: &Context) {
+        if !app_state.connection_pool.is_connected() {
+            return;
+        }
+        let now = crate::utils::WasmTimer::now_ms();
+        if !app_state.connection.heartbeat_overdue(now) {
+            // Wake up to notice a missing heartbeat even if nothing else happens
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+            return;
+        }
+        crate::sprintln!("No heartbeat from the server, reconnecting");
+        let table_id = app_state.session.table_id;
+        self.connect(app_state, ctx);
+        self.pending_join = table_id;
+    }
+
+    /// Show the table cached before a page refresh while reconnecting to it.
+    /// The cache is dropped if the table's state does not arrive within
+    /// `session_restore::MAX_ATTEMPTS` attempts.
+    fn restore_session(&mut self, app_state: &mut ClientState, ctx: &Context) {
+        let Some(restore) = self.restore.as_mut() else {
+            return;
+        };
+        if app_state.session.table_id.is_some() && !app_state.session.restored {
+            // Opened again while seated; nothing to restore
+            self.restore = None;
+            return;
+        }
+        let step = restore.update(
+            &app_state.connection.connection_status,
+            app_state.session.restored,
+            &crate::utils::WasmTimer,
         );
+        match step {
+            RestoreStep::Connect {
+                server_address,
+                table_id,
+            } => {
+                if app_state.session.game_state.is_none() {
+                    app_state.session.game_state = Some(restore.cached_state().clone());
+                    app_state.session.restored = true;
+                }
+                self.connection_manager.set_server_address(server_address);
+                self.connect(app_state, ctx);
+                self.pending_join = Some(table_id);
+            }
+            RestoreStep::Wait => {
+                ctx.request_repaint_after(std::time::Duration::from_millis(250));
+            }
+            RestoreStep::Restored => self.restore = None,
+            RestoreStep::Failed => {
+                app_state.session.game_state = None;
+                app_state.session.restored = false;
+                app_state.disconnect();
+                app_state.notify(NotificationLevel::Error, "Could not restore the session");
+                self.pending_join = None;
+                self.restore = None;
+            }
+        }
     }
 
-    fn disconnect(&mut self) {
-        self.conn.close();
+    fn connect(&mut self, app_state: &mut ClientState, ctx: &Context) {
+        self.connection_manager.connect(app_state, ctx);
     }
 
     fn send(&self, msg: &mcg_shared::Frontend2BackendMsg) {
-        self.conn.send_msg(msg);
+        if matches!(msg, mcg_shared::Frontend2BackendMsg::Action { .. }) {
+            self.auto_act.cancel();
+        }
+        self.outbox.send(msg);
+    }
+
+    /// Fold the preferred player once their auto-fold countdown runs out.
+    fn auto_fold_if_away(&mut self, state: &mcg_shared::GameStatePublic) {
+        let you = self.player_manager.get_preferred_player();
+        let timer = &crate::utils::WasmTimer;
+        if self
+            .auto_act
+            .update(state, you, self.auto_act_enabled, timer)
+        {
+            self.send(&mcg_shared::Frontend2BackendMsg::Action {
+                table_id: self.table_id,
+                player_id: you,
+                action: PlayerAction::Fold,
+            });
+        }
     }
 
     fn render_full_player_setup(
@@ -107,7 +306,7 @@ impl PokerOnlineScreen {
             ui.add_space(4.0);
 
             egui::Grid::new("players_grid")
-                .num_columns(4)
+                .num_columns(5)
                 .spacing([8.0, 4.0])
                 .striped(true)
                 .show(ui, |ui| {
@@ -121,6 +320,8 @@ impl PokerOnlineScreen {
         ui.label(RichText::new("ID").strong());
         ui.label(RichText::new("Name").strong());
         ui.label(RichText::new("Bot").strong());
+        ui.label(RichText::new("Seat").strong())
+            .on_hover_text("Reserve a seat for a human player; bots fill the free seats");
         ui.label(RichText::new("Actions").strong());
         ui.end_row();
     }
@@ -145,29 +346,33 @@ impl PokerOnlineScreen {
     ) {
         ui.label(format!("{}", player.id));
 
-        // Check if this player is being renamed
-        if self.player_manager.is_renaming(player.id) {
-            // Show text edit field for renaming
-            let response = ui.text_edit_singleline(self.player_manager.get_rename_buffer_mut());
-
-            // Auto-focus the text field when rename starts
-            response.request_focus();
-
-            // Check for Enter key to confirm rename
-            if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                edits.apply_rename = true;
-            } else if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
-                edits.cancel_rename = true;
-            }
-        } else {
-            ui.label(&player.name);
-        }
+        ui.label(&player.name);
 
         let mut is_bot = player.is_bot;
         if ui.checkbox(&mut is_bot, "").changed() {
             edits.bot_updates.push((idx, is_bot));
         }
 
+        if player.is_bot {
+            ui.weak("auto");
+        } else {
+            ui.horizontal(|ui| {
+                let last_seat = self.player_manager.get_players().len().saturating_sub(1);
+                let mut locked = player.preferred_seat.is_some();
+                let mut seat = player.preferred_seat.unwrap_or(idx as u8);
+                let lock = ui
+                    .checkbox(&mut locked, "")
+                    .on_hover_text("Reserve this seat");
+                let drag = ui.add_enabled(
+                    locked,
+                    egui::DragValue::new(&mut seat).range(0..=last_seat as u8),
+                );
+                if lock.changed() || drag.changed() {
+                    edits.seat_updates.push((idx, locked.then_some(seat)));
+                }
+            });
+        }
+
         ui.horizontal(|ui| {
             self.render_player_actions(ui, player, idx, edits);
         });
@@ -181,36 +386,26 @@ impl PokerOnlineScreen {
         idx: usize,
         edits: &mut PlayerTableEdits,
     ) {
-        // If this player is being renamed, show Save/Cancel buttons
-        if self.player_manager.is_renaming(player.id) {
-            if ui.button("Save").clicked() {
-                edits.apply_rename = true;
-            }
-            if ui.button("Cancel").clicked() {
-                edits.cancel_rename = true;
-            }
+        // Radio toggle to select which player the frontend would like to control.
+        // Bot players cannot be selected.
+        if player.is_bot {
+            ui.label("Bot");
         } else {
-            // Radio toggle to select which player the frontend would like to control.
-            // Bot players cannot be selected.
-            if player.is_bot {
-                ui.label("Bot");
-            } else {
-                ui.radio_value(
-                    self.player_manager.get_preferred_player_mut(),
-                    player.id,
-                    "Play as",
-                )
-                .on_hover_text("Select this player for this client");
-            }
+            ui.radio_value(
+                self.player_manager.get_preferred_player_mut(),
+                player.id,
+                "Play as",
+            )
+            .on_hover_text("Select this player for this client");
+        }
 
-            if ui.button("✏").on_hover_text("Rename").clicked() {
-                edits.to_rename = Some(idx);
-            }
-            if self.player_manager.get_players().len() > 1
-                && ui.button("🗑").on_hover_text("Remove").clicked()
-            {
-                edits.to_remove = Some(idx);
-            }
+        if ui.button("✏").on_hover_text("Rename").clicked() {
+            edits.to_rename = Some(idx);
+        }
+        if self.player_manager.get_players().len() > 1
+            && ui.button("🗑").on_hover_text("Remove").clicked()
+        {
+            edits.to_remove = Some(idx);
         }
     }
 
@@ -219,6 +414,14 @@ impl PokerOnlineScreen {
         for (idx, is_bot) in edits.bot_updates {
             if let Some(p) = self.player_manager.get_players_mut().get_mut(idx) {
                 p.is_bot = is_bot;
+                if is_bot {
+                    p.preferred_seat = None;
+                }
+            }
+        }
+        for (idx, seat) in edits.seat_updates {
+            if let Some(p) = self.player_manager.get_players_mut().get_mut(idx) {
+                p.preferred_seat = seat;
             }
         }
 
@@ -229,18 +432,127 @@ impl PokerOnlineScreen {
             }
         }
 
-        // Handle rename mode toggle
+        // Open the rename dialog
         if let Some(idx) = edits.to_rename {
             if let Some(player) = self.player_manager.get_players().get(idx) {
                 self.player_manager.start_renaming(player.id);
             }
         }
+    }
+
+    /// Button for players who joined a game in progress, who sit out until
+    /// they post a big blind. Blinds are posted between hands only.
+    fn render_post_blind_button(
+        &mut self,
+        ui: &mut egui::Ui,
+        state: &mcg_shared::GameStatePublic,
+        player_id: mcg_shared::PlayerId,
+    ) {
+        let hint = format!(
+            "You joined a game in progress. Post a big blind of {} to be dealt into \
+             the next hand; until then you sit out.",
+            state.bb
+        );
+        let between_hands = state.stage == mcg_shared::Stage::Showdown;
+        let post_label = RichText::new("🪙 Post Blind to Play").size(16.0);
+        if ui
+            .add_enabled(
+                between_hands,
+                egui::Button::new(post_label).min_size(egui::vec2(170.0, 40.0)),
+            )
+            .on_hover_text(&hint)
+            .on_disabled_hover_text(format!("{}\n\nBlinds are posted between hands.", hint))
+            .clicked()
+        {
+            self.send(&mcg_shared::Frontend2BackendMsg::Action {
+                table_id: self.table_id,
+                player_id,
+                action: PlayerAction::PostBlind,
+            });
+        }
+    }
+
+    /// Modal with a slider for the chips to add when rebuying between hands.
+    fn draw_rebuy_dialog(&mut self, ctx: &Context) {
+        let (Some((min, max)), Some(amount)) = (self.rebuy_offer, self.rebuy_amount.as_mut())
+        else {
+            self.rebuy_amount = None;
+            return;
+        };
+
+        let mut open = true;
+        let mut confirm = false;
+        let mut cancel = false;
+        egui::Window::new("Rebuy")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label("Chips to add before the next hand:");
+                ui.add(egui::Slider::new(amount, min..=max).step_by(10.0));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    confirm = ui.button("Rebuy").clicked();
+                    cancel = ui.button("Cancel").clicked();
+                });
+            });
+
+        if confirm {
+            let amount = *amount;
+            self.send(&mcg_shared::Frontend2BackendMsg::Rebuy {
+                player_id: self.player_manager.get_preferred_player(),
+                amount,
+            });
+        }
+        if !open || confirm || cancel {
+            self.rebuy_amount = None;
+        }
+    }
+
+    /// Modal for renaming a player from the setup table. Enter saves a valid
+    /// name, Escape or closing the window discards the edit.
+    fn draw_rename_dialog(&mut self, ctx: &Context) {
+        let Some(state) = self.player_manager.get_rename_state_mut() else {
+            return;
+        };
+
+        let mut open = true;
+        let mut save = false;
+        let mut cancel = false;
+        egui::Window::new("Rename Player")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut state.buffer)
+                        .char_limit(MAX_NAME_LEN)
+                        .hint_text("Player name"),
+                );
+                response.request_focus();
+
+                let validation = validate_name(&state.buffer);
+                if let Err(reason) = &validation {
+                    ui.colored_label(egui::Color32::LIGHT_RED, reason);
+                }
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    save |= ui
+                        .add_enabled(validation.is_ok(), egui::Button::new("Save"))
+                        .clicked();
+                    cancel |= ui.button("Cancel").clicked();
+                });
+                save |= ui.input(|i| i.key_pressed(egui::Key::Enter));
+                cancel |= ui.input(|i| i.key_pressed(egui::Key::Escape));
+            });
 
-        // Apply or cancel rename
-        if edits.apply_rename {
-            self.player_manager.apply_rename();
-        } else if edits.cancel_rename {
+        if !open || cancel {
             self.player_manager.cancel_rename();
+        } else if save {
+            // An invalid name leaves the dialog open with its message shown
+            let _ = self.player_manager.apply_rename();
         }
     }
 
@@ -266,7 +578,7 @@ impl PokerOnlineScreen {
         app_state: &mut ClientState,
         ctx: &Context,
     ) {
-        let connected = self.conn.is_connected();
+        let connected = app_state.connection_pool.is_connected();
         let label = if connected {
             "Start New Game"
         } else {
@@ -283,13 +595,19 @@ impl PokerOnlineScreen {
         };
 
         if button.clicked() {
-            if connected {
+            let players = self.player_manager.get_players().clone();
+            if !connected {
+                // If not connected, connect first; the table browser opens once connected
+                self.connect(app_state, ctx);
+            } else if app_state.session.table_id.is_some() {
                 self.send(&mcg_shared::Frontend2BackendMsg::NewGame {
-                    players: self.player_manager.get_players().clone(),
+                    player_id: self.player_manager.get_preferred_player(),
+                    players,
                 });
             } else {
-                // If not connected, connect (which sends NewGame automatically)
-                self.connect(app_state, ctx);
+                self.send(&mcg_shared::Frontend2BackendMsg::CreateTable {
+                    config: TableConfig::new(players),
+                });
             }
         }
     }
@@ -327,16 +645,22 @@ impl super::game_rendering::PokerScreenActions for PokerOnlineScreen {
     ) {
         let call_amount = BettingControls::calculate_call_amount(state, player_id);
         let player = state.players.iter().find(|p| p.id == player_id);
+        // Shortcuts only act for the player this client controls
+        let shortcut = self
+            .shortcut
+            .filter(|_| enabled && player_id == self.player_manager.get_preferred_player());
 
         if let Some(player) = player {
             ui.vertical(|ui| {
                 // First row: Check/Call and Fold buttons
                 ui.horizontal(|ui| {
-                    let check_call_label = if call_amount == 0 {
-                        RichText::new("✔ Check").size(18.0)
+                    let check_call_text = if call_amount == 0 {
+                        t(I18N_CHECK_BUTTON).to_string()
                     } else {
-                        RichText::new(format!("✔ Call {}", call_amount)).size(18.0)
+                        tf(I18N_CALL_BUTTON, call_amount)
                     };
+                    let check_call_label =
+                        RichText::new(Shortcut::CheckCall.label(&check_call_text)).size(18.0);
 
                     if enabled {
                         if ui
@@ -345,8 +669,10 @@ impl super::game_rendering::PokerScreenActions for PokerOnlineScreen {
                                     .min_size(egui::vec2(120.0, 40.0)),
                             )
                             .clicked()
+                            || shortcut == Some(Shortcut::CheckCall)
                         {
                             self.send(&mcg_shared::Frontend2BackendMsg::Action {
+                                table_id: self.table_id,
                                 player_id,
                                 action: PlayerAction::CheckCall,
                             });
@@ -358,13 +684,16 @@ impl super::game_rendering::PokerScreenActions for PokerOnlineScreen {
                         );
                     }
 
-                    let fold_label = RichText::new("✂ Fold").size(18.0);
+                    let fold_label =
+                        RichText::new(Shortcut::Fold.label(t(I18N_FOLD_BUTTON))).size(18.0);
                     if enabled {
                         if ui
                             .add(egui::Button::new(fold_label).min_size(egui::vec2(120.0, 40.0)))
                             .clicked()
+                            || shortcut == Some(Shortcut::Fold)
                         {
                             self.send(&mcg_shared::Frontend2BackendMsg::Action {
+                                table_id: self.table_id,
                                 player_id,
                                 action: PlayerAction::Fold,
                             });
@@ -375,6 +704,37 @@ impl super::game_rendering::PokerScreenActions for PokerOnlineScreen {
                             egui::Button::new(fold_label).min_size(egui::vec2(120.0, 40.0)),
                         );
                     }
+
+                    let all_in_label = RichText::new(Shortcut::AllIn.label(t(I18N_ALL_IN_BUTTON)))
+                        .size(18.0)
+                        .strong();
+                    let all_in = egui::Button::new(all_in_label)
+                        .fill(egui::Color32::from_rgb(140, 30, 20))
+                        .min_size(egui::vec2(120.0, 40.0));
+                    let can_go_all_in = enabled && player.stack > 0;
+                    if ui
+                        .add_enabled(can_go_all_in, all_in)
+                        .on_hover_text(tf(I18N_ALL_IN_HINT, player.stack))
+                        .clicked()
+                        || (can_go_all_in && shortcut == Some(Shortcut::AllIn))
+                    {
+                        self.send(&mcg_shared::Frontend2BackendMsg::Action {
+                            table_id: self.table_id,
+                            player_id,
+                            action: PlayerAction::AllIn,
+                        });
+                    }
+
+                    ui.add(egui::Button::new("?").min_size(egui::vec2(24.0, 40.0)))
+                        .on_hover_text(format!(
+                            "{}\n\n{}",
+                            t(I18N_SHORTCUTS_HINT),
+                            Shortcut::help_text()
+                        ));
+
+                    if player_id == self.player_manager.get_preferred_player() {
+                        self.auto_act.show_countdown(ui, &crate::utils::WasmTimer);
+                    }
                 });
 
                 if enabled {
@@ -382,13 +742,19 @@ impl super::game_rendering::PokerScreenActions for PokerOnlineScreen {
                     // Second row: Betting/Raising controls with slider
                     self.betting_controls
                         .update_from_game_state(state, player_id);
+                    if shortcut == Some(Shortcut::Bet) {
+                        self.betting_controls.focus_slider = true;
+                    }
 
                     self.betting_controls.render_betting_controls(
                         ui,
                         state,
                         player_id,
                         player,
-                        &self.conn as &dyn MessageSender,
+                        &CancelOnAction {
+                            sender: &self.outbox,
+                            auto_act: &self.auto_act,
+                        },
                     );
                 }
             });
@@ -406,12 +772,43 @@ impl super::game_rendering::PokerScreenActions for PokerOnlineScreen {
         ui.vertical(|ui| {
             if show_next {
                 ui.horizontal(|ui| {
-                    let next_label = RichText::new("▶ Next hand").size(16.0);
+                    let next_label =
+                        RichText::new(Shortcut::NextHand.label("▶ Next hand")).size(16.0);
                     if ui
                         .add(egui::Button::new(next_label).min_size(egui::vec2(140.0, 40.0)))
                         .clicked()
+                        || self.shortcut == Some(Shortcut::NextHand)
+                    {
+                        self.send(&mcg_shared::Frontend2BackendMsg::NextHand { player_id });
+                    }
+                    let review_label = RichText::new("🔍 Review Hand").size(16.0);
+                    if ui
+                        .add(egui::Button::new(review_label).min_size(egui::vec2(140.0, 40.0)))
+                        .on_hover_text("Compare every decision of this hand with GTO")
+                        .clicked()
                     {
-                        self.send(&mcg_shared::Frontend2BackendMsg::NextHand);
+                        self.send(&mcg_shared::Frontend2BackendMsg::ReviewHand);
+                    }
+                    let short_stacked = state
+                        .players
+                        .iter()
+                        .any(|p| p.id == player_id && p.stack < state.bb * 10);
+                    if let Some((_, max)) = self.rebuy_offer.filter(|_| short_stacked) {
+                        let rebuy_label = RichText::new("💰 Rebuy").size(16.0);
+                        if ui
+                            .add(egui::Button::new(rebuy_label).min_size(egui::vec2(110.0, 40.0)))
+                            .on_hover_text("Add chips to your stack before the next hand")
+                            .clicked()
+                        {
+                            self.rebuy_amount = Some(max);
+                        }
+                    }
+                    let must_post_blind = state
+                        .players
+                        .iter()
+                        .any(|p| p.id == player_id && p.must_post_blind);
+                    if must_post_blind {
+                        self.render_post_blind_button(ui, state, player_id);
                     }
                 });
                 ui.add_space(6.0);
@@ -422,7 +819,36 @@ impl super::game_rendering::PokerScreenActions for PokerOnlineScreen {
     }
 
     fn send(&self, msg: &mcg_shared::Frontend2BackendMsg) {
-        self.conn.send_msg(msg);
+        PokerOnlineScreen::send(self, msg);
+    }
+
+    fn card_theme(&self) -> Option<&'static str> {
+        self.card_theme
+    }
+
+    fn card_back(&self) -> &CardBackDesign {
+        &self.card_back
+    }
+
+    fn render_hand_strength(
+        &mut self,
+        ui: &mut Ui,
+        state: &mcg_shared::GameStatePublic,
+        player_id: mcg_shared::PlayerId,
+        cards: [mcg_shared::Card; 2],
+    ) {
+        if self.show_hand_strength {
+            self.hand_strength.show(ui, state, player_id, cards);
+        }
+    }
+
+    fn render_action_log(
+        &mut self,
+        ui: &mut Ui,
+        state: &mcg_shared::GameStatePublic,
+        preferred_player: mcg_shared::PlayerId,
+    ) {
+        self.action_log.show(ui, state, preferred_player);
     }
 }
 
@@ -431,10 +857,43 @@ impl ScreenWidget for PokerOnlineScreen {
         let ctx = ui.ctx().clone();
         let app_state = &mut app_interface.app_state;
 
-        // Process any queued WebSocket messages first
-        self.connection_manager.dispatch_queued_messages(app_state);
+        app_state.achievements.player = self.player_manager.get_preferred_player();
+        app_state.session_stats.player = self.player_manager.get_preferred_player();
+        self.apply_export_result(app_state);
+        self.sync_profile(app_state);
+        self.sync_card_theme(app_state);
+        self.restore_session(app_state, &ctx);
+        self.card_back = app_state.settings.card_back.clone();
+        self.show_hand_strength = app_state.settings.show_hand_strength;
+        self.reconnect_if_silent(app_state, &ctx);
+        self.follow_invite(app_state, &ctx);
+        self.table_id = app_state.session.table_id.unwrap_or(DEFAULT_TABLE_ID);
+        self.betting_controls.table_id = self.table_id;
+        self.shortcut = Shortcut::pressed(&ctx);
+        if let Some(state) = &app_state.session.game_state {
+            self.poker_clock.update(state, &crate::utils::WasmTimer);
+            self.table_info_bar.update(state);
+            self.action_log.update(state, &crate::utils::WasmTimer);
+            self.auto_fold_if_away(state);
+        }
+        self.auto_act.show_toast(&ctx, &crate::utils::WasmTimer);
 
-        self.draw_error_popup(app_state, &ctx);
+        self.draw_rename_dialog(&ctx);
+        self.rebuy_offer = app_state
+            .session
+            .rebuy_offers
+            .get(&self.player_manager.get_preferred_player())
+            .copied();
+        self.draw_rebuy_dialog(&ctx);
+        if let (Some(annotations), Some(state)) = (
+            &app_state.session.hand_review,
+            &app_state.session.game_state,
+        ) {
+            let you = self.player_manager.get_preferred_player();
+            if !super::hand_review::draw_hand_review(&ctx, annotations, &state.players, you) {
+                app_state.session.hand_review = None;
+            }
+        }
 
         // Check for button clicks
         let mut connect_clicked = false;
@@ -454,11 +913,31 @@ impl ScreenWidget for PokerOnlineScreen {
             self.connect(app_state, &ctx);
         }
         if disconnect_clicked {
-            self.disconnect();
+            app_state.disconnect();
+        }
+
+        if let Some(restore) = &self.restore {
+            restore.show_banner(ui);
         }
 
         // Render main content from the latest snapshot
-        if let Some(state) = &app_state.session.game_state {
+        if app_state.connection_pool.is_connected()
+            && app_state.session.table_id.is_none()
+            && !app_state.session.restored
+        {
+            let msg = self.table_browser.render(
+                ui,
+                &app_state.session.tables,
+                self.player_manager.get_players(),
+            );
+            if let Some(msg) = msg {
+                self.send(&msg);
+            }
+        } else if let Some(state) = &app_state.session.game_state {
+            self.table_info_bar.show(ui, state);
+            if super::session_stats::is_cash_game(state) {
+                super::session_stats::show(ui, &mut app_state.session_stats);
+            }
             super::game_rendering::render_showdown_banner(
                 ui,
                 state,
@@ -473,6 +952,10 @@ impl ScreenWidget for PokerOnlineScreen {
         } else {
             ui.label("No state yet. Click Connect to start a session.");
         }
+
+        for msg in self.outbox.0.take() {
+            app_state.send_msg(&msg);
+        }
     }
 }
 
@@ -488,11 +971,41 @@ impl PokerOnlineScreen {
         ui.horizontal(|ui| {
             ui.heading("Poker Online");
             ui.add_space(16.0);
-            if let Some(s) = &app_state.session.game_state {
-                ui.label(super::ui_components::stage_badge(s.stage));
+            if let Some(table_id) = app_state.session.table_id {
+                ui.label(format!("Table #{}", table_id));
                 ui.add_space(8.0);
             }
+            let selected = self
+                .card_theme
+                .and_then(crate::hardcoded_cards::ThemeRegistry::get)
+                .map_or("Text", |t| t.display_name);
+            egui::ComboBox::from_id_salt("poker_card_theme")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.card_theme, None, "Text");
+                    for theme in crate::hardcoded_cards::ThemeRegistry::list_themes() {
+                        ui.selectable_value(
+                            &mut self.card_theme,
+                            Some(theme.id),
+                            theme.display_name,
+                        );
+                    }
+                })
+                .response
+                .on_hover_text("Card style");
+            ui.add_space(8.0);
+            if app_state.session.game_state.is_some()
+                && ui
+                    .button("Export History")
+                    .on_hover_text("Download the hand history of this session as text")
+                    .clicked()
+            {
+                self.export_history(app_state);
+            }
         });
+        if let Some(s) = &app_state.session.game_state {
+            self.poker_clock.show(ui, s);
+        }
 
         let default_open = app_state.session.game_state.is_none();
         egui::CollapsingHeader::new("Connection & session")
@@ -505,6 +1018,12 @@ impl PokerOnlineScreen {
                     connect_clicked,
                     disconnect_clicked,
                 );
+                ui.toggle_value(&mut self.auto_act_enabled, "Auto-fold when away")
+                    .on_hover_text("Fold automatically when you do not act within 10 seconds");
+                ui.add_space(8.0);
+                if let Some(address) = self.lobby_browser.render(ui) {
+                    self.connection_manager.set_server_address(address);
+                }
             });
 
         egui::CollapsingHeader::new("Player Setup")
@@ -512,13 +1031,6 @@ impl PokerOnlineScreen {
             .show(ui, |ui| {
                 self.render_full_player_setup(ui, ctx, app_state);
             });
-
-        if let Some(err) = &app_state.ui.last_error {
-            ui.colored_label(egui::Color32::RED, err);
-        }
-        if let Some(info) = &app_state.ui.last_info {
-            ui.label(RichText::new(info));
-        }
         ui.separator();
     }
 }
@@ -0,0 +1,204 @@
+use egui::{RichText, Ui};
+use mcg_shared::{GameStatePublic, TableId};
+
+use crate::store::{CachedSession, ConnectionStatus};
+use crate::utils::Clock;
+
+/// Attempts to reconnect before the cached session is given up
+pub const MAX_ATTEMPTS: u32 = 3;
+
+/// Pause after a failed attempt before the next one
+const RETRY_DELAY_MS: f64 = 1_000.0;
+
+/// An attempt fails when the table's state has not arrived after this long,
+/// e.g. because the table was closed in the meantime
+const ATTEMPT_TIMEOUT_MS: f64 = 10_000.0;
+
+/// What the screen should do next to restore the session
+#[derive(Clone, Debug, PartialEq)]
+pub enum RestoreStep {
+    /// Connect to the server and join the table
+    Connect {
+        server_address: String,
+        table_id: TableId,
+    },
+    /// Waiting for the connection, the table's state or the next attempt
+    Wait,
+    /// The server sent the table's state
+    Restored,
+    /// Every attempt failed; the cached session should be dropped
+    Failed,
+}
+
+/// Reconnects to the table of a [`CachedSession`] after a page refresh,
+/// while the screen shows its cached state.
+#[derive(Debug)]
+pub struct SessionRestore {
+    session: CachedSession,
+    attempts: u32,
+    /// When the current attempt started
+    attempt_started_ms: f64,
+    /// When to try again after a failed attempt
+    retry_at_ms: Option<f64>,
+}
+
+impl SessionRestore {
+    pub fn new(session: CachedSession) -> Self {
+        Self {
+            session,
+            attempts: 0,
+            attempt_started_ms: 0.0,
+            retry_at_ms: None,
+        }
+    }
+
+    /// State of the table when the page was refreshed
+    pub fn cached_state(&self) -> &GameStatePublic {
+        &self.session.game_state
+    }
+
+    /// Next step, given the connection status and whether the state shown is
+    /// still the cached one; call once per frame.
+    pub fn update(
+        &mut self,
+        status: &ConnectionStatus,
+        showing_cached: bool,
+        clock: &impl Clock,
+    ) -> RestoreStep {
+        let now = clock.now_ms();
+        if self.attempts == 0 {
+            return self.attempt(now);
+        }
+        if !showing_cached {
+            return RestoreStep::Restored;
+        }
+        let failed = matches!(status, ConnectionStatus::Disconnected)
+            || now - self.attempt_started_ms > ATTEMPT_TIMEOUT_MS;
+        if !failed {
+            return RestoreStep::Wait;
+        }
+        if self.attempts >= MAX_ATTEMPTS {
+            return RestoreStep::Failed;
+        }
+        match self.retry_at_ms {
+            None => {
+                self.retry_at_ms = Some(now + RETRY_DELAY_MS);
+                RestoreStep::Wait
+            }
+            Some(at) if now >= at => self.attempt(now),
+            Some(_) => RestoreStep::Wait,
+        }
+    }
+
+    fn attempt(&mut self, now: f64) -> RestoreStep {
+        self.attempts += 1;
+        self.attempt_started_ms = now;
+        self.retry_at_ms = None;
+        RestoreStep::Connect {
+            server_address: self.session.server_address.clone(),
+            table_id: self.session.table_id,
+        }
+    }
+
+    pub fn show_banner(&self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label(RichText::new("Reconnecting to restore session...").italics());
+            if self.attempts > 1 {
+                ui.label(
+                    RichText::new(format!("attempt {} of {}", self.attempts, MAX_ATTEMPTS)).weak(),
+                );
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::{BettingLimit, PlayerId, Stage};
+    use std::cell::Cell;
+
+    struct MockClock(Cell<f64>);
+
+    impl Clock for MockClock {
+        fn now_ms(&self) -> f64 {
+            self.0.get()
+        }
+    }
+
+    fn restore() -> SessionRestore {
+        SessionRestore::new(CachedSession {
+            server_address: "example.org:3000".to_string(),
+            table_id: 7,
+            game_state: GameStatePublic {
+                players: Vec::new(),
+                community: Vec::new(),
+                pot: 0,
+                sb: 5,
+                bb: 10,
+                to_act: PlayerId(0),
+                stage: Stage::Flop,
+                winner_ids: Vec::new(),
+                action_log: Vec::new(),
+                current_bet: 0,
+                min_raise: 0,
+                betting_limit: BettingLimit::NoLimit,
+                blind_level: None,
+                pot_equities: None,
+                pot_breakdown: Vec::new(),
+                dealer: None,
+                hand_number: 3,
+                hands_remaining: None,
+            },
+        })
+    }
+
+    fn connect() -> RestoreStep {
+        RestoreStep::Connect {
+            server_address: "example.org:3000".to_string(),
+            table_id: 7,
+        }
+    }
+
+    #[test]
+    fn a_fresh_state_ends_the_restore() {
+        let clock = MockClock(Cell::new(0.0));
+        let mut restore = restore();
+        let connecting = ConnectionStatus::Connecting;
+        assert_eq!(restore.update(&connecting, true, &clock), connect());
+        assert_eq!(restore.update(&connecting, true, &clock), RestoreStep::Wait);
+        let connected = ConnectionStatus::Connected;
+        assert_eq!(
+            restore.update(&connected, false, &clock),
+            RestoreStep::Restored
+        );
+    }
+
+    #[test]
+    fn the_restore_fails_after_three_attempts() {
+        let clock = MockClock(Cell::new(0.0));
+        let mut restore = restore();
+        let down = ConnectionStatus::Disconnected;
+        assert_eq!(restore.update(&down, true, &clock), connect());
+        for _ in 1..MAX_ATTEMPTS {
+            // The next attempt waits out the retry delay
+            assert_eq!(restore.update(&down, true, &clock), RestoreStep::Wait);
+            clock.0.set(clock.0.get() + RETRY_DELAY_MS);
+            assert_eq!(restore.update(&down, true, &clock), connect());
+        }
+        assert_eq!(restore.update(&down, true, &clock), RestoreStep::Failed);
+    }
+
+    #[test]
+    fn an_attempt_without_the_tables_state_times_out() {
+        let clock = MockClock(Cell::new(0.0));
+        let mut restore = restore();
+        let connected = ConnectionStatus::Connected;
+        assert_eq!(restore.update(&connected, true, &clock), connect());
+        clock.0.set(ATTEMPT_TIMEOUT_MS + 1.0);
+        assert_eq!(restore.update(&connected, true, &clock), RestoreStep::Wait);
+        clock.0.set(ATTEMPT_TIMEOUT_MS + 1.0 + RETRY_DELAY_MS);
+        assert_eq!(restore.update(&connected, true, &clock), connect());
+    }
+}
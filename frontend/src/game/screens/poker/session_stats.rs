@@ -0,0 +1,60 @@
+use egui::{Align, Color32, Layout, RichText, Ui};
+use mcg_shared::GameStatePublic;
+
+use crate::store::SessionStats;
+
+const PROFIT_COLOR: Color32 = Color32::from_rgb(80, 200, 120);
+const LOSS_COLOR: Color32 = Color32::from_rgb(220, 80, 80);
+
+/// Only cash games have a session result; games with a blind schedule are
+/// tournaments.
+pub fn is_cash_game(gs: &GameStatePublic) -> bool {
+    gs.blind_level.is_none()
+}
+
+/// Session P&L in the top-right corner above the table. It takes up its own
+/// row rather than floating, so it never covers the action buttons.
+pub fn show(ui: &mut Ui, stats: &mut SessionStats) {
+    let net = stats.net();
+    let (text, color) = match net {
+        n if n > 0 => (format!("+{}", n), PROFIT_COLOR),
+        n if n < 0 => (n.to_string(), LOSS_COLOR),
+        _ => ("±0".to_string(), ui.visuals().text_color()),
+    };
+    ui.with_layout(Layout::right_to_left(Align::Min), |ui| {
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.with_layout(Layout::top_down(Align::LEFT), |ui| {
+                let header = RichText::new(format!("Session {}", text))
+                    .strong()
+                    .color(color);
+                egui::CollapsingHeader::new(header)
+                    .id_salt("session_stats")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        egui::Grid::new("session_stats_grid")
+                            .spacing([12.0, 2.0])
+                            .show(ui, |ui| {
+                                ui.label("Hands played");
+                                ui.monospace(stats.hands_played.to_string());
+                                ui.end_row();
+                                ui.label("Win rate");
+                                ui.monospace(stats.win_rate().map_or_else(
+                                    || "-".to_string(),
+                                    |rate| format!("{:.0}%", rate * 100.0),
+                                ));
+                                ui.end_row();
+                                ui.label("Biggest win");
+                                ui.monospace(stats.biggest_win.to_string());
+                                ui.end_row();
+                                ui.label("Biggest loss");
+                                ui.monospace(stats.biggest_loss.to_string());
+                                ui.end_row();
+                            });
+                        if ui.button("Reset Session Stats").clicked() {
+                            stats.reset();
+                        }
+                    });
+            });
+        });
+    });
+}
@@ -0,0 +1,135 @@
+//! Keyboard shortcuts for the actions of the local player.
+
+use egui::{Context, Key};
+
+/// Action that can be taken with a single key press
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shortcut {
+    CheckCall,
+    Fold,
+    /// Focus the bet or raise slider, so the amount can be set with the arrow keys
+    Bet,
+    AllIn,
+    NextHand,
+}
+
+impl Shortcut {
+    pub const ALL: [Shortcut; 5] = [
+        Shortcut::CheckCall,
+        Shortcut::Fold,
+        Shortcut::Bet,
+        Shortcut::AllIn,
+        Shortcut::NextHand,
+    ];
+
+    pub fn key(self) -> Key {
+        match self {
+            Shortcut::CheckCall => Key::C,
+            Shortcut::Fold => Key::F,
+            Shortcut::Bet => Key::B,
+            Shortcut::AllIn => Key::A,
+            Shortcut::NextHand => Key::N,
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Shortcut::CheckCall => "Check / Call",
+            Shortcut::Fold => "Fold",
+            Shortcut::Bet => "Bet or raise: focus the amount slider",
+            Shortcut::AllIn => "All in",
+            Shortcut::NextHand => "Next hand, after the showdown",
+        }
+    }
+
+    /// `text` with the key in brackets, e.g. "Fold [F]"
+    pub fn label(self, text: &str) -> String {
+        format!("{} [{}]", text, self.key().name())
+    }
+
+    /// Shortcut pressed this frame. Keys typed into a focused widget, such
+    /// as a text field, and key combinations like Ctrl+C are ignored.
+    pub fn pressed(ctx: &Context) -> Option<Shortcut> {
+        if ctx.memory(|m| m.focused().is_some()) {
+            return None;
+        }
+        ctx.input(|i| {
+            if i.modifiers.any() {
+                return None;
+            }
+            Self::ALL.into_iter().find(|s| i.key_pressed(s.key()))
+        })
+    }
+
+    /// Reference of all shortcuts, one per line
+    pub fn help_text() -> String {
+        Self::ALL
+            .iter()
+            .map(|s| format!("{}  {}", s.key().name(), s.description()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::{Event, Modifiers, RawInput};
+
+    fn press(key: Key, modifiers: Modifiers) -> RawInput {
+        RawInput {
+            events: vec![Event::Key {
+                key,
+                physical_key: None,
+                pressed: true,
+                repeat: false,
+                modifiers,
+            }],
+            modifiers,
+            ..Default::default()
+        }
+    }
+
+    /// Shortcut seen in a frame with `input`, optionally with a text field focused
+    fn run(input: RawInput, text_focused: bool) -> Option<Shortcut> {
+        let ctx = Context::default();
+        let mut text = String::new();
+        let mut shortcut = None;
+        let _ = ctx.run(input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let edit = ui.text_edit_singleline(&mut text);
+                if text_focused {
+                    edit.request_focus();
+                }
+            });
+            shortcut = Shortcut::pressed(ctx);
+        });
+        shortcut
+    }
+
+    #[test]
+    fn keys_map_to_actions_unless_typing_or_combined() {
+        assert_eq!(
+            run(press(Key::F, Modifiers::NONE), false),
+            Some(Shortcut::Fold)
+        );
+        assert_eq!(
+            run(press(Key::A, Modifiers::NONE), false),
+            Some(Shortcut::AllIn)
+        );
+        assert_eq!(run(press(Key::X, Modifiers::NONE), false), None);
+        assert_eq!(run(press(Key::C, Modifiers::CTRL), false), None);
+        assert_eq!(run(press(Key::C, Modifiers::NONE), true), None);
+    }
+
+    #[test]
+    fn labels_and_help_name_every_key() {
+        assert_eq!(
+            Shortcut::CheckCall.label("Check / Call"),
+            "Check / Call [C]"
+        );
+        let help = Shortcut::help_text();
+        assert_eq!(help.lines().count(), Shortcut::ALL.len());
+        assert!(help.starts_with("C  Check / Call"));
+    }
+}
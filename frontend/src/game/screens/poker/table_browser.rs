@@ -0,0 +1,131 @@
+//! Lobby browser listing the server's tables with create and join controls.
+
+use egui::{RichText, Ui};
+use mcg_shared::{BettingLimit, Frontend2BackendMsg, PlayerConfig, TableConfig, TableSummary};
+
+/// Blind levels, betting limit and knockout bounty chosen for the next table
+/// to be created
+pub struct TableBrowser {
+    sb: u32,
+    bb: u32,
+    limit: BettingLimit,
+    knockout: bool,
+    bounty: u32,
+}
+
+impl Default for TableBrowser {
+    fn default() -> Self {
+        let defaults = TableConfig::new(Vec::new());
+        Self {
+            sb: defaults.sb,
+            bb: defaults.bb,
+            limit: defaults.betting_limit,
+            knockout: false,
+            bounty: 100,
+        }
+    }
+}
+
+impl TableBrowser {
+    /// Fixed limit with the big blind as small bet, as is usual
+    fn fixed_limit(&self) -> BettingLimit {
+        BettingLimit::FixedLimit {
+            small_bet: self.bb,
+            big_bet: self.bb.saturating_mul(2),
+        }
+    }
+
+    /// Render the lobby. Returns the message to send when the user refreshes,
+    /// creates or joins a table.
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        tables: &[TableSummary],
+        players: &[PlayerConfig],
+    ) -> Option<Frontend2BackendMsg> {
+        let mut msg = None;
+
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Tables").strong());
+                if ui.button("⟳ Refresh").clicked() {
+                    msg = Some(Frontend2BackendMsg::ListTables);
+                }
+            });
+            ui.add_space(4.0);
+
+            if tables.is_empty() {
+                ui.label("No tables yet. Create one below.");
+            } else {
+                egui::Grid::new("tables_grid")
+                    .num_columns(4)
+                    .spacing([12.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label(RichText::new("Table").strong());
+                        ui.label(RichText::new("Players").strong());
+                        ui.label(RichText::new("Blinds").strong());
+                        ui.label("");
+                        ui.end_row();
+
+                        for t in tables {
+                            ui.label(format!("#{}", t.table_id));
+                            ui.label(t.player_count.to_string());
+                            ui.label(format!("{}/{}", t.sb, t.bb));
+                            if ui.button("Join").clicked() {
+                                msg = Some(Frontend2BackendMsg::JoinTable {
+                                    table_id: t.table_id,
+                                });
+                            }
+                            ui.end_row();
+                        }
+                    });
+            }
+        });
+
+        ui.add_space(8.0);
+        ui.group(|ui| {
+            ui.label(RichText::new("Create Table").strong());
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label("Small blind:");
+                ui.add(egui::DragValue::new(&mut self.sb).range(1..=u32::MAX / 2));
+                ui.label("Big blind:");
+                ui.add(egui::DragValue::new(&mut self.bb).range(self.sb..=u32::MAX));
+                if matches!(self.limit, BettingLimit::FixedLimit { .. }) {
+                    self.limit = self.fixed_limit();
+                }
+                let fixed = self.fixed_limit();
+                egui::ComboBox::from_id_salt("betting_limit")
+                    .selected_text(self.limit.to_string())
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.limit, BettingLimit::NoLimit, "No Limit");
+                        ui.selectable_value(&mut self.limit, BettingLimit::PotLimit, "Pot Limit");
+                        ui.selectable_value(&mut self.limit, fixed, fixed.to_string());
+                    });
+                ui.checkbox(&mut self.knockout, "Bounty:");
+                ui.add_enabled(
+                    self.knockout,
+                    egui::DragValue::new(&mut self.bounty).range(1..=u32::MAX / 2),
+                );
+                if ui
+                    .button("Create")
+                    .on_hover_text("Open a table with the players from Player Setup")
+                    .clicked()
+                {
+                    msg = Some(Frontend2BackendMsg::CreateTable {
+                        config: TableConfig {
+                            players: players.to_vec(),
+                            sb: self.sb,
+                            bb: self.bb.max(self.sb),
+                            bounty: self.knockout.then_some(self.bounty),
+                            betting_limit: self.limit,
+                        },
+                    });
+                }
+            });
+        });
+
+        msg
+    }
+}
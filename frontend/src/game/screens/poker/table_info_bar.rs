@@ -0,0 +1,142 @@
+use egui::{vec2, Align, Layout, RichText, Ui};
+use mcg_shared::{ActionEvent, GameAction, GameStatePublic, PlayerPublic, Stage};
+
+use super::ui_components::{dealer_button, stage_badge};
+use crate::store::new_events;
+
+/// Height of the single row the bar takes up
+const BAR_HEIGHT: f32 = 30.0;
+
+/// Row above the player panels with what players look up most: the dealer,
+/// the blinds, the hand number and the stage.
+#[derive(Debug, Default)]
+pub struct TableInfoBar {
+    /// Hands started since the screen was opened, shown for servers that
+    /// don't number their hands
+    hands: u32,
+    /// Action log of the last state, to count every hand once
+    seen_log: Vec<ActionEvent>,
+}
+
+fn is_hand_start(event: &ActionEvent) -> bool {
+    matches!(
+        event,
+        ActionEvent::GameAction(GameAction::StageChanged(Stage::Preflop))
+    )
+}
+
+impl TableInfoBar {
+    /// Count the hands started in `gs`; call once per frame with the latest state.
+    pub fn update(&mut self, gs: &GameStatePublic) {
+        if gs.action_log == self.seen_log {
+            return;
+        }
+        let started = new_events(&self.seen_log, &gs.action_log)
+            .iter()
+            .filter(|e| is_hand_start(e))
+            .count();
+        self.hands += started as u32;
+        self.seen_log = gs.action_log.clone();
+    }
+
+    /// Number of the current hand, counting from 1; 0 before the first deal.
+    pub fn hand_number(&self, gs: &GameStatePublic) -> u32 {
+        match gs.hand_number {
+            0 => self.hands,
+            n => n,
+        }
+    }
+
+    /// "Hand #47", or "Hand #47 of 50" in games of a fixed number of hands
+    fn hand_label(&self, gs: &GameStatePublic) -> String {
+        let hand = self.hand_number(gs);
+        match gs.hands_remaining {
+            _ if hand == 0 => "Hand #-".to_string(),
+            Some(left) => format!("Hand #{} of {}", hand, hand + left.saturating_sub(1)),
+            None => format!("Hand #{}", hand),
+        }
+    }
+
+    pub fn show(&self, ui: &mut Ui, gs: &GameStatePublic) {
+        let size = vec2(ui.available_width(), BAR_HEIGHT);
+        ui.allocate_ui_with_layout(size, Layout::left_to_right(Align::Center), |ui| {
+            ui.set_min_height(BAR_HEIGHT);
+            if let Some(dealer) = gs.dealer {
+                dealer_button(ui);
+                ui.label(PlayerPublic::name_of(&gs.players, dealer));
+                ui.separator();
+            }
+            ui.label(RichText::new(format!("SB: ${} / BB: ${}", gs.sb, gs.bb)).monospace());
+            ui.separator();
+            ui.label(RichText::new(self.hand_label(gs)).monospace().strong());
+            ui.separator();
+            ui.label(stage_badge(gs.stage));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::{BettingLimit, PlayerId};
+
+    fn state(action_log: Vec<ActionEvent>) -> GameStatePublic {
+        GameStatePublic {
+            players: Vec::new(),
+            community: Vec::new(),
+            pot: 0,
+            sb: 5,
+            bb: 10,
+            to_act: PlayerId(0),
+            stage: Stage::Preflop,
+            winner_ids: Vec::new(),
+            action_log,
+            current_bet: 0,
+            min_raise: 0,
+            betting_limit: BettingLimit::NoLimit,
+            blind_level: None,
+            pot_equities: None,
+            pot_breakdown: Vec::new(),
+            dealer: None,
+            hand_number: 0,
+            hands_remaining: None,
+        }
+    }
+
+    #[test]
+    fn hands_are_counted_once_per_preflop() {
+        let dealt = ActionEvent::game(GameAction::DealtHole {
+            player_id: PlayerId(0),
+        });
+        let preflop = ActionEvent::game(GameAction::StageChanged(Stage::Preflop));
+        let flop = ActionEvent::game(GameAction::StageChanged(Stage::Flop));
+        let mut bar = TableInfoBar::default();
+        assert_eq!(bar.hand_number(&state(Vec::new())), 0);
+
+        let mut log = vec![dealt.clone(), preflop.clone()];
+        bar.update(&state(log.clone()));
+        bar.update(&state(log.clone()));
+        assert_eq!(bar.hand_number(&state(log.clone())), 1);
+        log.push(flop);
+        bar.update(&state(log.clone()));
+        assert_eq!(bar.hand_number(&state(log)), 1);
+
+        // The log restarts with the next hand
+        let log = vec![dealt, preflop];
+        bar.update(&state(log.clone()));
+        assert_eq!(bar.hand_number(&state(log)), 2);
+    }
+
+    #[test]
+    fn the_servers_hand_number_is_shown() {
+        let mut bar = TableInfoBar::default();
+        let mut gs = state(Vec::new());
+        assert_eq!(bar.hand_label(&gs), "Hand #-");
+
+        gs.hand_number = 47;
+        bar.update(&gs);
+        assert_eq!(bar.hand_label(&gs), "Hand #47");
+        gs.hands_remaining = Some(4);
+        assert_eq!(bar.hand_label(&gs), "Hand #47 of 50");
+    }
+}
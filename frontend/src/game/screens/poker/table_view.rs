@@ -0,0 +1,110 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use egui::{vec2, Align2, Color32, FontId, Pos2, Sense, Shape, Stroke, Ui, Vec2};
+use mcg_shared::{GameStatePublic, PlayerId, Stage};
+
+/// Smallest size the oval table is drawn at; below it the seats would overlap
+const MIN_SIZE: Vec2 = vec2(360.0, 200.0);
+/// Height of the table relative to its width
+const ASPECT: f32 = 0.55;
+const SEAT_RADIUS: f32 = 16.0;
+const FELT_COLOR: Color32 = Color32::from_rgb(30, 90, 55);
+const RIM_COLOR: Color32 = Color32::from_rgb(90, 60, 35);
+const TO_ACT_COLOR: Color32 = Color32::from_rgb(255, 215, 0);
+
+/// Points around an ellipse with `radius` for `n` seats, in seat order, with
+/// the seat at index `you` at the bottom like a player looking at the table.
+pub fn seat_positions(n: usize, you: usize, center: Pos2, radius: Vec2) -> Vec<Pos2> {
+    (0..n)
+        .map(|i| {
+            let steps = (i + n - you) % n;
+            let angle = FRAC_PI_2 + TAU * steps as f32 / n as f32;
+            center + vec2(radius.x * angle.cos(), radius.y * angle.sin())
+        })
+        .collect()
+}
+
+/// Bird's-eye view of the table with every seat around an oval, if the
+/// space left in `ui` permits.
+pub fn render_table_view(ui: &mut Ui, state: &GameStatePublic, preferred_player: PlayerId) {
+    let width = ui.available_width();
+    let size = vec2(width, (width * ASPECT).min(ui.available_height()));
+    if size.x < MIN_SIZE.x || size.y < MIN_SIZE.y {
+        return;
+    }
+    let (rect, _) = ui.allocate_exact_size(size, Sense::hover());
+    let painter = ui.painter_at(rect);
+    // Leave room for the seats and their labels outside the rim
+    let radius = rect.size() / 2.0 - vec2(SEAT_RADIUS * 3.0, SEAT_RADIUS * 2.0);
+    painter.add(Shape::ellipse_filled(rect.center(), radius, FELT_COLOR));
+    painter.add(Shape::ellipse_stroke(
+        rect.center(),
+        radius,
+        Stroke::new(6.0, RIM_COLOR),
+    ));
+    painter.text(
+        rect.center(),
+        Align2::CENTER_CENTER,
+        format!("Pot {}", state.pot),
+        FontId::monospace(14.0),
+        Color32::WHITE,
+    );
+
+    let you = state
+        .players
+        .iter()
+        .position(|p| p.id == preferred_player)
+        .unwrap_or(0);
+    let seats = seat_positions(state.players.len(), you, rect.center(), radius);
+    let text_color = ui.visuals().text_color();
+    for (p, pos) in state.players.iter().zip(seats) {
+        let fill = match p.avatar_color {
+            Some([r, g, b]) => Color32::from_rgb(r, g, b),
+            None => Color32::from_gray(120),
+        };
+        let fill = if p.has_folded {
+            fill.gamma_multiply(0.3)
+        } else {
+            fill
+        };
+        painter.circle_filled(pos, SEAT_RADIUS, fill);
+        if p.id == state.to_act && state.stage != Stage::Showdown {
+            painter.circle_stroke(pos, SEAT_RADIUS + 2.0, Stroke::new(2.0, TO_ACT_COLOR));
+        }
+        if state.dealer == Some(p.id) {
+            let chip = pos + vec2(SEAT_RADIUS, -SEAT_RADIUS);
+            painter.circle_filled(chip, 7.0, Color32::WHITE);
+            painter.text(
+                chip,
+                Align2::CENTER_CENTER,
+                "D",
+                FontId::monospace(9.0),
+                Color32::BLACK,
+            );
+        }
+        painter.text(
+            pos + vec2(0.0, SEAT_RADIUS + 2.0),
+            Align2::CENTER_TOP,
+            format!("{}\n{}", p.name, p.stack),
+            FontId::proportional(12.0),
+            text_color,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn your_seat_is_at_the_bottom() {
+        let center = Pos2::new(100.0, 100.0);
+        let radius = vec2(80.0, 40.0);
+        let seats = seat_positions(4, 2, center, radius);
+        assert!((seats[2] - Pos2::new(100.0, 140.0)).length() < 1e-3);
+        // The rest follow clockwise on screen: left, top, right
+        assert!((seats[3] - Pos2::new(20.0, 100.0)).length() < 1e-3);
+        assert!((seats[0] - Pos2::new(100.0, 60.0)).length() < 1e-3);
+        assert!((seats[1] - Pos2::new(180.0, 100.0)).length() < 1e-3);
+    }
+}
@@ -1,9 +1,147 @@
-use egui::{Color32, RichText, Ui, WidgetText};
+use crate::store::CardBackDesign;
+use egui::ecolor::Hsva;
+use egui::{Color32, Painter, Pos2, RichText, Ui, WidgetText};
 use mcg_shared::{
-    ActionEvent, ActionKind, BlindKind, Card, GameAction, GameStatePublic, HandResult, PlayerId,
-    PlayerPublic, Stage,
+    ActionEvent, ActionKind, BlindKind, Card, GameAction, GameStatePublic, HandRankCategory,
+    HandResult, PlayerId, PlayerPublic, Stage,
 };
 
+/// How a card is drawn: as a text chip or as an image from the current card theme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CardRenderer {
+    Text,
+    Image(egui::TextureId),
+}
+
+impl CardRenderer {
+    /// Resolve the renderer for `card` in `theme`. Falls back to text while the
+    /// image is still loading, failed to load, or no theme is selected.
+    pub fn for_card(ctx: &egui::Context, card: Card, theme: Option<&str>) -> Self {
+        let Some(name) = theme.and_then(|t| crate::hardcoded_cards::card_image_name(t, card))
+        else {
+            return Self::Text;
+        };
+        let uri = format!(
+            "{}/media/{}/{}",
+            crate::game::card::get_origin(),
+            theme.unwrap_or_default(),
+            name
+        );
+        match ctx.try_load_texture(
+            &uri,
+            egui::TextureOptions::default(),
+            egui::load::SizeHint::default(),
+        ) {
+            Ok(egui::load::TexturePoll::Ready { texture }) => Self::Image(texture.id),
+            _ => Self::Text,
+        }
+    }
+}
+
+/// Size of a face-down card
+const CARD_BACK_SIZE: egui::Vec2 = egui::vec2(40.0, 56.0);
+const CARD_BACK_FILL: Color32 = Color32::from_rgb(30, 40, 80);
+const CARD_BACK_PATTERN: Color32 = Color32::from_rgb(120, 140, 200);
+
+impl CardRenderer {
+    /// Draw a face-down card with the given back design. A custom back falls
+    /// back to the default design until its image has loaded.
+    pub fn render_back(ui: &mut Ui, design: &CardBackDesign) {
+        let (rect, response) = ui.allocate_exact_size(CARD_BACK_SIZE, egui::Sense::hover());
+        response.on_hover_text("Face-down card");
+        let painter = ui.painter_at(rect);
+        let rounding = egui::CornerRadius::same(4);
+        let border = egui::Stroke::new(2.0, CARD_BACK_PATTERN);
+
+        if let CardBackDesign::Custom(theme) = design {
+            let uri = format!(
+                "{}/media/{}/back.png",
+                crate::game::card::get_origin(),
+                theme
+            );
+            if let Ok(egui::load::TexturePoll::Ready { texture }) = ui.ctx().try_load_texture(
+                &uri,
+                egui::TextureOptions::default(),
+                egui::load::SizeHint::default(),
+            ) {
+                let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+                painter.image(texture.id, rect, uv, Color32::WHITE);
+                return;
+            }
+        }
+
+        painter.rect_filled(rect, rounding, CARD_BACK_FILL);
+        let inner = rect.shrink(5.0);
+        let pattern = egui::Stroke::new(1.5, CARD_BACK_PATTERN);
+        match design {
+            CardBackDesign::Striped => {
+                let clipped = painter.with_clip_rect(inner);
+                let mut x = inner.left() - inner.height();
+                while x < inner.right() {
+                    clipped.line_segment(
+                        [
+                            egui::pos2(x, inner.bottom()),
+                            egui::pos2(x + inner.height(), inner.top()),
+                        ],
+                        pattern,
+                    );
+                    x += 6.0;
+                }
+            }
+            CardBackDesign::Dots => {
+                let mut y = inner.top() + 3.0;
+                while y < inner.bottom() {
+                    let mut x = inner.left() + 3.0;
+                    while x < inner.right() {
+                        painter.circle_filled(egui::pos2(x, y), 1.5, CARD_BACK_PATTERN);
+                        x += 6.0;
+                    }
+                    y += 6.0;
+                }
+            }
+            CardBackDesign::Default | CardBackDesign::Custom(_) => {
+                painter.rect_stroke(inner, rounding, pattern, egui::StrokeKind::Inside);
+            }
+        }
+        painter.rect_stroke(rect, rounding, border, egui::StrokeKind::Inside);
+    }
+}
+
+/// Draw a card with the given renderer, scaled down to fit the available space.
+pub fn render_card(ui: &mut Ui, card: Card, renderer: &CardRenderer) {
+    match renderer {
+        CardRenderer::Text => card_chip(ui, card),
+        CardRenderer::Image(id) => {
+            ui.add(card_image(*id, ui.available_size()))
+                .on_hover_text(card.to_detailed_string());
+        }
+    }
+}
+
+/// Draw a card revealed at showdown, set apart from the viewer's own cards.
+pub fn render_revealed_card(ui: &mut Ui, card: Card, renderer: &CardRenderer) {
+    match renderer {
+        CardRenderer::Text => revealed_card_chip(ui, card),
+        CardRenderer::Image(id) => {
+            egui::Frame::new()
+                .stroke(egui::Stroke::new(2.0, Color32::from_rgb(255, 215, 0)))
+                .show(ui, |ui| {
+                    ui.add(card_image(*id, ui.available_size()))
+                        .on_hover_text("Revealed at showdown");
+                });
+        }
+    }
+}
+
+fn card_image(id: egui::TextureId, max_size: egui::Vec2) -> egui::Image<'static> {
+    egui::Image::new(egui::load::SizedTexture::new(
+        id,
+        crate::game::card::CARD_NATURAL_SIZE,
+    ))
+    .maintain_aspect_ratio(true)
+    .max_size(max_size)
+}
+
 pub fn card_chip(ui: &mut Ui, c: Card) {
     let (text, color) = card_text_and_color(c);
     let b = egui::widgets::Button::new(RichText::new(text).color(color).size(28.0))
@@ -11,8 +149,18 @@ pub fn card_chip(ui: &mut Ui, c: Card) {
     ui.add(b);
 }
 
+/// A face-up card revealed at showdown, outlined to set it apart from the viewer's own cards.
+pub fn revealed_card_chip(ui: &mut Ui, c: Card) {
+    let (text, color) = card_text_and_color(c);
+    let b = egui::widgets::Button::new(RichText::new(text).color(color).size(28.0))
+        .min_size(egui::vec2(48.0, 40.0))
+        .fill(Color32::from_rgb(40, 60, 40))
+        .stroke(egui::Stroke::new(2.0, Color32::from_rgb(255, 215, 0)));
+    ui.add(b).on_hover_text("Revealed at showdown");
+}
+
 pub fn card_text_and_color(c: Card) -> (String, Color32) {
-    let text = c.to_string();
+    let text = format!("{:#}", c);
     let color = if c.is_red() {
         Color32::from_rgb(220, 50, 50)
     } else {
@@ -31,6 +179,10 @@ pub fn action_kind_text(kind: &ActionKind) -> (String, Color32) {
             format!("▲ raises to {} (+{})", to, by),
             Color32::from_rgb(250, 160, 60),
         ),
+        ActionKind::AllIn { amount } => (
+            format!("🔥 goes all-in for {}!", amount),
+            Color32::from_rgb(255, 70, 40),
+        ),
         ActionKind::PostBlind { kind, amount } => match kind {
             BlindKind::SmallBlind => (
                 format!("● posts small blind {}", amount),
@@ -44,6 +196,66 @@ pub fn action_kind_text(kind: &ActionKind) -> (String, Color32) {
     }
 }
 
+/// Color of the local player wherever players are told apart by color
+pub const YOU_COLOR: Color32 = Color32::from_rgb(60, 230, 90);
+/// Hues around `YOU_COLOR` that no other player gets, in degrees
+const YOU_HUES: std::ops::Range<f32> = 90.0..150.0;
+
+/// Color of a seat, evenly spaced with the others on the color wheel. The
+/// greens of `YOU_COLOR` are left out, so nobody is mistaken for the viewer.
+pub fn player_color(player_id: PlayerId, total_players: usize) -> Color32 {
+    let total = total_players.max(1);
+    let share = (player_id.0 % total) as f32 / total as f32;
+    let free = 360.0 - (YOU_HUES.end - YOU_HUES.start);
+    let hue = (YOU_HUES.end + share * free) % 360.0;
+    Hsva::new(hue / 360.0, 0.7, 0.9, 1.0).into()
+}
+
+/// `player_color` at half the brightness, for players who folded.
+pub fn player_color_dim(player_id: PlayerId, total_players: usize) -> Color32 {
+    dim(player_color(player_id, total_players))
+}
+
+fn dim(color: Color32) -> Color32 {
+    let mut hsva = Hsva::from(color);
+    hsva.v *= 0.5;
+    hsva.into()
+}
+
+/// Colors of the players at one table as seen by `you`, who is always shown
+/// in `YOU_COLOR`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerColorScheme {
+    pub you: PlayerId,
+    pub total_players: usize,
+}
+
+impl PlayerColorScheme {
+    pub fn new(state: &GameStatePublic, you: PlayerId) -> Self {
+        Self {
+            you,
+            total_players: state.players.len(),
+        }
+    }
+
+    pub fn color(&self, player_id: PlayerId) -> Color32 {
+        if player_id == self.you {
+            YOU_COLOR
+        } else {
+            player_color(player_id, self.total_players)
+        }
+    }
+
+    /// Color of `p`, dimmed once they have folded.
+    pub fn color_of(&self, p: &PlayerPublic) -> Color32 {
+        match (p.has_folded, p.id == self.you) {
+            (false, _) => self.color(p.id),
+            (true, true) => dim(YOU_COLOR),
+            (true, false) => player_color_dim(p.id, self.total_players),
+        }
+    }
+}
+
 pub fn name_of(players: &[PlayerPublic], id: PlayerId) -> String {
     PlayerPublic::name_of(players, id)
 }
@@ -63,6 +275,117 @@ pub fn stage_badge(stage: Stage) -> WidgetText {
     RichText::new(txt).color(color).strong().into()
 }
 
+/// The "D" chip marking the dealer seat
+pub fn dealer_button(ui: &mut Ui) -> egui::Response {
+    let chip = RichText::new(" D ")
+        .monospace()
+        .strong()
+        .color(Color32::BLACK)
+        .background_color(Color32::WHITE);
+    ui.label(chip).on_hover_text("Dealer button")
+}
+
+/// Chip values and their colors, largest first
+pub const CHIP_DENOMINATIONS: [(u32, Color32); 5] = [
+    (100, Color32::from_rgb(30, 30, 30)),
+    (25, Color32::from_rgb(40, 150, 70)),
+    (10, Color32::from_rgb(50, 90, 200)),
+    (5, Color32::from_rgb(200, 40, 40)),
+    (1, Color32::from_rgb(235, 235, 235)),
+];
+
+/// Chips drawn per denomination; larger counts are labelled "×N"
+pub const MAX_VISIBLE_CHIPS: u32 = 10;
+
+const CHIP_RADIUS: f32 = 8.0;
+/// Vertical distance between stacked chips
+const CHIP_OFFSET: f32 = 3.0;
+const CHIP_COLUMN_GAP: f32 = 3.0;
+const CHIP_LABEL_HEIGHT: f32 = 11.0;
+/// Rim that tells stacked chips apart, light and dark ones alike
+const CHIP_RIM: Color32 = Color32::from_gray(90);
+
+/// Fewest chips making up `amount`, as (denomination, count), largest first
+pub fn chip_breakdown(amount: u32) -> Vec<(u32, u32)> {
+    let mut rest = amount;
+    CHIP_DENOMINATIONS
+        .iter()
+        .filter_map(|&(value, _)| {
+            let count = rest / value;
+            rest %= value;
+            (count > 0).then_some((value, count))
+        })
+        .collect()
+}
+
+/// Space `draw_chip_stack` covers for `amount`
+pub fn chip_stack_size(amount: u32) -> egui::Vec2 {
+    let chips = chip_breakdown(amount);
+    let columns = chips.len() as f32;
+    let tallest = chips.iter().map(|&(_, n)| n).max().unwrap_or(0);
+    let label = if tallest > MAX_VISIBLE_CHIPS {
+        CHIP_LABEL_HEIGHT
+    } else {
+        0.0
+    };
+    let visible = tallest.clamp(1, MAX_VISIBLE_CHIPS) as f32;
+    egui::vec2(
+        (columns * (2.0 * CHIP_RADIUS + CHIP_COLUMN_GAP) - CHIP_COLUMN_GAP).max(0.0),
+        2.0 * CHIP_RADIUS + (visible - 1.0) * CHIP_OFFSET + label,
+    )
+}
+
+/// Draw `amount` as one stack of chips per denomination, left to right from
+/// the largest. `pos` is the bottom left corner; the value is printed on the
+/// top chip of each stack.
+pub fn draw_chip_stack(painter: &Painter, pos: Pos2, amount: u32) {
+    let font = egui::FontId::proportional(CHIP_RADIUS);
+    for (column, (value, count)) in chip_breakdown(amount).into_iter().enumerate() {
+        let color = CHIP_DENOMINATIONS
+            .iter()
+            .find(|&&(v, _)| v == value)
+            .map_or(Color32::GRAY, |&(_, c)| c);
+        let text_color = if value == 1 {
+            Color32::BLACK
+        } else {
+            Color32::WHITE
+        };
+        let x = pos.x + CHIP_RADIUS + column as f32 * (2.0 * CHIP_RADIUS + CHIP_COLUMN_GAP);
+        let visible = count.min(MAX_VISIBLE_CHIPS);
+        let mut center = Pos2::new(x, pos.y - CHIP_RADIUS);
+        for i in 0..visible {
+            center.y = pos.y - CHIP_RADIUS - i as f32 * CHIP_OFFSET;
+            painter.circle_filled(center, CHIP_RADIUS, color);
+            painter.circle_stroke(center, CHIP_RADIUS, (1.0, CHIP_RIM));
+        }
+        painter.text(
+            center,
+            egui::Align2::CENTER_CENTER,
+            value.to_string(),
+            font.clone(),
+            text_color,
+        );
+        if count > MAX_VISIBLE_CHIPS {
+            painter.text(
+                Pos2::new(x, center.y - CHIP_RADIUS),
+                egui::Align2::CENTER_BOTTOM,
+                format!("×{}", count),
+                font.clone(),
+                painter.ctx().style().visuals.text_color(),
+            );
+        }
+    }
+}
+
+/// Chip stack for `amount` as a widget; hovering it shows the amount
+pub fn chip_stack(ui: &mut Ui, amount: u32, sense: egui::Sense) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(chip_stack_size(amount), sense);
+    if ui.is_rect_visible(rect) {
+        draw_chip_stack(ui.painter(), rect.left_bottom(), amount);
+    }
+    response
+}
+
 pub fn stage_to_str(stage: Stage) -> &'static str {
     match stage {
         Stage::Preflop => "Preflop",
@@ -73,6 +396,10 @@ pub fn stage_to_str(stage: Stage) -> &'static str {
     }
 }
 
+pub fn category_text(category: HandRankCategory) -> String {
+    category.to_string()
+}
+
 pub fn format_game_for_clipboard(state: &GameStatePublic, you: PlayerId) -> String {
     let mut out = String::new();
 
@@ -193,6 +520,9 @@ fn format_player_action_entry(
         ActionKind::Raise { to, by } => {
             out.push_str(&format!("- {} raises to {} (+{})\n", who_name, to, by))
         }
+        ActionKind::AllIn { amount } => {
+            out.push_str(&format!("- {} goes all-in for {}!\n", who_name, amount))
+        }
         ActionKind::PostBlind { kind, amount } => {
             format_blind_entry(out, &who_name, kind, amount);
         }
@@ -228,6 +558,18 @@ fn format_game_action_entry(out: &mut String, game_action: &GameAction, state: &
         GameAction::PotAwarded { winners, amount } => {
             format_pot_awarded_entry(out, winners, amount, state);
         }
+        GameAction::PlayerEliminated {
+            player_id,
+            bounty_won_by,
+            bounty,
+        } => {
+            let text = elimination_text(*player_id, *bounty_won_by, *bounty, &state.players);
+            out.push_str(&format!("- {}\n", text));
+        }
+        GameAction::PlayerKicked { player_id } => {
+            let who = name_of(&state.players, *player_id);
+            out.push_str(&format!("- {} was kicked, a bot takes over\n", who));
+        }
     }
 }
 
@@ -258,7 +600,7 @@ fn format_showdown_entry(out: &mut String, hand_results: &[HandResult], state: &
     } else {
         for hr in hand_results {
             let who = name_of(&state.players, hr.player_id);
-            let cat = hr.rank.category.to_str();
+            let cat = category_text(hr.rank.category);
             let best = hr
                 .best_five
                 .iter()
@@ -284,137 +626,66 @@ fn format_pot_awarded_entry(
     out.push_str(&format!("- Pot {} awarded to {}\n", amount, names));
 }
 
-pub fn log_entry_row(ui: &mut Ui, entry: &ActionEvent, players: &[PlayerPublic], you_id: PlayerId) {
-    match entry {
-        ActionEvent::PlayerAction { player_id, action } => {
-            render_player_action_entry(ui, *player_id, action, players, you_id);
-        }
-        ActionEvent::GameAction(GameAction::StageChanged(s)) => {
-            render_stage_change_entry(ui, *s);
-        }
-        ActionEvent::GameAction(GameAction::DealtHole { player_id }) => {
-            render_dealt_hole_entry(ui, *player_id, players);
-        }
-        ActionEvent::GameAction(GameAction::DealtCommunity { cards }) => {
-            render_community_cards_entry(ui, cards);
-        }
-        ActionEvent::GameAction(GameAction::Showdown { hand_results }) => {
-            render_showdown_entry(ui, hand_results, players);
-        }
-        ActionEvent::GameAction(GameAction::PotAwarded { winners, amount }) => {
-            render_pot_awarded_entry(ui, winners, *amount, players);
-        }
-    }
-}
-
-fn render_player_action_entry(
-    ui: &mut Ui,
+fn elimination_text(
     player_id: PlayerId,
-    action: &ActionKind,
+    bounty_won_by: Option<PlayerId>,
+    bounty: u32,
     players: &[PlayerPublic],
-    you_id: PlayerId,
-) {
-    let who_name = name_of(players, player_id);
-    let (txt, color) = action_kind_text(action);
-    let is_you = player_id == you_id;
-    let label = if is_you {
-        RichText::new(format!("{} {}", who_name, txt))
-            .color(color)
-            .strong()
-    } else {
-        RichText::new(format!("{} {}", who_name, txt)).color(color)
-    };
-    ui.label(label);
-}
-
-fn render_stage_change_entry(ui: &mut Ui, stage: Stage) {
-    ui.add_space(6.0);
-    ui.separator();
-    ui.horizontal(|ui| {
-        ui.label(RichText::new("🕒").strong());
-        ui.label(stage_badge(stage));
-    });
-    ui.separator();
-    ui.add_space(6.0);
-}
-
-fn render_dealt_hole_entry(ui: &mut Ui, player_id: PlayerId, players: &[PlayerPublic]) {
+) -> String {
     let who = name_of(players, player_id);
-    ui.colored_label(
-        Color32::from_rgb(150, 150, 150),
-        format!("🃏 Dealt hole cards to {}", who),
-    );
+    match bounty_won_by {
+        Some(by) if bounty > 0 => format!(
+            "{} eliminated by {} (+{} bounty)",
+            who,
+            name_of(players, by),
+            bounty
+        ),
+        Some(by) => format!("{} eliminated by {}", who, name_of(players, by)),
+        None => format!("{} eliminated", who),
+    }
 }
 
-fn render_community_cards_entry(ui: &mut Ui, cards: &[Card]) {
-    match cards.len() {
-        3 => {
-            ui.colored_label(
-                Color32::from_rgb(100, 200, 120),
-                format!(
-                    "🃏 Flop: {} {} {}",
-                    card_text(cards[0]),
-                    card_text(cards[1]),
-                    card_text(cards[2])
-                ),
-            );
-        }
-        4 => {
-            ui.colored_label(
-                Color32::from_rgb(230, 180, 80),
-                format!("🃏 Turn: {}", card_text(cards[3])),
-            );
-        }
-        5 => {
-            ui.colored_label(
-                Color32::from_rgb(220, 120, 120),
-                format!("🃏 River: {}", card_text(cards[4])),
-            );
-        }
-        _ => {
-            ui.colored_label(
-                Color32::from_rgb(120, 120, 120),
-                format!(
-                    "🃏 Community: {}",
-                    cards
-                        .iter()
-                        .map(|&c| card_text(c))
-                        .collect::<Vec<_>>()
-                        .join(" ")
-                ),
-            );
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seat_colors_are_distinct_and_not_your_color() {
+        let colors: Vec<_> = (0..8).map(|i| player_color(PlayerId(i), 8)).collect();
+        for (i, a) in colors.iter().enumerate() {
+            assert_ne!(*a, YOU_COLOR);
+            let hue = Hsva::from(*a).h * 360.0;
+            assert!(!YOU_HUES.contains(&hue), "seat {} has hue {}", i, hue);
+            assert!(colors[i + 1..].iter().all(|b| b != a), "seat {} repeats", i);
         }
     }
-}
 
-fn render_showdown_entry(ui: &mut Ui, hand_results: &[HandResult], players: &[PlayerPublic]) {
-    let mut parts = Vec::new();
-    for hr in hand_results {
-        let who = name_of(players, hr.player_id);
-        let cat = hr.rank.category.to_str();
-        parts.push(format!("{}: {}", who, cat));
+    #[test]
+    fn chips_are_counted_largest_denomination_first() {
+        assert!(chip_breakdown(0).is_empty());
+        assert_eq!(chip_breakdown(137), [(100, 1), (25, 1), (10, 1), (1, 2)]);
+        assert_eq!(chip_breakdown(1_240), [(100, 12), (25, 1), (10, 1), (5, 1)]);
+        // The "×12" label adds to the height of a full stack
+        assert!(chip_stack_size(1_240).y > chip_stack_size(940).y);
     }
-    let text = if parts.is_empty() {
-        "🏁 Showdown".to_string()
-    } else {
-        format!("🏁 Showdown — {}", parts.join(", "))
-    };
-    ui.colored_label(Color32::from_rgb(180, 100, 220), text);
-}
 
-fn render_pot_awarded_entry(
-    ui: &mut Ui,
-    winners: &[PlayerId],
-    amount: u32,
-    players: &[PlayerPublic],
-) {
-    let names = winners
-        .iter()
-        .map(|&id| name_of(players, id))
-        .collect::<Vec<_>>()
-        .join(", ");
-    ui.colored_label(
-        Color32::from_rgb(240, 200, 80),
-        format!("🏆 Pot {} awarded to {}", amount, names),
-    );
+    #[test]
+    fn folded_players_are_dimmed() {
+        let bright = Hsva::from(player_color(PlayerId(2), 4));
+        let dimmed = Hsva::from(player_color_dim(PlayerId(2), 4));
+        assert!((dimmed.v - bright.v * 0.5).abs() < 0.01);
+        assert!((dimmed.h - bright.h).abs() < 0.01);
+    }
+
+    #[test]
+    fn you_keep_your_color_on_any_seat() {
+        for seat in 0..6 {
+            let scheme = PlayerColorScheme {
+                you: PlayerId(seat),
+                total_players: 6,
+            };
+            assert_eq!(scheme.color(PlayerId(seat)), YOU_COLOR);
+            assert_ne!(scheme.color(PlayerId((seat + 1) % 6)), YOU_COLOR);
+        }
+    }
 }
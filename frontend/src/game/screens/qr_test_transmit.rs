@@ -27,7 +27,7 @@ pub struct QrTestTransmit {
 
 impl QrTestTransmit {
     fn gen_new_code(&mut self) {
-        if let Ok(epoch) = self.epoch.try_borrow_mut() {
+        if let Ok(mut epoch) = self.epoch.try_borrow_mut() {
             if let Some(frame) = epoch.pop_recent_frame() {
                 let qr_res: Result<QrCode, _> = frame.try_into();
                 if let Ok(qr) = qr_res {
@@ -174,6 +174,19 @@ impl ScreenDef for QrTestTransmit {
             Backend2FrontendMsg::Pong => {
                 sprintln!("Got a pong");
             }
+            Backend2FrontendMsg::Welcome { .. }
+            | Backend2FrontendMsg::Ack { .. }
+            | Backend2FrontendMsg::StateDiff(_)
+            | Backend2FrontendMsg::RebuyAvailable { .. }
+            | Backend2FrontendMsg::PlayerKicked(_)
+            | Backend2FrontendMsg::PlayerEliminated { .. }
+            | Backend2FrontendMsg::Heartbeat { .. }
+            | Backend2FrontendMsg::PairingCode { .. }
+            | Backend2FrontendMsg::HandReview(_)
+            | Backend2FrontendMsg::ArenaHandComplete { .. }
+            | Backend2FrontendMsg::TournamentState(_)
+            | Backend2FrontendMsg::TableJoined(_)
+            | Backend2FrontendMsg::Tables(_) => {}
         };
         let on_err = |e| {
             sprintln!("Got an error:\n\t- {:?}", e);
@@ -186,10 +199,20 @@ impl ScreenDef for QrTestTransmit {
             id: PlayerId::from(1337),
             name: "QR_COMM".to_string(),
             is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
         };
         players.push(p);
-        me.web_socket_connection
-            .connect("127.0.0.1:3000", players, on_msg, on_err, on_cls);
+        me.web_socket_connection.connect(
+            "127.0.0.1:3000",
+            Frontend2BackendMsg::NewGame {
+                player_id: PlayerId::from(1337),
+                players,
+            },
+            on_msg,
+            on_err,
+            on_cls,
+        );
         Box::new(me)
     }
 }
@@ -0,0 +1,378 @@
+//! Bracket of a multi-table tournament: one column of table boxes per round,
+//! joined by lines where players moved on to a table of the next round.
+
+use std::collections::HashMap;
+
+use eframe::Frame;
+use egui::{pos2, vec2, Color32, FontId, Id, Pos2, Rect, RichText, Sense, Stroke, Ui};
+use mcg_shared::{Frontend2BackendMsg, TableId, TournamentState};
+
+use super::{AppInterface, ScreenDef, ScreenMetadata, ScreenWidget};
+use crate::game::AppEvent;
+
+const BOX_WIDTH: f32 = 170.0;
+const HEADER_HEIGHT: f32 = 22.0;
+const ROW_HEIGHT: f32 = 18.0;
+const BOX_GAP: f32 = 14.0;
+const COLUMN_GAP: f32 = 56.0;
+/// Seconds a new round takes to slide out of the previous one
+const COLLAPSE_SECS: f64 = 0.6;
+
+/// Position of one table box, relative to the top left of the bracket
+#[derive(Clone, Debug, PartialEq)]
+struct PlacedTable {
+    round_idx: usize,
+    table_idx: usize,
+    rect: Rect,
+}
+
+/// Place the tables of every round in columns, each column centered
+/// vertically. Returns the boxes and the size of the whole bracket.
+fn layout(tournament: &TournamentState) -> (Vec<PlacedTable>, egui::Vec2) {
+    let column_height = |tables: &[mcg_shared::BracketTable]| -> f32 {
+        let boxes: f32 = tables
+            .iter()
+            .map(|t| HEADER_HEIGHT + ROW_HEIGHT * t.players.len() as f32)
+            .sum();
+        boxes + BOX_GAP * tables.len().saturating_sub(1) as f32
+    };
+    let height = tournament
+        .rounds
+        .iter()
+        .map(|r| column_height(&r.tables))
+        .fold(0.0, f32::max);
+
+    let mut placed = Vec::new();
+    for (round_idx, round) in tournament.rounds.iter().enumerate() {
+        let x = round_idx as f32 * (BOX_WIDTH + COLUMN_GAP);
+        let mut y = (height - column_height(&round.tables)) / 2.0;
+        for (table_idx, table) in round.tables.iter().enumerate() {
+            let h = HEADER_HEIGHT + ROW_HEIGHT * table.players.len() as f32;
+            placed.push(PlacedTable {
+                round_idx,
+                table_idx,
+                rect: Rect::from_min_size(pos2(x, y), vec2(BOX_WIDTH, h)),
+            });
+            y += h + BOX_GAP;
+        }
+    }
+    let width = (tournament.rounds.len() as f32 * (BOX_WIDTH + COLUMN_GAP) - COLUMN_GAP).max(0.0);
+    (placed, vec2(width, height))
+}
+
+/// Pairs of (table index in round `r`, table index in round `r + 1`) that
+/// share a player still in the tournament after round `r`.
+fn links(tournament: &TournamentState, r: usize) -> Vec<(usize, usize)> {
+    let (Some(from), Some(to)) = (tournament.rounds.get(r), tournament.rounds.get(r + 1)) else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    for (a, table_a) in from.tables.iter().enumerate() {
+        for (b, table_b) in to.tables.iter().enumerate() {
+            let moved = table_a.players.iter().any(|p| {
+                p.eliminated_in_round.is_none_or(|out| out > from.round)
+                    && table_b.players.iter().any(|q| q.player_id == p.player_id)
+            });
+            if moved {
+                out.push((a, b));
+            }
+        }
+    }
+    out
+}
+
+/// Draws the tournament reported on the shared connection, which keeps the
+/// bracket up to date even while the poker screen is not open.
+#[derive(Default)]
+pub struct TournamentBracketScreen {
+    /// Server the shared connection was requested for
+    connected_to: Option<String>,
+    /// Whether the bracket was requested since the connection opened; later
+    /// changes are broadcast by the server
+    requested: bool,
+    tournament: Option<TournamentState>,
+    /// Time each round first showed up, for the collapse animation
+    round_seen: HashMap<u32, f64>,
+}
+
+impl TournamentBracketScreen {
+    fn apply_update(&mut self, latest: Option<&TournamentState>, now: f64) {
+        let Some(tournament) = latest else {
+            return;
+        };
+        if self.tournament.as_ref() == Some(tournament) {
+            return;
+        }
+        // Rounds already there when the bracket opens are not animated
+        let seen_at = if self.tournament.is_some() {
+            now
+        } else {
+            f64::NEG_INFINITY
+        };
+        for round in &tournament.rounds {
+            self.round_seen.entry(round.round).or_insert(seen_at);
+        }
+        self.tournament = Some(tournament.clone());
+    }
+
+    /// Draw the bracket; returns the table the user clicked on.
+    fn draw_bracket(&self, ui: &mut Ui, tournament: &TournamentState) -> Option<TableId> {
+        let (placed, size) = layout(tournament);
+        let (response, painter) = ui.allocate_painter(size, Sense::hover());
+        let origin = response.rect.min.to_vec2();
+        let now = ui.input(|i| i.time);
+        let last_round = tournament.rounds.len().saturating_sub(1);
+        let visuals = ui.visuals().clone();
+
+        // Fraction of the collapse animation done for each round
+        let progress: Vec<f32> = tournament
+            .rounds
+            .iter()
+            .map(|r| {
+                let seen = self
+                    .round_seen
+                    .get(&r.round)
+                    .copied()
+                    .unwrap_or(f64::NEG_INFINITY);
+                ((now - seen) / COLLAPSE_SECS).clamp(0.0, 1.0) as f32
+            })
+            .collect();
+        if progress.iter().any(|&p| p < 1.0) {
+            ui.ctx().request_repaint();
+        }
+        // New rounds slide out from the column before them
+        let rect_of = |p: &PlacedTable| -> Rect {
+            let t = progress[p.round_idx];
+            let dx = (1.0 - t) * (BOX_WIDTH + COLUMN_GAP);
+            p.rect.translate(origin - vec2(dx, 0.0))
+        };
+        let find = |round_idx: usize, table_idx: usize| {
+            placed
+                .iter()
+                .find(|p| p.round_idx == round_idx && p.table_idx == table_idx)
+        };
+
+        let line_color = visuals.weak_text_color();
+        for r in 0..last_round {
+            let alpha = progress[r + 1];
+            let stroke = Stroke::new(1.5, line_color.gamma_multiply(alpha));
+            for (a, b) in links(tournament, r) {
+                let (Some(pa), Some(pb)) = (find(r, a), find(r + 1, b)) else {
+                    continue;
+                };
+                let from = rect_of(pa).right_center();
+                let to = rect_of(pb).left_center();
+                let mid = (from.x + to.x) / 2.0;
+                painter.add(egui::Shape::line(
+                    vec![from, pos2(mid, from.y), pos2(mid, to.y), to],
+                    stroke,
+                ));
+            }
+        }
+
+        let mut picked = None;
+        for p in &placed {
+            let round = &tournament.rounds[p.round_idx];
+            let table = &round.tables[p.table_idx];
+            let rect = rect_of(p);
+            let alpha = progress[p.round_idx];
+            let live = p.round_idx == last_round;
+            let resp = ui.interact(
+                rect,
+                Id::new(("bracket_table", round.round, table.table_id)),
+                if live { Sense::click() } else { Sense::hover() },
+            );
+            let stroke_color = if live && resp.hovered() {
+                visuals.selection.stroke.color
+            } else {
+                visuals.widgets.noninteractive.bg_stroke.color
+            };
+            painter.rect(
+                rect,
+                6.0,
+                visuals.extreme_bg_color.gamma_multiply(alpha),
+                Stroke::new(1.0, stroke_color.gamma_multiply(alpha)),
+                egui::StrokeKind::Inside,
+            );
+            painter.text(
+                rect.min + vec2(8.0, HEADER_HEIGHT / 2.0),
+                egui::Align2::LEFT_CENTER,
+                format!("Table #{}", table.table_id),
+                FontId::proportional(14.0),
+                visuals.strong_text_color().gamma_multiply(alpha),
+            );
+            for (i, player) in table.players.iter().enumerate() {
+                let y = rect.min.y + HEADER_HEIGHT + ROW_HEIGHT * (i as f32 + 0.5);
+                let (color, stack) = match player.eliminated_in_round {
+                    Some(out) => (Color32::GRAY, format!("out R{}", out)),
+                    None => (visuals.text_color(), player.stack.to_string()),
+                };
+                let font = FontId::proportional(12.0);
+                painter.text(
+                    Pos2::new(rect.min.x + 8.0, y),
+                    egui::Align2::LEFT_CENTER,
+                    &player.name,
+                    font.clone(),
+                    color.gamma_multiply(alpha),
+                );
+                painter.text(
+                    Pos2::new(rect.max.x - 8.0, y),
+                    egui::Align2::RIGHT_CENTER,
+                    stack,
+                    font,
+                    color.gamma_multiply(alpha),
+                );
+            }
+            if live
+                && resp
+                    .on_hover_text(format!("Go to table #{}", table.table_id))
+                    .clicked()
+            {
+                picked = Some(table.table_id);
+            }
+        }
+        picked
+    }
+}
+
+impl ScreenWidget for TournamentBracketScreen {
+    fn ui(&mut self, app_interface: &mut AppInterface, ui: &mut Ui, _frame: &mut Frame) {
+        let state = app_interface.state();
+        let server_address = state.settings.server_address.clone();
+        if self.connected_to.as_deref() != Some(server_address.as_str()) {
+            state.get_or_create_connection(&server_address, ui.ctx());
+            self.connected_to = Some(server_address);
+        }
+        if !state.connection_pool.is_connected() {
+            self.requested = false;
+        } else if !self.requested {
+            state.send_msg(&Frontend2BackendMsg::RequestTournament);
+            self.requested = true;
+        }
+        self.apply_update(state.session.tournament.as_ref(), ui.input(|i| i.time));
+
+        ui.heading("Tournament Bracket");
+        let pool = &state.connection_pool;
+        if !pool.is_connected() {
+            let status = format!(
+                "Not connected to {} ({})",
+                pool.server_address().unwrap_or("-"),
+                pool.ready_state_name()
+            );
+            ui.label(RichText::new(status).color(Color32::RED));
+            if ui.button("Reconnect").clicked() {
+                self.connected_to = None;
+            }
+        }
+        let Some(tournament) = &self.tournament else {
+            ui.label("Waiting for the server to report a tournament...");
+            return;
+        };
+        ui.label(format!(
+            "Round {}: {} players left. Click a table to watch it.",
+            tournament.rounds.last().map_or(0, |r| r.round),
+            tournament.remaining_players()
+        ));
+        ui.separator();
+
+        let mut picked = None;
+        egui::ScrollArea::both().show(ui, |ui| {
+            picked = self.draw_bracket(ui, tournament);
+        });
+        if let Some(table_id) = picked {
+            app_interface.state().session.pending_table = Some(table_id);
+            app_interface.queue_event(AppEvent::ChangeRoute("/poker-online".to_string()));
+        }
+    }
+}
+
+crate::impl_screen_def!(
+    TournamentBracketScreen,
+    "/bracket",
+    "Tournament Bracket",
+    "🏆",
+    "Follow the tables of a multi-table tournament",
+    true
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::{BracketPlayer, BracketTable, PlayerId, TournamentRound};
+
+    fn player(id: usize, eliminated_in_round: Option<u32>) -> BracketPlayer {
+        BracketPlayer {
+            player_id: PlayerId(id),
+            name: format!("P{}", id),
+            stack: 1000,
+            eliminated_in_round,
+        }
+    }
+
+    fn table(table_id: TableId, players: Vec<BracketPlayer>) -> BracketTable {
+        BracketTable { table_id, players }
+    }
+
+    /// Two tables of three consolidate into one final table.
+    fn tournament() -> TournamentState {
+        TournamentState {
+            rounds: vec![
+                TournamentRound {
+                    round: 1,
+                    tables: vec![
+                        table(
+                            1,
+                            vec![player(0, None), player(1, Some(1)), player(2, None)],
+                        ),
+                        table(
+                            2,
+                            vec![player(3, None), player(4, None), player(5, Some(1))],
+                        ),
+                    ],
+                },
+                TournamentRound {
+                    round: 2,
+                    tables: vec![table(
+                        3,
+                        vec![
+                            player(0, None),
+                            player(2, None),
+                            player(3, None),
+                            player(4, Some(2)),
+                        ],
+                    )],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn columns_are_centered() {
+        let (placed, size) = layout(&tournament());
+        assert_eq!(placed.len(), 3);
+        let first_column = 2.0 * (HEADER_HEIGHT + 3.0 * ROW_HEIGHT) + BOX_GAP;
+        assert_eq!(size, vec2(2.0 * BOX_WIDTH + COLUMN_GAP, first_column));
+        let final_table = &placed[2];
+        assert_eq!(final_table.rect.min.x, BOX_WIDTH + COLUMN_GAP);
+        assert_eq!(final_table.rect.center().y, size.y / 2.0);
+    }
+
+    #[test]
+    fn tables_link_to_where_their_players_moved() {
+        let t = tournament();
+        assert_eq!(links(&t, 0), vec![(0, 0), (1, 0)]);
+        assert!(links(&t, 1).is_empty());
+        assert_eq!(t.remaining_players(), 3);
+    }
+
+    #[test]
+    fn busted_players_do_not_link_tables() {
+        let mut t = tournament();
+        // Player 4 still moves on from table 2
+        t.rounds[0].tables[1].players[0].eliminated_in_round = Some(1);
+        assert_eq!(links(&t, 0), vec![(0, 0), (1, 0)]);
+        // Nobody left table 2 for the final table
+        t.rounds[0].tables[1].players[1].eliminated_in_round = Some(1);
+        assert_eq!(links(&t, 0), vec![(0, 0)]);
+    }
+}
@@ -1,9 +1,23 @@
-use mcg_shared::{Frontend2BackendMsg, PlayerConfig, Backend2FrontendMsg};
+use mcg_shared::{Frontend2BackendMsg, Backend2FrontendMsg, Sequenced};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
 use std::rc::Rc;
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsCast;
 use web_sys::{CloseEvent, Event, MessageEvent, WebSocket};
 
+/// Scheme of server addresses that name an iroh node, like `iroh://NODE_ID`
+pub const IROH_SCHEME: &str = "iroh://";
+
+/// The node id of an `iroh://NODE_ID` server address.
+pub fn iroh_node_id(server_address: &str) -> Option<&str> {
+    server_address
+        .trim()
+        .strip_prefix(IROH_SCHEME)
+        .filter(|id| !id.is_empty())
+}
+
 /// Trait for sending messages to the server.
 /// Allows decoupling UI components from the concrete WebSocket implementation.
 pub trait MessageSender {
@@ -44,67 +58,97 @@ impl WebSocketConnection {
     ///
     /// Establishes a connection and sets up event handlers that immediately
     /// process incoming messages and trigger UI updates via callbacks.
+    /// `initial_msg` is sent right after subscribing.
     pub fn connect(
         &mut self,
         server_address: &str,
-        players: Vec<PlayerConfig>,
+        initial_msg: Frontend2BackendMsg,
         on_message: impl Fn(Backend2FrontendMsg) + 'static,
         on_error: impl Fn(String) + 'static,
         on_close: impl Fn(String) + 'static,
+    ) {
+        // Prepare the Subscribe and initial messages
+        let subscribe_payload = match serde_json::to_string(&Frontend2BackendMsg::Subscribe) {
+            Ok(s) => s,
+            Err(e) => {
+                on_error(format!("Failed to serialize Subscribe message: {:?}", e));
+                return;
+            }
+        };
+        let initial_payload = match serde_json::to_string(&initial_msg) {
+            Ok(s) => s,
+            Err(e) => {
+                on_error(format!("Failed to serialize initial message: {:?}", e));
+                return;
+            }
+        };
+
+        let on_error = Rc::new(on_error);
+        let on_error_clone = on_error.clone();
+        self.open(
+            server_address,
+            move |ws| {
+                if let Err(e) = ws.send_with_str(&subscribe_payload) {
+                    on_error_clone(format!("Error sending Subscribe: {:?}", e));
+                    return;
+                }
+                if let Err(e) = ws.send_with_str(&initial_payload) {
+                    on_error_clone(format!("Error sending initial message: {:?}", e));
+                }
+            },
+            move |_ws, txt| {
+                if let Ok(msg) = serde_json::from_str::<Backend2FrontendMsg>(&txt) {
+                    // Process the message immediately via callback
+                    on_message(msg);
+                }
+            },
+            move |error| on_error(error),
+            on_close,
+        );
+    }
+
+    /// Open a socket to the server and install the event handlers.
+    ///
+    /// `on_open` and `on_text` get the socket, so they can send on it.
+    pub fn open(
+        &mut self,
+        server_address: &str,
+        on_open: impl Fn(&WebSocket) + 'static,
+        on_text: impl Fn(&WebSocket, String) + 'static,
+        on_error: impl Fn(String) + 'static,
+        on_close: impl Fn(String) + 'static,
     ) {
         // Close any existing connection before starting a new one
         self.close();
 
+        // Browsers can only open WebSockets; iroh nodes are reached with the
+        // native client's `IrohTransport`
+        if let Some(node_id) = iroh_node_id(server_address) {
+            on_error(format!(
+                "Browsers cannot connect to iroh nodes. Use `mcg-cli --transport {}{}`, or the node's web address.",
+                IROH_SCHEME, node_id
+            ));
+            return;
+        }
+
         // Wrap callbacks in Rc to share with closures
-        let on_message = Rc::new(on_message);
         let on_error = Rc::new(on_error);
         let on_close = Rc::new(on_close);
 
         let ws_url = format!("ws://{}/ws", server_address);
         match WebSocket::new(&ws_url) {
             Ok(ws) => {
-                // Prepare the Subscribe and initial NewGame messages
-                let subscribe_json = match serde_json::to_string(&Frontend2BackendMsg::Subscribe) {
-                    Ok(s) => s,
-                    Err(e) => {
-                        on_error(format!("Failed to serialize Subscribe message: {:?}", e));
-                        return;
-                    }
-                };
-                let newgame_msg = Frontend2BackendMsg::NewGame {
-                    players: players.clone(),
-                };
-                let newgame_json = match serde_json::to_string(&newgame_msg) {
-                    Ok(s) => s,
-                    Err(e) => {
-                        on_error(format!("Failed to serialize NewGame message: {:?}", e));
-                        return;
-                    }
-                };
-
                 let ws_clone_for_open = ws.clone();
-                let subscribe_payload = subscribe_json;
-                let newgame_payload = newgame_json;
-                let on_error_clone = on_error.clone();
                 let onopen = Closure::<dyn FnMut(Event)>::new(move |_e: Event| {
-                    if let Err(e) = ws_clone_for_open.send_with_str(&subscribe_payload) {
-                        on_error_clone(format!("Error sending Subscribe: {:?}", e));
-                        return;
-                    }
-                    if let Err(e) = ws_clone_for_open.send_with_str(&newgame_payload) {
-                        on_error_clone(format!("Error sending NewGame: {:?}", e));
-                    }
+                    on_open(&ws_clone_for_open);
                 });
                 ws.set_onopen(Some(onopen.as_ref().unchecked_ref()));
 
-                // onmessage: Parse ServerMsg and process immediately
-                let on_message_clone = on_message.clone();
+                // onmessage: Hand text frames over immediately
+                let ws_clone_for_message = ws.clone();
                 let onmessage = Closure::<dyn FnMut(MessageEvent)>::new(move |e: MessageEvent| {
                     if let Some(txt) = e.data().as_string() {
-                        if let Ok(msg) = serde_json::from_str::<Backend2FrontendMsg>(&txt) {
-                            // Process the message immediately via callback
-                            on_message_clone(msg);
-                        }
+                        on_text(&ws_clone_for_message, txt);
                     }
                 });
                 ws.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
@@ -143,17 +187,22 @@ impl WebSocketConnection {
         }
     }
 
-    /// Send a `ClientMsg` to the server if connected.
-    pub fn send_msg(&self, msg: &Frontend2BackendMsg) {
+    /// Send already serialized text if connected.
+    pub fn send_text(&self, txt: &str) {
         if let Some(ws) = &self.ws {
-            if let Ok(txt) = serde_json::to_string(msg) {
-                if let Err(e) = ws.send_with_str(&txt) {
-                    web_sys::console::log_1(&format!("Failed to send message: {:?}", e).into());
-                }
+            if let Err(e) = ws.send_with_str(txt) {
+                web_sys::console::log_1(&format!("Failed to send message: {:?}", e).into());
             }
         }
     }
 
+    /// Send a `ClientMsg` to the server if connected.
+    pub fn send_msg(&self, msg: &Frontend2BackendMsg) {
+        if let Ok(txt) = serde_json::to_string(msg) {
+            self.send_text(&txt);
+        }
+    }
+
     /// Check if the WebSocket connection is open.
     pub fn is_connected(&self) -> bool {
         if let Some(ws) = &self.ws {
@@ -163,6 +212,24 @@ impl WebSocketConnection {
         }
     }
 
+    /// Whether the socket is open or still opening, so it needs no reconnect.
+    pub fn is_open_or_connecting(&self) -> bool {
+        self.ws
+            .as_ref()
+            .is_some_and(|ws| matches!(ws.ready_state(), WebSocket::CONNECTING | WebSocket::OPEN))
+    }
+
+    /// Human-readable socket ready state, `"NONE"` before the first connect.
+    pub fn ready_state_name(&self) -> &'static str {
+        match self.ws.as_ref().map(|ws| ws.ready_state()) {
+            None => "NONE",
+            Some(WebSocket::CONNECTING) => "CONNECTING",
+            Some(WebSocket::OPEN) => "OPEN",
+            Some(WebSocket::CLOSING) => "CLOSING",
+            Some(_) => "CLOSED",
+        }
+    }
+
     /// Close the WebSocket connection.
     pub fn close(&mut self) {
         if let Some(ws) = self.ws.take() {
@@ -197,3 +264,417 @@ impl MessageSender for WebSocketConnection {
         self.send_msg(msg);
     }
 }
+
+/// Numbered messages held back while waiting for a missing one; beyond this
+/// the missing message is given up on.
+const MAX_OUT_OF_ORDER: usize = 64;
+
+/// Sent messages kept for re-sending; a server that never acknowledges would
+/// otherwise make them pile up.
+const MAX_UNACKED: usize = 256;
+
+/// Puts numbered server messages back in the order they were sent.
+#[derive(Debug, Default)]
+pub struct InboundOrder {
+    next: u64,
+    pending: BTreeMap<u64, Backend2FrontendMsg>,
+}
+
+impl InboundOrder {
+    /// Take the message numbered `seq` and return all messages that are now
+    /// in order, oldest first. Unnumbered messages pass straight through and
+    /// repeated ones are dropped.
+    pub fn accept(
+        &mut self,
+        seq: Option<u64>,
+        msg: Backend2FrontendMsg,
+    ) -> Vec<Backend2FrontendMsg> {
+        let Some(seq) = seq else {
+            return vec![msg];
+        };
+        if seq < self.next {
+            return Vec::new();
+        }
+        self.pending.insert(seq, msg);
+        if self.pending.len() > MAX_OUT_OF_ORDER {
+            // The gap is not going to be filled; carry on after it
+            if let Some(&first) = self.pending.keys().next() {
+                self.next = first;
+            }
+        }
+        let mut ready = Vec::new();
+        while let Some(msg) = self.pending.remove(&self.next) {
+            ready.push(msg);
+            self.next += 1;
+        }
+        ready
+    }
+}
+
+/// Numbers outgoing messages and keeps them until the server acknowledges them.
+#[derive(Debug, Default)]
+pub struct OutboundLog {
+    next: u64,
+    /// Serialized messages by number, each with the number of the newest
+    /// message at the time it was last sent
+    unacked: BTreeMap<u64, (String, u64)>,
+}
+
+impl OutboundLog {
+    /// Number `msg` and serialize it for sending, keeping it for re-sends.
+    pub fn record(&mut self, msg: &Frontend2BackendMsg) -> serde_json::Result<String> {
+        let seq = self.next;
+        let text = serde_json::to_string(&Sequenced {
+            seq: Some(seq),
+            msg,
+        })?;
+        self.next += 1;
+        self.unacked.insert(seq, (text.clone(), seq));
+        while self.unacked.len() > MAX_UNACKED {
+            self.unacked.pop_first();
+        }
+        Ok(text)
+    }
+
+    /// Note that the server handled message `seq`. It handles messages in the
+    /// order they arrive, so unacknowledged ones sent before `seq` were lost;
+    /// they are returned to be sent again.
+    pub fn ack(&mut self, seq: u64) -> Vec<String> {
+        self.unacked.remove(&seq);
+        let newest = self.next.saturating_sub(1);
+        self.unacked
+            .range_mut(..seq)
+            .filter(|(_, (_, sent_at))| *sent_at < seq)
+            .map(|(_, (text, sent_at))| {
+                *sent_at = newest;
+                text.clone()
+            })
+            .collect()
+    }
+
+    /// Messages sent but not acknowledged yet.
+    pub fn unacked(&self) -> usize {
+        self.unacked.len()
+    }
+}
+
+/// A `WebSocketConnection` that numbers messages in both directions (see
+/// `mcg_shared::Sequenced`), so that a proxy reordering or dropping frames
+/// does not confuse the client.
+///
+/// Incoming messages are delivered in the order the server sent them.
+/// Outgoing ones are re-sent when the server acknowledges a later message
+/// before them.
+pub struct RobustWebSocket {
+    conn: WebSocketConnection,
+    outbound: Rc<RefCell<OutboundLog>>,
+}
+
+impl RobustWebSocket {
+    /// Connect like `WebSocketConnection::connect`: subscribe, send
+    /// `initial_msg` and pass every server message except `Ack`s to
+    /// `on_message`.
+    pub fn connect(
+        server_address: &str,
+        initial_msg: Frontend2BackendMsg,
+        on_message: impl Fn(Backend2FrontendMsg) + 'static,
+        on_error: impl Fn(String) + 'static,
+        on_close: impl Fn(String) + 'static,
+    ) -> Self {
+        let outbound = Rc::new(RefCell::new(OutboundLog::default()));
+        let inbound = RefCell::new(InboundOrder::default());
+        let on_error = Rc::new(on_error);
+
+        let outbound_open = outbound.clone();
+        let on_error_open = on_error.clone();
+        let outbound_text = outbound.clone();
+        let mut conn = WebSocketConnection::new();
+        conn.open(
+            server_address,
+            move |ws| {
+                for msg in [&Frontend2BackendMsg::Subscribe, &initial_msg] {
+                    if let Err(e) = send_numbered(ws, &outbound_open, msg) {
+                        on_error_open(e);
+                        return;
+                    }
+                }
+            },
+            move |ws, txt| {
+                let Ok(Sequenced { seq, msg }) = serde_json::from_str(&txt) else {
+                    return;
+                };
+                let Ok(ready) = inbound
+                    .try_borrow_mut()
+                    .map(|mut inbound| inbound.accept(seq, msg))
+                else {
+                    return;
+                };
+                for msg in ready {
+                    match msg {
+                        Backend2FrontendMsg::Ack { seq } => {
+                            let lost = outbound_text
+                                .try_borrow_mut()
+                                .map(|mut outbound| outbound.ack(seq))
+                                .unwrap_or_default();
+                            for text in lost {
+                                let _ = ws.send_with_str(&text);
+                            }
+                        }
+                        msg => on_message(msg),
+                    }
+                }
+            },
+            move |error| on_error(error),
+            on_close,
+        );
+        Self { conn, outbound }
+    }
+
+    /// Send a numbered `ClientMsg` to the server if connected.
+    pub fn send_msg(&self, msg: &Frontend2BackendMsg) {
+        let Ok(mut outbound) = self.outbound.try_borrow_mut() else {
+            return;
+        };
+        if let Ok(txt) = outbound.record(msg) {
+            self.conn.send_text(&txt);
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.conn.is_connected()
+    }
+
+    /// Whether the socket is open or still opening, so it needs no reconnect.
+    pub fn is_open_or_connecting(&self) -> bool {
+        self.conn.is_open_or_connecting()
+    }
+
+    /// Human-readable socket ready state, `"NONE"` before the first connect.
+    pub fn ready_state_name(&self) -> &'static str {
+        self.conn.ready_state_name()
+    }
+
+    pub fn close(&mut self) {
+        self.conn.close();
+    }
+}
+
+/// Number `msg`, send it and keep it for re-sending.
+fn send_numbered(
+    ws: &WebSocket,
+    outbound: &RefCell<OutboundLog>,
+    msg: &Frontend2BackendMsg,
+) -> Result<(), String> {
+    let txt = outbound
+        .try_borrow_mut()
+        .map_err(|_| "Outgoing messages are busy".to_string())?
+        .record(msg)
+        .map_err(|e| format!("Failed to serialize message: {:?}", e))?;
+    ws.send_with_str(&txt)
+        .map_err(|e| format!("Error sending message: {:?}", e))
+}
+
+impl MessageSender for RobustWebSocket {
+    fn send(&self, msg: &Frontend2BackendMsg) {
+        self.send_msg(msg);
+    }
+}
+
+/// The server connection of the client, shared by all screens so that
+/// navigating between them keeps the socket open. It lives in `ClientState`.
+///
+/// Incoming messages and errors are queued by the socket callbacks and
+/// drained by `ClientState::dispatch_pending_messages` once per frame.
+#[derive(Clone, Default)]
+pub struct ConnectionPool {
+    conn: Option<Rc<RobustWebSocket>>,
+    /// Server `conn` was opened to
+    server_address: Option<String>,
+    messages: Rc<RefCell<VecDeque<Backend2FrontendMsg>>>,
+    errors: Rc<RefCell<VecDeque<String>>>,
+}
+
+impl ConnectionPool {
+    /// The open connection to `server_address`, or a new one replacing any
+    /// connection to another server or one that was closed.
+    ///
+    /// A new connection subscribes and asks for the list of tables.
+    pub fn get_or_create(&mut self, server_address: &str, ctx: &egui::Context) -> &RobustWebSocket {
+        let reusable = self.server_address.as_deref() == Some(server_address)
+            && self
+                .conn
+                .as_ref()
+                .is_some_and(|conn| conn.is_open_or_connecting());
+        if !reusable {
+            self.open(server_address, ctx);
+        }
+        self.conn.as_deref().expect("a connection was just opened")
+    }
+
+    fn open(&mut self, server_address: &str, ctx: &egui::Context) {
+        self.close();
+        // Fresh queues, so nothing from the previous socket is dispatched
+        self.messages = Rc::default();
+        self.errors = Rc::default();
+
+        let messages = self.messages.clone();
+        let errors = self.errors.clone();
+        let errors_on_close = self.errors.clone();
+        let ctx_msg = ctx.clone();
+        let ctx_err = ctx.clone();
+        let ctx_close = ctx.clone();
+        let conn = RobustWebSocket::connect(
+            server_address,
+            Frontend2BackendMsg::ListTables,
+            move |msg| {
+                if let Ok(mut queue) = messages.try_borrow_mut() {
+                    queue.push_back(msg);
+                    ctx_msg.request_repaint();
+                }
+            },
+            move |error| {
+                if let Ok(mut queue) = errors.try_borrow_mut() {
+                    queue.push_back(error);
+                    ctx_err.request_repaint();
+                }
+            },
+            move |reason| {
+                if let Ok(mut queue) = errors_on_close.try_borrow_mut() {
+                    queue.push_back(reason);
+                    ctx_close.request_repaint();
+                }
+            },
+        );
+        self.conn = Some(Rc::new(conn));
+        self.server_address = Some(server_address.to_string());
+    }
+
+    /// Close the connection; screens opened later connect again.
+    pub fn close(&mut self) {
+        if let Some(mut conn) = self.conn.take() {
+            // Other clones of the pool keep the socket open until they drop
+            if let Some(conn) = Rc::get_mut(&mut conn) {
+                conn.close();
+            }
+        }
+        self.server_address = None;
+    }
+
+    /// Server of the current connection, open or not.
+    pub fn server_address(&self) -> Option<&str> {
+        self.server_address.as_deref()
+    }
+
+    pub fn send_msg(&self, msg: &Frontend2BackendMsg) {
+        if let Some(conn) = &self.conn {
+            conn.send_msg(msg);
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.conn.as_ref().is_some_and(|conn| conn.is_connected())
+    }
+
+    /// Human-readable socket ready state, `"NONE"` before the first connect.
+    pub fn ready_state_name(&self) -> &'static str {
+        self.conn
+            .as_ref()
+            .map_or("NONE", |conn| conn.ready_state_name())
+    }
+
+    /// Messages received since the last call.
+    pub fn take_messages(&self) -> Vec<Backend2FrontendMsg> {
+        self.messages
+            .try_borrow_mut()
+            .map(|mut queue| queue.drain(..).collect())
+            .unwrap_or_default()
+    }
+
+    /// Connection errors and close reasons since the last call.
+    pub fn take_errors(&self) -> Vec<String> {
+        self.errors
+            .try_borrow_mut()
+            .map(|mut queue| queue.drain(..).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for ConnectionPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectionPool")
+            .field("server_address", &self.server_address)
+            .field("ready_state", &self.ready_state_name())
+            .finish()
+    }
+}
+
+impl MessageSender for ConnectionPool {
+    fn send(&self, msg: &Frontend2BackendMsg) {
+        self.send_msg(msg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(text: &str) -> Backend2FrontendMsg {
+        Backend2FrontendMsg::Error(text.to_string())
+    }
+
+    fn texts(msgs: Vec<Backend2FrontendMsg>) -> Vec<String> {
+        msgs.into_iter()
+            .map(|msg| match msg {
+                Backend2FrontendMsg::Error(text) => text,
+                other => format!("{:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn iroh_addresses_are_recognized() {
+        assert_eq!(iroh_node_id("iroh://abc123"), Some("abc123"));
+        assert_eq!(iroh_node_id("iroh://"), None);
+        assert_eq!(iroh_node_id("127.0.0.1:3000"), None);
+    }
+
+    #[test]
+    fn server_messages_are_delivered_in_order() {
+        let mut order = InboundOrder::default();
+        assert_eq!(texts(order.accept(Some(0), error("a"))), ["a"]);
+        assert!(order.accept(Some(2), error("c")).is_empty());
+        assert_eq!(texts(order.accept(None, error("x"))), ["x"]);
+        assert_eq!(texts(order.accept(Some(1), error("b"))), ["b", "c"]);
+        // Repeats are dropped
+        assert!(order.accept(Some(1), error("b")).is_empty());
+    }
+
+    #[test]
+    fn a_gap_that_never_fills_is_skipped() {
+        let mut order = InboundOrder::default();
+        let mut delivered = Vec::new();
+        for seq in 1..=MAX_OUT_OF_ORDER as u64 + 1 {
+            delivered.extend(texts(order.accept(Some(seq), error(&seq.to_string()))));
+        }
+        assert_eq!(delivered.len(), MAX_OUT_OF_ORDER + 1);
+        assert_eq!(delivered[0], "1");
+    }
+
+    #[test]
+    fn messages_skipped_by_an_ack_are_sent_again_once() {
+        let mut log = OutboundLog::default();
+        let sent: Vec<String> = (0..4)
+            .map(|_| log.record(&Frontend2BackendMsg::Ping).unwrap())
+            .collect();
+        assert!(sent[0].contains(r#""seq":0"#));
+        assert!(log.ack(0).is_empty());
+        // 1 and 2 went missing
+        assert_eq!(log.ack(3), [sent[1].clone(), sent[2].clone()]);
+        assert_eq!(log.unacked(), 2);
+        // Acks for messages sent before the re-send do not repeat it
+        assert!(log.ack(3).is_empty());
+        log.record(&Frontend2BackendMsg::Ping).unwrap();
+        assert!(log.ack(1).is_empty());
+        assert_eq!(log.ack(4), [sent[2].clone()]);
+    }
+}
@@ -1,10 +1,49 @@
 use crate::game::card::DirectoryCardType;
 use crate::game::card::CARD_NATURAL_SIZE;
 use crate::sprintln;
+use mcg_shared::Card;
 
-pub const AVAILABLE_THEMES: &[&str] = &["img_cards", "alt_cards"];
 pub const DEFAULT_THEME: &str = "img_cards";
 
+/// A built-in card theme as shown in theme selectors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThemeInfo {
+    /// Media folder of the theme, as passed to `set_deck_by_theme`
+    pub id: &'static str,
+    pub display_name: &'static str,
+    /// Card image shown as preview, relative to the page origin
+    pub preview_card_path: &'static str,
+    pub description: &'static str,
+}
+
+const THEMES: &[ThemeInfo] = &[
+    ThemeInfo {
+        id: "img_cards",
+        display_name: "Standard Cards",
+        preview_card_path: "media/img_cards/1_spade.png",
+        description: "Classic 52-card deck",
+    },
+    ThemeInfo {
+        id: "alt_cards",
+        display_name: "Alternative Cards",
+        preview_card_path: "media/alt_cards/card_spade_1.png",
+        description: "Alternative artwork, including jokers",
+    },
+];
+
+/// Lookup of the card themes compiled into the frontend.
+pub struct ThemeRegistry;
+
+impl ThemeRegistry {
+    pub fn list_themes() -> Vec<ThemeInfo> {
+        THEMES.to_vec()
+    }
+
+    pub fn get(id: &str) -> Option<ThemeInfo> {
+        THEMES.iter().find(|t| t.id == id).copied()
+    }
+}
+
 const STANDARD_CARDS: &[&str] = &[
     "1_club.png",
     "1_diamond.png",
@@ -142,6 +181,17 @@ pub fn create_deck(theme: &str) -> DirectoryCardType {
     }
 }
 
+/// File name of the image showing `card` in the given theme, if the theme has one.
+pub fn card_image_name(theme: &str, card: Card) -> Option<&'static str> {
+    let rank = card.rank().as_usize();
+    let suit = card.suit().as_usize();
+    match theme {
+        "img_cards" => STANDARD_CARDS.get(rank * 4 + suit).copied(),
+        "alt_cards" => ALT_CARDS.get(suit * 13 + rank).copied(),
+        _ => None,
+    }
+}
+
 pub fn set_deck_by_theme(card_config: &mut Option<DirectoryCardType>, theme: &str) {
     let deck = create_deck(theme);
     *card_config = Some(deck);
@@ -0,0 +1,233 @@
+//! Translations of the UI strings.
+//!
+//! Every string has a key; `t` looks it up in the active locale and falls
+//! back to English. English and German are built in, other languages are
+//! loaded from the server's `GET /locale/:lang`.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+pub const I18N_CONNECT_BUTTON: &str = "connect_button";
+pub const I18N_DISCONNECT_BUTTON: &str = "disconnect_button";
+pub const I18N_SERVER_LABEL: &str = "server_label";
+pub const I18N_SERVER_ADDRESS_HINT: &str = "server_address_hint";
+pub const I18N_CHECK_BUTTON: &str = "check_button";
+pub const I18N_CALL_BUTTON: &str = "call_button";
+pub const I18N_FOLD_BUTTON: &str = "fold_button";
+pub const I18N_ALL_IN_BUTTON: &str = "all_in_button";
+pub const I18N_ALL_IN_HINT: &str = "all_in_hint";
+pub const I18N_SHORTCUTS_HINT: &str = "shortcuts_hint";
+pub const I18N_ACTION_LOG_TITLE: &str = "action_log_title";
+pub const I18N_COPY_TO_CLIPBOARD: &str = "copy_to_clipboard";
+pub const I18N_COPY_GAME_HINT: &str = "copy_game_hint";
+pub const I18N_LOG_FILTER_HINT: &str = "log_filter_hint";
+pub const I18N_LOG_SORT_HINT: &str = "log_sort_hint";
+pub const I18N_LOG_COLUMN_TIME: &str = "log_column_time";
+pub const I18N_LOG_COLUMN_PLAYER: &str = "log_column_player";
+pub const I18N_LOG_COLUMN_ACTION: &str = "log_column_action";
+pub const I18N_LOG_COLUMN_AMOUNT: &str = "log_column_amount";
+pub const I18N_LOG_COLUMN_STAGE: &str = "log_column_stage";
+pub const I18N_LOG_FOLD: &str = "log_fold";
+pub const I18N_LOG_CHECK: &str = "log_check";
+pub const I18N_LOG_CALL: &str = "log_call";
+pub const I18N_LOG_BET: &str = "log_bet";
+pub const I18N_LOG_RAISE: &str = "log_raise";
+pub const I18N_LOG_ALL_IN: &str = "log_all_in";
+pub const I18N_LOG_SMALL_BLIND: &str = "log_small_blind";
+pub const I18N_LOG_BIG_BLIND: &str = "log_big_blind";
+pub const I18N_LOG_FLOP: &str = "log_flop";
+pub const I18N_LOG_TURN: &str = "log_turn";
+pub const I18N_LOG_RIVER: &str = "log_river";
+pub const I18N_LOG_BOARD: &str = "log_board";
+pub const I18N_LOG_DEALT_HOLE: &str = "log_dealt_hole";
+pub const I18N_LOG_SHOWS: &str = "log_shows";
+pub const I18N_LOG_WINS_POT: &str = "log_wins_pot";
+pub const I18N_LOG_ELIMINATED: &str = "log_eliminated";
+pub const I18N_LOG_ELIMINATED_BY: &str = "log_eliminated_by";
+pub const I18N_LOG_KICKED: &str = "log_kicked";
+
+/// Every key a locale file translates
+pub const KEYS: &[&str] = &[
+    I18N_CONNECT_BUTTON,
+    I18N_DISCONNECT_BUTTON,
+    I18N_SERVER_LABEL,
+    I18N_SERVER_ADDRESS_HINT,
+    I18N_CHECK_BUTTON,
+    I18N_CALL_BUTTON,
+    I18N_FOLD_BUTTON,
+    I18N_ALL_IN_BUTTON,
+    I18N_ALL_IN_HINT,
+    I18N_SHORTCUTS_HINT,
+    I18N_ACTION_LOG_TITLE,
+    I18N_COPY_TO_CLIPBOARD,
+    I18N_COPY_GAME_HINT,
+    I18N_LOG_FILTER_HINT,
+    I18N_LOG_SORT_HINT,
+    I18N_LOG_COLUMN_TIME,
+    I18N_LOG_COLUMN_PLAYER,
+    I18N_LOG_COLUMN_ACTION,
+    I18N_LOG_COLUMN_AMOUNT,
+    I18N_LOG_COLUMN_STAGE,
+    I18N_LOG_FOLD,
+    I18N_LOG_CHECK,
+    I18N_LOG_CALL,
+    I18N_LOG_BET,
+    I18N_LOG_RAISE,
+    I18N_LOG_ALL_IN,
+    I18N_LOG_SMALL_BLIND,
+    I18N_LOG_BIG_BLIND,
+    I18N_LOG_FLOP,
+    I18N_LOG_TURN,
+    I18N_LOG_RIVER,
+    I18N_LOG_BOARD,
+    I18N_LOG_DEALT_HOLE,
+    I18N_LOG_SHOWS,
+    I18N_LOG_WINS_POT,
+    I18N_LOG_ELIMINATED,
+    I18N_LOG_ELIMINATED_BY,
+    I18N_LOG_KICKED,
+];
+
+/// Languages that work without a server, as `(lang, name)`
+pub const BUILT_IN: [(&str, &str); 2] = [("en", "English"), ("de", "Deutsch")];
+
+const EN_JSON: &str = include_str!("../../locales/en.json");
+const DE_JSON: &str = include_str!("../../locales/de.json");
+
+/// Translated UI strings of one language, by key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Locale {
+    pub lang: String,
+    pub strings: HashMap<&'static str, String>,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        english().clone()
+    }
+}
+
+impl Locale {
+    /// Parse a locale file: a JSON object of strings by key. Unknown keys
+    /// are ignored, missing ones fall back to English.
+    pub fn from_json(lang: &str, json: &str) -> Result<Self, String> {
+        let mut parsed: HashMap<String, String> =
+            serde_json::from_str(json).map_err(|e| format!("Invalid locale '{}': {}", lang, e))?;
+        let strings = KEYS
+            .iter()
+            .filter_map(|&key| parsed.remove(key).map(|text| (key, text)))
+            .collect();
+        Ok(Self {
+            lang: lang.to_string(),
+            strings,
+        })
+    }
+
+    /// One of the `BUILT_IN` languages.
+    pub fn built_in(lang: &str) -> Option<Self> {
+        let json = match lang {
+            "en" => EN_JSON,
+            "de" => DE_JSON,
+            _ => return None,
+        };
+        Self::from_json(lang, json).ok()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.strings.get(key).map(String::as_str)
+    }
+}
+
+/// Fetch the locale file of `lang` from the server.
+pub async fn fetch_locale(server_address: &str, lang: &str) -> Result<Locale, String> {
+    let url = format!("http://{}/locale/{}", server_address, lang);
+
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| format!("Failed to fetch locale: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    let json = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read locale: {}", e))?;
+    Locale::from_json(lang, &json)
+}
+
+fn english() -> &'static Locale {
+    static ENGLISH: OnceLock<Locale> = OnceLock::new();
+    ENGLISH.get_or_init(|| Locale::from_json("en", EN_JSON).expect("built-in English locale"))
+}
+
+thread_local! {
+    static CURRENT: Cell<&'static Locale> = Cell::new(english());
+}
+
+/// Make `locale` the one `t` translates to. Switching languages leaks the
+/// previous locale, which only happens when the player picks another one.
+pub fn set_locale(locale: Locale) {
+    if CURRENT.with(|current| *current.get() == locale) {
+        return;
+    }
+    let locale: &'static Locale = Box::leak(Box::new(locale));
+    CURRENT.with(|current| current.set(locale));
+}
+
+/// Language of the active locale.
+pub fn current_lang() -> &'static str {
+    CURRENT.with(|current| current.get().lang.as_str())
+}
+
+/// Text of `key` in the active locale, or in English if it has none; the
+/// key itself if no locale knows it.
+pub fn t(key: &str) -> &str {
+    let current = CURRENT.with(Cell::get);
+    current
+        .get(key)
+        .or_else(|| english().get(key))
+        .unwrap_or(key)
+}
+
+/// `t(key)` with its `{}` placeholder replaced by `arg`.
+pub fn tf(key: &str, arg: impl Display) -> String {
+    t(key).replacen("{}", &arg.to_string(), 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_locales_translate_every_key() {
+        for (lang, _) in BUILT_IN {
+            let locale = Locale::built_in(lang).expect("built-in locale parses");
+            for key in KEYS {
+                assert!(locale.get(key).is_some(), "{} lacks {}", lang, key);
+            }
+        }
+    }
+
+    #[test]
+    fn missing_keys_fall_back_to_english() {
+        let partial = r#"{"connect_button": "Connetti", "unknown": "x"}"#;
+        let locale = Locale::from_json("it", partial).unwrap();
+        assert_eq!(locale.strings.len(), 1);
+        set_locale(locale);
+        assert_eq!(current_lang(), "it");
+        assert_eq!(t(I18N_CONNECT_BUTTON), "Connetti");
+        assert_eq!(t(I18N_DISCONNECT_BUTTON), "Disconnect");
+        assert_eq!(t("no_such_key"), "no_such_key");
+        assert_eq!(tf(I18N_CALL_BUTTON, 20), "✔ Call 20");
+        set_locale(Locale::default());
+    }
+
+    #[test]
+    fn invalid_locale_files_are_rejected() {
+        assert!(Locale::from_json("xx", "[1, 2]").is_err());
+    }
+}
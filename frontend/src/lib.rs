@@ -1,14 +1,21 @@
 //! Client-side (WASM) library for the MCG app.
 
 pub mod articles;
+pub mod discovery;
 pub mod effects;
+pub mod equity;
 pub mod game;
 pub mod hardcoded_cards;
+pub mod i18n;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod plugin_loader;
 pub mod qr_scanner;
 pub mod router;
 pub mod store;
 pub mod utils;
 
+use std::cell::RefCell;
+
 #[allow(unused_imports)]
 use eframe::AppCreator;
 use eframe::{WebOptions, WebRunner};
@@ -26,11 +33,20 @@ extern "C" {
     pub fn log(s: &str);
 }
 
+thread_local! {
+    /// Runner of the app started last, for `resize_canvas`
+    static RUNNER: RefCell<Option<WebRunner>> = const { RefCell::new(None) };
+}
+
 /// Platform-agnostic println! alternative that works in both native and WASM targets
 #[macro_export]
 macro_rules! sprintln {
     ($($arg:tt)*) => {{
+        // wasm-bindgen imports panic when called outside the browser
+        #[cfg(target_arch = "wasm32")]
         $crate::log(format!($($arg)*).as_str());
+        #[cfg(not(target_arch = "wasm32"))]
+        eprintln!($($arg)*);
     }};
 }
 
@@ -43,11 +59,14 @@ pub fn start_game(
 
     // Initialize a wasm-friendly tracing subscriber so tracing::info!/warn!/error!
     // are forwarded to the browser console. tracing-wasm provides such a subscriber.
-    tracing_wasm::set_as_global_default();
+    // Only the first start on a page installs it; later starts keep that one.
+    let _ = tracing_wasm::try_set_as_global_default();
 
     let web_options = WebOptions::default();
+    let runner = WebRunner::new();
+    RUNNER.with(|r| *r.borrow_mut() = Some(runner.clone()));
     spawn_local(async move {
-        if let Err(e) = WebRunner::new().start(canvas, web_options, init).await {
+        if let Err(e) = runner.start(canvas, web_options, init).await {
             // Avoid panicking inside wasm task; log instead
             sprintln!("Failed to start eframe: {:?}", e);
         }
@@ -61,6 +80,12 @@ pub fn calculate_dpi_scale() -> f32 {
     let screen = window.screen().expect("unable to get screen object");
     let width = screen.width().unwrap_or(1920) as f32;
     let height = screen.height().unwrap_or(1080) as f32;
+    dpi_scale_for(width, height, device_pixel_ratio)
+}
+
+/// UI scale for an area of `width` x `height` CSS pixels, shown with
+/// `device_pixel_ratio` physical pixels per CSS pixel.
+pub fn dpi_scale_for(width: f32, height: f32, device_pixel_ratio: f32) -> f32 {
     let diagonal = (width * width + height * height).sqrt();
     let base_scale = if diagonal > 3000.0 {
         1.8
@@ -74,6 +99,61 @@ pub fn calculate_dpi_scale() -> f32 {
     base_scale * (device_pixel_ratio / 2.0).clamp(0.75, 1.5)
 }
 
+/// Rescale the UI to a canvas resized to `width` x `height` CSS pixels.
+/// Meant to be called from a `ResizeObserver`, see EMBEDDING.md; does nothing
+/// until the app started by `start` runs.
+#[wasm_bindgen]
+pub fn resize_canvas(width: u32, height: u32) {
+    let device_pixel_ratio = window().map_or(1.0, |w| w.device_pixel_ratio()) as f32;
+    let scale = dpi_scale_for(width as f32, height as f32, device_pixel_ratio);
+    RUNNER.with(|runner| {
+        // Busy while the runner draws a frame; the next resize catches up
+        if let Some(mut app) = runner.borrow().as_ref().and_then(|r| r.app_mut::<App>()) {
+            app.fit_canvas(scale);
+        }
+    });
+}
+
+/// Port of the server when the page does not configure an address
+const DEFAULT_SERVER_PORT: u16 = 3000;
+
+/// String property `key` of the `window.MCG_CONFIG` object the embedding page
+/// may define before starting the app, see EMBEDDING.md.
+pub fn mcg_config(key: &str) -> Option<String> {
+    let config = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("MCG_CONFIG")).ok()?;
+    if !config.is_object() {
+        return None;
+    }
+    js_sys::Reflect::get(&config, &JsValue::from_str(key))
+        .ok()?
+        .as_string()
+        .filter(|value| !value.is_empty())
+}
+
+/// Server address to pre-fill: `MCG_CONFIG.serverAddress` if set, otherwise
+/// the default port on the host the page was served from.
+#[wasm_bindgen]
+pub fn get_server_address() -> String {
+    if let Some(address) = mcg_config("serverAddress") {
+        return address;
+    }
+    let host = window()
+        .and_then(|w| w.location().hostname().ok())
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "127.0.0.1".to_string());
+    format!("{}:{}", host, DEFAULT_SERVER_PORT)
+}
+
+/// Path of the screen to open first: `MCG_CONFIG.initialScreen` if set,
+/// otherwise the path of the current URL.
+#[wasm_bindgen]
+pub fn get_initial_screen() -> String {
+    mcg_config("initialScreen")
+        .or_else(|| window().and_then(|w| w.location().pathname().ok()))
+        .filter(|path| !path.is_empty())
+        .unwrap_or_else(|| "/".to_string())
+}
+
 #[wasm_bindgen]
 pub fn start(canvas: HtmlCanvasElement) -> Result<(), JsValue> {
     let init = Box::new(|cc: &eframe::CreationContext| {
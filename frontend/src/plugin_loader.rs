@@ -0,0 +1,278 @@
+//! Screens loaded at runtime from plugin libraries (`.so`, `.dylib` or
+//! `.dll`). Browsers cannot load native libraries, so this is native only.
+//!
+//! A plugin exports
+//!
+//! ```ignore
+//! #[no_mangle]
+//! pub extern "C" fn mcg_plugin_abi_version() -> *const c_char
+//! #[no_mangle]
+//! pub extern "C" fn mcg_plugin_screens() -> *const PluginScreenDef
+//! ```
+//!
+//! The first returns [`PLUGIN_ABI_VERSION`]. Screens cross the library
+//! boundary as Rust trait objects, so a plugin has to be built with the same
+//! compiler and versions of this crate and egui as the app; libraries built
+//! otherwise are refused before their screens are read. The second returns
+//! the screens, ended by [`PluginScreenDef::END`]. They are registered next
+//! to the built-in screens, see `ScreenRegistry::new`.
+//! `crates/example_screen_plugin` is a minimal plugin.
+//!
+//! The libraries to load are listed in [`CONFIG_FILE`]:
+//!
+//! ```toml
+//! plugin_paths = ["target/debug/libexample_screen_plugin.so"]
+//! ```
+//!
+//! [`PLUGIN_PATHS_VAR`] overrides that list.
+
+use std::ffi::{c_char, c_void, CStr};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use libloading::{Library, Symbol};
+use serde::Deserialize;
+
+use crate::game::screens::{ScreenMetadata, ScreenWidget};
+
+/// Config file of native builds, in the working directory
+pub const CONFIG_FILE: &str = "mcg-frontend.toml";
+
+/// Environment variable listing plugin libraries, separated like `PATH`.
+/// Replaces the `plugin_paths` of [`CONFIG_FILE`] when set.
+pub const PLUGIN_PATHS_VAR: &str = "MCG_PLUGIN_PATHS";
+
+/// Symbol every plugin exports, returning its [`PLUGIN_ABI_VERSION`]
+const ABI_VERSION_SYMBOL: &[u8] = b"mcg_plugin_abi_version\0";
+
+/// Symbol every plugin exports, returning its screens
+const ENTRY_POINT: &[u8] = b"mcg_plugin_screens\0";
+
+/// Compiler, target and version of this crate of a build. A plugin returns
+/// the one it was compiled with, which has to equal the app's.
+pub const PLUGIN_ABI_VERSION: &CStr = match CStr::from_bytes_with_nul(
+    concat!(
+        env!("MCG_PLUGIN_ABI"),
+        " frontend ",
+        env!("CARGO_PKG_VERSION"),
+        "\0"
+    )
+    .as_bytes(),
+) {
+    Ok(version) => version,
+    Err(_) => panic!("the ABI version contains a NUL byte"),
+};
+
+/// Creates a screen, returned by [`into_plugin_screen`].
+pub type PluginFactory = extern "C" fn() -> *mut c_void;
+
+/// A screen a plugin provides. Strings are NUL-terminated UTF-8.
+#[repr(C)]
+pub struct PluginScreenDef {
+    /// URL path of the screen, like `/hello`
+    pub path: *const c_char,
+    /// Name shown in the main menu
+    pub display_name: *const c_char,
+    pub factory: Option<PluginFactory>,
+}
+
+impl PluginScreenDef {
+    /// Ends the list of a plugin's screens
+    pub const END: Self = Self {
+        path: std::ptr::null(),
+        display_name: std::ptr::null(),
+        factory: None,
+    };
+}
+
+/// Which plugins to load
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct PluginConfig {
+    #[serde(default)]
+    pub plugin_paths: Vec<PathBuf>,
+}
+
+impl PluginConfig {
+    /// The plugins listed in [`PLUGIN_PATHS_VAR`] if it is set, otherwise
+    /// those of [`CONFIG_FILE`]. No plugins if neither exists or the file
+    /// is invalid.
+    pub fn load() -> Self {
+        if let Some(paths) = std::env::var_os(PLUGIN_PATHS_VAR) {
+            return Self {
+                plugin_paths: std::env::split_paths(&paths).collect(),
+            };
+        }
+        let path = Path::new(CONFIG_FILE);
+        if !path.exists() {
+            return Self::default();
+        }
+        Self::from_file(path).unwrap_or_else(|e| {
+            crate::sprintln!("Failed to read {}: {}", path.display(), e);
+            Self::default()
+        })
+    }
+
+    /// Read a TOML config file. Relative plugin paths are relative to the
+    /// directory of the file.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let mut config: Self = toml::from_str(&text).map_err(|e| e.to_string())?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        for plugin in &mut config.plugin_paths {
+            *plugin = dir.join(&*plugin);
+        }
+        Ok(config)
+    }
+}
+
+/// A screen loaded from a plugin
+pub struct PluginScreen {
+    pub meta: ScreenMetadata,
+    pub factory: PluginFactory,
+}
+
+impl PluginScreen {
+    pub fn create(&self) -> Box<dyn ScreenWidget> {
+        let raw = (self.factory)();
+        assert!(!raw.is_null(), "plugin screen {} is null", self.meta.path);
+        // SAFETY: factories return screens boxed by `into_plugin_screen`
+        unsafe { *Box::from_raw(raw.cast::<Box<dyn ScreenWidget>>()) }
+    }
+}
+
+/// Hand `screen` over to the app, in the factory of a plugin screen.
+pub fn into_plugin_screen(screen: Box<dyn ScreenWidget>) -> *mut c_void {
+    Box::into_raw(Box::new(screen)).cast()
+}
+
+struct Plugins {
+    /// Never unloaded, since the code of the screens lives in them
+    _libraries: Vec<Library>,
+    screens: Vec<PluginScreen>,
+}
+
+static PLUGINS: OnceLock<Plugins> = OnceLock::new();
+
+/// Load the plugins of `config`, unless plugins were loaded already. Apps
+/// call this before creating their `App` to load other plugins than the
+/// ones of [`PluginConfig::load`].
+pub fn load_plugins(config: &PluginConfig) {
+    PLUGINS.get_or_init(|| load(config));
+}
+
+/// Screens of the loaded plugins. If none were loaded yet, the ones of
+/// [`PluginConfig::load`] are.
+pub fn plugin_screens() -> &'static [PluginScreen] {
+    &PLUGINS.get_or_init(|| load(&PluginConfig::load())).screens
+}
+
+/// Libraries that fail to load are left out.
+fn load(config: &PluginConfig) -> Plugins {
+    let mut plugins = Plugins {
+        _libraries: Vec::new(),
+        screens: Vec::new(),
+    };
+    for path in &config.plugin_paths {
+        // SAFETY: the configured libraries are trusted to be plugins
+        match unsafe { load_library(path) } {
+            Ok((library, screens)) => {
+                plugins._libraries.push(library);
+                plugins.screens.extend(screens);
+            }
+            Err(e) => crate::sprintln!("Failed to load plugin {}: {}", path.display(), e),
+        }
+    }
+    plugins
+}
+
+/// # Safety
+///
+/// `path` must be a plugin: loading it runs its initialization code, and
+/// its exports are called as described above.
+unsafe fn load_library(path: &Path) -> Result<(Library, Vec<PluginScreen>), String> {
+    let library = Library::new(path).map_err(|e| e.to_string())?;
+    {
+        let abi_version: Symbol<extern "C" fn() -> *const c_char> = library
+            .get(ABI_VERSION_SYMBOL)
+            .map_err(|e| format!("not a plugin: {e}"))?;
+        let abi_version = abi_version();
+        if abi_version.is_null() {
+            return Err("the plugin has no ABI version".into());
+        }
+        let abi_version = CStr::from_ptr(abi_version);
+        if abi_version != PLUGIN_ABI_VERSION {
+            return Err(format!(
+                "built with {}, but the app with {}",
+                abi_version.to_string_lossy(),
+                PLUGIN_ABI_VERSION.to_string_lossy()
+            ));
+        }
+    }
+    let mut screens = Vec::new();
+    {
+        let entry: Symbol<extern "C" fn() -> *const PluginScreenDef> =
+            library.get(ENTRY_POINT).map_err(|e| e.to_string())?;
+        let mut def = entry();
+        while let Some(screen) = def.as_ref().filter(|d| !d.path.is_null()) {
+            let factory = screen.factory.ok_or("a screen has no factory")?;
+            let meta = ScreenMetadata {
+                path: leak_str(screen.path)?,
+                display_name: leak_str(screen.display_name)?,
+                icon: "🧩",
+                description: "Plugin screen",
+                show_in_menu: true,
+            };
+            screens.push(PluginScreen { meta, factory });
+            def = def.add(1);
+        }
+    }
+    Ok((library, screens))
+}
+
+/// Copy a string of a plugin into one that lives as long as the app.
+///
+/// # Safety
+///
+/// `s` is null or points to a NUL-terminated string.
+unsafe fn leak_str(s: *const c_char) -> Result<&'static str, String> {
+    if s.is_null() {
+        return Err("a screen has no name".into());
+    }
+    let s = CStr::from_ptr(s).to_str().map_err(|e| e.to_string())?;
+    Ok(Box::leak(s.to_owned().into_boxed_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn libraries_that_fail_to_load_are_left_out() {
+        let config = PluginConfig {
+            plugin_paths: vec![PathBuf::from("/nonexistent/libplugin.so")],
+        };
+        assert!(load(&config).screens.is_empty());
+    }
+
+    #[test]
+    fn config_file_paths_are_relative_to_the_file() {
+        let dir = std::env::temp_dir().join(format!("mcg-plugins-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join(CONFIG_FILE);
+        std::fs::write(
+            &file,
+            "plugin_paths = [\"libhello.so\", \"/usr/lib/libother.so\"]\n",
+        )
+        .unwrap();
+
+        let config = PluginConfig::from_file(&file).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            config.plugin_paths,
+            [
+                dir.join("libhello.so"),
+                PathBuf::from("/usr/lib/libother.so")
+            ]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use base64::Engine;
 use egui::TextureHandle;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -209,28 +210,6 @@ impl Camera {
     pub fn get_facing_mode(&self) -> CameraFacing {
         self.facing_mode
     }
-    fn analyze_qr_frame(&mut self, pixels: &[egui::Color32], width: usize, height: usize) {
-        let mut gray_data = Vec::with_capacity(width * height);
-        for pixel in pixels {
-            let r = pixel.r() as f32;
-            let g = pixel.g() as f32;
-            let b = pixel.b() as f32;
-            let gray = (0.299 * r + 0.587 * g + 0.114 * b) as u8;
-            gray_data.push(gray);
-        }
-        let mut prepared_image =
-            rqrr::PreparedImage::prepare_from_greyscale(width, height, |x, y| {
-                gray_data[y * width + x]
-            });
-        let grids = prepared_image.detect_grids();
-        for grid in grids {
-            if let Ok((_meta, content)) = grid.decode() {
-                let qr_content = content;
-                self.last_qr_result = Some(qr_content);
-                break;
-            }
-        }
-    }
     fn analyze_qr_frame_raw(&mut self, pixels: &[egui::Color32], width: usize, height: usize) {
         let mut gray_data = Vec::with_capacity(width * height);
         for pixel in pixels {
@@ -255,15 +234,126 @@ impl Camera {
     }
 }
 
+/// What a player pasted into the scanner instead of showing a QR code to the camera
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PastedQr {
+    /// A `host:port` connection string
+    Address(String),
+    /// Text of the QR code in a pasted image
+    Text(String),
+    /// Binary content of the QR code in a pasted image
+    Raw(Vec<u8>),
+}
+
+/// Make sense of clipboard text: either a `host:port` connection string, or a
+/// QR code image as base64-encoded PNG (optionally as a `data:` URL).
+pub fn parse_clipboard(text: &str) -> Result<PastedQr, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("The clipboard is empty".into());
+    }
+    if is_connection_string(text) {
+        return Ok(PastedQr::Address(text.to_string()));
+    }
+    let encoded = text
+        .strip_prefix("data:image/png;base64,")
+        .unwrap_or(text)
+        .split_whitespace()
+        .collect::<String>();
+    let png = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|_| "The clipboard holds neither an address nor a base64-encoded image")?;
+    let image = image::load_from_memory_with_format(&png, image::ImageFormat::Png)
+        .map_err(|_| "The pasted data is not a PNG image")?
+        .to_luma8();
+    let mut prepared = rqrr::PreparedImage::prepare_from_greyscale(
+        image.width() as usize,
+        image.height() as usize,
+        |x, y| image.get_pixel(x as u32, y as u32)[0],
+    );
+    let content = prepared
+        .detect_grids()
+        .into_iter()
+        .find_map(|grid| {
+            let mut buf = Vec::new();
+            grid.decode_to(&mut buf).ok().map(|_| buf)
+        })
+        .ok_or("No QR code found in the pasted image")?;
+    Ok(match String::from_utf8(content) {
+        Ok(text) => PastedQr::Text(text),
+        Err(e) => PastedQr::Raw(e.into_bytes()),
+    })
+}
+
+/// Whether `text` looks like `host:port`, e.g. `localhost:3000`
+fn is_connection_string(text: &str) -> bool {
+    text.rsplit_once(':').is_some_and(|(host, port)| {
+        !host.is_empty() && !host.contains(char::is_whitespace) && port.parse::<u16>().is_ok()
+    })
+}
+
 #[allow(dead_code)]
 #[derive(Default)]
 pub struct QrScannerPopup {
     open: bool,
     camera: Rc<RefCell<Camera>>,
     started: bool,
+    /// Clipboard text read by `scan_from_clipboard`, picked up on a later frame
+    clipboard: Rc<RefCell<Option<Result<String, String>>>>,
+    /// Why the last paste could not be used
+    paste_error: Option<String>,
 }
 
 impl QrScannerPopup {
+    /// Read the clipboard, for players who copied a QR code image from another
+    /// app or a connection string. Browsers only allow this in response to a
+    /// click; the text is handled by `button_and_popup` once it arrives.
+    pub fn scan_from_clipboard(&mut self) {
+        self.paste_error = None;
+        let Some(window) = web_sys::window() else {
+            self.paste_error = Some("The clipboard is not available".into());
+            return;
+        };
+        // Ask right away, while the click still counts as a user gesture
+        let read = window.navigator().clipboard().read_text();
+        let result = self.clipboard.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let text = wasm_bindgen_futures::JsFuture::from(read)
+                .await
+                .map(|text| text.as_string().unwrap_or_default())
+                .map_err(|e| format!("Could not read the clipboard: {:?}", e));
+            if let Ok(mut slot) = result.try_borrow_mut() {
+                *slot = Some(text);
+            }
+        });
+    }
+
+    /// Apply text read from the clipboard; returns whether the popup is done.
+    fn take_pasted(&mut self, target: &mut String, target_raw: &mut Vec<u8>) -> bool {
+        let Some(pasted) = self
+            .clipboard
+            .try_borrow_mut()
+            .ok()
+            .and_then(|mut c| c.take())
+        else {
+            return false;
+        };
+        match pasted.and_then(|text| parse_clipboard(&text)) {
+            Ok(PastedQr::Address(text) | PastedQr::Text(text)) => {
+                *target = text;
+                true
+            }
+            Ok(PastedQr::Raw(bytes)) => {
+                *target_raw = bytes;
+                false
+            }
+            Err(e) => {
+                self.paste_error = Some(e);
+                false
+            }
+        }
+    }
+
     #[allow(clippy::ptr_arg)]
     pub fn button_and_popup(
         &mut self,
@@ -321,6 +411,16 @@ impl QrScannerPopup {
                     } else {
                         ui.label("Camera busy...");
                     }
+                    if self.take_pasted(_target, _target_raw) {
+                        if let Ok(mut camera) = self.camera.try_borrow_mut() {
+                            camera.stop();
+                        }
+                        self.started = false;
+                        self.open = false;
+                    }
+                    if let Some(error) = &self.paste_error {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                    }
                     ui.add_space(8.0);
                     if ui.button("Close").clicked() {
                         if let Ok(mut camera) = self.camera.try_borrow_mut() {
@@ -341,12 +441,22 @@ impl QrScannerPopup {
                         }
                         let camera_ref = self.camera.clone();
                         self.started = true;
+                        // The UI only uses `try_borrow_mut` on the camera, so
+                        // holding it while it starts cannot panic
+                        #[allow(clippy::await_holding_refcell_ref)]
                         wasm_bindgen_futures::spawn_local(async move {
                             if let Ok(mut cam) = camera_ref.try_borrow_mut() {
                                 let _ = cam.start().await;
                             }
                         });
                     }
+                    if ui
+                        .button("Paste from clipboard")
+                        .on_hover_text("Use a QR code image or address copied from another app")
+                        .clicked()
+                    {
+                        self.scan_from_clipboard();
+                    }
                     if ui
                         .button("Change Camera")
                         .on_hover_text("Change which camera is used")
@@ -376,3 +486,39 @@ impl QrScannerPopup {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A QR code holding `data`, as base64-encoded PNG
+    fn qr_png_base64(data: &[u8]) -> String {
+        let image = qrcode::QrCode::new(data)
+            .unwrap()
+            .render::<image::Luma<u8>>()
+            .build();
+        let mut png = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut png, image::ImageFormat::Png).unwrap();
+        base64::engine::general_purpose::STANDARD.encode(png.into_inner())
+    }
+
+    #[test]
+    fn pasted_connection_strings_are_used_directly() {
+        assert_eq!(
+            parse_clipboard(" localhost:3000\n"),
+            Ok(PastedQr::Address("localhost:3000".into()))
+        );
+        assert!(parse_clipboard("hello world").is_err());
+        assert!(parse_clipboard("").is_err());
+    }
+
+    #[test]
+    fn pasted_images_are_scanned() {
+        assert_eq!(
+            parse_clipboard(&qr_png_base64(b"hello")),
+            Ok(PastedQr::Text("hello".into()))
+        );
+        let data_url = format!("data:image/png;base64,{}", qr_png_base64(&[0xff, 0]));
+        assert_eq!(parse_clipboard(&data_url), Ok(PastedQr::Raw(vec![0xff, 0])));
+    }
+}
@@ -1,11 +1,462 @@
-use crate::articles::Post;
-use mcg_shared::{GameStatePublic, Backend2FrontendMsg};
-use std::collections::VecDeque;
+use mcg_shared::{
+    ActionEvent, ActionKind, ArticleMeta, Backend2FrontendMsg, Frontend2BackendMsg, GameAction,
+    GameStatePublic, GtoAnnotation, HandRankCategory, PairingInvite, PlayerId, PlayerPublic, Stage,
+    TableId, TableSummary, TournamentState,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+use crate::game::notifications::{Notification, NotificationLevel};
+use crate::game::websocket::{ConnectionPool, RobustWebSocket};
+use crate::i18n::Locale;
+
+/// Version sent to the server in `ClientVersion`
+pub const CLIENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Preset avatar colors offered in the settings.
+pub const AVATAR_COLORS: [[u8; 3]; 16] = [
+    [231, 76, 60],
+    [230, 126, 34],
+    [241, 196, 15],
+    [46, 204, 113],
+    [26, 188, 156],
+    [52, 152, 219],
+    [155, 89, 182],
+    [233, 30, 99],
+    [192, 57, 43],
+    [211, 84, 0],
+    [39, 174, 96],
+    [22, 160, 133],
+    [41, 128, 185],
+    [142, 68, 173],
+    [127, 140, 141],
+    [236, 240, 241],
+];
+
+/// The local player's profile, persisted in the browser's `localStorage`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlayerProfile {
+    pub name: String,
+    pub avatar_color: [u8; 3],
+    pub preferred_server: String,
+}
+
+impl Default for PlayerProfile {
+    fn default() -> Self {
+        Self {
+            name: "Player".to_string(),
+            avatar_color: AVATAR_COLORS[5],
+            preferred_server: "127.0.0.1:3000".to_string(),
+        }
+    }
+}
+
+impl PlayerProfile {
+    const STORAGE_KEY: &'static str = "mcg_profile";
+
+    fn storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    /// Load the stored profile, if one was saved and can be parsed.
+    pub fn load() -> Option<Self> {
+        let json = Self::storage()?.get_item(Self::STORAGE_KEY).ok()??;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// Persist the profile to `localStorage`.
+    pub fn save(&self) -> Result<(), String> {
+        let storage = Self::storage().ok_or("localStorage is not available")?;
+        let json = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        storage
+            .set_item(Self::STORAGE_KEY, &json)
+            .map_err(|e| format!("Failed to save profile: {:?}", e))
+    }
+}
+
+/// The table a client sat at and its last state, kept in the browser's
+/// `sessionStorage` so a refreshed page can show the table while it
+/// reconnects. See `StateSerializer`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CachedSession {
+    pub server_address: String,
+    pub table_id: TableId,
+    pub game_state: GameStatePublic,
+}
+
+/// Keeps the [`CachedSession`] of the current tab in `sessionStorage`, which
+/// survives page refreshes but not closing the tab.
+pub struct StateSerializer;
+
+impl StateSerializer {
+    const STORAGE_KEY: &'static str = "mcg_session";
+
+    fn storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.session_storage().ok()?
+    }
+
+    /// The cached session, if one was saved and can be parsed.
+    pub fn load() -> Option<CachedSession> {
+        let json = Self::storage()?.get_item(Self::STORAGE_KEY).ok()??;
+        serde_json::from_str(&json).ok()
+    }
+
+    pub fn save(session: &CachedSession) -> Result<(), String> {
+        let storage = Self::storage().ok_or("sessionStorage is not available")?;
+        let json = serde_json::to_string(session).map_err(|e| e.to_string())?;
+        storage
+            .set_item(Self::STORAGE_KEY, &json)
+            .map_err(|e| format!("Failed to cache the session: {:?}", e))
+    }
+
+    pub fn clear() {
+        if let Some(storage) = Self::storage() {
+            let _ = storage.remove_item(Self::STORAGE_KEY);
+        }
+    }
+}
+
+/// Poker milestones of the local player
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AchievementId {
+    FirstWin,
+    StraightFlushWin,
+    SurvivedAllIn,
+    FiveWinStreak,
+    /// An opponent raised, was re-raised by the player and folded
+    CalledBluff,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Achievement {
+    pub id: AchievementId,
+    pub title: &'static str,
+    pub description: &'static str,
+    pub unlocked: bool,
+    /// Hand of the session it was unlocked in, counting from 1
+    pub unlocked_at_hand: Option<u32>,
+}
+
+impl Achievement {
+    fn locked(id: AchievementId) -> Self {
+        let (title, description) = match id {
+            AchievementId::FirstWin => ("First Win", "Win a pot"),
+            AchievementId::StraightFlushWin => (
+                "Won with a Straight Flush",
+                "Win a pot with a straight flush",
+            ),
+            AchievementId::SurvivedAllIn => ("Survived an All-In", "Go all-in and win the pot"),
+            AchievementId::FiveWinStreak => ("Won 5 Hands in a Row", "Win five hands in a row"),
+            AchievementId::CalledBluff => (
+                "Called a Bluff",
+                "Re-raise an opponent's raise and make them fold",
+            ),
+        };
+        Self {
+            id,
+            title,
+            description,
+            unlocked: false,
+            unlocked_at_hand: None,
+        }
+    }
+}
+
+/// What happened to the tracked player in the current hand
+#[derive(Clone, Debug, Default)]
+struct HandProgress {
+    won: bool,
+    all_in: bool,
+    straight_flush: bool,
+    /// Opponent who made the last bet or raise
+    raiser: Option<PlayerId>,
+    /// Opponent the player re-raised
+    reraised: Option<PlayerId>,
+}
+
+/// Unlocks achievements by following the action log of the states the
+/// server sends. Unlocked achievements are kept in `localStorage`.
+#[derive(Clone, Debug)]
+pub struct AchievementManager {
+    pub achievements: Vec<Achievement>,
+    /// Player whose milestones are tracked, the one this client plays
+    pub player: PlayerId,
+    /// Achievements unlocked since the UI last announced them
+    pub toasts: VecDeque<AchievementId>,
+    /// Hands of `player` seen this session
+    hands: u32,
+    win_streak: u32,
+    hand: Option<HandProgress>,
+    /// Action log of the last state, to find the new events of the next one
+    seen_log: Vec<ActionEvent>,
+}
+
+impl Default for AchievementManager {
+    fn default() -> Self {
+        const ALL: [AchievementId; 5] = [
+            AchievementId::FirstWin,
+            AchievementId::StraightFlushWin,
+            AchievementId::SurvivedAllIn,
+            AchievementId::FiveWinStreak,
+            AchievementId::CalledBluff,
+        ];
+        Self {
+            achievements: ALL.into_iter().map(Achievement::locked).collect(),
+            player: PlayerId(0),
+            toasts: VecDeque::new(),
+            hands: 0,
+            win_streak: 0,
+            hand: None,
+            seen_log: Vec::new(),
+        }
+    }
+}
+
+impl AchievementManager {
+    const STORAGE_KEY: &'static str = "mcg_achievements";
+
+    /// Achievements with those unlocked in earlier sessions restored.
+    pub fn load() -> Self {
+        let mut manager = Self::default();
+        let stored = PlayerProfile::storage()
+            .and_then(|s| s.get_item(Self::STORAGE_KEY).ok()?)
+            .and_then(|json| serde_json::from_str::<Vec<(AchievementId, u32)>>(&json).ok());
+        for (id, hand) in stored.unwrap_or_default() {
+            if let Some(a) = manager.achievements.iter_mut().find(|a| a.id == id) {
+                a.unlocked = true;
+                a.unlocked_at_hand = Some(hand);
+            }
+        }
+        manager
+    }
+
+    /// Persist the unlocked achievements to `localStorage`.
+    pub fn save(&self) -> Result<(), String> {
+        let storage = PlayerProfile::storage().ok_or("localStorage is not available")?;
+        let unlocked: Vec<(AchievementId, u32)> = self
+            .achievements
+            .iter()
+            .filter_map(|a| Some((a.id, a.unlocked_at_hand?)))
+            .collect();
+        let json = serde_json::to_string(&unlocked).map_err(|e| e.to_string())?;
+        storage
+            .set_item(Self::STORAGE_KEY, &json)
+            .map_err(|e| format!("Failed to save achievements: {:?}", e))
+    }
+
+    /// Check the events that are new in `gs`; returns whether anything was unlocked.
+    pub fn observe(&mut self, gs: &GameStatePublic) -> bool {
+        let unlocked_before = self.toasts.len();
+        let new_events = new_events(&self.seen_log, &gs.action_log).to_vec();
+        for event in &new_events {
+            self.handle(event);
+        }
+        self.seen_log = gs.action_log.clone();
+        self.toasts.len() > unlocked_before
+    }
+
+    fn handle(&mut self, event: &ActionEvent) {
+        let me = self.player;
+        if let ActionEvent::GameAction(GameAction::DealtHole { player_id }) = event {
+            if *player_id == me {
+                if self.hand.take().is_some_and(|h| !h.won) {
+                    self.win_streak = 0;
+                }
+                self.hands += 1;
+                self.hand = Some(HandProgress::default());
+            }
+            return;
+        }
+        // Hands joined halfway through are not tracked
+        let Some(hand) = self.hand.as_mut() else {
+            return;
+        };
+        let mut unlocked = Vec::new();
+        match event {
+            ActionEvent::PlayerAction { player_id, action } if *player_id == me => match action {
+                ActionKind::AllIn { .. } => {
+                    hand.all_in = true;
+                    hand.reraised = hand.raiser;
+                }
+                ActionKind::Raise { .. } => hand.reraised = hand.raiser,
+                _ => {}
+            },
+            ActionEvent::PlayerAction { player_id, action } => match action {
+                ActionKind::Bet(_) | ActionKind::Raise { .. } | ActionKind::AllIn { .. } => {
+                    hand.raiser = Some(*player_id);
+                    hand.reraised = None;
+                }
+                ActionKind::Fold if hand.reraised == Some(*player_id) => {
+                    unlocked.push(AchievementId::CalledBluff);
+                }
+                _ => {}
+            },
+            ActionEvent::GameAction(GameAction::Showdown { hand_results }) => {
+                hand.straight_flush = hand_results.iter().any(|r| {
+                    r.player_id == me && r.rank.category == HandRankCategory::StraightFlush
+                });
+            }
+            ActionEvent::GameAction(GameAction::PotAwarded { winners, .. })
+                if winners.contains(&me) =>
+            {
+                if !hand.won {
+                    hand.won = true;
+                    self.win_streak += 1;
+                }
+                unlocked.push(AchievementId::FirstWin);
+                if hand.all_in {
+                    unlocked.push(AchievementId::SurvivedAllIn);
+                }
+                if hand.straight_flush {
+                    unlocked.push(AchievementId::StraightFlushWin);
+                }
+                if self.win_streak >= 5 {
+                    unlocked.push(AchievementId::FiveWinStreak);
+                }
+            }
+            _ => {}
+        }
+        for id in unlocked {
+            self.unlock(id);
+        }
+    }
+
+    fn unlock(&mut self, id: AchievementId) {
+        let hand = self.hands;
+        if let Some(a) = self
+            .achievements
+            .iter_mut()
+            .find(|a| a.id == id && !a.unlocked)
+        {
+            a.unlocked = true;
+            a.unlocked_at_hand = Some(hand);
+            self.toasts.push_back(id);
+        }
+    }
+}
+
+/// Events of `new` that were not in `old`. The log grows during a hand, may
+/// drop its oldest events when it is capped, and restarts with each hand.
+pub(crate) fn new_events<'a>(old: &[ActionEvent], new: &'a [ActionEvent]) -> &'a [ActionEvent] {
+    let overlap = (1..=old.len().min(new.len()))
+        .rev()
+        .find(|&k| old[old.len() - k..] == new[..k])
+        .unwrap_or(0);
+    &new[overlap..]
+}
+
+/// Chips won and lost at a cash table since the session started or the
+/// stats were last reset.
+#[derive(Clone, Debug)]
+pub struct SessionStats {
+    /// Player whose stack is followed, the one this client plays
+    pub player: PlayerId,
+    pub starting_stack: u32,
+    pub current_stack: u32,
+    pub hands_played: u32,
+    pub hands_won: u32,
+    pub biggest_win: u32,
+    pub biggest_loss: u32,
+    /// Whether `starting_stack` was taken from a state yet
+    started: bool,
+    /// Stack before the blinds of the hand in progress; hands joined halfway
+    /// through are not counted
+    hand_start_stack: Option<u32>,
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self {
+            player: PlayerId(0),
+            starting_stack: 0,
+            current_stack: 0,
+            hands_played: 0,
+            hands_won: 0,
+            biggest_win: 0,
+            biggest_loss: 0,
+            started: false,
+            hand_start_stack: None,
+        }
+    }
+}
+
+impl SessionStats {
+    /// Follow `player`'s stack in `gs`; hands are settled when they reach showdown.
+    pub fn observe(&mut self, gs: &GameStatePublic) {
+        let Some(me) = gs.players.iter().find(|p| p.id == self.player) else {
+            return;
+        };
+        self.current_stack = me.stack;
+        if !self.started {
+            self.starting_stack = me.stack + me.bet_this_round;
+            self.started = true;
+        }
+        if gs.stage == Stage::Showdown {
+            // Settle once; later states of the same showdown find no hand in progress
+            if let Some(start) = self.hand_start_stack.take() {
+                self.hands_played += 1;
+                if gs.winner_ids.contains(&self.player) {
+                    self.hands_won += 1;
+                }
+                if me.stack > start {
+                    self.biggest_win = self.biggest_win.max(me.stack - start);
+                } else {
+                    self.biggest_loss = self.biggest_loss.max(start - me.stack);
+                }
+            }
+        } else if gs.stage == Stage::Preflop
+            && self.hand_start_stack.is_none()
+            && me.cards.is_some()
+        {
+            self.hand_start_stack = Some(me.stack + me.bet_this_round);
+        }
+    }
+
+    /// Chips won (positive) or lost since the session started
+    pub fn net(&self) -> i64 {
+        i64::from(self.current_stack) - i64::from(self.starting_stack)
+    }
+
+    /// Share of the played hands that were won, once a hand was played
+    pub fn win_rate(&self) -> Option<f32> {
+        (self.hands_played > 0).then(|| self.hands_won as f32 / self.hands_played as f32)
+    }
+
+    /// Start a new session from the current stack.
+    pub fn reset(&mut self) {
+        *self = SessionStats {
+            player: self.player,
+            starting_stack: self.current_stack,
+            current_stack: self.current_stack,
+            started: true,
+            ..Default::default()
+        };
+    }
+}
+
+/// Design drawn on the back of face-down cards
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum CardBackDesign {
+    /// Solid color with a border
+    #[default]
+    Default,
+    Striped,
+    Dots,
+    /// `back.png` from the named card theme directory
+    Custom(String),
+}
 
 #[derive(Clone, Default, Debug)]
 pub struct ClientSettings {
     pub name: String,
     pub server_address: String,
+    /// Id of the selected card theme, see `ThemeRegistry`
+    pub card_theme: String,
+    pub card_back: CardBackDesign,
+    /// Show the hand strength meter next to the own hole cards; off by default
+    pub show_hand_strength: bool,
+    /// Language of the UI strings, see `crate::i18n`
+    pub locale: Locale,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -21,44 +472,80 @@ pub enum ArticlesLoading {
     #[default]
     NotStarted,
     Loading,
-    Loaded(Vec<Post>),
+    Loaded(Vec<ArticleMeta>),
     Error(String),
 }
 
-#[derive(Clone, Debug)]
-pub struct PairingPlayer {
-    pub name: String,
-    pub paired: bool,
-}
-
-impl PairingPlayer {
-    pub fn new(name: &str) -> Self {
-        Self {
-            name: name.to_string(),
-            paired: false,
-        }
-    }
-}
-
 #[derive(Clone, Debug, Default)]
 pub struct GameSessionState {
     pub game_state: Option<GameStatePublic>,
+    /// `game_state` was restored from the `CachedSession` of a refreshed
+    /// page; cleared by the first state the server sends
+    pub restored: bool,
+    /// Table this client is seated at; `None` while browsing the lobby.
+    pub table_id: Option<TableId>,
+    /// Latest list of tables reported by the server.
+    pub tables: Vec<TableSummary>,
+    /// Rebuys offered between hands, as (min, max) chips by player
+    pub rebuy_offers: HashMap<PlayerId, (u32, u32)>,
+    /// Invite accepted on the pairing screen; the poker screen connects to
+    /// the invite's server and joins its table.
+    pub pending_invite: Option<PairingInvite>,
+    /// Table picked on another screen, e.g. the tournament bracket; the poker
+    /// screen joins it on the current server.
+    pub pending_table: Option<TableId>,
+    /// Server picked on another screen, e.g. the iroh discovery screen; the
+    /// poker screen fills it into its connection form.
+    pub pending_server_address: Option<String>,
+    /// Annotations of the last reviewed hand; the review window is open while set
+    pub hand_review: Option<Vec<GtoAnnotation>>,
+    /// Latest bracket of the tournament running on the server, if any
+    pub tournament: Option<TournamentState>,
 }
 
+/// Reconnect when the server has not sent a heartbeat for this long; it
+/// sends one every 15 seconds.
+pub const HEARTBEAT_TIMEOUT_MS: f64 = 45_000.0;
+
 #[derive(Clone, Debug, Default)]
 pub struct ConnectionState {
     pub connection_status: ConnectionStatus,
     pub pending_messages: VecDeque<Backend2FrontendMsg>,
+    /// Server clock minus local clock, from the last heartbeat
+    pub clock_offset_ms: f64,
+    /// Local time the last heartbeat arrived, or the connection was opened
+    pub last_heartbeat_ms: Option<f64>,
+    /// Version of a server with another major version than ours, until the
+    /// player dismisses the warning
+    pub incompatible_server: Option<String>,
+}
+
+impl ConnectionState {
+    /// Sync the clock to a heartbeat sent at `server_time_ms` and received at `local_ms`.
+    pub fn record_heartbeat(&mut self, server_time_ms: u64, local_ms: f64) {
+        self.clock_offset_ms = server_time_ms as f64 - local_ms;
+        self.last_heartbeat_ms = Some(local_ms);
+    }
+
+    /// Local time of a server timestamp, such as a deadline to display.
+    pub fn server_to_local_ms(&self, server_ms: u64) -> f64 {
+        server_ms as f64 - self.clock_offset_ms
+    }
+
+    /// Whether the server has been silent for longer than `HEARTBEAT_TIMEOUT_MS`.
+    pub fn heartbeat_overdue(&self, now_ms: f64) -> bool {
+        self.last_heartbeat_ms
+            .is_some_and(|last| now_ms - last > HEARTBEAT_TIMEOUT_MS)
+    }
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct UIState {
-    pub last_error: Option<String>,
-    pub last_info: Option<String>,
+    /// Raised while handling server messages; the app shows them as toasts
+    pub notifications: Vec<Notification>,
     pub articles: ArticlesLoading,
-    pub pairing_players: Vec<PairingPlayer>,
-    pub pairing_confirm_player: Option<String>,
-    pub pairing_confirm_action: Option<bool>,
+    /// Markdown bodies of articles fetched so far, by article id
+    pub article_bodies: HashMap<String, String>,
 }
 
 #[derive(Clone, Debug)]
@@ -67,6 +554,11 @@ pub struct ClientState {
     pub connection: ConnectionState,
     pub ui: UIState,
     pub settings: ClientSettings,
+    pub profile: PlayerProfile,
+    pub achievements: AchievementManager,
+    pub session_stats: SessionStats,
+    /// Socket to the server, shared by all screens
+    pub connection_pool: ConnectionPool,
 }
 
 impl Default for ClientState {
@@ -77,71 +569,434 @@ impl Default for ClientState {
 
 impl ClientState {
     pub fn new() -> Self {
+        let profile = PlayerProfile::load().unwrap_or_default();
         let default_settings = ClientSettings {
-            name: "Player".to_string(),
-            server_address: "127.0.0.1:3000".to_string(),
+            name: profile.name.clone(),
+            server_address: profile.preferred_server.clone(),
+            card_theme: crate::hardcoded_cards::DEFAULT_THEME.to_string(),
+            card_back: CardBackDesign::Default,
+            show_hand_strength: false,
+            locale: Locale::default(),
         };
 
-        let players = vec![
-            PairingPlayer::new("Alice"),
-            PairingPlayer::new("Bob"),
-            PairingPlayer::new("Charlie"),
-            PairingPlayer::new("David"),
-            PairingPlayer::new("Eve"),
-            PairingPlayer::new("Frank"),
-            PairingPlayer::new("Grace"),
-            PairingPlayer::new("Heidi"),
-            PairingPlayer::new("Ivan"),
-            PairingPlayer::new("Julia"),
-            PairingPlayer::new("Kevin"),
-            PairingPlayer::new("Laura"),
-            PairingPlayer::new("Michael"),
-            PairingPlayer::new("Natalie"),
-            PairingPlayer::new("Oscar"),
-            PairingPlayer::new("Patricia"),
-        ];
-
         ClientState {
             settings: default_settings,
-            session: GameSessionState { game_state: None },
+            profile,
+            achievements: AchievementManager::load(),
+            session_stats: SessionStats::default(),
+            session: GameSessionState::default(),
+            connection_pool: ConnectionPool::default(),
             connection: ConnectionState {
                 connection_status: ConnectionStatus::Disconnected,
                 pending_messages: VecDeque::new(),
+                clock_offset_ms: 0.0,
+                last_heartbeat_ms: None,
+                incompatible_server: None,
             },
             ui: UIState {
-                last_error: None,
-                last_info: None,
+                notifications: Vec::new(),
                 articles: ArticlesLoading::NotStarted,
-                pairing_players: players,
-                pairing_confirm_player: None,
-                pairing_confirm_action: None,
+                article_bodies: HashMap::new(),
             },
         }
     }
 
+    /// Show `message` as a toast, see `game::notifications`.
+    pub fn notify(&mut self, level: NotificationLevel, message: impl Into<String>) {
+        self.ui
+            .notifications
+            .push(Notification::new(level, message));
+    }
+
     pub fn queue_server_msg(&mut self, msg: Backend2FrontendMsg) {
         self.connection.pending_messages.push_back(msg);
     }
 
     pub fn dispatch_pending_messages(&mut self) {
+        let received = self.connection_pool.take_messages();
+        self.connection.pending_messages.extend(received);
         while let Some(msg) = self.connection.pending_messages.pop_front() {
             self.apply_server_msg(msg);
         }
+        for error in self.connection_pool.take_errors() {
+            self.notify(NotificationLevel::Error, error);
+            self.connection.connection_status = ConnectionStatus::Disconnected;
+            self.session.table_id = None;
+        }
+    }
+
+    /// The shared connection to `server_address`, opened if there is none to
+    /// that server yet. See `ConnectionPool::get_or_create`.
+    pub fn get_or_create_connection(
+        &mut self,
+        server_address: &str,
+        ctx: &egui::Context,
+    ) -> &RobustWebSocket {
+        self.connection_pool.get_or_create(server_address, ctx)
+    }
+
+    /// Send `msg` over the shared connection, if one is open.
+    pub fn send_msg(&self, msg: &Frontend2BackendMsg) {
+        self.connection_pool.send_msg(msg);
+    }
+
+    /// Close the shared connection and leave the table.
+    pub fn disconnect(&mut self) {
+        self.connection_pool.close();
+        self.connection.connection_status = ConnectionStatus::Disconnected;
+        self.session.table_id = None;
+        StateSerializer::clear();
+    }
+
+    /// Keep the state of the current table for after a page refresh.
+    fn cache_session(&self) {
+        let (Some(table_id), Some(game_state)) = (self.session.table_id, &self.session.game_state)
+        else {
+            return;
+        };
+        let session = CachedSession {
+            server_address: self.settings.server_address.clone(),
+            table_id,
+            game_state: game_state.clone(),
+        };
+        if let Err(e) = StateSerializer::save(&session) {
+            crate::sprintln!("{}", e);
+        }
+    }
+
+    fn check_achievements(&mut self) {
+        let Some(gs) = &self.session.game_state else {
+            return;
+        };
+        if self.achievements.observe(gs) {
+            if let Err(e) = self.achievements.save() {
+                crate::sprintln!("{}", e);
+            }
+        }
+    }
+
+    /// Rebuys are only offered between hands; drop them once the next hand is dealt.
+    fn expire_rebuy_offers(&mut self) {
+        let between_hands = self
+            .session
+            .game_state
+            .as_ref()
+            .is_some_and(|gs| gs.stage == Stage::Showdown);
+        if !between_hands {
+            self.session.rebuy_offers.clear();
+        }
     }
 
     pub fn apply_server_msg(&mut self, msg: Backend2FrontendMsg) {
         match msg {
             Backend2FrontendMsg::State(gs) => {
+                if !matches!(
+                    self.connection.connection_status,
+                    ConnectionStatus::Connected
+                ) {
+                    self.notify(NotificationLevel::Success, "Connected to server");
+                }
                 self.connection.connection_status = ConnectionStatus::Connected;
                 self.session.game_state = Some(gs.clone());
-                self.ui.last_error = None;
-                self.ui.last_info = None;
+                self.session.restored = false;
+                self.expire_rebuy_offers();
+                self.check_achievements();
+                self.session_stats.observe(&gs);
+                self.cache_session();
+            }
+            Backend2FrontendMsg::StateDiff(diff) => {
+                if self.session.restored {
+                    // Diffs follow a state from the server, not the cached one
+                    return;
+                }
+                if let Some(gs) = self.session.game_state.as_mut() {
+                    diff.apply_to(gs);
+                    self.session_stats.observe(gs);
+                }
+                self.expire_rebuy_offers();
+                self.check_achievements();
+                self.cache_session();
+            }
+            Backend2FrontendMsg::RebuyAvailable {
+                player_id,
+                min_amount,
+                max_amount,
+            } => {
+                self.session
+                    .rebuy_offers
+                    .insert(player_id, (min_amount, max_amount));
+            }
+            Backend2FrontendMsg::PlayerKicked(player_id) => {
+                let name = self.session.game_state.as_ref().map_or_else(
+                    || format!("Player {}", player_id),
+                    |gs| PlayerPublic::name_of(&gs.players, player_id),
+                );
+                self.notify(
+                    NotificationLevel::Warning,
+                    format!("{} was kicked, a bot takes over", name),
+                );
+            }
+            Backend2FrontendMsg::PlayerEliminated {
+                player_id,
+                final_rank,
+            } => {
+                if player_id == self.session_stats.player {
+                    // Back to the lobby, to watch a table or start a new game
+                    self.session.table_id = None;
+                    self.session.game_state = None;
+                    StateSerializer::clear();
+                    self.notify(
+                        NotificationLevel::Info,
+                        format!("You finished in place {}", final_rank),
+                    );
+                } else {
+                    let name = self.session.game_state.as_ref().map_or_else(
+                        || format!("Player {}", player_id),
+                        |gs| PlayerPublic::name_of(&gs.players, player_id),
+                    );
+                    self.notify(
+                        NotificationLevel::Info,
+                        format!("{} was eliminated in place {}", name, final_rank),
+                    );
+                }
             }
             Backend2FrontendMsg::Error(e) => {
-                self.ui.last_error = Some(e.clone());
+                self.notify(NotificationLevel::Error, e);
+            }
+            Backend2FrontendMsg::Welcome { server_version } => {
+                self.send_msg(&Frontend2BackendMsg::ClientVersion {
+                    version: CLIENT_VERSION.to_string(),
+                });
+                self.connection.incompatible_server =
+                    (!mcg_shared::versions_compatible(CLIENT_VERSION, &server_version))
+                        .then_some(server_version);
             }
             Backend2FrontendMsg::Pong => {}
+            // Handled by `RobustWebSocket` already
+            Backend2FrontendMsg::Ack { .. } => {}
+            Backend2FrontendMsg::Heartbeat { server_time_ms } => {
+                self.connection
+                    .record_heartbeat(server_time_ms, crate::utils::WasmTimer::now_ms());
+            }
+            Backend2FrontendMsg::PairingCode { invite_code, .. } => {
+                self.notify(
+                    NotificationLevel::Info,
+                    format!("Invite code: {}", invite_code),
+                );
+            }
             Backend2FrontendMsg::QrRes(_content) => {}
+            Backend2FrontendMsg::TableJoined(table_id) => {
+                self.session.table_id = Some(table_id);
+                // Claim our seat, the server only shows a connection the hole
+                // cards of the player it sends messages for
+                self.send_msg(&Frontend2BackendMsg::RequestState {
+                    player_id: self.session_stats.player,
+                });
+                // A restored state stays on screen until the table's state arrives
+                if !self.session.restored {
+                    self.session.game_state = None;
+                }
+                self.session_stats = SessionStats {
+                    player: self.session_stats.player,
+                    ..Default::default()
+                };
+                self.notify(
+                    NotificationLevel::Success,
+                    format!("Joined table {}", table_id),
+                );
+            }
+            Backend2FrontendMsg::Tables(tables) => {
+                self.session.tables = tables;
+            }
+            Backend2FrontendMsg::HandReview(annotations) => {
+                self.session.hand_review = Some(annotations);
+            }
+            Backend2FrontendMsg::ArenaHandComplete { .. } => {}
+            Backend2FrontendMsg::TournamentState(tournament) => {
+                self.session.tournament = Some(tournament);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::{BettingLimit, Card, HandRank, HandResult};
+
+    fn state(action_log: Vec<ActionEvent>) -> GameStatePublic {
+        GameStatePublic {
+            players: Vec::new(),
+            community: Vec::new(),
+            pot: 0,
+            sb: 5,
+            bb: 10,
+            to_act: PlayerId(0),
+            stage: Stage::Preflop,
+            winner_ids: Vec::new(),
+            action_log,
+            current_bet: 0,
+            min_raise: 0,
+            betting_limit: BettingLimit::NoLimit,
+            blind_level: None,
+            pot_equities: None,
+            pot_breakdown: Vec::new(),
+            dealer: None,
+            hand_number: 0,
+            hands_remaining: None,
         }
     }
+
+    fn dealt(player: usize) -> ActionEvent {
+        ActionEvent::game(GameAction::DealtHole {
+            player_id: PlayerId(player),
+        })
+    }
+
+    fn act(player: usize, action: ActionKind) -> ActionEvent {
+        ActionEvent::player(PlayerId(player), action)
+    }
+
+    fn won(player: usize) -> ActionEvent {
+        ActionEvent::game(GameAction::PotAwarded {
+            winners: vec![PlayerId(player)],
+            amount: 100,
+        })
+    }
+
+    fn unlocked(manager: &AchievementManager) -> Vec<AchievementId> {
+        manager
+            .achievements
+            .iter()
+            .filter(|a| a.unlocked)
+            .map(|a| a.id)
+            .collect()
+    }
+
+    #[test]
+    fn wins_unlock_achievements_once() {
+        let mut manager = AchievementManager::default();
+        let straight_flush = HandResult {
+            player_id: PlayerId(0),
+            rank: HandRank {
+                category: HandRankCategory::StraightFlush,
+                tiebreakers: vec![9],
+            },
+            best_five: [Card(0); 5],
+            lo_rank: None,
+        };
+        let mut log = vec![
+            dealt(0),
+            dealt(1),
+            act(0, ActionKind::AllIn { amount: 500 }),
+        ];
+        assert!(!manager.observe(&state(log.clone())));
+        log.push(ActionEvent::game(GameAction::Showdown {
+            hand_results: vec![straight_flush],
+        }));
+        log.push(won(0));
+        assert!(manager.observe(&state(log.clone())));
+        assert_eq!(
+            unlocked(&manager),
+            vec![
+                AchievementId::FirstWin,
+                AchievementId::StraightFlushWin,
+                AchievementId::SurvivedAllIn
+            ]
+        );
+        assert_eq!(manager.achievements[0].unlocked_at_hand, Some(1));
+
+        // The same state again unlocks nothing new
+        assert!(!manager.observe(&state(log)));
+        assert_eq!(manager.toasts.len(), 3);
+    }
+
+    #[test]
+    fn five_wins_in_a_row_unlock_the_streak() {
+        let mut manager = AchievementManager::default();
+        for hand in 0..5 {
+            // The deal starts left of the dealer button, which moves every hand
+            let (first, second) = if hand % 2 == 0 { (0, 1) } else { (1, 0) };
+            let log = vec![
+                dealt(first),
+                dealt(second),
+                act(1, ActionKind::Fold),
+                won(0),
+            ];
+            manager.observe(&state(log));
+            let streak = unlocked(&manager).contains(&AchievementId::FiveWinStreak);
+            assert_eq!(streak, hand == 4);
+        }
+    }
+
+    #[test]
+    fn reraising_a_raiser_off_the_pot_calls_the_bluff() {
+        let mut manager = AchievementManager::default();
+        let raise = |to| ActionKind::Raise { to, by: to / 2 };
+        let log = vec![
+            dealt(0),
+            dealt(1),
+            act(1, raise(40)),
+            act(0, raise(120)),
+            act(1, ActionKind::Fold),
+        ];
+        manager.observe(&state(log));
+        assert_eq!(unlocked(&manager), vec![AchievementId::CalledBluff]);
+    }
+
+    #[test]
+    fn only_events_missing_from_the_last_log_are_new() {
+        let old = [dealt(0), dealt(1), act(1, ActionKind::Check)];
+        let grown = [dealt(0), dealt(1), act(1, ActionKind::Check), won(0)];
+        assert_eq!(new_events(&old, &grown), &grown[3..]);
+        // A capped log drops its oldest events
+        let capped = [dealt(1), act(1, ActionKind::Check), won(0)];
+        assert_eq!(new_events(&old, &capped), &capped[2..]);
+        let next_hand = [dealt(0)];
+        assert_eq!(new_events(&grown, &next_hand), &next_hand[..]);
+    }
+
+    #[test]
+    fn session_stats_settle_hands_at_showdown() {
+        let at = |stage, stack, bet, winner: bool| {
+            let mut gs = state(Vec::new());
+            gs.stage = stage;
+            gs.players = vec![PlayerPublic {
+                id: PlayerId(0),
+                name: "You".to_string(),
+                stack,
+                cards: Some([Card(0), Card(1)]),
+                has_folded: false,
+                all_in: false,
+                bet_this_round: bet,
+                avatar_color: None,
+                bounty: None,
+                seat_number: 0,
+                must_post_blind: false,
+            }];
+            if winner {
+                gs.winner_ids = vec![PlayerId(0)];
+            }
+            gs
+        };
+        let mut stats = SessionStats::default();
+        // Joined on the flop: that hand is not counted
+        stats.observe(&at(Stage::Flop, 1000, 0, false));
+        stats.observe(&at(Stage::Showdown, 1000, 0, false));
+        assert_eq!(stats.hands_played, 0);
+
+        stats.observe(&at(Stage::Preflop, 990, 10, false));
+        stats.observe(&at(Stage::Showdown, 1200, 0, true));
+        stats.observe(&at(Stage::Showdown, 1200, 0, true));
+        stats.observe(&at(Stage::Preflop, 1195, 5, false));
+        stats.observe(&at(Stage::Showdown, 1100, 0, false));
+        assert_eq!((stats.hands_played, stats.hands_won), (2, 1));
+        assert_eq!((stats.biggest_win, stats.biggest_loss), (200, 100));
+        assert_eq!(stats.net(), 100);
+        assert_eq!(stats.win_rate(), Some(0.5));
+
+        stats.reset();
+        assert_eq!((stats.starting_stack, stats.net()), (1100, 0));
+        assert_eq!(stats.win_rate(), None);
+    }
 }
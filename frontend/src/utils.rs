@@ -2,6 +2,7 @@ use egui::{Context, FontFamily, FontId};
 use sha2::{Digest, Sha256};
 use std::char;
 use std::collections::HashSet;
+use wasm_bindgen::{JsCast, JsValue};
 
 #[cfg(feature = "console_error_panic_hook")]
 #[allow(dead_code)]
@@ -69,5 +70,113 @@ pub fn emoji_hash(data: &[u8], ctx: &Context) -> String {
     emoji_string
 }
 
+/// Trigger a browser download of `text` saved as `filename`.
+pub fn download_text_file(filename: &str, text: &str) -> Result<(), String> {
+    let window = web_sys::window().ok_or("no global window exists")?;
+    let document = window.document().ok_or("no document on window")?;
+
+    let parts = js_sys::Array::of1(&JsValue::from_str(text));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type("text/plain;charset=utf-8");
+    let blob = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options)
+        .map_err(|e| format!("Failed to create blob: {:?}", e))?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)
+        .map_err(|e| format!("Failed to create object URL: {:?}", e))?;
+
+    let anchor = document
+        .create_element("a")
+        .map_err(|e| format!("Failed to create link element: {:?}", e))?
+        .dyn_into::<web_sys::HtmlAnchorElement>()
+        .map_err(|_| "Created element is not an anchor".to_string())?;
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    anchor.click();
+
+    let _ = web_sys::Url::revoke_object_url(&url);
+    Ok(())
+}
+
+/// Source of wall-clock time in milliseconds since the Unix epoch.
+pub trait Clock {
+    fn now_ms(&self) -> f64;
+
+    /// Milliseconds passed since the timestamp `ms`.
+    fn elapsed_since(&self, ms: f64) -> f64 {
+        self.now_ms() - ms
+    }
+}
+
+/// Cross-platform timer: `std::time::Instant` is unavailable on wasm, so the
+/// browser clock is used there and `SystemTime` everywhere else.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WasmTimer;
+
+impl WasmTimer {
+    #[cfg(target_arch = "wasm32")]
+    pub fn now_ms() -> f64 {
+        js_sys::Date::now()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn now_ms() -> f64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64()
+            * 1000.0
+    }
+
+    pub fn elapsed_since(ms: f64) -> f64 {
+        Clock::elapsed_since(&WasmTimer, ms)
+    }
+
+    /// Wait until `now_ms()` reaches `target`. On wasm this is a busy-wait stub.
+    #[cfg(target_arch = "wasm32")]
+    pub async fn sleep_until_ms(target: f64) {
+        while Self::now_ms() < target {}
+    }
+
+    /// Wait until `now_ms()` reaches `target`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn sleep_until_ms(target: f64) {
+        let remaining = target - Self::now_ms();
+        if remaining > 0.0 {
+            tokio::time::sleep(std::time::Duration::from_secs_f64(remaining / 1000.0)).await;
+        }
+    }
+}
+
+impl Clock for WasmTimer {
+    fn now_ms(&self) -> f64 {
+        WasmTimer::now_ms()
+    }
+}
+
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct MockClock(Cell<f64>);
+
+    impl Clock for MockClock {
+        fn now_ms(&self) -> f64 {
+            self.0.get()
+        }
+    }
+
+    #[test]
+    fn elapsed_since_uses_the_injected_clock() {
+        let clock = MockClock(Cell::new(1_000.0));
+        assert_eq!(clock.elapsed_since(1_000.0), 0.0);
+        clock.0.set(1_250.5);
+        assert_eq!(clock.elapsed_since(1_000.0), 250.5);
+    }
+
+    #[test]
+    fn system_clock_is_after_the_epoch_and_monotonic_enough() {
+        let start = WasmTimer::now_ms();
+        assert!(start > 1_600_000_000_000.0);
+        assert!(WasmTimer::elapsed_since(start) >= 0.0);
+    }
+}
@@ -0,0 +1,73 @@
+//! Browser tests of the WASM entry points in `lib.rs`.
+//!
+//! Run with `wasm-pack test --headless --chrome --features wasm` from the
+//! `frontend/` directory, or `just test-wasm`.
+#![cfg(target_arch = "wasm32")]
+
+use frontend::{calculate_dpi_scale, dpi_scale_for, resize_canvas, sprintln, start};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+use web_sys::HtmlCanvasElement;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// A canvas attached to the page, like the one `index.html` provides.
+fn canvas() -> HtmlCanvasElement {
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .expect("the test page has a document");
+    let canvas = document
+        .create_element("canvas")
+        .expect("canvas elements can be created")
+        .dyn_into::<HtmlCanvasElement>()
+        .expect("a canvas element is an HtmlCanvasElement");
+    document
+        .body()
+        .expect("the test page has a body")
+        .append_child(&canvas)
+        .expect("the canvas can be attached");
+    canvas
+}
+
+#[wasm_bindgen_test]
+fn dpi_scale_is_in_a_sensible_range() {
+    let scale = calculate_dpi_scale();
+    assert!((0.5..=5.0).contains(&scale), "scale {}", scale);
+}
+
+#[wasm_bindgen_test]
+fn dpi_scale_follows_the_canvas_width() {
+    // 16:9 canvases at a device pixel ratio of 2, growing and shrinking again
+    let scale = |width: f32| dpi_scale_for(width, width * 9.0 / 16.0, 2.0);
+    let widths = [800.0, 1600.0, 2400.0, 3200.0, 1600.0];
+    let scales: Vec<f32> = widths.into_iter().map(scale).collect();
+    assert_eq!(scales, [1.0, 1.2, 1.4, 1.8, 1.2]);
+    // Denser displays scale up, within limits
+    assert_eq!(dpi_scale_for(800.0, 450.0, 3.0), 1.5);
+    assert_eq!(dpi_scale_for(800.0, 450.0, 8.0), 1.5);
+    assert_eq!(dpi_scale_for(800.0, 450.0, 1.0), 0.75);
+}
+
+#[wasm_bindgen_test]
+fn resize_canvas_works_before_and_after_start() {
+    resize_canvas(1600, 900);
+    assert!(start(canvas()).is_ok());
+    resize_canvas(800, 450);
+}
+
+#[wasm_bindgen_test]
+fn start_accepts_a_canvas() {
+    assert!(start(canvas()).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn start_can_be_called_again() {
+    // The logging and panic hook setup only happens once per page
+    assert!(start(canvas()).is_ok());
+    assert!(start(canvas()).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn sprintln_logs_to_the_console() {
+    sprintln!("logged from a test: {} + {} = {}", 1, 2, 1 + 2);
+}
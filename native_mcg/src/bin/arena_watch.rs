@@ -0,0 +1,122 @@
+//! Live leaderboard for a server running in arena mode.
+//!
+//! Usage:
+//! ```text
+//! arena_watch --server http://localhost:3000 --hands 500
+//! ```
+
+use std::collections::HashMap;
+
+use anyhow::Context;
+use clap::Parser;
+use futures_util::{SinkExt, StreamExt};
+use mcg_shared::{ArenaHandSummary, Backend2FrontendMsg, PlayerId};
+use native_mcg::transport::build_ws_url;
+use tokio_tungstenite::tungstenite::Message;
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "arena_watch", version, about = "Leaderboard of the bots playing in an MCG arena", long_about = None)]
+struct ArenaWatchCli {
+    /// Server address (e.g. `http://localhost:3000`)
+    #[arg(long, default_value = "http://localhost:3000")]
+    server: String,
+
+    /// Stop after this many hands (watches forever when omitted)
+    #[arg(long)]
+    hands: Option<u32>,
+}
+
+/// Totals of one bot over the hands watched so far
+#[derive(Debug, Clone, Default)]
+struct Standing {
+    name: String,
+    hands: u32,
+    hands_won: u32,
+    chips: i64,
+    vpip_hands: u32,
+}
+
+impl Standing {
+    fn vpip_pct(&self) -> f64 {
+        match self.hands {
+            0 => 0.0,
+            n => f64::from(self.vpip_hands) * 100.0 / f64::from(n),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Leaderboard {
+    standings: HashMap<PlayerId, Standing>,
+    hands: u32,
+}
+
+impl Leaderboard {
+    fn record(&mut self, summary: &ArenaHandSummary) {
+        self.hands += 1;
+        for p in &summary.players {
+            let standing = self.standings.entry(p.player_id).or_default();
+            standing.name.clone_from(&p.name);
+            standing.hands += 1;
+            standing.hands_won += u32::from(p.won);
+            standing.chips += p.chips_won;
+            standing.vpip_hands += u32::from(p.vpip);
+        }
+    }
+
+    fn print(&self, hand_number: u32) {
+        let mut rows: Vec<&Standing> = self.standings.values().collect();
+        rows.sort_by(|a, b| b.chips.cmp(&a.chips).then(a.name.cmp(&b.name)));
+
+        println!();
+        println!("Arena hand #{} ({} watched)", hand_number, self.hands);
+        println!(
+            "{:<16} {:>6} {:>10} {:>6}",
+            "Player", "Won", "Chips", "VPIP"
+        );
+        for s in rows {
+            println!(
+                "{:<16} {:>6} {:>+10} {:>5.1}%",
+                s.name,
+                s.hands_won,
+                s.chips,
+                s.vpip_pct()
+            );
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = ArenaWatchCli::parse();
+
+    let mut url = build_ws_url(&cli.server)?;
+    url.set_path("/arena");
+    let (ws_stream, _resp) = tokio_tungstenite::connect_async(url.as_str())
+        .await
+        .with_context(|| format!("connecting to {url}"))?;
+    let (mut write, mut read) = ws_stream.split();
+    println!("Watching arena at {url}");
+
+    let mut leaderboard = Leaderboard::default();
+    while let Some(frame) = read.next().await {
+        let txt = match frame? {
+            Message::Text(txt) => txt,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let Ok(Backend2FrontendMsg::ArenaHandComplete { hand_summary }) =
+            serde_json::from_str::<Backend2FrontendMsg>(&txt)
+        else {
+            continue;
+        };
+        leaderboard.record(&hand_summary);
+        leaderboard.print(hand_summary.hand_number);
+        if cli.hands.is_some_and(|n| leaderboard.hands >= n) {
+            break;
+        }
+    }
+
+    let _ = write.send(Message::Close(None)).await;
+    Ok(())
+}
@@ -0,0 +1,253 @@
+//! Headless poker bot that joins a table over WebSocket and plays automatically.
+//!
+//! Usage:
+//! ```text
+//! bot --server http://localhost:3000 --name "Player 2" --strategy call --hands 20
+//! ```
+
+use std::time::Duration;
+
+use anyhow::{bail, Context};
+use clap::Parser;
+use futures_util::{Sink, SinkExt, StreamExt};
+use mcg_shared::{
+    Backend2FrontendMsg, Frontend2BackendMsg, GameStatePublic, PlayerAction, Stage, TableId,
+    DEFAULT_TABLE_ID,
+};
+use native_mcg::bot::{BotContext, CallingBot, PotOddsBot, SimpleBot};
+use native_mcg::transport::build_ws_url;
+use rand::random;
+use tokio_tungstenite::tungstenite::{self, Message};
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "bot", version, about = "Automated poker player for MCG servers", long_about = None)]
+struct BotCli {
+    /// Server address (e.g. `http://localhost:3000` or `ws://host:3000/ws`)
+    #[arg(long, default_value = "http://localhost:3000")]
+    server: String,
+
+    /// Name of the seat the bot plays; must match a player at the table
+    #[arg(short, long, default_value = "Bot")]
+    name: String,
+
+    /// Table to join
+    #[arg(long, default_value_t = DEFAULT_TABLE_ID)]
+    table: TableId,
+
+    /// Decision strategy
+    #[arg(long, value_enum, default_value_t = StrategyKind::Random)]
+    strategy: StrategyKind,
+
+    /// Disconnect after this many hands reached showdown (plays forever when omitted)
+    #[arg(long)]
+    hands: Option<u32>,
+
+    /// Ask the server to deal the next hand after each showdown; enable it on
+    /// one bot per table when no other client advances hands
+    #[arg(long, default_value_t = false)]
+    deal_next: bool,
+
+    /// Average think time before each action (ms); the actual delay varies by ±50%
+    #[arg(long, default_value_t = 0)]
+    delay_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum StrategyKind {
+    /// Probabilistic bot used for server-side seats
+    Random,
+    /// Always check or call
+    Call,
+    /// Pot-odds based calling with balanced bet frequencies
+    Gto,
+}
+
+enum Strategy {
+    Random(SimpleBot),
+    Call(CallingBot),
+    Gto(PotOddsBot),
+}
+
+impl Strategy {
+    fn new(kind: StrategyKind) -> Self {
+        match kind {
+            StrategyKind::Random => Self::Random(SimpleBot::default()),
+            StrategyKind::Call => Self::Call(CallingBot),
+            StrategyKind::Gto => Self::Gto(PotOddsBot::default()),
+        }
+    }
+
+    fn decide_action(&self, context: &BotContext) -> PlayerAction {
+        match self {
+            Self::Random(bot) => bot.decide_action(context),
+            Self::Call(bot) => bot.decide_action(context),
+            Self::Gto(bot) => bot.decide_action(context),
+        }
+    }
+}
+
+/// Build the decision context for the seat at `idx` from a public state.
+fn bot_context(gs: &GameStatePublic, idx: usize) -> BotContext {
+    let me = &gs.players[idx];
+    BotContext {
+        stack: me.stack,
+        call_amount: gs.current_bet.saturating_sub(me.bet_this_round),
+        current_bet: gs.current_bet,
+        big_blind: gs.bb,
+        pot: gs.pot,
+        stage: gs.stage,
+        position: idx,
+        total_players: gs.players.len(),
+        // Opponent tendencies are only tracked on the server
+        opponents: Vec::new(),
+    }
+}
+
+fn think_time(delay_ms: u64) -> Duration {
+    if delay_ms == 0 {
+        return Duration::ZERO;
+    }
+    let jitter = random::<u64>() % (delay_ms + 1);
+    Duration::from_millis(delay_ms / 2 + jitter)
+}
+
+/// The state `msg` brings the table to, keeping `latest` as the base for the
+/// next diff; `None` for messages without a state.
+fn received_state(
+    msg: Backend2FrontendMsg,
+    latest: &mut Option<GameStatePublic>,
+) -> Option<GameStatePublic> {
+    match msg {
+        Backend2FrontendMsg::State(gs) => {
+            *latest = Some(gs.clone());
+            Some(gs)
+        }
+        Backend2FrontendMsg::StateDiff(diff) => {
+            let gs = latest.as_mut()?;
+            diff.apply_to(gs);
+            Some(gs.clone())
+        }
+        _ => None,
+    }
+}
+
+/// Send `msg` to the server as a JSON text frame.
+async fn send_json<S>(write: &mut S, msg: &Frontend2BackendMsg) -> anyhow::Result<()>
+where
+    S: Sink<Message, Error = tungstenite::Error> + Unpin,
+{
+    write
+        .send(Message::Text(serde_json::to_string(msg)?))
+        .await?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = BotCli::parse();
+    let strategy = Strategy::new(cli.strategy);
+
+    let ws_url = build_ws_url(&cli.server)?;
+    let (ws_stream, _resp) = tokio_tungstenite::connect_async(ws_url.as_str())
+        .await
+        .with_context(|| format!("connecting to {ws_url}"))?;
+    let (mut write, mut read) = ws_stream.split();
+
+    for msg in [
+        Frontend2BackendMsg::JoinTable {
+            table_id: cli.table,
+        },
+        Frontend2BackendMsg::Subscribe,
+    ] {
+        send_json(&mut write, &msg).await?;
+    }
+    println!(
+        "{} connected to {} (table #{}, strategy {:?})",
+        cli.name, ws_url, cli.table, cli.strategy
+    );
+
+    let mut hands_played = 0;
+    let mut last_stage: Option<Stage> = None;
+    // (hand, street, bet to match) of the decision already answered, so
+    // repeated copies of the same state do not trigger a second action. The
+    // action log is capped, so its length stops changing in long hands; the
+    // bot only gets to act again on a street after someone raised.
+    let mut last_decision: Option<(u32, Stage, u32)> = None;
+    let mut warned_unseated = false;
+    // The server only shows a connection the hole cards of the player it
+    // sends messages for, so the bot claims its seat once it finds it
+    let mut claimed_seat = false;
+    // Base for the state diffs the server sends between full states
+    let mut latest_state: Option<GameStatePublic> = None;
+
+    while let Some(frame) = read.next().await {
+        let txt = match frame? {
+            Message::Text(txt) => txt,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let msg = serde_json::from_str::<Backend2FrontendMsg>(&txt)?;
+        if let Backend2FrontendMsg::Error(e) = &msg {
+            if last_stage.is_none() {
+                bail!("server rejected the bot: {e}");
+            }
+            eprintln!("Server error: {e}");
+            continue;
+        }
+        let Some(gs) = received_state(msg, &mut latest_state) else {
+            continue;
+        };
+
+        if gs.stage == Stage::Showdown && last_stage != Some(Stage::Showdown) {
+            hands_played += 1;
+            println!("Hand {hands_played} finished");
+            if cli.hands.is_some_and(|n| hands_played >= n) {
+                break;
+            }
+            // The server only lets a connection deal as its own seat
+            let seat = gs.players.iter().find(|p| p.name == cli.name);
+            if let Some(me) = seat.filter(|_| cli.deal_next) {
+                tokio::time::sleep(think_time(cli.delay_ms)).await;
+                let next = Frontend2BackendMsg::NextHand { player_id: me.id };
+                send_json(&mut write, &next).await?;
+            }
+        }
+        last_stage = Some(gs.stage);
+
+        let Some(idx) = gs.players.iter().position(|p| p.name == cli.name) else {
+            if !warned_unseated {
+                eprintln!("No player named '{}' at this table; waiting", cli.name);
+                warned_unseated = true;
+            }
+            continue;
+        };
+        warned_unseated = false;
+
+        let me = &gs.players[idx];
+        if !claimed_seat {
+            claimed_seat = true;
+            let claim = Frontend2BackendMsg::RequestState { player_id: me.id };
+            send_json(&mut write, &claim).await?;
+            continue;
+        }
+        let decision = (gs.hand_number, gs.stage, gs.current_bet);
+        if gs.stage == Stage::Showdown || gs.to_act != me.id || last_decision == Some(decision) {
+            continue;
+        }
+        last_decision = Some(decision);
+
+        let action = strategy.decide_action(&bot_context(&gs, idx));
+        tokio::time::sleep(think_time(cli.delay_ms)).await;
+        println!("{:?}: {:?}", gs.stage, action);
+        let msg = Frontend2BackendMsg::Action {
+            table_id: cli.table,
+            player_id: me.id,
+            action,
+        };
+        send_json(&mut write, &msg).await?;
+    }
+
+    let _ = write.send(Message::Close(None)).await;
+    println!("{} left after {} hands", cli.name, hands_played);
+    Ok(())
+}
@@ -9,13 +9,16 @@ pub struct Cli {
 
     /// Transport to use and its address. Accepted forms:
     /// - Full URL starting with http:// or https:// (treated as HTTP)
-    ///   (e.g. --transport 'http://localhost:3000' or '--transport https://example.com')
+    ///   (e.g. `--transport http://localhost:3000` or `--transport https://example.com`)
     /// - Full URL starting with ws:// or wss:// (treated as WebSocket)
-    ///   (e.g. --transport 'ws://localhost:3000/ws' or '--transport wss://example.com/ws')
+    ///   (e.g. `--transport ws://localhost:3000/ws` or `--transport wss://example.com/ws`)
     /// - iroh prefix for Iroh peer ids:
     ///   - iroh:<PEER>            (e.g. --transport 'iroh:zb2...peerid...')
+    ///   - iroh://<PEER>          (as listed by the iroh discovery screen)
+    /// - Address starting with quic:// for a server started with --quic-port
+    ///   (e.g. `--transport quic://localhost:4433`)
     ///
-    /// Default: http://localhost:3000
+    /// Default: `http://localhost:3000`
     #[arg(long, default_value = "http://localhost:3000")]
     pub transport: TransportKind,
 
@@ -58,17 +61,20 @@ pub enum ActionKind {
     Fold,
     CheckCall,
     Bet,
+    AllIn,
 }
 
 /// Transport kind for the CLI. Each variant carries an address string:
-/// - Http(address)      : HTTP server base URL (e.g. http://host:port)
-/// - WebSocket(address) : WebSocket URL or HTTP base that will be converted (e.g. ws://host:port/ws or http://host:port)
+/// - Http(address)      : HTTP server base URL (e.g. `http://host:port`)
+/// - WebSocket(address) : WebSocket URL or HTTP base that will be converted (e.g. `ws://host:port/ws` or `http://host:port`)
 /// - Iroh(peer)         : Iroh peer id (z-base-32)
+/// - Quic(address)      : QUIC address of a local server (e.g. `quic://localhost:4433`)
 #[derive(Debug, Clone)]
 pub enum TransportKind {
     WebSocket(String),
     Http(String),
     Iroh { peer: Option<String> },
+    Quic(String),
 }
 
 impl std::str::FromStr for TransportKind {
@@ -82,30 +88,33 @@ impl std::str::FromStr for TransportKind {
 
         // Explicit iroh: prefix
         if lower == "iroh" {
-            return Ok(TransportKind::Iroh { peer: None });
+            return Ok(Self::Iroh { peer: None });
         }
         if lower.starts_with("iroh:") {
-            let peer = s["iroh:".len()..].trim();
+            // `iroh://PEER`, as shown by the discovery screen, works too
+            let peer = s["iroh:".len()..].trim_start_matches('/').trim();
             if peer.is_empty() {
                 return Err("iroh transport requires a peer id: --transport 'iroh:PEER'".into());
             }
-            return Ok(TransportKind::Iroh {
+            return Ok(Self::Iroh {
                 peer: Some(peer.to_string()),
             });
         }
 
         // Full URL forms: http(s) -> Http, ws(s) -> WebSocket
         if lower.starts_with("http://") || lower.starts_with("https://") {
-            return Ok(TransportKind::Http(s.to_string()));
+            return Ok(Self::Http(s.to_string()));
         }
         if lower.starts_with("ws://") || lower.starts_with("wss://") {
-            return Ok(TransportKind::WebSocket(s.to_string()));
+            return Ok(Self::WebSocket(s.to_string()));
+        }
+        if lower.starts_with("quic://") {
+            return Ok(Self::Quic(s.to_string()));
         }
 
         // No legacy prefixed forms supported anymore
         Err(format!(
-            "unknown transport '{}', expected forms: http(s)://URL, ws(s)://URL, or iroh:PEER",
-            s
+            "unknown transport '{s}', expected forms: http(s)://URL, ws(s)://URL, quic://HOST:PORT, or iroh:PEER"
         ))
     }
 }
@@ -113,10 +122,9 @@ impl std::str::FromStr for TransportKind {
 impl std::fmt::Display for TransportKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            TransportKind::WebSocket(addr) => write!(f, "{}", addr),
-            TransportKind::Http(addr) => write!(f, "{}", addr),
-            TransportKind::Iroh { peer: Some(peer) } => write!(f, "iroh:{}", peer),
-            TransportKind::Iroh { peer: None } => write!(f, "iroh"),
+            Self::WebSocket(addr) | Self::Http(addr) | Self::Quic(addr) => write!(f, "{addr}"),
+            Self::Iroh { peer: Some(peer) } => write!(f, "iroh:{peer}"),
+            Self::Iroh { peer: None } => write!(f, "iroh"),
         }
     }
 }
@@ -148,6 +156,19 @@ mod tests {
         assert!(matches!(i, TransportKind::Iroh { peer: Some(ref p) } if p == "zb2examplepeer"));
     }
 
+    #[test]
+    fn parse_quic() {
+        let q = TransportKind::from_str("quic://localhost:4433").expect("should parse quic");
+        assert!(matches!(q, TransportKind::Quic(ref a) if a == "quic://localhost:4433"));
+        assert_eq!(q.to_string(), "quic://localhost:4433");
+    }
+
+    #[test]
+    fn parse_iroh_url() {
+        let i = TransportKind::from_str("iroh://zb2examplepeer").expect("should parse iroh url");
+        assert!(matches!(i, TransportKind::Iroh { peer: Some(ref p) } if p == "zb2examplepeer"));
+    }
+
     #[test]
     fn parse_iroh_without_peer() {
         let i = TransportKind::from_str("iroh").expect("should parse bare iroh");
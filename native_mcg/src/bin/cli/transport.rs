@@ -3,32 +3,17 @@ use std::time::Duration;
 use anyhow::Context;
 use futures_util::{SinkExt, StreamExt};
 use tokio_tungstenite::tungstenite::Message;
-use url::Url;
 
 use mcg_shared::{Frontend2BackendMsg, Backend2FrontendMsg};
 
 use super::utils::MessagePrinter;
 
-/// Try to build a websocket URL from a base string (like "localhost:3000" or "http://host:3000")
-pub fn build_ws_url(base: &str) -> anyhow::Result<Url> {
-    let mut url = Url::parse(base).or_else(|_| Url::parse(&format!("http://{}", base)))?;
+use native_mcg::public::PublicInfo;
+use native_mcg::server::quic::QUIC_SERVER_NAME;
+pub use native_mcg::transport::build_ws_url;
+use native_mcg::transport::{connect_quic, recv_msg_on_uni, resolve_quic_addr, send_msg_on_uni};
 
-    match url.scheme() {
-        "http" => url.set_scheme("ws").ok(),
-        "https" => url.set_scheme("wss").ok(),
-        "ws" | "wss" => Some(()),
-        _ => None,
-    }
-    .ok_or_else(|| anyhow::anyhow!("Unsupported URL scheme: {}", url.scheme()))?;
-
-    // Force path to /ws
-    if url.path() != "/ws" {
-        url.set_path("/ws");
-    }
-    Ok(url)
-}
-
-/// Connect over websocket, send the provided ClientMsg and pass all responses to the printer until timeout.
+/// Connect over websocket, send the provided `ClientMsg` and pass all responses to the printer until timeout.
 pub async fn run_once_ws(
     ws_addr: &str,
     client_msg: Frontend2BackendMsg,
@@ -55,11 +40,11 @@ pub async fn run_once_ws(
             }
             Ok(Some(Ok(_other))) => { /* ignore */ }
             Ok(Some(Err(e))) => {
-                eprintln!("WebSocket error: {}", e);
+                eprintln!("WebSocket error: {e}");
                 break;
             }
-            Ok(None) => break, // socket closed
-            Err(_) => break,   // timeout
+            // Socket closed or timeout
+            Ok(None) | Err(_) => break,
         }
     }
 
@@ -117,7 +102,7 @@ pub async fn run_once_iroh(
     Ok(())
 }
 
-/// Write the provided ClientMsg as newline-delimited JSON to the given writer.
+/// Write the provided `ClientMsg` as newline-delimited JSON to the given writer.
 async fn send_client_msg_over_stream<W>(send: &mut W, client_msg: &Frontend2BackendMsg) -> anyhow::Result<()>
 where
     W: tokio::io::AsyncWrite + Unpin + Send,
@@ -130,8 +115,8 @@ where
     Ok(())
 }
 
-/// Read newline-delimited ServerMsg responses from `reader` until the timeout (ms)
-/// Returns the last ServerMsg received (if any) and leaves handling to the caller.
+/// Read newline-delimited `ServerMsg` responses from `reader` until the timeout (ms)
+/// Returns the last `ServerMsg` received (if any) and leaves handling to the caller.
 async fn read_iroh_responses_until_timeout<R>(
     reader: &mut R,
     wait_ms: u64,
@@ -152,7 +137,8 @@ where
         )
         .await
         {
-            Ok(Ok(0)) => break, // connection closed
+            // Connection closed or timeout
+            Ok(Ok(0)) | Err(_) => break,
             Ok(Ok(_)) => {
                 let trimmed = line.trim();
                 if trimmed.is_empty() {
@@ -163,18 +149,66 @@ where
                         printer.handle(&sm);
                     }
                     Err(_) => {
-                        eprintln!("Invalid JSON from iroh peer: {}", trimmed);
+                        eprintln!("Invalid JSON from iroh peer: {trimmed}");
                     }
                 }
             }
             Ok(Err(e)) => {
-                eprintln!("iroh read error: {}", e);
+                eprintln!("iroh read error: {e}");
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Certificate the local server published for its QUIC listener.
+fn load_quic_cert() -> anyhow::Result<quinn::rustls::pki_types::CertificateDer<'static>> {
+    let path = PublicInfo::default_path();
+    let cert_hex = PublicInfo::load(&path)?.quic_cert.ok_or_else(|| {
+        anyhow::anyhow!(
+            "no QUIC certificate found. start the server with --quic-port or ensure '{}' contains a 'quic_cert' value",
+            path.display()
+        )
+    })?;
+    let der = hex::decode(cert_hex.trim()).context("decoding QUIC certificate")?;
+    Ok(der.into())
+}
+
+/// Connect to the QUIC transport of a local server at `addr` (quic://host:port).
+pub async fn connect_quic_addr(addr: &str) -> anyhow::Result<quinn::Connection> {
+    let socket_addr = resolve_quic_addr(addr).await?;
+    connect_quic(socket_addr, QUIC_SERVER_NAME, load_quic_cert()?).await
+}
+
+/// Connect over QUIC, send the provided `ClientMsg` and pass all responses to the printer until timeout.
+pub async fn run_once_quic(
+    addr: &str,
+    client_msg: Frontend2BackendMsg,
+    wait_ms: u64,
+    printer: &mut MessagePrinter,
+) -> anyhow::Result<()> {
+    let conn = connect_quic_addr(addr).await?;
+    send_msg_on_uni(&conn, &client_msg).await?;
+
+    loop {
+        match tokio::time::timeout(
+            Duration::from_millis(wait_ms),
+            recv_msg_on_uni::<Backend2FrontendMsg>(&conn),
+        )
+        .await
+        {
+            Ok(Ok(sm)) => printer.handle(&sm),
+            Ok(Err(e)) => {
+                eprintln!("QUIC error: {e}");
                 break;
             }
             Err(_) => break, // timeout
         }
     }
 
+    conn.close(0u32.into(), b"done");
     Ok(())
 }
 
@@ -186,7 +220,7 @@ pub async fn run_once_http(
     printer: &mut MessagePrinter,
 ) -> anyhow::Result<()> {
     let client = reqwest::Client::new();
-    let url = format!("{}/api/message", base);
+    let url = format!("{base}/api/message");
 
     let response = tokio::time::timeout(Duration::from_millis(wait_ms), async {
         client.post(&url).json(&client_msg).send().await
@@ -1,6 +1,6 @@
 use std::io::IsTerminal;
 
-use mcg_shared::{GameStatePublic, PlayerConfig, Backend2FrontendMsg};
+use mcg_shared::{Backend2FrontendMsg, GameStatePublic, PlayerConfig};
 
 use native_mcg::pretty::{format_event_human, format_state_human, format_table_header};
 
@@ -18,7 +18,7 @@ pub struct MessagePrinter {
 }
 
 impl MessagePrinter {
-    pub fn new(json: bool, mode: DisplayMode) -> Self {
+    pub const fn new(json: bool, mode: DisplayMode) -> Self {
         Self {
             json,
             mode,
@@ -36,10 +36,92 @@ impl MessagePrinter {
                     DisplayMode::Incremental => self.print_incremental(gs),
                 }
             }
-            Backend2FrontendMsg::Error(e) => eprintln!("Server error: {}", e),
+            Backend2FrontendMsg::StateDiff(diff) => {
+                let Some(mut gs) = self.latest_state.take() else {
+                    return;
+                };
+                diff.apply_to(&mut gs);
+                match self.mode {
+                    DisplayMode::FullState => self.print_full_state(&gs),
+                    DisplayMode::Incremental => self.print_incremental(&gs),
+                }
+                self.latest_state = Some(gs);
+            }
+            Backend2FrontendMsg::Welcome { server_version } => {
+                println!("Server version {server_version}");
+                if !mcg_shared::versions_compatible(env!("CARGO_PKG_VERSION"), server_version) {
+                    eprintln!(
+                        "Warning: server version {} is incompatible with this client ({})",
+                        server_version,
+                        env!("CARGO_PKG_VERSION")
+                    );
+                }
+            }
+            Backend2FrontendMsg::Error(e) => eprintln!("Server error: {e}"),
             Backend2FrontendMsg::Pong => println!("Received pong"),
+            // The CLI does not re-send its messages, so there is nothing to acknowledge
+            Backend2FrontendMsg::Ack { .. } | Backend2FrontendMsg::Heartbeat { .. } => {}
             Backend2FrontendMsg::QrRes(inner) => {
-                println!("Qr Response: {:?}", inner);
+                println!("Qr Response: {inner:?}");
+            }
+            Backend2FrontendMsg::TableJoined(table_id) => println!("Joined table {table_id}"),
+            Backend2FrontendMsg::RebuyAvailable {
+                player_id,
+                min_amount,
+                max_amount,
+            } => println!(
+                "Player {player_id} may rebuy {min_amount} to {max_amount} chips before the next hand"
+            ),
+            Backend2FrontendMsg::PlayerKicked(player_id) => {
+                println!("Player {player_id} was kicked, a bot takes over");
+            }
+            Backend2FrontendMsg::PlayerEliminated {
+                player_id,
+                final_rank,
+            } => println!(
+                "Player {player_id} was eliminated in place {final_rank}"
+            ),
+            Backend2FrontendMsg::PairingCode {
+                node_id,
+                invite_code,
+            } => println!("Invite code {invite_code} (server node {node_id})"),
+            Backend2FrontendMsg::ArenaHandComplete { hand_summary } => {
+                let winners: Vec<&str> = hand_summary
+                    .players
+                    .iter()
+                    .filter(|p| p.won)
+                    .map(|p| p.name.as_str())
+                    .collect();
+                println!(
+                    "Arena hand {} won by {}",
+                    hand_summary.hand_number,
+                    winners.join(", ")
+                );
+            }
+            Backend2FrontendMsg::TournamentState(tournament) => {
+                let tables = tournament.rounds.last().map_or(0, |r| r.tables.len());
+                println!(
+                    "Tournament round {}: {} players left at {} tables",
+                    tournament.rounds.len(),
+                    tournament.remaining_players(),
+                    tables
+                );
+            }
+            Backend2FrontendMsg::HandReview(annotations) => {
+                for a in annotations {
+                    println!(
+                        "Player {} {:?}: {:?}, suggested {:?} ({:.1} chips)",
+                        a.player_id, a.stage, a.actual, a.suggested, a.ev_diff
+                    );
+                }
+            }
+            Backend2FrontendMsg::Tables(tables) => {
+                for t in tables {
+                    println!(
+                        "Table {}: {} players, blinds {}/{}",
+                        t.table_id, t.player_count, t.sb, t.bb
+                    );
+                }
             }
         }
     }
@@ -47,8 +129,8 @@ impl MessagePrinter {
     fn print_full_state(&self, gs: &GameStatePublic) {
         if self.json {
             match serde_json::to_string_pretty(gs) {
-                Ok(json_str) => println!("{}", json_str),
-                Err(e) => eprintln!("Failed to serialize state to JSON: {}", e),
+                Ok(json_str) => println!("{json_str}"),
+                Err(e) => eprintln!("Failed to serialize state to JSON: {e}"),
             }
         } else {
             let use_color = std::io::stdout().is_terminal();
@@ -59,8 +141,8 @@ impl MessagePrinter {
     fn print_incremental(&mut self, gs: &GameStatePublic) {
         if self.json {
             match serde_json::to_string_pretty(gs) {
-                Ok(json_str) => println!("{}", json_str),
-                Err(e) => eprintln!("Failed to serialize state to JSON: {}", e),
+                Ok(json_str) => println!("{json_str}"),
+                Err(e) => eprintln!("Failed to serialize state to JSON: {e}"),
             }
             return;
         }
@@ -70,7 +152,7 @@ impl MessagePrinter {
         if total < already {
             let use_color = std::io::stdout().is_terminal();
             let header = format_table_header(gs, gs.sb, gs.bb, use_color);
-            println!("{}", header);
+            println!("{header}");
             self.last_printed = total;
         } else if total > already {
             for e in gs.action_log.iter().skip(already) {
@@ -90,12 +172,16 @@ pub fn generate_demo_players(num_players: usize) -> Vec<PlayerConfig> {
         id: mcg_shared::PlayerId(0),
         name: format!("Huuman player {}", 1),
         is_bot: false,
+        avatar_color: None,
+        preferred_seat: None,
     });
     for i in 1..num_players {
         players.push(PlayerConfig {
             id: mcg_shared::PlayerId(i),
             name: format!("Player {}", i + 1),
             is_bot: true,
+            avatar_color: None,
+            preferred_seat: None,
         });
     }
     players
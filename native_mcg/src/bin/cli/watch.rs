@@ -2,15 +2,15 @@ use anyhow::Context;
 use futures_util::{SinkExt, StreamExt};
 use tokio_tungstenite::tungstenite::Message;
 
-use mcg_shared::{Frontend2BackendMsg, Backend2FrontendMsg};
+use mcg_shared::{Frontend2BackendMsg, Backend2FrontendMsg, PlayerId};
 
 use super::utils::{DisplayMode, MessagePrinter};
 
 fn announce_connection(json: bool, message: &str) {
     if json {
-        eprintln!("{}", message);
+        eprintln!("{message}");
     } else {
-        println!("{}", message);
+        println!("{message}");
     }
 }
 
@@ -24,7 +24,7 @@ pub async fn watch_ws(ws_addr: &str, json: bool) -> anyhow::Result<()> {
     let subscribe_txt = serde_json::to_string(&Frontend2BackendMsg::Subscribe)?;
     write.send(Message::Text(subscribe_txt)).await?;
 
-    announce_connection(json, &format!("Connected to WebSocket {}", ws_url));
+    announce_connection(json, &format!("Connected to WebSocket {ws_url}"));
 
     let mut printer = MessagePrinter::new(json, DisplayMode::Incremental);
     loop {
@@ -36,7 +36,7 @@ pub async fn watch_ws(ws_addr: &str, json: bool) -> anyhow::Result<()> {
             }
             Some(Ok(_other)) => { /* ignore non-text frames */ }
             Some(Err(e)) => {
-                eprintln!("WebSocket error: {}", e);
+                eprintln!("WebSocket error: {e}");
                 break;
             }
             None => break, // closed
@@ -46,18 +46,41 @@ pub async fn watch_ws(ws_addr: &str, json: bool) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Watch over a QUIC connection and print events as they arrive.
+pub async fn watch_quic(addr: &str, json: bool) -> anyhow::Result<()> {
+    let conn = super::transport::connect_quic_addr(addr).await?;
+    native_mcg::transport::send_msg_on_uni(&conn, &Frontend2BackendMsg::Subscribe).await?;
+
+    announce_connection(json, &format!("Connected to QUIC server {addr}"));
+
+    let mut printer = MessagePrinter::new(json, DisplayMode::Incremental);
+    loop {
+        match native_mcg::transport::recv_msg_on_uni::<Backend2FrontendMsg>(&conn).await {
+            Ok(sm) => printer.handle(&sm),
+            Err(e) => {
+                eprintln!("QUIC error: {e}");
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Implement a basic long-polling watcher over the HTTP API.
 pub async fn watch_http(base: &str, json: bool) -> anyhow::Result<()> {
     let client = reqwest::Client::new();
-    announce_connection(json, &format!("Polling HTTP endpoint {}", base));
+    announce_connection(json, &format!("Polling HTTP endpoint {base}"));
     let mut printer = MessagePrinter::new(json, DisplayMode::Incremental);
     loop {
         // Long-poll GET state with a 30s timeout
         match tokio::time::timeout(
             std::time::Duration::from_secs(30),
             client
-                .post(format!("{}/api/message", base))
-                .json(&Frontend2BackendMsg::RequestState)
+                .post(format!("{base}/api/message"))
+                .json(&Frontend2BackendMsg::RequestState {
+                    player_id: PlayerId(0),
+                })
                 .send(),
         )
         .await
@@ -68,7 +91,7 @@ pub async fn watch_http(base: &str, json: bool) -> anyhow::Result<()> {
                 }
             }
             Ok(Err(e)) => {
-                eprintln!("HTTP error: {}", e);
+                eprintln!("HTTP error: {e}");
                 break;
             }
             Err(_) => {
@@ -121,7 +144,7 @@ pub async fn watch_iroh(peer_uri: &str, json: bool) -> anyhow::Result<()> {
         send.flush().await?;
     }
 
-    announce_connection(json, &format!("Connected to Iroh peer {}", peer_uri));
+    announce_connection(json, &format!("Connected to Iroh peer {peer_uri}"));
 
     let mut reader = BufReader::new(recv);
 
@@ -140,11 +163,11 @@ pub async fn watch_iroh(peer_uri: &str, json: bool) -> anyhow::Result<()> {
                 if let Ok(sm) = serde_json::from_str::<Backend2FrontendMsg>(trimmed) {
                     printer.handle(&sm);
                 } else {
-                    eprintln!("Invalid JSON from iroh peer: {}", trimmed);
+                    eprintln!("Invalid JSON from iroh peer: {trimmed}");
                 }
             }
             Err(e) => {
-                eprintln!("iroh read error: {}", e);
+                eprintln!("iroh read error: {e}");
                 break;
             }
         }
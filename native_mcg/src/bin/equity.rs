@@ -0,0 +1,115 @@
+//! Monte Carlo equity calculator for hold'em hands.
+//!
+//! Usage:
+//! ```text
+//! equity AhKh QsQd -- 2c 7d Jh
+//! ```
+
+use anyhow::{bail, Context};
+use clap::Parser;
+use mcg_shared::Card;
+use native_mcg::poker::equity::{simulate_equity, EquityResult};
+use owo_colors::OwoColorize;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "equity", version, about = "Estimate hold'em equity by random runouts", long_about = None)]
+struct EquityCli {
+    /// Hole cards of each player, e.g. `AhKh QsQd`
+    #[arg(required = true, num_args = 2..)]
+    hands: Vec<String>,
+
+    /// Known board cards, given after `--`, e.g. -- 2c 7d Jh
+    #[arg(last = true)]
+    board: Vec<String>,
+
+    /// Number of random runouts to simulate
+    #[arg(long, default_value_t = 100_000)]
+    runouts: u32,
+
+    /// Seed for reproducible results
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Disable colored output
+    #[arg(long, default_value_t = false)]
+    no_color: bool,
+}
+
+/// Parse two concatenated cards like `AhKh` (separators are also accepted).
+fn parse_hole(text: &str) -> anyhow::Result<[Card; 2]> {
+    let chars: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if chars.len() != 4 {
+        bail!("expected two cards like 'AhKh', got '{text}'");
+    }
+    let first = chars[..2].iter().collect::<String>().parse()?;
+    let second = chars[2..].iter().collect::<String>().parse()?;
+    Ok([first, second])
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = EquityCli::parse();
+
+    let hands = cli
+        .hands
+        .iter()
+        .map(|h| parse_hole(h))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let board = cli
+        .board
+        .iter()
+        .map(|c| c.parse::<Card>())
+        .collect::<Result<Vec<_>, _>>()
+        .context("parsing board")?;
+
+    let mut rng = cli
+        .seed
+        .map_or_else(StdRng::from_os_rng, StdRng::seed_from_u64);
+    let results = simulate_equity(&hands, 0, &board, cli.runouts, &mut rng)?;
+
+    let board_text = if board.is_empty() {
+        "(preflop)".to_string()
+    } else {
+        board
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let runouts = results.first().map_or(0, EquityResult::runouts);
+    println!("Board: {board_text}  ({runouts} runouts)");
+
+    let header = format!(
+        "{:<6} {:>8} {:>8} {:>8} {:>8}",
+        "Hand", "Win", "Tie", "Lose", "Equity"
+    );
+    if cli.no_color {
+        println!("{header}");
+    } else {
+        println!("{}", header.bold());
+    }
+    let best = results
+        .iter()
+        .map(|r| r.pot_share)
+        .fold(f64::NEG_INFINITY, f64::max);
+    for (hole, r) in hands.iter().zip(&results) {
+        let equity = format!("{:>7.2}%", r.equity_pct());
+        // `best` is one of the shares, so the leader compares exactly equal
+        #[allow(clippy::float_cmp)]
+        let equity = if !cli.no_color && r.pot_share == best {
+            equity.green().bold().to_string()
+        } else {
+            equity
+        };
+        println!(
+            "{:<6} {:>7.2}% {:>7.2}% {:>7.2}% {}",
+            format!("{}{}", hole[0], hole[1]),
+            r.win_pct(),
+            r.tie_pct(),
+            r.loss_pct(),
+            equity
+        );
+    }
+    Ok(())
+}
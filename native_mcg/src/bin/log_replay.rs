@@ -0,0 +1,92 @@
+//! Replay a game log written by `mcg-server --log-file`.
+//!
+//! Usage:
+//! ```text
+//! log_replay game-log.jsonl --speed 4
+//! log_replay game-log.jsonl --instant --table 1
+//! ```
+
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::bail;
+use clap::Parser;
+use mcg_shared::TableId;
+use native_mcg::pretty::{format_event_human, format_state_human, format_table_header};
+use native_mcg::server::game_log::{read_game_log, GameLogEntry, GameLogRecord};
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "log_replay", version, about = "Replay a game log of an MCG server", long_about = None)]
+struct LogReplayCli {
+    /// Game log written with `--log-file`
+    path: PathBuf,
+
+    /// Playback speed relative to the original timing, e.g. 4 for four times as fast
+    #[arg(long, default_value_t = 1.0)]
+    speed: f64,
+
+    /// Print all entries without waiting between them
+    #[arg(long, default_value_t = false)]
+    instant: bool,
+
+    /// Only replay this table
+    #[arg(long)]
+    table: Option<TableId>,
+
+    /// Disable colored output
+    #[arg(long, default_value_t = false)]
+    no_color: bool,
+}
+
+fn print_entry(entry: &GameLogEntry, color: bool) {
+    match &entry.record {
+        GameLogRecord::HandStart { hand_number, state } => {
+            println!("[table {}] Hand #{}", entry.table_id, hand_number);
+            print!("{}", format_table_header(state, state.sb, state.bb, color));
+        }
+        GameLogRecord::Action { event, state } => {
+            println!(
+                "[table {}] {}",
+                entry.table_id,
+                format_event_human(event, &state.players, color)
+            );
+            println!("{}", format_state_human(state, color));
+        }
+        GameLogRecord::HandEnd { hand_number, .. } => {
+            println!(
+                "[table {}] Hand #{} finished\n",
+                entry.table_id, hand_number
+            );
+        }
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = LogReplayCli::parse();
+    if !(cli.speed.is_finite() && cli.speed > 0.0) {
+        bail!("--speed must be a positive number, got {}", cli.speed);
+    }
+    let color = !cli.no_color && std::io::stdout().is_terminal();
+
+    let entries: Vec<GameLogEntry> = read_game_log(&cli.path)?
+        .into_iter()
+        .filter(|e| cli.table.is_none_or(|t| t == e.table_id))
+        .collect();
+    if entries.is_empty() {
+        bail!("no entries to replay in '{}'", cli.path.display());
+    }
+
+    let mut previous_ms = entries[0].timestamp_ms;
+    for entry in &entries {
+        if !cli.instant {
+            // Gaps between log entries are far below 2^52 ms
+            #[allow(clippy::cast_precision_loss)]
+            let gap_ms = entry.timestamp_ms.saturating_sub(previous_ms) as f64 / cli.speed;
+            std::thread::sleep(Duration::from_secs_f64(gap_ms / 1000.0));
+        }
+        previous_ms = entry.timestamp_ms;
+        print_entry(entry, color);
+    }
+    Ok(())
+}
@@ -3,9 +3,12 @@ mod cli;
 use anyhow::anyhow;
 use clap::Parser;
 use cli::{generate_demo_players, Cli, Commands, DisplayMode, MessagePrinter, TransportKind};
-use mcg_shared::{Frontend2BackendMsg, PlayerAction};
+use mcg_shared::{Frontend2BackendMsg, PlayerAction, PlayerId};
 use native_mcg::public::PublicInfo;
 
+/// Seat of the CLI's human player in the demo games it starts
+const CLI_PLAYER: PlayerId = PlayerId(0);
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
@@ -17,144 +20,60 @@ async fn main() -> anyhow::Result<()> {
         _ => None,
     };
 
-    match cli.command {
-        Commands::State => {
-            let mut printer = MessagePrinter::new(cli.json, DisplayMode::FullState);
-            match &transport {
-                TransportKind::Iroh { .. } => {
-                    let peer = resolved_iroh_peer
-                        .as_ref()
-                        .ok_or_else(|| anyhow!("iroh node id unavailable"))?;
-                    cli::run_once_iroh(peer, Frontend2BackendMsg::RequestState, cli.wait_ms, &mut printer)
-                        .await?
-                }
-                TransportKind::Http(addr) => {
-                    cli::run_once_http(addr, Frontend2BackendMsg::RequestState, cli.wait_ms, &mut printer)
-                        .await?
-                }
-                TransportKind::WebSocket(addr) => {
-                    cli::run_once_ws(addr, Frontend2BackendMsg::RequestState, cli.wait_ms, &mut printer)
-                        .await?
-                }
-            };
-        }
+    let msg = match cli.command {
+        Commands::State => Frontend2BackendMsg::RequestState {
+            player_id: CLI_PLAYER,
+        },
         Commands::Action { kind, amount } => {
             let pa = match kind {
                 cli::ActionKind::Fold => PlayerAction::Fold,
                 cli::ActionKind::CheckCall => PlayerAction::CheckCall,
                 cli::ActionKind::Bet => PlayerAction::Bet(amount),
+                cli::ActionKind::AllIn => PlayerAction::AllIn,
             };
-            let mut printer = MessagePrinter::new(cli.json, DisplayMode::FullState);
-            match &transport {
-                TransportKind::Iroh { .. } => {
-                    let peer = resolved_iroh_peer
-                        .as_ref()
-                        .ok_or_else(|| anyhow!("iroh node id unavailable"))?;
-                    cli::run_once_iroh(
-                        peer,
-                        Frontend2BackendMsg::Action {
-                            player_id: mcg_shared::PlayerId(0),
-                            action: pa,
-                        },
-                        cli.wait_ms,
-                        &mut printer,
-                    )
-                    .await?
-                }
-                TransportKind::Http(addr) => {
-                    cli::run_once_http(
-                        addr,
-                        Frontend2BackendMsg::Action {
-                            player_id: mcg_shared::PlayerId(0),
-                            action: pa,
-                        },
-                        cli.wait_ms,
-                        &mut printer,
-                    )
-                    .await?
-                }
-                TransportKind::WebSocket(addr) => {
-                    cli::run_once_ws(
-                        addr,
-                        Frontend2BackendMsg::Action {
-                            player_id: mcg_shared::PlayerId(0),
-                            action: pa,
-                        },
-                        cli.wait_ms,
-                        &mut printer,
-                    )
-                    .await?
-                }
-            };
-        }
-        Commands::NextHand => {
-            let mut printer = MessagePrinter::new(cli.json, DisplayMode::FullState);
-            match &transport {
-                TransportKind::Iroh { .. } => {
-                    let peer = resolved_iroh_peer
-                        .as_ref()
-                        .ok_or_else(|| anyhow!("iroh node id unavailable"))?;
-                    cli::run_once_iroh(peer, Frontend2BackendMsg::NextHand, cli.wait_ms, &mut printer).await?
-                }
-                TransportKind::Http(addr) => {
-                    cli::run_once_http(addr, Frontend2BackendMsg::NextHand, cli.wait_ms, &mut printer).await?
-                }
-                TransportKind::WebSocket(addr) => {
-                    cli::run_once_ws(addr, Frontend2BackendMsg::NextHand, cli.wait_ms, &mut printer).await?
-                }
-            };
-        }
-        Commands::NewGame => {
-            let players = generate_demo_players(3);
-            let msg = Frontend2BackendMsg::NewGame { players };
-            let mut printer = MessagePrinter::new(cli.json, DisplayMode::FullState);
-            match &transport {
-                TransportKind::Iroh { .. } => {
-                    let peer = resolved_iroh_peer
-                        .as_ref()
-                        .ok_or_else(|| anyhow!("iroh node id unavailable"))?;
-                    cli::run_once_iroh(peer, msg, cli.wait_ms, &mut printer).await?
-                }
-                TransportKind::Http(addr) => {
-                    cli::run_once_http(addr, msg, cli.wait_ms, &mut printer).await?
-                }
-                TransportKind::WebSocket(addr) => {
-                    cli::run_once_ws(addr, msg, cli.wait_ms, &mut printer).await?
-                }
-            };
+            Frontend2BackendMsg::Action {
+                table_id: mcg_shared::DEFAULT_TABLE_ID,
+                player_id: CLI_PLAYER,
+                action: pa,
+            }
         }
+        Commands::NextHand => Frontend2BackendMsg::NextHand {
+            player_id: CLI_PLAYER,
+        },
+        Commands::NewGame => Frontend2BackendMsg::NewGame {
+            player_id: CLI_PLAYER,
+            players: generate_demo_players(3),
+        },
         Commands::Watch => {
-            match &transport {
+            return match &transport {
                 TransportKind::Iroh { .. } => {
                     let peer = resolved_iroh_peer
                         .as_ref()
                         .ok_or_else(|| anyhow!("iroh node id unavailable"))?;
-                    cli::watch_iroh(peer, cli.json).await?
+                    cli::watch_iroh(peer, cli.json).await
                 }
-                TransportKind::Http(addr) => cli::watch_http(addr, cli.json).await?,
-                TransportKind::WebSocket(addr) => cli::watch_ws(addr, cli.json).await?,
+                TransportKind::Http(addr) => cli::watch_http(addr, cli.json).await,
+                TransportKind::WebSocket(addr) => cli::watch_ws(addr, cli.json).await,
+                TransportKind::Quic(addr) => cli::watch_quic(addr, cli.json).await,
             };
         }
-        Commands::Ping => {
-            let mut printer = MessagePrinter::new(cli.json, DisplayMode::FullState);
-            match &transport {
-                TransportKind::Iroh { .. } => {
-                    let peer = resolved_iroh_peer
-                        .as_ref()
-                        .ok_or_else(|| anyhow!("iroh node id unavailable"))?;
-                    cli::run_once_iroh(peer, Frontend2BackendMsg::Ping, cli.wait_ms, &mut printer).await?;
-                }
-                TransportKind::Http(addr) => {
-                    cli::run_once_http(addr, Frontend2BackendMsg::Ping, cli.wait_ms, &mut printer).await?;
-                }
-                TransportKind::WebSocket(addr) => {
-                    cli::run_once_ws(addr, Frontend2BackendMsg::Ping, cli.wait_ms, &mut printer).await?;
-                }
-            }
+        Commands::Ping => Frontend2BackendMsg::Ping,
+    };
+
+    let mut printer = MessagePrinter::new(cli.json, DisplayMode::FullState);
+    match &transport {
+        TransportKind::Iroh { .. } => {
+            let peer = resolved_iroh_peer
+                .as_ref()
+                .ok_or_else(|| anyhow!("iroh node id unavailable"))?;
+            cli::run_once_iroh(peer, msg, cli.wait_ms, &mut printer).await
+        }
+        TransportKind::Http(addr) => cli::run_once_http(addr, msg, cli.wait_ms, &mut printer).await,
+        TransportKind::WebSocket(addr) => {
+            cli::run_once_ws(addr, msg, cli.wait_ms, &mut printer).await
         }
+        TransportKind::Quic(addr) => cli::run_once_quic(addr, msg, cli.wait_ms, &mut printer).await,
     }
-
-    Ok(())
 }
 
 fn resolve_iroh_peer(peer: Option<String>) -> anyhow::Result<String> {
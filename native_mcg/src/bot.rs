@@ -3,9 +3,13 @@
 //! This module provides a clean interface for bot decision-making and action
 //! generation, separated from the backend state management concerns.
 
+mod stats;
+
+pub use stats::PlayerStats;
+
 use anyhow::Result;
-use mcg_shared::{PlayerAction, Stage};
-use rand::random;
+use mcg_shared::{PlayerAction, PlayerId, Stage};
+use rand::{random, random_range};
 
 /// Information about a bot player's current situation needed for decision making.
 #[derive(Debug, Clone)]
@@ -18,12 +22,16 @@ pub struct BotContext {
     pub current_bet: u32,
     /// Big blind amount
     pub big_blind: u32,
+    /// Chips in the pot
+    pub pot: u32,
     /// Current stage of the game
     pub stage: Stage,
     /// Bot's position/index in the game
     pub position: usize,
     /// Total number of players
     pub total_players: usize,
+    /// Session tendencies of the other players at the table
+    pub opponents: Vec<(PlayerId, PlayerStats)>,
 }
 
 /// Simple bot implementation using basic probabilistic decision making.
@@ -47,6 +55,7 @@ impl Default for SimpleBot {
 
 impl SimpleBot {
     /// Decide what action the bot should take given the current context.
+    #[must_use]
     pub fn decide_action(&self, context: &BotContext) -> PlayerAction {
         if context.call_amount == 0 {
             // No outstanding bet: decide whether to check or make an opening bet
@@ -56,15 +65,13 @@ impl SimpleBot {
             } else {
                 // 70% chance to make an opening bet of varying sizes
                 let bet_options = [
-                    context.big_blind,                       // Min bet
-                    context.big_blind * 2,                   // 2x big blind
-                    context.big_blind * 3,                   // 3x big blind
-                    (context.big_blind as f64 * 2.5) as u32, // 2.5x big blind
+                    context.big_blind,         // Min bet
+                    context.big_blind * 2,     // 2x big blind
+                    context.big_blind * 3,     // 3x big blind
+                    context.big_blind * 5 / 2, // 2.5x big blind
                 ];
 
-                let random_index = (random::<f32>() * bet_options.len() as f32) as usize;
-                let bet_amount =
-                    bet_options[random_index.min(bet_options.len() - 1)].min(context.stack);
+                let bet_amount = bet_options[random_range(..bet_options.len())].min(context.stack);
 
                 PlayerAction::Bet(bet_amount)
             }
@@ -75,11 +82,11 @@ impl SimpleBot {
             // There is a bet to call. Use probabilistic decision making:
             // Higher relative bet size -> more likely to fold
             let relative_bet =
-                context.call_amount as f64 / (context.stack + context.current_bet) as f64;
+                f64::from(context.call_amount) / f64::from(context.stack + context.current_bet);
 
             // Blend base fold chance with relative bet-based chance
-            let fold_chance = (self.base_fold_chance
-                + relative_bet * (1.0 - self.base_fold_chance))
+            let fold_chance = relative_bet
+                .mul_add(1.0 - self.base_fold_chance, self.base_fold_chance)
                 .min(self.max_fold_chance);
 
             if random::<f64>() < fold_chance {
@@ -92,9 +99,9 @@ impl SimpleBot {
                     // Choose a raise amount randomly
                     let remaining_after_call = context.stack - context.call_amount;
                     // Ensure minimum raise is at least the big blind to prevent Bet(0)
-                    let min_raise =
-                        ((context.current_bet as f64 * 0.5) as u32).max(context.big_blind);
-                    let max_raise = (context.current_bet as f64 * 1.5) as u32; // 1.5x pot maximum
+                    let min_raise = (context.current_bet / 2).max(context.big_blind);
+                    // 1.5x pot maximum
+                    let max_raise = context.current_bet.saturating_add(context.current_bet / 2);
 
                     let raise_options = [
                         min_raise,
@@ -104,8 +111,7 @@ impl SimpleBot {
                         remaining_after_call,                       // All-in
                     ];
 
-                    let random_index = (random::<f32>() * raise_options.len() as f32) as usize;
-                    let raise_amount = raise_options[random_index.min(raise_options.len() - 1)]
+                    let raise_amount = raise_options[random_range(..raise_options.len())]
                         .max(min_raise)
                         .min(remaining_after_call);
 
@@ -118,6 +124,89 @@ impl SimpleBot {
     }
 }
 
+/// Bot that never folds or raises: it checks when it can and calls any bet.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CallingBot;
+
+impl CallingBot {
+    #[must_use]
+    pub const fn decide_action(&self, _context: &BotContext) -> PlayerAction {
+        PlayerAction::CheckCall
+    }
+}
+
+/// Bot that weighs the price of a call against the pot.
+///
+/// Facing a bet it calls only when the call costs at most `max_price` of the
+/// resulting pot, otherwise it folds. With no bet outstanding it mixes checks
+/// with half-pot bets, betting more often against opponents that fold a lot.
+#[derive(Debug, Clone)]
+pub struct PotOddsBot {
+    /// Largest share of the final pot the bot is willing to pay to call (0.0 to 1.0)
+    pub max_price: f64,
+    /// Probability of betting when no bet is outstanding (0.0 to 1.0)
+    pub bet_frequency: f64,
+}
+
+/// Fold-to-continuation-bet rate the base bet frequency is tuned against
+const BASELINE_FOLD_TO_CBET: f64 = 50.0;
+
+impl Default for PotOddsBot {
+    fn default() -> Self {
+        Self {
+            max_price: 0.33,
+            bet_frequency: 0.35,
+        }
+    }
+}
+
+impl PotOddsBot {
+    /// Probability of betting into an unopened pot against these opponents.
+    pub fn bluff_frequency(&self, context: &BotContext) -> f64 {
+        // Only opponents with enough hands behind their numbers count
+        let folds: Vec<f64> = context
+            .opponents
+            .iter()
+            .filter_map(|(_, stats)| stats.fold_to_cbet_pct())
+            .map(f64::from)
+            .collect();
+        if folds.is_empty() {
+            return self.bet_frequency;
+        }
+        // There are never more opponents than seats
+        #[allow(clippy::cast_precision_loss)]
+        let avg_fold = folds.iter().sum::<f64>() / folds.len() as f64;
+
+        // A bet without a hand profits when opponents fold more often than the
+        // pot odds it lays them. Scale the baseline frequency by how far the
+        // table deviates from a 50% fold rate: against players who give up
+        // often we bluff more, against call stations that rarely fold we keep
+        // our bets for value and bluff less. The clamp keeps the bot from
+        // becoming fully predictable in either direction.
+        (self.bet_frequency * avg_fold / BASELINE_FOLD_TO_CBET).clamp(0.05, 0.8)
+    }
+
+    #[must_use]
+    pub fn decide_action(&self, context: &BotContext) -> PlayerAction {
+        if context.call_amount == 0 {
+            if random::<f64>() < self.bluff_frequency(context) && context.stack > 0 {
+                let bet = (context.pot / 2).max(context.big_blind).min(context.stack);
+                PlayerAction::Bet(bet)
+            } else {
+                PlayerAction::CheckCall
+            }
+        } else {
+            let call = context.call_amount.min(context.stack);
+            let price = f64::from(call) / f64::from(context.pot + call);
+            if price <= self.max_price {
+                PlayerAction::CheckCall
+            } else {
+                PlayerAction::Fold
+            }
+        }
+    }
+}
+
 /// Bot manager that handles bot decision-making and provides the interface
 /// between the backend state and bot AI implementations.
 ///
@@ -129,7 +218,8 @@ pub struct BotManager {
 }
 
 impl BotManager {
-    /// Create a new bot manager with the default SimpleBot AI.
+    /// Create a new bot manager with the default `SimpleBot` AI.
+    #[must_use]
     pub fn new() -> Self {
         Self {
             bot: SimpleBot::default(),
@@ -137,6 +227,10 @@ impl BotManager {
     }
 
     /// Generate a bot action given the current game context.
+    ///
+    /// # Errors
+    ///
+    /// Never; the `Result` leaves room for bots that cannot decide.
     pub fn generate_action(&self, context: &BotContext) -> Result<PlayerAction> {
         let action = self.bot.decide_action(context);
         tracing::debug!(
@@ -162,9 +256,11 @@ mod tests {
             call_amount: 0,
             current_bet: 0,
             big_blind: 10,
+            pot: 40,
             stage: Stage::Preflop,
             position: 0,
             total_players: 4,
+            opponents: Vec::new(),
         };
 
         // Run multiple times to test both check and bet behaviors
@@ -181,6 +277,8 @@ mod tests {
                     assert!(amount <= 1000);
                 }
                 PlayerAction::Fold => panic!("Bot should not fold with no bet to call"),
+                PlayerAction::AllIn => panic!("Bot should bet an amount, not go all-in"),
+                PlayerAction::PostBlind => panic!("Bot is dealt in and owes no blind"),
             }
         }
 
@@ -197,15 +295,72 @@ mod tests {
             call_amount: 100, // More than stack
             current_bet: 100,
             big_blind: 10,
+            pot: 40,
             stage: Stage::Flop,
             position: 1,
             total_players: 4,
+            opponents: Vec::new(),
         };
 
         let action = bot.decide_action(&context);
         assert!(matches!(action, PlayerAction::CheckCall));
     }
 
+    #[test]
+    fn pot_odds_bot_folds_to_overbets_and_calls_small_bets() {
+        let bot = PotOddsBot::default();
+        let mut context = BotContext {
+            stack: 1000,
+            call_amount: 200,
+            current_bet: 200,
+            big_blind: 10,
+            pot: 100,
+            stage: Stage::River,
+            position: 0,
+            total_players: 2,
+            opponents: Vec::new(),
+        };
+        assert!(matches!(bot.decide_action(&context), PlayerAction::Fold));
+
+        context.call_amount = 20;
+        context.current_bet = 20;
+        assert!(matches!(
+            bot.decide_action(&context),
+            PlayerAction::CheckCall
+        ));
+    }
+
+    #[test]
+    // Without opponent stats the configured frequency is returned unchanged
+    #[allow(clippy::float_cmp)]
+    fn pot_odds_bot_bluffs_more_against_frequent_folders() {
+        let bot = PotOddsBot::default();
+        let opponent = |folds| PlayerStats {
+            fold_to_cbet_opportunities: 20,
+            folds_to_cbet: folds,
+            ..Default::default()
+        };
+        let mut context = BotContext {
+            stack: 1000,
+            call_amount: 0,
+            current_bet: 0,
+            big_blind: 10,
+            pot: 100,
+            stage: Stage::Flop,
+            position: 0,
+            total_players: 2,
+            opponents: Vec::new(),
+        };
+        assert_eq!(bot.bluff_frequency(&context), bot.bet_frequency);
+
+        context.opponents = vec![(PlayerId(1), opponent(16))];
+        let vs_folder = bot.bluff_frequency(&context);
+        context.opponents = vec![(PlayerId(1), opponent(4))];
+        let vs_station = bot.bluff_frequency(&context);
+        assert!(vs_folder > bot.bet_frequency);
+        assert!(vs_station < bot.bet_frequency);
+    }
+
     #[test]
     fn bot_manager_generates_actions() {
         let manager = BotManager::new();
@@ -214,9 +369,11 @@ mod tests {
             call_amount: 20,
             current_bet: 20,
             big_blind: 10,
+            pot: 40,
             stage: Stage::Turn,
             position: 2,
             total_players: 3,
+            opponents: Vec::new(),
         };
 
         let result = manager.generate_action(&context);
@@ -0,0 +1,197 @@
+//! Per-player tendencies collected from finished hands.
+
+use std::collections::HashMap;
+
+use mcg_shared::{ActionEvent, ActionKind, GameAction, PlayerId, Stage};
+
+/// Number of observed opportunities below which a percentage is not reported
+const MIN_SAMPLE: u32 = 10;
+
+/// Counters for a player's betting tendencies across the session.
+///
+/// Each percentage is the number of times the player took the action divided
+/// by the number of times they had the chance to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlayerStats {
+    pub hands: u32,
+    /// Flops seen as the preflop aggressor with no bet in front
+    pub cbet_opportunities: u32,
+    pub cbets: u32,
+    /// Continuation bets faced before anyone raised them
+    pub fold_to_cbet_opportunities: u32,
+    pub folds_to_cbet: u32,
+    /// Preflop decisions facing exactly one raise
+    pub three_bet_opportunities: u32,
+    pub three_bets: u32,
+}
+
+// Counts of hands stay far below 2^24, where f32 starts to round integers
+#[allow(clippy::cast_precision_loss)]
+fn pct(made: u32, opportunities: u32) -> Option<f32> {
+    (opportunities >= MIN_SAMPLE).then(|| made as f32 * 100.0 / opportunities as f32)
+}
+
+impl PlayerStats {
+    /// How often the player continuation-bets the flop, in percent
+    #[must_use]
+    pub fn cbet_pct(&self) -> Option<f32> {
+        pct(self.cbets, self.cbet_opportunities)
+    }
+
+    /// How often the player folds to a continuation bet, in percent
+    #[must_use]
+    pub fn fold_to_cbet_pct(&self) -> Option<f32> {
+        pct(self.folds_to_cbet, self.fold_to_cbet_opportunities)
+    }
+
+    /// How often the player re-raises a single preflop raise, in percent
+    #[must_use]
+    pub fn three_bet_pct(&self) -> Option<f32> {
+        pct(self.three_bets, self.three_bet_opportunities)
+    }
+
+    /// Add the tendencies shown in one hand's action log to `stats`.
+    pub fn record_hand(stats: &mut HashMap<PlayerId, Self>, log: &[ActionEvent]) {
+        let mut stage = Stage::Preflop;
+        let mut seen: Vec<PlayerId> = Vec::new();
+        let mut preflop_raises = 0;
+        let mut aggressor: Option<PlayerId> = None;
+        let mut flop_bet_made = false;
+        let mut cbet_pending = false;
+        let mut answered_cbet: Vec<PlayerId> = Vec::new();
+
+        for event in log {
+            let (player_id, action) = match event {
+                ActionEvent::GameAction(GameAction::StageChanged(s)) => {
+                    stage = *s;
+                    continue;
+                }
+                ActionEvent::GameAction(GameAction::DealtHole { player_id }) => {
+                    if !seen.contains(player_id) {
+                        seen.push(*player_id);
+                    }
+                    continue;
+                }
+                ActionEvent::PlayerAction { player_id, action } => (*player_id, action),
+                ActionEvent::GameAction(_) => continue,
+            };
+            let aggressive = matches!(
+                action,
+                ActionKind::Bet(_) | ActionKind::Raise { .. } | ActionKind::AllIn { .. }
+            );
+            if matches!(action, ActionKind::PostBlind { .. }) {
+                continue;
+            }
+            let entry = stats.entry(player_id).or_default();
+
+            match stage {
+                Stage::Preflop => {
+                    if preflop_raises == 1 && aggressor != Some(player_id) {
+                        entry.three_bet_opportunities += 1;
+                        if aggressive {
+                            entry.three_bets += 1;
+                        }
+                    }
+                    if aggressive {
+                        preflop_raises += 1;
+                        aggressor = Some(player_id);
+                    }
+                }
+                Stage::Flop => {
+                    if !flop_bet_made && aggressor == Some(player_id) {
+                        entry.cbet_opportunities += 1;
+                        if aggressive {
+                            entry.cbets += 1;
+                            cbet_pending = true;
+                        }
+                    } else if cbet_pending && !answered_cbet.contains(&player_id) {
+                        entry.fold_to_cbet_opportunities += 1;
+                        if matches!(action, ActionKind::Fold) {
+                            entry.folds_to_cbet += 1;
+                        }
+                        answered_cbet.push(player_id);
+                        // A raise turns the pot into a new betting war; later
+                        // folds are to the raise, not the continuation bet
+                        if aggressive {
+                            cbet_pending = false;
+                        }
+                    }
+                    if aggressive {
+                        flop_bet_made = true;
+                    }
+                }
+                Stage::Turn | Stage::River | Stage::Showdown => {}
+            }
+        }
+
+        for id in seen {
+            stats.entry(id).or_default().hands += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn act(id: usize, action: ActionKind) -> ActionEvent {
+        ActionEvent::player(PlayerId(id), action)
+    }
+
+    fn stage(stage: Stage) -> ActionEvent {
+        ActionEvent::game(GameAction::StageChanged(stage))
+    }
+
+    #[test]
+    fn records_three_bets_cbets_and_folds_to_cbets() {
+        let log = vec![
+            ActionEvent::game(GameAction::DealtHole {
+                player_id: PlayerId(0),
+            }),
+            ActionEvent::game(GameAction::DealtHole {
+                player_id: PlayerId(1),
+            }),
+            ActionEvent::game(GameAction::DealtHole {
+                player_id: PlayerId(2),
+            }),
+            stage(Stage::Preflop),
+            act(0, ActionKind::Raise { to: 30, by: 20 }),
+            act(1, ActionKind::Call(30)),
+            act(2, ActionKind::Raise { to: 90, by: 60 }),
+            act(0, ActionKind::Call(60)),
+            act(1, ActionKind::Call(60)),
+            stage(Stage::Flop),
+            act(0, ActionKind::Check),
+            act(1, ActionKind::Check),
+            act(2, ActionKind::Bet(100)),
+            act(0, ActionKind::Fold),
+            act(1, ActionKind::Call(100)),
+        ];
+        let mut stats = HashMap::new();
+        PlayerStats::record_hand(&mut stats, &log);
+
+        let p0 = stats[&PlayerId(0)];
+        let p1 = stats[&PlayerId(1)];
+        let p2 = stats[&PlayerId(2)];
+        assert_eq!(p0.hands, 1);
+        assert_eq!((p1.three_bet_opportunities, p1.three_bets), (1, 0));
+        assert_eq!((p2.three_bet_opportunities, p2.three_bets), (1, 1));
+        // Player 0 faced the 3-bet, which is not a single raise
+        assert_eq!(p0.three_bet_opportunities, 0);
+        assert_eq!((p2.cbet_opportunities, p2.cbets), (1, 1));
+        assert_eq!((p0.fold_to_cbet_opportunities, p0.folds_to_cbet), (1, 1));
+        assert_eq!((p1.fold_to_cbet_opportunities, p1.folds_to_cbet), (1, 0));
+    }
+
+    #[test]
+    fn percentages_need_a_minimum_sample() {
+        let mut stats = PlayerStats {
+            fold_to_cbet_opportunities: 9,
+            folds_to_cbet: 9,
+            ..Default::default()
+        };
+        assert_eq!(stats.fold_to_cbet_pct(), None);
+        stats.fold_to_cbet_opportunities = 10;
+        assert_eq!(stats.fold_to_cbet_pct(), Some(90.0));
+    }
+}
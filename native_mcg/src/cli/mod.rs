@@ -9,7 +9,7 @@ pub struct ServerCli {
     #[arg(long, default_value = "mcg-server.toml")]
     pub config: PathBuf,
 
-    /// Iroh key as hex (overrides config.iroh_key)
+    /// Iroh key as hex (overrides `config.iroh_key`)
     #[arg(long)]
     pub iroh_key: Option<String>,
 
@@ -17,6 +17,22 @@ pub struct ServerCli {
     #[arg(long, default_value_t = false)]
     pub persist: bool,
 
+    /// Game rules TOML file for new games (overrides `config.game_config`)
+    #[arg(long, value_name = "PATH")]
+    pub game_config: Option<PathBuf>,
+
+    /// Append every hand and event to PATH as JSON lines (overrides `config.game_log`)
+    #[arg(long, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+
+    /// Also accept QUIC connections on this UDP port (overrides `config.quic_port`)
+    #[arg(long, value_name = "PORT")]
+    pub quic_port: Option<u16>,
+
+    /// Run a bots-only arena table that deals hands continuously (overrides config.arena)
+    #[arg(long, default_value_t = false)]
+    pub arena: bool,
+
     /// Enable verbose debug logging
     #[arg(long, short, default_value_t = false)]
     pub debug: bool,
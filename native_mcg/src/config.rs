@@ -1,4 +1,5 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use mcg_shared::{BettingLimit, BlindLevelInfo};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
@@ -7,21 +8,69 @@ use std::path::Path;
 ///
 /// Fields:
 /// - bots: number of bot players to start with
-/// - iroh_key: optional iroh key stored as hex string of 32 bytes
-/// - bot_delay: average bot acting delay in milliseconds (default: 200)
+/// - `iroh_key`: optional iroh key stored as hex string of 32 bytes
+/// - `bot_delay`: average bot acting delay in milliseconds (default: 200)
+/// - `min_buy_in` / `max_buy_in`: bounds on the chips added by one rebuy (default: 200 / 1000)
+/// - `admin_token`: optional bearer token for admin commands; unset disables them
+/// - arena: run a table of bots that deals hands continuously (default: false)
+/// - `game_config`: optional path to a `GameConfig` TOML file for new games
+/// - `game_log`: optional path of a JSON lines game log, see `server::game_log`
+/// - `quic_port`: optional UDP port of the QUIC transport, see `server::quic`
+/// - `next_hand_delay_ms`: pause before a reset policy deals the next hand (default: 3000)
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
     pub bots: usize,
     pub iroh_key: Option<String>,
     pub bot_delay: u64,
+    #[serde(default = "default_min_buy_in")]
+    pub min_buy_in: u32,
+    #[serde(default = "default_max_buy_in")]
+    pub max_buy_in: u32,
+    pub admin_token: Option<String>,
+    /// File every game event is appended to, if set.
+    pub event_log: Option<String>,
+    #[serde(default)]
+    pub arena: bool,
+    /// Rules for new games, see `GameConfig`. Built-in defaults when unset.
+    pub game_config: Option<String>,
+    /// File every hand and event is appended to as JSON lines, for replays.
+    pub game_log: Option<String>,
+    /// UDP port of the QUIC transport; disabled when unset.
+    #[serde(default)]
+    pub quic_port: Option<u16>,
+    /// Milliseconds between the end of a hand and the next one dealt by a
+    /// `GameResetPolicy`.
+    #[serde(default = "default_next_hand_delay_ms")]
+    pub next_hand_delay_ms: u64,
+}
+
+const fn default_min_buy_in() -> u32 {
+    200
+}
+
+const fn default_max_buy_in() -> u32 {
+    1000
+}
+
+const fn default_next_hand_delay_ms() -> u64 {
+    3000
 }
 
 impl Default for Config {
     fn default() -> Self {
-        Config {
+        Self {
             bots: 1,
             iroh_key: None,
             bot_delay: 200, // Increased from 100ms to 200ms for better UX during bot turns
+            min_buy_in: default_min_buy_in(),
+            max_buy_in: default_max_buy_in(),
+            admin_token: None,
+            event_log: None,
+            arena: false,
+            game_config: None,
+            game_log: None,
+            quic_port: None,
+            next_hand_delay_ms: default_next_hand_delay_ms(),
         }
     }
 }
@@ -29,11 +78,16 @@ impl Default for Config {
 impl Config {
     /// Load configuration from `path`. If the file does not exist, create it
     /// with reasonable defaults and return the default config.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read or parsed, or the default config cannot be
+    /// written.
     pub fn load_or_create(path: &Path) -> Result<Self> {
         if path.exists() {
             let s = fs::read_to_string(path)
                 .with_context(|| format!("reading config file '{}'", path.display()))?;
-            let cfg: Config = toml::from_str(&s)
+            let cfg: Self = toml::from_str(&s)
                 .with_context(|| format!("parsing TOML config '{}'", path.display()))?;
             Ok(cfg)
         } else {
@@ -46,7 +100,7 @@ impl Config {
                 }
             }
 
-            let cfg = Config::default();
+            let cfg = Self::default();
             //TODO: use ordinary context (probably)
             let toml_text = toml::to_string_pretty(&cfg)
                 .with_context(|| "serializing default config to TOML")?;
@@ -57,6 +111,10 @@ impl Config {
     }
 
     /// Save the current config state back to the provided path (overwrites).
+    ///
+    /// # Errors
+    ///
+    /// If the config cannot be serialized or written to `path`.
     pub fn save(&self, path: &Path) -> Result<()> {
         if let Some(parent) = path.parent() {
             if !parent.exists() {
@@ -72,6 +130,7 @@ impl Config {
     }
 
     /// Return iroh key bytes if present in config (hex-decoded).
+    #[must_use]
     pub fn iroh_key_bytes(&self) -> Option<Vec<u8>> {
         if let Some(ref s) = self.iroh_key {
             if let Ok(b) = hex::decode(s) {
@@ -82,22 +141,39 @@ impl Config {
     }
 
     /// Set iroh key from raw bytes and persist to disk (via save).
+    ///
+    /// # Errors
+    ///
+    /// If the config cannot be saved.
     pub fn set_iroh_key_bytes_and_save(&mut self, path: &Path, bytes: &[u8]) -> Result<()> {
         self.iroh_key = Some(hex::encode(bytes));
         self.save(path)?;
         Ok(())
     }
 
+    /// Whether `token` grants admin rights. Always false without a configured token.
+    #[must_use]
+    pub fn is_admin_token(&self, token: &str) -> bool {
+        self.admin_token
+            .as_deref()
+            .is_some_and(|t| !t.is_empty() && t == token)
+    }
+
     /// Calculate min/max delay range for bot actions based on the average delay.
     /// Returns a range of ±50% of the average delay to provide variation.
+    #[must_use]
     pub fn bot_delay_range(&self) -> (u64, u64) {
-        let min = (self.bot_delay as f64 * 0.5) as u64;
-        let max = (self.bot_delay as f64 * 1.5) as u64;
+        let min = self.bot_delay / 2;
+        let max = self.bot_delay.saturating_add(self.bot_delay / 2);
         (min.max(10), max.max(min)) // ensure minimum 10ms delay
     }
 
     /// Load (or create) config and optionally override with a CLI-provided `bots` value.
     /// If an override is applied, the config file will be updated on disk to reflect it.
+    ///
+    /// # Errors
+    ///
+    /// Like [`Config::load_or_create`], or if the override cannot be saved.
     #[allow(dead_code)]
     pub fn load_or_create_with_override(path: &Path, cli_bots: Option<usize>) -> Result<Self> {
         let mut cfg = Self::load_or_create(path)?;
@@ -112,3 +188,279 @@ impl Config {
         Ok(cfg)
     }
 }
+
+/// Poker variant dealt at a table
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameVariant {
+    /// Texas hold'em, the best high hand takes the pot
+    #[default]
+    Holdem,
+    /// Hold'em hi-lo, the pot is split with the best qualifying low hand
+    HiLo,
+}
+
+/// One level of the blind schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlindLevel {
+    pub sb: u32,
+    pub bb: u32,
+    /// Hands played at this level; the last level may omit it to last forever
+    pub hands: Option<u32>,
+}
+
+/// Whether and by how much short stacks may rebuy between hands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RebuyPolicy {
+    pub allowed: bool,
+    pub min_buy_in: u32,
+    pub max_buy_in: u32,
+}
+
+impl Default for RebuyPolicy {
+    fn default() -> Self {
+        Self {
+            allowed: true,
+            min_buy_in: default_min_buy_in(),
+            max_buy_in: default_max_buy_in(),
+        }
+    }
+}
+
+/// Rules for the games the server deals, loaded from a TOML file so operators
+/// can define games without a client choosing them. Templates live in
+/// `native_mcg/game-configs/`.
+///
+/// Fields missing from the file keep their defaults, which match a game
+/// created without a game config. In code, build one with [`GameConfigBuilder`]:
+///
+/// ```
+/// use native_mcg::config::{GameConfigBuilder, GameVariant};
+///
+/// let rules = GameConfigBuilder::default()
+///     .variant(GameVariant::Holdem)
+///     .starting_stack(1000)
+///     .blinds(5, 10)
+///     .player_count(6)
+///     .build()
+///     .unwrap();
+/// assert_eq!(rules.blinds_for_hand(1), (5, 10));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameConfig {
+    pub variant: GameVariant,
+    /// Largest number of players seated in one game; unlimited when unset
+    pub player_count: Option<usize>,
+    pub starting_stack: u32,
+    /// Blind levels in the order they are played, starting with the first hand
+    pub blind_schedule: Vec<BlindLevel>,
+    pub betting_limit: BettingLimit,
+    pub rebuy_policy: RebuyPolicy,
+    /// Knockout bounty placed on every player, if set
+    pub bounty: Option<u32>,
+    /// Number of hands a game lasts; unlimited when unset
+    pub hand_limit: Option<u32>,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            variant: GameVariant::Holdem,
+            player_count: None,
+            starting_stack: 1000,
+            blind_schedule: vec![BlindLevel {
+                sb: 5,
+                bb: 10,
+                hands: None,
+            }],
+            betting_limit: BettingLimit::NoLimit,
+            rebuy_policy: RebuyPolicy::default(),
+            bounty: None,
+            hand_limit: None,
+        }
+    }
+}
+
+impl GameConfig {
+    /// Load and validate a game config from the TOML file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read or parsed, or the config is not valid.
+    pub fn from_toml(path: &Path) -> Result<Self> {
+        let s = fs::read_to_string(path)
+            .with_context(|| format!("reading game config '{}'", path.display()))?;
+        let cfg: Self = toml::from_str(&s)
+            .with_context(|| format!("parsing TOML game config '{}'", path.display()))?;
+        cfg.validate()
+            .with_context(|| format!("invalid game config '{}'", path.display()))?;
+        Ok(cfg)
+    }
+
+    /// Check the rules for values a game cannot be dealt with, beyond what
+    /// the TOML types already enforce.
+    ///
+    /// # Errors
+    ///
+    /// With the first rule the config breaks.
+    pub fn validate(&self) -> Result<()> {
+        if self.player_count.is_some_and(|n| n < 2) {
+            bail!("player_count must be at least 2");
+        }
+        if self.starting_stack == 0 {
+            bail!("starting_stack must be positive");
+        }
+        if self.blind_schedule.is_empty() {
+            bail!("blind_schedule needs at least one level");
+        }
+        let last = self.blind_schedule.len() - 1;
+        for (i, level) in self.blind_schedule.iter().enumerate() {
+            let n = i + 1;
+            if level.sb == 0 || level.sb > level.bb {
+                bail!("blind level {n}: sb must be positive and at most bb");
+            }
+            if level.bb > self.starting_stack {
+                bail!("blind level {n}: bb is larger than starting_stack");
+            }
+            match level.hands {
+                Some(0) => bail!("blind level {n}: hands must be positive"),
+                None if i != last => bail!("blind level {n}: only the last level may omit hands"),
+                _ => {}
+            }
+        }
+        if let BettingLimit::FixedLimit { small_bet, big_bet } = self.betting_limit {
+            if small_bet == 0 || small_bet > big_bet {
+                bail!("fixed limit small_bet must be positive and at most big_bet");
+            }
+        }
+        let rebuy = &self.rebuy_policy;
+        if rebuy.allowed && (rebuy.min_buy_in == 0 || rebuy.min_buy_in > rebuy.max_buy_in) {
+            bail!("rebuy_policy min_buy_in must be positive and at most max_buy_in");
+        }
+        if self.bounty == Some(0) {
+            bail!("bounty must be positive when set");
+        }
+        if self.hand_limit == Some(0) {
+            bail!("hand_limit must be positive when set");
+        }
+        Ok(())
+    }
+
+    /// Small and big blind of the `hand_number`th hand of a game, counting from 1.
+    /// After the schedule runs out the last level stays in play.
+    #[must_use]
+    pub fn blinds_for_hand(&self, hand_number: u32) -> (u32, u32) {
+        let (idx, _) = self.level_of_hand(hand_number);
+        self.blind_schedule
+            .get(idx)
+            .map_or((5, 10), |l| (l.sb, l.bb))
+    }
+
+    /// Blind level of the `hand_number`th hand of a game, counting from 1.
+    #[must_use]
+    pub fn blind_level_for_hand(&self, hand_number: u32) -> BlindLevelInfo {
+        let (idx, hands_left) = self.level_of_hand(hand_number);
+        BlindLevelInfo {
+            level: u32::try_from(idx + 1).unwrap_or(u32::MAX),
+            hands_left,
+        }
+    }
+
+    /// Index into the schedule of the level `hand_number` is played at, and the
+    /// hands left at that level including this one; `None` on the last level.
+    fn level_of_hand(&self, hand_number: u32) -> (usize, Option<u32>) {
+        let last = self.blind_schedule.len().saturating_sub(1);
+        let mut first_hand_after = 1u32;
+        for (idx, level) in self.blind_schedule.iter().enumerate() {
+            let Some(hands) = level.hands else {
+                return (idx, None);
+            };
+            first_hand_after = first_hand_after.saturating_add(hands);
+            if hand_number < first_hand_after {
+                let hands_left = first_hand_after - hand_number;
+                return (idx, (idx != last).then_some(hands_left));
+            }
+        }
+        (last, None)
+    }
+}
+
+/// Builds a [`GameConfig`] one rule at a time, starting from the defaults,
+/// so adding a field to `GameConfig` does not break existing callers.
+#[derive(Debug, Clone, Default)]
+pub struct GameConfigBuilder {
+    config: GameConfig,
+}
+
+impl GameConfigBuilder {
+    #[must_use]
+    pub const fn variant(mut self, variant: GameVariant) -> Self {
+        self.config.variant = variant;
+        self
+    }
+
+    #[must_use]
+    pub const fn player_count(mut self, player_count: usize) -> Self {
+        self.config.player_count = Some(player_count);
+        self
+    }
+
+    #[must_use]
+    pub const fn starting_stack(mut self, starting_stack: u32) -> Self {
+        self.config.starting_stack = starting_stack;
+        self
+    }
+
+    /// Play every hand at the same small and big blind.
+    #[must_use]
+    pub fn blinds(self, sb: u32, bb: u32) -> Self {
+        self.blind_schedule(vec![BlindLevel {
+            sb,
+            bb,
+            hands: None,
+        }])
+    }
+
+    #[must_use]
+    pub fn blind_schedule(mut self, blind_schedule: Vec<BlindLevel>) -> Self {
+        self.config.blind_schedule = blind_schedule;
+        self
+    }
+
+    #[must_use]
+    pub const fn betting_limit(mut self, betting_limit: BettingLimit) -> Self {
+        self.config.betting_limit = betting_limit;
+        self
+    }
+
+    #[must_use]
+    pub const fn rebuy_policy(mut self, rebuy_policy: RebuyPolicy) -> Self {
+        self.config.rebuy_policy = rebuy_policy;
+        self
+    }
+
+    #[must_use]
+    pub const fn bounty(mut self, bounty: u32) -> Self {
+        self.config.bounty = Some(bounty);
+        self
+    }
+
+    #[must_use]
+    pub const fn hand_limit(mut self, hand_limit: u32) -> Self {
+        self.config.hand_limit = Some(hand_limit);
+        self
+    }
+
+    /// The config, if it passes [`GameConfig::validate`].
+    ///
+    /// # Errors
+    ///
+    /// If the config is not valid.
+    pub fn build(self) -> Result<GameConfig> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
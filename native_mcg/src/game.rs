@@ -1,14 +1,27 @@
 #![allow(clippy::module_inception)]
 //! Refactored game module. Implementation split across multiple files for clarity.
 //
+//!
+//
 //! Module ordering matters: declare utility/dealing/showdown before engine so
 //! engine can reference sibling modules via `super::...`.
 
 mod betting;
 mod dealing;
+mod deck;
 mod engine;
 mod flow;
+mod pots;
+#[cfg(test)]
+mod proptest;
 mod showdown;
-mod utils;
+mod table;
+#[cfg(feature = "undo")]
+mod undo;
 
+pub use betting::{BettingRound, RoundEvent};
+pub use deck::Deck;
 pub use engine::{Game, Player};
+pub use table::{PokerTable, Seat, TableError, MAX_SEATS};
+#[cfg(feature = "undo")]
+pub use undo::{GameSnapshot, UndoError};
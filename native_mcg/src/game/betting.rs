@@ -1,20 +1,55 @@
 //! Betting and action handling for Game.
+//!
+//! The chip arithmetic of a single street lives in [`BettingRound`], which
+//! only sees the acting player, so it can be tested and reused for other
+//! betting structures without a full `Game`.
 
-use crate::game::Game;
+use crate::game::{Game, Player};
 use anyhow::{bail, Result};
-use mcg_shared::{ActionEvent, ActionKind, PlayerAction};
-
-/// Compute the normalized add amount for an open bet (when current_bet == 0).
-/// Ensures the total bet is at least the big blind and not more than
-/// the player's total available chips (round contribution + stack).
-fn compute_open_bet_add(game: &Game, actor: usize, desired_total: u32) -> (u32, u32) {
-    let bet_to = desired_total
-        .max(game.bb)
-        .min(game.players[actor].stack + game.round_bets[actor]);
-    let add = bet_to
-        .saturating_sub(game.round_bets[actor])
-        .min(game.players[actor].stack);
-    (add, bet_to)
+use mcg_shared::{ActionEvent, ActionKind, BettingLimit, PlayerAction, Stage};
+use serde::{Deserialize, Serialize};
+
+/// Raises allowed per street in fixed-limit games
+pub const MAX_FIXED_LIMIT_RAISES: u32 = 4;
+
+/// Betting state of the current street.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BettingRound {
+    /// Seats that still need to act this street, in acting order
+    pub pending_to_act: Vec<usize>,
+    /// Contributions this street, indexed by seat
+    pub round_bets: Vec<u32>,
+    /// Highest contribution this street, which everyone has to match
+    pub current_bet: u32,
+    /// Smallest allowed raise increment; an opening bet must be at least this
+    pub min_raise: u32,
+    /// Chips put into the pot this street, blinds included
+    #[serde(default)]
+    pub pot_contribution: u32,
+    /// Seats that have neither folded nor gone all-in
+    #[serde(default)]
+    can_act: Vec<bool>,
+    /// Betting structure in force
+    #[serde(default)]
+    pub limit: BettingLimit,
+    /// Chips in the pot from earlier streets, counted for pot-limit bets
+    #[serde(default)]
+    pub pot_before: u32,
+    /// Size of every bet and raise this street in fixed-limit games
+    #[serde(default)]
+    pub fixed_bet: u32,
+    /// Raises made this street
+    #[serde(default)]
+    pub raises: u32,
+}
+
+/// Outcome of one action within a betting round.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoundEvent {
+    /// The action as resolved, e.g. an undersized raise becomes a call
+    pub kind: ActionKind,
+    /// Chips moved from the player's stack into the pot
+    pub paid: u32,
 }
 
 /// Internal outcome when attempting a raise over a non-zero current bet.
@@ -24,108 +59,362 @@ enum RaiseOutcome {
     Raise { add: u32, by: u32 },
 }
 
-/// Decide how to resolve a raise attempt over a non-zero current bet.
-fn decide_raise_outcome(
-    game: &Game,
-    actor: usize,
-    raise_by: u32,
-    prev_current_bet: u32,
-) -> RaiseOutcome {
-    let need = game.current_bet.saturating_sub(game.round_bets[actor]);
-    let target_to = game.current_bet + raise_by;
-    let required = target_to.saturating_sub(game.round_bets[actor]);
-    let add = required.min(game.players[actor].stack);
+impl BettingRound {
+    /// Open a street with `start_idx` acting first. Preflop the posted blinds
+    /// are passed by seat in `preflop_blind_bets`, and the full big blind has
+    /// to be called even when it was posted short. Later streets pass an
+    /// empty slice.
+    #[must_use]
+    pub fn start<'a>(
+        players: impl IntoIterator<Item = &'a Player>,
+        start_idx: usize,
+        preflop_blind_bets: &[u32],
+        big_blind: u32,
+    ) -> Self {
+        let can_act: Vec<bool> = players
+            .into_iter()
+            .map(|p| !p.has_folded && !p.all_in)
+            .collect();
+        let n = can_act.len();
+        let mut round_bets = vec![0; n];
+        for (bet, blind) in round_bets.iter_mut().zip(preflop_blind_bets) {
+            *bet = *blind;
+        }
+        let pending_to_act = (0..n)
+            .map(|i| (start_idx + i) % n)
+            .filter(|&idx| can_act[idx])
+            .collect();
+        let blinds_posted = preflop_blind_bets.iter().any(|&b| b > 0);
+        Self {
+            pending_to_act,
+            pot_contribution: round_bets.iter().sum(),
+            round_bets,
+            current_bet: if blinds_posted { big_blind } else { 0 },
+            min_raise: big_blind,
+            can_act,
+            ..Default::default()
+        }
+    }
 
-    if add <= need {
-        return RaiseOutcome::Call;
+    /// Apply the betting structure `limit` to this round on `stage`, with
+    /// `pot_before` chips already in the pot from earlier streets.
+    #[must_use]
+    pub const fn with_limit(mut self, limit: BettingLimit, stage: Stage, pot_before: u32) -> Self {
+        self.limit = limit;
+        self.pot_before = pot_before;
+        if let BettingLimit::FixedLimit { small_bet, big_bet } = limit {
+            self.fixed_bet = match stage {
+                Stage::Turn | Stage::River => big_bet,
+                _ => small_bet,
+            };
+            self.min_raise = self.fixed_bet;
+        }
+        self
     }
 
-    let required_add = prev_current_bet.saturating_sub(game.round_bets[actor]) + game.min_raise;
-    if add < required_add {
-        return RaiseOutcome::Call;
+    /// Round without any bets yet, e.g. for posting the blinds into.
+    #[must_use]
+    pub fn empty(seats: usize) -> Self {
+        Self {
+            round_bets: vec![0; seats],
+            ..Default::default()
+        }
     }
 
-    let new_to = game.round_bets[actor] + add;
-    let by = new_to.saturating_sub(prev_current_bet);
-    RaiseOutcome::Raise { add, by }
-}
+    /// Make room for a player who sat down at position `idx` and has not bet.
+    pub(crate) fn insert_seat(&mut self, idx: usize) {
+        for seat in &mut self.pending_to_act {
+            if *seat >= idx {
+                *seat += 1;
+            }
+        }
+        self.round_bets.insert(idx.min(self.round_bets.len()), 0);
+        self.can_act.insert(idx.min(self.can_act.len()), false);
+    }
 
-impl Game {
-    /// Helper to execute a check or call.
-    /// Handles stack updates, pot contribution, all-in detection, and logging.
-    fn do_call(&mut self, actor: usize) {
-        let need = self.current_bet.saturating_sub(self.round_bets[actor]);
-        if need == 0 {
-            self.log(ActionEvent::player(
-                mcg_shared::PlayerId(actor),
-                ActionKind::Check,
-            ));
-        } else {
-            let pay = need.min(self.players[actor].stack);
-            self.players[actor].stack -= pay;
-            self.round_bets[actor] += pay;
-            self.pot += pay;
-            // distinct from "pay < need" check elsewhere: if pay consumes entire stack, they are all-in?
-            // "pay < need" implies they didn't have enough to cover the bet.
-            // If they had exactly enough, stack becomes 0, are they all-in?
-            // Usually yes if stack is 0.
-            if self.players[actor].stack == 0 {
-                self.players[actor].all_in = true;
+    /// Forget the player who left position `idx`.
+    pub(crate) fn remove_seat(&mut self, idx: usize) {
+        self.pending_to_act.retain(|&seat| seat != idx);
+        for seat in &mut self.pending_to_act {
+            if *seat > idx {
+                *seat -= 1;
             }
-            self.log(ActionEvent::player(
-                mcg_shared::PlayerId(actor),
-                ActionKind::Call(pay),
-            ));
         }
+        if idx < self.round_bets.len() {
+            self.round_bets.remove(idx);
+        }
+        if idx < self.can_act.len() {
+            self.can_act.remove(idx);
+        }
+    }
+
+    /// Seat whose turn it is, if anyone still has to act.
+    #[must_use]
+    pub fn next_to_act(&self) -> Option<usize> {
+        self.pending_to_act.first().copied()
     }
 
-    fn execute_fold(&mut self, actor: usize) {
-        self.players[actor].has_folded = true;
-        self.log(ActionEvent::player(
-            mcg_shared::PlayerId(actor),
-            ActionKind::Fold,
-        ));
+    /// Chips `actor` has to add to match the current bet.
+    #[must_use]
+    pub fn to_call(&self, actor: usize) -> u32 {
+        self.current_bet.saturating_sub(self.round_bets[actor])
+    }
+
+    /// Apply `action` by the player in seat `actor`, moving chips from their
+    /// stack and updating who still has to act. Turn order and whether the
+    /// player may act at all are checked by the caller, who also handles
+    /// `PlayerAction::PostBlind`, which is no betting action.
+    pub fn apply_action(
+        &mut self,
+        actor: usize,
+        action: &PlayerAction,
+        player: &mut Player,
+    ) -> RoundEvent {
+        let prev_current_bet = self.current_bet;
+        let event = match *action {
+            PlayerAction::Fold => {
+                player.has_folded = true;
+                RoundEvent {
+                    kind: ActionKind::Fold,
+                    paid: 0,
+                }
+            }
+            PlayerAction::CheckCall => self.call(actor, player),
+            PlayerAction::Bet(x) => self.bet(actor, x, player),
+            PlayerAction::AllIn if self.limit == BettingLimit::NoLimit => {
+                self.all_in(actor, player)
+            }
+            PlayerAction::AllIn => self.limited_all_in(actor, player),
+            PlayerAction::PostBlind => {
+                unreachable!(
+                    "blinds to play are posted between hands, see `Game::post_blind_to_play`"
+                )
+            }
+        };
+
+        if player.has_folded || player.all_in {
+            self.can_act[actor] = false;
+        }
+        // A bet or raise makes everyone who has not matched it act again
+        if self.current_bet > prev_current_bet {
+            self.rebuild_pending_to_act(actor);
+        }
+        self.remove_from_pending(actor);
+        event
     }
 
-    fn execute_check_call(&mut self, actor: usize) {
-        self.do_call(actor);
+    /// A betting round ends when no one is left to act for this street.
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        self.pending_to_act.is_empty()
     }
 
-    fn execute_bet(&mut self, actor: usize, amount: u32) {
-        let (add, _bet_to) = compute_open_bet_add(self, actor, amount);
-        self.players[actor].stack -= add;
-        self.round_bets[actor] += add;
-        self.pot += add;
+    /// Largest opening bet allowed by the betting structure.
+    fn limit_open_bet(&self, desired_total: u32) -> u32 {
+        match self.limit {
+            BettingLimit::NoLimit => desired_total,
+            BettingLimit::PotLimit => desired_total.min(self.pot_before + self.pot_contribution),
+            BettingLimit::FixedLimit { .. } => self.fixed_bet,
+        }
+    }
+
+    /// Largest raise allowed by the betting structure. A pot-limit raise may
+    /// be as large as the pot after calling.
+    fn limit_raise(&self, actor: usize, raise_by: u32) -> u32 {
+        match self.limit {
+            BettingLimit::NoLimit => raise_by,
+            BettingLimit::PotLimit => {
+                let pot_after_call = self.pot_before + self.pot_contribution + self.to_call(actor);
+                raise_by.min(pot_after_call)
+            }
+            BettingLimit::FixedLimit { .. } => self.fixed_bet,
+        }
+    }
+
+    /// Whether the street has reached the fixed-limit raise cap, after which
+    /// further raises are treated as calls.
+    const fn raise_capped(&self) -> bool {
+        matches!(self.limit, BettingLimit::FixedLimit { .. })
+            && self.raises >= MAX_FIXED_LIMIT_RAISES
+    }
+
+    /// Move `amount` from the player's stack into the round.
+    fn pay(&mut self, actor: usize, amount: u32, player: &mut Player) {
+        player.stack -= amount;
+        self.round_bets[actor] += amount;
+        self.pot_contribution += amount;
+        if player.stack == 0 {
+            player.all_in = true;
+        }
+    }
+
+    fn call(&mut self, actor: usize, player: &mut Player) -> RoundEvent {
+        let need = self.to_call(actor);
+        if need == 0 {
+            return RoundEvent {
+                kind: ActionKind::Check,
+                paid: 0,
+            };
+        }
+        let pay = need.min(player.stack);
+        self.pay(actor, pay, player);
+        RoundEvent {
+            kind: ActionKind::Call(pay),
+            paid: pay,
+        }
+    }
+
+    /// Open the betting (when `current_bet` == 0). The total bet is at least the
+    /// minimum raise and no more than the player's round contribution plus stack.
+    fn open_bet(&mut self, actor: usize, desired_total: u32, player: &mut Player) -> RoundEvent {
+        let bet_to = desired_total
+            .max(self.min_raise)
+            .min(player.stack + self.round_bets[actor]);
+        let add = bet_to
+            .saturating_sub(self.round_bets[actor])
+            .min(player.stack);
+        self.pay(actor, add, player);
         self.current_bet = self.round_bets[actor];
         self.min_raise = add;
-        if self.players[actor].stack == 0 {
-            self.players[actor].all_in = true;
+        RoundEvent {
+            kind: ActionKind::Bet(add),
+            paid: add,
         }
-        self.log(ActionEvent::player(
-            mcg_shared::PlayerId(actor),
-            ActionKind::Bet(add),
-        ));
     }
 
-    fn execute_raise(&mut self, actor: usize, add: u32, by: u32) {
-        self.players[actor].stack -= add;
-        self.round_bets[actor] += add;
-        self.pot += add;
+    fn raise(&mut self, actor: usize, add: u32, by: u32, player: &mut Player) -> RoundEvent {
+        self.pay(actor, add, player);
         self.current_bet = self.round_bets[actor];
         self.min_raise = by;
-        if self.players[actor].stack == 0 {
-            self.players[actor].all_in = true;
-        }
-        self.log(ActionEvent::player(
-            mcg_shared::PlayerId(actor),
-            ActionKind::Raise {
+        self.raises += 1;
+        RoundEvent {
+            kind: ActionKind::Raise {
                 to: self.current_bet,
                 by,
             },
-        ));
+            paid: add,
+        }
     }
 
+    /// Bet `x` chips when no one has bet yet, otherwise raise by `x`, within
+    /// the betting structure. Bets too small to raise become calls.
+    fn bet(&mut self, actor: usize, x: u32, player: &mut Player) -> RoundEvent {
+        if x == 0 {
+            return self.call(actor, player);
+        }
+        if self.current_bet == 0 {
+            let x = self.limit_open_bet(x);
+            return self.open_bet(actor, x, player);
+        }
+        if self.raise_capped() {
+            return self.call(actor, player);
+        }
+        match self.decide_raise_outcome(actor, self.limit_raise(actor, x), player) {
+            RaiseOutcome::Call => self.call(actor, player),
+            RaiseOutcome::Raise { add, by } => self.raise(actor, add, by, player),
+        }
+    }
+
+    /// Put the player's whole stack in. Short of a full raise it still raises
+    /// the bet to match, but leaves the minimum raise as it was.
+    fn all_in(&mut self, actor: usize, player: &mut Player) -> RoundEvent {
+        if player.stack == 0 {
+            return self.call(actor, player);
+        }
+        let amount = player.stack;
+        let prev_current_bet = self.current_bet;
+        self.pay(actor, amount, player);
+        let total = self.round_bets[actor];
+        if total > prev_current_bet {
+            let by = total - prev_current_bet;
+            if prev_current_bet == 0 {
+                self.min_raise = by;
+            } else if by >= self.min_raise {
+                self.min_raise = by;
+                self.raises += 1;
+            }
+            self.current_bet = total;
+        }
+        RoundEvent {
+            kind: ActionKind::AllIn { amount },
+            paid: amount,
+        }
+    }
+
+    /// All-in under a pot or fixed limit: the largest bet or raise allowed,
+    /// which only counts as all-in if it empties the stack.
+    fn limited_all_in(&mut self, actor: usize, player: &mut Player) -> RoundEvent {
+        let total = player.stack + self.round_bets[actor];
+        let bet = if self.current_bet == 0 {
+            total
+        } else {
+            total.saturating_sub(self.current_bet)
+        };
+        let event = self.bet(actor, bet, player);
+        if player.all_in && event.paid > 0 {
+            RoundEvent {
+                kind: ActionKind::AllIn { amount: event.paid },
+                paid: event.paid,
+            }
+        } else {
+            event
+        }
+    }
+
+    /// Decide how to resolve a raise attempt over a non-zero current bet.
+    fn decide_raise_outcome(&self, actor: usize, raise_by: u32, player: &Player) -> RaiseOutcome {
+        let need = self.to_call(actor);
+        let target_to = self.current_bet + raise_by;
+        let required = target_to.saturating_sub(self.round_bets[actor]);
+        let add = required.min(player.stack);
+
+        if add <= need {
+            return RaiseOutcome::Call;
+        }
+
+        let required_add = need + self.min_raise;
+        if add < required_add {
+            return RaiseOutcome::Call;
+        }
+
+        let new_to = self.round_bets[actor] + add;
+        let by = new_to.saturating_sub(self.current_bet);
+        RaiseOutcome::Raise { add, by }
+    }
+
+    /// Rebuild the list of players that still need to act this street.
+    /// Called when a bet or raise increases `current_bet`.
+    fn rebuild_pending_to_act(&mut self, actor: usize) {
+        let n = self.round_bets.len();
+        self.pending_to_act = (1..=n)
+            .map(|i| (actor + i) % n)
+            .filter(|&idx| self.can_act[idx] && self.round_bets[idx] < self.current_bet)
+            .collect();
+    }
+
+    fn remove_from_pending(&mut self, actor: usize) {
+        if let Some(pos) = self.pending_to_act.iter().position(|&i| i == actor) {
+            if !self.can_act[actor] || self.to_call(actor) == 0 {
+                self.pending_to_act.remove(pos);
+            }
+        }
+    }
+}
+
+impl Game {
+    // Actions arrive owned from messages and bots, so callers hand them over
+    /// Apply `action` of the player at `actor` and move the hand on.
+    ///
+    /// # Errors
+    ///
+    /// If it is not `actor`'s turn, `actor` cannot act any more or the action
+    /// is not allowed.
+    #[allow(clippy::needless_pass_by_value)]
     pub fn apply_player_action(&mut self, actor: usize, action: PlayerAction) -> Result<()> {
+        // Posted out of turn, between hands
+        if matches!(action, PlayerAction::PostBlind) {
+            return self.post_blind_to_play(actor);
+        }
         if actor != self.to_act {
             bail!("Not your turn");
         }
@@ -135,33 +424,221 @@ impl Game {
         if self.players[actor].all_in {
             bail!("You are all-in");
         }
+        #[cfg(feature = "undo")]
+        self.save_undo_snapshot();
 
-        let prev_current_bet = self.current_bet;
-        match action {
-            PlayerAction::Fold => {
-                self.execute_fold(actor);
+        let event = self
+            .round
+            .apply_action(actor, &action, &mut self.players[actor]);
+        self.pot += event.paid;
+        self.add_contribution(actor, event.paid);
+        tracing::info!(
+            player_id = %self.players[actor].id,
+            action = ?event.kind,
+            stage = ?self.stage,
+            pot = self.pot,
+            "player action applied"
+        );
+        self.log(ActionEvent::player(mcg_shared::PlayerId(actor), event.kind));
+
+        self.post_action_update()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::{Card, PlayerId};
+
+    fn players(stacks: &[u32]) -> Vec<Player> {
+        stacks
+            .iter()
+            .enumerate()
+            .map(|(i, &stack)| Player {
+                id: PlayerId(i),
+                name: format!("P{i}"),
+                stack,
+                cards: [Card(0), Card(1)],
+                has_folded: false,
+                all_in: false,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn raise_reopens_action_and_calls_close_the_round() {
+        let mut ps = players(&[1000, 1000, 1000]);
+        let mut round = BettingRound::start(&ps, 0, &[], 10);
+        assert_eq!(round.pending_to_act, vec![0, 1, 2]);
+
+        let ev = round.apply_action(0, &PlayerAction::CheckCall, &mut ps[0]);
+        assert_eq!(ev.kind, ActionKind::Check);
+        let ev = round.apply_action(1, &PlayerAction::Bet(30), &mut ps[1]);
+        assert_eq!(
+            ev,
+            RoundEvent {
+                kind: ActionKind::Bet(30),
+                paid: 30
             }
-            PlayerAction::CheckCall => {
-                self.execute_check_call(actor);
+        );
+        // Seat 0 already checked but has to respond to the bet
+        assert_eq!(round.pending_to_act, vec![2, 0]);
+
+        let ev = round.apply_action(2, &PlayerAction::Bet(60), &mut ps[2]);
+        assert_eq!(ev.kind, ActionKind::Raise { to: 90, by: 60 });
+        assert_eq!(round.pending_to_act, vec![0, 1]);
+        round.apply_action(0, &PlayerAction::Fold, &mut ps[0]);
+        let ev = round.apply_action(1, &PlayerAction::CheckCall, &mut ps[1]);
+        assert_eq!(
+            ev,
+            RoundEvent {
+                kind: ActionKind::Call(60),
+                paid: 60
             }
-            PlayerAction::Bet(x) => {
-                if x == 0 {
-                    self.execute_check_call(actor);
-                } else if self.current_bet == 0 {
-                    self.execute_bet(actor, x);
-                } else {
-                    match decide_raise_outcome(self, actor, x, prev_current_bet) {
-                        RaiseOutcome::Call => {
-                            self.execute_check_call(actor);
-                        }
-                        RaiseOutcome::Raise { add, by } => {
-                            self.execute_raise(actor, add, by);
-                        }
-                    }
-                }
+        );
+
+        assert!(round.is_complete());
+        assert_eq!(round.pot_contribution, 180);
+        assert!(ps[0].has_folded);
+        assert_eq!((ps[1].stack, ps[2].stack), (910, 910));
+    }
+
+    #[test]
+    fn short_stacks_go_all_in_and_leave_the_action() {
+        let mut ps = players(&[20, 990]);
+        let mut round = BettingRound::start(&ps, 0, &[5, 10], 10);
+        assert_eq!(round.current_bet, 10);
+
+        // Undersized raise is treated as a call
+        let ev = round.apply_action(0, &PlayerAction::Bet(3), &mut ps[0]);
+        assert_eq!(ev.kind, ActionKind::Call(5));
+        let ev = round.apply_action(1, &PlayerAction::Bet(100), &mut ps[1]);
+        assert_eq!(ev.kind, ActionKind::Raise { to: 110, by: 100 });
+        let ev = round.apply_action(0, &PlayerAction::CheckCall, &mut ps[0]);
+        assert_eq!(
+            ev,
+            RoundEvent {
+                kind: ActionKind::Call(15),
+                paid: 15
             }
+        );
+        assert!(ps[0].all_in);
+        assert!(round.is_complete());
+    }
+
+    #[test]
+    fn explicit_all_in_puts_the_whole_stack_in() {
+        let mut ps = players(&[347, 1000, 60]);
+        let mut round = BettingRound::start(&ps, 0, &[], 10);
+
+        let ev = round.apply_action(0, &PlayerAction::AllIn, &mut ps[0]);
+        assert_eq!(
+            ev,
+            RoundEvent {
+                kind: ActionKind::AllIn { amount: 347 },
+                paid: 347
+            }
+        );
+        assert!(ps[0].all_in);
+        assert_eq!((round.current_bet, round.min_raise), (347, 347));
+
+        // Short of a full raise, it only calls for less
+        let ev = round.apply_action(1, &PlayerAction::Bet(400), &mut ps[1]);
+        assert_eq!(ev.kind, ActionKind::Raise { to: 747, by: 400 });
+        let ev = round.apply_action(2, &PlayerAction::AllIn, &mut ps[2]);
+        assert_eq!(ev.kind, ActionKind::AllIn { amount: 60 });
+        assert_eq!((round.current_bet, round.min_raise), (747, 400));
+        assert!(round.is_complete());
+    }
+
+    #[test]
+    fn short_all_in_raise_keeps_the_minimum_raise() {
+        let mut ps = players(&[1000, 130, 1000]);
+        let mut round = BettingRound::start(&ps, 0, &[], 10);
+        round.apply_action(0, &PlayerAction::Bet(100), &mut ps[0]);
+
+        // 30 over the bet is less than the 100 minimum raise, but still goes in
+        let ev = round.apply_action(1, &PlayerAction::AllIn, &mut ps[1]);
+        assert_eq!(ev.kind, ActionKind::AllIn { amount: 130 });
+        assert_eq!((round.current_bet, round.min_raise), (130, 100));
+        assert_eq!(round.pending_to_act, vec![2, 0]);
+    }
+
+    #[test]
+    fn limited_all_in_is_capped_by_the_structure() {
+        let mut ps = players(&[1000, 50]);
+        let mut round = BettingRound::start(&ps, 0, &[], 10).with_limit(
+            BettingLimit::PotLimit,
+            Stage::Flop,
+            60,
+        );
+
+        let ev = round.apply_action(0, &PlayerAction::AllIn, &mut ps[0]);
+        assert_eq!(ev.kind, ActionKind::Bet(60));
+        assert!(!ps[0].all_in);
+        let ev = round.apply_action(1, &PlayerAction::AllIn, &mut ps[1]);
+        assert_eq!(ev.kind, ActionKind::AllIn { amount: 50 });
+    }
+
+    #[test]
+    fn pot_limit_caps_bets_and_raises_at_the_pot() {
+        let mut ps = players(&[1000, 1000, 1000]);
+        let mut round = BettingRound::start(&ps, 0, &[], 10).with_limit(
+            BettingLimit::PotLimit,
+            Stage::Flop,
+            60,
+        );
+
+        let ev = round.apply_action(0, &PlayerAction::Bet(500), &mut ps[0]);
+        assert_eq!(ev.kind, ActionKind::Bet(60));
+        // Pot after calling is 60 + 60 + 60, so the raise is at most 180
+        let ev = round.apply_action(1, &PlayerAction::Bet(900), &mut ps[1]);
+        assert_eq!(ev.kind, ActionKind::Raise { to: 240, by: 180 });
+        // Smaller raises stay untouched
+        let ev = round.apply_action(2, &PlayerAction::Bet(200), &mut ps[2]);
+        assert_eq!(ev.kind, ActionKind::Raise { to: 440, by: 200 });
+    }
+
+    #[test]
+    fn fixed_limit_uses_the_street_bet_size() {
+        let limit = BettingLimit::FixedLimit {
+            small_bet: 10,
+            big_bet: 20,
+        };
+        let mut ps = players(&[1000, 1000]);
+        let mut round = BettingRound::start(&ps, 0, &[], 10).with_limit(limit, Stage::Flop, 20);
+        let ev = round.apply_action(0, &PlayerAction::Bet(300), &mut ps[0]);
+        assert_eq!(ev.kind, ActionKind::Bet(10));
+        let ev = round.apply_action(1, &PlayerAction::Bet(1), &mut ps[1]);
+        assert_eq!(ev.kind, ActionKind::Raise { to: 20, by: 10 });
+
+        let mut round = BettingRound::start(&ps, 0, &[], 10).with_limit(limit, Stage::Turn, 60);
+        let ev = round.apply_action(0, &PlayerAction::Bet(5), &mut ps[0]);
+        assert_eq!(ev.kind, ActionKind::Bet(20));
+        let ev = round.apply_action(1, &PlayerAction::Bet(500), &mut ps[1]);
+        assert_eq!(ev.kind, ActionKind::Raise { to: 40, by: 20 });
+    }
+
+    #[test]
+    fn fixed_limit_caps_raises_per_street() {
+        let limit = BettingLimit::FixedLimit {
+            small_bet: 10,
+            big_bet: 20,
+        };
+        let mut ps = players(&[1000, 1000]);
+        let mut round =
+            BettingRound::start(&ps, 0, &[5, 10], 10).with_limit(limit, Stage::Preflop, 0);
+
+        for i in 0..MAX_FIXED_LIMIT_RAISES as usize {
+            let ev = round.apply_action(i % 2, &PlayerAction::Bet(10), &mut ps[i % 2]);
+            assert!(matches!(ev.kind, ActionKind::Raise { by: 10, .. }));
         }
+        assert_eq!(round.current_bet, 50);
 
-        self.post_action_update(actor, prev_current_bet)
+        // The fifth raise is only a call and closes the street
+        let ev = round.apply_action(0, &PlayerAction::Bet(10), &mut ps[0]);
+        assert_eq!(ev.kind, ActionKind::Call(10));
+        assert!(round.is_complete());
+        assert_eq!(round.round_bets, vec![50, 50]);
     }
 }
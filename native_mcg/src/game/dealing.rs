@@ -1,18 +1,22 @@
 //! Dealing and hand initialization helpers.
 
 use anyhow::{Context, Result};
-use rand::seq::SliceRandom;
-use std::collections::VecDeque;
 
-use mcg_shared::{ActionEvent, ActionKind, BlindKind, Card, GameAction};
+use mcg_shared::{ActionEvent, ActionKind, BlindKind, GameAction};
 
-use super::Game;
+#[cfg(test)]
+use mcg_shared::Card;
+
+use super::{Deck, Game};
 
 /// Public method on Game to start a new hand with a fresh shuffled deck.
 impl Game {
+    /// # Errors
+    ///
+    /// If the deck runs out of cards while dealing.
     pub fn start_new_hand(&mut self) -> Result<()> {
         // Shuffle fresh deck
-        let mut deck: Vec<Card> = (0..52).map(Card).collect();
+        let mut deck = Deck::from_standard_52();
         deck.shuffle(&mut rand::rng());
         start_new_hand_from_deck(self, deck).context("Failed to start new hand from shuffled deck")
     }
@@ -21,21 +25,43 @@ impl Game {
 /// Initialize a new hand using the provided deck order.
 /// This resets round state, deals hole cards, posts blinds and
 /// establishes the first player to act according to heads-up vs 3+ rules.
-pub(crate) fn start_new_hand_from_deck(g: &mut Game, deck: Vec<Card>) -> Result<()> {
-    g.deck = VecDeque::from(deck);
+/// Players who still have to post a blind to play sit the hand out.
+pub fn start_new_hand_from_deck(g: &mut Game, deck: Deck) -> Result<()> {
+    g.deck = deck;
+    g.hand_number += 1;
+    // Actions of earlier hands cannot be undone
+    #[cfg(feature = "undo")]
+    if let Some(stack) = g.undo_stack.as_mut() {
+        stack.clear();
+    }
+
+    // Late joiners only wait for their blind while two others can play
+    let waiting = g
+        .players
+        .iter()
+        .filter(|p| g.must_post_blind.contains(&p.id))
+        .count();
+    if g.players.len() - waiting < 2 {
+        g.must_post_blind.clear();
+    }
 
     // Deal hole cards
     let mut dealt_events = Vec::with_capacity(g.players.len());
     for p in &mut g.players {
         p.has_folded = false;
         p.all_in = false;
-        let c1 = g.deck.pop_front().ok_or_else(|| {
+        // Sitting out counts as having folded before the first card
+        if g.must_post_blind.contains(&p.id) {
+            p.has_folded = true;
+            continue;
+        }
+        let c1 = g.deck.deal_card().ok_or_else(|| {
             anyhow::anyhow!(
                 "Deck underflow while dealing hole card 1 to player {}",
                 p.id
             )
         })?;
-        let c2 = g.deck.pop_front().ok_or_else(|| {
+        let c2 = g.deck.deal_card().ok_or_else(|| {
             anyhow::anyhow!(
                 "Deck underflow while dealing hole card 2 to player {}",
                 p.id
@@ -50,38 +76,51 @@ pub(crate) fn start_new_hand_from_deck(g: &mut Game, deck: Vec<Card>) -> Result<
     // Reset table state
     g.community.clear();
     g.pot = 0;
+    g.contributions = vec![0; g.players.len()];
     g.stage = mcg_shared::Stage::Preflop;
-    g.current_bet = 0;
-    g.min_raise = g.bb;
-    g.round_bets = vec![0; g.players.len()];
+    // Blinds are posted into a fresh round; init_round_for_stage carries them over
+    g.round = super::BettingRound::empty(g.players.len());
     g.recent_actions.clear();
     g.winner_ids.clear();
+    g.pot_equities = None;
 
     // Emit dealing events now that borrowing finished (derive legacy LogEntry on public serialization)
+    g.logged_events += dealt_events.len();
     g.recent_actions.extend(dealt_events);
-    super::utils::cap_logs(g);
 
-    // Post blinds
+    // Post blinds, skipping the players who sit out
     let n = g.players.len();
-    if n > 1 {
-        // In heads-up, dealer posts SB and acts first preflop; otherwise SB=dealer+1, BB=dealer+2
-        let (sb_idx, bb_idx) = if n == 2 {
-            (g.dealer_idx, (g.dealer_idx + 1) % n)
-        } else {
-            ((g.dealer_idx + 1) % n, (g.dealer_idx + 2) % n)
-        };
+    let dealt_in: Vec<usize> = (0..n)
+        .map(|i| (g.dealer_idx + i) % n)
+        .filter(|&idx| !g.players[idx].has_folded)
+        .collect();
+    if dealt_in.len() > 1 {
+        // In heads-up, dealer posts SB and acts first preflop; otherwise the
+        // two players after the dealer post
+        let first = usize::from(dealt_in.len() > 2 && dealt_in[0] == g.dealer_idx);
+        let (sb_idx, bb_idx) = (dealt_in[first], dealt_in[first + 1]);
         post_blind(g, sb_idx, BlindKind::SmallBlind, g.sb);
         post_blind(g, bb_idx, BlindKind::BigBlind, g.bb);
-        g.current_bet = g.bb;
-        g.min_raise = g.bb;
+        // Players who posted to play put in a big blind of their own,
+        // unless it is their turn to post one anyway
+        for &idx in &dealt_in {
+            if idx != sb_idx && idx != bb_idx && g.posted_to_play.contains(&g.players[idx].id) {
+                post_blind(g, idx, BlindKind::BigBlind, g.bb);
+            }
+        }
         // Preflop first to act is left of BB
         g.to_act = (bb_idx + 1) % n;
     } else {
         g.to_act = g.dealer_idx;
     }
+    g.posted_to_play.clear();
 
     g.init_round_for_stage();
     g.log(ActionEvent::game(GameAction::StageChanged(g.stage)));
+    // The blinds may have put everyone all-in already
+    if g.round.is_complete() {
+        g.post_action_update()?;
+    }
     Ok(())
 }
 
@@ -89,8 +128,9 @@ pub(crate) fn start_new_hand_from_deck(g: &mut Game, deck: Vec<Card>) -> Result<
 fn post_blind(g: &mut Game, idx: usize, kind: BlindKind, amount: u32) {
     let a = amount.min(g.players[idx].stack);
     g.players[idx].stack -= a;
-    g.round_bets[idx] += a;
+    g.round.round_bets[idx] += a;
     g.pot += a;
+    g.add_contribution(idx, a);
     if a < amount {
         g.players[idx].all_in = true;
     }
@@ -103,19 +143,21 @@ fn post_blind(g: &mut Game, idx: usize, kind: BlindKind, amount: u32) {
 
 #[cfg(test)]
 #[allow(dead_code)]
-pub(crate) fn shuffled_deck_with_seed(seed: u64) -> Vec<Card> {
+pub fn shuffled_deck_with_seed(seed: u64) -> Deck {
     // Simple LCG for deterministic shuffling in tests
+    // The low bits of an LCG are weak, so the middle ones are kept on purpose
+    #[allow(clippy::cast_possible_truncation)]
     fn lcg(next: &mut u64) -> u32 {
         // Constants from Numerical Recipes
-        *next = next.wrapping_mul(1664525).wrapping_add(1013904223);
+        *next = next.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
         (*next >> 16) as u32
     }
-    let mut deck: Vec<Card> = (0..52).map(Card).collect();
+    let mut deck: Vec<Card> = (0..52).map(Card::from).collect();
     let mut s = seed;
     // Fisher-Yates
     for i in (1..deck.len()).rev() {
         let r = lcg(&mut s) as usize % (i + 1);
         deck.swap(i, r);
     }
-    deck
+    Deck::from(deck)
 }
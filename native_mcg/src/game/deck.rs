@@ -0,0 +1,112 @@
+//! The deck hole and community cards are dealt from.
+
+use std::collections::VecDeque;
+
+use mcg_shared::Card;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Cards left to deal, top of the deck first.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct Deck(VecDeque<Card>);
+
+impl Deck {
+    /// All 52 cards in a fixed order; shuffle before dealing.
+    pub fn from_standard_52() -> Self {
+        Self((0..52).map(Card::from).collect())
+    }
+
+    pub fn shuffle(&mut self, rng: &mut impl Rng) {
+        self.0.make_contiguous().shuffle(rng);
+    }
+
+    /// Take the top card, or `None` once the deck is empty.
+    pub fn deal_card(&mut self) -> Option<Card> {
+        self.0.pop_front()
+    }
+
+    #[must_use]
+    pub fn cards_remaining(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// A deck in the given order, first card on top.
+impl From<Vec<Card>> for Deck {
+    fn from(cards: Vec<Card>) -> Self {
+        Self(VecDeque::from(cards))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::dealing::shuffled_deck_with_seed;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::HashSet;
+
+    #[test]
+    fn standard_deck_deals_every_card_once() {
+        let mut deck = Deck::from_standard_52();
+        deck.shuffle(&mut StdRng::seed_from_u64(3));
+        assert_eq!(deck.cards_remaining(), 52);
+
+        let mut seen = HashSet::new();
+        while let Some(card) = deck.deal_card() {
+            assert!(seen.insert(card), "{card} dealt twice");
+        }
+        assert_eq!(seen.len(), 52);
+        assert_eq!(deck.cards_remaining(), 0);
+    }
+
+    #[test]
+    fn test_shuffle_uniformity() {
+        const DECKS: u32 = 10_000;
+        // Chi-square critical value for 51 degrees of freedom at p = 0.001
+        const CRITICAL: f64 = 87.97;
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let mut top_counts = [0u32; 52];
+        for _ in 0..DECKS {
+            let mut deck = Deck::from_standard_52();
+            deck.shuffle(&mut rng);
+            let top = deck.deal_card().unwrap();
+            top_counts[top.0 as usize] += 1;
+        }
+
+        let expected = f64::from(DECKS) / 52.0;
+        let chi_square: f64 = top_counts
+            .iter()
+            .map(|&n| (f64::from(n) - expected).powi(2) / expected)
+            .sum();
+        assert!(
+            chi_square < CRITICAL,
+            "top card is not uniform: chi-square {chi_square:.1}, counts {top_counts:?}"
+        );
+    }
+
+    #[test]
+    fn seeded_shuffle_is_reproducible() {
+        let order = |seed| {
+            let mut deck = shuffled_deck_with_seed(seed);
+            std::iter::from_fn(move || deck.deal_card()).collect::<Vec<_>>()
+        };
+        assert_eq!(order(42), order(42));
+        assert_ne!(order(42), order(43));
+        // Pinned, so tests built on seeded decks deal the same cards everywhere
+        assert_eq!(
+            order(42)[..5],
+            [Card(17), Card(37), Card(15), Card(31), Card(33)]
+        );
+    }
+
+    #[test]
+    fn deals_from_the_top() {
+        let mut deck = Deck::from(vec![Card(7), Card(3)]);
+        assert_eq!(deck.deal_card(), Some(Card(7)));
+        assert_eq!(deck.deal_card(), Some(Card(3)));
+        assert_eq!(deck.deal_card(), None);
+    }
+}
@@ -1,14 +1,17 @@
 //! Core Game and Player definitions + constructors and small helpers.
 
+use super::{BettingRound, Deck, PokerTable, TableError};
+use crate::observable::ObservableVec;
 use anyhow::{Context, Result};
-use mcg_shared::{ActionEvent, Card, GameStatePublic, PlayerId, PlayerPublic, Stage};
+use mcg_shared::{
+    ActionEvent, BettingLimit, Card, GameAction, GameStatePublic, PlayerId, PlayerPublic, Stage,
+};
 
 #[cfg(test)]
 use mcg_shared::{CardRank, CardSuit};
-use rand::seq::SliceRandom;
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet};
 
-pub(crate) const MAX_RECENT_ACTIONS: usize = 50;
+pub const MAX_RECENT_ACTIONS: usize = 50;
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Player {
@@ -23,56 +26,133 @@ pub struct Player {
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Game {
     // Table
-    pub players: Vec<Player>,
-    pub deck: VecDeque<Card>,
+    /// Seated players, indexed by position among the occupied seats
+    pub players: PokerTable,
+    pub deck: Deck,
     pub community: Vec<Card>,
 
     // Betting state
     pub pot: u32,
+    /// Chips each seat put into the pot this hand, see `pot_breakdown`
+    #[serde(default)]
+    pub contributions: Vec<u32>,
     pub stage: Stage,
     pub dealer_idx: usize,
     pub to_act: usize,
-    pub current_bet: u32,
-    pub min_raise: u32,
-    pub round_bets: Vec<u32>, // contributions this street, indexed by player idx
+    #[serde(flatten)]
+    pub round: BettingRound,
 
     // Blinds
     pub sb: u32,
     pub bb: u32,
 
+    // Variant
+    /// Split each pot between the best high and the best qualifying low hand.
+    #[serde(default)]
+    pub hi_lo: bool,
+    /// Knockout bounty every player started with, if this is a bounty game.
+    #[serde(default)]
+    pub bounty: Option<u32>,
+    /// Bounties still on the table, by player. Removed once paid out.
+    #[serde(default)]
+    pub bounties: HashMap<PlayerId, u32>,
+    /// Chips added to the table through rebuys
+    #[serde(default)]
+    pub rebought: u32,
+    /// Limit on the size of bets and raises
+    #[serde(default)]
+    pub betting_limit: BettingLimit,
+
+    // Late joiners
+    /// Players who joined a game in progress. They sit out until they post
+    /// a big blind to play, see `post_blind_to_play`.
+    #[serde(default)]
+    pub must_post_blind: HashSet<PlayerId>,
+    /// Players who posted to play; they put in a big blind next hand
+    #[serde(default)]
+    pub posted_to_play: HashSet<PlayerId>,
+
     // Flow bookkeeping
-    pub pending_to_act: Vec<usize>, // players that still need to act this street (non-folded, non-all-in)
-    // canonical in-memory store of typed events
-    pub recent_actions: Vec<ActionEvent>,
+    /// Number of the current hand, counting from 1
+    #[serde(default)]
+    pub hand_number: u32,
+    /// Number of the last hand of a game of a fixed number of hands
+    #[serde(default)]
+    pub last_hand: Option<u32>,
+    // canonical in-memory store of typed events; subscribe to be told about
+    // each new one
+    pub recent_actions: ObservableVec<ActionEvent, MAX_RECENT_ACTIONS>,
+    /// Events logged since the game was created, including those already
+    /// dropped from `recent_actions`
+    #[serde(default)]
+    pub logged_events: usize,
     pub winner_ids: Vec<PlayerId>,
+    /// Pot equity by seat once the hand is all-in, see `update_pot_equities`
+    #[serde(default)]
+    pub pot_equities: Option<Vec<(usize, f32)>>,
+    /// Snapshots taken before each player action of the current hand, see
+    /// `undo_last_action`; `None` for games restored from a serialized state
+    #[cfg(feature = "undo")]
+    #[serde(skip)]
+    pub undo_stack: Option<Vec<super::GameSnapshot>>,
 }
 
 impl Game {
+    /// Start a no-limit game with blinds of 5 and 10.
+    ///
+    /// # Errors
+    ///
+    /// If there are more players than seats.
     pub fn with_players(players: Vec<Player>) -> Result<Self> {
-        let mut deck: Vec<Card> = (0..52).map(Card).collect();
-        // Use a seeded StdRng for non-deterministic shuffles from entropy
+        Self::with_blinds(players, 5, 10, BettingLimit::NoLimit)
+    }
+
+    /// Like `with_players`, but the first hand is dealt with the given blinds
+    /// and betting limit.
+    ///
+    /// # Errors
+    ///
+    /// If there are more players than seats.
+    pub fn with_blinds(
+        players: Vec<Player>,
+        sb: u32,
+        bb: u32,
+        betting_limit: BettingLimit,
+    ) -> Result<Self> {
+        let mut deck = Deck::from_standard_52();
         deck.shuffle(&mut rand::rng());
-        let player_count = players.len();
 
         let mut g = Self {
-            players,
-            deck: VecDeque::from(deck.clone()),
+            players: PokerTable::from_players(players)?,
+            deck: Deck::default(),
             community: vec![],
 
             pot: 0,
+            contributions: Vec::new(),
             stage: Stage::Preflop,
             dealer_idx: 0,
             to_act: 0,
-            current_bet: 0,
-            min_raise: 0,
-            round_bets: vec![0; player_count],
-
-            sb: 5,
-            bb: 10,
-
-            pending_to_act: Vec::new(),
-            recent_actions: Vec::new(),
+            round: BettingRound::default(),
+
+            sb,
+            bb,
+
+            hi_lo: false,
+            bounty: None,
+            bounties: HashMap::new(),
+            rebought: 0,
+            betting_limit,
+            must_post_blind: HashSet::new(),
+            posted_to_play: HashSet::new(),
+
+            hand_number: 0,
+            last_hand: None,
+            recent_actions: ObservableVec::new(),
+            logged_events: 0,
             winner_ids: Vec::new(),
+            pot_equities: None,
+            #[cfg(feature = "undo")]
+            undo_stack: Some(Vec::new()),
         };
         // delegate dealing/init to sibling module
         super::dealing::start_new_hand_from_deck(&mut g, deck)
@@ -80,6 +160,12 @@ impl Game {
         Ok(g)
     }
 
+    /// Start a game of `human_name` and `bot_count` bots with a deck
+    /// shuffled by `seed`.
+    ///
+    /// # Errors
+    ///
+    /// If there are more players than seats.
     #[cfg(test)]
     #[allow(dead_code)]
     pub fn new_with_seed(human_name: String, bot_count: usize, seed: u64) -> Result<Self> {
@@ -121,32 +207,45 @@ impl Game {
 
     /// Create a game from existing players and deck
     #[cfg(test)]
-    fn from_players_and_deck(players: Vec<Player>, deck: Vec<Card>) -> Result<Self> {
+    pub(crate) fn from_players_and_deck(players: Vec<Player>, deck: Deck) -> Result<Self> {
         let mut g = Self {
-            players,
-            deck: VecDeque::new(),
+            players: PokerTable::from_players(players)?,
+            deck: Deck::default(),
             community: vec![],
 
             pot: 0,
+            contributions: Vec::new(),
             stage: Stage::Preflop,
             dealer_idx: 0,
             to_act: 0,
-            current_bet: 0,
-            min_raise: 0,
-            round_bets: vec![],
+            round: BettingRound::default(),
 
             sb: 5,
             bb: 10,
 
-            pending_to_act: Vec::new(),
-            recent_actions: Vec::new(),
+            hi_lo: false,
+            bounty: None,
+            bounties: HashMap::new(),
+            rebought: 0,
+            betting_limit: BettingLimit::NoLimit,
+            must_post_blind: HashSet::new(),
+            posted_to_play: HashSet::new(),
+
+            hand_number: 0,
+            last_hand: None,
+            recent_actions: ObservableVec::new(),
+            logged_events: 0,
             winner_ids: Vec::new(),
+            pot_equities: None,
+            #[cfg(feature = "undo")]
+            undo_stack: Some(Vec::new()),
         };
         super::dealing::start_new_hand_from_deck(&mut g, deck)
             .context("Failed to initialize new hand from deterministic deck")?;
         Ok(g)
     }
 
+    #[must_use]
     pub fn public(&self) -> GameStatePublic {
         let players = self
             .players
@@ -156,10 +255,15 @@ impl Game {
                 id: p.id,
                 name: p.name.clone(),
                 stack: p.stack,
-                cards: Some(p.cards),
+                // Players sitting out were not dealt in
+                cards: (!self.must_post_blind.contains(&p.id)).then_some(p.cards),
                 has_folded: p.has_folded,
                 all_in: p.all_in,
-                bet_this_round: self.round_bets[idx],
+                bet_this_round: self.round.round_bets[idx],
+                avatar_color: None,
+                bounty: self.bounties.get(&p.id).copied(),
+                seat_number: self.players.seat_number(idx),
+                must_post_blind: self.must_post_blind.contains(&p.id),
             })
             .collect();
 
@@ -167,28 +271,208 @@ impl Game {
             players,
             community: self.community.clone(),
             pot: self.pot,
+            pot_breakdown: self.pot_breakdown(),
+            dealer: self.players.get(self.dealer_idx).map(|p| p.id),
+            hand_number: self.hand_number,
+            hands_remaining: self.hands_remaining(),
             sb: self.sb,
             bb: self.bb,
             to_act: self.players[self.to_act].id,
             stage: self.stage,
             winner_ids: self.winner_ids.clone(),
-            action_log: self.recent_actions.clone(),
-            current_bet: self.current_bet,
-            min_raise: self.min_raise,
+            action_log: self.recent_actions.to_vec(),
+            current_bet: self.round.current_bet,
+            min_raise: self.round.min_raise,
+            betting_limit: self.betting_limit,
+            blind_level: None,
+            pot_equities: self.pot_equities.as_ref().map(|equities| {
+                equities
+                    .iter()
+                    .map(|&(seat, equity)| (self.players[seat].id, equity))
+                    .collect()
+            }),
         }
     }
 
+    /// Public state as seen by `viewer`: only the viewer's hole cards are shown,
+    /// except at showdown where every non-folded player's cards are revealed.
+    #[must_use]
+    pub fn public_for(&self, viewer: PlayerId) -> GameStatePublic {
+        self.public().seen_by(Some(viewer))
+    }
+
+    /// Hands left to play including the current one, if the game ends after
+    /// `last_hand`.
+    #[must_use]
+    pub fn hands_remaining(&self) -> Option<u32> {
+        self.last_hand
+            .map(|last| (last + 1).saturating_sub(self.hand_number))
+    }
+
     pub(crate) fn log(&mut self, ev: ActionEvent) {
-        // canonical store is recent_actions (typed ActionEvent).
+        // canonical store is recent_actions (typed ActionEvent), which
+        // drops the oldest events beyond MAX_RECENT_ACTIONS
+        self.logged_events += 1;
         self.recent_actions.push(ev);
-        // cap logs via utils helper
-        super::utils::cap_logs(self);
     }
 
-    /// Validate stack consistency - the sum of all player stacks plus pot should remain constant
-    /// This helps detect stack management bugs
+    /// Events logged after `logged_events` reached `since`, as far as they
+    /// are still held in `recent_actions`.
+    #[must_use]
+    pub fn events_since(&self, since: usize) -> &[ActionEvent] {
+        let new = self.logged_events.saturating_sub(since);
+        let len = self.recent_actions.len();
+        &self.recent_actions[len - new.min(len)..]
+    }
+
+    /// Put a knockout bounty on every player.
+    pub fn enable_bounties(&mut self, bounty: u32) {
+        self.bounty = Some(bounty);
+        self.bounties = self.players.iter().map(|p| (p.id, bounty)).collect();
+    }
+
+    /// Stack below which a player may rebuy between hands.
+    #[must_use]
+    pub const fn rebuy_threshold(&self) -> u32 {
+        self.bb * 10
+    }
+
+    /// Add `amount` chips to a short-stacked player's stack. Only allowed
+    /// between hands, never during a betting round.
+    ///
+    /// # Errors
+    ///
+    /// If a hand is in progress, `idx` is not a player or their stack is not
+    /// below the rebuy threshold.
+    pub fn rebuy(&mut self, idx: usize, amount: u32) -> Result<()> {
+        if self.stage != Stage::Showdown {
+            anyhow::bail!("Rebuys are only allowed between hands");
+        }
+        let threshold = self.rebuy_threshold();
+        let player = self
+            .players
+            .get_mut(idx)
+            .ok_or_else(|| anyhow::anyhow!("Unknown player index {idx}"))?;
+        if player.stack >= threshold {
+            anyhow::bail!("Rebuys are only allowed below a stack of {threshold}");
+        }
+        player.stack += amount;
+        self.rebought += amount;
+        tracing::info!(player = %player.name, amount, stack = player.stack, "player rebought");
+        Ok(())
+    }
+
+    /// Let the player at `idx`, who joined a game in progress, post a big
+    /// blind so they are dealt into the next hand. Only allowed between hands.
+    ///
+    /// # Errors
+    ///
+    /// If a hand is in progress or the player at `idx` need not post.
+    pub fn post_blind_to_play(&mut self, idx: usize) -> Result<()> {
+        if self.stage != Stage::Showdown {
+            anyhow::bail!("Blinds to play are only posted between hands");
+        }
+        let player = self
+            .players
+            .get(idx)
+            .ok_or_else(|| anyhow::anyhow!("Unknown player index {idx}"))?;
+        if !self.must_post_blind.remove(&player.id) {
+            anyhow::bail!("{} does not have to post a blind", player.name);
+        }
+        self.posted_to_play.insert(player.id);
+        tracing::info!(player = %player.name, "player posted to play");
+        Ok(())
+    }
+
+    /// Record that an admin removed the player at `idx`. Their seat, stack and
+    /// cards stay in play; the backend hands the seat to a bot.
+    ///
+    /// # Errors
+    ///
+    /// If `idx` is not a player.
+    pub fn kick_player(&mut self, idx: usize) -> Result<()> {
+        let player_id = self
+            .players
+            .get(idx)
+            .map(|p| p.id)
+            .ok_or_else(|| anyhow::anyhow!("Unknown player index {idx}"))?;
+        self.log(ActionEvent::game(GameAction::PlayerKicked { player_id }));
+        Ok(())
+    }
+
+    /// Seat `player` at `seat`. Only allowed between hands; the dealer
+    /// button stays with the player who has it. The player sits out until
+    /// they post a big blind to play.
+    ///
+    /// # Errors
+    ///
+    /// If a hand is in progress or the seat is taken or does not exist.
+    pub fn join_at_seat(&mut self, seat: u8, player: Player) -> Result<(), TableError> {
+        if self.stage != Stage::Showdown {
+            return Err(TableError::HandInProgress);
+        }
+        let player_id = player.id;
+        self.players.join_at_seat(seat, player)?;
+        self.must_post_blind.insert(player_id);
+        let idx = self
+            .players
+            .position_of_seat(seat)
+            .ok_or(TableError::NoSuchSeat(seat))?;
+        if idx <= self.dealer_idx && self.players.len() > 1 {
+            self.dealer_idx += 1;
+        }
+        if idx <= self.to_act && self.players.len() > 1 {
+            self.to_act += 1;
+        }
+        self.round.insert_seat(idx);
+        self.contributions
+            .insert(idx.min(self.contributions.len()), 0);
+        self.pot_equities = None;
+        Ok(())
+    }
+
+    /// Empty `seat` between hands and return the player who sat there. The
+    /// button moves on to the next player if it was theirs.
+    ///
+    /// # Errors
+    ///
+    /// If a hand is in progress or the player is the last one at the table.
+    pub fn leave_seat(&mut self, seat: u8) -> Result<Option<Player>, TableError> {
+        if self.stage != Stage::Showdown {
+            return Err(TableError::HandInProgress);
+        }
+        let Some(idx) = self.players.position_of_seat(seat) else {
+            return Ok(None);
+        };
+        if self.players.len() == 1 {
+            return Err(TableError::LastPlayer);
+        }
+        let player = self.players.leave_seat(seat);
+        if let Some(p) = &player {
+            self.must_post_blind.remove(&p.id);
+            self.posted_to_play.remove(&p.id);
+        }
+        let n = self.players.len();
+        let shift = |pos: usize| {
+            let pos = if idx < pos { pos - 1 } else { pos };
+            pos % n
+        };
+        self.dealer_idx = shift(self.dealer_idx);
+        self.to_act = shift(self.to_act);
+        self.round.remove_seat(idx);
+        if idx < self.contributions.len() {
+            self.contributions.remove(idx);
+        }
+        self.pot_equities = None;
+        Ok(player)
+    }
+
+    /// Check that no chips were created or lost. Unpaid bounties count towards
+    /// the total, since paying one moves it into a stack.
     pub(crate) fn validate_stack_consistency(&self, initial_total: u32) -> Result<()> {
-        let current_total: u32 = self.players.iter().map(|p| p.stack).sum::<u32>() + self.pot;
+        let current_total: u32 = self.players.iter().map(|p| p.stack).sum::<u32>()
+            + self.pot
+            + self.bounties.values().sum::<u32>();
         if current_total != initial_total {
             anyhow::bail!(
                 "Stack inconsistency: expected total {}, but got {} (stacks: {:?}, pot: {})",
@@ -209,7 +493,24 @@ mod tests {
     use super::*;
     use crate::game::dealing;
     use anyhow::Result;
-    use std::collections::VecDeque;
+
+    #[test]
+    fn public_for_reveals_non_folded_cards_at_showdown() -> Result<()> {
+        let mut g = Game::new_with_seed("Alice".to_owned(), 2, 7)?;
+        let viewer = g.players[0].id;
+
+        let gs = g.public_for(viewer);
+        assert_eq!(gs.players[0].cards, Some(g.players[0].cards));
+        assert!(gs.players[1..].iter().all(|p| p.cards.is_none()));
+
+        g.players[2].has_folded = true;
+        g.stage = Stage::Showdown;
+        let gs = g.public_for(viewer);
+        assert_eq!(gs.players[0].cards, Some(g.players[0].cards));
+        assert_eq!(gs.players[1].cards, Some(g.players[1].cards));
+        assert_eq!(gs.players[2].cards, None);
+        Ok(())
+    }
 
     #[test]
     fn heads_up_blinds_and_dealing() -> Result<()> {
@@ -245,8 +546,8 @@ mod tests {
         let bb_idx = (g.dealer_idx + 2) % n;
 
         // round_bets should reflect blinds posted
-        assert_eq!(g.round_bets[sb_idx], g.sb);
-        assert_eq!(g.round_bets[bb_idx], g.bb);
+        assert_eq!(g.round.round_bets[sb_idx], g.sb);
+        assert_eq!(g.round.round_bets[bb_idx], g.bb);
 
         // Pot should equal sum of blinds
         assert_eq!(g.pot, g.sb + g.bb);
@@ -287,24 +588,36 @@ mod tests {
         ];
 
         let mut g = Game {
-            players,
-            deck: VecDeque::new(),
+            players: PokerTable::from_players(players)?,
+            deck: Deck::default(),
             community: vec![],
 
             pot: 0,
+            contributions: Vec::new(),
             stage: mcg_shared::Stage::Preflop,
             dealer_idx: 0,
             to_act: 0,
-            current_bet: 0,
-            min_raise: 0,
-            round_bets: vec![],
+            round: BettingRound::default(),
 
             sb: 5,
             bb: 10,
 
-            pending_to_act: Vec::new(),
-            recent_actions: Vec::new(),
+            hi_lo: false,
+            bounty: None,
+            bounties: HashMap::new(),
+            rebought: 0,
+            betting_limit: BettingLimit::NoLimit,
+            must_post_blind: HashSet::new(),
+            posted_to_play: HashSet::new(),
+
+            hand_number: 0,
+            last_hand: None,
+            recent_actions: ObservableVec::new(),
+            logged_events: 0,
             winner_ids: Vec::new(),
+            pot_equities: None,
+            #[cfg(feature = "undo")]
+            undo_stack: Some(Vec::new()),
         };
 
         // Start the hand using deterministic deck
@@ -315,9 +628,98 @@ mod tests {
         assert!(g.players[0].all_in);
 
         // Their contribution should be equal to their stack (3) and pot should include both blinds
-        assert_eq!(g.round_bets[0], 3);
+        assert_eq!(g.round.round_bets[0], 3);
         assert_eq!(g.pot, 3 + g.bb);
 
         Ok(())
     }
+
+    /// Everyone folds until the hand is over.
+    fn fold_to_showdown(g: &mut Game) -> Result<()> {
+        while g.stage != Stage::Showdown {
+            g.apply_player_action(g.to_act, mcg_shared::PlayerAction::Fold)?;
+        }
+        Ok(())
+    }
+
+    fn seat_numbers(g: &Game) -> Vec<u8> {
+        g.public().players.iter().map(|p| p.seat_number).collect()
+    }
+
+    #[test]
+    fn players_leave_and_rejoin_at_a_seat_between_hands() -> Result<()> {
+        let mut g = Game::new_with_seed("Alice".to_owned(), 3, 7)?;
+        assert_eq!(g.leave_seat(1).unwrap_err(), TableError::HandInProgress);
+        fold_to_showdown(&mut g)?;
+
+        g.dealer_idx = 2;
+        let Some(bob) = g.leave_seat(1)? else {
+            anyhow::bail!("seat 1 was empty");
+        };
+        assert_eq!(seat_numbers(&g), [0, 2, 3]);
+        // The button stays with the player in seat 2
+        assert_eq!(g.players.seat_number(g.dealer_idx), 2);
+
+        g.start_new_hand()?;
+        assert_eq!(g.public().players.len(), 3);
+        assert_eq!(
+            g.join_at_seat(5, bob.clone()).unwrap_err(),
+            TableError::HandInProgress
+        );
+        fold_to_showdown(&mut g)?;
+        assert_eq!(
+            g.join_at_seat(0, bob.clone()).unwrap_err(),
+            TableError::SeatTaken(0)
+        );
+        g.join_at_seat(5, bob)?;
+        assert_eq!(seat_numbers(&g), [0, 2, 3, 5]);
+        g.start_new_hand()?;
+        assert_eq!(g.public().players[3].name, "Bot 1");
+        Ok(())
+    }
+
+    #[test]
+    fn late_joiners_sit_out_until_they_post_a_big_blind() -> Result<()> {
+        let mut g = Game::new_with_seed("Alice".to_owned(), 3, 7)?;
+        fold_to_showdown(&mut g)?;
+        let Some(bob) = g.leave_seat(1)? else {
+            anyhow::bail!("seat 1 was empty");
+        };
+        g.join_at_seat(1, bob)?;
+        assert!(g.public().players[1].must_post_blind);
+
+        // The blinds skip Bob, who is not dealt in
+        g.dealer_idx = 0;
+        g.start_new_hand()?;
+        let bob = &g.public().players[1];
+        assert!(bob.has_folded && bob.cards.is_none());
+        assert_eq!(g.round.round_bets, [0, 0, g.sb, g.bb]);
+        assert!(g
+            .apply_player_action(1, mcg_shared::PlayerAction::PostBlind)
+            .is_err());
+        fold_to_showdown(&mut g)?;
+
+        g.apply_player_action(1, mcg_shared::PlayerAction::PostBlind)?;
+        assert!(!g.public().players[1].must_post_blind);
+        assert!(g.post_blind_to_play(1).is_err());
+
+        // On the button, Bob still puts in a big blind of his own
+        g.dealer_idx = 1;
+        g.start_new_hand()?;
+        assert!(!g.players[1].has_folded);
+        assert_eq!(g.round.round_bets, [0, g.bb, g.sb, g.bb]);
+        assert_eq!(g.pot, g.sb + 2 * g.bb);
+        assert!(g.posted_to_play.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn the_last_player_stays_seated() -> Result<()> {
+        let mut g = Game::new_with_seed("Alice".to_owned(), 1, 7)?;
+        fold_to_showdown(&mut g)?;
+        assert!(g.leave_seat(0)?.is_some());
+        assert_eq!(g.leave_seat(1).unwrap_err(), TableError::LastPlayer);
+        assert!(g.leave_seat(0)?.is_none());
+        Ok(())
+    }
 }
@@ -1,29 +1,29 @@
 use anyhow::Result;
 use mcg_shared::{ActionEvent, GameAction, Stage};
 
-use crate::game::Game;
+use crate::game::{BettingRound, Game};
 use crate::poker::cards::card_str;
 
 impl Game {
     /// After an action is applied, update the game flow (next actor, stage changes, etc).
     /// This centralized function replaces the distributed logic that was previously
     /// at the end of `apply_player_action`.
-    pub fn post_action_update(&mut self, actor: usize, prev_current_bet: u32) -> Result<()> {
-        // If the action was a bet or raise, the list of players who need to act changes.
-        if self.current_bet > prev_current_bet {
-            self.rebuild_pending_to_act(actor);
-        }
-
-        // The player who just acted is no longer pending for this street.
-        self.remove_from_pending(actor);
-
+    ///
+    /// # Errors
+    ///
+    /// If the deck runs out of cards while dealing the board.
+    pub fn post_action_update(&mut self) -> Result<()> {
         // Validate stack integrity after each action
         if let Some(initial_total) = self.recent_actions.first().map(|_| {
             // Use a reasonable default for total chips (players.len() * 1000)
-            self.players.len() as u32 * 1000
+            // plus the bounties everyone started with and any rebuys
+            // There are at most `MAX_SEATS` players
+            #[allow(clippy::cast_possible_truncation)]
+            let players = self.players.len() as u32;
+            players * (1000 + self.bounty.unwrap_or(0)) + self.rebought
         }) {
             if let Err(e) = self.validate_stack_consistency(initial_total) {
-                eprintln!("[ERROR] Stack consistency check failed: {}", e);
+                tracing::error!(error = %e, "stack consistency check failed");
                 // Don't bail to avoid crashing the game, but log the error
             }
         }
@@ -35,8 +35,10 @@ impl Game {
             return Ok(());
         }
 
-        // If the betting round is complete, advance to the next stage.
-        if self.is_betting_round_complete() {
+        // If the betting round is complete, advance to the next stage. Streets
+        // on which nobody can act, e.g. because everyone left is all-in, are
+        // dealt right away.
+        while self.round.is_complete() {
             self.advance_stage()?;
             // Advancing might have led to showdown.
             if self.stage == mcg_shared::Stage::Showdown {
@@ -45,28 +47,40 @@ impl Game {
             }
             // Initialize the next round's betting state.
             self.init_round_for_stage();
-        } else {
-            // Otherwise, simply advance to the next player in the pending list.
-            self.to_act = self.pending_to_act.first().copied().unwrap_or(self.to_act);
         }
+        // Otherwise, simply advance to the next player in the pending list.
+        self.to_act = self.round.next_to_act().unwrap_or(self.to_act);
 
         Ok(())
     }
 
-    /// Rebuild the list of players that still need to act this street.
-    /// Should be called when a bet or raise increases `current_bet`.
-    fn rebuild_pending_to_act(&mut self, actor: usize) {
-        let n = self.players.len();
-        self.pending_to_act.clear();
-        // Iterate through players starting from the one after the actor.
-        for i in 1..=n {
-            let idx = (actor + i) % n;
-            if !self.players[idx].has_folded
-                && !self.players[idx].all_in
-                && self.round_bets[idx] < self.current_bet
-            {
-                self.pending_to_act.push(idx);
+    /// Set `pot_equities` while betting is closed and clear it otherwise.
+    /// Not part of `post_action_update` since the simulation is expensive;
+    /// the server calls it once per applied action.
+    pub fn update_pot_equities(&mut self) {
+        self.pot_equities = self
+            .betting_closed()
+            .then(|| crate::poker::equity::compute_all_in_equities(self));
+    }
+
+    /// Whether the hand is decided by the cards alone: someone is all-in and
+    /// at most one player could still bet, without a bet left to call.
+    pub(crate) fn betting_closed(&self) -> bool {
+        if self.stage == Stage::Showdown || self.active_players().len() < 2 {
+            return false;
+        }
+        let mut can_bet = self
+            .players
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| !p.has_folded && !p.all_in);
+        let any_all_in = self.players.iter().any(|p| !p.has_folded && p.all_in);
+        match (can_bet.next(), can_bet.next()) {
+            (None, _) => any_all_in,
+            (Some((seat, _)), None) => {
+                any_all_in && self.round.round_bets[seat] >= self.round.current_bet
             }
+            _ => false,
         }
     }
 
@@ -78,59 +92,30 @@ impl Game {
             .collect()
     }
 
-    pub(crate) fn remove_from_pending(&mut self, actor: usize) {
-        if let Some(pos) = self.pending_to_act.iter().position(|&i| i == actor) {
-            let need = self.current_bet.saturating_sub(self.round_bets[actor]);
-            if self.players[actor].has_folded || self.players[actor].all_in || need == 0 {
-                self.pending_to_act.remove(pos);
-            }
-        }
-    }
-
-    /// A betting round ends when no one is left to act for this street.
-    pub(crate) fn is_betting_round_complete(&self) -> bool {
-        self.pending_to_act.is_empty()
-    }
-
-    /// Initialize per-street state and who acts first for each stage.
-    /// For post-flop streets, round contributions are reset and the minimum
-    /// future raise size starts at the big blind.
+    /// Start the betting round for the current stage and decide who acts first.
+    /// Preflop the blinds already posted into `round.round_bets` carry over;
+    /// later streets start from zero with the minimum raise at the big blind.
     pub(crate) fn init_round_for_stage(&mut self) {
-        // For streets after preflop, reset round contributions and betting state.
-        // For preflop, do NOT zero round_bets: blinds were already posted into round_bets.
-        if self.stage != Stage::Preflop {
-            // fill round_bets with zeros sized to players
-            self.round_bets = vec![0; self.players.len()];
-            self.current_bet = 0;
-            self.min_raise = self.bb;
-        }
-
         let n = self.players.len();
         let start = match self.stage {
-            Stage::Preflop => {
-                if n > 1 {
-                    if n == 2 {
-                        // Heads-up: dealer (SB) acts first preflop
-                        self.dealer_idx
-                    } else {
-                        // 3+ players: first to act is left of BB
-                        (self.dealer_idx + 3) % n
-                    }
-                } else {
-                    self.dealer_idx
-                }
-            }
+            // Left of the BB, or the dealer (SB) in heads-up, as set when
+            // the blinds were posted
+            Stage::Preflop => self.to_act,
             Stage::Flop | Stage::Turn | Stage::River => (self.dealer_idx + 1) % n,
             Stage::Showdown => self.dealer_idx,
         };
-        self.pending_to_act.clear();
-        for i in 0..n {
-            let idx = (start + i) % n;
-            if !self.players[idx].has_folded && !self.players[idx].all_in {
-                self.pending_to_act.push(idx);
-            }
-        }
-        self.to_act = *self.pending_to_act.first().unwrap_or(&self.dealer_idx);
+        let blinds = if self.stage == Stage::Preflop {
+            std::mem::take(&mut self.round.round_bets)
+        } else {
+            Vec::new()
+        };
+        let pot_before = self.pot - blinds.iter().sum::<u32>();
+        self.round = BettingRound::start(&self.players, start, &blinds, self.bb).with_limit(
+            self.betting_limit,
+            self.stage,
+            pot_before,
+        );
+        self.to_act = self.round.next_to_act().unwrap_or(self.dealer_idx);
     }
 
     /// Deal community cards and advance the hand's stage, emitting appropriate logs.
@@ -140,15 +125,15 @@ impl Game {
                 // Flop (burn ignored for simplicity)
                 let c1 = self
                     .deck
-                    .pop_front()
+                    .deal_card()
                     .ok_or_else(|| anyhow::anyhow!("Deck underflow while dealing flop card 1"))?;
                 let c2 = self
                     .deck
-                    .pop_front()
+                    .deal_card()
                     .ok_or_else(|| anyhow::anyhow!("Deck underflow while dealing flop card 2"))?;
                 let c3 = self
                     .deck
-                    .pop_front()
+                    .deal_card()
                     .ok_or_else(|| anyhow::anyhow!("Deck underflow while dealing flop card 3"))?;
                 self.community.push(c1);
                 self.community.push(c2);
@@ -157,36 +142,37 @@ impl Game {
                 self.log(ActionEvent::game(GameAction::DealtCommunity {
                     cards: self.community.clone(),
                 }));
-                println!(
-                    "[STAGE] Flop: {} {} {}",
-                    card_str(self.community[0]),
-                    card_str(self.community[1]),
-                    card_str(self.community[2])
+                tracing::info!(
+                    stage = ?self.stage,
+                    card0 = %card_str(self.community[0]),
+                    card1 = %card_str(self.community[1]),
+                    card2 = %card_str(self.community[2]),
+                    "dealt flop"
                 );
             }
             Stage::Flop => {
                 let c = self
                     .deck
-                    .pop_front()
+                    .deal_card()
                     .ok_or_else(|| anyhow::anyhow!("Deck underflow while dealing turn card"))?;
                 self.community.push(c);
                 self.stage = Stage::Turn;
                 self.log(ActionEvent::game(GameAction::DealtCommunity {
                     cards: self.community.clone(),
                 }));
-                println!("[STAGE] Turn: {}", card_str(self.community[3]));
+                tracing::info!(stage = ?self.stage, card = %card_str(self.community[3]), "dealt turn");
             }
             Stage::Turn => {
                 let c = self
                     .deck
-                    .pop_front()
+                    .deal_card()
                     .ok_or_else(|| anyhow::anyhow!("Deck underflow while dealing river card"))?;
                 self.community.push(c);
                 self.stage = Stage::River;
                 self.log(ActionEvent::game(GameAction::DealtCommunity {
                     cards: self.community.clone(),
                 }));
-                println!("[STAGE] River: {}", card_str(self.community[4]));
+                tracing::info!(stage = ?self.stage, card = %card_str(self.community[4]), "dealt river");
             }
             Stage::River => {
                 self.stage = Stage::Showdown;
@@ -200,8 +186,8 @@ impl Game {
 
 #[cfg(test)]
 mod tests {
-    use crate::game::Game;
-    use mcg_shared::{PlayerAction, Stage};
+    use crate::game::{dealing, Game, Player};
+    use mcg_shared::{Card, PlayerAction, PlayerId, Stage};
 
     #[test]
     fn test_post_action_update_advances_turn() {
@@ -234,4 +220,51 @@ mod tests {
             "Stage should have advanced to Flop"
         );
     }
+
+    #[test]
+    fn pot_equities_are_shown_once_betting_is_closed() {
+        let players = [100, 1000, 1000]
+            .iter()
+            .enumerate()
+            .map(|(i, &stack)| Player {
+                id: PlayerId(i),
+                name: format!("P{i}"),
+                stack,
+                cards: [Card(0), Card(1)],
+                has_folded: false,
+                all_in: false,
+            })
+            .collect();
+        let mut game =
+            Game::from_players_and_deck(players, dealing::shuffled_deck_with_seed(3)).unwrap();
+
+        game.apply_player_action(0, PlayerAction::Bet(1000))
+            .unwrap();
+        assert!(game.players[0].all_in);
+        game.apply_player_action(1, PlayerAction::Fold).unwrap();
+        // Seat 2 can still fold instead of calling the all-in
+        game.update_pot_equities();
+        assert!(game.pot_equities.is_none());
+        game.apply_player_action(2, PlayerAction::CheckCall)
+            .unwrap();
+        game.update_pot_equities();
+
+        assert_eq!(game.stage, Stage::Flop);
+        let equities = game.pot_equities.clone().expect("betting is closed");
+        assert_eq!(
+            equities.iter().map(|&(seat, _)| seat).collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+        let total: f32 = equities.iter().map(|&(_, e)| e).sum();
+        assert!((total - 1.0).abs() < 1e-3, "equities sum to {total}");
+        assert!(game.public().pot_equities.is_some());
+
+        // The remaining streets are checked down to the showdown
+        while game.stage != Stage::Showdown {
+            game.apply_player_action(2, PlayerAction::CheckCall)
+                .unwrap();
+        }
+        game.update_pot_equities();
+        assert!(game.pot_equities.is_none());
+    }
 }
@@ -0,0 +1,167 @@
+//! Main pot and side pots of the current hand.
+
+use super::{Game, Player};
+
+/// One pot and the positions of the players who can win it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pot {
+    pub amount: u32,
+    pub eligible: Vec<usize>,
+}
+
+impl Game {
+    /// Chips put in by the player in `seat` this hand.
+    pub(crate) fn add_contribution(&mut self, seat: usize, amount: u32) {
+        if self.contributions.len() < self.players.len() {
+            self.contributions.resize(self.players.len(), 0);
+        }
+        self.contributions[seat] += amount;
+    }
+
+    /// The main pot followed by the side pots. The pot is kept whole, open
+    /// to everyone still in the hand, if the contributions do not add up to
+    /// it, e.g. for a game saved before they were tracked.
+    pub(crate) fn pots(&self) -> Vec<Pot> {
+        if self.pot == 0 {
+            return Vec::new();
+        }
+        if self.contributions.iter().sum::<u32>() == self.pot {
+            return split_pots(&self.players, &self.contributions);
+        }
+        let eligible = self
+            .players
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| !p.has_folded)
+            .map(|(i, _)| i)
+            .collect();
+        vec![Pot {
+            amount: self.pot,
+            eligible,
+        }]
+    }
+
+    /// The pot as labelled main and side pots. A single pot is labelled
+    /// "Pot".
+    #[must_use]
+    pub fn pot_breakdown(&self) -> Vec<(String, u32)> {
+        let pots = self.pots();
+        match pots.as_slice() {
+            [pot] => vec![("Pot".to_string(), pot.amount)],
+            _ => pots
+                .iter()
+                .enumerate()
+                .map(|(i, pot)| match i {
+                    0 => ("Main Pot".to_string(), pot.amount),
+                    _ => (format!("Side Pot {i}"), pot.amount),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The main pot and the side pots, from the chips each seat put in. A
+/// player who is all-in can only win up to their own contribution from
+/// everyone, so a new pot starts above each all-in amount.
+fn split_pots<'a>(
+    players: impl IntoIterator<Item = &'a Player>,
+    contributions: &[u32],
+) -> Vec<Pot> {
+    let players: Vec<&Player> = players.into_iter().collect();
+    let mut caps: Vec<u32> = players
+        .iter()
+        .zip(contributions)
+        .filter(|(p, _)| p.all_in && !p.has_folded)
+        .map(|(_, &c)| c)
+        .chain(contributions.iter().max().copied())
+        .collect();
+    caps.sort_unstable();
+    caps.dedup();
+
+    let mut pots = Vec::with_capacity(caps.len());
+    let mut below = 0;
+    for cap in caps {
+        let amount: u32 = contributions
+            .iter()
+            .map(|&c| c.min(cap) - c.min(below))
+            .sum();
+        if amount > 0 {
+            let eligible = players
+                .iter()
+                .zip(contributions)
+                .enumerate()
+                .filter(|(_, (p, &c))| !p.has_folded && c > below)
+                .map(|(i, _)| i)
+                .collect();
+            pots.push(Pot { amount, eligible });
+        }
+        below = cap;
+    }
+    pots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::{Card, PlayerId};
+
+    fn player(seat: usize, all_in: bool, has_folded: bool) -> Player {
+        Player {
+            id: PlayerId(seat),
+            name: format!("P{seat}"),
+            stack: if all_in { 0 } else { 500 },
+            cards: [Card(0), Card(1)],
+            has_folded,
+            all_in,
+        }
+    }
+
+    fn amounts(pots: &[Pot]) -> Vec<u32> {
+        pots.iter().map(|p| p.amount).collect()
+    }
+
+    #[test]
+    fn equal_contributions_make_one_pot() {
+        let players = [player(0, false, false), player(1, false, false)];
+        assert_eq!(amounts(&split_pots(&players, &[100, 100])), vec![200]);
+    }
+
+    #[test]
+    fn all_in_players_cap_the_pots_they_can_win() {
+        let players = [
+            player(0, true, false),
+            player(1, true, false),
+            player(2, false, false),
+            player(3, false, true),
+        ];
+        // 50 and 120 all-in, 300 bet, 80 folded
+        let pots = split_pots(&players, &[50, 120, 300, 80]);
+        assert_eq!(amounts(&pots), vec![200, 170, 180]);
+        assert_eq!(pots[0].eligible, vec![0, 1, 2]);
+        assert_eq!(pots[1].eligible, vec![1, 2]);
+        assert_eq!(pots[2].eligible, vec![2]);
+    }
+
+    #[test]
+    fn breakdown_labels_main_and_side_pots() -> anyhow::Result<()> {
+        let players = vec![
+            player(0, false, false),
+            player(1, false, false),
+            player(2, false, false),
+        ];
+        let mut g = Game::with_players(players)?;
+        assert_eq!(g.pot_breakdown(), vec![("Pot".to_string(), 15)]);
+
+        g.players[1].all_in = true;
+        g.contributions = vec![200, 5, 200];
+        g.pot = 405;
+        assert_eq!(
+            g.pot_breakdown(),
+            vec![
+                ("Main Pot".to_string(), 15),
+                ("Side Pot 1".to_string(), 390)
+            ]
+        );
+        Ok(())
+    }
+}
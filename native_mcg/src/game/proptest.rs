@@ -0,0 +1,122 @@
+//! Property-based tests for `Game::apply_player_action`: random action
+//! sequences on seeded tables must never break the chip and flow invariants.
+
+use mcg_shared::{Card, PlayerAction, PlayerId, Stage};
+use proptest::prelude::*;
+
+use super::dealing::shuffled_deck_with_seed;
+use super::{Game, Player};
+
+fn arb_player_action() -> impl Strategy<Value = PlayerAction> {
+    prop_oneof![
+        1 => Just(PlayerAction::Fold),
+        3 => Just(PlayerAction::CheckCall),
+        2 => (0u32..=2000).prop_map(PlayerAction::Bet),
+        1 => Just(PlayerAction::AllIn),
+    ]
+}
+
+/// Stacks of a table with 2 to 6 players, some of them short enough to be
+/// all-in from the blinds.
+fn arb_stacks() -> impl Strategy<Value = Vec<u32>> {
+    proptest::collection::vec(prop_oneof![1u32..=30, 30u32..=2000], 2..=6)
+}
+
+fn seeded_game(stacks: &[u32], seed: u64) -> Game {
+    let players = stacks
+        .iter()
+        .enumerate()
+        .map(|(i, &stack)| Player {
+            id: PlayerId(i),
+            name: format!("P{i}"),
+            stack,
+            cards: [Card(0), Card(1)],
+            has_folded: false,
+            all_in: false,
+        })
+        .collect();
+    Game::from_players_and_deck(players, shuffled_deck_with_seed(seed))
+        .expect("seeded deck has enough cards")
+}
+
+fn stage_index(stage: Stage) -> usize {
+    match stage {
+        Stage::Preflop => 0,
+        Stage::Flop => 1,
+        Stage::Turn => 2,
+        Stage::River => 3,
+        Stage::Showdown => 4,
+    }
+}
+
+fn check_invariants(g: &Game, initial_total: u32, prev_stage: Stage) -> Result<(), TestCaseError> {
+    let total = g.players.iter().map(|p| p.stack).sum::<u32>() + g.pot;
+    prop_assert_eq!(total, initial_total, "chips created or lost");
+    if g.pot > 0 {
+        prop_assert_eq!(
+            g.contributions.iter().sum::<u32>(),
+            g.pot,
+            "contributions do not add up to the pot"
+        );
+    }
+    let breakdown: u32 = g.pot_breakdown().iter().map(|(_, amount)| amount).sum();
+    prop_assert_eq!(breakdown, g.pot, "pot breakdown does not add up");
+    prop_assert!(
+        stage_index(g.stage) >= stage_index(prev_stage),
+        "stage went back from {:?} to {:?}",
+        prev_stage,
+        g.stage
+    );
+    match g.stage {
+        Stage::Showdown => {
+            prop_assert!(!g.winner_ids.is_empty(), "showdown without winners");
+            prop_assert_eq!(g.pot, 0, "pot not paid out at showdown");
+        }
+        stage => {
+            let board = match stage {
+                Stage::Preflop => 0,
+                Stage::Flop => 3,
+                Stage::Turn => 4,
+                _ => 5,
+            };
+            prop_assert_eq!(g.community.len(), board, "board does not match {:?}", stage);
+            let actor = &g.players[g.to_act];
+            prop_assert!(
+                !actor.has_folded && !actor.all_in,
+                "seat {} is to act but folded or all-in",
+                g.to_act
+            );
+        }
+    }
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1000))]
+
+    #[test]
+    fn random_actions_keep_the_game_consistent(
+        stacks in arb_stacks(),
+        seed in any::<u64>(),
+        actions in proptest::collection::vec(arb_player_action(), 1..50),
+    ) {
+        let mut g = seeded_game(&stacks, seed);
+        let initial_total: u32 = stacks.iter().sum();
+        check_invariants(&g, initial_total, Stage::Preflop)?;
+
+        for action in actions {
+            if g.stage == Stage::Showdown {
+                break;
+            }
+            let prev_stage = g.stage;
+            let actor = g.to_act;
+            prop_assert!(
+                g.apply_player_action(actor, action.clone()).is_ok(),
+                "seat {} could not {:?}",
+                actor,
+                action
+            );
+            check_invariants(&g, initial_total, prev_stage)?;
+        }
+    }
+}
@@ -1,13 +1,18 @@
 //! Showdown resolution and pot awarding.
 
 use super::Game;
-use crate::poker::evaluation::{evaluate_best_hand, pick_best_five};
-use mcg_shared::{ActionEvent, GameAction, HandResult};
+use crate::poker::evaluation::{evaluate_best_hand, evaluate_best_low, pick_best_five};
+use mcg_shared::{ActionEvent, ActionKind, GameAction, HandResult, PlayerId};
 
-/// Resolve showdown by evaluating all non-folded hands, splitting the pot on ties
-/// and logging the results. Pot is distributed chip-by-chip for any remainder to
-/// the earliest winners in table order. (Side-pots are intentionally not modeled here.)
-pub(crate) fn finish_showdown(g: &mut Game) {
+/// Resolve showdown by evaluating all non-folded hands, awarding the main pot
+/// and each side pot to the best hand(s) among the players eligible for it and
+/// logging the results. A pot is split on ties, with any remainder distributed
+/// chip-by-chip to the winners in seat order starting left of the dealer.
+///
+/// In hi-lo games each pot is halved: the high half (including any odd chip) goes
+/// to the best high hand(s), the low half to the best qualifying low hand(s).
+/// If no low qualifies the high hand(s) scoop the whole pot.
+pub fn finish_showdown(g: &mut Game) {
     // Evaluate all non-folded players
     let mut results: Vec<HandResult> = Vec::new();
     for (i, p) in g.players.iter().enumerate() {
@@ -16,48 +21,389 @@ pub(crate) fn finish_showdown(g: &mut Game) {
         }
         let rank = evaluate_best_hand(p.cards, &g.community);
         let best_five = pick_best_five(p.cards, &g.community);
+        let lo_rank = if g.hi_lo {
+            evaluate_best_low(p.cards, &g.community)
+        } else {
+            None
+        };
         results.push(HandResult {
             player_id: mcg_shared::PlayerId(i),
             rank,
             best_five,
+            lo_rank,
         });
     }
 
-    // Determine winners (top rank; split on ties)
     results.sort_by(|a, b| a.rank.cmp(&b.rank));
-    let winners: Vec<mcg_shared::PlayerId> = if let Some(best) = results.last().cloned() {
-        results
+    g.log(ActionEvent::game(GameAction::Showdown {
+        hand_results: results.clone(),
+    }));
+
+    g.winner_ids.clear();
+    if !results.is_empty() {
+        for pot in g.pots() {
+            let (winners, low_winners) = pot_winners(&results, &pot.eligible);
+            if low_winners.is_empty() {
+                award_share(g, &winners, pot.amount);
+            } else {
+                let low_half = pot.amount / 2;
+                award_share(g, &winners, pot.amount - low_half);
+                award_share(g, &low_winners, low_half);
+            }
+            for w in winners.into_iter().chain(low_winners) {
+                if !g.winner_ids.contains(&w) {
+                    g.winner_ids.push(w);
+                }
+            }
+        }
+        g.pot = 0;
+    }
+
+    let eliminators = g.winner_ids.clone();
+    record_eliminations(g, &eliminators);
+}
+
+/// The best high hand(s) and the best qualifying low hand(s) among the
+/// players eligible for a pot; `results` are sorted by rank. A pot nobody
+/// still in the hand put chips into goes to the best hands overall.
+fn pot_winners(results: &[HandResult], eligible: &[usize]) -> (Vec<PlayerId>, Vec<PlayerId>) {
+    let contenders: Vec<&HandResult> = results
+        .iter()
+        .filter(|r| eligible.contains(&usize::from(r.player_id)))
+        .collect();
+    let contenders = if contenders.is_empty() {
+        results.iter().collect()
+    } else {
+        contenders
+    };
+
+    // Top rank; split on ties
+    let best = contenders.last().map(|r| &r.rank);
+    let winners = contenders
+        .iter()
+        .rev()
+        .take_while(|r| Some(&r.rank) == best)
+        .map(|r| r.player_id)
+        .collect();
+
+    // Lowest qualifying low; split on ties
+    let best_low = contenders.iter().filter_map(|r| r.lo_rank).min();
+    let low_winners = best_low.map_or_else(Vec::new, |best| {
+        contenders
             .iter()
             .rev()
-            .take_while(|r| r.rank == best.rank)
+            .filter(|r| r.lo_rank == Some(best))
             .map(|r| r.player_id)
             .collect()
-    } else {
-        vec![]
-    };
-    g.winner_ids = winners.clone();
+    });
+    (winners, low_winners)
+}
 
-    g.log(ActionEvent::game(GameAction::Showdown {
-        hand_results: results.clone(),
-    }));
+/// Log every player who lost their last chip in this hand and pay the bounty on
+/// their head to the first pot winner.
+fn record_eliminations(g: &mut Game, winners: &[PlayerId]) {
+    let eliminated: Vec<PlayerId> = g
+        .players
+        .iter()
+        .filter(|p| p.stack == 0 && put_chips_in(g, p.id))
+        .map(|p| p.id)
+        .collect();
 
-    if !winners.is_empty() && g.pot > 0 {
-        let share = g.pot / winners.len() as u32;
-        let mut remainder = g.pot % winners.len() as u32;
-        for &w in &winners {
-            let mut win = share;
-            if remainder > 0 {
-                win += 1;
-                remainder -= 1;
+    for player_id in eliminated {
+        let winner_idx = winners
+            .iter()
+            .map(|&w| usize::from(w))
+            .find(|&idx| g.players[idx].id != player_id);
+        let paid = match (winner_idx, g.bounties.get(&player_id).copied()) {
+            (Some(idx), Some(bounty)) => {
+                g.bounties.remove(&player_id);
+                g.players[idx].stack += bounty;
+                Some((g.players[idx].id, bounty))
             }
-            let w_idx: usize = w.into();
-            g.players[w_idx].stack += win;
-        }
-        g.log(ActionEvent::game(GameAction::PotAwarded {
-            winners: winners.clone(),
-            amount: g.pot,
+            _ => None,
+        };
+        g.log(ActionEvent::game(GameAction::PlayerEliminated {
+            player_id,
+            bounty_won_by: paid.map(|(id, _)| id),
+            bounty: paid.map_or(0, |(_, bounty)| bounty),
         }));
-        println!("[SHOWDOWN] Pot {} awarded to {:?}", g.pot, winners);
-        g.pot = 0;
+    }
+}
+
+/// Whether the player committed any chips this hand, which tells a fresh bust
+/// apart from a player that was already out of chips when the hand began.
+fn put_chips_in(g: &Game, player_id: PlayerId) -> bool {
+    g.recent_actions.iter().any(|ev| match ev {
+        ActionEvent::PlayerAction {
+            player_id: id,
+            action,
+        } if *id == player_id => match action {
+            ActionKind::Call(n) | ActionKind::Bet(n) | ActionKind::AllIn { amount: n } => *n > 0,
+            ActionKind::PostBlind { amount, .. } => *amount > 0,
+            ActionKind::Raise { .. } => true,
+            ActionKind::Fold | ActionKind::Check => false,
+        },
+        _ => false,
+    })
+}
+
+/// Split `amount` evenly among `winners`, giving any remainder chip-by-chip to
+/// the winners closest to the dealer's left, and log the award.
+// A pot has at most `MAX_SEATS` winners
+#[allow(clippy::cast_possible_truncation)]
+fn award_share(g: &mut Game, winners: &[PlayerId], amount: u32) {
+    let n = g.players.len();
+    let mut winners = winners.to_vec();
+    winners.sort_by_key(|&w| (usize::from(w) + n - g.dealer_idx - 1) % n);
+    let share = amount / winners.len() as u32;
+    let mut remainder = amount % winners.len() as u32;
+    for &w in &winners {
+        let mut win = share;
+        if remainder > 0 {
+            win += 1;
+            remainder -= 1;
+        }
+        let w_idx: usize = w.into();
+        g.players[w_idx].stack += win;
+    }
+    tracing::info!(amount, winners = ?winners, "pot awarded");
+    g.log(ActionEvent::game(GameAction::PotAwarded {
+        winners,
+        amount,
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::finish_showdown;
+    use crate::game::Game;
+    use mcg_shared::{ActionEvent, Card, CardRank, CardSuit, GameAction, PlayerId, Stage};
+
+    fn c(rank: CardRank, suit: CardSuit) -> Card {
+        Card::new(rank, suit)
+    }
+
+    /// Build a hi-lo game at showdown with the given hole cards, board and pot.
+    fn hi_lo_game(holes: Vec<[Card; 2]>, board: Vec<Card>, pot: u32) -> Game {
+        let mut g = Game::new_with_seed("Alice".to_owned(), holes.len() - 1, 1).unwrap();
+        for (p, cards) in g.players.iter_mut().zip(holes) {
+            p.cards = cards;
+            p.stack = 0;
+            p.has_folded = false;
+        }
+        g.community = board;
+        g.pot = pot;
+        g.stage = Stage::Showdown;
+        g.hi_lo = true;
+        g
+    }
+
+    fn low_board() -> Vec<Card> {
+        vec![
+            c(CardRank::Two, CardSuit::Clubs),
+            c(CardRank::Four, CardSuit::Diamonds),
+            c(CardRank::Seven, CardSuit::Hearts),
+            c(CardRank::King, CardSuit::Spades),
+            c(CardRank::King, CardSuit::Clubs),
+        ]
+    }
+
+    #[test]
+    fn hi_lo_scoops_when_no_low_qualifies() {
+        let board = vec![
+            c(CardRank::Nine, CardSuit::Clubs),
+            c(CardRank::Ten, CardSuit::Diamonds),
+            c(CardRank::Jack, CardSuit::Hearts),
+            c(CardRank::King, CardSuit::Spades),
+            c(CardRank::Two, CardSuit::Clubs),
+        ];
+        let mut g = hi_lo_game(
+            vec![
+                [
+                    c(CardRank::King, CardSuit::Hearts),
+                    c(CardRank::Three, CardSuit::Hearts),
+                ],
+                [
+                    c(CardRank::Ace, CardSuit::Hearts),
+                    c(CardRank::Four, CardSuit::Spades),
+                ],
+            ],
+            board,
+            100,
+        );
+        finish_showdown(&mut g);
+
+        assert_eq!(g.players[0].stack, 100);
+        assert_eq!(g.players[1].stack, 0);
+        assert_eq!(g.winner_ids, vec![PlayerId(0)]);
+    }
+
+    #[test]
+    fn hi_lo_splits_between_high_and_low() {
+        let mut g = hi_lo_game(
+            vec![
+                // Trip kings: best high, no low
+                [
+                    c(CardRank::King, CardSuit::Hearts),
+                    c(CardRank::Queen, CardSuit::Hearts),
+                ],
+                // A-3 gives 7-4-3-2-A low
+                [
+                    c(CardRank::Ace, CardSuit::Spades),
+                    c(CardRank::Three, CardSuit::Spades),
+                ],
+            ],
+            low_board(),
+            101,
+        );
+        finish_showdown(&mut g);
+
+        // Odd chip goes to the high half
+        assert_eq!(g.players[0].stack, 51);
+        assert_eq!(g.players[1].stack, 50);
+        assert_eq!(g.pot, 0);
+        assert!(g.winner_ids.contains(&PlayerId(0)));
+        assert!(g.winner_ids.contains(&PlayerId(1)));
+    }
+
+    #[test]
+    fn hi_lo_scoops_with_both_high_and_low() {
+        let mut g = hi_lo_game(
+            vec![
+                // A-3 makes the best low and the pair of kings with ace kicker
+                [
+                    c(CardRank::Ace, CardSuit::Spades),
+                    c(CardRank::Three, CardSuit::Spades),
+                ],
+                // 8-6 makes a worse low and a worse pair of kings
+                [
+                    c(CardRank::Eight, CardSuit::Spades),
+                    c(CardRank::Six, CardSuit::Spades),
+                ],
+            ],
+            low_board(),
+            100,
+        );
+        finish_showdown(&mut g);
+
+        assert_eq!(g.players[0].stack, 100);
+        assert_eq!(g.players[1].stack, 0);
+        assert_eq!(g.winner_ids, vec![PlayerId(0)]);
+    }
+
+    #[test]
+    fn short_all_in_stack_only_wins_the_main_pot() {
+        let board = vec![
+            c(CardRank::Two, CardSuit::Clubs),
+            c(CardRank::Seven, CardSuit::Diamonds),
+            c(CardRank::Nine, CardSuit::Hearts),
+            c(CardRank::Jack, CardSuit::Spades),
+            c(CardRank::King, CardSuit::Clubs),
+        ];
+        let mut g = hi_lo_game(
+            vec![
+                // Pair of aces, all-in for 50
+                [
+                    c(CardRank::Ace, CardSuit::Hearts),
+                    c(CardRank::Ace, CardSuit::Spades),
+                ],
+                // Pair of kings
+                [
+                    c(CardRank::King, CardSuit::Hearts),
+                    c(CardRank::Queen, CardSuit::Hearts),
+                ],
+                // King high
+                [
+                    c(CardRank::Four, CardSuit::Spades),
+                    c(CardRank::Three, CardSuit::Spades),
+                ],
+            ],
+            board,
+            450,
+        );
+        g.hi_lo = false;
+        g.players[0].all_in = true;
+        g.contributions = vec![50, 200, 200];
+        finish_showdown(&mut g);
+
+        assert_eq!(g.players[0].stack, 150);
+        assert_eq!(g.players[1].stack, 300);
+        assert_eq!(g.players[2].stack, 0);
+        assert_eq!(g.pot, 0);
+        assert_eq!(g.winner_ids, vec![PlayerId(0), PlayerId(1)]);
+    }
+
+    #[test]
+    fn odd_chip_goes_to_the_first_winner_left_of_the_dealer() {
+        // The board plays, so everybody still in splits the pot
+        let board = vec![
+            c(CardRank::Nine, CardSuit::Clubs),
+            c(CardRank::Ten, CardSuit::Diamonds),
+            c(CardRank::Jack, CardSuit::Hearts),
+            c(CardRank::Queen, CardSuit::Spades),
+            c(CardRank::King, CardSuit::Clubs),
+        ];
+        for (dealer_idx, odd_chip_winner) in [(0, 2), (1, 2), (2, 0)] {
+            let mut g = hi_lo_game(
+                vec![
+                    [
+                        c(CardRank::Two, CardSuit::Hearts),
+                        c(CardRank::Three, CardSuit::Hearts),
+                    ],
+                    [
+                        c(CardRank::Two, CardSuit::Spades),
+                        c(CardRank::Three, CardSuit::Spades),
+                    ],
+                    [
+                        c(CardRank::Two, CardSuit::Diamonds),
+                        c(CardRank::Three, CardSuit::Diamonds),
+                    ],
+                ],
+                board.clone(),
+                101,
+            );
+            g.hi_lo = false;
+            g.players[1].has_folded = true;
+            g.dealer_idx = dealer_idx;
+            finish_showdown(&mut g);
+
+            let other = 2 - odd_chip_winner;
+            assert_eq!(g.players[odd_chip_winner].stack, 51, "dealer {dealer_idx}");
+            assert_eq!(g.players[other].stack, 50, "dealer {dealer_idx}");
+            assert_eq!(g.players[1].stack, 0);
+        }
+    }
+
+    #[test]
+    fn knockout_bounty_is_paid_to_the_eliminator() {
+        let mut g = hi_lo_game(
+            vec![
+                [
+                    c(CardRank::King, CardSuit::Hearts),
+                    c(CardRank::Queen, CardSuit::Hearts),
+                ],
+                [
+                    c(CardRank::Eight, CardSuit::Spades),
+                    c(CardRank::Three, CardSuit::Spades),
+                ],
+            ],
+            low_board(),
+            200,
+        );
+        g.hi_lo = false;
+        g.enable_bounties(50);
+        finish_showdown(&mut g);
+
+        assert_eq!(g.players[0].stack, 250);
+        assert!(!g.bounties.contains_key(&PlayerId(1)));
+        assert_eq!(g.bounties.get(&PlayerId(0)), Some(&50));
+        assert!(g.recent_actions.iter().any(|ev| matches!(
+            ev,
+            ActionEvent::GameAction(GameAction::PlayerEliminated {
+                player_id: PlayerId(1),
+                bounty_won_by: Some(PlayerId(0)),
+                bounty: 50,
+            })
+        )));
     }
 }
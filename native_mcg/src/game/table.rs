@@ -0,0 +1,314 @@
+//! Seats of the poker table.
+//!
+//! Players keep their seat number for the whole game, while the engine deals,
+//! posts blinds and acts by position among the occupied seats. Indexing a
+//! `PokerTable` goes by that position, so `table[0]` is the player in the
+//! lowest occupied seat.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use serde::{Deserialize, Serialize};
+
+use super::Player;
+
+/// Seats at a table
+pub const MAX_SEATS: usize = 9;
+
+/// One seat at the table, empty or taken by a player.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Seat {
+    pub player: Option<Player>,
+    pub seat_number: u8,
+}
+
+/// Why a player could not be seated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableError {
+    /// Seat numbers go from 0 to `MAX_SEATS - 1`
+    NoSuchSeat(u8),
+    SeatTaken(u8),
+    /// More players than seats
+    TableFull,
+    /// Seats only change between hands
+    HandInProgress,
+    /// A game needs someone at the table
+    LastPlayer,
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSuchSeat(seat) => write!(f, "A table has no seat {seat}"),
+            Self::SeatTaken(seat) => write!(f, "Seat {seat} is taken"),
+            Self::TableFull => write!(f, "A table seats at most {MAX_SEATS} players"),
+            Self::HandInProgress => write!(f, "Seats can only change between hands"),
+            Self::LastPlayer => write!(f, "The last player cannot leave the table"),
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
+
+/// The seats of a game, serialized as the occupied ones only.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(try_from = "Vec<Seat>", into = "Vec<Seat>")]
+pub struct PokerTable {
+    seats: [Seat; MAX_SEATS],
+    /// Numbers of the occupied seats, in seat order
+    active_seats: Vec<u8>,
+}
+
+impl Default for PokerTable {
+    // Seat numbers are below `MAX_SEATS`
+    #[allow(clippy::cast_possible_truncation)]
+    fn default() -> Self {
+        Self {
+            seats: std::array::from_fn(|i| Seat {
+                player: None,
+                seat_number: i as u8,
+            }),
+            active_seats: Vec::new(),
+        }
+    }
+}
+
+impl PokerTable {
+    /// Seat `players` in order, starting at seat 0.
+    ///
+    /// # Errors
+    ///
+    /// If there are more players than seats.
+    pub fn from_players(players: Vec<Player>) -> Result<Self, TableError> {
+        if players.len() > MAX_SEATS {
+            return Err(TableError::TableFull);
+        }
+        let mut table = Self::default();
+        for (seat, player) in players.into_iter().enumerate() {
+            let seat = u8::try_from(seat).map_err(|_| TableError::TableFull)?;
+            table.join_at_seat(seat, player)?;
+        }
+        Ok(table)
+    }
+
+    /// Seat `player` at `seat`.
+    ///
+    /// # Errors
+    ///
+    /// If the seat is taken or does not exist.
+    pub fn join_at_seat(&mut self, seat: u8, player: Player) -> Result<(), TableError> {
+        let slot = self
+            .seats
+            .get_mut(usize::from(seat))
+            .ok_or(TableError::NoSuchSeat(seat))?;
+        if slot.player.is_some() {
+            return Err(TableError::SeatTaken(seat));
+        }
+        slot.player = Some(player);
+        self.update_active_seats();
+        Ok(())
+    }
+
+    /// Empty `seat`, returning the player who sat there.
+    pub fn leave_seat(&mut self, seat: u8) -> Option<Player> {
+        let player = self.seats.get_mut(usize::from(seat))?.player.take();
+        self.update_active_seats();
+        player
+    }
+
+    fn update_active_seats(&mut self) {
+        self.active_seats = self
+            .seats
+            .iter()
+            .filter(|s| s.player.is_some())
+            .map(|s| s.seat_number)
+            .collect();
+    }
+
+    #[must_use]
+    pub const fn seats(&self) -> &[Seat] {
+        &self.seats
+    }
+
+    #[must_use]
+    pub fn active_seats(&self) -> &[u8] {
+        &self.active_seats
+    }
+
+    /// Number of seated players
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.active_seats.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.active_seats.is_empty()
+    }
+
+    /// Seat number of the player at position `idx`.
+    #[must_use]
+    pub fn seat_number(&self, idx: usize) -> u8 {
+        self.active_seats[idx]
+    }
+
+    /// Position among the seated players of the player in `seat`.
+    #[must_use]
+    pub fn position_of_seat(&self, seat: u8) -> Option<usize> {
+        self.active_seats.iter().position(|&s| s == seat)
+    }
+
+    #[must_use]
+    pub fn get(&self, idx: usize) -> Option<&Player> {
+        let seat = *self.active_seats.get(idx)?;
+        self.seats[usize::from(seat)].player.as_ref()
+    }
+
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut Player> {
+        let seat = *self.active_seats.get(idx)?;
+        self.seats[usize::from(seat)].player.as_mut()
+    }
+
+    /// Seated players in seat order
+    #[must_use]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Player> + Clone {
+        self.seats.iter().filter_map(|s| s.player.as_ref())
+    }
+
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut Player> {
+        self.seats.iter_mut().filter_map(|s| s.player.as_mut())
+    }
+}
+
+impl Index<usize> for PokerTable {
+    type Output = Player;
+
+    fn index(&self, idx: usize) -> &Player {
+        self.get(idx)
+            .unwrap_or_else(|| panic!("no player at position {} of {}", idx, self.len()))
+    }
+}
+
+impl IndexMut<usize> for PokerTable {
+    fn index_mut(&mut self, idx: usize) -> &mut Player {
+        let len = self.len();
+        self.get_mut(idx)
+            .unwrap_or_else(|| panic!("no player at position {idx} of {len}"))
+    }
+}
+
+impl<'a> IntoIterator for &'a PokerTable {
+    type Item = &'a Player;
+    type IntoIter = Box<dyn DoubleEndedIterator<Item = &'a Player> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+impl<'a> IntoIterator for &'a mut PokerTable {
+    type Item = &'a mut Player;
+    type IntoIter = Box<dyn DoubleEndedIterator<Item = &'a mut Player> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter_mut())
+    }
+}
+
+impl TryFrom<Vec<Seat>> for PokerTable {
+    type Error = TableError;
+
+    fn try_from(seats: Vec<Seat>) -> Result<Self, TableError> {
+        let mut table = Self::default();
+        for seat in seats {
+            if let Some(player) = seat.player {
+                table.join_at_seat(seat.seat_number, player)?;
+            }
+        }
+        Ok(table)
+    }
+}
+
+impl From<PokerTable> for Vec<Seat> {
+    fn from(table: PokerTable) -> Self {
+        table
+            .seats
+            .into_iter()
+            .filter(|s| s.player.is_some())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::{Card, PlayerId};
+
+    fn player(id: usize) -> Player {
+        Player {
+            id: PlayerId(id),
+            name: format!("P{id}"),
+            stack: 1000,
+            cards: [Card(0), Card(1)],
+            has_folded: false,
+            all_in: false,
+        }
+    }
+
+    fn ids(table: &PokerTable) -> Vec<usize> {
+        table.iter().map(|p| p.id.0).collect()
+    }
+
+    #[test]
+    fn players_are_indexed_by_position_among_occupied_seats() {
+        let mut table = PokerTable::default();
+        table.join_at_seat(7, player(0)).unwrap();
+        table.join_at_seat(2, player(1)).unwrap();
+        table.join_at_seat(4, player(2)).unwrap();
+        assert_eq!(table.active_seats(), [2, 4, 7]);
+        assert_eq!(ids(&table), [1, 2, 0]);
+        assert_eq!(table[2].id, PlayerId(0));
+        assert_eq!(table.seat_number(1), 4);
+        assert_eq!(table.position_of_seat(7), Some(2));
+    }
+
+    #[test]
+    fn players_leave_and_rejoin_at_a_seat() {
+        let mut table = PokerTable::from_players((0..3).map(player).collect()).unwrap();
+        let left = table.leave_seat(1).unwrap();
+        assert_eq!(table.active_seats(), [0, 2]);
+        assert_eq!(table[1].id, PlayerId(2));
+        assert!(table.leave_seat(1).is_none());
+
+        assert_eq!(
+            table.join_at_seat(0, left.clone()),
+            Err(TableError::SeatTaken(0))
+        );
+        assert_eq!(
+            table.join_at_seat(9, left.clone()),
+            Err(TableError::NoSuchSeat(9))
+        );
+        table.join_at_seat(5, left).unwrap();
+        assert_eq!(table.active_seats(), [0, 2, 5]);
+        assert_eq!(ids(&table), [0, 2, 1]);
+    }
+
+    #[test]
+    fn at_most_nine_players_are_seated() {
+        assert!(PokerTable::from_players((0..MAX_SEATS).map(player).collect()).is_ok());
+        let err = PokerTable::from_players((0..=MAX_SEATS).map(player).collect()).unwrap_err();
+        assert_eq!(err, TableError::TableFull);
+    }
+
+    #[test]
+    fn serialized_tables_keep_their_seats() {
+        let mut table = PokerTable::default();
+        table.join_at_seat(3, player(0)).unwrap();
+        table.join_at_seat(6, player(1)).unwrap();
+        let json = serde_json::to_value(&table).unwrap();
+        assert_eq!(json.as_array().map(Vec::len), Some(2));
+        let restored: PokerTable = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.active_seats(), [3, 6]);
+        assert_eq!(ids(&restored), [0, 1]);
+    }
+}
@@ -0,0 +1,150 @@
+//! Taking back player actions, for development and tests (`undo` feature).
+
+use std::fmt;
+
+use super::Game;
+
+/// Copy of a whole game taken before a player action, without the undo
+/// history of its own.
+#[derive(Clone, Debug)]
+pub struct GameSnapshot(Box<Game>);
+
+/// Why `Game::undo_last_action` did nothing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UndoError {
+    /// No player action was applied since the hand started
+    NothingToUndo,
+    /// The game keeps no snapshots, e.g. because it was restored from a
+    /// serialized state
+    UndoDisabled,
+}
+
+impl fmt::Display for UndoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NothingToUndo => write!(f, "Nothing to undo"),
+            Self::UndoDisabled => write!(f, "Undo is disabled for this game"),
+        }
+    }
+}
+
+impl std::error::Error for UndoError {}
+
+impl Game {
+    /// Remember the current state so the next player action can be undone.
+    pub(crate) fn save_undo_snapshot(&mut self) {
+        let Some(mut stack) = self.undo_stack.take() else {
+            return;
+        };
+        stack.push(GameSnapshot(Box::new(self.clone())));
+        self.undo_stack = Some(stack);
+    }
+
+    /// Restore the game to how it was before the last player action. Only
+    /// actions of the current hand can be taken back.
+    ///
+    /// # Errors
+    ///
+    /// If the game keeps no snapshots or nothing happened since the hand
+    /// started.
+    pub fn undo_last_action(&mut self) -> Result<(), UndoError> {
+        let mut stack = self.undo_stack.take().ok_or(UndoError::UndoDisabled)?;
+        let Some(GameSnapshot(previous)) = stack.pop() else {
+            self.undo_stack = Some(stack);
+            return Err(UndoError::NothingToUndo);
+        };
+        let mut previous = *previous;
+        // Whoever watches the action log keeps watching the restored game
+        self.recent_actions
+            .transfer_observers(&mut previous.recent_actions);
+        *self = previous;
+        self.undo_stack = Some(stack);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::PlayerAction;
+
+    /// Everything about the game except the undo history, for comparison
+    fn state_of(game: &Game) -> serde_json::Value {
+        serde_json::to_value(game).unwrap()
+    }
+
+    /// Apply `action` for the player to act, undo it and check that nothing
+    /// of the game changed.
+    fn assert_undo_restores(game: &mut Game, action: &PlayerAction) {
+        let before = state_of(game);
+        game.apply_player_action(game.to_act, action.clone())
+            .unwrap();
+        assert_ne!(state_of(game), before, "{action:?} changed nothing");
+        game.undo_last_action().unwrap();
+        assert_eq!(state_of(game), before, "undo of {action:?}");
+    }
+
+    fn game() -> Game {
+        Game::new_with_seed("Alice".to_owned(), 2, 7).unwrap()
+    }
+
+    #[test]
+    fn undo_restores_fold_call_and_raise() {
+        let mut g = game();
+        assert_undo_restores(&mut g, &PlayerAction::Fold);
+        assert_undo_restores(&mut g, &PlayerAction::CheckCall);
+        assert_undo_restores(&mut g, &PlayerAction::Bet(40));
+    }
+
+    #[test]
+    fn undo_restores_check() {
+        let mut g = game();
+        // Everyone calls the big blind, who may then check
+        while g.round.current_bet > g.round.round_bets[g.to_act] {
+            g.apply_player_action(g.to_act, PlayerAction::CheckCall)
+                .unwrap();
+        }
+        assert_undo_restores(&mut g, &PlayerAction::CheckCall);
+    }
+
+    #[test]
+    fn undo_steps_back_one_action_at_a_time() {
+        let mut g = game();
+        let start = state_of(&g);
+        g.apply_player_action(g.to_act, PlayerAction::CheckCall)
+            .unwrap();
+        let after_call = state_of(&g);
+        g.apply_player_action(g.to_act, PlayerAction::Fold).unwrap();
+
+        g.undo_last_action().unwrap();
+        assert_eq!(state_of(&g), after_call);
+        g.undo_last_action().unwrap();
+        assert_eq!(state_of(&g), start);
+        assert_eq!(g.undo_last_action(), Err(UndoError::NothingToUndo));
+    }
+
+    #[test]
+    fn rejected_actions_and_new_hands_leave_nothing_to_undo() {
+        let mut g = game();
+        let not_to_act = (g.to_act + 1) % g.players.len();
+        assert!(g
+            .apply_player_action(not_to_act, PlayerAction::Fold)
+            .is_err());
+        assert_eq!(g.undo_last_action(), Err(UndoError::NothingToUndo));
+
+        g.apply_player_action(g.to_act, PlayerAction::CheckCall)
+            .unwrap();
+        g.start_new_hand().unwrap();
+        assert_eq!(g.undo_last_action(), Err(UndoError::NothingToUndo));
+    }
+
+    #[test]
+    fn restored_games_keep_no_snapshots() {
+        let g = game();
+        let mut restored: Game = serde_json::from_value(state_of(&g)).unwrap();
+        restored
+            .apply_player_action(restored.to_act, PlayerAction::CheckCall)
+            .unwrap();
+        assert_eq!(restored.undo_last_action(), Err(UndoError::UndoDisabled));
+    }
+}
@@ -4,15 +4,19 @@ use native_mcg::{cli, config, server};
 
 use anyhow::Context;
 use clap::Parser;
-use config::Config;
+use config::{Config, GameConfig};
 use server::AppState;
 use std::net::{SocketAddr, TcpListener};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Count heap allocations for the memory estimate of `/health?detail=true`.
+#[global_allocator]
+static ALLOCATOR: server::metrics::CountingAllocator = server::metrics::CountingAllocator;
 
 /// Minimal server entrypoint: parse CLI args and run the server.
 ///
 /// Usage:
-///   mcg-server [--config PATH]
+///   mcg-server [--config PATH] [--game-config PATH] [--log-file PATH]
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Use clap-based CLI for parsing
@@ -52,6 +56,18 @@ async fn main() -> anyhow::Result<()> {
     if let Some(k) = cli.iroh_key {
         cfg.iroh_key = Some(k);
     }
+    if cli.arena {
+        cfg.arena = true;
+    }
+    if let Some(p) = cli.game_config {
+        cfg.game_config = Some(p.display().to_string());
+    }
+    if let Some(p) = cli.log_file {
+        cfg.game_log = Some(p.display().to_string());
+    }
+    if let Some(port) = cli.quic_port {
+        cfg.quic_port = Some(port);
+    }
 
     // Persist overrides only if requested
     if cli.persist {
@@ -63,12 +79,22 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::info!(config = %config_path.display(), bots);
 
+    // A broken game config is fatal: silently dealing default games would hide it
+    let game_config = cfg
+        .game_config
+        .as_deref()
+        .map(|p| GameConfig::from_toml(Path::new(p)))
+        .transpose()?;
+    if let Some(path) = &cfg.game_config {
+        tracing::info!(game_config = %path, "using game config for new games");
+    }
+
     // Initialize shared state for the server and record config path for transports.
-    let state = AppState::new(cfg.clone(), Some(config_path.clone()));
+    let state = AppState::new(cfg.clone(), Some(config_path.clone())).with_game_config(game_config);
 
     // Find first available port starting from 3000
     let port = find_available_port(3000)
-        .map_err(|e| anyhow::anyhow!("Could not find an available port: {}", e))?;
+        .map_err(|e| anyhow::anyhow!("Could not find an available port: {e}"))?;
     let addr = SocketAddr::from(([0, 0, 0, 0], port));
 
     tracing::info!(port, "starting server");
@@ -84,9 +110,8 @@ async fn main() -> anyhow::Result<()> {
 /// Find the first available port starting from the given port number
 fn find_available_port(start_port: u16) -> anyhow::Result<u16> {
     for port in start_port..start_port + 100 {
-        match TcpListener::bind(("0.0.0.0", port)) {
-            Ok(_) => return Ok(port),
-            Err(_) => continue,
+        if TcpListener::bind(("0.0.0.0", port)).is_ok() {
+            return Ok(port);
         }
     }
     Err(anyhow::anyhow!(
@@ -0,0 +1,196 @@
+//! A `Vec` that tells its observers about every pushed item.
+//!
+//! The game keeps its action log in an `ObservableVec`, so code interested in
+//! new events can subscribe instead of polling the log for changes.
+
+use std::fmt;
+use std::ops::Deref;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Called with each item right after it was pushed.
+pub type PushObserver<T> = Box<dyn Fn(&T) + Send + Sync>;
+
+/// A `Vec<T>` holding at most `CAP` items. Pushing beyond the cap drops the
+/// oldest items.
+///
+/// Clones and deserialized values start without observers, so a copy of a
+/// game used for simulation does not report its events.
+pub struct ObservableVec<T, const CAP: usize = { usize::MAX }> {
+    items: Vec<T>,
+    observers: Vec<PushObserver<T>>,
+}
+
+impl<T, const CAP: usize> ObservableVec<T, CAP> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            observers: Vec::new(),
+        }
+    }
+
+    /// Call `observer` for every item pushed from now on, after the observers
+    /// that subscribed earlier.
+    pub fn subscribe(&mut self, observer: impl Fn(&T) + Send + Sync + 'static) {
+        self.observers.push(Box::new(observer));
+    }
+
+    /// Hand the observers over to `other`, e.g. when a game is replaced by
+    /// an earlier snapshot of itself.
+    pub fn transfer_observers(&mut self, other: &mut Self) {
+        other.observers.append(&mut self.observers);
+    }
+
+    /// Append `item`, drop the oldest items beyond `CAP`, then notify the
+    /// observers.
+    pub fn push(&mut self, item: T) {
+        self.items.push(item);
+        if self.items.len() > CAP {
+            let to_remove = self.items.len() - CAP;
+            self.items.drain(0..to_remove);
+        }
+        if let Some(item) = self.items.last() {
+            for observer in &self.observers {
+                observer(item);
+            }
+        }
+    }
+
+    /// Remove all items; the observers stay subscribed.
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+}
+
+impl<T, const CAP: usize> Default for ObservableVec<T, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const CAP: usize> Deref for ObservableVec<T, CAP> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<T, const CAP: usize> Extend<T> for ObservableVec<T, CAP> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<T, const CAP: usize> From<Vec<T>> for ObservableVec<T, CAP> {
+    fn from(items: Vec<T>) -> Self {
+        let mut vec = Self::new();
+        vec.extend(items);
+        vec
+    }
+}
+
+impl<T: Clone, const CAP: usize> Clone for ObservableVec<T, CAP> {
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+            observers: Vec::new(),
+        }
+    }
+}
+
+impl<T: fmt::Debug, const CAP: usize> fmt::Debug for ObservableVec<T, CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservableVec")
+            .field("items", &self.items)
+            .field("observers", &self.observers.len())
+            .finish()
+    }
+}
+
+/// Serialized as a plain sequence of the items.
+impl<T: Serialize, const CAP: usize> Serialize for ObservableVec<T, CAP> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.items.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, const CAP: usize> Deserialize<'de> for ObservableVec<T, CAP> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn observers_fire_in_subscription_order_after_the_push() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut vec: ObservableVec<u32> = ObservableVec::new();
+        for name in ["first", "second"] {
+            let seen = seen.clone();
+            vec.subscribe(move |item| seen.lock().unwrap().push((name, *item)));
+        }
+        vec.push(7);
+        vec.extend([8, 9]);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            [
+                ("first", 7),
+                ("second", 7),
+                ("first", 8),
+                ("second", 8),
+                ("first", 9),
+                ("second", 9)
+            ]
+        );
+    }
+
+    #[test]
+    fn observers_see_the_item_already_in_the_vec() {
+        let lens = Arc::new(Mutex::new(Vec::new()));
+        let mut vec: ObservableVec<u32> = ObservableVec::new();
+        let (tx, rx) = std::sync::mpsc::channel();
+        vec.subscribe(move |item| tx.send(*item).unwrap());
+        for i in 0..3 {
+            vec.push(i);
+            lens.lock().unwrap().push((vec.len(), rx.recv().unwrap()));
+        }
+        assert_eq!(*lens.lock().unwrap(), [(1, 0), (2, 1), (3, 2)]);
+    }
+
+    #[test]
+    fn pushes_beyond_the_cap_drop_the_oldest_items() {
+        let mut vec: ObservableVec<u32, 3> = (0..5).collect::<Vec<_>>().into();
+        assert_eq!(*vec, [2, 3, 4]);
+        vec.push(5);
+        assert_eq!(*vec, [3, 4, 5]);
+
+        let json = serde_json::to_string(&vec).unwrap();
+        assert_eq!(json, "[3,4,5]");
+        let restored: ObservableVec<u32, 2> = serde_json::from_str(&json).unwrap();
+        assert_eq!(*restored, [4, 5]);
+    }
+
+    #[test]
+    fn clones_start_without_observers() {
+        let count = Arc::new(Mutex::new(0));
+        let mut vec: ObservableVec<u32> = ObservableVec::new();
+        let counter = count.clone();
+        vec.subscribe(move |_| *counter.lock().unwrap() += 1);
+        let mut copy = vec.clone();
+        copy.push(1);
+        assert_eq!(*count.lock().unwrap(), 0);
+
+        copy.transfer_observers(&mut vec);
+        vec.transfer_observers(&mut copy);
+        copy.push(2);
+        assert_eq!(*count.lock().unwrap(), 1);
+    }
+}
@@ -3,7 +3,8 @@ use mcg_shared::Card;
 // Re-export the shared types for backward compatibility
 pub use mcg_shared::{CardRank, CardSuit};
 
-/// Returns a string like "A♣", "T♦", etc.
+/// Returns a string like "Ac", "Td", etc.
+#[must_use]
 pub fn card_str(c: Card) -> String {
     c.to_string()
 }
@@ -1,4 +0,0 @@
-/// Constants for card deck configuration
-pub const NUM_SUITS: usize = 4;
-pub const NUM_RANKS: usize = 13;
-pub const RANK_COUNT_ARRAY_SIZE: usize = 15; // 2..14 + unused 0..1
@@ -0,0 +1,68 @@
+//! Monte Carlo equity estimation for hold'em hands. The simulation lives in
+//! `mcg_shared::evaluation` so the frontend can use it too.
+
+use mcg_shared::Card;
+
+use crate::game::Game;
+
+pub use mcg_shared::evaluation::{simulate_equity, EquityResult};
+
+/// Runouts simulated for the pot equities shown once everyone is all-in
+pub const ALL_IN_EQUITY_RUNOUTS: u32 = 10_000;
+
+/// Share of the pot every player still in the hand wins on average, by seat.
+/// Meant for all-in situations, when the remaining cards are dealt without
+/// further betting.
+// The table shows equities as whole percentages, f32 is precise enough
+#[allow(clippy::cast_possible_truncation)]
+pub fn compute_all_in_equities(game: &Game) -> Vec<(usize, f32)> {
+    let seats: Vec<usize> = game
+        .players
+        .iter()
+        .enumerate()
+        .filter_map(|(i, p)| (!p.has_folded).then_some(i))
+        .collect();
+    let hands: Vec<[Card; 2]> = seats.iter().map(|&i| game.players[i].cards).collect();
+    match simulate_equity(
+        &hands,
+        0,
+        &game.community,
+        ALL_IN_EQUITY_RUNOUTS,
+        &mut rand::rng(),
+    ) {
+        Ok(results) => seats
+            .into_iter()
+            .zip(results)
+            .map(|(seat, r)| (seat, (r.equity_pct() / 100.0) as f32))
+            .collect(),
+        Err(e) => {
+            tracing::warn!(error = %e, "could not compute all-in equities");
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::parse_cards;
+
+    fn hand(text: &str) -> [Card; 2] {
+        let cards = parse_cards(text).unwrap();
+        [cards[0], cards[1]]
+    }
+
+    #[test]
+    fn all_in_equities_cover_the_players_left() {
+        let mut game = Game::new_with_seed("Alice".to_owned(), 2, 5).unwrap();
+        game.players[0].cards = hand("Ah As");
+        game.players[1].cards = hand("Kh Ks");
+        game.players[2].has_folded = true;
+        let equities = compute_all_in_equities(&game);
+        assert_eq!(equities.len(), 2);
+        assert_eq!((equities[0].0, equities[1].0), (0, 1));
+        // Aces are about a 4 to 1 favourite over kings
+        assert!((0.77..0.87).contains(&equities[0].1), "{equities:?}");
+        assert!((equities[0].1 + equities[1].1 - 1.0).abs() < 1e-3);
+    }
+}
@@ -5,11 +5,13 @@ pub struct HandRanker;
 
 impl HandRanker {
     /// Compare two hand ranks and return the winner
+    #[must_use]
     pub fn compare_hands(rank1: &HandRank, rank2: &HandRank) -> std::cmp::Ordering {
         rank1.cmp(rank2)
     }
 
     /// Check if a hand beats another hand
+    #[must_use]
     pub fn hand_beats(rank1: &HandRank, rank2: &HandRank) -> bool {
         Self::compare_hands(rank1, rank2) == std::cmp::Ordering::Greater
     }
@@ -1,4 +1,5 @@
 pub mod cards;
-pub mod constants;
+pub mod equity;
 pub mod evaluation;
 pub mod hand_ranking;
+pub mod review;
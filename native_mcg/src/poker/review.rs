@@ -0,0 +1,362 @@
+//! Post-hand review: replays a finished hand and compares every voluntary
+//! action with a basic equity-driven reference strategy.
+//!
+//! The review knows every player's hole cards, so it is only meaningful once
+//! the hand is over. Equity is estimated against the actual cards of the
+//! players still in the hand, and every bet is assumed to be called; the
+//! resulting EV numbers are a rough guide, not a solver output.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{bail, Context, Result};
+use mcg_shared::{
+    ActionEvent, ActionKind, Card, GameAction, GameStatePublic, GtoAnnotation, PlayerId, Stage,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use super::equity::simulate_equity;
+
+/// Runouts per decision; the review runs once per hand so it can afford accuracy
+pub const REVIEW_RUNOUTS: u32 = 2000;
+
+/// Monte Carlo equity of one hand against known opponent hands.
+pub struct EquityEstimator {
+    runouts: u32,
+    rng: StdRng,
+}
+
+impl EquityEstimator {
+    /// A seeded estimator, so reviewing the same hand twice gives the same result.
+    #[must_use]
+    pub fn new(runouts: u32, seed: u64) -> Self {
+        Self {
+            runouts,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Expected share of the pot (0.0 to 1.0) of `hero` against `opponents`.
+    ///
+    /// # Errors
+    ///
+    /// Like [`simulate_equity`].
+    pub fn equity(
+        &mut self,
+        hero: [Card; 2],
+        opponents: &[[Card; 2]],
+        board: &[Card],
+    ) -> Result<f64> {
+        if opponents.is_empty() {
+            return Ok(1.0);
+        }
+        let mut hands = Vec::with_capacity(opponents.len() + 1);
+        hands.push(hero);
+        hands.extend_from_slice(opponents);
+        let results = simulate_equity(&hands, 0, board, self.runouts, &mut self.rng)?;
+        Ok(results[0].equity_pct() / 100.0)
+    }
+}
+
+/// The situation a player faced when they acted, reconstructed from the log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecisionPoint {
+    pub player_id: PlayerId,
+    pub stage: Stage,
+    pub hole: [Card; 2],
+    /// Hole cards of the other players that had not folded yet
+    pub opponents: Vec<[Card; 2]>,
+    pub board: Vec<Card>,
+    /// Chips in the pot before the action
+    pub pot: u32,
+    /// Highest bet of the street before the action
+    pub current_bet: u32,
+    /// Chips the player had already put in on this street
+    pub round_bet: u32,
+    pub big_blind: u32,
+    pub actual: ActionKind,
+}
+
+impl DecisionPoint {
+    #[must_use]
+    pub const fn to_call(&self) -> u32 {
+        self.current_bet.saturating_sub(self.round_bet)
+    }
+
+    /// Chips the player pays for `action` and chips an opponent adds to match it.
+    fn investment(&self, action: &ActionKind) -> (u32, u32) {
+        match action {
+            ActionKind::Fold | ActionKind::Check => (0, 0),
+            ActionKind::Call(paid) => (*paid, 0),
+            ActionKind::Bet(amount) => (*amount, *amount),
+            ActionKind::Raise { to, by } => (to.saturating_sub(self.round_bet), *by),
+            ActionKind::AllIn { amount } => {
+                let over = (self.round_bet + amount).saturating_sub(self.current_bet);
+                (*amount, over)
+            }
+            ActionKind::PostBlind { amount, .. } => (*amount, 0),
+        }
+    }
+}
+
+/// Steps through the action log of a finished hand, one decision at a time.
+pub struct HandReplayer<'a> {
+    events: &'a [ActionEvent],
+    next_event: usize,
+    /// Hole cards in seat order
+    holes: Vec<(PlayerId, [Card; 2])>,
+    big_blind: u32,
+    stage: Stage,
+    board: Vec<Card>,
+    pot: u32,
+    current_bet: u32,
+    round_bets: HashMap<PlayerId, u32>,
+    folded: HashSet<PlayerId>,
+}
+
+impl<'a> HandReplayer<'a> {
+    /// Replay `gs`, which must show every player's hole cards.
+    ///
+    /// # Errors
+    ///
+    /// If a player's hole cards are not known.
+    pub fn new(gs: &'a GameStatePublic) -> Result<Self> {
+        let mut holes = Vec::with_capacity(gs.players.len());
+        for p in &gs.players {
+            let cards = p
+                .cards
+                .with_context(|| format!("hole cards of {} are not known", p.name))?;
+            holes.push((p.id, cards));
+        }
+        Ok(Self {
+            events: &gs.action_log,
+            next_event: 0,
+            holes,
+            big_blind: gs.bb,
+            stage: Stage::Preflop,
+            board: Vec::new(),
+            pot: 0,
+            current_bet: 0,
+            round_bets: HashMap::new(),
+            folded: HashSet::new(),
+        })
+    }
+
+    fn decision(&self, player_id: PlayerId, action: &ActionKind) -> Result<DecisionPoint> {
+        let hole = self
+            .holes
+            .iter()
+            .find(|(id, _)| *id == player_id)
+            .map(|(_, cards)| *cards)
+            .with_context(|| format!("unknown player {player_id} in the action log"))?;
+        let opponents = self
+            .holes
+            .iter()
+            .filter(|(id, _)| *id != player_id && !self.folded.contains(id))
+            .map(|(_, cards)| *cards)
+            .collect();
+        Ok(DecisionPoint {
+            player_id,
+            stage: self.stage,
+            hole,
+            opponents,
+            board: self.board.clone(),
+            pot: self.pot,
+            current_bet: self.current_bet,
+            round_bet: self.round_bets.get(&player_id).copied().unwrap_or(0),
+            big_blind: self.big_blind,
+            actual: action.clone(),
+        })
+    }
+
+    fn apply(&mut self, player_id: PlayerId, action: &ActionKind) {
+        let round_bet = self.round_bets.entry(player_id).or_insert(0);
+        let paid = match action {
+            ActionKind::Fold => {
+                self.folded.insert(player_id);
+                0
+            }
+            ActionKind::Check => 0,
+            ActionKind::Call(paid) => *paid,
+            ActionKind::Bet(amount)
+            | ActionKind::PostBlind { amount, .. }
+            | ActionKind::AllIn { amount } => *amount,
+            ActionKind::Raise { to, .. } => to.saturating_sub(*round_bet),
+        };
+        *round_bet += paid;
+        self.current_bet = self.current_bet.max(*round_bet);
+        self.pot += paid;
+    }
+}
+
+impl Iterator for HandReplayer<'_> {
+    type Item = Result<DecisionPoint>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let events = self.events;
+        while let Some(event) = events.get(self.next_event) {
+            self.next_event += 1;
+            match event {
+                ActionEvent::PlayerAction { player_id, action } => {
+                    let player_id = *player_id;
+                    let decision = match action {
+                        ActionKind::PostBlind { .. } => None,
+                        _ => Some(self.decision(player_id, action)),
+                    };
+                    self.apply(player_id, action);
+                    if decision.is_some() {
+                        return decision;
+                    }
+                }
+                ActionEvent::GameAction(GameAction::StageChanged(stage)) => {
+                    self.stage = *stage;
+                    self.current_bet = 0;
+                    self.round_bets.clear();
+                }
+                // Carries the whole board so far, not just the new cards
+                ActionEvent::GameAction(GameAction::DealtCommunity { cards }) => {
+                    self.board.clone_from(cards);
+                }
+                ActionEvent::GameAction(_) => {}
+            }
+        }
+        None
+    }
+}
+
+/// Reference strategy for reviews: with a known equity it picks the action
+/// with the highest expected value, assuming every bet gets called.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BasicGto;
+
+impl BasicGto {
+    /// Expected chips won from this point on by taking `action` with `equity`.
+    #[must_use]
+    pub fn expected_value(&self, spot: &DecisionPoint, equity: f64, action: &ActionKind) -> f64 {
+        if *action == ActionKind::Fold {
+            return 0.0;
+        }
+        let (paid, matched) = spot.investment(action);
+        equity.mul_add(f64::from(spot.pot + paid + matched), -f64::from(paid))
+    }
+
+    /// Actions considered for `spot`: fold or call when facing a bet,
+    /// otherwise check, plus a bet of half the pot or a pot-sized raise.
+    #[must_use]
+    pub fn candidates(&self, spot: &DecisionPoint) -> Vec<ActionKind> {
+        let to_call = spot.to_call();
+        if to_call == 0 && spot.current_bet == 0 {
+            let bet = (spot.pot / 2).max(spot.big_blind);
+            vec![ActionKind::Check, ActionKind::Bet(bet)]
+        } else if to_call == 0 {
+            // The big blind preflop may check its option or raise
+            let by = spot.pot.max(spot.big_blind);
+            let to = spot.current_bet + by;
+            vec![ActionKind::Check, ActionKind::Raise { to, by }]
+        } else {
+            let by = (spot.pot + to_call).max(spot.big_blind);
+            let to = spot.current_bet + by;
+            vec![
+                ActionKind::Fold,
+                ActionKind::Call(to_call),
+                ActionKind::Raise { to, by },
+            ]
+        }
+    }
+
+    /// Compare the action taken at `spot` with the best candidate. The actual
+    /// action competes as well, so a better sized bet is never flagged.
+    #[must_use]
+    // The EV difference is only shown to the player, f32 is precise enough
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn annotate(&self, spot: &DecisionPoint, equity: f64) -> GtoAnnotation {
+        let actual_ev = self.expected_value(spot, equity, &spot.actual);
+        let (suggested, best_ev) = self
+            .candidates(spot)
+            .into_iter()
+            .map(|action| {
+                let ev = self.expected_value(spot, equity, &action);
+                (action, ev)
+            })
+            .fold((spot.actual.clone(), actual_ev), |best, candidate| {
+                if candidate.1 > best.1 {
+                    candidate
+                } else {
+                    best
+                }
+            });
+        GtoAnnotation {
+            player_id: spot.player_id,
+            stage: spot.stage,
+            actual: spot.actual.clone(),
+            suggested,
+            ev_diff: (best_ev - actual_ev) as f32,
+        }
+    }
+}
+
+/// Annotate every voluntary action of the finished hand in `gs`.
+///
+/// # Errors
+///
+/// If the hand is still in progress or a player's hole cards are not known.
+pub fn review_hand(
+    gs: &GameStatePublic,
+    estimator: &mut EquityEstimator,
+) -> Result<Vec<GtoAnnotation>> {
+    if gs.stage != Stage::Showdown {
+        bail!("the hand is still in progress");
+    }
+    let strategy = BasicGto;
+    let mut annotations = Vec::new();
+    for spot in HandReplayer::new(gs)? {
+        let spot = spot?;
+        let equity = estimator.equity(spot.hole, &spot.opponents, &spot.board)?;
+        annotations.push(strategy.annotate(&spot, equity));
+    }
+    Ok(annotations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::parse_cards;
+
+    fn spot(pot: u32, current_bet: u32, actual: ActionKind) -> DecisionPoint {
+        let hole = parse_cards("Ah Ad").unwrap();
+        DecisionPoint {
+            player_id: PlayerId(0),
+            stage: Stage::River,
+            hole: [hole[0], hole[1]],
+            opponents: Vec::new(),
+            board: Vec::new(),
+            pot,
+            current_bet,
+            round_bet: 0,
+            big_blind: 10,
+            actual,
+        }
+    }
+
+    #[test]
+    fn strong_hands_should_bet_and_weak_hands_fold() {
+        let gto = BasicGto;
+        let checked = spot(100, 0, ActionKind::Check);
+        let note = gto.annotate(&checked, 0.9);
+        assert_eq!(note.suggested, ActionKind::Bet(50));
+        assert!(note.ev_diff > 0.0);
+
+        let called = spot(100, 200, ActionKind::Call(200));
+        let note = gto.annotate(&called, 0.1);
+        assert_eq!(note.suggested, ActionKind::Fold);
+        // Calling 200 into 300 with 10% equity loses 170 chips
+        assert!((note.ev_diff - 170.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn bigger_value_bets_are_not_flagged() {
+        let note = BasicGto.annotate(&spot(100, 0, ActionKind::Bet(100)), 0.9);
+        assert!(note.is_optimal());
+        assert!(note.ev_diff.abs() < 1e-3);
+    }
+}
@@ -1,8 +1,13 @@
+mod history;
+
+pub use history::{HandHistoryParser, HandHistoryWriter, ParsedHand};
+
 use mcg_shared::{
     ActionEvent, ActionKind as SharedActionKind, BlindKind, Card, GameAction, GameStatePublic,
     PlayerId, PlayerPublic, Stage,
 };
 use owo_colors::OwoColorize;
+use std::fmt::Write;
 
 fn format_card(c: Card, color: bool) -> String {
     let mut text = c.to_detailed_string();
@@ -25,6 +30,8 @@ fn player_name(players: &[PlayerPublic], id: PlayerId) -> String {
     PlayerPublic::name_of(players, id)
 }
 
+// One arm per kind of log entry; split up, the wording would be hard to compare
+#[allow(clippy::too_many_lines)]
 fn format_log_entry(entry: &ActionEvent, players: &[PlayerPublic], color: bool) -> String {
     match entry {
         ActionEvent::PlayerAction { player_id, action } => {
@@ -79,12 +86,22 @@ fn format_log_entry(entry: &ActionEvent, players: &[PlayerPublic], color: bool)
                     to,
                     by
                 ),
+                SharedActionKind::AllIn { amount } => format!(
+                    "{} {} {} (all-in)",
+                    if color {
+                        "▲▲".red().bold().to_string()
+                    } else {
+                        "ALL-IN".into()
+                    },
+                    who,
+                    amount
+                ),
                 SharedActionKind::PostBlind { kind, amount } => {
                     let k = match *kind {
                         BlindKind::SmallBlind => "SB",
                         BlindKind::BigBlind => "BB",
                     };
-                    format!("{} {} {}", k, who, amount)
+                    format!("{k} {who} {amount}")
                 }
             }
         }
@@ -94,11 +111,11 @@ fn format_log_entry(entry: &ActionEvent, players: &[PlayerPublic], color: bool)
                 .map(|c| format_card(*c, color))
                 .collect::<Vec<_>>()
                 .join(", ");
-            format!("Board +[{}]", list)
+            format!("Board +[{list}]")
         }
         ActionEvent::GameAction(GameAction::DealtHole { player_id }) => {
             let who = player_name(players, *player_id);
-            format!("Dealt hole to {}", who)
+            format!("Dealt hole to {who}")
         }
         ActionEvent::GameAction(GameAction::Showdown { .. }) => "Showdown".into(),
         ActionEvent::GameAction(GameAction::PotAwarded { winners, amount }) => {
@@ -107,16 +124,39 @@ fn format_log_entry(entry: &ActionEvent, players: &[PlayerPublic], color: bool)
                 .map(|id| player_name(players, *id))
                 .collect::<Vec<_>>()
                 .join(", ");
-            format!("Pot awarded {} -> [{}]", amount, names)
+            format!("Pot awarded {amount} -> [{names}]")
+        }
+        ActionEvent::GameAction(GameAction::PlayerEliminated {
+            player_id,
+            bounty_won_by,
+            bounty,
+        }) => {
+            let who = player_name(players, *player_id);
+            bounty_won_by.map_or_else(
+                || format!("{who} eliminated"),
+                |winner| {
+                    format!(
+                        "{} eliminated, {} collects bounty {}",
+                        who,
+                        player_name(players, winner),
+                        bounty
+                    )
+                },
+            )
+        }
+        ActionEvent::GameAction(GameAction::PlayerKicked { player_id }) => {
+            let who = player_name(players, *player_id);
+            format!("{who} kicked, a bot takes over")
         }
         ActionEvent::GameAction(GameAction::StageChanged(_)) => unreachable!(),
     }
 }
 
+#[must_use]
 pub fn format_event_human(entry: &ActionEvent, players: &[PlayerPublic], color: bool) -> String {
     match entry {
         ActionEvent::GameAction(GameAction::StageChanged(s)) => {
-            let sname = format!("== {:?} ==", s);
+            let sname = format!("== {s:?} ==");
             if color {
                 sname.bold().purple().to_string()
             } else {
@@ -127,6 +167,7 @@ pub fn format_event_human(entry: &ActionEvent, players: &[PlayerPublic], color:
     }
 }
 
+#[must_use]
 pub fn format_table_header(gs: &GameStatePublic, sb: u32, bb: u32, color: bool) -> String {
     let mut out = String::new();
     let title = if color {
@@ -137,9 +178,9 @@ pub fn format_table_header(gs: &GameStatePublic, sb: u32, bb: u32, color: bool)
     let blinds = if color {
         format!("{} SB {} / BB {}", "Blinds:".bold().yellow(), sb, bb)
     } else {
-        format!("Blinds: SB {} / BB {}", sb, bb)
+        format!("Blinds: SB {sb} / BB {bb}")
     };
-    out.push_str(&format!("{}\n{}\n", title, blinds));
+    let _ = writeln!(out, "{title}\n{blinds}");
     out.push_str("Players:\n");
     for p in &gs.players {
         let name = p.name.clone();
@@ -162,15 +203,17 @@ pub fn format_table_header(gs: &GameStatePublic, sb: u32, bb: u32, color: bool)
             String::new()
         };
         let to_act_text = if p.id == gs.to_act { " (to act)" } else { "" };
-        out.push_str(&format!(
-            "  #{} {}  stack={}{}{}{}\n",
+        let _ = writeln!(
+            out,
+            "  #{} {}  stack={}{}{}{}",
             p.id, name, p.stack, folded, to_act_icon, to_act_text
-        ));
+        );
     }
     out
 }
 
 #[allow(dead_code)]
+#[must_use]
 pub fn format_state_human(gs: &GameStatePublic, color: bool) -> String {
     let mut out = String::new();
 
@@ -186,17 +229,17 @@ pub fn format_state_human(gs: &GameStatePublic, color: bool) -> String {
     } else {
         format!("Pot: {}", gs.pot)
     };
-    out.push_str(&format!("{}  |  {}\n", stage_s, pot_s));
+    let _ = writeln!(out, "{stage_s}  |  {pot_s}");
 
     // Board and hole
     if !gs.community.is_empty() {
         let board = format_cards(&gs.community, color);
-        out.push_str(&format!("Board: [{}]\n", board));
+        let _ = writeln!(out, "Board: [{board}]");
     }
     for p in &gs.players {
         if let Some(cards) = p.cards {
             let player_cards = format_cards(&cards, color);
-            out.push_str(&format!("{}'s cards: [{}]\n", p.name, player_cards));
+            let _ = writeln!(out, "{}'s cards: [{}]", p.name, player_cards);
         }
     }
 
@@ -223,10 +266,11 @@ pub fn format_state_human(gs: &GameStatePublic, color: bool) -> String {
             String::new()
         };
         let to_act_text = if p.id == gs.to_act { " (to act)" } else { "" };
-        out.push_str(&format!(
-            "  #{} {}  stack={}{}{}{}\n",
+        let _ = writeln!(
+            out,
+            "  #{} {}  stack={}{}{}{}",
             p.id, name, p.stack, folded, to_act_icon, to_act_text
-        ));
+        );
     }
 
     // Log
@@ -237,17 +281,17 @@ pub fn format_state_human(gs: &GameStatePublic, color: bool) -> String {
             if let ActionEvent::GameAction(GameAction::StageChanged(s)) = e {
                 if last_stage != Some(*s) {
                     last_stage = Some(*s);
-                    let sname = format!("== {:?} ==", s);
+                    let sname = format!("== {s:?} ==");
                     let sline = if color {
                         sname.bold().purple().to_string()
                     } else {
                         sname
                     };
-                    out.push_str(&format!("{}\n", sline));
+                    let _ = writeln!(out, "{sline}");
                 }
                 continue;
             }
-            out.push_str(&format!("  {}\n", format_log_entry(e, &gs.players, color)));
+            let _ = writeln!(out, "  {}", format_log_entry(e, &gs.players, color));
         }
     }
 
@@ -0,0 +1,409 @@
+//! Text hand history export (PokerStars-like) and a matching parser.
+//!
+//! Each hand is rendered from a `GameStatePublic` snapshot. Starting stacks
+//! are reconstructed by replaying the chip movements in the action log.
+
+use std::fmt::Write;
+
+use anyhow::{anyhow, bail, Context, Result};
+use mcg_shared::{
+    ActionEvent, ActionKind, BlindKind, Card, GameAction, GameStatePublic, PlayerId, PlayerPublic,
+};
+
+/// Formats hands into a portable text hand history.
+pub struct HandHistoryWriter;
+
+impl HandHistoryWriter {
+    /// Format a single hand. `timestamp_secs` is a Unix timestamp (UTC) used for the header.
+    #[must_use]
+    pub fn format_hand(gs: &GameStatePublic, hand_number: u32, timestamp_secs: u64) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "MCG Hand #{}: Hold'em {} ({}/{}) - {}",
+            hand_number,
+            gs.betting_limit,
+            gs.sb,
+            gs.bb,
+            format_utc(timestamp_secs)
+        );
+
+        for (seat, (p, stack)) in gs.players.iter().zip(starting_stacks(gs)).enumerate() {
+            let _ = writeln!(out, "Seat {}: {} ({} in chips)", seat + 1, p.name, stack);
+        }
+
+        out.push_str("*** HOLE CARDS ***\n");
+        for p in &gs.players {
+            if let Some([first, second]) = p.cards {
+                let _ = writeln!(out, "Dealt to {} [{} {}]", p.name, first, second);
+            }
+        }
+
+        for entry in &gs.action_log {
+            match entry {
+                ActionEvent::PlayerAction { player_id, action } => {
+                    let who = PlayerPublic::name_of(&gs.players, *player_id);
+                    let _ = writeln!(out, "{}: {}", who, action_text(action));
+                }
+                ActionEvent::GameAction(GameAction::DealtCommunity { cards }) => {
+                    out.push_str(&street_line(cards));
+                }
+                ActionEvent::GameAction(GameAction::Showdown { hand_results }) => {
+                    out.push_str("*** SHOWDOWN ***\n");
+                    for hr in hand_results {
+                        let Some(p) = gs.players.iter().find(|p| p.id == hr.player_id) else {
+                            continue;
+                        };
+                        if let Some([first, second]) = p.cards {
+                            let _ = writeln!(
+                                out,
+                                "{}: shows [{} {}] ({})",
+                                p.name, first, second, hr.rank.category
+                            );
+                        }
+                    }
+                }
+                ActionEvent::GameAction(GameAction::PotAwarded { winners, amount }) => {
+                    for (id, won) in split_award(winners, *amount) {
+                        let who = PlayerPublic::name_of(&gs.players, id);
+                        let _ = writeln!(out, "{who} collected {won} from pot");
+                    }
+                }
+                ActionEvent::GameAction(GameAction::PlayerEliminated {
+                    player_id,
+                    bounty_won_by,
+                    bounty,
+                }) => {
+                    let who = PlayerPublic::name_of(&gs.players, *player_id);
+                    match bounty_won_by {
+                        Some(winner) => {
+                            let _ = writeln!(
+                                out,
+                                "{} was eliminated by {} (bounty {})",
+                                who,
+                                PlayerPublic::name_of(&gs.players, *winner),
+                                bounty
+                            );
+                        }
+                        None => {
+                            let _ = writeln!(out, "{who} was eliminated");
+                        }
+                    }
+                }
+                ActionEvent::GameAction(
+                    GameAction::DealtHole { .. }
+                    | GameAction::PlayerKicked { .. }
+                    | GameAction::StageChanged(_),
+                ) => {}
+            }
+        }
+
+        out.push_str("*** SUMMARY ***\n");
+        let _ = writeln!(out, "Board [{}]", cards_text(&gs.community));
+        out
+    }
+
+    /// Join several formatted hands into a single session history document.
+    #[must_use]
+    pub fn format_session(hands: &[String]) -> String {
+        hands.join("\n")
+    }
+}
+
+/// A hand reconstructed from the text format produced by [`HandHistoryWriter`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParsedHand {
+    pub hand_number: u32,
+    pub date: String,
+    pub sb: u32,
+    pub bb: u32,
+    /// Seat order with each player's starting stack.
+    pub seats: Vec<(String, u32)>,
+    pub hole_cards: Vec<(String, [Card; 2])>,
+    pub actions: Vec<(String, ActionKind)>,
+    pub board: Vec<Card>,
+    pub collected: Vec<(String, u32)>,
+}
+
+/// Parses the text format produced by [`HandHistoryWriter`].
+pub struct HandHistoryParser;
+
+impl HandHistoryParser {
+    /// Parse every hand contained in a session history document.
+    ///
+    /// # Errors
+    ///
+    /// If one of the hands cannot be parsed.
+    pub fn parse_session(text: &str) -> Result<Vec<ParsedHand>> {
+        let mut hands = Vec::new();
+        let mut current: Vec<&str> = Vec::new();
+        for line in text.lines() {
+            if line.starts_with("MCG Hand #") && !current.is_empty() {
+                hands.push(Self::parse_hand(&current.join("\n"))?);
+                current.clear();
+            }
+            if !line.trim().is_empty() {
+                current.push(line);
+            }
+        }
+        if !current.is_empty() {
+            hands.push(Self::parse_hand(&current.join("\n"))?);
+        }
+        Ok(hands)
+    }
+
+    /// Parse a single hand.
+    ///
+    /// # Errors
+    ///
+    /// If the text is not a hand history.
+    pub fn parse_hand(text: &str) -> Result<ParsedHand> {
+        let mut lines = text.lines();
+        let header = lines.next().ok_or_else(|| anyhow!("empty hand history"))?;
+        let mut hand = parse_header(header)?;
+
+        for line in lines {
+            if let Some(rest) = line.strip_prefix("Seat ") {
+                let (_, seat) = rest
+                    .split_once(": ")
+                    .ok_or_else(|| anyhow!("malformed seat line '{line}'"))?;
+                let (name, chips) = seat
+                    .rsplit_once(" (")
+                    .ok_or_else(|| anyhow!("malformed seat line '{line}'"))?;
+                let stack = chips
+                    .trim_end_matches(" in chips)")
+                    .parse()
+                    .with_context(|| format!("parsing stack in '{line}'"))?;
+                hand.seats.push((name.to_string(), stack));
+            } else if let Some(rest) = line.strip_prefix("Dealt to ") {
+                let (name, cards) = rest
+                    .rsplit_once(" [")
+                    .ok_or_else(|| anyhow!("malformed hole cards line '{line}'"))?;
+                let cards = mcg_shared::parse_cards(cards.trim_end_matches(']'))?;
+                if cards.len() != 2 {
+                    bail!("expected two hole cards in '{line}'");
+                }
+                hand.hole_cards
+                    .push((name.to_string(), [cards[0], cards[1]]));
+            } else if let Some(rest) = line.strip_prefix("Board [") {
+                hand.board = mcg_shared::parse_cards(rest.trim_end_matches(']'))?;
+            } else if line.starts_with("***") || line.contains(" was eliminated") {
+                // Street and section markers carry no extra information, and
+                // eliminations follow from the stacks.
+            } else if let Some((name, amount)) = line.rsplit_once(" collected ") {
+                let amount = amount
+                    .trim_end_matches(" from pot")
+                    .parse()
+                    .with_context(|| format!("parsing collected amount in '{line}'"))?;
+                hand.collected.push((name.to_string(), amount));
+            } else if let Some((name, action)) = line.rsplit_once(": ") {
+                if action.starts_with("shows ") {
+                    continue;
+                }
+                hand.actions.push((name.to_string(), parse_action(action)?));
+            } else {
+                bail!("unrecognized hand history line '{line}'");
+            }
+        }
+        Ok(hand)
+    }
+}
+
+// ===== Internal helpers =====
+
+fn action_text(action: &ActionKind) -> String {
+    match action {
+        ActionKind::Fold => "folds".into(),
+        ActionKind::Check => "checks".into(),
+        ActionKind::Call(n) => format!("calls {n}"),
+        ActionKind::Bet(n) => format!("bets {n}"),
+        ActionKind::Raise { to, by } => format!("raises {by} to {to}"),
+        ActionKind::AllIn { amount } => format!("goes all-in for {amount}"),
+        ActionKind::PostBlind { kind, amount } => match kind {
+            BlindKind::SmallBlind => format!("posts small blind {amount}"),
+            BlindKind::BigBlind => format!("posts big blind {amount}"),
+        },
+    }
+}
+
+fn parse_action(text: &str) -> Result<ActionKind> {
+    let amount = |s: &str| -> Result<u32> {
+        s.parse()
+            .with_context(|| format!("parsing amount in action '{text}'"))
+    };
+    let action = if text == "folds" {
+        ActionKind::Fold
+    } else if text == "checks" {
+        ActionKind::Check
+    } else if let Some(n) = text.strip_prefix("calls ") {
+        ActionKind::Call(amount(n)?)
+    } else if let Some(n) = text.strip_prefix("bets ") {
+        ActionKind::Bet(amount(n)?)
+    } else if let Some(n) = text.strip_prefix("goes all-in for ") {
+        ActionKind::AllIn { amount: amount(n)? }
+    } else if let Some(rest) = text.strip_prefix("raises ") {
+        let (by, to) = rest
+            .split_once(" to ")
+            .ok_or_else(|| anyhow!("malformed raise '{text}'"))?;
+        ActionKind::Raise {
+            to: amount(to)?,
+            by: amount(by)?,
+        }
+    } else if let Some(n) = text.strip_prefix("posts small blind ") {
+        ActionKind::PostBlind {
+            kind: BlindKind::SmallBlind,
+            amount: amount(n)?,
+        }
+    } else if let Some(n) = text.strip_prefix("posts big blind ") {
+        ActionKind::PostBlind {
+            kind: BlindKind::BigBlind,
+            amount: amount(n)?,
+        }
+    } else {
+        bail!("unknown action '{text}'");
+    };
+    Ok(action)
+}
+
+fn parse_header(line: &str) -> Result<ParsedHand> {
+    let rest = line
+        .strip_prefix("MCG Hand #")
+        .ok_or_else(|| anyhow!("missing hand header in '{line}'"))?;
+    let (number, rest) = rest
+        .split_once(": ")
+        .ok_or_else(|| anyhow!("malformed hand header '{line}'"))?;
+    let (blinds, date) = rest
+        .split_once(") - ")
+        .ok_or_else(|| anyhow!("malformed hand header '{line}'"))?;
+    let (_, blinds) = blinds
+        .rsplit_once('(')
+        .ok_or_else(|| anyhow!("missing blinds in '{line}'"))?;
+    let (sb, bb) = blinds
+        .split_once('/')
+        .ok_or_else(|| anyhow!("malformed blinds in '{line}'"))?;
+    Ok(ParsedHand {
+        hand_number: number.parse().context("parsing hand number")?,
+        date: date.to_string(),
+        sb: sb.parse().context("parsing small blind")?,
+        bb: bb.parse().context("parsing big blind")?,
+        ..ParsedHand::default()
+    })
+}
+
+fn street_line(cards: &[Card]) -> String {
+    match cards.len() {
+        3 => format!("*** FLOP *** [{}]\n", cards_text(cards)),
+        4 => format!(
+            "*** TURN *** [{}] [{}]\n",
+            cards_text(&cards[..3]),
+            cards[3]
+        ),
+        5 => format!(
+            "*** RIVER *** [{}] [{}]\n",
+            cards_text(&cards[..4]),
+            cards[4]
+        ),
+        _ => format!("*** BOARD *** [{}]\n", cards_text(cards)),
+    }
+}
+
+fn cards_text(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(std::string::ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split an awarded amount the same way the showdown does: evenly, with the
+/// remainder going chip-by-chip to the earliest winners. The showdown logs
+/// them in the order it hands out the odd chips, left of the dealer first.
+// A hand has at most as many winners as seats
+#[allow(clippy::cast_possible_truncation)]
+fn split_award(winners: &[PlayerId], amount: u32) -> Vec<(PlayerId, u32)> {
+    if winners.is_empty() {
+        return Vec::new();
+    }
+    let share = amount / winners.len() as u32;
+    let remainder = amount % winners.len() as u32;
+    winners
+        .iter()
+        .enumerate()
+        .map(|(i, &id)| (id, share + u32::from((i as u32) < remainder)))
+        .collect()
+}
+
+/// Reconstruct each player's stack at the start of the hand by undoing the
+/// contributions and winnings recorded in the action log.
+fn starting_stacks(gs: &GameStatePublic) -> Vec<u32> {
+    let mut stacks: Vec<i64> = gs.players.iter().map(|p| i64::from(p.stack)).collect();
+    let idx_of = |id: PlayerId| gs.players.iter().position(|p| p.id == id);
+    let mut street = vec![0u32; gs.players.len()];
+
+    for entry in &gs.action_log {
+        match entry {
+            ActionEvent::PlayerAction { player_id, action } => {
+                let Some(i) = idx_of(*player_id) else {
+                    continue;
+                };
+                let paid = match action {
+                    ActionKind::Call(n) | ActionKind::Bet(n) => *n,
+                    ActionKind::PostBlind { amount, .. } | ActionKind::AllIn { amount } => *amount,
+                    ActionKind::Raise { to, .. } => to.saturating_sub(street[i]),
+                    ActionKind::Fold | ActionKind::Check => 0,
+                };
+                street[i] += paid;
+                stacks[i] += i64::from(paid);
+            }
+            ActionEvent::GameAction(GameAction::DealtCommunity { .. }) => {
+                street.fill(0);
+            }
+            ActionEvent::GameAction(GameAction::PotAwarded { winners, amount }) => {
+                for (id, won) in split_award(winners, *amount) {
+                    if let Some(i) = idx_of(id) {
+                        stacks[i] -= i64::from(won);
+                    }
+                }
+            }
+            ActionEvent::GameAction(GameAction::PlayerEliminated {
+                bounty_won_by: Some(winner),
+                bounty,
+                ..
+            }) => {
+                if let Some(i) = idx_of(*winner) {
+                    stacks[i] -= i64::from(*bounty);
+                }
+            }
+            ActionEvent::GameAction(_) => {}
+        }
+    }
+    stacks
+        .into_iter()
+        .map(|s| u32::try_from(s.max(0)).unwrap_or(u32::MAX))
+        .collect()
+}
+
+/// Format a Unix timestamp as `YYYY/MM/DD HH:MM:SS UTC`.
+fn format_utc(timestamp_secs: u64) -> String {
+    let days = (timestamp_secs / 86_400).cast_signed();
+    let secs = timestamp_secs % 86_400;
+    // Civil-from-days conversion (proleptic Gregorian calendar).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}/{:02}/{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        secs / 3_600,
+        (secs % 3_600) / 60,
+        secs % 60
+    )
+}
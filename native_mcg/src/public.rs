@@ -9,9 +9,17 @@ pub const PUBLIC_FILE_NAME: &str = "mcg_server_public.toml";
 pub struct PublicInfo {
     #[serde(default)]
     pub iroh_node_id: Option<String>,
+    /// Hex encoded DER of the QUIC listener's self-signed certificate
+    #[serde(default)]
+    pub quic_cert: Option<String>,
 }
 
 impl PublicInfo {
+    /// Read the public info at `path`, or the default if there is none.
+    ///
+    /// # Errors
+    ///
+    /// If the file exists but cannot be read or parsed.
     pub fn load(path: &Path) -> Result<Self> {
         if path.exists() {
             let text = fs::read_to_string(path)
@@ -24,6 +32,11 @@ impl PublicInfo {
         }
     }
 
+    /// Write the public info to `path`, creating its directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be written.
     pub fn save(&self, path: &Path) -> Result<()> {
         if let Some(parent) = path.parent() {
             if !parent.as_os_str().is_empty() {
@@ -38,6 +51,11 @@ impl PublicInfo {
         Ok(())
     }
 
+    /// Record the iroh node id in the public info at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Like [`PublicInfo::load`] and [`PublicInfo::save`].
     pub fn write_iroh_node_id(path: &Path, node_id: impl Into<String>) -> Result<Self> {
         let mut info = Self::load(path)?;
         info.iroh_node_id = Some(node_id.into());
@@ -45,14 +63,27 @@ impl PublicInfo {
         Ok(info)
     }
 
+    /// Record the QUIC certificate in the public info at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Like [`PublicInfo::load`] and [`PublicInfo::save`].
+    pub fn write_quic_cert(path: &Path, cert_hex: impl Into<String>) -> Result<Self> {
+        let mut info = Self::load(path)?;
+        info.quic_cert = Some(cert_hex.into());
+        info.save(path)?;
+        Ok(info)
+    }
+
+    #[must_use]
     pub fn default_path() -> PathBuf {
         PathBuf::from(PUBLIC_FILE_NAME)
     }
 }
 
+#[must_use]
 pub fn path_for_config(config_path: Option<&Path>) -> PathBuf {
-    match config_path {
-        Some(path) => path.with_file_name(PUBLIC_FILE_NAME),
-        None => PublicInfo::default_path(),
-    }
+    config_path.map_or_else(PublicInfo::default_path, |path| {
+        path.with_file_name(PUBLIC_FILE_NAME)
+    })
 }
@@ -0,0 +1,161 @@
+// Arena mode: a table of bots that plays hand after hand without a human
+// client, for benchmarking bot strategies.
+//
+// The bot driver plays the hands like at any other table. The arena task only
+// waits for each showdown, reports the hand on the broadcast channel (picked
+// up by the `/arena` websocket endpoint) and deals the next hand.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use mcg_shared::{
+    ActionEvent, ActionKind, ArenaHandSummary, ArenaPlayerResult, Backend2FrontendMsg,
+    BettingLimit, GameAction, GameStatePublic, PlayerConfig, PlayerId, Stage, TableConfig, TableId,
+};
+use tokio::time::{sleep, Duration};
+
+use crate::server::state::{
+    broadcast_state, create_table, current_state_public, start_new_hand_and_print, AppState,
+};
+
+/// Stack busted arena bots are topped up to before the next hand
+const ARENA_STACK: u32 = 1000;
+const POLL_MS: u64 = 50;
+
+/// Open the arena table and start dealing hands in the background.
+///
+/// Call this before the bot driver starts, so the stacks recorded for the
+/// first hand do not include any bot action yet.
+///
+/// # Errors
+///
+/// If the arena table cannot be created.
+pub async fn start_arena(state: AppState) -> Result<TableId> {
+    let bots = state.config.read().await.bots.max(2);
+    let players = (0..bots)
+        .map(|i| PlayerConfig {
+            id: PlayerId(i),
+            name: format!("Bot {}", i + 1),
+            is_bot: true,
+            avatar_color: None,
+            preferred_seat: None,
+        })
+        .collect();
+    let config = TableConfig {
+        players,
+        sb: 5,
+        bb: 10,
+        bounty: None,
+        betting_limit: BettingLimit::default(),
+    };
+    let table_id = create_table(&state, config).await?;
+    // Blinds are already posted; add them back to get the stacks before the hand
+    let start_stacks = current_state_public(&state, table_id)
+        .await
+        .map(|gs| {
+            gs.players
+                .iter()
+                .map(|p| (p.id, p.stack + p.bet_this_round))
+                .collect()
+        })
+        .unwrap_or_default();
+    tracing::info!(table_id, bots, "arena started");
+    tokio::spawn(run_arena(state, table_id, start_stacks));
+    Ok(table_id)
+}
+
+/// Deal a new hand after every showdown at the arena table, forever.
+async fn run_arena(state: AppState, table_id: TableId, mut start_stacks: HashMap<PlayerId, u32>) {
+    let mut hand_number = 0;
+    loop {
+        let Some(gs) = current_state_public(&state, table_id).await else {
+            tracing::warn!(table_id, "arena table closed");
+            return;
+        };
+        if gs.stage != Stage::Showdown {
+            sleep(Duration::from_millis(POLL_MS)).await;
+            continue;
+        }
+
+        hand_number += 1;
+        let hand_summary = summarize_hand(&gs, table_id, hand_number, &start_stacks);
+        let _ = state.broadcaster.send((
+            table_id,
+            Backend2FrontendMsg::ArenaHandComplete { hand_summary },
+        ));
+
+        // Leave watchers a moment to see the showdown, like the bots' think time
+        let delay = state.config.read().await.bot_delay;
+        sleep(Duration::from_millis(delay)).await;
+
+        start_stacks = top_up_busted_bots(&state, table_id).await;
+        if let Err(e) = start_new_hand_and_print(&state, table_id).await {
+            tracing::error!(error = %e, "arena failed to deal the next hand");
+            return;
+        }
+        broadcast_state(&state, table_id).await;
+    }
+}
+
+/// Give busted bots a fresh stack and return everyone's stack before the next hand.
+// The game is borrowed from the table lock until the stacks are read
+#[allow(clippy::significant_drop_tightening)]
+async fn top_up_busted_bots(state: &AppState, table_id: TableId) -> HashMap<PlayerId, u32> {
+    let mut tables = state.tables.write().await;
+    let Some(game) = tables.get_mut(table_id).and_then(|l| l.game.as_mut()) else {
+        return HashMap::new();
+    };
+    for p in game.players.iter_mut().filter(|p| p.stack == 0) {
+        tracing::info!(player = %p.name, "arena bot busted, rebuying");
+        p.stack = ARENA_STACK;
+    }
+    game.players.iter().map(|p| (p.id, p.stack)).collect()
+}
+
+fn summarize_hand(
+    gs: &GameStatePublic,
+    table_id: TableId,
+    hand_number: u32,
+    start_stacks: &HashMap<PlayerId, u32>,
+) -> ArenaHandSummary {
+    let mut stage = Stage::Preflop;
+    let mut vpip = Vec::new();
+    for event in &gs.action_log {
+        match event {
+            ActionEvent::GameAction(GameAction::StageChanged(s)) => stage = *s,
+            ActionEvent::PlayerAction { player_id, action } if stage == Stage::Preflop => {
+                if matches!(
+                    action,
+                    ActionKind::Call(_)
+                        | ActionKind::Bet(_)
+                        | ActionKind::Raise { .. }
+                        | ActionKind::AllIn { .. }
+                ) {
+                    vpip.push(*player_id);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let players = gs
+        .players
+        .iter()
+        .map(|p| {
+            let start = start_stacks.get(&p.id).copied().unwrap_or(p.stack);
+            ArenaPlayerResult {
+                player_id: p.id,
+                name: p.name.clone(),
+                stack: p.stack,
+                chips_won: i64::from(p.stack) - i64::from(start),
+                won: gs.winner_ids.contains(&p.id),
+                vpip: vpip.contains(&p.id),
+            }
+        })
+        .collect();
+    ArenaHandSummary {
+        table_id,
+        hand_number,
+        players,
+    }
+}
@@ -0,0 +1,80 @@
+//! Markdown articles bundled into the server binary and served over HTTP.
+
+use mcg_shared::ArticleMeta;
+
+/// Article id and markdown source; the first `# ` line is the title.
+const ARTICLES: &[(&str, &str)] = &[
+    (
+        "texas-holdem-rules",
+        include_str!("../../articles/texas-holdem-rules.md"),
+    ),
+    (
+        "hand-rankings",
+        include_str!("../../articles/hand-rankings.md"),
+    ),
+    (
+        "mental-poker",
+        include_str!("../../articles/mental-poker.md"),
+    ),
+];
+
+/// Title and summary of every bundled article, in display order.
+#[must_use]
+pub fn list_articles() -> Vec<ArticleMeta> {
+    ARTICLES
+        .iter()
+        .map(|(id, markdown)| article_meta(id, markdown))
+        .collect()
+}
+
+/// Markdown body of the article with the given id.
+#[must_use]
+pub fn article_body(id: &str) -> Option<&'static str> {
+    ARTICLES
+        .iter()
+        .find(|(article_id, _)| *article_id == id)
+        .map(|(_, markdown)| *markdown)
+}
+
+/// Title from the leading `# ` heading (falling back to the id) and the first
+/// paragraph as summary.
+fn article_meta(id: &str, markdown: &str) -> ArticleMeta {
+    let title = markdown
+        .lines()
+        .find_map(|line| line.strip_prefix("# "))
+        .unwrap_or(id)
+        .trim()
+        .to_string();
+    let summary = markdown
+        .split("\n\n")
+        .map(str::trim)
+        .find(|paragraph| !paragraph.is_empty() && !paragraph.starts_with('#'))
+        .unwrap_or_default()
+        .replace('\n', " ");
+    ArticleMeta {
+        id: id.to_string(),
+        title,
+        summary,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn meta_uses_heading_and_first_paragraph() {
+        let meta = article_meta("x", "# Title\n\nFirst line\ncontinued.\n\n## More\n\nRest");
+        assert_eq!(meta.title, "Title");
+        assert_eq!(meta.summary, "First line continued.");
+    }
+
+    #[test]
+    fn every_bundled_article_has_a_title_and_body() {
+        for meta in list_articles() {
+            assert!(!meta.title.is_empty() && !meta.summary.is_empty());
+            assert!(article_body(&meta.id).is_some());
+        }
+        assert!(article_body("missing").is_none());
+    }
+}
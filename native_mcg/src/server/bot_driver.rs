@@ -1,6 +1,6 @@
-use super::state::AppState;
+use super::state::{apply_action_to_game, AppState};
 use crate::bot::BotContext;
-use mcg_shared::{PlayerId, Stage};
+use mcg_shared::{PlayerAction, PlayerId, Stage, TableId};
 use rand::random;
 use tokio::time::{sleep, Duration};
 
@@ -9,39 +9,34 @@ const IDLE_SLEEP_MS: u64 = 50;
 /// Continuously drive bots whenever it is their turn.
 ///
 /// This loop runs for the lifetime of the server. When no bots are scheduled to
-/// act it idles with a short sleep, otherwise it produces a single bot action,
-/// broadcasts the state, and waits for a randomized delay before re-checking.
+/// act on any table it idles with a short sleep, otherwise it applies a single
+/// bot action (the observers broadcast the table's state) and waits for a
+/// randomized delay before re-checking.
 pub async fn run_bot_driver(state: AppState) {
-    let mut last_logged_bot: Option<PlayerId> = None;
+    let mut last_logged_bot: Option<(TableId, PlayerId)> = None;
     let mut logged_idle = false;
 
     loop {
         let bot_to_act = {
-            let lobby = state.lobby.read().await;
-            let game = match &lobby.game {
-                Some(game) if game.stage != Stage::Showdown => Some(game),
-                _ => {
-                    last_logged_bot = None;
-                    None
-                }
-            };
-
-            game.and_then(|game| {
-                let idx = game.to_act;
-                game.players.get(idx).and_then(|player| {
-                    if lobby.bots.contains(&player.id) {
-                        Some((player.id, player.name.clone()))
-                    } else {
-                        None
-                    }
-                })
+            let tables = state.tables.read().await;
+            tables.table_ids().into_iter().find_map(|table_id| {
+                let lobby = tables.get(table_id)?;
+                let game = lobby
+                    .game
+                    .as_ref()
+                    .filter(|game| game.stage != Stage::Showdown)?;
+                let player = game.players.get(game.to_act)?;
+                lobby
+                    .bots
+                    .contains(&player.id)
+                    .then(|| (table_id, player.id, player.name.clone()))
             })
         };
 
-        if let Some((bot_id, bot_name)) = bot_to_act {
-            if last_logged_bot != Some(bot_id) {
-                tracing::debug!(player = %bot_name, player_id = ?bot_id, "Bot driver: bot turn detected");
-                last_logged_bot = Some(bot_id);
+        if let Some((table_id, bot_id, bot_name)) = bot_to_act {
+            if last_logged_bot != Some((table_id, bot_id)) {
+                tracing::debug!(table_id, player = %bot_name, player_id = ?bot_id, "Bot driver: bot turn detected");
+                last_logged_bot = Some((table_id, bot_id));
             }
             logged_idle = false;
 
@@ -50,14 +45,12 @@ pub async fn run_bot_driver(state: AppState) {
                 cfg.bot_delay_range()
             };
 
-            if !process_single_bot_action(&state).await {
+            if !process_single_bot_action(&state, table_id).await {
                 tracing::warn!(player = %bot_name, player_id = ?bot_id, "Bot driver: bot action failed or skipped");
                 sleep(Duration::from_millis(IDLE_SLEEP_MS)).await;
                 continue;
             }
 
-            crate::server::broadcast_state(&state).await;
-
             let delay_ms = pick_delay(min_delay, max_delay);
             tracing::trace!(delay_ms, "Bot driver: sleeping before next bot action");
             sleep(Duration::from_millis(delay_ms)).await;
@@ -81,73 +74,83 @@ fn pick_delay(min_ms: u64, max_ms: u64) -> u64 {
     min_ms + jitter
 }
 
-/// Process a single bot action and return whether it was successful
-async fn process_single_bot_action(state: &AppState) -> bool {
-    let mut lobby_w = state.lobby.write().await;
-
-    // Clone the bot manager first to avoid borrowing conflicts
-    let bot_manager = lobby_w.bot_manager.clone();
-    let bots = lobby_w.bots.clone();
-
-    if let Some(game) = &mut lobby_w.game {
-        let actor_idx = game.to_act;
-
-        // Double-check that the current player is still a bot
-        if let Some(player) = game.players.get(actor_idx) {
-            if !bots.contains(&player.id) {
-                return false; // Not a bot anymore
-            }
-        } else {
-            return false; // Invalid player index
-        }
-
-        // Generate bot action
-        let need = game.current_bet.saturating_sub(game.round_bets[actor_idx]);
-        let context = BotContext {
-            stack: game.players[actor_idx].stack,
-            call_amount: need,
-            current_bet: game.current_bet,
-            big_blind: game.bb,
-            stage: game.stage,
-            position: actor_idx,
-            total_players: game.players.len(),
-        };
-
-        let action = match bot_manager.generate_action(&context) {
-            Ok(action) => action,
-            Err(e) => {
-                tracing::error!("Bot manager failed to generate action: {}", e);
-                // Fallback to a safe action
-                if need == 0 {
-                    mcg_shared::PlayerAction::CheckCall
-                } else {
-                    mcg_shared::PlayerAction::Fold
-                }
-            }
-        };
+/// Process a single bot action at a table and return whether it was successful
+pub(crate) async fn process_single_bot_action(state: &AppState, table_id: TableId) -> bool {
+    let Some((actor_idx, action, player_name, player_stack)) =
+        choose_bot_action(state, table_id).await
+    else {
+        return false;
+    };
+
+    // Clone action for logging
+    let action_for_log = action.clone();
+
+    // Apply the bot action
+    let Some(e) = apply_action_to_game(state, table_id, actor_idx, action).await else {
+        tracing::info!(
+            "🤖 Bot {} took action: {:?} (stack: {})",
+            player_name,
+            action_for_log,
+            player_stack
+        );
+        return true;
+    };
+    tracing::error!("❌ Bot {} failed to apply action: {}", player_name, e);
+    false
+}
 
-        // Clone action for logging
-        let action_for_log = action.clone();
-        let player_name = game.players[actor_idx].name.clone();
-        let player_stack = game.players[actor_idx].stack;
+/// Let the bot manager pick an action for the bot to act at a table. Returns
+/// the actor index, the action, and the bot's name and stack for logging.
+// The acting player is borrowed from the table lock until the end
+#[allow(clippy::significant_drop_tightening)]
+async fn choose_bot_action(
+    state: &AppState,
+    table_id: TableId,
+) -> Option<(usize, PlayerAction, String, u32)> {
+    let tables = state.tables.read().await;
+    let lobby = tables.get(table_id)?;
+    let game = lobby.game.as_ref()?;
+    let actor_idx = game.to_act;
+
+    // Double-check that the current player is still a bot
+    let player = game.players.get(actor_idx)?;
+    if !lobby.bots.contains(&player.id) {
+        return None; // Not a bot anymore
+    }
 
-        // Apply the bot action
-        match game.apply_player_action(actor_idx, action) {
-            Ok(_) => {
-                tracing::info!(
-                    "🤖 Bot {} took action: {:?} (stack: {})",
-                    player_name,
-                    action_for_log,
-                    player_stack
-                );
-                true
-            }
-            Err(e) => {
-                tracing::error!("❌ Bot {} failed to apply action: {}", player_name, e);
-                false
+    // Generate bot action
+    let need = game.round.to_call(actor_idx);
+    let context = BotContext {
+        stack: player.stack,
+        call_amount: need,
+        current_bet: game.round.current_bet,
+        big_blind: game.bb,
+        pot: game.pot,
+        stage: game.stage,
+        position: actor_idx,
+        total_players: game.players.len(),
+        opponents: game
+            .players
+            .iter()
+            .filter(|p| p.id != player.id)
+            .map(|p| {
+                let opponent_stats = lobby.player_stats.get(&p.id).copied();
+                (p.id, opponent_stats.unwrap_or_default())
+            })
+            .collect(),
+    };
+
+    let action = match lobby.bot_manager.generate_action(&context) {
+        Ok(action) => action,
+        Err(e) => {
+            tracing::error!("Bot manager failed to generate action: {}", e);
+            // Fallback to a safe action
+            if need == 0 {
+                PlayerAction::CheckCall
+            } else {
+                PlayerAction::Fold
             }
         }
-    } else {
-        false
-    }
+    };
+    Some((actor_idx, action, player.name.clone(), player.stack))
 }
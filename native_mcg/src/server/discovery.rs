@@ -0,0 +1,68 @@
+//! LAN presence announcements so that native clients can discover servers.
+
+use std::net::Ipv4Addr;
+
+use mcg_shared::{ServerAd, DISCOVERY_GROUP, DISCOVERY_PORT};
+use tokio::net::UdpSocket;
+use tokio::time::{interval, Duration};
+
+use super::state::AppState;
+
+const ANNOUNCE_INTERVAL: Duration = Duration::from_secs(5);
+const SERVER_NAME: &str = "MCG Server";
+const MAX_PLAYERS: usize = 9;
+
+/// Multicast a [`ServerAd`] every few seconds for the lifetime of the server.
+///
+/// Discovery is best effort: if no socket can be opened the task logs a warning
+/// and exits, and failed sends are retried on the next tick.
+pub async fn run_discovery_broadcast(state: AppState, port: u16) {
+    let socket = match UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await {
+        Ok(socket) => socket,
+        Err(e) => {
+            tracing::warn!(error = %e, "LAN discovery disabled: cannot open UDP socket");
+            return;
+        }
+    };
+    // Keep announcements on the local network segment
+    if let Err(e) = socket.set_multicast_ttl_v4(1) {
+        tracing::debug!(error = %e, "failed to set multicast TTL");
+    }
+
+    let mut ticker = interval(ANNOUNCE_INTERVAL);
+    loop {
+        ticker.tick().await;
+        let ad = server_ad(&state, port).await;
+        let payload = match serde_json::to_vec(&ad) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::error!("failed to serialize server announcement: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = socket
+            .send_to(&payload, (DISCOVERY_GROUP, DISCOVERY_PORT))
+            .await
+        {
+            tracing::debug!(error = %e, "failed to send server announcement");
+        }
+    }
+}
+
+/// The announcement describing this server right now.
+pub async fn server_ad(state: &AppState, port: u16) -> ServerAd {
+    let players = state
+        .tables
+        .read()
+        .await
+        .summaries()
+        .iter()
+        .map(|t| t.player_count)
+        .sum();
+    ServerAd {
+        name: SERVER_NAME.to_string(),
+        port,
+        players,
+        max: MAX_PLAYERS,
+    }
+}
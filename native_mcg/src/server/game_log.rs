@@ -0,0 +1,163 @@
+// Machine readable game log: every hand and event as one JSON object per line.
+//
+// Unlike the human readable `LogObserver` output, each entry carries a
+// timestamp and the public table state, so `log_replay` can play a session
+// back without running the game engine.
+
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use mcg_shared::{ActionEvent, GameStatePublic, HandResult, TableId};
+use serde::{Deserialize, Serialize};
+
+use crate::server::observer::GameObserver;
+use crate::server::state::{current_state_public, AppState};
+
+/// One line of the game log.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GameLogEntry {
+    /// Milliseconds since the Unix epoch when the entry was written
+    pub timestamp_ms: u64,
+    pub table_id: TableId,
+    #[serde(flatten)]
+    pub record: GameLogRecord,
+}
+
+/// What happened at the table.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GameLogRecord {
+    /// A new hand was dealt; `state` is the table right after the deal.
+    HandStart {
+        hand_number: u32,
+        state: GameStatePublic,
+    },
+    /// An event of the action log, with the table state after the mutation
+    /// that caused it.
+    Action {
+        event: ActionEvent,
+        state: GameStatePublic,
+    },
+    /// The hand reached showdown.
+    HandEnd {
+        hand_number: u32,
+        results: Vec<HandResult>,
+    },
+}
+
+/// Milliseconds since the Unix epoch.
+#[must_use]
+pub fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+}
+
+/// Read all entries of the game log at `path`, skipping blank lines.
+///
+/// # Errors
+///
+/// If the file cannot be read or an entry cannot be parsed.
+pub fn read_game_log(path: &Path) -> Result<Vec<GameLogEntry>> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("opening game log '{}'", path.display()))?;
+    let mut entries = Vec::new();
+    for (idx, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.with_context(|| format!("reading game log '{}'", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str(&line)
+            .with_context(|| format!("parsing line {} of '{}'", idx + 1, path.display()))?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Appends a `GameLogEntry` for every hand and event to a file.
+pub struct GameLogObserver {
+    file: Mutex<std::fs::File>,
+}
+
+impl GameLogObserver {
+    /// Open `path` for appending, creating it if needed.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be opened.
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("opening game log '{}'", path.display()))?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+
+    fn write(&self, table_id: TableId, record: GameLogRecord) {
+        let entry = GameLogEntry {
+            timestamp_ms: now_ms(),
+            table_id,
+            record,
+        };
+        let line = match serde_json::to_string(&entry) {
+            Ok(line) => line,
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to serialize game log entry");
+                return;
+            }
+        };
+        let Ok(mut file) = self.file.lock() else {
+            return;
+        };
+        if let Err(e) = writeln!(file, "{line}") {
+            tracing::warn!(error = %e, "failed to write to game log");
+        }
+    }
+}
+
+/// Number of the hand currently played at the table, counting from 1.
+async fn current_hand_number(state: &AppState, table_id: TableId) -> u32 {
+    let tables = state.tables.read().await;
+    tables
+        .get(table_id)
+        .map_or(1, crate::server::state::Lobby::hand_number)
+}
+
+#[async_trait]
+impl GameObserver for GameLogObserver {
+    async fn on_action(&self, state: &AppState, table_id: TableId, event: &ActionEvent) {
+        let Some(gs) = current_state_public(state, table_id).await else {
+            return;
+        };
+        let record = GameLogRecord::Action {
+            event: event.clone(),
+            state: gs,
+        };
+        self.write(table_id, record);
+    }
+
+    async fn on_hand_end(&self, state: &AppState, table_id: TableId, results: &[HandResult]) {
+        let record = GameLogRecord::HandEnd {
+            hand_number: current_hand_number(state, table_id).await,
+            results: results.to_vec(),
+        };
+        self.write(table_id, record);
+    }
+
+    async fn on_hand_start(&self, state: &AppState, table_id: TableId) {
+        let Some(gs) = current_state_public(state, table_id).await else {
+            return;
+        };
+        let record = GameLogRecord::HandStart {
+            hand_number: current_hand_number(state, table_id).await,
+            state: gs,
+        };
+        self.write(table_id, record);
+    }
+}
@@ -4,15 +4,289 @@
 // Handlers reuse the centralized backend handler `dispatch_client_message` to ensure
 // consistent behavior across transports (iroh, websocket, HTTP).
 
-use axum::{extract::State, Json};
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
 
 use crate::server::AppState;
-use mcg_shared::{Frontend2BackendMsg, Backend2FrontendMsg};
+use mcg_shared::{
+    ArticleMeta, Backend2FrontendMsg, Frontend2BackendMsg, LeaderboardEntry, PairingInvite,
+    PeerInfo, PlayerId, Stage, TableId, TableSummary, DEFAULT_TABLE_ID,
+};
 
-/// Unified handler for all ClientMsg variants. Returns the serialized ServerMsg response.
+/// Query parameters selecting a table; omitted means the default table.
+#[derive(serde::Deserialize)]
+pub struct TableQuery {
+    table: Option<TableId>,
+}
+
+/// Query parameters of the health check.
+#[derive(serde::Deserialize)]
+pub struct HealthQuery {
+    #[serde(default)]
+    detail: bool,
+}
+
+/// Stage and bots of one table in the detailed health check.
+#[derive(serde::Serialize)]
+pub struct TableHealth {
+    pub table_id: TableId,
+    /// `None` while no game has been started at the table
+    pub stage: Option<Stage>,
+    pub bots: usize,
+}
+
+/// Response of `/health?detail=true`.
+#[derive(serde::Serialize)]
+pub struct HealthDetail {
+    pub ok: bool,
+    pub uptime_secs: u64,
+    pub ws_connections_active: u64,
+    pub hands_played: u64,
+    pub bot_count: usize,
+    /// Heap bytes in use, if the server binary counts allocations
+    pub memory_bytes: Option<u64>,
+    pub iroh_node_id: Option<String>,
+    pub tables: Vec<TableHealth>,
+}
+
+/// Health check for load balancers: `{"ok": true}`, or server metadata with
+/// `?detail=true`.
+pub async fn health_handler(
+    State(state): State<AppState>,
+    Query(query): Query<HealthQuery>,
+) -> Response {
+    if !query.detail {
+        return Json(serde_json::json!({ "ok": true })).into_response();
+    }
+    let tables = {
+        let tables = state.tables.read().await;
+        tables
+            .table_ids()
+            .into_iter()
+            .filter_map(|table_id| {
+                let lobby = tables.get(table_id)?;
+                Some(TableHealth {
+                    table_id,
+                    stage: lobby.game.as_ref().map(|g| g.stage),
+                    bots: lobby.bots.len(),
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+    let metrics = &state.metrics;
+    Json(HealthDetail {
+        ok: true,
+        uptime_secs: metrics.uptime().as_secs(),
+        ws_connections_active: metrics.ws_connections_active(),
+        hands_played: metrics.hands_played(),
+        bot_count: tables.iter().map(|t| t.bots).sum(),
+        memory_bytes: crate::server::metrics::allocated_bytes(),
+        iroh_node_id: iroh_node_id(&state),
+        tables,
+    })
+    .into_response()
+}
+
+/// Server counters in the Prometheus text exposition format.
+pub async fn metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        [("content-type", "text/plain; version=0.0.4; charset=utf-8")],
+        state.metrics.render_prometheus(),
+    )
+}
+
+/// Unified handler for all `ClientMsg` variants. Returns the serialized `ServerMsg` response.
+///
+/// HTTP is stateless, so table-scoped messages without a table id target the default table.
+/// Without a session there is no seat to check a `Rebuy` against, so rebuys are refused, and
+/// states are sent as a spectator sees them, with every hole card hidden until showdown.
 pub async fn message_handler(
     State(state): State<AppState>,
     Json(cm): Json<Frontend2BackendMsg>,
 ) -> Json<Backend2FrontendMsg> {
-    Json(crate::server::dispatch_client_message(&state, cm).await)
+    if matches!(cm, Frontend2BackendMsg::Rebuy { .. }) {
+        return Json(Backend2FrontendMsg::Error(
+            "Rebuys need a websocket, iroh or QUIC connection".into(),
+        ));
+    }
+    match crate::server::dispatch_client_message(&state, DEFAULT_TABLE_ID, cm).await {
+        Backend2FrontendMsg::State(gs) => Json(Backend2FrontendMsg::State(gs.seen_by(None))),
+        other => Json(other),
+    }
+}
+
+/// List active tables with their player count and blind levels.
+pub async fn tables_handler(State(state): State<AppState>) -> Json<Vec<TableSummary>> {
+    Json(state.tables.read().await.summaries())
+}
+
+/// `/articles` is also a client-side route, so browser navigations asking for
+/// HTML get the SPA instead of the JSON API.
+fn wants_html(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/html"))
+}
+
+/// List the bundled articles with their title and summary.
+pub async fn articles_handler(headers: HeaderMap) -> Response {
+    if wants_html(&headers) {
+        return crate::server::run::serve_index().await.into_response();
+    }
+    Json::<Vec<ArticleMeta>>(crate::server::articles::list_articles()).into_response()
+}
+
+/// Serve the markdown body of one article.
+pub async fn article_handler(Path(id): Path<String>, headers: HeaderMap) -> Response {
+    if wants_html(&headers) {
+        return crate::server::run::serve_index().await.into_response();
+    }
+    crate::server::articles::article_body(&id).map_or_else(
+        || (StatusCode::NOT_FOUND, format!("no article '{id}'")).into_response(),
+        |body| {
+            (
+                StatusCode::OK,
+                [("content-type", "text/markdown; charset=utf-8")],
+                body,
+            )
+                .into_response()
+        },
+    )
+}
+
+/// All-time player standings, best first.
+pub async fn leaderboard_handler(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    if wants_html(&headers) {
+        return crate::server::run::serve_index().await.into_response();
+    }
+    Json::<Vec<LeaderboardEntry>>(state.leaderboard.read().await.entries()).into_response()
+}
+
+/// Directory of the UI translations, relative to the repo root like `pkg`
+const LOCALES_DIR: &str = "locales";
+
+/// Serve the UI translations of one language as JSON.
+pub async fn locale_handler(Path(lang): Path<String>) -> Response {
+    let valid = !lang.is_empty()
+        && lang.len() <= 16
+        && lang
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return (
+            StatusCode::BAD_REQUEST,
+            format!("invalid language '{lang}'"),
+        )
+            .into_response();
+    }
+    let path = std::path::Path::new(LOCALES_DIR).join(format!("{lang}.json"));
+    tokio::fs::read_to_string(&path).await.map_or_else(
+        |_| (StatusCode::NOT_FOUND, format!("no locale '{lang}'")).into_response(),
+        |json| {
+            (
+                StatusCode::OK,
+                [("content-type", "application/json; charset=utf-8")],
+                json,
+            )
+                .into_response()
+        },
+    )
+}
+
+/// Kick a player, replacing them with a bot. Requires the configured admin
+/// token as `Authorization: Bearer <token>`.
+pub async fn kick_player_handler(
+    State(state): State<AppState>,
+    Path(id): Path<usize>,
+    Query(query): Query<TableQuery>,
+    headers: HeaderMap,
+) -> Response {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    let authorized = match token {
+        Some(token) => state.config.read().await.is_admin_token(token),
+        None => false,
+    };
+    if !authorized {
+        return (StatusCode::UNAUTHORIZED, "missing or invalid admin token").into_response();
+    }
+
+    let table_id = query.table.unwrap_or(DEFAULT_TABLE_ID);
+    match crate::server::state::kick_player(&state, table_id, PlayerId(id)).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+/// Serve the session hand history of a table as plain text (PokerStars-like format).
+pub async fn history_handler(
+    State(state): State<AppState>,
+    Query(query): Query<TableQuery>,
+) -> impl IntoResponse {
+    let table_id = query.table.unwrap_or(DEFAULT_TABLE_ID);
+    let text = crate::server::state::session_hand_history(&state, table_id).await;
+    (
+        axum::http::StatusCode::OK,
+        [("content-type", "text/plain; charset=utf-8")],
+        text,
+    )
+}
+
+/// Iroh node id the server stored once its endpoint came up.
+fn iroh_node_id(state: &AppState) -> Option<String> {
+    let path = crate::public::path_for_config(state.config_path.as_deref());
+    crate::public::PublicInfo::load(&path).ok()?.iroh_node_id
+}
+
+/// Report the server's iroh node id as `{"node_id": ...}`.
+pub async fn node_id_handler(State(state): State<AppState>) -> Response {
+    iroh_node_id(&state).map_or_else(
+        || (StatusCode::SERVICE_UNAVAILABLE, "iroh endpoint not ready").into_response(),
+        |node_id| Json(serde_json::json!({ "node_id": node_id })).into_response(),
+    )
+}
+
+/// Iroh nodes the server knows of, most recently seen first, as a JSON
+/// list of `PeerInfo`.
+pub async fn iroh_peers_handler(State(state): State<AppState>) -> Json<Vec<PeerInfo>> {
+    let now = crate::server::game_log::now_ms();
+    Json(state.iroh_peers.read().await.list(now))
+}
+
+/// Create an invite to a table for the pairing screen. The server address in
+/// the invite is the `Host` the request was sent to.
+pub async fn pairing_code_handler(
+    State(state): State<AppState>,
+    Query(query): Query<TableQuery>,
+    headers: HeaderMap,
+) -> Response {
+    let Some(node_id) = iroh_node_id(&state) else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "iroh endpoint not ready").into_response();
+    };
+    let Some(host) = headers.get(header::HOST).and_then(|v| v.to_str().ok()) else {
+        return (StatusCode::BAD_REQUEST, "missing Host header").into_response();
+    };
+    let table_id = query.table.unwrap_or(DEFAULT_TABLE_ID);
+    if state.tables.read().await.get(table_id).is_none() {
+        return (StatusCode::NOT_FOUND, format!("Unknown table {table_id}")).into_response();
+    }
+
+    let invite = PairingInvite {
+        server_address: host.to_string(),
+        node_id: node_id.clone(),
+        table_id,
+    };
+    Json(Backend2FrontendMsg::PairingCode {
+        node_id,
+        invite_code: invite.to_code(),
+    })
+    .into_response()
 }
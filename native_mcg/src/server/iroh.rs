@@ -20,17 +20,23 @@ use anyhow::{Context, Result};
 use tokio::io::AsyncBufReadExt;
 use tokio::io::BufReader;
 use tokio::sync::broadcast;
+use tracing::Instrument;
 
 use crate::public::{path_for_config, PublicInfo};
-use crate::server::state::subscribe_connection;
+use crate::server::session::ClientSession;
+use crate::server::state::{current_state_public, subscribe_connection, TableBroadcast};
 use crate::server::AppState;
-use crate::transport::send_server_msg_to_writer;
-use mcg_shared::{Frontend2BackendMsg, Backend2FrontendMsg};
+use crate::transport::{send_server_msg_to_writer, StateDiffer};
+use mcg_shared::{Frontend2BackendMsg, Backend2FrontendMsg, TableId, DEFAULT_TABLE_ID};
 
 /// Public entrypoint spawned by server startup
 ///
 /// Refactored to delegate sub-tasks to smaller helper functions to improve
 /// readability and make the high-level flow easier to follow.
+///
+/// # Errors
+///
+/// If the iroh endpoint cannot be bound.
 pub async fn spawn_iroh_listener(state: AppState) -> Result<()> {
     // Keep the iroh-specific imports local to this function so the module does
     // not require iroh at compile time when the feature is disabled.
@@ -41,7 +47,7 @@ pub async fn spawn_iroh_listener(state: AppState) -> Result<()> {
     const ALPN: &[u8] = b"mcg/iroh/1";
 
     // Obtain or generate the node secret key (may persist to config)
-    let secret_key: SecretKey = load_or_generate_iroh_secret(state.clone()).await;
+    let secret_key: SecretKey = load_or_generate_iroh_secret(&state).await;
 
     // Build and bind the iroh endpoint (advertising our ALPN)
     let endpoint = build_iroh_endpoint(secret_key, ALPN).await?;
@@ -60,15 +66,17 @@ pub async fn spawn_iroh_listener(state: AppState) -> Result<()> {
     // Print endpoint id for CLI users (renamed from node_id in iroh 0.95)
     let pk = endpoint.id();
 
-    // Nice readable banner for the user
-    println!("\n\x1b[1;32m=== Iroh Endpoint Ready ===\x1b[0m");
-    println!("\x1b[1mNode ID:\x1b[0m {}", pk);
-    println!("\x1b[1;32m===========================\x1b[0m\n");
+    tracing::info!(node_id = %pk, "iroh endpoint ready");
 
     // Keep structured info for debug mode
     let addr = endpoint.addr();
     let relay_urls: Vec<_> = addr.relay_urls().collect();
     tracing::debug!(iroh_node_id = %pk, iroh_addr = ?addr, relay_urls = ?relay_urls);
+    state.iroh_peers.write().await.see(
+        pk.to_string(),
+        relay_urls.first().map(|url| url.to_string()),
+        crate::server::game_log::now_ms(),
+    );
 
     let public_path = path_for_config(state.config_path.as_deref());
     match PublicInfo::write_iroh_node_id(&public_path, pk.to_string()) {
@@ -79,15 +87,15 @@ pub async fn spawn_iroh_listener(state: AppState) -> Result<()> {
     }
 
     // Start the accept loop which will spawn a handler per connection
-    start_iroh_accept_loop(endpoint, state.clone());
+    start_iroh_accept_loop(endpoint, state);
 
     tracing::info!(alpn = %std::str::from_utf8(ALPN).unwrap_or("mcg/iroh/1"), "iroh listener started");
     Ok(())
 }
 
 /// Load an existing iroh secret key from state/config or generate a new one.
-/// Mirrors the original persistence logic but kept in a focused helper.
-async fn load_or_generate_iroh_secret(state: AppState) -> iroh::SecretKey {
+/// A generated key is persisted to the config if the server has one.
+async fn load_or_generate_iroh_secret(state: &AppState) -> iroh::SecretKey {
     use getrandom::getrandom;
     use iroh::SecretKey;
 
@@ -100,60 +108,36 @@ async fn load_or_generate_iroh_secret(state: AppState) -> iroh::SecretKey {
         SecretKey::from_bytes(&arr)
     };
 
-    if let Some(cfg_path) = state.config_path.clone() {
-        // First try a read lock to see if a key already exists in memory.
-        {
-            let cfg_r = state.config.read().await;
-            if let Some(bytes) = cfg_r.iroh_key_bytes() {
-                if bytes.len() >= 32 {
-                    let mut arr = [0u8; 32];
-                    arr.copy_from_slice(&bytes[..32]);
-                    SecretKey::from_bytes(&arr)
-                } else {
-                    // Invalid length in-memory config; fall through to generate-and-save below.
-                    drop(cfg_r);
-                    let sk = generate_new_key();
-                    let mut cfg_w = state.config.write().await;
-                    if let Err(e) = cfg_w.set_iroh_key_bytes_and_save(&cfg_path, &sk.to_bytes()) {
-                        tracing::error!(error = %e, "Failed to save generated iroh key to config '{}'", cfg_path.display());
-                    } else {
-                        tracing::info!(config_path = %cfg_path.display(), "Saved generated iroh key into config");
-                    }
-                    sk
-                }
-            } else {
-                // No key in memory: upgrade to write lock and generate + persist.
-                drop(cfg_r);
-                let sk = generate_new_key();
-                let mut cfg_w = state.config.write().await;
-                // Double-check another writer didn't set the key while we waited for the write lock.
-                if cfg_w.iroh_key_bytes().is_none() {
-                    if let Err(e) = cfg_w.set_iroh_key_bytes_and_save(&cfg_path, &sk.to_bytes()) {
-                        tracing::error!(error = %e, "Failed to save generated iroh key to config '{}'", cfg_path.display());
-                    } else {
-                        tracing::info!(config_path = %cfg_path.display(), "Saved generated iroh key into config");
-                    }
-                    sk
-                } else {
-                    // Another writer added the key: use that one instead.
-                    if let Some(bytes) = cfg_w.iroh_key_bytes() {
-                        let mut arr = [0u8; 32];
-                        arr.copy_from_slice(&bytes[..32]);
-                        SecretKey::from_bytes(&arr)
-                    } else {
-                        // Unlikely: fall back to generated key
-                        sk
-                    }
-                }
-            }
-        }
-    } else {
+    let Some(cfg_path) = state.config_path.as_deref() else {
         // No config path available: generate an ephemeral key (do not persist).
         tracing::warn!(
             "no server config path provided; generating ephemeral iroh key (not persisted)"
         );
-        generate_new_key()
+        return generate_new_key();
+    };
+
+    // Hold the write lock throughout, so no other writer stores a key meanwhile
+    let mut cfg = state.config.write().await;
+    let stored = cfg
+        .iroh_key_bytes()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes.get(..32)?).ok());
+    if let Some(arr) = stored {
+        drop(cfg);
+        return SecretKey::from_bytes(&arr);
     }
+    // No valid key in the config: generate and persist one
+    let sk = generate_new_key();
+    let saved = cfg.set_iroh_key_bytes_and_save(cfg_path, &sk.to_bytes());
+    drop(cfg);
+    match saved {
+        Ok(()) => {
+            tracing::info!(config_path = %cfg_path.display(), "Saved generated iroh key into config");
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to save generated iroh key to config '{}'", cfg_path.display());
+        }
+    }
+    sk
 }
 
 /// Build and bind an iroh Endpoint advertising our ALPN.
@@ -179,29 +163,35 @@ async fn build_iroh_endpoint(
 /// task for each connection. This mirrors the previous inline logic but
 /// keeps the accept loop isolated for clarity.
 fn start_iroh_accept_loop(endpoint: iroh::endpoint::Endpoint, state: AppState) {
-    let ep_accept = endpoint;
-    let state_clone = state.clone();
     tokio::spawn(async move {
         loop {
-            match ep_accept.accept().await {
-                Some(connect_future) => match connect_future.await {
-                    Ok(conn) => {
-                        let remote_node_id = conn.remote_id();
-                        tracing::info!(peer = %remote_node_id, "Accepted new iroh connection");
-                        let state_for_conn = state_clone.clone();
-                        tokio::spawn(async move {
+            let Some(connect_future) = endpoint.accept().await else {
+                // No incoming connection was ready; back off briefly to avoid tight loop.
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                continue;
+            };
+            match connect_future.await {
+                Ok(conn) => {
+                    let remote_node_id = conn.remote_id();
+                    tracing::info!(peer = %remote_node_id, "Accepted new iroh connection");
+                    state.iroh_peers.write().await.see(
+                        remote_node_id.to_string(),
+                        None,
+                        crate::server::game_log::now_ms(),
+                    );
+                    let state_for_conn = state.clone();
+                    let span = tracing::info_span!("iroh_client", name = %remote_node_id);
+                    tokio::spawn(
+                        async move {
                             if let Err(e) = manage_iroh_connection(state_for_conn, conn).await {
                                 tracing::error!(error = %e, "iroh connection handler error");
                             }
-                        });
-                    }
-                    Err(e) => {
-                        tracing::error!(error = %e, "iroh accept/connect error");
-                    }
-                },
-                None => {
-                    // No incoming connection was ready; back off briefly to avoid tight loop.
-                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                        }
+                        .instrument(span),
+                    );
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "iroh accept/connect error");
                 }
             }
         }
@@ -221,7 +211,10 @@ async fn manage_iroh_connection(
 
     tracing::info!(peer = %connection.remote_id(), "Iroh bi-stream established");
 
-    let mut subscription: Option<broadcast::Receiver<Backend2FrontendMsg>> = None;
+    let mut subscription: Option<broadcast::Receiver<TableBroadcast>> = None;
+    let mut table_id = DEFAULT_TABLE_ID;
+    let mut differ = StateDiffer::default();
+    let mut session = ClientSession::default();
 
     let mut line = String::new();
     loop {
@@ -230,8 +223,9 @@ async fn manage_iroh_connection(
             tokio::select! {
                 recv = rx.recv() => {
                     match recv {
-                        Ok(sm) => {
-                            if let Err(e) = send_server_msg_to_writer(&mut send, &sm).await {
+                        Ok((tid, _)) if tid != table_id => {}
+                        Ok((_, sm)) => {
+                            if let Err(e) = send_server_msg_to_writer(&mut send, &differ.encode(sm, session.you_id())).await {
                                 tracing::error!(error = %e, "iroh send error while forwarding broadcast");
                                 break;
                             }
@@ -248,7 +242,7 @@ async fn manage_iroh_connection(
                     match res {
                         Ok(0) => break,
                         Ok(_) => {
-                            if !process_iroh_line(&state, &mut send, &mut subscription, line.trim()).await? {
+                            if !process_iroh_line(&state, &mut send, &mut differ, &mut subscription, &mut table_id, &mut session, line.trim()).await? {
                                 break;
                             }
                         }
@@ -263,7 +257,16 @@ async fn manage_iroh_connection(
             match reader.read_line(&mut line).await {
                 Ok(0) => break,
                 Ok(_) => {
-                    if !process_iroh_line(&state, &mut send, &mut subscription, line.trim()).await?
+                    if !process_iroh_line(
+                        &state,
+                        &mut send,
+                        &mut differ,
+                        &mut subscription,
+                        &mut table_id,
+                        &mut session,
+                        line.trim(),
+                    )
+                    .await?
                     {
                         break;
                     }
@@ -286,7 +289,10 @@ async fn manage_iroh_connection(
 async fn process_iroh_line<W>(
     state: &AppState,
     send: &mut W,
-    subscription: &mut Option<broadcast::Receiver<Backend2FrontendMsg>>,
+    differ: &mut StateDiffer,
+    subscription: &mut Option<broadcast::Receiver<TableBroadcast>>,
+    table_id: &mut TableId,
+    session: &mut ClientSession,
     trimmed: &str,
 ) -> Result<bool>
 where
@@ -304,24 +310,49 @@ where
                         .await;
                 return Ok(true);
             }
-            let sub = subscribe_connection(state).await;
+            let sub = subscribe_connection(state, *table_id).await;
             if let Some(gs) = sub.initial_state {
-                send_server_msg_to_writer(send, &Backend2FrontendMsg::State(gs)).await?;
+                send_server_msg_to_writer(send, &differ.encode(Backend2FrontendMsg::State(gs), session.you_id())).await?;
             }
             *subscription = Some(sub.receiver);
             Ok(true)
         }
+        Ok(Frontend2BackendMsg::Action {
+            table_id: action_table,
+            ..
+        }) if action_table != *table_id => {
+            let msg =
+                Backend2FrontendMsg::Error(format!("Join table {action_table} before acting at it"));
+            let _ = send_server_msg_to_writer(send, &msg).await;
+            Ok(true)
+        }
         Ok(other) => {
             tracing::debug!(client_msg = ?other, "iroh received client message");
-            let resp = crate::server::dispatch_client_message(state, other).await;
-            if let Err(e) = send_server_msg_to_writer(send, &resp).await {
+            if let Err(unauthorized) = session.authorize(&other) {
+                let _ = send_server_msg_to_writer(send, &unauthorized).await;
+                return Ok(true);
+            }
+            let resp = crate::server::dispatch_client_message(state, *table_id, other).await;
+            let joined = match resp {
+                Backend2FrontendMsg::TableJoined(joined) => Some(joined),
+                _ => None,
+            };
+            if let Err(e) = send_server_msg_to_writer(send, &differ.encode(resp, session.you_id())).await {
                 tracing::error!(error = %e, "iroh send error while forwarding response");
                 return Err(e);
             }
+            if let Some(joined) = joined {
+                *table_id = joined;
+                session.leave_seat();
+                if let Some(gs) = current_state_public(state, joined).await {
+                    let msg = differ.encode(Backend2FrontendMsg::State(gs), session.you_id());
+                    send_server_msg_to_writer(send, &msg).await?;
+                }
+            }
             Ok(true)
         }
         Err(e) => {
-            let msg = Backend2FrontendMsg::Error(format!("Invalid JSON message: {}", e));
+            let msg = Backend2FrontendMsg::Error(format!("Invalid JSON message: {e}"));
             let _ = send_server_msg_to_writer(send, &msg).await;
             Ok(true)
         }
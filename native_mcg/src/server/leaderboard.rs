@@ -0,0 +1,223 @@
+// All-time player standings for the `/leaderboard` endpoint.
+//
+// Standings are kept by player name, since player ids are only unique within
+// a game, and last until the server restarts.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use mcg_shared::{HandRankCategory, HandResult, LeaderboardEntry, PlayerId, TableId};
+
+use crate::server::observer::GameObserver;
+use crate::server::state::AppState;
+
+/// Results of one player across all tables
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Standing {
+    hands_won: u32,
+    total_chips_won: i64,
+    sessions_played: u32,
+    best_hand: Option<HandRankCategory>,
+}
+
+/// Standings of every player seated since the server started.
+#[derive(Debug, Default)]
+pub struct Leaderboard {
+    standings: HashMap<String, Standing>,
+    /// Stacks at the start of the hand being played, by table
+    hand_start_stacks: HashMap<TableId, HashMap<PlayerId, u32>>,
+}
+
+impl Leaderboard {
+    /// Count a new game for each of the seated players.
+    pub fn record_session<'a>(&mut self, names: impl IntoIterator<Item = &'a str>) {
+        for name in names {
+            self.standings
+                .entry(name.to_owned())
+                .or_default()
+                .sessions_played += 1;
+        }
+    }
+
+    /// Remember the stacks a hand starts with, to tell what it won or lost.
+    pub fn record_hand_start(&mut self, table_id: TableId, stacks: HashMap<PlayerId, u32>) {
+        self.hand_start_stacks.insert(table_id, stacks);
+    }
+
+    /// Add a finished hand: the players with their stack after the hand,
+    /// the winners and the hands shown down.
+    pub fn record_hand_end(
+        &mut self,
+        table_id: TableId,
+        players: &[(PlayerId, String, u32)],
+        winners: &[PlayerId],
+        results: &[HandResult],
+    ) {
+        let start_stacks = self.hand_start_stacks.remove(&table_id).unwrap_or_default();
+        for (id, name, stack) in players {
+            let standing = self.standings.entry(name.clone()).or_default();
+            if let Some(&start) = start_stacks.get(id) {
+                standing.total_chips_won += i64::from(*stack) - i64::from(start);
+            }
+            if winners.contains(id) {
+                standing.hands_won += 1;
+            }
+            let shown = results
+                .iter()
+                .filter(|r| r.player_id == *id)
+                .map(|r| r.rank.category);
+            standing.best_hand = standing.best_hand.into_iter().chain(shown).max();
+        }
+    }
+
+    /// Every player, most chips won first; ties go to more hands won, then
+    /// to the name.
+    #[must_use]
+    pub fn entries(&self) -> Vec<LeaderboardEntry> {
+        let mut standings: Vec<_> = self.standings.iter().collect();
+        standings.sort_by(|(a_name, a), (b_name, b)| {
+            b.total_chips_won
+                .cmp(&a.total_chips_won)
+                .then(b.hands_won.cmp(&a.hands_won))
+                .then(a_name.cmp(b_name))
+        });
+        standings
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, s))| LeaderboardEntry {
+                rank: u32::try_from(i + 1).unwrap_or(u32::MAX),
+                player_name: name.clone(),
+                hands_won: s.hands_won,
+                total_chips_won: s.total_chips_won,
+                sessions_played: s.sessions_played,
+                best_hand: s.best_hand,
+            })
+            .collect()
+    }
+}
+
+/// Keeps the leaderboard on the `AppState` up to date.
+pub struct LeaderboardObserver;
+
+#[async_trait]
+impl GameObserver for LeaderboardObserver {
+    // The stacks are read through the table lock until the block ends
+    #[allow(clippy::significant_drop_tightening)]
+    async fn on_hand_start(&self, state: &AppState, table_id: TableId) {
+        let stacks = {
+            let tables = state.tables.read().await;
+            let Some(game) = tables.get(table_id).and_then(|l| l.game.as_ref()) else {
+                return;
+            };
+            // The blinds are already posted when the observers hear of the hand
+            game.players
+                .iter()
+                .enumerate()
+                .map(|(seat, p)| {
+                    let posted = game.contributions.get(seat).copied().unwrap_or(0);
+                    (p.id, p.stack + posted)
+                })
+                .collect()
+        };
+        state
+            .leaderboard
+            .write()
+            .await
+            .record_hand_start(table_id, stacks);
+    }
+
+    // The players are read through the table lock until the block ends
+    #[allow(clippy::significant_drop_tightening)]
+    async fn on_hand_end(&self, state: &AppState, table_id: TableId, results: &[HandResult]) {
+        let (players, winners) = {
+            let tables = state.tables.read().await;
+            let Some(game) = tables.get(table_id).and_then(|l| l.game.as_ref()) else {
+                return;
+            };
+            let players: Vec<_> = game
+                .players
+                .iter()
+                .map(|p| (p.id, p.name.clone(), p.stack))
+                .collect();
+            (players, game.winner_ids.clone())
+        };
+        state
+            .leaderboard
+            .write()
+            .await
+            .record_hand_end(table_id, &players, &winners, results);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcg_shared::{Card, HandRank};
+
+    fn shown(player_id: usize, category: HandRankCategory) -> HandResult {
+        HandResult {
+            player_id: PlayerId(player_id),
+            rank: HandRank {
+                category,
+                tiebreakers: Vec::new(),
+            },
+            best_five: [Card(0); 5],
+            lo_rank: None,
+        }
+    }
+
+    fn players(stacks: [u32; 2]) -> Vec<(PlayerId, String, u32)> {
+        vec![
+            (PlayerId(0), "Alice".to_owned(), stacks[0]),
+            (PlayerId(1), "Bob".to_owned(), stacks[1]),
+        ]
+    }
+
+    #[test]
+    fn hands_are_ranked_by_chips_won() {
+        let mut board = Leaderboard::default();
+        board.record_session(["Alice", "Bob"]);
+        let start = HashMap::from([(PlayerId(0), 1000), (PlayerId(1), 1000)]);
+
+        board.record_hand_start(0, start);
+        let results = [
+            shown(0, HandRankCategory::Flush),
+            shown(1, HandRankCategory::Pair),
+        ];
+        board.record_hand_end(0, &players([1200, 800]), &[PlayerId(0)], &results);
+        board.record_hand_start(0, HashMap::from([(PlayerId(0), 1200), (PlayerId(1), 800)]));
+        let results = [
+            shown(0, HandRankCategory::Pair),
+            shown(1, HandRankCategory::Straight),
+        ];
+        board.record_hand_end(0, &players([900, 1100]), &[PlayerId(1)], &results);
+
+        let entries = board.entries();
+        assert_eq!(
+            entries[0],
+            LeaderboardEntry {
+                rank: 1,
+                player_name: "Bob".into(),
+                hands_won: 1,
+                total_chips_won: 100,
+                sessions_played: 1,
+                best_hand: Some(HandRankCategory::Straight),
+            }
+        );
+        assert_eq!(entries[1].player_name, "Alice");
+        assert_eq!(entries[1].rank, 2);
+        assert_eq!(entries[1].total_chips_won, -100);
+        assert_eq!(entries[1].best_hand, Some(HandRankCategory::Flush));
+    }
+
+    #[test]
+    fn hands_without_a_recorded_start_only_count_wins() {
+        let mut board = Leaderboard::default();
+        board.record_hand_end(3, &players([1500, 500]), &[PlayerId(0)], &[]);
+        let entries = board.entries();
+        assert_eq!(entries[0].player_name, "Alice");
+        assert_eq!(entries[0].hands_won, 1);
+        assert_eq!(entries[0].total_chips_won, 0);
+        assert_eq!(entries[0].best_hand, None);
+    }
+}
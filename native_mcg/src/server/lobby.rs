@@ -1,23 +1,272 @@
-// Lobby management functionality
-// Currently integrated with state.rs, but separated for future expansion
+// Table management: the server hosts several concurrent games, one Lobby per table.
 
-use super::state::AppState;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 
-/// Lobby management functions
-pub struct LobbyManager;
+use mcg_shared::{PlayerId, TableId, TableSummary, DEFAULT_TABLE_ID};
 
-impl LobbyManager {
-    /// Get a reference to the lobby
-    pub async fn get_lobby(
-        state: &AppState,
-    ) -> tokio::sync::RwLockReadGuard<'_, super::state::Lobby> {
-        state.lobby.read().await
+use super::state::Lobby;
+
+/// All tables hosted by the server, keyed by their id.
+///
+/// The default table always exists so transports that never join a table keep
+/// working against a single shared game.
+#[derive(Clone)]
+pub struct TableManager {
+    pub(crate) tables: HashMap<TableId, Lobby>,
+    next_id: TableId,
+}
+
+impl Default for TableManager {
+    fn default() -> Self {
+        let mut tables = HashMap::new();
+        tables.insert(DEFAULT_TABLE_ID, Lobby::default());
+        Self {
+            tables,
+            next_id: DEFAULT_TABLE_ID + 1,
+        }
+    }
+}
+
+impl TableManager {
+    /// Open a new empty table and return its id.
+    pub fn create_table(&mut self) -> TableId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.tables.insert(id, Lobby::default());
+        id
+    }
+
+    #[must_use]
+    pub fn get(&self, table_id: TableId) -> Option<&Lobby> {
+        self.tables.get(&table_id)
+    }
+
+    pub fn get_mut(&mut self, table_id: TableId) -> Option<&mut Lobby> {
+        self.tables.get_mut(&table_id)
+    }
+
+    /// Summaries of all tables with a game in progress, ordered by id.
+    #[must_use]
+    pub fn summaries(&self) -> Vec<TableSummary> {
+        let mut out: Vec<TableSummary> = self
+            .tables
+            .iter()
+            .filter_map(|(&table_id, lobby)| {
+                lobby.game.as_ref().map(|g| TableSummary {
+                    table_id,
+                    player_count: g.players.len(),
+                    sb: g.sb,
+                    bb: g.bb,
+                })
+            })
+            .collect();
+        out.sort_by_key(|t| t.table_id);
+        out
+    }
+
+    /// Ids of all tables, ordered.
+    #[must_use]
+    pub fn table_ids(&self) -> Vec<TableId> {
+        let mut ids: Vec<TableId> = self.tables.keys().copied().collect();
+        ids.sort_unstable();
+        ids
     }
+}
+
+/// Tables of one round of a seat draw, each listing its players in seat order
+pub type TableAssignment = Vec<Vec<PlayerId>>;
+
+/// Nodes searched for the draw of one round; the best draw found within
+/// them is used.
+const DRAW_SEARCH_NODES: usize = 200_000;
+
+/// Why a seat draw is not balanced, see [`validate_schedule`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScheduleError {
+    /// A round has another number of tables than the first
+    TableCount { round: usize },
+    /// Table sizes of a round differ by more than one player
+    Unbalanced { round: usize },
+    /// A player is seated twice in a round
+    SeatedTwice { round: usize, player: PlayerId },
+    /// A player of the first round is not seated in a later one, or the
+    /// other way around
+    Missing { round: usize, player: PlayerId },
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TableCount { round } => {
+                write!(f, "Round {} has another number of tables", round + 1)
+            }
+            Self::Unbalanced { round } => write!(f, "Round {} has unbalanced tables", round + 1),
+            Self::SeatedTwice { round, player } => {
+                write!(f, "Player {} sits twice in round {}", player, round + 1)
+            }
+            Self::Missing { round, player } => {
+                write!(f, "Player {} is missing from round {}", player, round + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScheduleError {}
+
+/// Draw seats for `rounds` rounds of a tournament of `players` at
+/// `table_count` tables (round → table → seats).
+///
+/// Tables of a round differ by at most one player. Each round is drawn so
+/// that players meet as few opponents again as possible, which for 9 players
+/// at 3 tables gives 4 rounds in which no two players share a table twice.
+/// Seats rotate from round to round, so nobody keeps the first seat.
+#[must_use]
+pub fn generate_seat_draw(
+    players: &[PlayerId],
+    table_count: usize,
+    rounds: usize,
+) -> Vec<TableAssignment> {
+    let n = players.len();
+    let table_count = table_count.clamp(1, n.max(1));
+    // Times each two players shared a table, by index into `players`
+    let mut met = vec![vec![0u32; n]; n];
+    let mut schedule = Vec::with_capacity(rounds);
+    for round in 0..rounds {
+        let tables = draw_round(&met, table_count);
+        for table in &tables {
+            for (i, &a) in table.iter().enumerate() {
+                for &b in &table[i + 1..] {
+                    met[a][b] += 1;
+                    met[b][a] += 1;
+                }
+            }
+        }
+        let seated = tables
+            .into_iter()
+            .map(|mut table| {
+                if !table.is_empty() {
+                    let shift = round % table.len();
+                    table.rotate_left(shift);
+                }
+                table.into_iter().map(|i| players[i]).collect()
+            })
+            .collect();
+        schedule.push(seated);
+    }
+    schedule
+}
+
+/// Check that every round of `schedule` seats the players of the first round
+/// once each, at as many tables as the first, with table sizes differing by
+/// at most one player.
+///
+/// # Errors
+///
+/// With the first round that breaks one of the rules.
+pub fn validate_schedule(schedule: &[TableAssignment]) -> Result<(), ScheduleError> {
+    let Some(first) = schedule.first() else {
+        return Ok(());
+    };
+    let field: HashSet<PlayerId> = first.iter().flatten().copied().collect();
+    for (round, tables) in schedule.iter().enumerate() {
+        if tables.len() != first.len() {
+            return Err(ScheduleError::TableCount { round });
+        }
+        let sizes = tables.iter().map(Vec::len);
+        if sizes.clone().max().unwrap_or(0) > sizes.min().unwrap_or(0) + 1 {
+            return Err(ScheduleError::Unbalanced { round });
+        }
+        let mut seated = HashSet::new();
+        for &player in tables.iter().flatten() {
+            if !seated.insert(player) {
+                return Err(ScheduleError::SeatedTwice { round, player });
+            }
+            if !field.contains(&player) {
+                return Err(ScheduleError::Missing { round, player });
+            }
+        }
+        if let Some(&player) = field.iter().find(|p| !seated.contains(p)) {
+            return Err(ScheduleError::Missing { round, player });
+        }
+    }
+    Ok(())
+}
+
+/// Tables of one round, as indices into `met`, that seat the fewest
+/// players together who met before.
+fn draw_round(met: &[Vec<u32>], table_count: usize) -> Vec<Vec<usize>> {
+    let n = met.len();
+    let capacity = (0..table_count)
+        .map(|t| n / table_count + usize::from(t < n % table_count))
+        .collect();
+    let mut search = RoundSearch {
+        met,
+        capacity,
+        tables: vec![Vec::new(); table_count],
+        cost: 0,
+        best: None,
+        nodes: 0,
+    };
+    search.seat(0);
+    search.best.map(|(_, tables)| tables).unwrap_or_default()
+}
+
+/// Branch and bound over the tables each player can take, cheapest first,
+/// so the first draw found is the greedy one.
+struct RoundSearch<'a> {
+    met: &'a [Vec<u32>],
+    /// Players each table seats
+    capacity: Vec<usize>,
+    tables: Vec<Vec<usize>>,
+    /// Earlier meetings of the players seated together so far
+    cost: u32,
+    best: Option<(u32, Vec<Vec<usize>>)>,
+    nodes: usize,
+}
+
+impl RoundSearch<'_> {
+    fn seat(&mut self, player: usize) {
+        let best_cost = self.best.as_ref().map(|(cost, _)| *cost);
+        if best_cost == Some(0) || self.nodes >= DRAW_SEARCH_NODES {
+            return;
+        }
+        self.nodes += 1;
+        if player == self.met.len() {
+            if best_cost.is_none_or(|best| self.cost < best) {
+                self.best = Some((self.cost, self.tables.clone()));
+            }
+            return;
+        }
+
+        let mut options: Vec<(u32, usize)> = Vec::new();
+        let mut empty_sizes_tried = Vec::new();
+        for (t, table) in self.tables.iter().enumerate() {
+            if table.len() == self.capacity[t] {
+                continue;
+            }
+            // Empty tables of the same size are interchangeable
+            if table.is_empty() {
+                if empty_sizes_tried.contains(&self.capacity[t]) {
+                    continue;
+                }
+                empty_sizes_tried.push(self.capacity[t]);
+            }
+            let added = table.iter().map(|&other| self.met[player][other]).sum();
+            options.push((added, t));
+        }
+        options.sort_unstable();
 
-    /// Get a mutable reference to the lobby
-    pub async fn get_lobby_mut(
-        state: &AppState,
-    ) -> tokio::sync::RwLockWriteGuard<'_, super::state::Lobby> {
-        state.lobby.write().await
+        for (added, t) in options {
+            let best_cost = self.best.as_ref().map(|(cost, _)| *cost);
+            if best_cost.is_some_and(|best| self.cost + added >= best) {
+                break;
+            }
+            self.tables[t].push(player);
+            self.cost += added;
+            self.seat(player + 1);
+            self.cost -= added;
+            self.tables[t].pop();
+        }
     }
 }
@@ -0,0 +1,185 @@
+// Server metrics for the `/health?detail=true` and `/metrics` endpoints.
+//
+// Counters are plain atomics on the AppState so transports and observers can
+// bump them without taking the table lock.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use mcg_shared::{ActionEvent, HandResult, TableId};
+
+use crate::server::observer::GameObserver;
+use crate::server::state::AppState;
+
+/// Counters of the running server.
+#[derive(Debug)]
+pub struct ServerMetrics {
+    started: Instant,
+    hands_played: AtomicU64,
+    actions_applied: AtomicU64,
+    ws_connections_total: AtomicU64,
+    ws_connections_active: AtomicU64,
+}
+
+impl Default for ServerMetrics {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            hands_played: AtomicU64::new(0),
+            actions_applied: AtomicU64::new(0),
+            ws_connections_total: AtomicU64::new(0),
+            ws_connections_active: AtomicU64::new(0),
+        }
+    }
+}
+
+impl ServerMetrics {
+    pub fn uptime(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Hands finished on all tables since the server started.
+    pub fn hands_played(&self) -> u64 {
+        self.hands_played.load(Ordering::Relaxed)
+    }
+
+    /// Player actions applied on all tables since the server started.
+    pub fn actions_applied(&self) -> u64 {
+        self.actions_applied.load(Ordering::Relaxed)
+    }
+
+    pub fn ws_connections_total(&self) -> u64 {
+        self.ws_connections_total.load(Ordering::Relaxed)
+    }
+
+    pub fn ws_connections_active(&self) -> u64 {
+        self.ws_connections_active.load(Ordering::Relaxed)
+    }
+
+    /// Count a new WebSocket connection; it stays active until the returned
+    /// guard is dropped.
+    pub fn ws_connected(self: &Arc<Self>) -> WsConnectionGuard {
+        self.ws_connections_total.fetch_add(1, Ordering::Relaxed);
+        self.ws_connections_active.fetch_add(1, Ordering::Relaxed);
+        WsConnectionGuard(self.clone())
+    }
+
+    /// All counters in the Prometheus text exposition format.
+    pub fn render_prometheus(&self) -> String {
+        let counters = [
+            (
+                "mcg_hands_played",
+                "counter",
+                "Hands finished since the server started",
+                self.hands_played(),
+            ),
+            (
+                "mcg_actions_applied",
+                "counter",
+                "Player actions applied since the server started",
+                self.actions_applied(),
+            ),
+            (
+                "mcg_ws_connections_total",
+                "counter",
+                "WebSocket connections accepted since the server started",
+                self.ws_connections_total(),
+            ),
+            (
+                "mcg_ws_connections_active",
+                "gauge",
+                "Currently open WebSocket connections",
+                self.ws_connections_active(),
+            ),
+        ];
+        let mut out = String::new();
+        for (name, kind, help, value) in counters {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} {kind}");
+            let _ = writeln!(out, "{name} {value}");
+        }
+        out
+    }
+}
+
+/// Marks a WebSocket connection as active while alive.
+pub struct WsConnectionGuard(Arc<ServerMetrics>);
+
+impl Drop for WsConnectionGuard {
+    fn drop(&mut self) {
+        self.0.ws_connections_active.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Counts applied player actions and finished hands.
+pub struct MetricsObserver;
+
+#[async_trait]
+impl GameObserver for MetricsObserver {
+    async fn on_action(&self, state: &AppState, _table_id: TableId, event: &ActionEvent) {
+        if matches!(event, ActionEvent::PlayerAction { .. }) {
+            state
+                .metrics
+                .actions_applied
+                .fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    async fn on_hand_end(&self, state: &AppState, _table_id: TableId, _results: &[HandResult]) {
+        state.metrics.hands_played.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// System allocator that keeps track of the bytes currently allocated.
+/// Install it with `#[global_allocator]` to get a memory usage estimate.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size >= layout.size() {
+                ALLOCATED_BYTES.fetch_add(new_size - layout.size(), Ordering::Relaxed);
+            } else {
+                ALLOCATED_BYTES.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// Bytes currently allocated on the heap, or `None` if the process does not
+/// use the `CountingAllocator`.
+pub fn allocated_bytes() -> Option<u64> {
+    match ALLOCATED_BYTES.load(Ordering::Relaxed) {
+        0 => None,
+        bytes => Some(bytes as u64),
+    }
+}
@@ -0,0 +1,169 @@
+// In-process stand-in for a server connection, for testing clients without
+// a network.
+
+use mcg_shared::{Backend2FrontendMsg, Frontend2BackendMsg, Stage, TableId, DEFAULT_TABLE_ID};
+use tokio::sync::{broadcast, mpsc};
+
+use crate::server::bot_driver::process_single_bot_action;
+use crate::server::session::ClientSession;
+use crate::server::state::{
+    current_state_public, dispatch_client_message, subscribe_connection, AppState, TableBroadcast,
+};
+
+/// One client connection to a real `AppState`, answering messages the way
+/// the websocket transport does but without serializing them.
+///
+/// States are always sent in full, never as diffs, with only the hole cards
+/// the client may see.
+///
+/// Bots act as soon as it is their turn, before `handle` returns, so a test
+/// never has to wait for them.
+pub struct MockServer {
+    state: AppState,
+    table_id: TableId,
+    session: ClientSession,
+    subscription: Option<broadcast::Receiver<TableBroadcast>>,
+}
+
+impl Default for MockServer {
+    fn default() -> Self {
+        Self::new(AppState::default())
+    }
+}
+
+impl MockServer {
+    #[must_use]
+    pub fn new(state: AppState) -> Self {
+        Self {
+            state,
+            table_id: DEFAULT_TABLE_ID,
+            session: ClientSession::default(),
+            subscription: None,
+        }
+    }
+
+    /// Server state shared with any other connection to it.
+    #[must_use]
+    pub const fn state(&self) -> &AppState {
+        &self.state
+    }
+
+    /// Table the connection is seated at.
+    #[must_use]
+    pub const fn table_id(&self) -> TableId {
+        self.table_id
+    }
+
+    /// Apply `msg` and return everything the client receives in response: the
+    /// reply first, then the broadcasts for its table if it has subscribed.
+    pub async fn handle(&mut self, msg: Frontend2BackendMsg) -> Vec<Backend2FrontendMsg> {
+        let mut out = Vec::new();
+        match msg {
+            Frontend2BackendMsg::Subscribe if self.subscription.is_some() => {
+                out.push(Backend2FrontendMsg::Error("already subscribed".into()));
+            }
+            Frontend2BackendMsg::Subscribe => {
+                let sub = subscribe_connection(&self.state, self.table_id).await;
+                out.extend(sub.initial_state.map(Backend2FrontendMsg::State));
+                self.subscription = Some(sub.receiver);
+            }
+            Frontend2BackendMsg::Action { table_id, .. } if table_id != self.table_id => {
+                out.push(Backend2FrontendMsg::Error(format!(
+                    "Join table {table_id} before acting at it"
+                )));
+            }
+            other => {
+                if let Err(unauthorized) = self.session.authorize(&other) {
+                    return vec![*unauthorized];
+                }
+                let resp = dispatch_client_message(&self.state, self.table_id, other).await;
+                let joined = match resp {
+                    Backend2FrontendMsg::TableJoined(joined) => Some(joined),
+                    _ => None,
+                };
+                out.push(resp);
+                if let Some(joined) = joined {
+                    self.table_id = joined;
+                    self.session.leave_seat();
+                    if let Some(gs) = current_state_public(&self.state, joined).await {
+                        out.push(Backend2FrontendMsg::State(gs));
+                    }
+                }
+            }
+        }
+        self.drive_bots().await;
+        out.extend(self.drain_broadcasts());
+        let viewer = self.session.you_id();
+        out.into_iter()
+            .map(|msg| match msg {
+                Backend2FrontendMsg::State(gs) => Backend2FrontendMsg::State(gs.seen_by(viewer)),
+                other => other,
+            })
+            .collect()
+    }
+
+    async fn drive_bots(&self) {
+        while let Some(gs) = current_state_public(&self.state, self.table_id).await {
+            if gs.stage == Stage::Showdown
+                || !process_single_bot_action(&self.state, self.table_id).await
+            {
+                break;
+            }
+        }
+    }
+
+    fn drain_broadcasts(&mut self) -> Vec<Backend2FrontendMsg> {
+        let Some(rx) = self.subscription.as_mut() else {
+            return Vec::new();
+        };
+        let mut out = Vec::new();
+        loop {
+            match rx.try_recv() {
+                Ok((table_id, msg)) if table_id == self.table_id => out.push(msg),
+                Ok(_) | Err(broadcast::error::TryRecvError::Lagged(_)) => {}
+                Err(_) => break,
+            }
+        }
+        out
+    }
+
+    /// Run the server on its own task, talking to the client over channels
+    /// like a socket would. The task ends when the client drops its sender.
+    #[must_use]
+    pub fn connect(mut self) -> MockConnection {
+        let (to_server, mut from_client) = mpsc::unbounded_channel();
+        let (to_client, from_server) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(msg) = from_client.recv().await {
+                for reply in self.handle(msg).await {
+                    if to_client.send(reply).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        MockConnection {
+            sender: to_server,
+            receiver: from_server,
+        }
+    }
+}
+
+/// Client end of a `MockServer` running on its own task.
+pub struct MockConnection {
+    pub sender: mpsc::UnboundedSender<Frontend2BackendMsg>,
+    pub receiver: mpsc::UnboundedReceiver<Backend2FrontendMsg>,
+}
+
+impl MockConnection {
+    /// Send `msg`; false once the server task has ended.
+    #[must_use]
+    pub fn send(&self, msg: Frontend2BackendMsg) -> bool {
+        self.sender.send(msg).is_ok()
+    }
+
+    /// Next message from the server, `None` once the server task has ended.
+    pub async fn recv(&mut self) -> Option<Backend2FrontendMsg> {
+        self.receiver.recv().await
+    }
+}
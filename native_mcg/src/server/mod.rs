@@ -1,10 +1,22 @@
+pub mod arena;
+pub mod articles;
 pub mod bot_driver;
+pub mod discovery;
+pub mod game_log;
 pub mod http;
 pub mod iroh;
+pub mod leaderboard;
 pub mod lobby;
+pub mod metrics;
+pub mod mock_server;
+pub mod observer;
+pub mod peers;
+pub mod quic;
+pub mod reset_policy;
 pub mod run;
 pub mod session;
 pub mod state;
+pub mod tournament;
 pub mod ws;
 
 // Export commonly used types and functions
@@ -0,0 +1,139 @@
+// Observers for side effects of game state changes.
+//
+// `apply_action_to_game` only mutates the game; everything that should happen
+// afterwards (broadcasting, stats, logging) is an observer registered on the
+// AppState. New side effects such as analytics or replays plug in here.
+
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use mcg_shared::{ActionEvent, GameAction, HandResult, Stage, TableId};
+
+use crate::bot::PlayerStats;
+use crate::pretty;
+use crate::server::state::{broadcast_state, current_state_public, AppState};
+
+/// Receives the changes made by each game state mutation.
+///
+/// Observers are called after the table lock has been released, so they are
+/// free to read or lock the table themselves. All hooks default to no-ops.
+#[async_trait]
+pub trait GameObserver {
+    /// Called for every event the mutation added to the action log.
+    async fn on_action(&self, _state: &AppState, _table_id: TableId, _event: &ActionEvent) {}
+
+    /// Called when the mutation moved the game to a new stage.
+    async fn on_stage_change(&self, _state: &AppState, _table_id: TableId, _new_stage: Stage) {}
+
+    /// Called when the mutation finished a hand.
+    async fn on_hand_end(&self, _state: &AppState, _table_id: TableId, _results: &[HandResult]) {}
+
+    /// Called after a new hand has been dealt.
+    async fn on_hand_start(&self, _state: &AppState, _table_id: TableId) {}
+
+    /// Called once after all other hooks of a mutation.
+    async fn on_update(&self, _state: &AppState, _table_id: TableId) {}
+}
+
+/// Notify all observers registered on `state` about the events of one mutation.
+pub async fn notify_observers(
+    state: &AppState,
+    table_id: TableId,
+    events: &[ActionEvent],
+    new_stage: Option<Stage>,
+) {
+    let observers = state.observers.read().await;
+    for observer in observers.iter() {
+        for event in events {
+            observer.on_action(state, table_id, event).await;
+        }
+        if let Some(stage) = new_stage {
+            observer.on_stage_change(state, table_id, stage).await;
+        }
+        for event in events {
+            if let ActionEvent::GameAction(GameAction::Showdown { hand_results }) = event {
+                observer.on_hand_end(state, table_id, hand_results).await;
+            }
+        }
+        observer.on_update(state, table_id).await;
+    }
+}
+
+/// Notify all observers registered on `state` that a new hand was dealt.
+pub async fn notify_hand_start(state: &AppState, table_id: TableId) {
+    let observers = state.observers.read().await;
+    for observer in observers.iter() {
+        observer.on_hand_start(state, table_id).await;
+    }
+}
+
+/// Sends the new table state to all subscribers.
+pub struct BroadcastObserver;
+
+#[async_trait]
+impl GameObserver for BroadcastObserver {
+    async fn on_update(&self, state: &AppState, table_id: TableId) {
+        broadcast_state(state, table_id).await;
+    }
+}
+
+/// Adds each finished hand to the betting tendencies of its players.
+pub struct StatsObserver;
+
+#[async_trait]
+impl GameObserver for StatsObserver {
+    // The lobby is borrowed from the table lock until the history is stored
+    #[allow(clippy::significant_drop_tightening)]
+    async fn on_hand_end(&self, state: &AppState, table_id: TableId, _results: &[HandResult]) {
+        let mut tables = state.tables.write().await;
+        let Some(lobby) = tables.get_mut(table_id) else {
+            return;
+        };
+        if let Some(game) = &lobby.game {
+            PlayerStats::record_hand(&mut lobby.player_stats, &game.recent_actions);
+        }
+    }
+}
+
+/// Appends every event in human readable form to a log file.
+pub struct LogObserver {
+    file: Mutex<std::fs::File>,
+}
+
+impl LogObserver {
+    /// Open `path` for appending, creating it if needed.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be opened.
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("opening event log '{}'", path.display()))?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+#[async_trait]
+impl GameObserver for LogObserver {
+    async fn on_action(&self, state: &AppState, table_id: TableId, event: &ActionEvent) {
+        let players = current_state_public(state, table_id)
+            .await
+            .map(|gs| gs.players)
+            .unwrap_or_default();
+        let line = pretty::format_event_human(event, &players, false);
+        let Ok(mut file) = self.file.lock() else {
+            return;
+        };
+        if let Err(e) = writeln!(file, "[table {table_id}] {line}") {
+            tracing::warn!(error = %e, "failed to write to event log");
+        }
+    }
+}
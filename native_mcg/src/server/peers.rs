@@ -0,0 +1,50 @@
+// Iroh nodes the server knows of, for the `/iroh/peers` endpoint.
+//
+// The server lists its own endpoint and every peer that connected to it over
+// iroh. Entries last until the server restarts, but only recently seen ones
+// are listed.
+
+use std::collections::HashMap;
+
+use mcg_shared::PeerInfo;
+
+/// Peers not heard of for this long are left out of the list
+const PEER_TTL_MS: u64 = 60 * 60 * 1000;
+
+#[derive(Debug, Clone, Default)]
+pub struct PeerRegistry {
+    peers: HashMap<String, PeerInfo>,
+}
+
+impl PeerRegistry {
+    /// Note that `node_id` was seen at `now_ms`. A relay url learned
+    /// earlier is kept when `relay_url` is `None`.
+    pub fn see(&mut self, node_id: String, relay_url: Option<String>, now_ms: u64) {
+        let peer = self.peers.entry(node_id.clone()).or_insert(PeerInfo {
+            node_id,
+            last_seen_ms: now_ms,
+            relay_url: None,
+        });
+        peer.last_seen_ms = peer.last_seen_ms.max(now_ms);
+        if relay_url.is_some() {
+            peer.relay_url = relay_url;
+        }
+    }
+
+    /// Peers seen within an hour of `now_ms`, most recently seen first.
+    #[must_use]
+    pub fn list(&self, now_ms: u64) -> Vec<PeerInfo> {
+        let mut peers: Vec<PeerInfo> = self
+            .peers
+            .values()
+            .filter(|p| now_ms.saturating_sub(p.last_seen_ms) <= PEER_TTL_MS)
+            .cloned()
+            .collect();
+        peers.sort_by(|a, b| {
+            b.last_seen_ms
+                .cmp(&a.last_seen_ms)
+                .then_with(|| a.node_id.cmp(&b.node_id))
+        });
+        peers
+    }
+}
@@ -0,0 +1,233 @@
+// QUIC transport listener for low-latency local play.
+//
+// Speaks the same JSON messages as the WebSocket and iroh transports, but
+// every message travels on its own unidirectional QUIC stream: the client
+// opens one stream per ClientMsg and the server one per ServerMsg. A lost
+// packet then only delays the message it belongs to instead of everything
+// queued behind it on a single TCP connection.
+//
+// Both sides read the streams in the order they were opened, which QUIC
+// hands out in sequence, so state diffs are still applied in order.
+//
+// The TLS certificate is self-signed and generated at startup. Its DER
+// encoding is written to the public info file, where local clients pick it
+// up to verify the server.
+
+use std::net::SocketAddr;
+
+use anyhow::{Context, Result};
+use quinn::rustls::pki_types::{CertificateDer, PrivatePkcs8KeyDer};
+use quinn::{Connection, Endpoint, ServerConfig};
+use tokio::sync::broadcast;
+use tracing::Instrument;
+
+use crate::public::{path_for_config, PublicInfo};
+use crate::server::session::ClientSession;
+use crate::server::state::{current_state_public, subscribe_connection, TableBroadcast};
+use crate::server::AppState;
+use crate::transport::{send_msg_on_uni, StateDiffer, MAX_QUIC_MESSAGE_BYTES};
+use mcg_shared::{Backend2FrontendMsg, Frontend2BackendMsg, TableId, DEFAULT_TABLE_ID};
+
+/// Server name in the self-signed certificate; clients connect with it.
+pub const QUIC_SERVER_NAME: &str = "localhost";
+
+/// Generate a self-signed certificate and server config for `QUIC_SERVER_NAME`.
+///
+/// # Errors
+///
+/// If the certificate cannot be generated or used.
+pub fn self_signed_server_config() -> Result<(ServerConfig, CertificateDer<'static>)> {
+    let certified = rcgen::generate_simple_self_signed(vec![QUIC_SERVER_NAME.to_owned()])
+        .context("generating self-signed certificate")?;
+    let cert = certified.cert.der().clone();
+    let key = PrivatePkcs8KeyDer::from(certified.key_pair.serialize_der());
+    let config = ServerConfig::with_single_cert(vec![cert.clone()], key.into())
+        .context("building QUIC server config")?;
+    Ok((config, cert))
+}
+
+/// Bind the QUIC endpoint on `port`, publish its certificate and accept
+/// connections in the background.
+///
+/// # Errors
+///
+/// Like [`start_quic_listener`].
+pub fn spawn_quic_listener(state: AppState, port: u16) -> Result<SocketAddr> {
+    let public_path = path_for_config(state.config_path.as_deref());
+    let (local_addr, cert) = start_quic_listener(state, port)?;
+    match PublicInfo::write_quic_cert(&public_path, hex::encode(cert.as_ref())) {
+        Ok(_) => tracing::info!(path = %public_path.display(), "stored QUIC certificate"),
+        Err(e) => {
+            tracing::warn!(error = %e, path = %public_path.display(), "failed to persist QUIC certificate");
+        }
+    }
+    Ok(local_addr)
+}
+
+/// Bind the QUIC endpoint on `port` and accept connections in the
+/// background without publishing the certificate. Returns the bound address
+/// and the certificate clients have to trust.
+///
+/// # Errors
+///
+/// If the certificate cannot be generated or the port cannot be bound.
+pub fn start_quic_listener(
+    state: AppState,
+    port: u16,
+) -> Result<(SocketAddr, CertificateDer<'static>)> {
+    let (server_config, cert) = self_signed_server_config()?;
+    let endpoint = Endpoint::server(server_config, SocketAddr::from(([0, 0, 0, 0], port)))
+        .with_context(|| format!("binding QUIC endpoint on UDP port {port}"))?;
+    let local_addr = endpoint.local_addr()?;
+
+    tokio::spawn(async move {
+        while let Some(incoming) = endpoint.accept().await {
+            let state = state.clone();
+            tokio::spawn(async move {
+                let conn = match incoming.await {
+                    Ok(conn) => conn,
+                    Err(e) => {
+                        tracing::error!(error = %e, "QUIC handshake failed");
+                        return;
+                    }
+                };
+                let span = tracing::info_span!("quic_client", peer = %conn.remote_address());
+                async move {
+                    tracing::info!("Accepted new QUIC connection");
+                    if let Err(e) = manage_quic_connection(state, conn).await {
+                        tracing::error!(error = %e, "QUIC connection handler error");
+                    }
+                }
+                .instrument(span)
+                .await;
+            });
+        }
+    });
+
+    tracing::info!(addr = %local_addr, "QUIC listener started");
+    Ok((local_addr, cert))
+}
+
+/// Per-connection state shared by the message handlers.
+struct QuicClient {
+    conn: Connection,
+    differ: StateDiffer,
+    subscription: Option<broadcast::Receiver<TableBroadcast>>,
+    table_id: TableId,
+    session: ClientSession,
+}
+
+impl QuicClient {
+    async fn send(&mut self, msg: Backend2FrontendMsg) -> Result<()> {
+        let msg = self.differ.encode(msg, self.session.you_id());
+        send_msg_on_uni(&self.conn, &msg).await
+    }
+}
+
+/// What woke up the connection loop
+enum QuicEvent {
+    /// Boxed, as a whole game state is much larger than a stream handle
+    Broadcast(Result<Box<TableBroadcast>, broadcast::error::RecvError>),
+    Stream(Result<quinn::RecvStream, quinn::ConnectionError>),
+}
+
+async fn manage_quic_connection(state: AppState, conn: Connection) -> Result<()> {
+    let mut client = QuicClient {
+        conn,
+        differ: StateDiffer::default(),
+        subscription: None,
+        table_id: DEFAULT_TABLE_ID,
+        session: ClientSession::default(),
+    };
+
+    loop {
+        let event = match client.subscription.as_mut() {
+            Some(rx) => tokio::select! {
+                recv = rx.recv() => QuicEvent::Broadcast(recv.map(Box::new)),
+                stream = client.conn.accept_uni() => QuicEvent::Stream(stream),
+            },
+            None => QuicEvent::Stream(client.conn.accept_uni().await),
+        };
+        let mut recv = match event {
+            QuicEvent::Broadcast(Ok(broadcast)) => {
+                let (tid, sm) = *broadcast;
+                if tid == client.table_id {
+                    client.send(sm).await?;
+                }
+                continue;
+            }
+            QuicEvent::Broadcast(Err(broadcast::error::RecvError::Lagged(_))) => continue,
+            QuicEvent::Broadcast(Err(broadcast::error::RecvError::Closed))
+            | QuicEvent::Stream(Err(quinn::ConnectionError::ApplicationClosed(_))) => break,
+            QuicEvent::Stream(Ok(recv)) => recv,
+            QuicEvent::Stream(Err(e)) => {
+                tracing::error!(error = %e, "QUIC read error");
+                break;
+            }
+        };
+        // Reading the whole stream before accepting the next keeps the
+        // client's messages in order
+        let bytes = match recv.read_to_end(MAX_QUIC_MESSAGE_BYTES).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                let msg = Backend2FrontendMsg::Error(format!("Invalid QUIC message: {e}"));
+                client.send(msg).await?;
+                continue;
+            }
+        };
+        process_quic_msg(&state, &mut client, &bytes).await?;
+    }
+
+    tracing::info!("[QUIC DISCONNECT] Client");
+    Ok(())
+}
+
+async fn process_quic_msg(state: &AppState, client: &mut QuicClient, bytes: &[u8]) -> Result<()> {
+    match serde_json::from_slice::<Frontend2BackendMsg>(bytes) {
+        Ok(Frontend2BackendMsg::Subscribe) => {
+            if client.subscription.is_some() {
+                return client
+                    .send(Backend2FrontendMsg::Error("already subscribed".into()))
+                    .await;
+            }
+            let sub = subscribe_connection(state, client.table_id).await;
+            if let Some(gs) = sub.initial_state {
+                client.send(Backend2FrontendMsg::State(gs)).await?;
+            }
+            client.subscription = Some(sub.receiver);
+        }
+        Ok(Frontend2BackendMsg::Action {
+            table_id: action_table,
+            ..
+        }) if action_table != client.table_id => {
+            let msg = Backend2FrontendMsg::Error(format!(
+                "Join table {action_table} before acting at it"
+            ));
+            client.send(msg).await?;
+        }
+        Ok(other) => {
+            tracing::debug!(client_msg = ?other, "QUIC received client message");
+            if let Err(unauthorized) = client.session.authorize(&other) {
+                return client.send(*unauthorized).await;
+            }
+            let resp = crate::server::dispatch_client_message(state, client.table_id, other).await;
+            let joined = match resp {
+                Backend2FrontendMsg::TableJoined(joined) => Some(joined),
+                _ => None,
+            };
+            client.send(resp).await?;
+            if let Some(joined) = joined {
+                client.table_id = joined;
+                client.session.leave_seat();
+                if let Some(gs) = current_state_public(state, joined).await {
+                    client.send(Backend2FrontendMsg::State(gs)).await?;
+                }
+            }
+        }
+        Err(e) => {
+            let msg = Backend2FrontendMsg::Error(format!("Invalid JSON message: {e}"));
+            client.send(msg).await?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,136 @@
+// What happens after each hand, by the `GameResetPolicy` an admin set on the
+// `AppState`. Without a policy, hands are only dealt on `NextHand`.
+//
+// Hands are dealt by a task that waits `next_hand_delay_ms` first, so players
+// get to see the showdown. The task gives up if a client dealt the next hand
+// in the meantime.
+
+use async_trait::async_trait;
+use mcg_shared::{
+    ActionEvent, Backend2FrontendMsg, GameAction, GameResetPolicy, HandResult, PlayerConfig, Stage,
+    TableConfig, TableId,
+};
+use tokio::time::{sleep, Duration};
+
+use crate::server::observer::{notify_hand_start, GameObserver};
+use crate::server::state::{broadcast_state, create_new_game, start_new_hand_and_print, AppState};
+
+/// Applies the server's `GameResetPolicy` at the end of each hand.
+pub struct ResetPolicyObserver;
+
+#[async_trait]
+impl GameObserver for ResetPolicyObserver {
+    async fn on_action(&self, state: &AppState, table_id: TableId, event: &ActionEvent) {
+        let ActionEvent::GameAction(GameAction::PlayerEliminated { player_id, .. }) = event else {
+            return;
+        };
+        if *state.reset_policy.read().await != Some(GameResetPolicy::ReturnToLobby) {
+            return;
+        }
+        let Some(final_rank) = final_rank(state, table_id).await else {
+            return;
+        };
+        tracing::info!(table_id, player_id = %player_id, final_rank, "player eliminated, back to the lobby");
+        let _ = state.broadcaster.send((
+            table_id,
+            Backend2FrontendMsg::PlayerEliminated {
+                player_id: *player_id,
+                final_rank,
+            },
+        ));
+    }
+
+    async fn on_hand_end(&self, state: &AppState, table_id: TableId, _results: &[HandResult]) {
+        let policy = *state.reset_policy.read().await;
+        if !matches!(
+            policy,
+            Some(GameResetPolicy::ContinueWithSameConfig | GameResetPolicy::StartNewTournament)
+        ) {
+            return;
+        }
+        let Some(hands_played) = hands_played(state, table_id).await else {
+            return;
+        };
+        tokio::spawn(deal_after_delay(state.clone(), table_id, hands_played));
+    }
+}
+
+/// Place of a player busted in the hand just finished. Everyone busted in the
+/// same hand shares the place behind the players who still have chips.
+// The game is borrowed from the table lock until the count
+#[allow(clippy::significant_drop_tightening)]
+async fn final_rank(state: &AppState, table_id: TableId) -> Option<u32> {
+    let tables = state.tables.read().await;
+    let game = tables.get(table_id)?.game.as_ref()?;
+    let still_in = game.players.iter().filter(|p| p.stack > 0).count();
+    Some(u32::try_from(still_in + 1).unwrap_or(u32::MAX))
+}
+
+/// Hands finished at the table before the one being played.
+async fn hands_played(state: &AppState, table_id: TableId) -> Option<usize> {
+    Some(state.tables.read().await.get(table_id)?.hand_history.len())
+}
+
+/// Wait for the configured delay, then deal the next hand, or start a new
+/// tournament once a single player has chips left.
+// The lobby is borrowed from the table lock until the block ends
+#[allow(clippy::significant_drop_tightening)]
+async fn deal_after_delay(state: AppState, table_id: TableId, hands_played: usize) {
+    let delay = state.config.read().await.next_hand_delay_ms;
+    sleep(Duration::from_millis(delay)).await;
+
+    let policy = *state.reset_policy.read().await;
+    let (still_in, players) = {
+        let tables = state.tables.read().await;
+        let Some(lobby) = tables.get(table_id) else {
+            return;
+        };
+        let Some(game) = lobby.game.as_ref() else {
+            return;
+        };
+        // A client sent `NextHand` or started a new game during the delay
+        if lobby.hand_history.len() != hands_played || game.stage != Stage::Showdown {
+            return;
+        }
+        let still_in = game.players.iter().filter(|p| p.stack > 0).count();
+        let players: Vec<PlayerConfig> = game
+            .players
+            .iter()
+            .enumerate()
+            .map(|(seat, p)| PlayerConfig {
+                id: p.id,
+                name: p.name.clone(),
+                is_bot: lobby.bots.contains(&p.id),
+                avatar_color: lobby.avatar_colors.get(&p.id).copied(),
+                preferred_seat: u8::try_from(seat).ok(),
+            })
+            .collect();
+        (still_in, players)
+    };
+
+    let result = match policy {
+        Some(GameResetPolicy::ContinueWithSameConfig | GameResetPolicy::StartNewTournament)
+            if still_in >= 2 =>
+        {
+            start_new_hand_and_print(&state, table_id).await
+        }
+        Some(GameResetPolicy::StartNewTournament) => {
+            tracing::info!(table_id, "tournament decided, starting a new one");
+            let result = create_new_game(&state, table_id, TableConfig::new(players)).await;
+            if result.is_ok() {
+                notify_hand_start(&state, table_id).await;
+            }
+            result
+        }
+        Some(GameResetPolicy::ContinueWithSameConfig) => {
+            tracing::info!(table_id, "a single player has chips left, not dealing");
+            return;
+        }
+        // The policy was changed during the delay
+        Some(GameResetPolicy::ReturnToLobby) | None => return,
+    };
+    match result {
+        Ok(()) => broadcast_state(&state, table_id).await,
+        Err(e) => tracing::error!(table_id, error = %e, "failed to deal the next hand"),
+    }
+}
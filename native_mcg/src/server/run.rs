@@ -5,8 +5,8 @@ use std::net::SocketAddr;
 use axum::{
     http::Uri,
     response::IntoResponse,
-    routing::{get, post},
-    Json, Router,
+    routing::{delete, get, post},
+    Router,
 };
 use tower_http::services::ServeDir;
 
@@ -19,14 +19,43 @@ pub fn build_router(state: AppState) -> Router {
     let serve_media = ServeDir::new("media").append_index_html_on_directories(true);
 
     Router::new()
-        .route(
-            "/health",
-            get(|| async { Json(serde_json::json!({ "ok": true })) }),
-        )
+        // Load balancer health check; `?detail=true` adds server metadata
+        .route("/health", get(crate::server::http::health_handler))
+        // Counters in the Prometheus text format
+        .route("/metrics", get(crate::server::http::metrics_handler))
         // WebSocket endpoint (WASM GUI remains websocket-only)
         .route("/ws", get(crate::server::ws::ws_handler))
+        // Results of the hands played at the arena table, see `arena`
+        .route("/arena", get(crate::server::ws::arena_ws_handler))
         // HTTP API endpoint using unified ClientMsg/ServerMsg payloads
         .route("/api/message", post(crate::server::http::message_handler))
+        // Active tables with player count and blind levels
+        .route("/tables", get(crate::server::http::tables_handler))
+        // Plain-text hand history export for the current session
+        .route("/history.txt", get(crate::server::http::history_handler))
+        // Bundled markdown articles: JSON list and raw markdown bodies
+        .route("/articles", get(crate::server::http::articles_handler))
+        .route("/articles/:id", get(crate::server::http::article_handler))
+        // All-time player standings; the page of the same name for browsers
+        .route(
+            "/leaderboard",
+            get(crate::server::http::leaderboard_handler),
+        )
+        // UI translations from `locales/<lang>.json`
+        .route("/locale/:lang", get(crate::server::http::locale_handler))
+        // Pairing: the iroh node id and QR invites to a table
+        .route("/node-id", get(crate::server::http::node_id_handler))
+        // Iroh nodes seen by the server, for the discovery screen
+        .route("/iroh/peers", get(crate::server::http::iroh_peers_handler))
+        .route(
+            "/pairing-code",
+            get(crate::server::http::pairing_code_handler),
+        )
+        // Admin: replace a player by a bot (bearer token from config)
+        .route(
+            "/players/:id",
+            delete(crate::server::http::kick_player_handler),
+        )
         .nest_service("/pkg", serve_dir)
         .nest_service("/media", serve_media)
         // Serve index.html for the root route
@@ -36,6 +65,12 @@ pub fn build_router(state: AppState) -> Router {
         .with_state(state)
 }
 
+/// Serve HTTP and WebSocket clients on `addr`, with the iroh and QUIC
+/// listeners in the background.
+///
+/// # Errors
+///
+/// If `addr` cannot be bound or the server stops with an error.
 pub async fn run_server(addr: SocketAddr, state: AppState) -> Result<()> {
     let app = build_router(state.clone());
 
@@ -45,11 +80,26 @@ pub async fn run_server(addr: SocketAddr, state: AppState) -> Result<()> {
         let state_clone = state.clone();
         tokio::spawn(async move {
             if let Err(e) = crate::server::iroh::spawn_iroh_listener(state_clone).await {
-                eprintln!("Iroh listener failed: {}", e);
+                tracing::error!(error = %e, "iroh listener failed");
             }
         });
     }
 
+    // The QUIC listener is opt-in, for low-latency local play.
+    let quic_port = state.config.read().await.quic_port;
+    if let Some(port) = quic_port {
+        if let Err(e) = crate::server::quic::spawn_quic_listener(state.clone(), port) {
+            tracing::error!(error = %e, "QUIC listener failed");
+        }
+    }
+
+    // The arena table has to exist before the bot driver starts acting at it.
+    if state.config.read().await.arena {
+        if let Err(e) = crate::server::arena::start_arena(state.clone()).await {
+            tracing::error!(error = %e, "failed to start the arena");
+        }
+    }
+
     // Continuously drive bots in the background.
     {
         let state_clone = state.clone();
@@ -58,6 +108,15 @@ pub async fn run_server(addr: SocketAddr, state: AppState) -> Result<()> {
         });
     }
 
+    // Announce the server on the local network for native clients.
+    {
+        let state_clone = state.clone();
+        let port = addr.port();
+        tokio::spawn(async move {
+            crate::server::discovery::run_discovery_broadcast(state_clone, port).await;
+        });
+    }
+
     let display_addr = if addr.ip().is_loopback() {
         format!("localhost:{}", addr.port())
     } else {
@@ -65,36 +124,32 @@ pub async fn run_server(addr: SocketAddr, state: AppState) -> Result<()> {
     };
 
     tracing::info!(display_addr = %display_addr, "MCG Server running");
-
-    // Nice clickable banner for the Web UI
-    println!("\n\x1b[1;36m=== Web UI Available ===\x1b[0m");
-    println!(
-        "\x1b[1mURL:\x1b[0m       \x1b[4;34mhttp://{}\x1b[0m",
-        display_addr
-    );
-    println!("\x1b[1;36m========================\x1b[0m\n");
-
-    tracing::info!("open your browser and navigate to the above URL");
-    tracing::debug!("blank line");
+    tracing::info!(url = %format!("http://{}", display_addr), "web UI available");
     let listener = tokio::net::TcpListener::bind(addr)
         .await
-        .with_context(|| format!("Failed to bind to {}", display_addr))?;
+        .with_context(|| format!("Failed to bind to {display_addr}"))?;
     // axum::serve returns a future that runs the server; propagate any error if it returns one.
-    let _ = axum::serve(listener, app).await;
+    let _ = axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await;
     Ok(())
 }
 
 /// Serve index.html file
-async fn serve_index() -> impl IntoResponse {
-    match tokio::fs::read_to_string("index.html").await {
-        Ok(content) => (
-            axum::http::StatusCode::OK,
-            [("content-type", "text/html")],
-            content,
-        )
-            .into_response(),
-        Err(_) => (axum::http::StatusCode::NOT_FOUND, "index.html not found").into_response(),
-    }
+pub(crate) async fn serve_index() -> impl IntoResponse {
+    tokio::fs::read_to_string("index.html").await.map_or_else(
+        |_| (axum::http::StatusCode::NOT_FOUND, "index.html not found").into_response(),
+        |content| {
+            (
+                axum::http::StatusCode::OK,
+                [("content-type", "text/html")],
+                content,
+            )
+                .into_response()
+        },
+    )
 }
 
 /// Single Page Application (SPA) fallback handler - serves index.html for client-side routing
@@ -107,6 +162,8 @@ async fn spa_handler(uri: Uri) -> impl IntoResponse {
         || path.starts_with("/media")
         || path.starts_with("/ws")
         || path.starts_with("/health")
+        || path.starts_with("/metrics")
+        || path.starts_with("/tables")
     {
         return axum::http::StatusCode::NOT_FOUND.into_response();
     }
@@ -1,20 +1,77 @@
-// Client session handling functionality
-// Stub for future session management features
+// Client sessions of the long-lived transports (websocket, iroh, QUIC).
+//
+// HTTP is stateless and has no session, so its messages are not checked;
+// rebuys, which are not tied to the player's turn, are refused there.
 
-use super::state::AppState;
+use std::collections::BTreeSet;
 
-#[derive(Default)]
-pub struct SessionManager;
+use mcg_shared::{Backend2FrontendMsg, Frontend2BackendMsg, PlayerId};
 
-impl SessionManager {
+/// Numbered messages a client may send ahead of a lost one before the lost
+/// one is given up on.
+const MAX_SEQ_GAP: usize = 256;
 
-    /// Handle client connection
-    pub async fn handle_connection(&self, _state: &AppState) {
-        // Session management logic would go here
+/// Seat of one client connection.
+///
+/// The first message sent on behalf of a player registers that player as the connection's `you_id`; messages on
+/// behalf of any other player are refused, so a client only plays its own seat.
+#[derive(Debug, Default)]
+pub struct ClientSession {
+    you_id: Option<PlayerId>,
+    /// Every message numbered below this one has been handled
+    contiguous_seq: u64,
+    /// Messages handled above `contiguous_seq`, which arrived before a gap
+    seqs_above: BTreeSet<u64>,
+}
+
+impl ClientSession {
+    /// Player this connection plays as, once it has sent a message for one.
+    #[must_use]
+    pub const fn you_id(&self) -> Option<PlayerId> {
+        self.you_id
+    }
+
+    /// Check the player `msg` is sent on behalf of, registering it as the
+    /// connection's seat if there is none yet. The error is the reply to send.
+    ///
+    /// # Errors
+    ///
+    /// If `msg` is sent on behalf of another player than the connection's.
+    pub fn authorize(&mut self, msg: &Frontend2BackendMsg) -> Result<(), Box<Backend2FrontendMsg>> {
+        let Some(claimed) = msg.player_id() else {
+            return Ok(());
+        };
+        match self.you_id {
+            None => {
+                self.you_id = Some(claimed);
+                Ok(())
+            }
+            Some(you) if you == claimed => Ok(()),
+            Some(_) => Err(Box::new(Backend2FrontendMsg::Error(
+                "Unauthorized player_id".into(),
+            ))),
+        }
+    }
+
+    /// Record the client message numbered `seq` as handled. False if it was
+    /// handled before: clients re-send messages whose `Ack` is late, and such
+    /// a copy is only acknowledged again.
+    pub fn first_delivery(&mut self, seq: u64) -> bool {
+        if seq < self.contiguous_seq || !self.seqs_above.insert(seq) {
+            return false;
+        }
+        // The client stops re-sending after a while, so a gap may never close
+        if self.seqs_above.len() > MAX_SEQ_GAP {
+            self.contiguous_seq = self.seqs_above.first().copied().unwrap_or(seq);
+        }
+        while self.seqs_above.remove(&self.contiguous_seq) {
+            self.contiguous_seq += 1;
+        }
+        true
     }
 
-    /// Handle client disconnection
-    pub async fn handle_disconnection(&self, _state: &AppState) {
-        // Session cleanup logic would go here
+    /// Give up the seat, e.g. after joining another table.
+    pub const fn leave_seat(&mut self) {
+        self.you_id = None;
     }
 }
@@ -1,15 +1,29 @@
 // Server state management: AppState, Lobby, and helpers that operate on shared state.
 
+use std::collections::HashMap;
 use std::io::IsTerminal;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
-use mcg_shared::{Card, CardRank, CardSuit, PlayerId};
+use mcg_shared::{Card, CardRank, CardSuit, GameResetPolicy, PlayerId, TableConfig, TableId};
 // rand import removed; use rand::random::<f64>() for probabilistic decisions
-use crate::bot::BotManager;
-use crate::game::{Game, Player};
+use crate::bot::{BotManager, PlayerStats};
+use crate::config::{GameConfig, GameVariant};
+use crate::game::{Game, Player, TableError};
+use crate::poker::review::{review_hand, EquityEstimator, REVIEW_RUNOUTS};
 use crate::pretty;
+use crate::server::game_log::GameLogObserver;
+use crate::server::leaderboard::{Leaderboard, LeaderboardObserver};
+use crate::server::lobby::TableManager;
+use crate::server::metrics::{MetricsObserver, ServerMetrics};
+use crate::server::observer::{
+    notify_hand_start, notify_observers, BroadcastObserver, GameObserver, LogObserver,
+    StatsObserver,
+};
+use crate::server::peers::PeerRegistry;
+use crate::server::reset_policy::ResetPolicyObserver;
+use crate::server::tournament::{TournamentObserver, TournamentTracker};
 use mcg_shared::GameStatePublic;
 use tokio::fs::File;
 use tokio::io::AsyncReadExt;
@@ -18,31 +32,116 @@ use tokio::sync::RwLock;
 
 pub const CHANNEL_BUFFER_SIZE: usize = 256;
 
+/// Version announced to clients in `Welcome`
+pub const SERVER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Greeting sent to every new websocket client
+#[must_use]
+pub fn welcome_msg() -> mcg_shared::Backend2FrontendMsg {
+    mcg_shared::Backend2FrontendMsg::Welcome {
+        server_version: SERVER_VERSION.to_string(),
+    }
+}
+
+/// A broadcast message together with the table it belongs to. Transports only
+/// forward messages for the table their connection has joined.
+pub type TableBroadcast = (TableId, mcg_shared::Backend2FrontendMsg);
+
 /// Shared application state exposed to handlers.
 #[derive(Clone)]
 pub struct AppState {
-    pub(crate) lobby: Arc<RwLock<Lobby>>,
-    pub broadcaster: broadcast::Sender<mcg_shared::Backend2FrontendMsg>,
+    pub(crate) tables: Arc<RwLock<TableManager>>,
+    pub broadcaster: broadcast::Sender<TableBroadcast>,
     /// In-memory shared Config instance. Holds the authoritative configuration
-    /// for the running server. Use tokio::sync::RwLock for concurrent access.
+    /// for the running server. Use `tokio::sync::RwLock` for concurrent access.
     pub config: std::sync::Arc<RwLock<crate::config::Config>>,
     /// Optional path to the TOML config file used by the running server.
     /// If present, transports (e.g. iroh) may persist changes to this path.
     pub config_path: Option<PathBuf>,
+    /// Side effects run after each game state mutation, see `observer`.
+    pub(crate) observers: Arc<RwLock<Vec<Box<dyn GameObserver + Send + Sync>>>>,
+    /// Operator-defined rules for new games; built-in defaults when unset.
+    pub game_config: Option<Arc<GameConfig>>,
+    /// Counters reported by `/health?detail=true` and `/metrics`.
+    pub metrics: Arc<ServerMetrics>,
+    /// Player standings reported by `/leaderboard`.
+    pub leaderboard: Arc<RwLock<Leaderboard>>,
+    /// What happens after each hand, set by an admin; see `reset_policy`.
+    pub reset_policy: Arc<RwLock<Option<GameResetPolicy>>>,
+    /// Iroh nodes reported by `/iroh/peers`, see `peers`.
+    pub iroh_peers: Arc<RwLock<PeerRegistry>>,
+    /// Bracket of the tournament played on the tables, see `tournament`.
+    pub tournament: Arc<RwLock<TournamentTracker>>,
 }
 
 impl AppState {
-    /// Create a new AppState with the given config and optional config path
+    /// Create a new `AppState` with the given config and optional config path
     // TODO: config path should not be optional
     pub fn new(config: crate::config::Config, config_path: Option<PathBuf>) -> Self {
         let (tx, _rx) = broadcast::channel(CHANNEL_BUFFER_SIZE);
+        let mut observers = default_observers();
+        if let Some(path) = &config.event_log {
+            match LogObserver::open(std::path::Path::new(path)) {
+                Ok(log) => observers.push(Box::new(log)),
+                Err(e) => tracing::warn!(error = %e, "event log disabled"),
+            }
+        }
+        if let Some(path) = &config.game_log {
+            match GameLogObserver::open(std::path::Path::new(path)) {
+                Ok(log) => observers.push(Box::new(log)),
+                Err(e) => tracing::warn!(error = %e, "game log disabled"),
+            }
+        }
         Self {
-            lobby: Arc::new(RwLock::new(Lobby::default())),
+            tables: Arc::new(RwLock::new(TableManager::default())),
             broadcaster: tx,
             config: std::sync::Arc::new(RwLock::new(config)),
             config_path,
+            observers: Arc::new(RwLock::new(observers)),
+            game_config: None,
+            metrics: Arc::new(ServerMetrics::default()),
+            leaderboard: Arc::new(RwLock::new(Leaderboard::default())),
+            reset_policy: Arc::new(RwLock::new(None)),
+            iroh_peers: Arc::new(RwLock::new(PeerRegistry::default())),
+            tournament: Arc::new(RwLock::new(TournamentTracker::default())),
         }
     }
+
+    /// Deal new games by `game_config` instead of the built-in defaults.
+    #[must_use]
+    pub fn with_game_config(mut self, game_config: Option<GameConfig>) -> Self {
+        self.game_config = game_config.map(Arc::new);
+        self
+    }
+
+    /// Chips one rebuy may add, or `None` if the game config forbids rebuys.
+    async fn buy_in_range(&self) -> Option<(u32, u32)> {
+        match self.game_config.as_deref() {
+            Some(rules) if !rules.rebuy_policy.allowed => None,
+            Some(rules) => Some((rules.rebuy_policy.min_buy_in, rules.rebuy_policy.max_buy_in)),
+            None => {
+                let config = self.config.read().await;
+                Some((config.min_buy_in, config.max_buy_in))
+            }
+        }
+    }
+
+    /// Register an additional observer for game state mutations.
+    pub async fn add_observer(&self, observer: Box<dyn GameObserver + Send + Sync>) {
+        self.observers.write().await.push(observer);
+    }
+}
+
+fn default_observers() -> Vec<Box<dyn GameObserver + Send + Sync>> {
+    vec![
+        Box::new(StatsObserver),
+        Box::new(MetricsObserver),
+        Box::new(LeaderboardObserver),
+        Box::new(BroadcastObserver),
+        Box::new(TournamentObserver),
+        // After the broadcast, so clients see the final state of the hand first
+        Box::new(ResetPolicyObserver),
+    ]
 }
 
 #[derive(Clone)]
@@ -54,6 +153,12 @@ pub struct Lobby {
     pub(crate) bots: Vec<PlayerId>,
     /// Bot manager for AI decision making
     pub(crate) bot_manager: BotManager,
+    /// Avatar colors chosen by the players, attached to the public state.
+    pub(crate) avatar_colors: HashMap<PlayerId, [u8; 3]>,
+    /// Text hand histories of all completed hands in this session.
+    pub(crate) hand_history: Vec<String>,
+    /// Betting tendencies of each player over the completed hands.
+    pub(crate) player_stats: HashMap<PlayerId, PlayerStats>,
 }
 
 #[allow(clippy::derivable_impls)]
@@ -64,45 +169,182 @@ impl Default for Lobby {
             last_printed_log_len: 0,
             bots: Vec::new(),
             bot_manager: BotManager::default(),
+            avatar_colors: HashMap::new(),
+            hand_history: Vec::new(),
+            player_stats: HashMap::new(),
         }
     }
 }
 
+impl Lobby {
+    /// Number of the hand being played, counting from 1.
+    #[must_use]
+    pub fn hand_number(&self) -> u32 {
+        u32::try_from(self.hand_history.len() + 1).unwrap_or(u32::MAX)
+    }
+
+    /// Hand a human player's seat to a bot. The player keeps their stack and
+    /// cards, so the table stays at the same size and the hand can go on.
+    ///
+    /// # Errors
+    ///
+    /// If there is no game, or `player_id` is not in it or already a bot.
+    pub fn kick_player(&mut self, player_id: PlayerId) -> Result<()> {
+        let game = self
+            .game
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("No active game at this table"))?;
+        let idx = game
+            .players
+            .iter()
+            .position(|p| p.id == player_id)
+            .ok_or_else(|| anyhow::anyhow!("Unknown player id {player_id}"))?;
+        if self.bots.contains(&player_id) {
+            anyhow::bail!("Player {player_id} is already a bot");
+        }
+        game.kick_player(idx)?;
+        self.bots.push(player_id);
+        Ok(())
+    }
+}
+
 impl Default for AppState {
     fn default() -> Self {
         let (tx, _rx) = broadcast::channel(CHANNEL_BUFFER_SIZE);
-        AppState {
-            lobby: Arc::new(RwLock::new(Lobby::default())),
+        Self {
+            tables: Arc::new(RwLock::new(TableManager::default())),
             broadcaster: tx,
             config: std::sync::Arc::new(RwLock::new(crate::config::Config::default())),
             config_path: None,
+            observers: Arc::new(RwLock::new(default_observers())),
+            game_config: None,
+            metrics: Arc::new(ServerMetrics::default()),
+            leaderboard: Arc::new(RwLock::new(Leaderboard::default())),
+            reset_policy: Arc::new(RwLock::new(None)),
+            iroh_peers: Arc::new(RwLock::new(PeerRegistry::default())),
+            tournament: Arc::new(RwLock::new(TournamentTracker::default())),
         }
     }
 }
 
 /// Represents a subscription to broadcast state updates.
 pub struct Subscription {
-    pub receiver: broadcast::Receiver<mcg_shared::Backend2FrontendMsg>,
+    pub receiver: broadcast::Receiver<TableBroadcast>,
     pub initial_state: Option<GameStatePublic>,
 }
 
-/// Register a connection as a broadcast subscriber and capture the current state.
-pub async fn subscribe_connection(state: &AppState) -> Subscription {
+/// Register a connection as a broadcast subscriber and capture the current state
+/// of the table it is seated at.
+pub async fn subscribe_connection(state: &AppState, table_id: TableId) -> Subscription {
     let receiver = state.broadcaster.subscribe();
-    let initial_state = current_state_public(state).await;
+    let initial_state = current_state_public(state, table_id).await;
     Subscription {
         receiver,
         initial_state,
     }
 }
 
-/// Create a new game with the specified players.
+/// Open a new table and start a game on it with the configured players and blinds.
+///
+/// A server game config takes precedence over the blinds, limit and bounty
+/// requested by the client.
+///
+/// # Errors
+///
+/// If the game cannot be created with `config`.
+pub async fn create_table(state: &AppState, config: TableConfig) -> Result<TableId> {
+    let table_id = state.tables.write().await.create_table();
+    let (sb, bb, limit) = (config.sb, config.bb, config.betting_limit);
+    if let Err(e) = create_new_game(state, table_id, config).await {
+        state.tables.write().await.tables.remove(&table_id);
+        return Err(e);
+    }
+    if state.game_config.is_some() {
+        tracing::info!(table_id, "created table with the server game config");
+    } else {
+        tracing::info!(table_id, sb, bb, limit = %limit, "created table");
+    }
+    notify_hand_start(state, table_id).await;
+    Ok(table_id)
+}
+
+/// Order `players` by seat.
+///
+/// Players with a `preferred_seat` get that seat,
+/// everyone else fills the free seats in the order they are listed. The
+/// engine deals, rotates the button and acts by seat index, so the result
+/// fixes each player's position at the table.
+///
+/// # Errors
+///
+/// If a preferred seat does not exist or is wanted by two players.
+pub fn seat_players(
+    players: Vec<mcg_shared::PlayerConfig>,
+) -> Result<Vec<mcg_shared::PlayerConfig>> {
+    let seat_count = players.len();
+    let mut seats: Vec<Option<mcg_shared::PlayerConfig>> = vec![None; seat_count];
+    let mut unseated = Vec::new();
+    for player in players {
+        let Some(seat) = player.preferred_seat else {
+            unseated.push(player);
+            continue;
+        };
+        let Some(slot) = seats.get_mut(usize::from(seat)) else {
+            anyhow::bail!(
+                "{} asked for seat {}, but a table of {} players has no such seat",
+                player.name,
+                seat,
+                seat_count
+            );
+        };
+        if let Some(taken) = slot {
+            anyhow::bail!(
+                "{} and {} both asked for seat {}",
+                taken.name,
+                player.name,
+                seat
+            );
+        }
+        *slot = Some(player);
+    }
+    let mut unseated = unseated.into_iter();
+    Ok(seats
+        .into_iter()
+        .filter_map(|seat| seat.or_else(|| unseated.next()))
+        .collect())
+}
+
+/// Create a new game with the players of `config`, seated by `seat_players`.
+///
+/// The first hand is dealt with the blinds, limit and bounty of `config`
+/// unless a server game config sets them.
+///
+/// # Errors
+///
+/// If the table does not exist or the game cannot be created with its
+/// players.
 pub async fn create_new_game(
     state: &AppState,
-    players: Vec<mcg_shared::PlayerConfig>,
+    table_id: TableId,
+    config: TableConfig,
 ) -> Result<()> {
-    let mut lobby = state.lobby.write().await;
+    let mut tables = state.tables.write().await;
+    let lobby = tables
+        .get_mut(table_id)
+        .ok_or_else(|| anyhow::anyhow!("Unknown table {table_id}"))?;
+    let TableConfig {
+        players,
+        sb,
+        bb,
+        bounty,
+        betting_limit,
+    } = config;
     let player_count = players.len();
+    let rules = state.game_config.as_deref().cloned().unwrap_or_default();
+    if let Some(max) = rules.player_count.filter(|&max| player_count > max) {
+        anyhow::bail!("{player_count} players do not fit at a table of {max}");
+    }
+    let players = seat_players(players)?;
 
     // Convert PlayerConfig to internal Player format. The engine's Player type
     // is agnostic about bot status; the backend tracks bot-driven IDs separately.
@@ -112,53 +354,91 @@ pub async fn create_new_game(
         if config.is_bot {
             bot_ids.push(config.id);
         }
-        let player = Player {
-            id: config.id,
-            name: config.name.clone(),
-            stack: 1000, // Default stack size
-            cards: [
-                Card::new(CardRank::Ace, CardSuit::Clubs),
-                Card::new(CardRank::Ace, CardSuit::Clubs),
-            ], // Default cards initially
-            has_folded: false,
-            all_in: false,
-        };
-        game_players.push(player);
+        game_players.push(engine_player(config, rules.starting_stack));
     }
     // Store bot ids on the lobby so backend drive logic can consult it.
     lobby.bots = bot_ids;
+    lobby.avatar_colors = players
+        .iter()
+        .filter_map(|p| p.avatar_color.map(|c| (p.id, c)))
+        .collect();
 
     // Create the game with the players
-    let game = Game::with_players(game_players)
+    let (sb, bb, betting_limit, bounty) = if state.game_config.is_some() {
+        let (sb, bb) = rules.blinds_for_hand(1);
+        (sb, bb, rules.betting_limit, rules.bounty)
+    } else {
+        (sb, bb, betting_limit, bounty)
+    };
+    let mut game = Game::with_blinds(game_players, sb, bb, betting_limit)
         // TODO: evaluate with_context or context should be used
         .with_context(|| "creating new game with specified players")?;
+    game.hi_lo = rules.variant == GameVariant::HiLo;
+    if let Some(bounty) = bounty {
+        game.enable_bounties(bounty);
+    }
+    // Hands are counted on across games at the table
+    if let Some(previous) = &lobby.game {
+        game.hand_number += previous.hand_number;
+    }
+    game.last_hand = rules.hand_limit.map(|n| game.hand_number + n - 1);
 
     lobby.game = Some(game);
-    tracing::info!(player_count = player_count, "created new game");
+    drop(tables);
+    state
+        .leaderboard
+        .write()
+        .await
+        .record_session(players.iter().map(|p| p.name.as_str()));
+    tracing::info!(table_id, player_count = player_count, "created new game");
 
     Ok(())
 }
 
-pub async fn current_state_public(state: &AppState) -> Option<GameStatePublic> {
-    let lobby_r = state.lobby.read().await;
-    if let Some(game) = &lobby_r.game {
-        let gs = game.public();
-        Some(gs)
-    } else {
-        None
+/// The engine's player for `config`, before any cards are dealt.
+fn engine_player(config: &mcg_shared::PlayerConfig, stack: u32) -> Player {
+    Player {
+        id: config.id,
+        name: config.name.clone(),
+        stack,
+        cards: [
+            Card::new(CardRank::Ace, CardSuit::Clubs),
+            Card::new(CardRank::Ace, CardSuit::Clubs),
+        ], // Default cards initially
+        has_folded: false,
+        all_in: false,
     }
 }
 
-/// Broadcast the current state (and print new events to server console) to all subscribers.
+// The lobby is borrowed from the table lock until the state is built
+#[allow(clippy::significant_drop_tightening)]
+pub async fn current_state_public(state: &AppState, table_id: TableId) -> Option<GameStatePublic> {
+    let tables = state.tables.read().await;
+    let lobby = tables.get(table_id)?;
+    let mut gs = lobby.game.as_ref()?.public();
+    for p in &mut gs.players {
+        p.avatar_color = lobby.avatar_colors.get(&p.id).copied();
+    }
+    if let Some(rules) = &state.game_config {
+        gs.blind_level = Some(rules.blind_level_for_hand(lobby.hand_number()));
+    }
+    Some(gs)
+}
+
+/// Broadcast the current state of a table (and print new events to server
+/// console) to all subscribers seated at that table.
 ///
 /// Transports receive the same `ServerMsg::State` payload; the backend does not
 /// embed per-connection personalization in the broadcast. If transports or a
 /// future session manager needs to expose client-specific views, they should
 /// compute those on the transport/session layer.
-pub async fn broadcast_state(state: &AppState) {
-    if let Some(gs) = current_state_public(state).await {
+pub async fn broadcast_state(state: &AppState, table_id: TableId) {
+    if let Some(gs) = current_state_public(state, table_id).await {
         // Print any newly added events to server console and update bookkeeping.
-        let mut lobby = state.lobby.write().await;
+        let mut tables = state.tables.write().await;
+        let Some(lobby) = tables.get_mut(table_id) else {
+            return;
+        };
         let already = lobby.last_printed_log_len;
         let total = gs.action_log.len();
         if total > already {
@@ -170,50 +450,91 @@ pub async fn broadcast_state(state: &AppState) {
             }
             lobby.last_printed_log_len = total;
         }
-        drop(lobby);
+        let short_stacked = short_stacked_players(lobby);
+        drop(tables);
 
         // Broadcast the new state to all subscribers.
         let subscriber_count = state.broadcaster.receiver_count();
         let current_player_name = mcg_shared::PlayerPublic::name_of(&gs.players, gs.to_act);
         tracing::info!(
-            "📡 Broadcasting game state to {} subscribers (stage: {:?}, to_act: {})",
+            "📡 Broadcasting game state of table {} to {} subscribers (stage: {:?}, to_act: {})",
+            table_id,
             subscriber_count,
             gs.stage,
             current_player_name
         );
-        let _ = state.broadcaster.send(mcg_shared::Backend2FrontendMsg::State(gs));
+        let _ = state
+            .broadcaster
+            .send((table_id, mcg_shared::Backend2FrontendMsg::State(gs)));
+
+        if short_stacked.is_empty() {
+            return;
+        }
+        // Nobody is offered a rebuy in games that forbid them
+        if let Some((min_amount, max_amount)) = state.buy_in_range().await {
+            for player_id in short_stacked {
+                let msg = mcg_shared::Backend2FrontendMsg::RebuyAvailable {
+                    player_id,
+                    min_amount,
+                    max_amount,
+                };
+                let _ = state.broadcaster.send((table_id, msg));
+            }
+        }
     }
 }
 
-/// Apply an action to the game's state. Returns Some(error_string) if the
-/// underlying Game::apply_player_action returned an error, otherwise None.
+/// Apply an action to the game's state and notify the observers about the
+/// resulting events. Returns `Some(error_string)` if the underlying
+/// `Game::apply_player_action` returned an error, otherwise None.
+// The game is borrowed from the table lock until its new events are taken
+#[allow(clippy::significant_drop_tightening)]
 pub async fn apply_action_to_game(
     state: &AppState,
+    table_id: TableId,
     // TODO: should this be a playerid?
     actor: usize,
     action: mcg_shared::PlayerAction,
 ) -> Option<String> {
-    let mut lobby = state.lobby.write().await;
-    if let Some(game) = &mut lobby.game {
+    let (events, new_stage) = {
+        let mut tables = state.tables.write().await;
+        let game = tables.get_mut(table_id).and_then(|l| l.game.as_mut())?;
+        let logged_before = game.logged_events;
+        let stage_before = game.stage;
         if let Err(e) = game.apply_player_action(actor, action) {
             return Some(e.to_string());
         }
-    }
+        game.update_pot_equities();
+        let events = game.events_since(logged_before).to_vec();
+        (events, (game.stage != stage_before).then_some(game.stage))
+    };
+    notify_observers(state, table_id, &events, new_stage).await;
     None
 }
 
-/// Validate that the provided player_id is currently allowed to take an action
+/// Validate that the provided `player_id` is currently allowed to take an action
 /// and apply the action. Returns Ok(()) on success or Err(String) with an error
 /// message to send back to the client.
+///
+/// # Errors
+///
+/// With the message for the client if the table, game or player does not
+/// exist, or the action is not allowed.
+// The game is borrowed from the table lock for the whole validation
+#[allow(clippy::significant_drop_tightening)]
 pub async fn validate_and_apply_action(
     state: &AppState,
+    table_id: TableId,
     player_id: PlayerId,
     action: mcg_shared::PlayerAction,
 ) -> Result<(), String> {
     // Single lock acquisition for all validation
     let actor_idx = {
-        let lobby_r = state.lobby.read().await;
-        let game = lobby_r
+        let tables = state.tables.read().await;
+        let lobby = tables
+            .get(table_id)
+            .ok_or_else(|| format!("Unknown table {table_id}"))?;
+        let game = lobby
             .game
             .as_ref()
             .ok_or("No active game. Please start a new game first.")?;
@@ -224,14 +545,16 @@ pub async fn validate_and_apply_action(
             .position(|p| p.id == player_id)
             .ok_or("Unknown player id")?;
 
-        if game.stage == mcg_shared::Stage::Showdown || game.to_act != idx {
+        // Blinds to play are posted out of turn, the game checks when
+        let out_of_turn = matches!(action, mcg_shared::PlayerAction::PostBlind);
+        if !out_of_turn && (game.stage == mcg_shared::Stage::Showdown || game.to_act != idx) {
             return Err("Not your turn".into());
         }
         idx
     };
 
     // Apply the action using the existing helper. translate underlying errors to String.
-    if let Some(e) = apply_action_to_game(state, actor_idx, action).await {
+    if let Some(e) = apply_action_to_game(state, table_id, actor_idx, action).await {
         return Err(e);
     }
     Ok(())
@@ -240,128 +563,444 @@ pub async fn validate_and_apply_action(
 /// Handle an Action message from a client
 async fn execute_player_action(
     state: &AppState,
+    table_id: TableId,
     player_id: PlayerId,
     action: mcg_shared::PlayerAction,
 ) -> mcg_shared::Backend2FrontendMsg {
-    match validate_and_apply_action(state, player_id, action.clone()).await {
-        Ok(()) => {
-            broadcast_state(state).await;
-            if let Some(gs) = current_state_public(state).await {
-                mcg_shared::Backend2FrontendMsg::State(gs)
-            } else {
-                mcg_shared::Backend2FrontendMsg::Error("No active game after action".into())
-            }
-        }
-        Err(e) => mcg_shared::Backend2FrontendMsg::Error(e.to_string()),
+    match validate_and_apply_action(state, table_id, player_id, action.clone()).await {
+        Ok(()) => current_state_public(state, table_id).await.map_or_else(
+            || mcg_shared::Backend2FrontendMsg::Error("No active game after action".into()),
+            mcg_shared::Backend2FrontendMsg::State,
+        ),
+        Err(e) => mcg_shared::Backend2FrontendMsg::Error(e),
     }
 }
 
-/// Handle a RequestState message from a client
-async fn fetch_current_state(state: &AppState) -> mcg_shared::Backend2FrontendMsg {
-    if let Some(gs) = current_state_public(state).await {
-        broadcast_state(state).await;
+/// Handle a `RequestState` message from a client
+async fn fetch_current_state(
+    state: &AppState,
+    table_id: TableId,
+) -> mcg_shared::Backend2FrontendMsg {
+    if let Some(gs) = current_state_public(state, table_id).await {
+        broadcast_state(state, table_id).await;
         mcg_shared::Backend2FrontendMsg::State(gs)
     } else {
-        mcg_shared::Backend2FrontendMsg::Error("No active game. Please start a new game first.".into())
+        mcg_shared::Backend2FrontendMsg::Error(
+            "No active game. Please start a new game first.".into(),
+        )
     }
 }
 
-/// Handle a NextHand message from a client
-async fn advance_to_next_hand(state: &AppState) -> mcg_shared::Backend2FrontendMsg {
+/// Handle a `ReviewHand` message from a client
+async fn review_finished_hand(
+    state: &AppState,
+    table_id: TableId,
+) -> mcg_shared::Backend2FrontendMsg {
+    // Unlike the broadcast state, the full state shows every player's cards
+    let gs = {
+        let tables = state.tables.read().await;
+        match tables.get(table_id).and_then(|l| l.game.as_ref()) {
+            Some(game) => game.public(),
+            None => {
+                return mcg_shared::Backend2FrontendMsg::Error(
+                    "No active game. Please start a new game first.".into(),
+                )
+            }
+        }
+    };
+    let mut estimator = EquityEstimator::new(REVIEW_RUNOUTS, 0);
+    match review_hand(&gs, &mut estimator) {
+        Ok(annotations) => mcg_shared::Backend2FrontendMsg::HandReview(annotations),
+        Err(e) => mcg_shared::Backend2FrontendMsg::Error(format!("Cannot review hand: {e}")),
+    }
+}
+
+/// Handle a `NextHand` message from a client
+async fn advance_to_next_hand(
+    state: &AppState,
+    table_id: TableId,
+) -> mcg_shared::Backend2FrontendMsg {
     // Ensure a game exists first
     {
-        let lobby_r = state.lobby.read().await;
-        if lobby_r.game.is_none() {
+        let tables = state.tables.read().await;
+        if tables.get(table_id).and_then(|l| l.game.as_ref()).is_none() {
             return mcg_shared::Backend2FrontendMsg::Error(
                 "No active game. Please start a new game first.".into(),
             );
         }
     }
 
-    match start_new_hand_and_print(state).await {
+    match start_new_hand_and_print(state, table_id).await {
         Ok(()) => {
-            broadcast_state(state).await;
-            if let Some(gs) = current_state_public(state).await {
-                mcg_shared::Backend2FrontendMsg::State(gs)
-            } else {
-                mcg_shared::Backend2FrontendMsg::Error("No active game after starting next hand".into())
-            }
+            broadcast_state(state, table_id).await;
+            current_state_public(state, table_id).await.map_or_else(
+                || {
+                    mcg_shared::Backend2FrontendMsg::Error(
+                        "No active game after starting next hand".into(),
+                    )
+                },
+                mcg_shared::Backend2FrontendMsg::State,
+            )
         }
-        Err(e) => mcg_shared::Backend2FrontendMsg::Error(format!("Failed to start new hand: {}", e)),
+        Err(e) => mcg_shared::Backend2FrontendMsg::Error(format!("Failed to start new hand: {e}")),
     }
 }
 
-/// Handle a NewGame message from a client
+/// Handle a `NewGame` message from a client
 async fn create_game_session(
     state: &AppState,
+    table_id: TableId,
     players: Vec<mcg_shared::PlayerConfig>,
 ) -> mcg_shared::Backend2FrontendMsg {
-    match create_new_game(state, players).await {
+    match create_new_game(state, table_id, TableConfig::new(players)).await {
         Ok(()) => {
-            broadcast_state(state).await;
-            if let Some(gs) = current_state_public(state).await {
-                mcg_shared::Backend2FrontendMsg::State(gs)
-            } else {
-                mcg_shared::Backend2FrontendMsg::Error(
-                    "Failed to produce initial state after creating game".into(),
-                )
-            }
+            notify_hand_start(state, table_id).await;
+            broadcast_state(state, table_id).await;
+            current_state_public(state, table_id).await.map_or_else(
+                || {
+                    mcg_shared::Backend2FrontendMsg::Error(
+                        "Failed to produce initial state after creating game".into(),
+                    )
+                },
+                mcg_shared::Backend2FrontendMsg::State,
+            )
         }
-        Err(e) => mcg_shared::Backend2FrontendMsg::Error(format!("Failed to create new game: {}", e)),
+        Err(e) => mcg_shared::Backend2FrontendMsg::Error(format!("Failed to create new game: {e}")),
     }
 }
 
-/// Handle a PushState message from a peer node (P2P state sync)
+/// Handle a `PushState` message from a peer node (P2P state sync)
 async fn import_game_state(
     app_state: &AppState,
+    table_id: TableId,
     game_state: serde_json::Value,
 ) -> mcg_shared::Backend2FrontendMsg {
     match serde_json::from_value::<Game>(game_state) {
         Ok(game) => {
-            let mut lobby = app_state.lobby.write().await;
+            let mut tables = app_state.tables.write().await;
+            let Some(lobby) = tables.get_mut(table_id) else {
+                return mcg_shared::Backend2FrontendMsg::Error(format!("Unknown table {table_id}"));
+            };
             lobby.game = Some(game);
             lobby.last_printed_log_len = 0; // Reset log tracking since state was replaced
-            drop(lobby);
-
-            broadcast_state(app_state).await;
-            if let Some(gs) = current_state_public(app_state).await {
-                tracing::info!("Game state replaced via PushState from peer");
-                mcg_shared::Backend2FrontendMsg::State(gs)
-            } else {
-                mcg_shared::Backend2FrontendMsg::Error("Failed to produce state after PushState".into())
-            }
+            drop(tables);
+
+            broadcast_state(app_state, table_id).await;
+            current_state_public(app_state, table_id).await.map_or_else(
+                || {
+                    mcg_shared::Backend2FrontendMsg::Error(
+                        "Failed to produce state after PushState".into(),
+                    )
+                },
+                |gs| {
+                    tracing::info!("Game state replaced via PushState from peer");
+                    mcg_shared::Backend2FrontendMsg::State(gs)
+                },
+            )
+        }
+        Err(e) => {
+            mcg_shared::Backend2FrontendMsg::Error(format!("Failed to deserialize game state: {e}"))
+        }
+    }
+}
+
+/// Handle a Rebuy message from a client
+// The game is borrowed from the table lock until the rebuy
+#[allow(clippy::significant_drop_tightening)]
+async fn rebuy_player(
+    state: &AppState,
+    table_id: TableId,
+    player_id: PlayerId,
+    amount: u32,
+) -> mcg_shared::Backend2FrontendMsg {
+    let Some((min_buy_in, max_buy_in)) = state.buy_in_range().await else {
+        return mcg_shared::Backend2FrontendMsg::Error(
+            "Rebuys are not allowed in this game".into(),
+        );
+    };
+    if !(min_buy_in..=max_buy_in).contains(&amount) {
+        return mcg_shared::Backend2FrontendMsg::Error(format!(
+            "Rebuy amount must be between {min_buy_in} and {max_buy_in}"
+        ));
+    }
+
+    {
+        let mut tables = state.tables.write().await;
+        let Some(game) = tables.get_mut(table_id).and_then(|l| l.game.as_mut()) else {
+            return mcg_shared::Backend2FrontendMsg::Error(
+                "No active game. Please start a new game first.".into(),
+            );
+        };
+        let Some(idx) = game.players.iter().position(|p| p.id == player_id) else {
+            return mcg_shared::Backend2FrontendMsg::Error("Unknown player id".into());
+        };
+        if let Err(e) = game.rebuy(idx, amount) {
+            return mcg_shared::Backend2FrontendMsg::Error(e.to_string());
+        }
+    }
+
+    broadcast_state(state, table_id).await;
+    current_state_public(state, table_id).await.map_or_else(
+        || mcg_shared::Backend2FrontendMsg::Error("No active game after rebuy".into()),
+        mcg_shared::Backend2FrontendMsg::State,
+    )
+}
+
+/// Replace a player by a bot and tell the kicked player's connection, which
+/// the transport then closes.
+///
+/// # Errors
+///
+/// With the message for the admin if the table or player does not exist.
+// The lobby is borrowed from the table lock until the kick
+#[allow(clippy::significant_drop_tightening)]
+pub async fn kick_player(
+    state: &AppState,
+    table_id: TableId,
+    player_id: PlayerId,
+) -> Result<(), String> {
+    {
+        let mut tables = state.tables.write().await;
+        let lobby = tables
+            .get_mut(table_id)
+            .ok_or_else(|| format!("Unknown table {table_id}"))?;
+        lobby.kick_player(player_id).map_err(|e| e.to_string())?;
+    }
+    tracing::info!(table_id, player_id = %player_id, "kicked player, a bot takes over");
+
+    broadcast_state(state, table_id).await;
+    let _ = state.broadcaster.send((
+        table_id,
+        mcg_shared::Backend2FrontendMsg::PlayerKicked(player_id),
+    ));
+    Ok(())
+}
+
+/// Take back the last player action at a table. Observers, such as the
+/// metrics, have already seen the action and are not told.
+#[cfg(feature = "undo")]
+// The game is borrowed from the table lock until the action is undone
+#[allow(clippy::significant_drop_tightening)]
+async fn undo_last_action(state: &AppState, table_id: TableId) -> mcg_shared::Backend2FrontendMsg {
+    {
+        let mut tables = state.tables.write().await;
+        let Some(game) = tables.get_mut(table_id).and_then(|l| l.game.as_mut()) else {
+            return mcg_shared::Backend2FrontendMsg::Error(
+                "No active game. Please start a new game first.".into(),
+            );
+        };
+        if let Err(e) = game.undo_last_action() {
+            return mcg_shared::Backend2FrontendMsg::Error(e.to_string());
+        }
+    }
+    tracing::info!(table_id, "undid the last player action");
+
+    broadcast_state(state, table_id).await;
+    current_state_public(state, table_id).await.map_or_else(
+        || mcg_shared::Backend2FrontendMsg::Error("No active game after undo".into()),
+        mcg_shared::Backend2FrontendMsg::State,
+    )
+}
+
+/// Handle an `AdminKick` message from a privileged client
+async fn admin_kick(
+    state: &AppState,
+    table_id: TableId,
+    player_id: PlayerId,
+    token: &str,
+) -> mcg_shared::Backend2FrontendMsg {
+    if !state.config.read().await.is_admin_token(token) {
+        return mcg_shared::Backend2FrontendMsg::Error("Invalid admin token".into());
+    }
+    if let Err(e) = kick_player(state, table_id, player_id).await {
+        return mcg_shared::Backend2FrontendMsg::Error(e);
+    }
+    current_state_public(state, table_id).await.map_or_else(
+        || mcg_shared::Backend2FrontendMsg::Error("No active game after kick".into()),
+        mcg_shared::Backend2FrontendMsg::State,
+    )
+}
+
+/// Handle a `SetResetPolicy` message from a privileged client. The policy
+/// applies to every table, so the reply is the table list.
+async fn set_reset_policy(
+    state: &AppState,
+    policy: Option<GameResetPolicy>,
+    token: &str,
+) -> mcg_shared::Backend2FrontendMsg {
+    if !state.config.read().await.is_admin_token(token) {
+        return mcg_shared::Backend2FrontendMsg::Error("Invalid admin token".into());
+    }
+    *state.reset_policy.write().await = policy;
+    tracing::info!(?policy, "set the reset policy");
+    mcg_shared::Backend2FrontendMsg::Tables(state.tables.read().await.summaries())
+}
+
+/// Human players at a finished hand whose stack fell below the rebuy threshold.
+fn short_stacked_players(lobby: &Lobby) -> Vec<PlayerId> {
+    let Some(game) = lobby.game.as_ref() else {
+        return Vec::new();
+    };
+    if game.stage != mcg_shared::Stage::Showdown {
+        return Vec::new();
+    }
+    game.players
+        .iter()
+        .filter(|p| p.stack < game.rebuy_threshold() && !lobby.bots.contains(&p.id))
+        .map(|p| p.id)
+        .collect()
+}
+
+/// Handle a `CreateTable` message from a client
+async fn open_table(state: &AppState, config: TableConfig) -> mcg_shared::Backend2FrontendMsg {
+    match create_table(state, config).await {
+        Ok(table_id) => {
+            broadcast_state(state, table_id).await;
+            mcg_shared::Backend2FrontendMsg::TableJoined(table_id)
         }
-        Err(e) => mcg_shared::Backend2FrontendMsg::Error(format!("Failed to deserialize game state: {}", e)),
+        Err(e) => mcg_shared::Backend2FrontendMsg::Error(format!("Failed to create table: {e}")),
     }
 }
 
-/// Unified handler for ClientMsg coming from any transport.
+/// Handle a `JoinTable` message from a client
+async fn join_table(state: &AppState, table_id: TableId) -> mcg_shared::Backend2FrontendMsg {
+    if state.tables.read().await.get(table_id).is_some() {
+        mcg_shared::Backend2FrontendMsg::TableJoined(table_id)
+    } else {
+        mcg_shared::Backend2FrontendMsg::Error(format!("Unknown table {table_id}"))
+    }
+}
+
+/// Handle a `TakeSeat` message from a client
+// The lobby is borrowed from the table lock until the player is seated
+#[allow(clippy::significant_drop_tightening)]
+async fn take_seat(
+    state: &AppState,
+    table_id: TableId,
+    config: mcg_shared::PlayerConfig,
+) -> mcg_shared::Backend2FrontendMsg {
+    // Bots never owe a blind to play, they are seated with the game
+    if config.is_bot {
+        return mcg_shared::Backend2FrontendMsg::Error(
+            "Bots are seated when the game starts".into(),
+        );
+    }
+    let starting_stack = state.game_config.as_deref().map_or_else(
+        || GameConfig::default().starting_stack,
+        |r| r.starting_stack,
+    );
+
+    {
+        let mut tables = state.tables.write().await;
+        let Some(lobby) = tables.get_mut(table_id) else {
+            return mcg_shared::Backend2FrontendMsg::Error(format!("Unknown table {table_id}"));
+        };
+        let Some(game) = lobby.game.as_mut() else {
+            return mcg_shared::Backend2FrontendMsg::Error(
+                "No active game. Please start a new game first.".into(),
+            );
+        };
+        if game.players.iter().any(|p| p.id == config.id) {
+            return mcg_shared::Backend2FrontendMsg::Error(format!(
+                "Player {} is already seated",
+                config.id
+            ));
+        }
+        let free_seat = game
+            .players
+            .seats()
+            .iter()
+            .find(|s| s.player.is_none())
+            .map(|s| s.seat_number);
+        let Some(seat) = config.preferred_seat.or(free_seat) else {
+            return mcg_shared::Backend2FrontendMsg::Error(TableError::TableFull.to_string());
+        };
+        if let Err(e) = game.join_at_seat(seat, engine_player(&config, starting_stack)) {
+            return mcg_shared::Backend2FrontendMsg::Error(e.to_string());
+        }
+        if let Some(color) = config.avatar_color {
+            lobby.avatar_colors.insert(config.id, color);
+        }
+    }
+    tracing::info!(table_id, player_id = %config.id, "player took a seat");
+
+    broadcast_state(state, table_id).await;
+    current_state_public(state, table_id).await.map_or_else(
+        || mcg_shared::Backend2FrontendMsg::Error("No active game after seating".into()),
+        mcg_shared::Backend2FrontendMsg::State,
+    )
+}
+
+/// Unified handler for `ClientMsg` coming from any transport.
 ///
 /// Centralizes validation, state mutation, and side-effects (broadcasting and
-/// bot-driving). Returns a ServerMsg that the originating transport should send
+/// bot-driving). Returns a `ServerMsg` that the originating transport should send
 /// back to the client. Transports should delegate to this function rather than
 /// duplicating handling logic to ensure consistent behavior across transports.
+///
+/// `table_id` is the table the client is seated at; actions carry their own table id.
 pub async fn dispatch_client_message(
     state: &AppState,
+    table_id: TableId,
     cm: mcg_shared::Frontend2BackendMsg,
 ) -> mcg_shared::Backend2FrontendMsg {
     match cm {
-        mcg_shared::Frontend2BackendMsg::Action { player_id, action } => {
-            execute_player_action(state, player_id, action).await
+        mcg_shared::Frontend2BackendMsg::Action {
+            table_id,
+            player_id,
+            action,
+        } => execute_player_action(state, table_id, player_id, action).await,
+        mcg_shared::Frontend2BackendMsg::Subscribe => {
+            mcg_shared::Backend2FrontendMsg::Error("not supported".into())
+        }
+        mcg_shared::Frontend2BackendMsg::RequestState { .. } => {
+            fetch_current_state(state, table_id).await
         }
-        mcg_shared::Frontend2BackendMsg::Subscribe => mcg_shared::Backend2FrontendMsg::Error("not supported".into()),
-        mcg_shared::Frontend2BackendMsg::RequestState => fetch_current_state(state).await,
         mcg_shared::Frontend2BackendMsg::Ping => {
             tracing::info!("received ping from client");
             mcg_shared::Backend2FrontendMsg::Pong
         }
-        mcg_shared::Frontend2BackendMsg::NextHand => advance_to_next_hand(state).await,
-        mcg_shared::Frontend2BackendMsg::NewGame { players } => create_game_session(state, players).await,
+        mcg_shared::Frontend2BackendMsg::NextHand { .. } => {
+            advance_to_next_hand(state, table_id).await
+        }
+        mcg_shared::Frontend2BackendMsg::ReviewHand => review_finished_hand(state, table_id).await,
+        mcg_shared::Frontend2BackendMsg::ClientVersion { version } => {
+            check_client_version(&version)
+        }
+        mcg_shared::Frontend2BackendMsg::NewGame { players, .. } => {
+            create_game_session(state, table_id, players).await
+        }
         mcg_shared::Frontend2BackendMsg::PushState { state: game_state } => {
-            import_game_state(state, game_state).await
+            import_game_state(state, table_id, game_state).await
+        }
+        mcg_shared::Frontend2BackendMsg::CreateTable { config } => open_table(state, config).await,
+        mcg_shared::Frontend2BackendMsg::JoinTable { table_id } => {
+            join_table(state, table_id).await
+        }
+        mcg_shared::Frontend2BackendMsg::TakeSeat { player } => {
+            take_seat(state, table_id, player).await
+        }
+        mcg_shared::Frontend2BackendMsg::ListTables => {
+            mcg_shared::Backend2FrontendMsg::Tables(state.tables.read().await.summaries())
+        }
+        mcg_shared::Frontend2BackendMsg::RequestTournament => {
+            mcg_shared::Backend2FrontendMsg::TournamentState(
+                state.tournament.read().await.state().clone(),
+            )
         }
+        mcg_shared::Frontend2BackendMsg::Rebuy { player_id, amount } => {
+            rebuy_player(state, table_id, player_id, amount).await
+        }
+        mcg_shared::Frontend2BackendMsg::AdminKick { player_id, token } => {
+            admin_kick(state, table_id, player_id, &token).await
+        }
+        mcg_shared::Frontend2BackendMsg::SetResetPolicy { policy, token } => {
+            set_reset_policy(state, policy, &token).await
+        }
+        #[cfg(feature = "undo")]
+        mcg_shared::Frontend2BackendMsg::UndoAction => undo_last_action(state, table_id).await,
         mcg_shared::Frontend2BackendMsg::QrReq(file) => {
-            match File::open(format!("media/qr_test/{}", file)).await {
+            match File::open(format!("media/qr_test/{file}")).await {
                 Ok(mut file) => {
                     let mut buf = Vec::new();
                     match file.read_to_end(&mut buf).await {
@@ -378,14 +1017,75 @@ pub async fn dispatch_client_message(
     }
 }
 
+/// Answer a client announcing its version: another `Welcome` if it speaks
+/// our protocol, an error if the major versions differ.
+fn check_client_version(version: &str) -> mcg_shared::Backend2FrontendMsg {
+    if mcg_shared::versions_compatible(SERVER_VERSION, version) {
+        welcome_msg()
+    } else {
+        tracing::warn!(client_version = %version, server_version = SERVER_VERSION, "incompatible client");
+        mcg_shared::Backend2FrontendMsg::Error("Incompatible client".into())
+    }
+}
+
+/// Render the hand history of a table's session, including the hand currently
+/// in play with its hole cards hidden until showdown.
+// The hands are borrowed from the table lock until they are formatted
+#[allow(clippy::significant_drop_tightening)]
+pub async fn session_hand_history(state: &AppState, table_id: TableId) -> String {
+    let tables = state.tables.read().await;
+    let Some(lobby) = tables.get(table_id) else {
+        return String::new();
+    };
+    let mut hands = lobby.hand_history.clone();
+    if let Some(game) = &lobby.game {
+        let hand_number = lobby.hand_number();
+        hands.push(pretty::HandHistoryWriter::format_hand(
+            &game.public().seen_by(None),
+            hand_number,
+            unix_timestamp(),
+        ));
+    }
+    pretty::HandHistoryWriter::format_session(&hands)
+}
+
+fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
 /// Advance to the next hand (increment dealer, start a new hand) and print a table header.
-pub async fn start_new_hand_and_print(state: &AppState) -> Result<()> {
-    let mut lobby = state.lobby.write().await;
+///
+/// The finished hand is appended to the session hand history before dealing.
+///
+/// # Errors
+///
+/// If the table does not exist or the next hand cannot be dealt.
+pub async fn start_new_hand_and_print(state: &AppState, table_id: TableId) -> Result<()> {
+    let mut tables = state.tables.write().await;
+    let Some(lobby) = tables.get_mut(table_id) else {
+        anyhow::bail!("Unknown table {table_id}");
+    };
+    let hand_number = lobby.hand_number();
     if let Some(game) = &mut lobby.game {
+        if game.hands_remaining() == Some(1) {
+            anyhow::bail!("the game is over after {} hands", game.hand_number);
+        }
+        lobby
+            .hand_history
+            .push(pretty::HandHistoryWriter::format_hand(
+                &game.public(),
+                hand_number,
+                unix_timestamp(),
+            ));
         let n = game.players.len();
         if n > 0 {
             game.dealer_idx = (game.dealer_idx + 1) % n;
         }
+        if let Some(rules) = &state.game_config {
+            (game.sb, game.bb) = rules.blinds_for_hand(hand_number + 1);
+        }
         game.start_new_hand()?;
         let sb = game.sb;
         let bb = game.bb;
@@ -396,5 +1096,7 @@ pub async fn start_new_hand_and_print(state: &AppState) -> Result<()> {
         let header = pretty::format_table_header(&gs, sb, bb, std::io::stdout().is_terminal());
         tracing::info!("{}", header);
     }
+    drop(tables);
+    notify_hand_start(state, table_id).await;
     Ok(())
 }
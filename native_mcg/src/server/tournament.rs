@@ -0,0 +1,242 @@
+// Bracket of the tournament played on the server's tables, reported to
+// clients as `TournamentState`.
+//
+// Every table with a game takes part. After each hand the seating is compared
+// with the latest round: when players moved, joined or left, e.g. because a
+// table was broken up, a new round starts. Otherwise only the stacks and
+// eliminations of the latest round are updated.
+
+use std::collections::{BTreeMap, HashSet};
+
+use async_trait::async_trait;
+use mcg_shared::{
+    Backend2FrontendMsg, BracketPlayer, BracketTable, HandResult, PlayerId, TableId,
+    TournamentRound, TournamentState,
+};
+
+use crate::server::observer::GameObserver;
+use crate::server::state::AppState;
+
+/// Players with chips left, by table
+type Seating = BTreeMap<TableId, HashSet<PlayerId>>;
+
+/// The players of one table with their stacks, in seat order
+pub type TablePlayers = (TableId, Vec<(PlayerId, String, u32)>);
+
+/// Rounds of the tournament since the server started.
+#[derive(Debug, Default)]
+pub struct TournamentTracker {
+    state: TournamentState,
+}
+
+impl TournamentTracker {
+    #[must_use]
+    pub const fn state(&self) -> &TournamentState {
+        &self.state
+    }
+
+    /// Add the tables after a hand; a player without chips is out of the
+    /// tournament. Returns whether the bracket changed.
+    pub fn record(&mut self, tables: &[TablePlayers]) -> bool {
+        if tables.is_empty() {
+            return false;
+        }
+        let busted: HashSet<(TableId, PlayerId)> = tables
+            .iter()
+            .flat_map(|(table_id, players)| {
+                players
+                    .iter()
+                    .filter(|p| p.2 == 0)
+                    .map(move |p| (*table_id, p.0))
+            })
+            .collect();
+        let seating: Seating = tables
+            .iter()
+            .map(|(table_id, players)| {
+                let ids = players.iter().filter(|p| p.2 > 0).map(|p| p.0).collect();
+                (*table_id, ids)
+            })
+            .collect();
+        let latest = self.state.rounds.last();
+        // Busting out alone keeps the round going
+        let new_round = latest.is_none_or(|latest| {
+            let mut before: Seating = latest
+                .tables
+                .iter()
+                .map(|t| {
+                    let ids = t
+                        .players
+                        .iter()
+                        .filter(|p| p.eliminated_in_round.is_none())
+                        .map(|p| p.player_id)
+                        .filter(|id| !busted.contains(&(t.table_id, *id)))
+                        .collect();
+                    (t.table_id, ids)
+                })
+                .collect();
+            before.retain(|_, ids| !ids.is_empty());
+            before != seating.into_iter().filter(|(_, ids)| !ids.is_empty()).collect()
+        });
+        let round = match latest {
+            Some(latest) if !new_round => latest.round,
+            Some(latest) => latest.round + 1,
+            None => 1,
+        };
+
+        let mut bracket: Vec<BracketTable> = tables
+            .iter()
+            .map(|(table_id, players)| BracketTable {
+                table_id: *table_id,
+                players: players
+                    .iter()
+                    .map(|(player_id, name, stack)| BracketPlayer {
+                        player_id: *player_id,
+                        name: name.clone(),
+                        stack: *stack,
+                        eliminated_in_round: (*stack == 0).then(|| {
+                            self.eliminated_in(*table_id, *player_id).unwrap_or(round)
+                        }),
+                    })
+                    .collect(),
+            })
+            .collect();
+        bracket.sort_by_key(|t| t.table_id);
+
+        match self.state.rounds.last_mut() {
+            Some(latest) if !new_round => {
+                if latest.tables == bracket {
+                    return false;
+                }
+                latest.tables = bracket;
+            }
+            _ => self.state.rounds.push(TournamentRound {
+                round,
+                tables: bracket,
+            }),
+        }
+        true
+    }
+
+    /// Round a player seated at `table_id` in the latest round busted out in.
+    fn eliminated_in(&self, table_id: TableId, player_id: PlayerId) -> Option<u32> {
+        self.state
+            .rounds
+            .last()?
+            .tables
+            .iter()
+            .filter(|t| t.table_id == table_id)
+            .flat_map(|t| &t.players)
+            .find(|p| p.player_id == player_id)?
+            .eliminated_in_round
+    }
+}
+
+/// Keeps the bracket on the `AppState` up to date and sends it to every
+/// table when it changes.
+pub struct TournamentObserver;
+
+#[async_trait]
+impl GameObserver for TournamentObserver {
+    // The games are borrowed from the table lock until the snapshot is taken
+    #[allow(clippy::significant_drop_tightening)]
+    async fn on_hand_end(&self, state: &AppState, _table_id: TableId, _results: &[HandResult]) {
+        let (table_ids, tables) = {
+            let tables = state.tables.read().await;
+            let table_ids = tables.table_ids();
+            let players: Vec<TablePlayers> = table_ids
+                .iter()
+                .filter_map(|&id| {
+                    let game = tables.get(id)?.game.as_ref()?;
+                    let players = game
+                        .players
+                        .iter()
+                        .map(|p| (p.id, p.name.clone(), p.stack))
+                        .collect();
+                    Some((id, players))
+                })
+                .collect();
+            (table_ids, players)
+        };
+        let bracket = {
+            let mut tracker = state.tournament.write().await;
+            if !tracker.record(&tables) {
+                return;
+            }
+            tracker.state().clone()
+        };
+        // Also to tables without a game, where the bracket screen may listen
+        for table_id in table_ids {
+            let _ = state
+                .broadcaster
+                .send((table_id, Backend2FrontendMsg::TournamentState(bracket.clone())));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(table_id: TableId, stacks: &[(usize, u32)]) -> TablePlayers {
+        let players = stacks
+            .iter()
+            .map(|&(id, stack)| (PlayerId(id), format!("P{id}"), stack))
+            .collect();
+        (table_id, players)
+    }
+
+    #[test]
+    fn hands_at_the_same_tables_update_the_round() {
+        let mut tracker = TournamentTracker::default();
+        assert!(tracker.record(&[table(1, &[(0, 100), (1, 100)])]));
+        assert!(tracker.record(&[table(1, &[(0, 150), (1, 50)])]));
+        assert!(!tracker.record(&[table(1, &[(0, 150), (1, 50)])]));
+
+        let rounds = &tracker.state().rounds;
+        assert_eq!(rounds.len(), 1);
+        assert_eq!(rounds[0].round, 1);
+        assert_eq!(rounds[0].tables[0].players[1].stack, 50);
+    }
+
+    #[test]
+    fn busted_players_stay_in_their_round() {
+        let mut tracker = TournamentTracker::default();
+        tracker.record(&[table(1, &[(0, 100), (1, 100), (2, 100)])]);
+        tracker.record(&[table(1, &[(0, 200), (1, 100), (2, 0)])]);
+
+        let state = tracker.state();
+        assert_eq!(state.rounds.len(), 1);
+        assert_eq!(state.rounds[0].tables[0].players[2].eliminated_in_round, Some(1));
+        assert_eq!(state.remaining_players(), 2);
+    }
+
+    #[test]
+    fn moving_players_starts_a_new_round() {
+        let mut tracker = TournamentTracker::default();
+        tracker.record(&[
+            table(1, &[(0, 100), (1, 0)]),
+            table(2, &[(2, 100), (3, 100)]),
+        ]);
+        // Table 1 is broken up and its last player moves to table 2
+        tracker.record(&[table(2, &[(2, 100), (3, 100), (0, 100)])]);
+
+        let rounds = &tracker.state().rounds;
+        assert_eq!(rounds.len(), 2);
+        assert_eq!(rounds[0].tables.len(), 2);
+        assert_eq!(rounds[0].tables[0].players[1].eliminated_in_round, Some(1));
+        assert_eq!(rounds[1].round, 2);
+        assert_eq!(rounds[1].tables.len(), 1);
+        assert_eq!(tracker.state().remaining_players(), 3);
+    }
+
+    #[test]
+    fn eliminations_keep_their_round() {
+        let mut tracker = TournamentTracker::default();
+        tracker.record(&[table(1, &[(0, 100), (1, 0)]), table(2, &[(2, 100)])]);
+        tracker.record(&[table(1, &[(0, 100), (1, 0), (2, 100)])]);
+
+        let rounds = &tracker.state().rounds;
+        assert_eq!(rounds.len(), 2);
+        assert_eq!(rounds[1].tables[0].players[1].eliminated_in_round, Some(1));
+    }
+}
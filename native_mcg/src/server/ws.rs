@@ -1,27 +1,90 @@
 // WebSocket handlers and websocket-specific helpers.
 
+use std::net::SocketAddr;
+use std::time::Duration;
+
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
-        State,
+        ConnectInfo, State,
     },
     response::IntoResponse,
 };
 use futures::StreamExt;
+use mcg_shared::{TableId, DEFAULT_TABLE_ID};
 use tokio::sync::broadcast;
+use tokio::time::{interval_at, Instant, MissedTickBehavior};
+use tracing::Instrument;
 
-use crate::server::state::{subscribe_connection, AppState};
+use crate::server::game_log::now_ms;
+use crate::server::session::ClientSession;
+use crate::server::state::{
+    current_state_public, subscribe_connection, welcome_msg, AppState, TableBroadcast,
+};
+use crate::transport::StateDiffer;
 use owo_colors::OwoColorize;
 
-pub async fn ws_handler(ws: WebSocketUpgrade, State(state): State<AppState>) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| manage_websocket(socket, state))
+/// How often each websocket client is sent a `Heartbeat`. Proxies tend to
+/// drop connections that are idle for a minute.
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+/// A client's websocket, numbering the messages sent on it from 0 (see
+/// `mcg_shared::Sequenced`).
+struct ClientSocket {
+    socket: WebSocket,
+    next_seq: u64,
+}
+
+impl ClientSocket {
+    const fn new(socket: WebSocket) -> Self {
+        Self {
+            socket,
+            next_seq: 0,
+        }
+    }
+
+    async fn recv(&mut self) -> Option<Result<Message, axum::Error>> {
+        self.socket.next().await
+    }
+
+    async fn close(&mut self) {
+        let _ = self.socket.send(Message::Close(None)).await;
+    }
+}
+
+/// Upgrade to a websocket.
+///
+/// Everything logged during the connection's lifetime
+/// is tagged with the client's address, when the server was started with
+/// connect info (routers served directly, as in tests, have none).
+pub async fn ws_handler(
+    ws: WebSocketUpgrade,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    let name = connect_info.map_or_else(
+        || "unknown".to_string(),
+        |ConnectInfo(addr)| addr.to_string(),
+    );
+    let span = tracing::info_span!("ws_client", name = %name);
+    ws.on_upgrade(move |socket| manage_websocket(socket, state).instrument(span))
 }
 
-async fn manage_websocket(mut socket: WebSocket, state: AppState) {
+async fn manage_websocket(socket: WebSocket, state: AppState) {
+    let mut socket = ClientSocket::new(socket);
     let hello = format!("{} {}", "[CONNECT]".bold().green(), "Client".bold());
     tracing::info!("{}", hello);
+    let _connection = state.metrics.ws_connected();
 
-    let mut subscription: Option<broadcast::Receiver<mcg_shared::Backend2FrontendMsg>> = None;
+    let mut subscription: Option<broadcast::Receiver<TableBroadcast>> = None;
+    let mut table_id = DEFAULT_TABLE_ID;
+    let mut differ = StateDiffer::default();
+    // Seat this connection plays; it is closed if that player gets kicked
+    let mut session = ClientSession::default();
+    // The first heartbeat is due one interval after connecting
+    let mut heartbeat = interval_at(Instant::now() + HEARTBEAT_INTERVAL, HEARTBEAT_INTERVAL);
+    heartbeat.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    send_ws(&mut socket, &welcome_msg()).await;
 
     loop {
         if let Some(rx) = subscription.as_mut() {
@@ -29,37 +92,93 @@ async fn manage_websocket(mut socket: WebSocket, state: AppState) {
                 biased;
                 recv = rx.recv() => {
                     match recv {
-                        Ok(sm) => {
-                            send_ws(&mut socket, &sm).await;
-                        }
-                        Err(broadcast::error::RecvError::Lagged(_)) => {
-                            continue;
+                        Ok((tid, sm)) => {
+                            if tid != table_id {
+                                continue;
+                            }
+                            if let mcg_shared::Backend2FrontendMsg::PlayerKicked(kicked) = &sm {
+                                if session.you_id() == Some(*kicked) {
+                                    tracing::info!(player_id = %kicked, "closing websocket of kicked player");
+                                    send_ws(
+                                        &mut socket,
+                                        &mcg_shared::Backend2FrontendMsg::Error(
+                                            "You were removed from the table by an admin".into(),
+                                        ),
+                                    )
+                                    .await;
+                                    socket.close().await;
+                                    break;
+                                }
+                            }
+                            send_encoded_ws(&mut socket, &mut differ, &session, sm).await;
                         }
+                        Err(broadcast::error::RecvError::Lagged(_)) => {}
                         Err(broadcast::error::RecvError::Closed) => {
                             break;
                         }
                     }
                 }
-                msg = socket.next() => {
-                    if !process_websocket_frame(&state, &mut socket, &mut subscription, msg).await {
+                msg = socket.recv() => {
+                    if !process_websocket_frame(&state, &mut socket, &mut differ, &mut subscription, &mut table_id, &mut session, msg).await {
                         break;
                     }
                 }
+                _ = heartbeat.tick() => send_heartbeat(&mut socket).await,
             }
         } else {
-            let msg = socket.next().await;
-            if !process_websocket_frame(&state, &mut socket, &mut subscription, msg).await {
-                break;
+            tokio::select! {
+                msg = socket.recv() => {
+                    if !process_websocket_frame(&state, &mut socket, &mut differ, &mut subscription, &mut table_id, &mut session, msg).await {
+                        break;
+                    }
+                }
+                _ = heartbeat.tick() => send_heartbeat(&mut socket).await,
             }
         }
     }
     tracing::info!("client disconnecting: websocket client");
 }
 
-async fn send_ws(socket: &mut WebSocket, msg: &mcg_shared::Backend2FrontendMsg) {
-    match serde_json::to_string(msg) {
+/// Upgrade to a websocket that streams the result of every arena hand.
+/// Messages from the watcher are ignored.
+pub async fn arena_ws_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| stream_arena_hands(socket, state))
+}
+
+async fn stream_arena_hands(socket: WebSocket, state: AppState) {
+    let mut socket = ClientSocket::new(socket);
+    let _connection = state.metrics.ws_connected();
+    let mut rx = state.broadcaster.subscribe();
+    loop {
+        tokio::select! {
+            recv = rx.recv() => match recv {
+                Ok((_, msg @ mcg_shared::Backend2FrontendMsg::ArenaHandComplete { .. })) => {
+                    send_ws(&mut socket, &msg).await;
+                }
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            msg = socket.recv() => match msg {
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+    tracing::info!("arena watcher disconnected");
+}
+
+async fn send_ws(socket: &mut ClientSocket, msg: &mcg_shared::Backend2FrontendMsg) {
+    let numbered = mcg_shared::Sequenced {
+        seq: Some(socket.next_seq),
+        msg,
+    };
+    match serde_json::to_string(&numbered) {
         Ok(txt) => {
-            let _ = socket.send(Message::Text(txt)).await;
+            socket.next_seq += 1;
+            let _ = socket.socket.send(Message::Text(txt)).await;
         }
         Err(e) => {
             tracing::error!(error = %e, "failed to serialize ServerMsg for websocket send");
@@ -67,30 +186,85 @@ async fn send_ws(socket: &mut WebSocket, msg: &mcg_shared::Backend2FrontendMsg)
     }
 }
 
+async fn send_heartbeat(socket: &mut ClientSocket) {
+    let heartbeat = mcg_shared::Backend2FrontendMsg::Heartbeat {
+        server_time_ms: now_ms(),
+    };
+    send_ws(socket, &heartbeat).await;
+}
+
+/// Send a message that may carry a game state as `session` may see it,
+/// diffing it against the last one sent.
+async fn send_encoded_ws(
+    socket: &mut ClientSocket,
+    differ: &mut StateDiffer,
+    session: &ClientSession,
+    msg: mcg_shared::Backend2FrontendMsg,
+) {
+    send_ws(socket, &differ.encode(msg, session.you_id())).await;
+}
+
 async fn process_websocket_frame(
     state: &AppState,
-    socket: &mut WebSocket,
-    subscription: &mut Option<broadcast::Receiver<mcg_shared::Backend2FrontendMsg>>,
+    socket: &mut ClientSocket,
+    differ: &mut StateDiffer,
+    subscription: &mut Option<broadcast::Receiver<TableBroadcast>>,
+    table_id: &mut TableId,
+    session: &mut ClientSession,
     msg: Option<Result<Message, axum::Error>>,
 ) -> bool {
     match msg {
         Some(Ok(Message::Text(txt))) => {
-            process_websocket_text(state, socket, subscription, txt).await;
+            process_websocket_text(state, socket, differ, subscription, table_id, session, txt)
+                .await;
             true
         }
-        Some(Ok(Message::Binary(_))) => true,
-        Some(Ok(Message::Close(_))) | Some(Err(_)) | None => false,
+        Some(Ok(Message::Close(_)) | Err(_)) | None => false,
         _ => true,
     }
 }
 
+/// Handle a client message, then acknowledge it if the client numbered it.
+/// A numbered message that was handled before is only acknowledged again.
 async fn process_websocket_text(
     state: &AppState,
-    socket: &mut WebSocket,
-    subscription: &mut Option<broadcast::Receiver<mcg_shared::Backend2FrontendMsg>>,
+    socket: &mut ClientSocket,
+    differ: &mut StateDiffer,
+    subscription: &mut Option<broadcast::Receiver<TableBroadcast>>,
+    table_id: &mut TableId,
+    session: &mut ClientSession,
     txt: String,
 ) {
-    match serde_json::from_str::<mcg_shared::Frontend2BackendMsg>(&txt) {
+    let (seq, parsed) = parse_sequenced_client_msg(&txt);
+    if seq.is_some_and(|seq| !session.first_delivery(seq)) {
+        tracing::debug!(?seq, "ignoring a re-sent websocket message");
+    } else {
+        handle_client_msg(
+            state,
+            socket,
+            differ,
+            subscription,
+            table_id,
+            session,
+            parsed,
+        )
+        .await;
+    }
+    if let Some(seq) = seq {
+        send_ws(socket, &mcg_shared::Backend2FrontendMsg::Ack { seq }).await;
+    }
+}
+
+async fn handle_client_msg(
+    state: &AppState,
+    socket: &mut ClientSocket,
+    differ: &mut StateDiffer,
+    subscription: &mut Option<broadcast::Receiver<TableBroadcast>>,
+    table_id: &mut TableId,
+    session: &mut ClientSession,
+    parsed: Result<mcg_shared::Frontend2BackendMsg, String>,
+) {
+    match parsed {
         Ok(mcg_shared::Frontend2BackendMsg::Subscribe) => {
             if subscription.is_some() {
                 send_ws(
@@ -100,23 +274,108 @@ async fn process_websocket_text(
                 .await;
                 return;
             }
-            let sub = subscribe_connection(state).await;
+            let sub = subscribe_connection(state, *table_id).await;
             if let Some(gs) = sub.initial_state {
-                send_ws(socket, &mcg_shared::Backend2FrontendMsg::State(gs)).await;
+                send_encoded_ws(
+                    socket,
+                    differ,
+                    session,
+                    mcg_shared::Backend2FrontendMsg::State(gs),
+                )
+                .await;
             }
             *subscription = Some(sub.receiver);
         }
-        Ok(other) => {
-            let resp = crate::server::dispatch_client_message(state, other).await;
-            send_ws(socket, &resp).await;
-        }
-        Err(err) => {
-            tracing::warn!(error = %err, "failed to parse incoming ClientMsg JSON");
+        Ok(mcg_shared::Frontend2BackendMsg::Action {
+            table_id: action_table,
+            ..
+        }) if action_table != *table_id => {
             send_ws(
                 socket,
-                &mcg_shared::Backend2FrontendMsg::Error("Malformed ClientMsg JSON".into()),
+                &mcg_shared::Backend2FrontendMsg::Error(format!(
+                    "Join table {action_table} before acting at it"
+                )),
             )
             .await;
         }
+        Ok(other) => {
+            if let Err(unauthorized) = session.authorize(&other) {
+                send_ws(socket, &unauthorized).await;
+                return;
+            }
+            let resp = crate::server::dispatch_client_message(state, *table_id, other).await;
+            let joined = match resp {
+                mcg_shared::Backend2FrontendMsg::TableJoined(joined) => Some(joined),
+                _ => None,
+            };
+            send_encoded_ws(socket, differ, session, resp).await;
+            if let Some(joined) = joined {
+                *table_id = joined;
+                session.leave_seat();
+                if let Some(gs) = current_state_public(state, joined).await {
+                    send_encoded_ws(
+                        socket,
+                        differ,
+                        session,
+                        mcg_shared::Backend2FrontendMsg::State(gs),
+                    )
+                    .await;
+                }
+            }
+        }
+        Err(err) => {
+            tracing::warn!(error = %err, "malformed websocket message");
+            send_ws(socket, &mcg_shared::Backend2FrontendMsg::Error(err)).await;
+        }
     }
 }
+
+/// Decode a client message, checking its envelope first.
+///
+/// Misbehaving clients get a precise error: the frame has to be a JSON object with a
+/// string `"type"`. Only variants with a payload need `"data"`; unit variants
+/// like `Ping` are serialized without it.
+///
+/// # Errors
+///
+/// With a description of what is wrong with the frame.
+pub fn parse_client_msg(txt: &str) -> Result<mcg_shared::Frontend2BackendMsg, String> {
+    parse_sequenced_client_msg(txt).1
+}
+
+/// Decode a client message like `parse_client_msg`, together with its `seq`.
+///
+/// The number (see `mcg_shared::Sequenced`) is returned even if the message
+/// itself is malformed, so that it is acknowledged all the same.
+pub fn parse_sequenced_client_msg(
+    txt: &str,
+) -> (Option<u64>, Result<mcg_shared::Frontend2BackendMsg, String>) {
+    match serde_json::from_str::<serde_json::Value>(txt) {
+        Ok(value) => (
+            value.get("seq").and_then(serde_json::Value::as_u64),
+            decode_client_value(value),
+        ),
+        Err(e) => (None, Err(format!("Malformed message: invalid JSON ({e})"))),
+    }
+}
+
+fn decode_client_value(
+    value: serde_json::Value,
+) -> Result<mcg_shared::Frontend2BackendMsg, String> {
+    let obj = value
+        .as_object()
+        .ok_or_else(|| "Malformed message: expected a JSON object".to_string())?;
+    let kind = obj
+        .get("type")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| "Malformed message: missing \"type\" field".to_string())?
+        .to_string();
+    let has_data = obj.contains_key("data");
+    serde_json::from_value(value).map_err(|e| {
+        if !has_data && e.to_string().contains("missing field `data`") {
+            format!("Malformed message: missing \"data\" field for {kind}")
+        } else {
+            format!("Malformed message: invalid {kind} ({e})")
+        }
+    })
+}
@@ -1,16 +1,38 @@
-//! Shared transport helpers for sending ServerMsg over different transports.
+//! Shared transport helpers for sending `ServerMsg` over different transports.
 //!
-//! Provides small, focused helpers so websocket and iroh handlers can reuse
-//! the same serialization logic and error handling.
+//! Provides small, focused helpers so websocket, iroh and QUIC handlers can
+//! reuse the same serialization logic and error handling, plus the websocket
+//! and QUIC address resolution shared by the command line clients. Clients
+//! reach a server through the [`Transport`] trait, which a
+//! [`NetworkSimulator`] can wrap to test them on a bad network.
 
-use anyhow::Result;
-use mcg_shared::Backend2FrontendMsg;
+mod client;
+mod iroh;
+mod simulator;
+
+pub use client::{LinesTransport, Transport, WebSocketTransport};
+pub use iroh::IrohTransport;
+pub use simulator::{NetworkConditions, NetworkSimulator};
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use mcg_shared::{Backend2FrontendMsg, GameStatePublic, PlayerId};
+use quinn::rustls::pki_types::CertificateDer;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use tokio::io::AsyncWrite;
 use tokio::io::AsyncWriteExt;
+use url::Url;
 
-/// Send a ServerMsg to an AsyncWrite sink as a newline-delimited JSON line.
+/// Send a `ServerMsg` to an `AsyncWrite` sink as a newline-delimited JSON line.
 ///
 /// Used by the iroh transport which exposes an AsyncWrite-like send handle.
+///
+/// # Errors
+///
+/// If the message cannot be serialized or written.
 pub async fn send_server_msg_to_writer<W>(writer: &mut W, msg: &Backend2FrontendMsg) -> Result<()>
 where
     W: AsyncWrite + Unpin + Send,
@@ -21,3 +43,161 @@ where
     writer.flush().await?;
     Ok(())
 }
+
+/// Largest message accepted on a single QUIC stream
+pub const MAX_QUIC_MESSAGE_BYTES: usize = 1 << 20;
+
+/// Send `msg` as JSON on a new unidirectional QUIC stream. Every message
+/// gets its own stream, so a lost packet only delays that message.
+///
+/// # Errors
+///
+/// If the message cannot be serialized or sent.
+pub async fn send_msg_on_uni<T: Serialize + Sync>(conn: &quinn::Connection, msg: &T) -> Result<()> {
+    let txt = serde_json::to_string(msg)?;
+    let mut send = conn.open_uni().await?;
+    send.write_all(txt.as_bytes()).await?;
+    send.finish()?;
+    Ok(())
+}
+
+/// Receive the next message sent with [`send_msg_on_uni`]. Streams are
+/// accepted in the order the peer opened them.
+///
+/// # Errors
+///
+/// If no stream can be accepted, or its message is too large or cannot be
+/// parsed.
+pub async fn recv_msg_on_uni<T: DeserializeOwned>(conn: &quinn::Connection) -> Result<T> {
+    let mut recv = conn.accept_uni().await?;
+    let bytes = recv.read_to_end(MAX_QUIC_MESSAGE_BYTES).await?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+/// Client config that only trusts the server certificate `cert`, as
+/// published by a server with a self-signed certificate.
+///
+/// # Errors
+///
+/// If `cert` is not a valid certificate.
+pub fn quic_client_config(cert: CertificateDer<'static>) -> Result<quinn::ClientConfig> {
+    let mut roots = quinn::rustls::RootCertStore::empty();
+    roots.add(cert).context("adding QUIC server certificate")?;
+    quinn::ClientConfig::with_root_certificates(Arc::new(roots))
+        .context("building QUIC client config")
+}
+
+/// Open a QUIC connection to `addr`, verifying the server with `cert`.
+///
+/// # Errors
+///
+/// If no endpoint can be bound or the connection fails.
+pub async fn connect_quic(
+    addr: SocketAddr,
+    server_name: &str,
+    cert: CertificateDer<'static>,
+) -> Result<quinn::Connection> {
+    let local: SocketAddr = if addr.is_ipv6() {
+        "[::]:0".parse()?
+    } else {
+        "0.0.0.0:0".parse()?
+    };
+    let mut endpoint = quinn::Endpoint::client(local).context("binding QUIC client endpoint")?;
+    endpoint.set_default_client_config(quic_client_config(cert)?);
+    let conn = endpoint
+        .connect(addr, server_name)?
+        .await
+        .with_context(|| format!("connecting to QUIC server {addr}"))?;
+    Ok(conn)
+}
+
+/// Resolve a `quic://host:port` address (the scheme is optional).
+///
+/// # Errors
+///
+/// If the address cannot be resolved.
+pub async fn resolve_quic_addr(base: &str) -> Result<SocketAddr> {
+    let host_port = base.strip_prefix("quic://").unwrap_or(base);
+    let host_port = host_port.trim_end_matches('/');
+    let mut addrs: Vec<SocketAddr> = tokio::net::lookup_host(host_port)
+        .await
+        .with_context(|| format!("resolving QUIC address '{base}'"))?
+        .collect();
+    // The listener binds IPv4, so prefer an IPv4 address for e.g. localhost
+    addrs.sort_by_key(std::net::SocketAddr::is_ipv6);
+    addrs
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("no address found for '{base}'"))
+}
+
+/// Every this many states a connection is sent the full state again so a
+/// client that missed or misapplied a diff cannot drift for long.
+pub const FULL_STATE_INTERVAL: u32 = 10;
+
+/// Per-connection encoder that turns outgoing `State` messages into
+/// `StateDiff`s against the last state sent on the same connection.
+#[derive(Default)]
+pub struct StateDiffer {
+    last_sent: Option<GameStatePublic>,
+    diffs_since_full: u32,
+}
+
+impl StateDiffer {
+    /// Rewrite `msg` for sending to `viewer`, the connection's player or
+    /// `None` for a spectator, hiding the hole cards it may not see. The first
+    /// state, and every [`FULL_STATE_INTERVAL`]th after it, goes out in full.
+    /// Joining a table clears the client's state, so the next state after it
+    /// is full as well.
+    pub fn encode(
+        &mut self,
+        msg: Backend2FrontendMsg,
+        viewer: Option<PlayerId>,
+    ) -> Backend2FrontendMsg {
+        match msg {
+            Backend2FrontendMsg::State(gs) => {
+                let gs = gs.seen_by(viewer);
+                let msg = match &self.last_sent {
+                    Some(last) if self.diffs_since_full + 1 < FULL_STATE_INTERVAL => {
+                        self.diffs_since_full += 1;
+                        Backend2FrontendMsg::StateDiff(gs.diff_from(last))
+                    }
+                    _ => {
+                        self.diffs_since_full = 0;
+                        Backend2FrontendMsg::State(gs.clone())
+                    }
+                };
+                self.last_sent = Some(gs);
+                msg
+            }
+            Backend2FrontendMsg::TableJoined(_) => {
+                self.last_sent = None;
+                msg
+            }
+            other => other,
+        }
+    }
+}
+
+/// Try to build a websocket URL from a base string (like "localhost:3000" or "<http://host:3000>")
+///
+/// # Errors
+///
+/// If `base` is not a URL or has a scheme other than HTTP(S) or WS(S).
+pub fn build_ws_url(base: &str) -> anyhow::Result<Url> {
+    let mut url = Url::parse(base).or_else(|_| Url::parse(&format!("http://{base}")))?;
+
+    match url.scheme() {
+        "http" => url.set_scheme("ws").ok(),
+        "https" => url.set_scheme("wss").ok(),
+        "ws" | "wss" => Some(()),
+        _ => None,
+    }
+    .ok_or_else(|| anyhow::anyhow!("Unsupported URL scheme: {}", url.scheme()))?;
+
+    // Force path to /ws
+    if url.path() != "/ws" {
+        url.set_path("/ws");
+    }
+    Ok(url)
+}
@@ -0,0 +1,122 @@
+//! Client ends of the server transports behind one [`Transport`] trait, so
+//! tests and tools can talk to a server without caring how they reach it.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use mcg_shared::{Backend2FrontendMsg, Frontend2BackendMsg};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use super::build_ws_url;
+
+/// A client's connection to the server.
+#[async_trait]
+pub trait Transport: Send {
+    /// Send `msg` to the server.
+    async fn send(&mut self, msg: &Frontend2BackendMsg) -> Result<()>;
+
+    /// The next message from the server, or `None` once it closed the
+    /// connection.
+    ///
+    /// Must be cancel safe: if the future is dropped before it completes, no
+    /// message is lost, so callers may race it against a timeout.
+    async fn recv(&mut self) -> Result<Option<Backend2FrontendMsg>>;
+}
+
+/// Connection to the websocket endpoint of a server.
+pub struct WebSocketTransport {
+    ws: WebSocketStream<MaybeTlsStream<TcpStream>>,
+}
+
+impl WebSocketTransport {
+    /// Connect to the server at `base`, e.g. "localhost:3000".
+    ///
+    /// # Errors
+    ///
+    /// If the URL is not valid or the connection fails.
+    pub async fn connect(base: &str) -> Result<Self> {
+        let url = build_ws_url(base)?;
+        let (ws, _resp) = tokio_tungstenite::connect_async(url.as_str())
+            .await
+            .with_context(|| format!("connecting to websocket {url}"))?;
+        Ok(Self { ws })
+    }
+}
+
+#[async_trait]
+impl Transport for WebSocketTransport {
+    async fn send(&mut self, msg: &Frontend2BackendMsg) -> Result<()> {
+        let txt = serde_json::to_string(msg)?;
+        self.ws.send(Message::Text(txt)).await?;
+        Ok(())
+    }
+
+    async fn recv(&mut self) -> Result<Option<Backend2FrontendMsg>> {
+        while let Some(frame) = self.ws.next().await {
+            // Sequence numbers are left out; the message parses without them
+            if let Message::Text(txt) = frame? {
+                return Ok(Some(serde_json::from_str(&txt)?));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Newline-delimited JSON over a pair of byte streams, the framing of the
+/// iroh transport.
+pub struct LinesTransport<W, R> {
+    writer: W,
+    reader: BufReader<R>,
+    /// Part of the next line read so far
+    line: Vec<u8>,
+}
+
+impl<W, R> LinesTransport<W, R>
+where
+    W: AsyncWrite + Unpin + Send,
+    R: AsyncRead + Unpin + Send,
+{
+    pub fn new(writer: W, reader: R) -> Self {
+        Self {
+            writer,
+            reader: BufReader::new(reader),
+            line: Vec::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl<W, R> Transport for LinesTransport<W, R>
+where
+    W: AsyncWrite + Unpin + Send,
+    R: AsyncRead + Unpin + Send,
+{
+    async fn send(&mut self, msg: &Frontend2BackendMsg) -> Result<()> {
+        let txt = serde_json::to_string(msg)?;
+        self.writer.write_all(txt.as_bytes()).await?;
+        self.writer.write_all(b"\n").await?;
+        self.writer.flush().await?;
+        Ok(())
+    }
+
+    async fn recv(&mut self) -> Result<Option<Backend2FrontendMsg>> {
+        loop {
+            // `read_until` keeps what it read in `self.line` when cancelled
+            if self.reader.read_until(b'\n', &mut self.line).await? == 0 {
+                return Ok(None);
+            }
+            if self.line.last() != Some(&b'\n') {
+                // The stream ended in the middle of a line
+                return Ok(None);
+            }
+            let line = std::mem::take(&mut self.line);
+            let trimmed = line.trim_ascii();
+            if !trimmed.is_empty() {
+                return Ok(Some(serde_json::from_slice(trimmed)?));
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! Client end of the iroh transport.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use iroh::endpoint::{Endpoint, RecvStream, SendStream};
+use iroh::EndpointId;
+use mcg_shared::{Backend2FrontendMsg, Frontend2BackendMsg};
+
+use super::client::{LinesTransport, Transport};
+
+/// Application ALPN identifier, the same as the server's
+const ALPN: &[u8] = b"mcg/iroh/1";
+
+/// Connection to the iroh endpoint of a server.
+pub struct IrohTransport {
+    /// Kept so the connection stays open
+    _endpoint: Endpoint,
+    lines: LinesTransport<SendStream, RecvStream>,
+}
+
+impl IrohTransport {
+    /// Connect to the server with the endpoint id `peer_uri` (z-base-32).
+    ///
+    /// # Errors
+    ///
+    /// If `peer_uri` is not an endpoint id or the connection fails.
+    pub async fn connect(peer_uri: &str) -> Result<Self> {
+        let peer_id: EndpointId = peer_uri
+            .parse()
+            .context("parsing iroh endpoint id (z-base-32)")?;
+        let endpoint = Endpoint::builder()
+            .bind()
+            .await
+            .context("binding iroh endpoint for client")?;
+        let connection = endpoint
+            .connect(peer_id, ALPN)
+            .await
+            .context("connecting to iroh peer (endpoint id)")?;
+        let (send, recv) = connection
+            .open_bi()
+            .await
+            .context("opening bidirectional stream")?;
+        Ok(Self {
+            _endpoint: endpoint,
+            lines: LinesTransport::new(send, recv),
+        })
+    }
+}
+
+#[async_trait]
+impl Transport for IrohTransport {
+    async fn send(&mut self, msg: &Frontend2BackendMsg) -> Result<()> {
+        self.lines.send(msg).await
+    }
+
+    async fn recv(&mut self) -> Result<Option<Backend2FrontendMsg>> {
+        self.lines.recv().await
+    }
+}
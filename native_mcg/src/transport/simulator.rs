@@ -0,0 +1,190 @@
+//! Degraded network conditions, for testing how clients cope with them.
+
+use std::collections::VecDeque;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use mcg_shared::{Backend2FrontendMsg, Frontend2BackendMsg};
+use tokio::time::{Duration, Instant};
+
+use super::client::Transport;
+
+/// What a [`NetworkSimulator`] does to the messages passing through it. The
+/// default is a perfect network.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NetworkConditions {
+    /// Shortest delay of a message in milliseconds, each way
+    pub min_delay_ms: u64,
+    /// Longest delay of a message in milliseconds, each way
+    pub max_delay_ms: u64,
+    /// Chance that a message is lost, `0.0..=1.0`
+    pub drop_probability: f64,
+    /// Chance that a message arrives twice, `0.0..=1.0`
+    pub duplicate_probability: f64,
+}
+
+impl NetworkConditions {
+    fn delay(&self) -> Duration {
+        let max = self.max_delay_ms.max(self.min_delay_ms);
+        Duration::from_millis(rand::random_range(self.min_delay_ms..=max))
+    }
+
+    /// How many times a message is delivered: 0, 1 or 2
+    fn copies(&self) -> usize {
+        if rand::random::<f64>() < self.drop_probability {
+            0
+        } else if rand::random::<f64>() < self.duplicate_probability {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+/// A [`Transport`] that delays, drops and duplicates the messages of the one
+/// it wraps, in both directions.
+///
+/// Messages stay in order, as on the stream transports the server speaks:
+/// one is never delivered before a message received ahead of it. Sending
+/// waits out the delay before returning.
+pub struct NetworkSimulator<T> {
+    inner: T,
+    conditions: NetworkConditions,
+    /// Messages received and when to deliver them
+    incoming: VecDeque<(Instant, Backend2FrontendMsg)>,
+    /// The inner transport was closed; what is left is still delivered
+    closed: bool,
+}
+
+impl<T: Transport> NetworkSimulator<T> {
+    pub const fn new(inner: T, conditions: NetworkConditions) -> Self {
+        Self {
+            inner,
+            conditions,
+            incoming: VecDeque::new(),
+            closed: false,
+        }
+    }
+
+    fn receive(&mut self, msg: Option<Backend2FrontendMsg>) {
+        let Some(msg) = msg else {
+            self.closed = true;
+            return;
+        };
+        let mut due = Instant::now() + self.conditions.delay();
+        if let Some(&(last, _)) = self.incoming.back() {
+            due = due.max(last);
+        }
+        for _ in 0..self.conditions.copies() {
+            self.incoming.push_back((due, msg.clone()));
+        }
+    }
+}
+
+#[async_trait]
+impl<T: Transport> Transport for NetworkSimulator<T> {
+    async fn send(&mut self, msg: &Frontend2BackendMsg) -> Result<()> {
+        let copies = self.conditions.copies();
+        tokio::time::sleep(self.conditions.delay()).await;
+        for _ in 0..copies {
+            self.inner.send(msg).await?;
+        }
+        Ok(())
+    }
+
+    async fn recv(&mut self) -> Result<Option<Backend2FrontendMsg>> {
+        loop {
+            let received = match self.incoming.front() {
+                Some(&(due, _)) if due <= Instant::now() => {
+                    return Ok(self.incoming.pop_front().map(|(_, msg)| msg));
+                }
+                Some(&(due, _)) => tokio::select! {
+                    () = tokio::time::sleep_until(due) => continue,
+                    msg = self.inner.recv(), if !self.closed => msg?,
+                },
+                None if self.closed => return Ok(None),
+                None => self.inner.recv().await?,
+            };
+            self.receive(received);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::client::LinesTransport;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream, ReadHalf, WriteHalf};
+
+    type Pipe = LinesTransport<WriteHalf<DuplexStream>, ReadHalf<DuplexStream>>;
+
+    /// A client transport and the server end of its connection
+    fn connection() -> (Pipe, DuplexStream) {
+        let (client, server) = tokio::io::duplex(1 << 16);
+        let (read, write) = tokio::io::split(client);
+        (LinesTransport::new(write, read), server)
+    }
+
+    /// Send `count` numbered errors from the server end, then hang up.
+    async fn serve_errors(mut server: DuplexStream, count: usize) -> Result<()> {
+        for i in 0..count {
+            let msg = Backend2FrontendMsg::Error(i.to_string());
+            server
+                .write_all(format!("{}\n", serde_json::to_string(&msg)?).as_bytes())
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn recv_all<T: Transport>(transport: &mut T) -> Result<Vec<String>> {
+        let mut texts = Vec::new();
+        while let Some(msg) = transport.recv().await? {
+            match msg {
+                Backend2FrontendMsg::Error(text) => texts.push(text),
+                other => anyhow::bail!("unexpected {other:?}"),
+            }
+        }
+        Ok(texts)
+    }
+
+    #[tokio::test]
+    async fn messages_are_delayed_but_stay_in_order() -> Result<()> {
+        let (client, server) = connection();
+        let conditions = NetworkConditions {
+            min_delay_ms: 20,
+            max_delay_ms: 60,
+            ..NetworkConditions::default()
+        };
+        let mut sim = NetworkSimulator::new(client, conditions);
+        let start = Instant::now();
+        serve_errors(server, 5).await?;
+        assert_eq!(recv_all(&mut sim).await?, ["0", "1", "2", "3", "4"]);
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn messages_are_dropped_or_duplicated_both_ways() -> Result<()> {
+        let (client, mut server) = connection();
+        let lossy = NetworkConditions {
+            drop_probability: 1.0,
+            ..NetworkConditions::default()
+        };
+        let mut sim = NetworkSimulator::new(client, lossy);
+        sim.send(&Frontend2BackendMsg::Ping).await?;
+        drop(sim);
+        let mut sent = String::new();
+        server.read_to_string(&mut sent).await?;
+        assert_eq!(sent, "");
+
+        let (client, server) = connection();
+        let echoing = NetworkConditions {
+            duplicate_probability: 1.0,
+            ..NetworkConditions::default()
+        };
+        let mut sim = NetworkSimulator::new(client, echoing);
+        serve_errors(server, 2).await?;
+        assert_eq!(recv_all(&mut sim).await?, ["0", "0", "1", "1"]);
+        Ok(())
+    }
+}
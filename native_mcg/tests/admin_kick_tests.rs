@@ -0,0 +1,187 @@
+//! Tests for kicking players through the admin command and HTTP endpoint
+
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use mcg_shared::{
+    ActionEvent, Backend2FrontendMsg, BettingLimit, Frontend2BackendMsg, GameAction, PlayerAction,
+    PlayerConfig, PlayerId, Stage, TableConfig, TableId, DEFAULT_TABLE_ID,
+};
+use native_mcg::config::Config;
+use native_mcg::server::{current_state_public, dispatch_client_message, AppState};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+const TOKEN: &str = "secret";
+
+fn admin_state() -> AppState {
+    let config = Config {
+        admin_token: Some(TOKEN.to_string()),
+        bot_delay: 10,
+        ..Config::default()
+    };
+    AppState::new(config, None)
+}
+
+async fn open_table(state: &AppState) -> Result<TableId> {
+    let players = ["Alice", "Bob", "Carol"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| PlayerConfig {
+            id: PlayerId(i),
+            name: name.to_string(),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        })
+        .collect();
+    let config = TableConfig {
+        players,
+        sb: 5,
+        bb: 10,
+        bounty: None,
+        betting_limit: BettingLimit::NoLimit,
+    };
+    let msg = Frontend2BackendMsg::CreateTable { config };
+    match dispatch_client_message(state, DEFAULT_TABLE_ID, msg).await {
+        Backend2FrontendMsg::TableJoined(id) => Ok(id),
+        other => bail!("unexpected response {other:?}"),
+    }
+}
+
+fn kick(player_id: PlayerId, token: &str) -> Frontend2BackendMsg {
+    Frontend2BackendMsg::AdminKick {
+        player_id,
+        token: token.to_string(),
+    }
+}
+
+fn was_kicked(log: &[ActionEvent], player_id: PlayerId) -> bool {
+    log.contains(&ActionEvent::GameAction(GameAction::PlayerKicked {
+        player_id,
+    }))
+}
+
+#[tokio::test]
+async fn admin_kick_requires_the_configured_token() -> Result<()> {
+    // Without a configured token admin commands are disabled
+    let state = AppState::default();
+    let table_id = open_table(&state).await?;
+    let resp = dispatch_client_message(&state, table_id, kick(PlayerId(0), "")).await;
+    assert!(matches!(resp, Backend2FrontendMsg::Error(_)));
+
+    let state = admin_state();
+    let table_id = open_table(&state).await?;
+    let resp = dispatch_client_message(&state, table_id, kick(PlayerId(0), "wrong")).await;
+    assert!(matches!(resp, Backend2FrontendMsg::Error(_)));
+    let Some(gs) = current_state_public(&state, table_id).await else {
+        bail!("table {table_id} has no game");
+    };
+    assert!(!was_kicked(&gs.action_log, PlayerId(0)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn game_continues_after_kick() -> Result<()> {
+    let state = admin_state();
+    let table_id = open_table(&state).await?;
+    let Some(gs) = current_state_public(&state, table_id).await else {
+        bail!("table {table_id} has no game");
+    };
+    let kicked = gs.to_act;
+
+    let mut rx = state.broadcaster.subscribe();
+    let resp = dispatch_client_message(&state, table_id, kick(kicked, TOKEN)).await;
+    let Backend2FrontendMsg::State(gs) = resp else {
+        bail!("expected state after kick, got {resp:?}");
+    };
+    assert!(was_kicked(&gs.action_log, kicked));
+    assert_eq!(gs.players.len(), 3);
+    let mut notified = false;
+    while let Ok((_, msg)) = rx.try_recv() {
+        notified |= matches!(msg, Backend2FrontendMsg::PlayerKicked(id) if id == kicked);
+    }
+    assert!(notified);
+
+    // A bot already holds the seat
+    let resp = dispatch_client_message(&state, table_id, kick(kicked, TOKEN)).await;
+    assert!(matches!(resp, Backend2FrontendMsg::Error(_)));
+
+    // The bot plays the kicked seat while the others check or call down
+    let driver = tokio::spawn(native_mcg::server::bot_driver::run_bot_driver(
+        state.clone(),
+    ));
+    let mut finished = false;
+    for _ in 0..500 {
+        let Some(gs) = current_state_public(&state, table_id).await else {
+            bail!("table {table_id} lost its game");
+        };
+        if gs.stage == Stage::Showdown {
+            let total: u32 = gs.players.iter().map(|p| p.stack).sum::<u32>() + gs.pot;
+            assert_eq!(total, 3000);
+            finished = true;
+            break;
+        }
+        if gs.to_act == kicked {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            continue;
+        }
+        let action = Frontend2BackendMsg::Action {
+            table_id,
+            player_id: gs.to_act,
+            action: PlayerAction::CheckCall,
+        };
+        dispatch_client_message(&state, table_id, action).await;
+    }
+    assert!(finished, "hand did not finish after the kick");
+
+    let next = Frontend2BackendMsg::NextHand {
+        player_id: PlayerId(0),
+    };
+
+    let resp = dispatch_client_message(&state, table_id, next).await;
+    let Backend2FrontendMsg::State(gs) = resp else {
+        bail!("expected state for the next hand, got {resp:?}");
+    };
+    assert_eq!(gs.stage, Stage::Preflop);
+    assert_eq!(gs.players.len(), 3);
+    driver.abort();
+    Ok(())
+}
+
+/// Send a raw HTTP request and return the status line of the response.
+async fn http_status(addr: std::net::SocketAddr, request: &str) -> Result<String> {
+    let mut stream = tokio::net::TcpStream::connect(addr).await?;
+    stream.write_all(request.as_bytes()).await?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await?;
+    Ok(response.lines().next().unwrap_or_default().to_string())
+}
+
+#[tokio::test]
+async fn http_delete_kicks_player() -> Result<()> {
+    let state = admin_state();
+    let table_id = open_table(&state).await?;
+    let app = native_mcg::server::run::build_router(state.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let server = tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+
+    let path = format!("/players/1?table={table_id}");
+    let unauthorized =
+        format!("DELETE {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+    assert!(http_status(addr, &unauthorized).await?.contains("401"));
+
+    let authorized = format!(
+        "DELETE {path} HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer {TOKEN}\r\nConnection: close\r\n\r\n"
+    );
+    assert!(http_status(addr, &authorized).await?.contains("204"));
+    let Some(gs) = current_state_public(&state, table_id).await else {
+        bail!("table {table_id} has no game");
+    };
+    assert!(was_kicked(&gs.action_log, PlayerId(1)));
+
+    server.abort();
+    Ok(())
+}
@@ -0,0 +1,47 @@
+//! Tests for arena mode, where bots play hand after hand on their own
+
+use std::time::Duration;
+
+use anyhow::Result;
+use mcg_shared::Backend2FrontendMsg;
+use native_mcg::config::Config;
+use native_mcg::server::arena::start_arena;
+use native_mcg::server::bot_driver::run_bot_driver;
+use native_mcg::server::AppState;
+
+#[tokio::test]
+async fn arena_deals_hands_and_reports_each_one() -> Result<()> {
+    let config = Config {
+        arena: true,
+        bots: 3,
+        bot_delay: 10,
+        ..Config::default()
+    };
+    let state = AppState::new(config, None);
+    let mut rx = state.broadcaster.subscribe();
+    let table_id = start_arena(state.clone()).await?;
+    tokio::spawn(run_bot_driver(state.clone()));
+
+    let mut summaries = Vec::new();
+    tokio::time::timeout(Duration::from_secs(30), async {
+        while summaries.len() < 3 {
+            if let Ok((_, Backend2FrontendMsg::ArenaHandComplete { hand_summary })) =
+                rx.recv().await
+            {
+                summaries.push(hand_summary);
+            }
+        }
+    })
+    .await?;
+
+    for (i, summary) in summaries.iter().enumerate() {
+        assert_eq!(summary.table_id, table_id);
+        assert_eq!(summary.hand_number as usize, i + 1);
+        assert_eq!(summary.players.len(), 3);
+        assert!(summary.players.iter().any(|p| p.won));
+        // Chips only move between the bots
+        let net: i64 = summary.players.iter().map(|p| p.chips_won).sum();
+        assert_eq!(net, 0);
+    }
+    Ok(())
+}
@@ -1,6 +1,6 @@
 //! Tests for hand evaluation logic, especially tiebreaker scenarios
 
-use mcg_shared::{Card, CardRank, CardSuit, HandRankCategory};
+use mcg_shared::{Card, CardRank, CardSuit, HandRankCategory, LowHandRank};
 use native_mcg::poker::evaluation::*;
 
 /// Test that pair tiebreakers work correctly
@@ -35,9 +35,9 @@ fn test_pair_tiebreakers() {
     let rank2 = evaluate_best_hand(hole2, &community);
     let rank3 = evaluate_best_hand(hole3, &community);
 
-    println!("Rank1: {:?}", rank1);
-    println!("Rank2: {:?}", rank2);
-    println!("Rank3: {:?}", rank3);
+    println!("Rank1: {rank1:?}");
+    println!("Rank2: {rank2:?}");
+    println!("Rank3: {rank3:?}");
 
     // All should be pairs
     assert_eq!(rank1.category, HandRankCategory::Pair);
@@ -120,9 +120,9 @@ fn test_high_card_tiebreakers() {
     let rank2 = evaluate_best_hand(hole2, &community);
     let rank3 = evaluate_best_hand(hole3, &community);
 
-    println!("Rank1 (A-K high): {:?}", rank1);
-    println!("Rank2 (A-Q high): {:?}", rank2);
-    println!("Rank3 (K-Q high): {:?}", rank3);
+    println!("Rank1 (A-K high): {rank1:?}");
+    println!("Rank2 (A-Q high): {rank2:?}");
+    println!("Rank3 (K-Q high): {rank3:?}");
 
     // All should be high card
     assert_eq!(rank1.category, HandRankCategory::HighCard);
@@ -295,9 +295,9 @@ fn test_straight_tiebreakers() {
     let rank2 = evaluate_best_hand(hole2, &community2);
     let rank3 = evaluate_best_hand(hole3, &community3);
 
-    println!("Rank1 (Ace-high straight): {:?}", rank1);
-    println!("Rank2 (King-high straight): {:?}", rank2);
-    println!("Rank3 (Ace-low straight): {:?}", rank3);
+    println!("Rank1 (Ace-high straight): {rank1:?}");
+    println!("Rank2 (King-high straight): {rank2:?}");
+    println!("Rank3 (Ace-low straight): {rank3:?}");
 
     // All should be straights
     assert_eq!(rank1.category, HandRankCategory::Straight);
@@ -355,10 +355,10 @@ fn test_bug_report_scenario() {
     let rank_bot2 = evaluate_best_hand(hole_bot2, &community);
     let rank_bot3 = evaluate_best_hand(hole_bot3, &community);
 
-    println!("You: {:?}", rank_you);
-    println!("Bot 1: {:?}", rank_bot1);
-    println!("Bot 2: {:?}", rank_bot2);
-    println!("Bot 3: {:?}", rank_bot3);
+    println!("You: {rank_you:?}");
+    println!("Bot 1: {rank_bot1:?}");
+    println!("Bot 2: {rank_bot2:?}");
+    println!("Bot 3: {rank_bot3:?}");
 
     // Verify all are pairs
     assert_eq!(rank_you.category, HandRankCategory::Pair);
@@ -385,3 +385,70 @@ fn test_bug_report_scenario() {
     let count_highest = ranks.iter().filter(|r| **r == *highest).count();
     assert_eq!(count_highest, 2); // Should be 2 winners with aces
 }
+
+/// Test that Omaha low uses exactly two hole cards and three board cards
+#[test]
+fn test_omaha_low_uses_two_hole_cards() {
+    // Board: 2♣ 3♦ 4♥ K♠ Q♣ only offers three low cards
+    let community = [
+        Card::new(CardRank::Two, CardSuit::Clubs),
+        Card::new(CardRank::Three, CardSuit::Diamonds),
+        Card::new(CardRank::Four, CardSuit::Hearts),
+        Card::new(CardRank::King, CardSuit::Spades),
+        Card::new(CardRank::Queen, CardSuit::Clubs),
+    ];
+
+    // A-5 with two high cards: best low is 5-4-3-2-A (the wheel)
+    let hole = [
+        Card::new(CardRank::Ace, CardSuit::Spades),
+        Card::new(CardRank::Five, CardSuit::Spades),
+        Card::new(CardRank::King, CardSuit::Hearts),
+        Card::new(CardRank::Jack, CardSuit::Hearts),
+    ];
+    let low = evaluate_omaha_low(hole, &community);
+    assert_eq!(low, Some(LowHandRank([5, 4, 3, 2, 1])));
+
+    // Only one low hole card: no qualifying low even though 4 low cards are visible
+    let hole_one_low = [
+        Card::new(CardRank::Ace, CardSuit::Spades),
+        Card::new(CardRank::King, CardSuit::Hearts),
+        Card::new(CardRank::Queen, CardSuit::Hearts),
+        Card::new(CardRank::Jack, CardSuit::Hearts),
+    ];
+    assert_eq!(evaluate_omaha_low(hole_one_low, &community), None);
+}
+
+/// Test that paired or high cards do not qualify for low and that lower lows win
+#[test]
+fn test_low_hand_qualification_and_ordering() {
+    let community = [
+        Card::new(CardRank::Two, CardSuit::Clubs),
+        Card::new(CardRank::Three, CardSuit::Diamonds),
+        Card::new(CardRank::Seven, CardSuit::Hearts),
+        Card::new(CardRank::Nine, CardSuit::Spades),
+        Card::new(CardRank::Nine, CardSuit::Clubs),
+    ];
+
+    let hole_ace_four = [
+        Card::new(CardRank::Ace, CardSuit::Hearts),
+        Card::new(CardRank::Four, CardSuit::Hearts),
+    ];
+    let hole_eight_six = [
+        Card::new(CardRank::Eight, CardSuit::Hearts),
+        Card::new(CardRank::Six, CardSuit::Hearts),
+    ];
+    let hole_paired = [
+        Card::new(CardRank::Two, CardSuit::Hearts),
+        Card::new(CardRank::Three, CardSuit::Hearts),
+    ];
+
+    let low_ace_four = evaluate_best_low(hole_ace_four, &community).expect("A-4 should make a low");
+    let low_eight_six =
+        evaluate_best_low(hole_eight_six, &community).expect("8-6 should make a low");
+    assert_eq!(low_ace_four, LowHandRank([7, 4, 3, 2, 1]));
+    assert_eq!(low_eight_six, LowHandRank([8, 7, 6, 3, 2]));
+    assert!(low_ace_four < low_eight_six); // lower is better
+
+    // Pairing the board leaves only three distinct low ranks
+    assert_eq!(evaluate_best_low(hole_paired, &community), None);
+}
@@ -0,0 +1,261 @@
+//! Tests for operator-defined game configs
+
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use mcg_shared::{
+    Backend2FrontendMsg, BettingLimit, BlindLevelInfo, Frontend2BackendMsg, PlayerConfig, PlayerId,
+    TableConfig, TableId, DEFAULT_TABLE_ID,
+};
+use native_mcg::config::{BlindLevel, GameConfig, GameConfigBuilder, GameVariant, RebuyPolicy};
+use native_mcg::server::{current_state_public, dispatch_client_message, AppState};
+
+const fn level(sb: u32, bb: u32, hands: Option<u32>) -> BlindLevel {
+    BlindLevel { sb, bb, hands }
+}
+
+async fn open_table(state: &AppState, players: usize) -> Result<TableId> {
+    let players = (0..players)
+        .map(|i| PlayerConfig {
+            id: PlayerId(i),
+            name: format!("Player {}", i + 1),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        })
+        .collect();
+    let msg = Frontend2BackendMsg::CreateTable {
+        config: TableConfig::new(players),
+    };
+    match dispatch_client_message(state, DEFAULT_TABLE_ID, msg).await {
+        Backend2FrontendMsg::TableJoined(id) => Ok(id),
+        other => bail!("unexpected response {other:?}"),
+    }
+}
+
+#[test]
+fn shipped_templates_are_valid() -> Result<()> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("game-configs");
+    let mut templates = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|e| e == "toml") {
+            GameConfig::from_toml(&path)?;
+            templates += 1;
+        }
+    }
+    assert!(templates > 0);
+    Ok(())
+}
+
+#[test]
+fn example_template_matches_the_defaults() -> Result<()> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("game-configs/example.toml");
+    assert_eq!(GameConfig::from_toml(&path)?, GameConfig::default());
+    Ok(())
+}
+
+#[test]
+fn fields_may_be_left_out_but_not_misspelled() {
+    let cfg: GameConfig = toml::from_str("variant = \"hi_lo\"\nstarting_stack = 500").unwrap();
+    assert_eq!(cfg.variant, GameVariant::HiLo);
+    assert_eq!(cfg.starting_stack, 500);
+    assert_eq!(cfg.blind_schedule, GameConfig::default().blind_schedule);
+
+    assert!(toml::from_str::<GameConfig>("starting_stak = 500").is_err());
+}
+
+#[test]
+fn invalid_rules_are_rejected() {
+    let rules = GameConfigBuilder::default;
+    let invalid = [
+        rules().starting_stack(0),
+        rules().player_count(1),
+        rules().blind_schedule(Vec::new()),
+        rules().blinds(20, 10),
+        rules().blind_schedule(vec![level(5, 10, None), level(10, 20, None)]),
+        rules().blind_schedule(vec![level(5, 10, Some(0))]),
+        rules().betting_limit(BettingLimit::FixedLimit {
+            small_bet: 40,
+            big_bet: 20,
+        }),
+        rules().bounty(0),
+        rules().hand_limit(0),
+    ];
+    for builder in invalid {
+        assert!(builder.clone().build().is_err(), "{builder:?}");
+    }
+    assert_eq!(rules().build().ok(), Some(GameConfig::default()));
+    assert!(GameConfig::default().validate().is_ok());
+}
+
+#[test]
+fn blinds_follow_the_schedule() {
+    let cfg = GameConfigBuilder::default()
+        .blind_schedule(vec![
+            level(5, 10, Some(2)),
+            level(10, 20, Some(3)),
+            level(25, 50, None),
+        ])
+        .build()
+        .unwrap();
+    let blinds: Vec<_> = (1..=7).map(|n| cfg.blinds_for_hand(n)).collect();
+    assert_eq!(
+        blinds,
+        [
+            (5, 10),
+            (5, 10),
+            (10, 20),
+            (10, 20),
+            (10, 20),
+            (25, 50),
+            (25, 50)
+        ]
+    );
+
+    let capped = GameConfigBuilder::default()
+        .blind_schedule(vec![level(5, 10, Some(1)), level(10, 20, Some(1))])
+        .build()
+        .unwrap();
+    assert_eq!(capped.blinds_for_hand(10), (10, 20));
+}
+
+#[test]
+fn blind_levels_count_down_the_hands() {
+    let cfg = GameConfigBuilder::default()
+        .blind_schedule(vec![
+            level(5, 10, Some(2)),
+            level(10, 20, Some(3)),
+            level(25, 50, None),
+        ])
+        .build()
+        .unwrap();
+    let info = |level, hands_left| BlindLevelInfo { level, hands_left };
+    assert_eq!(cfg.blind_level_for_hand(1), info(1, Some(2)));
+    assert_eq!(cfg.blind_level_for_hand(2), info(1, Some(1)));
+    assert_eq!(cfg.blind_level_for_hand(3), info(2, Some(3)));
+    assert_eq!(cfg.blind_level_for_hand(6), info(3, None));
+
+    // The last level never counts down, even with a number of hands
+    let capped = GameConfigBuilder::default()
+        .blind_schedule(vec![level(5, 10, Some(1)), level(10, 20, Some(1))])
+        .build()
+        .unwrap();
+    assert_eq!(capped.blind_level_for_hand(2), info(2, None));
+    assert_eq!(capped.blind_level_for_hand(10), info(2, None));
+}
+
+#[tokio::test]
+async fn new_tables_use_the_game_config() -> Result<()> {
+    let rules = GameConfigBuilder::default()
+        .player_count(3)
+        .starting_stack(500)
+        .blind_schedule(vec![level(25, 50, Some(1)), level(50, 100, None)])
+        .bounty(20)
+        .build()?;
+    let state = AppState::default().with_game_config(Some(rules));
+
+    assert!(open_table(&state, 4).await.is_err());
+
+    let table_id = open_table(&state, 2).await?;
+    let Some(gs) = current_state_public(&state, table_id).await else {
+        bail!("table {table_id} has no game");
+    };
+    assert_eq!((gs.sb, gs.bb), (25, 50));
+    assert_eq!(
+        gs.blind_level,
+        Some(BlindLevelInfo {
+            level: 1,
+            hands_left: Some(1)
+        })
+    );
+    let chips: u32 = gs.players.iter().map(|p| p.stack).sum::<u32>() + gs.pot;
+    assert_eq!(chips, 1000);
+    assert!(gs.players.iter().all(|p| p.bounty == Some(20)));
+
+    let next = Frontend2BackendMsg::NextHand {
+        player_id: PlayerId(0),
+    };
+
+    let resp = dispatch_client_message(&state, table_id, next).await;
+    let Backend2FrontendMsg::State(gs) = resp else {
+        bail!("expected the next hand, got {resp:?}");
+    };
+    assert_eq!((gs.sb, gs.bb), (50, 100));
+    Ok(())
+}
+
+#[tokio::test]
+async fn games_end_after_the_hand_limit() -> Result<()> {
+    let rules = GameConfigBuilder::default().hand_limit(2).build()?;
+    let state = AppState::default().with_game_config(Some(rules));
+    let table_id = open_table(&state, 2).await?;
+    let Some(gs) = current_state_public(&state, table_id).await else {
+        bail!("table {table_id} has no game");
+    };
+    assert_eq!((gs.hand_number, gs.hands_remaining), (1, Some(2)));
+
+    let next = || Frontend2BackendMsg::NextHand {
+        player_id: PlayerId(0),
+    };
+    let resp = dispatch_client_message(&state, table_id, next()).await;
+    let Backend2FrontendMsg::State(gs) = resp else {
+        bail!("expected the next hand, got {resp:?}");
+    };
+    assert_eq!((gs.hand_number, gs.hands_remaining), (2, Some(1)));
+
+    let resp = dispatch_client_message(&state, table_id, next()).await;
+    let Backend2FrontendMsg::Error(e) = resp else {
+        bail!("expected an error, got {resp:?}");
+    };
+    assert!(e.contains("over"));
+
+    // A new game at the table counts on from the last hand
+    let Some(gs) = current_state_public(&state, table_id).await else {
+        bail!("table {table_id} has no game");
+    };
+    let players = gs
+        .players
+        .iter()
+        .map(|p| PlayerConfig {
+            id: p.id,
+            name: p.name.clone(),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        })
+        .collect();
+    let new_game = Frontend2BackendMsg::NewGame {
+        player_id: PlayerId(0),
+        players,
+    };
+    let resp = dispatch_client_message(&state, table_id, new_game).await;
+    let Backend2FrontendMsg::State(gs) = resp else {
+        bail!("expected a new game, got {resp:?}");
+    };
+    assert_eq!((gs.hand_number, gs.hands_remaining), (3, Some(2)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn rebuys_can_be_disabled() -> Result<()> {
+    let rules = GameConfigBuilder::default()
+        .rebuy_policy(RebuyPolicy {
+            allowed: false,
+            ..RebuyPolicy::default()
+        })
+        .build()?;
+    let state = AppState::default().with_game_config(Some(rules));
+    let table_id = open_table(&state, 2).await?;
+
+    let msg = Frontend2BackendMsg::Rebuy {
+        player_id: PlayerId(0),
+        amount: 500,
+    };
+    let resp = dispatch_client_message(&state, table_id, msg).await;
+    let Backend2FrontendMsg::Error(e) = resp else {
+        bail!("expected an error, got {resp:?}");
+    };
+    assert!(e.contains("not allowed"));
+    Ok(())
+}
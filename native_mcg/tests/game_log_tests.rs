@@ -0,0 +1,121 @@
+//! Tests for the JSON lines game log written with `--log-file`
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use mcg_shared::{
+    Backend2FrontendMsg, BettingLimit, Frontend2BackendMsg, PlayerAction, PlayerConfig, PlayerId,
+    Stage, TableConfig, TableId, DEFAULT_TABLE_ID,
+};
+use native_mcg::config::Config;
+use native_mcg::server::game_log::{read_game_log, GameLogRecord};
+use native_mcg::server::{current_state_public, dispatch_client_message, AppState};
+
+fn log_path(test: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "mcg-game-log-{}-{}.jsonl",
+        test,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+async fn open_table(state: &AppState) -> Result<TableId> {
+    let players = ["Alice", "Bob"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| PlayerConfig {
+            id: PlayerId(i),
+            name: name.to_string(),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        })
+        .collect();
+    let config = TableConfig {
+        players,
+        sb: 5,
+        bb: 10,
+        bounty: None,
+        betting_limit: BettingLimit::NoLimit,
+    };
+    let msg = Frontend2BackendMsg::CreateTable { config };
+    match dispatch_client_message(state, DEFAULT_TABLE_ID, msg).await {
+        Backend2FrontendMsg::TableJoined(id) => Ok(id),
+        other => bail!("unexpected response {other:?}"),
+    }
+}
+
+/// Check/call until the hand reaches showdown.
+async fn play_hand(state: &AppState, table_id: TableId) -> Result<()> {
+    loop {
+        let Some(gs) = current_state_public(state, table_id).await else {
+            bail!("table {table_id} has no game");
+        };
+        if gs.stage == Stage::Showdown {
+            return Ok(());
+        }
+        let action = Frontend2BackendMsg::Action {
+            table_id,
+            player_id: gs.to_act,
+            action: PlayerAction::CheckCall,
+        };
+        let resp = dispatch_client_message(state, table_id, action).await;
+        assert!(matches!(resp, Backend2FrontendMsg::State(_)));
+    }
+}
+
+#[tokio::test]
+async fn game_log_records_hands_and_events_in_order() -> Result<()> {
+    let path = log_path("hands");
+    let config = Config {
+        game_log: Some(path.display().to_string()),
+        ..Config::default()
+    };
+    let state = AppState::new(config, None);
+    let table_id = open_table(&state).await?;
+    play_hand(&state, table_id).await?;
+    let next = Frontend2BackendMsg::NextHand {
+        player_id: PlayerId(0),
+    };
+    dispatch_client_message(&state, table_id, next).await;
+
+    let entries = read_game_log(&path)?;
+    let _ = std::fs::remove_file(&path);
+    assert!(entries.iter().all(|e| e.table_id == table_id));
+    assert!(entries
+        .windows(2)
+        .all(|w| w[0].timestamp_ms <= w[1].timestamp_ms));
+
+    let kinds: Vec<&str> = entries
+        .iter()
+        .map(|e| match &e.record {
+            GameLogRecord::HandStart { .. } => "start",
+            GameLogRecord::Action { .. } => "action",
+            GameLogRecord::HandEnd { .. } => "end",
+        })
+        .collect();
+    assert_eq!(kinds.first(), Some(&"start"));
+    assert_eq!(kinds.last(), Some(&"start"));
+    assert_eq!(kinds.iter().filter(|k| **k == "end").count(), 1);
+    assert!(kinds.iter().filter(|k| **k == "action").count() >= 4);
+
+    let hand_numbers: Vec<u32> = entries
+        .iter()
+        .filter_map(|e| match &e.record {
+            GameLogRecord::HandStart { hand_number, .. }
+            | GameLogRecord::HandEnd { hand_number, .. } => Some(*hand_number),
+            GameLogRecord::Action { .. } => None,
+        })
+        .collect();
+    assert_eq!(hand_numbers, vec![1, 1, 2]);
+
+    // The state of the last action is the showdown of the first hand
+    let last_action = entries.iter().rev().find_map(|e| match &e.record {
+        GameLogRecord::Action { state, .. } => Some(state),
+        _ => None,
+    });
+    assert_eq!(last_action.map(|gs| gs.stage), Some(Stage::Showdown));
+    Ok(())
+}
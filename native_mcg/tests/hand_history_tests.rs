@@ -0,0 +1,170 @@
+//! Round-trip tests for the text hand history format
+
+use anyhow::Result;
+use mcg_shared::{
+    ActionEvent, ActionKind, Backend2FrontendMsg, BettingLimit, Card, CardRank, CardSuit,
+    Frontend2BackendMsg, GameAction, PlayerAction, PlayerConfig, Stage, TableConfig,
+    DEFAULT_TABLE_ID,
+};
+use native_mcg::game::{Game, Player};
+use native_mcg::pretty::{HandHistoryParser, HandHistoryWriter};
+use native_mcg::server::{dispatch_client_message, AppState};
+
+fn create_players(count: usize) -> Vec<Player> {
+    (0..count)
+        .map(|i| Player {
+            id: mcg_shared::PlayerId(i),
+            name: format!("Player {}", i + 1),
+            stack: 1000,
+            cards: [
+                Card::new(CardRank::Ace, CardSuit::Clubs),
+                Card::new(CardRank::Ace, CardSuit::Diamonds),
+            ],
+            has_folded: false,
+            all_in: false,
+        })
+        .collect()
+}
+
+/// Play a hand with a bet, a raise and a fold, then check/call down to showdown.
+fn play_scripted_hand() -> Result<Game> {
+    let mut game = Game::with_players(create_players(3))?;
+    let mut script = vec![
+        PlayerAction::Bet(30),
+        PlayerAction::Bet(40),
+        PlayerAction::Fold,
+    ]
+    .into_iter();
+    while game.stage != Stage::Showdown {
+        let action = script.next().unwrap_or(PlayerAction::CheckCall);
+        game.apply_player_action(game.to_act, action)?;
+    }
+    Ok(game)
+}
+
+#[test]
+fn test_hand_history_round_trip() -> Result<()> {
+    let game = play_scripted_hand()?;
+    let gs = game.public();
+
+    let text = HandHistoryWriter::format_hand(&gs, 7, 1_700_000_000);
+    let parsed = HandHistoryParser::parse_hand(&text)?;
+
+    assert_eq!(parsed.hand_number, 7);
+    assert_eq!(parsed.date, "2023/11/14 22:13:20 UTC");
+    assert_eq!((parsed.sb, parsed.bb), (gs.sb, gs.bb));
+
+    // Every player starts the hand with 1000 chips
+    let seats: Vec<(String, u32)> = gs.players.iter().map(|p| (p.name.clone(), 1000)).collect();
+    assert_eq!(parsed.seats, seats);
+
+    for (name, cards) in &parsed.hole_cards {
+        let p = gs.players.iter().find(|p| &p.name == name).unwrap();
+        assert_eq!(Some(*cards), p.cards);
+    }
+    assert_eq!(parsed.hole_cards.len(), gs.players.len());
+
+    let expected_actions: Vec<_> = gs
+        .action_log
+        .iter()
+        .filter_map(|e| match e {
+            ActionEvent::PlayerAction { player_id, action } => Some((
+                mcg_shared::PlayerPublic::name_of(&gs.players, *player_id),
+                action.clone(),
+            )),
+            ActionEvent::GameAction(_) => None,
+        })
+        .collect();
+    assert_eq!(parsed.actions, expected_actions);
+    assert_eq!(parsed.board, gs.community);
+
+    // Everything awarded from the pot was collected by the winners
+    let collected: u32 = parsed.collected.iter().map(|(_, n)| n).sum();
+    let awarded: u32 = gs
+        .action_log
+        .iter()
+        .filter_map(|e| match e {
+            ActionEvent::GameAction(GameAction::PotAwarded { amount, .. }) => Some(*amount),
+            _ => None,
+        })
+        .sum();
+    assert!(awarded > 0);
+    assert_eq!(collected, awarded);
+
+    Ok(())
+}
+
+#[test]
+fn test_session_history_parses_multiple_hands() -> Result<()> {
+    let first = HandHistoryWriter::format_hand(&play_scripted_hand()?.public(), 1, 0);
+    let second = HandHistoryWriter::format_hand(&play_scripted_hand()?.public(), 2, 86_400);
+    let session = HandHistoryWriter::format_session(&[first, second]);
+
+    let hands = HandHistoryParser::parse_session(&session)?;
+    assert_eq!(hands.len(), 2);
+    assert_eq!(hands[0].hand_number, 1);
+    assert_eq!(hands[0].date, "1970/01/01 00:00:00 UTC");
+    assert_eq!(hands[1].hand_number, 2);
+    assert_eq!(hands[1].date, "1970/01/02 00:00:00 UTC");
+    Ok(())
+}
+
+#[test]
+fn test_all_in_round_trips() -> Result<()> {
+    let mut game = Game::with_players(create_players(3))?;
+    let mut script = vec![PlayerAction::AllIn, PlayerAction::Fold].into_iter();
+    while game.stage != Stage::Showdown {
+        let action = script.next().unwrap_or(PlayerAction::CheckCall);
+        game.apply_player_action(game.to_act, action)?;
+    }
+    let gs = game.public();
+
+    let text = HandHistoryWriter::format_hand(&gs, 1, 0);
+    assert!(text.contains("goes all-in for 1000"));
+    let parsed = HandHistoryParser::parse_hand(&text)?;
+    assert!(parsed
+        .actions
+        .iter()
+        .any(|(_, action)| *action == ActionKind::AllIn { amount: 1000 }));
+    assert_eq!(parsed.seats[0].1, 1000);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_session_export_hides_the_hand_in_play() -> Result<()> {
+    let state = AppState::default();
+    let players = (0..3)
+        .map(|i| PlayerConfig {
+            id: mcg_shared::PlayerId(i),
+            name: format!("Player {}", i + 1),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        })
+        .collect();
+    let config = TableConfig {
+        betting_limit: BettingLimit::PotLimit,
+        ..TableConfig::new(players)
+    };
+    let resp = dispatch_client_message(
+        &state,
+        DEFAULT_TABLE_ID,
+        Frontend2BackendMsg::CreateTable { config },
+    )
+    .await;
+    let Backend2FrontendMsg::TableJoined(table_id) = resp else {
+        anyhow::bail!("unexpected response {resp:?}");
+    };
+
+    let text = native_mcg::server::state::session_hand_history(&state, table_id).await;
+    assert!(text.contains("Hold'em Pot Limit (5/10)"));
+    let hands = HandHistoryParser::parse_session(&text)?;
+    assert_eq!(hands.len(), 1);
+    assert!(hands[0].hole_cards.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_parser_rejects_garbage() {
+    assert!(HandHistoryParser::parse_hand("not a hand history").is_err());
+}
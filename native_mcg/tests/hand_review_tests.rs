@@ -0,0 +1,74 @@
+//! Tests for reviewing a finished hand against the reference strategy
+
+use anyhow::{bail, Result};
+use mcg_shared::{
+    ActionKind, Backend2FrontendMsg, BettingLimit, Frontend2BackendMsg, PlayerAction, PlayerConfig,
+    PlayerId, Stage, TableConfig, TableId, DEFAULT_TABLE_ID,
+};
+use native_mcg::server::{current_state_public, dispatch_client_message, AppState};
+
+async fn open_table(state: &AppState) -> Result<TableId> {
+    let players = ["Alice", "Bob"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| PlayerConfig {
+            id: PlayerId(i),
+            name: name.to_string(),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        })
+        .collect();
+    let config = TableConfig {
+        players,
+        sb: 5,
+        bb: 10,
+        bounty: None,
+        betting_limit: BettingLimit::NoLimit,
+    };
+    let msg = Frontend2BackendMsg::CreateTable { config };
+    match dispatch_client_message(state, DEFAULT_TABLE_ID, msg).await {
+        Backend2FrontendMsg::TableJoined(id) => Ok(id),
+        other => bail!("unexpected response {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn finished_hands_are_reviewed_action_by_action() -> Result<()> {
+    let state = AppState::default();
+    let table_id = open_table(&state).await?;
+
+    let resp = dispatch_client_message(&state, table_id, Frontend2BackendMsg::ReviewHand).await;
+    assert!(matches!(resp, Backend2FrontendMsg::Error(_)));
+
+    let mut actions = 0;
+    loop {
+        let Some(gs) = current_state_public(&state, table_id).await else {
+            bail!("table {table_id} has no game");
+        };
+        if gs.stage == Stage::Showdown {
+            break;
+        }
+        let action = Frontend2BackendMsg::Action {
+            table_id,
+            player_id: gs.to_act,
+            action: PlayerAction::CheckCall,
+        };
+        dispatch_client_message(&state, table_id, action).await;
+        actions += 1;
+    }
+
+    let resp = dispatch_client_message(&state, table_id, Frontend2BackendMsg::ReviewHand).await;
+    let Backend2FrontendMsg::HandReview(annotations) = resp else {
+        bail!("expected a hand review, got {resp:?}");
+    };
+    // Blinds are forced, so only the checks and calls are reviewed
+    assert_eq!(annotations.len(), actions);
+    assert!(matches!(annotations[0].actual, ActionKind::Call(_)));
+    assert_eq!(annotations[0].stage, Stage::Preflop);
+    for note in &annotations {
+        assert!(note.ev_diff >= 0.0);
+        assert_eq!(note.is_optimal(), note.ev_diff == 0.0);
+    }
+    Ok(())
+}
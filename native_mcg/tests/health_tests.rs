@@ -0,0 +1,123 @@
+//! Tests for the health check and Prometheus metrics endpoints
+
+use anyhow::{Context, Result};
+use mcg_shared::{
+    Frontend2BackendMsg, PlayerAction, PlayerConfig, PlayerId, Stage, DEFAULT_TABLE_ID,
+};
+use native_mcg::config::Config;
+use native_mcg::server::{current_state_public, dispatch_client_message, AppState};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Serve the router and send a raw GET request, returning the status line and body.
+async fn get(state: AppState, path: &str) -> Result<(String, String)> {
+    let app = native_mcg::server::run::build_router(state);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let server = tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+
+    let mut stream = tokio::net::TcpStream::connect(addr).await?;
+    let request = format!("GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+    stream.write_all(request.as_bytes()).await?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await?;
+    server.abort();
+
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .context("response without body")?;
+    let status = head.lines().next().unwrap_or_default().to_string();
+    Ok((status, body.to_string()))
+}
+
+/// Start a heads-up game with one bot at the default table and check/call
+/// until the hand reaches showdown.
+async fn play_hand(state: &AppState) -> Result<()> {
+    let players = vec![
+        PlayerConfig {
+            id: PlayerId(0),
+            name: "Alice".into(),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        },
+        PlayerConfig {
+            id: PlayerId(1),
+            name: "Bot".into(),
+            is_bot: true,
+            avatar_color: None,
+            preferred_seat: None,
+        },
+    ];
+    let msg = Frontend2BackendMsg::NewGame {
+        player_id: PlayerId(0),
+        players,
+    };
+    dispatch_client_message(state, DEFAULT_TABLE_ID, msg).await;
+    loop {
+        let gs = current_state_public(state, DEFAULT_TABLE_ID)
+            .await
+            .context("no game at the default table")?;
+        if gs.stage == Stage::Showdown {
+            return Ok(());
+        }
+        let action = Frontend2BackendMsg::Action {
+            table_id: DEFAULT_TABLE_ID,
+            player_id: gs.to_act,
+            action: PlayerAction::CheckCall,
+        };
+        dispatch_client_message(state, DEFAULT_TABLE_ID, action).await;
+    }
+}
+
+#[tokio::test]
+async fn plain_health_check() -> Result<()> {
+    let state = AppState::new(Config::default(), None);
+    let (status, body) = get(state, "/health").await?;
+    assert!(status.contains("200"));
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(json, serde_json::json!({ "ok": true }));
+    Ok(())
+}
+
+#[tokio::test]
+async fn detailed_health_check_reports_tables_and_hands() -> Result<()> {
+    let state = AppState::new(Config::default(), None);
+    play_hand(&state).await?;
+
+    let (status, body) = get(state, "/health?detail=true").await?;
+    assert!(status.contains("200"));
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(json["ok"], true);
+    assert_eq!(json["hands_played"], 1);
+    assert_eq!(json["bot_count"], 1);
+    assert_eq!(json["ws_connections_active"], 0);
+    assert!(json["uptime_secs"].is_u64());
+    assert_eq!(json["tables"][0]["table_id"], DEFAULT_TABLE_ID);
+    assert_eq!(json["tables"][0]["stage"], "Showdown");
+    Ok(())
+}
+
+#[tokio::test]
+async fn metrics_in_prometheus_format() -> Result<()> {
+    let state = AppState::new(Config::default(), None);
+    play_hand(&state).await?;
+    let guard = state.metrics.ws_connected();
+
+    let (status, body) = get(state.clone(), "/metrics").await?;
+    assert!(status.contains("200"));
+    assert!(body.contains("# TYPE mcg_hands_played counter\nmcg_hands_played 1\n"));
+    assert!(body.contains("mcg_ws_connections_total 1\n"));
+    assert!(body.contains("mcg_ws_connections_active 1\n"));
+    let actions = body
+        .lines()
+        .find_map(|l| l.strip_prefix("mcg_actions_applied "))
+        .context("missing mcg_actions_applied")?;
+    assert!(actions.parse::<u64>()? >= 2);
+
+    drop(guard);
+    assert_eq!(state.metrics.ws_connections_active(), 0);
+    assert_eq!(state.metrics.ws_connections_total(), 1);
+    Ok(())
+}
@@ -1,8 +1,30 @@
 use anyhow::Result;
 use futures_util::{SinkExt, StreamExt};
-use mcg_shared::{Frontend2BackendMsg, PlayerConfig, PlayerId, Backend2FrontendMsg};
+use mcg_shared::{
+    Backend2FrontendMsg, Frontend2BackendMsg, PlayerAction, PlayerConfig, PlayerId, Sequenced,
+    DEFAULT_TABLE_ID,
+};
 use std::time::Duration;
 
+/// Drain the responses a client gets right after subscribing.
+async fn drain_initial_messages<R>(read: &mut R)
+where
+    R: StreamExt<
+            Item = Result<
+                tokio_tungstenite::tungstenite::Message,
+                tokio_tungstenite::tungstenite::Error,
+            >,
+        > + Unpin,
+{
+    let start = tokio::time::Instant::now();
+    while start.elapsed() < Duration::from_millis(500) {
+        let next = tokio::time::timeout(Duration::from_millis(100), read.next()).await;
+        if !matches!(next, Ok(Some(Ok(_)))) {
+            break;
+        }
+    }
+}
+
 #[allow(clippy::collapsible_match)]
 #[tokio::test]
 async fn ws_broadcasts_state_to_other_clients() -> Result<()> {
@@ -18,7 +40,7 @@ async fn ws_broadcasts_state_to_other_clients() -> Result<()> {
     let server_handle = tokio::spawn(async move {
         let result = axum::serve(listener, app).await;
         if let Err(e) = result {
-            eprintln!("server error: {}", e);
+            eprintln!("server error: {e}");
         }
     });
 
@@ -45,29 +67,6 @@ async fn ws_broadcasts_state_to_other_clients() -> Result<()> {
         .await?;
 
     // Drain any immediate responses triggered by subscription
-    async fn drain_initial_messages<R>(read: &mut R)
-    where
-        R: StreamExt<
-                Item = Result<
-                    tokio_tungstenite::tungstenite::Message,
-                    tokio_tungstenite::tungstenite::Error,
-                >,
-            > + Unpin,
-    {
-        let start = tokio::time::Instant::now();
-        while start.elapsed() < Duration::from_millis(500) {
-            if let Ok(Some(Ok(msg))) =
-                tokio::time::timeout(Duration::from_millis(100), read.next()).await
-            {
-                if !matches!(msg, tokio_tungstenite::tungstenite::Message::Text(_)) {
-                    continue;
-                }
-            } else {
-                break;
-            }
-        }
-    }
-
     drain_initial_messages(&mut read1).await;
     drain_initial_messages(&mut read2).await;
 
@@ -77,15 +76,22 @@ async fn ws_broadcasts_state_to_other_clients() -> Result<()> {
             id: PlayerId(0),
             name: "Alice".to_string(),
             is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
         },
         PlayerConfig {
             id: PlayerId(1),
             name: "Bob".to_string(),
             is_bot: true,
+            avatar_color: None,
+            preferred_seat: None,
         },
     ];
 
-    let cm = Frontend2BackendMsg::NewGame { players };
+    let cm = Frontend2BackendMsg::NewGame {
+        player_id: PlayerId(0),
+        players,
+    };
     let txt = serde_json::to_string(&cm)?;
     write1
         .send(tokio_tungstenite::tungstenite::Message::Text(txt))
@@ -118,3 +124,241 @@ async fn ws_broadcasts_state_to_other_clients() -> Result<()> {
     );
     Ok(())
 }
+
+/// The next text message from the server, decoded with its sequence number.
+async fn next_server_msg<S>(ws: &mut S) -> Result<Sequenced<Backend2FrontendMsg>>
+where
+    S: StreamExt<
+            Item = Result<
+                tokio_tungstenite::tungstenite::Message,
+                tokio_tungstenite::tungstenite::Error,
+            >,
+        > + Unpin,
+{
+    loop {
+        let msg = tokio::time::timeout(Duration::from_secs(3), ws.next())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("websocket closed"))??;
+        if let tokio_tungstenite::tungstenite::Message::Text(txt) = msg {
+            return Ok(serde_json::from_str(&txt)?);
+        }
+    }
+}
+
+#[tokio::test]
+async fn ws_greets_clients_and_rejects_incompatible_versions() -> Result<()> {
+    let app = native_mcg::server::run::build_router(native_mcg::server::AppState::default());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let ws_url = format!("ws://{}/ws", listener.local_addr()?);
+    let server_handle = tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(&ws_url).await?;
+    let welcome = next_server_msg(&mut ws).await?.msg;
+    assert!(
+        matches!(&welcome, Backend2FrontendMsg::Welcome { server_version }
+            if server_version == env!("CARGO_PKG_VERSION")),
+        "expected Welcome, got {welcome:?}"
+    );
+
+    let claim = Frontend2BackendMsg::ClientVersion {
+        version: "999.0.0".to_string(),
+    };
+    ws.send(tokio_tungstenite::tungstenite::Message::Text(
+        serde_json::to_string(&claim)?,
+    ))
+    .await?;
+    let reply = next_server_msg(&mut ws).await?.msg;
+
+    server_handle.abort();
+    assert!(
+        matches!(&reply, Backend2FrontendMsg::Error(e) if e == "Incompatible client"),
+        "expected an error, got {reply:?}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn ws_numbers_messages_and_acknowledges_numbered_ones() -> Result<()> {
+    let app = native_mcg::server::run::build_router(native_mcg::server::AppState::default());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let ws_url = format!("ws://{}/ws", listener.local_addr()?);
+    let server_handle = tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(&ws_url).await?;
+    let welcome = next_server_msg(&mut ws).await?;
+    assert_eq!(welcome.seq, Some(0));
+
+    for seq in [7, 8] {
+        let ping = Sequenced {
+            seq: Some(seq),
+            msg: Frontend2BackendMsg::Ping,
+        };
+        ws.send(tokio_tungstenite::tungstenite::Message::Text(
+            serde_json::to_string(&ping)?,
+        ))
+        .await?;
+    }
+    // Unnumbered messages are answered but not acknowledged
+    ws.send(tokio_tungstenite::tungstenite::Message::Text(
+        serde_json::to_string(&Frontend2BackendMsg::Ping)?,
+    ))
+    .await?;
+    let mut replies = Vec::new();
+    for _ in 0..5 {
+        replies.push(next_server_msg(&mut ws).await?);
+    }
+
+    server_handle.abort();
+    let seqs: Vec<_> = replies.iter().map(|reply| reply.seq).collect();
+    assert_eq!(seqs, [Some(1), Some(2), Some(3), Some(4), Some(5)]);
+    let kinds: Vec<_> = replies
+        .iter()
+        .map(|reply| match reply.msg {
+            Backend2FrontendMsg::Pong => "Pong".to_string(),
+            Backend2FrontendMsg::Ack { seq } => format!("Ack {seq}"),
+            ref other => format!("{other:?}"),
+        })
+        .collect();
+    assert_eq!(kinds, ["Pong", "Ack 7", "Pong", "Ack 8", "Pong"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn ws_late_joiner_posts_a_blind_to_play() -> Result<()> {
+    let state = native_mcg::server::AppState::default();
+    let players = ["Alice", "Bob"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| PlayerConfig {
+            id: PlayerId(i),
+            name: name.to_string(),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        })
+        .collect();
+    let new_game = Frontend2BackendMsg::NewGame {
+        player_id: PlayerId(0),
+        players,
+    };
+    native_mcg::server::dispatch_client_message(&state, DEFAULT_TABLE_ID, new_game).await;
+    // Seats only change between hands
+    let Some(gs) = native_mcg::server::current_state_public(&state, DEFAULT_TABLE_ID).await else {
+        anyhow::bail!("no game was started");
+    };
+    let fold = Frontend2BackendMsg::Action {
+        table_id: DEFAULT_TABLE_ID,
+        player_id: gs.to_act,
+        action: PlayerAction::Fold,
+    };
+    native_mcg::server::dispatch_client_message(&state, DEFAULT_TABLE_ID, fold).await;
+
+    let app = native_mcg::server::run::build_router(state);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let ws_url = format!("ws://{}/ws", listener.local_addr()?);
+    let server_handle = tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    let (mut ws, _) = tokio_tungstenite::connect_async(&ws_url).await?;
+    next_server_msg(&mut ws).await?;
+
+    let carol = PlayerId(2);
+    let take_seat = Frontend2BackendMsg::TakeSeat {
+        player: PlayerConfig {
+            id: carol,
+            name: "Carol".to_string(),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        },
+    };
+    ws.send(tokio_tungstenite::tungstenite::Message::Text(
+        serde_json::to_string(&take_seat)?,
+    ))
+    .await?;
+    let Backend2FrontendMsg::State(mut gs) = next_server_msg(&mut ws).await?.msg else {
+        anyhow::bail!("expected the state after taking a seat");
+    };
+    let seated = gs.players.iter().find(|p| p.id == carol);
+    assert!(seated.is_some_and(|p| p.must_post_blind && p.cards.is_none()));
+
+    let post = Frontend2BackendMsg::Action {
+        table_id: DEFAULT_TABLE_ID,
+        player_id: carol,
+        action: PlayerAction::PostBlind,
+    };
+    ws.send(tokio_tungstenite::tungstenite::Message::Text(
+        serde_json::to_string(&post)?,
+    ))
+    .await?;
+    let reply = next_server_msg(&mut ws).await?.msg;
+
+    server_handle.abort();
+    match reply {
+        Backend2FrontendMsg::StateDiff(diff) => diff.apply_to(&mut gs),
+        other => anyhow::bail!("expected a state diff, got {other:?}"),
+    }
+    let seated = gs.players.iter().find(|p| p.id == carol);
+    assert!(seated.is_some_and(|p| !p.must_post_blind));
+    Ok(())
+}
+
+#[tokio::test]
+async fn ws_handles_a_re_sent_message_once() -> Result<()> {
+    let state = native_mcg::server::AppState::default();
+    let players = ["Alice", "Bob"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| PlayerConfig {
+            id: PlayerId(i),
+            name: name.to_string(),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        })
+        .collect();
+    let new_game = Frontend2BackendMsg::NewGame {
+        player_id: PlayerId(0),
+        players,
+    };
+    native_mcg::server::dispatch_client_message(&state, DEFAULT_TABLE_ID, new_game).await;
+
+    let app = native_mcg::server::run::build_router(state.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let ws_url = format!("ws://{}/ws", listener.local_addr()?);
+    let server_handle = tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    let (mut ws, _) = tokio_tungstenite::connect_async(&ws_url).await?;
+    next_server_msg(&mut ws).await?;
+
+    // The client re-sends a message whose `Ack` was late
+    let next_hand = serde_json::to_string(&Sequenced {
+        seq: Some(0),
+        msg: Frontend2BackendMsg::NextHand {
+            player_id: PlayerId(0),
+        },
+    })?;
+    for _ in 0..2 {
+        ws.send(tokio_tungstenite::tungstenite::Message::Text(
+            next_hand.clone(),
+        ))
+        .await?;
+    }
+    let mut replies = Vec::new();
+    for _ in 0..3 {
+        replies.push(next_server_msg(&mut ws).await?.msg);
+    }
+    let gs = native_mcg::server::current_state_public(&state, DEFAULT_TABLE_ID).await;
+
+    server_handle.abort();
+    assert!(matches!(replies[0], Backend2FrontendMsg::State(_)));
+    assert!(matches!(replies[1], Backend2FrontendMsg::Ack { seq: 0 }));
+    assert!(matches!(replies[2], Backend2FrontendMsg::Ack { seq: 0 }));
+    assert_eq!(gs.map(|gs| gs.hand_number), Some(2));
+    Ok(())
+}
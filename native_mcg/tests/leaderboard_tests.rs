@@ -0,0 +1,85 @@
+//! Tests for the all-time leaderboard
+
+use anyhow::{bail, Context, Result};
+use mcg_shared::{
+    Backend2FrontendMsg, BettingLimit, Frontend2BackendMsg, LeaderboardEntry, PlayerAction,
+    PlayerConfig, PlayerId, TableConfig, DEFAULT_TABLE_ID,
+};
+use native_mcg::server::{current_state_public, dispatch_client_message, AppState};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Serve the router and send a raw GET request, returning the status line and body.
+async fn get(state: AppState, path: &str) -> Result<(String, String)> {
+    let app = native_mcg::server::run::build_router(state);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let server = tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+
+    let mut stream = tokio::net::TcpStream::connect(addr).await?;
+    let request = format!("GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+    stream.write_all(request.as_bytes()).await?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await?;
+    server.abort();
+
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .context("response without body")?;
+    let status = head.lines().next().unwrap_or_default().to_string();
+    Ok((status, body.to_string()))
+}
+
+#[tokio::test]
+async fn leaderboard_ranks_the_winner_of_a_hand_first() -> Result<()> {
+    let state = AppState::default();
+    let players = ["Alice", "Bob"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| PlayerConfig {
+            id: PlayerId(i),
+            name: name.to_string(),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        })
+        .collect();
+    let config = TableConfig {
+        players,
+        sb: 5,
+        bb: 10,
+        bounty: None,
+        betting_limit: BettingLimit::NoLimit,
+    };
+    let msg = Frontend2BackendMsg::CreateTable { config };
+    let table_id = match dispatch_client_message(&state, DEFAULT_TABLE_ID, msg).await {
+        Backend2FrontendMsg::TableJoined(id) => id,
+        other => bail!("unexpected response {other:?}"),
+    };
+    let Some(gs) = current_state_public(&state, table_id).await else {
+        bail!("table {table_id} has no game");
+    };
+    let folder = gs.to_act;
+    let fold = Frontend2BackendMsg::Action {
+        table_id,
+        player_id: folder,
+        action: PlayerAction::Fold,
+    };
+    dispatch_client_message(&state, table_id, fold).await;
+
+    let (status, body) = get(state, "/leaderboard").await?;
+    assert!(status.contains("200"), "{}", status);
+    let entries: Vec<LeaderboardEntry> = serde_json::from_str(&body)?;
+    assert_eq!(entries.len(), 2);
+    let (winner, loser) = (&entries[0], &entries[1]);
+    assert_eq!(winner.rank, 1);
+    assert_eq!(winner.hands_won, 1);
+    assert_eq!(winner.sessions_played, 1);
+    assert_eq!(loser.rank, 2);
+    assert_eq!(loser.hands_won, 0);
+    assert_eq!(winner.total_chips_won, -loser.total_chips_won);
+    assert!(winner.total_chips_won > 0);
+    assert_eq!(loser.player_name, gs.players[usize::from(folder)].name);
+    Ok(())
+}
@@ -0,0 +1,45 @@
+//! Tests for serving UI translations
+
+use anyhow::{Context, Result};
+use native_mcg::server::AppState;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Serve the router and send a raw GET request, returning the status line and body.
+async fn get(path: &str) -> Result<(String, String)> {
+    let app = native_mcg::server::run::build_router(AppState::default());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let server = tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+
+    let mut stream = tokio::net::TcpStream::connect(addr).await?;
+    let request = format!("GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+    stream.write_all(request.as_bytes()).await?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await?;
+    server.abort();
+
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .context("response without body")?;
+    let status = head.lines().next().unwrap_or_default().to_string();
+    Ok((status, body.to_string()))
+}
+
+#[tokio::test]
+async fn malformed_language_codes_are_rejected() -> Result<()> {
+    for path in ["/locale/en.json", "/locale/..%2Fconfig", "/locale/a%20b"] {
+        let (status, _) = get(path).await?;
+        assert!(status.contains("400"), "{path}: {status}");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn unknown_languages_are_not_found() -> Result<()> {
+    let (status, body) = get("/locale/xx").await?;
+    assert!(status.contains("404"), "{}", status);
+    assert!(body.contains("no locale 'xx'"));
+    Ok(())
+}
@@ -0,0 +1,124 @@
+//! Tests for the in-process mock server that client tests connect to
+
+use anyhow::{bail, Result};
+use mcg_shared::{
+    Backend2FrontendMsg, Frontend2BackendMsg, GameStatePublic, PlayerAction, PlayerConfig,
+    PlayerId, Stage, DEFAULT_TABLE_ID,
+};
+use native_mcg::server::mock_server::MockServer;
+
+const YOU: PlayerId = PlayerId(0);
+
+fn new_game() -> Frontend2BackendMsg {
+    let player = |id: usize, name: &str, is_bot: bool| PlayerConfig {
+        id: PlayerId(id),
+        name: name.to_string(),
+        is_bot,
+        avatar_color: None,
+        preferred_seat: None,
+    };
+    Frontend2BackendMsg::NewGame {
+        player_id: YOU,
+        players: vec![player(0, "You", false), player(1, "Bot", true)],
+    }
+}
+
+const fn check_call() -> Frontend2BackendMsg {
+    Frontend2BackendMsg::Action {
+        table_id: DEFAULT_TABLE_ID,
+        player_id: YOU,
+        action: PlayerAction::CheckCall,
+    }
+}
+
+/// Last state among `msgs`, as the client would display it.
+fn latest_state(msgs: &[Backend2FrontendMsg]) -> Option<&GameStatePublic> {
+    msgs.iter().rev().find_map(|msg| match msg {
+        Backend2FrontendMsg::State(gs) => Some(gs),
+        _ => None,
+    })
+}
+
+#[tokio::test]
+async fn a_hand_is_played_to_showdown() -> Result<()> {
+    let mut server = MockServer::default();
+    assert!(server
+        .handle(Frontend2BackendMsg::Subscribe)
+        .await
+        .is_empty());
+
+    let replies = server.handle(new_game()).await;
+    let Some(mut gs) = latest_state(&replies).cloned() else {
+        bail!("no state after starting a game: {replies:?}");
+    };
+    assert_eq!(gs.players.len(), 2);
+
+    // The bot acts on its own, so the client only ever acts for itself
+    for _ in 0..10 {
+        if gs.stage == Stage::Showdown {
+            break;
+        }
+        assert_eq!(gs.to_act, YOU);
+        let replies = server.handle(check_call()).await;
+        let Some(next) = latest_state(&replies) else {
+            bail!("no state after acting: {replies:?}");
+        };
+        gs = next.clone();
+    }
+    assert_eq!(gs.stage, Stage::Showdown);
+    assert!(!gs.winner_ids.is_empty());
+
+    let replies = server
+        .handle(Frontend2BackendMsg::NextHand { player_id: YOU })
+        .await;
+    assert_eq!(
+        latest_state(&replies).map(|gs| gs.stage),
+        Some(Stage::Preflop)
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn the_connection_only_plays_its_own_seat() -> Result<()> {
+    let mut server = MockServer::default();
+    server.handle(new_game()).await;
+    let replies = server
+        .handle(Frontend2BackendMsg::Action {
+            table_id: DEFAULT_TABLE_ID,
+            player_id: PlayerId(1),
+            action: PlayerAction::Fold,
+        })
+        .await;
+    assert!(
+        matches!(&replies[..], [Backend2FrontendMsg::Error(e)] if e == "Unauthorized player_id")
+    );
+
+    let replies = server
+        .handle(Frontend2BackendMsg::Action {
+            table_id: DEFAULT_TABLE_ID + 1,
+            player_id: YOU,
+            action: PlayerAction::Fold,
+        })
+        .await;
+    assert!(matches!(&replies[..], [Backend2FrontendMsg::Error(_)]));
+    Ok(())
+}
+
+#[tokio::test]
+async fn a_connection_talks_over_channels() -> Result<()> {
+    let mut conn = MockServer::default().connect();
+    assert!(conn.send(Frontend2BackendMsg::Subscribe));
+    assert!(conn.send(new_game()));
+    match conn.recv().await {
+        Some(Backend2FrontendMsg::State(gs)) => assert_eq!(gs.players.len(), 2),
+        other => bail!("expected a state, got {other:?}"),
+    }
+
+    assert!(conn.send(Frontend2BackendMsg::Ping));
+    while let Some(msg) = conn.recv().await {
+        if matches!(msg, Backend2FrontendMsg::Pong) {
+            return Ok(());
+        }
+    }
+    bail!("the server task ended before answering the ping")
+}
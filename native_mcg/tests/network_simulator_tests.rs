@@ -0,0 +1,137 @@
+//! Playing over a slow, lossy network
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use mcg_shared::{
+    Backend2FrontendMsg, Frontend2BackendMsg, GameStatePublic, PlayerAction, PlayerConfig,
+    PlayerId, Stage, DEFAULT_TABLE_ID,
+};
+use native_mcg::server::AppState;
+use native_mcg::transport::{NetworkConditions, NetworkSimulator, Transport, WebSocketTransport};
+
+const HANDS: usize = 10;
+
+/// Longest a client waits for news before assuming it missed some
+const PATIENCE: Duration = Duration::from_secs(1);
+
+fn new_game() -> Frontend2BackendMsg {
+    let players = ["Alice", "Bob"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| PlayerConfig {
+            id: PlayerId(i),
+            name: name.to_string(),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        })
+        .collect();
+    Frontend2BackendMsg::NewGame {
+        player_id: PlayerId(0),
+        players,
+    }
+}
+
+/// Check or call for `me` until `showdowns` hands reached the showdown. The
+/// dealer starts the game and every next hand.
+///
+/// Lost messages leave the client behind or the server waiting for it, so
+/// whenever the client hears nothing for a while it joins the table again,
+/// which sends it the full state.
+async fn play<T: Transport>(
+    transport: &mut T,
+    me: PlayerId,
+    dealer: bool,
+    showdowns: usize,
+) -> Result<()> {
+    let mut state: Option<GameStatePublic> = None;
+    let mut seen = 0;
+    loop {
+        let Ok(msg) = tokio::time::timeout(PATIENCE, transport.recv()).await else {
+            if dealer && state.is_none() {
+                transport.send(&new_game()).await?;
+            }
+            transport.send(&Frontend2BackendMsg::Subscribe).await?;
+            let rejoin = Frontend2BackendMsg::JoinTable {
+                table_id: DEFAULT_TABLE_ID,
+            };
+            transport.send(&rejoin).await?;
+            continue;
+        };
+        let was_at_showdown = state.as_ref().is_some_and(|gs| gs.stage == Stage::Showdown);
+        let (gs, full) = match msg?.context("the server closed the connection")? {
+            Backend2FrontendMsg::State(gs) => (state.insert(gs), true),
+            Backend2FrontendMsg::StateDiff(diff) => match state.as_mut() {
+                Some(gs) => {
+                    diff.apply_to(gs);
+                    (gs, false)
+                }
+                None => continue,
+            },
+            _ => continue,
+        };
+
+        if gs.stage == Stage::Showdown {
+            if !was_at_showdown {
+                seen += 1;
+                if seen == showdowns {
+                    return Ok(());
+                }
+            }
+            if dealer && (full || !was_at_showdown) {
+                transport
+                    .send(&Frontend2BackendMsg::NextHand { player_id: me })
+                    .await?;
+            }
+        } else if gs.to_act == me {
+            let action = Frontend2BackendMsg::Action {
+                table_id: DEFAULT_TABLE_ID,
+                player_id: me,
+                action: PlayerAction::CheckCall,
+            };
+            transport.send(&action).await?;
+        }
+    }
+}
+
+#[tokio::test]
+async fn hands_are_played_over_a_slow_lossy_network() -> Result<()> {
+    let app = native_mcg::server::run::build_router(AppState::default());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let base = listener.local_addr()?.to_string();
+    let server_handle = tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+
+    // 200ms ± 50ms each way, and one message in twenty lost
+    let conditions = NetworkConditions {
+        min_delay_ms: 150,
+        max_delay_ms: 250,
+        drop_probability: 0.05,
+        ..NetworkConditions::default()
+    };
+    let mut alice = NetworkSimulator::new(WebSocketTransport::connect(&base).await?, conditions);
+    let mut bob = NetworkSimulator::new(WebSocketTransport::connect(&base).await?, conditions);
+    bob.send(&Frontend2BackendMsg::Subscribe).await?;
+    alice.send(&Frontend2BackendMsg::Subscribe).await?;
+    alice.send(&new_game()).await?;
+
+    // Bob plays along until the end of the test
+    let bob_handle =
+        tokio::spawn(async move { play(&mut bob, PlayerId(1), false, usize::MAX).await });
+    let alice_result = tokio::time::timeout(
+        Duration::from_mins(3),
+        play(&mut alice, PlayerId(0), true, HANDS),
+    )
+    .await;
+
+    if bob_handle.is_finished() {
+        bob_handle.await??;
+    } else {
+        bob_handle.abort();
+    }
+    server_handle.abort();
+    alice_result.context("the hands took too long")??;
+    Ok(())
+}
@@ -0,0 +1,116 @@
+//! Tests for the game observers notified after each action
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use mcg_shared::{
+    ActionEvent, Backend2FrontendMsg, BettingLimit, Frontend2BackendMsg, HandResult, PlayerAction,
+    PlayerConfig, PlayerId, Stage, TableConfig, TableId, DEFAULT_TABLE_ID,
+};
+use native_mcg::server::observer::GameObserver;
+use native_mcg::server::{current_state_public, dispatch_client_message, AppState};
+
+#[derive(Default)]
+struct Counts {
+    actions: AtomicUsize,
+    hands: AtomicUsize,
+    updates: AtomicUsize,
+    stages: Mutex<Vec<Stage>>,
+}
+
+struct CountingObserver(Arc<Counts>);
+
+#[async_trait]
+impl GameObserver for CountingObserver {
+    async fn on_action(&self, _state: &AppState, _table_id: TableId, _event: &ActionEvent) {
+        self.0.actions.fetch_add(1, Ordering::SeqCst);
+    }
+
+    async fn on_stage_change(&self, _state: &AppState, _table_id: TableId, new_stage: Stage) {
+        self.0.stages.lock().unwrap().push(new_stage);
+    }
+
+    async fn on_hand_end(&self, _state: &AppState, _table_id: TableId, results: &[HandResult]) {
+        assert_eq!(results.len(), 3);
+        self.0.hands.fetch_add(1, Ordering::SeqCst);
+    }
+
+    async fn on_update(&self, _state: &AppState, _table_id: TableId) {
+        self.0.updates.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+async fn open_table(state: &AppState) -> Result<TableId> {
+    let players = ["Alice", "Bob", "Carol"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| PlayerConfig {
+            id: PlayerId(i),
+            name: name.to_string(),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        })
+        .collect();
+    let config = TableConfig {
+        players,
+        sb: 5,
+        bb: 10,
+        bounty: None,
+        betting_limit: BettingLimit::NoLimit,
+    };
+    let msg = Frontend2BackendMsg::CreateTable { config };
+    match dispatch_client_message(state, DEFAULT_TABLE_ID, msg).await {
+        Backend2FrontendMsg::TableJoined(id) => Ok(id),
+        other => bail!("unexpected response {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn observers_see_every_action_of_a_hand() -> Result<()> {
+    let state = AppState::default();
+    let table_id = open_table(&state).await?;
+    let counts = Arc::new(Counts::default());
+    state
+        .add_observer(Box::new(CountingObserver(counts.clone())))
+        .await;
+    let mut rx = state.broadcaster.subscribe();
+
+    let mut actions = 0;
+    loop {
+        let Some(gs) = current_state_public(&state, table_id).await else {
+            bail!("table {table_id} has no game");
+        };
+        if gs.stage == Stage::Showdown {
+            break;
+        }
+        let action = Frontend2BackendMsg::Action {
+            table_id,
+            player_id: gs.to_act,
+            action: PlayerAction::CheckCall,
+        };
+        let resp = dispatch_client_message(&state, table_id, action).await;
+        assert!(matches!(resp, Backend2FrontendMsg::State(_)));
+        actions += 1;
+    }
+
+    assert_eq!(counts.updates.load(Ordering::SeqCst), actions);
+    assert!(counts.actions.load(Ordering::SeqCst) >= actions);
+    assert_eq!(counts.hands.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        *counts.stages.lock().unwrap(),
+        vec![Stage::Flop, Stage::Turn, Stage::River, Stage::Showdown]
+    );
+
+    // The broadcast observer sends one state per action
+    let mut broadcasts = 0;
+    while let Ok((_, msg)) = rx.try_recv() {
+        if matches!(msg, Backend2FrontendMsg::State(_)) {
+            broadcasts += 1;
+        }
+    }
+    assert_eq!(broadcasts, actions);
+    Ok(())
+}
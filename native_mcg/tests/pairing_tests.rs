@@ -0,0 +1,119 @@
+//! Tests for the pairing endpoints that hand out the iroh node id and table invites
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use mcg_shared::{Backend2FrontendMsg, PairingInvite, PeerInfo, DEFAULT_TABLE_ID};
+use native_mcg::config::Config;
+use native_mcg::public::{path_for_config, PublicInfo};
+use native_mcg::server::AppState;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+const NODE_ID: &str = "ae58ff8833241ac82d6ff7611046ed67b5072d142c588d0063e942d9a75502b6";
+
+/// Config path in a fresh directory, so each test has its own public info file.
+fn config_path(test: &str) -> Result<PathBuf> {
+    let dir = std::env::temp_dir().join(format!("mcg-pairing-{}-{}", test, std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir.join("mcg-server.toml"))
+}
+
+/// Serve the router and send a raw GET request, returning the status line and body.
+async fn get(state: AppState, path: &str) -> Result<(String, String)> {
+    let app = native_mcg::server::run::build_router(state);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let server = tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+
+    let mut stream = tokio::net::TcpStream::connect(addr).await?;
+    let request =
+        format!("GET {path} HTTP/1.1\r\nHost: 192.168.1.20:3000\r\nConnection: close\r\n\r\n");
+    stream.write_all(request.as_bytes()).await?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await?;
+    server.abort();
+
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .context("response without body")?;
+    let status = head.lines().next().unwrap_or_default().to_string();
+    Ok((status, body.to_string()))
+}
+
+#[tokio::test]
+async fn pairing_code_needs_the_iroh_node_id() -> Result<()> {
+    let state = AppState::new(Config::default(), Some(config_path("missing")?));
+    let (status, _) = get(state.clone(), "/node-id").await?;
+    assert!(status.contains("503"));
+    let (status, _) = get(state, "/pairing-code").await?;
+    assert!(status.contains("503"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn pairing_code_invites_to_the_table() -> Result<()> {
+    let path = config_path("invite")?;
+    PublicInfo::write_iroh_node_id(&path_for_config(Some(&path)), NODE_ID)?;
+    let state = AppState::new(Config::default(), Some(path));
+
+    let (status, body) = get(state.clone(), "/node-id").await?;
+    assert!(status.contains("200"));
+    assert!(body.contains(NODE_ID));
+
+    // The default table exists without a game; other tables have to be created
+    let (status, _) = get(state.clone(), "/pairing-code?table=7").await?;
+    assert!(status.contains("404"));
+
+    let (status, body) = get(state, "/pairing-code").await?;
+    assert!(status.contains("200"));
+    let Backend2FrontendMsg::PairingCode {
+        node_id,
+        invite_code,
+    } = serde_json::from_str(&body)?
+    else {
+        bail!("expected a pairing code, got {body}");
+    };
+    assert_eq!(node_id, NODE_ID);
+    let invite = PairingInvite::parse(&invite_code).context("invite code does not parse")?;
+    assert_eq!(
+        invite,
+        PairingInvite {
+            server_address: "192.168.1.20:3000".to_string(),
+            node_id,
+            table_id: DEFAULT_TABLE_ID,
+        }
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn iroh_peers_are_listed_most_recent_first() -> Result<()> {
+    let state = AppState::default();
+    let now = native_mcg::server::game_log::now_ms();
+    {
+        let mut peers = state.iroh_peers.write().await;
+        peers.see(
+            NODE_ID.into(),
+            Some("https://relay.example/".into()),
+            now - 5_000,
+        );
+        peers.see("peer".into(), None, now - 1_000);
+        peers.see("gone".into(), None, now - 2 * 60 * 60 * 1000);
+        // Seen again without a relay url, the known one is kept
+        peers.see(NODE_ID.into(), None, now - 3_000);
+    }
+
+    let (status, body) = get(state, "/iroh/peers").await?;
+    assert!(status.contains("200"), "{}", status);
+    let peers: Vec<PeerInfo> = serde_json::from_str(&body)?;
+    let ids: Vec<&str> = peers.iter().map(|p| p.node_id.as_str()).collect();
+    assert_eq!(ids, ["peer", NODE_ID]);
+    assert_eq!(peers[1].last_seen_ms, now - 3_000);
+    assert_eq!(
+        peers[1].relay_url.as_deref(),
+        Some("https://relay.example/")
+    );
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use anyhow::Result;
+use futures_util::{SinkExt, StreamExt};
+use mcg_shared::{Backend2FrontendMsg, Frontend2BackendMsg};
+use native_mcg::server::quic::{start_quic_listener, QUIC_SERVER_NAME};
+use native_mcg::server::AppState;
+use native_mcg::transport::{connect_quic, recv_msg_on_uni, send_msg_on_uni};
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tokio_tungstenite::tungstenite::Message;
+
+async fn connect_to_new_server(state: AppState) -> Result<quinn::Connection> {
+    let (addr, cert) = start_quic_listener(state, 0)?;
+    let addr = SocketAddr::from(([127, 0, 0, 1], addr.port()));
+    connect_quic(addr, QUIC_SERVER_NAME, cert).await
+}
+
+#[tokio::test]
+async fn quic_ping_is_answered_with_pong() -> Result<()> {
+    let conn = connect_to_new_server(AppState::default()).await?;
+
+    send_msg_on_uni(&conn, &Frontend2BackendMsg::Ping).await?;
+    let resp: Backend2FrontendMsg =
+        tokio::time::timeout(Duration::from_secs(5), recv_msg_on_uni(&conn)).await??;
+    assert!(matches!(resp, Backend2FrontendMsg::Pong), "got {resp:?}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn quic_invalid_json_is_reported() -> Result<()> {
+    let conn = connect_to_new_server(AppState::default()).await?;
+
+    let mut send = conn.open_uni().await?;
+    send.write_all(b"not json").await?;
+    send.finish()?;
+    let resp: Backend2FrontendMsg =
+        tokio::time::timeout(Duration::from_secs(5), recv_msg_on_uni(&conn)).await??;
+    assert!(
+        matches!(resp, Backend2FrontendMsg::Error(ref e) if e.starts_with("Invalid JSON")),
+        "got {resp:?}"
+    );
+    Ok(())
+}
+
+/// Compare Ping round trips over QUIC and WebSocket on loopback.
+/// Run with `cargo test --release --test quic_tests -- --ignored --nocapture`.
+#[tokio::test]
+#[ignore = "latency comparison, run on demand"]
+async fn quic_vs_websocket_ping_latency() -> Result<()> {
+    const ROUNDS: u32 = 1000;
+    let state = AppState::default();
+
+    let conn = connect_to_new_server(state.clone()).await?;
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        send_msg_on_uni(&conn, &Frontend2BackendMsg::Ping).await?;
+        let _: Backend2FrontendMsg = recv_msg_on_uni(&conn).await?;
+    }
+    let quic = start.elapsed() / ROUNDS;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let ws_url = format!("ws://{}/ws", listener.local_addr()?);
+    let app = native_mcg::server::run::build_router(state);
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    let (mut ws, _) = tokio_tungstenite::connect_async(&ws_url).await?;
+    let ping = serde_json::to_string(&Frontend2BackendMsg::Ping)?;
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        ws.send(Message::Text(ping.clone())).await?;
+        while !matches!(ws.next().await, Some(Ok(Message::Text(_))) | None) {}
+    }
+    let websocket = start.elapsed() / ROUNDS;
+
+    println!("mean ping round trip over {ROUNDS} rounds: QUIC {quic:?}, WebSocket {websocket:?}");
+    Ok(())
+}
@@ -0,0 +1,111 @@
+//! Tests for rebuying chips between hands
+
+use anyhow::{bail, Result};
+use mcg_shared::{
+    Backend2FrontendMsg, BettingLimit, Frontend2BackendMsg, PlayerAction, PlayerConfig, PlayerId,
+    Stage, TableConfig, DEFAULT_TABLE_ID,
+};
+use native_mcg::server::{current_state_public, dispatch_client_message, AppState};
+
+const fn rebuy(player_id: PlayerId, amount: u32) -> Frontend2BackendMsg {
+    Frontend2BackendMsg::Rebuy { player_id, amount }
+}
+
+#[tokio::test]
+async fn short_stacks_may_rebuy_between_hands_only() -> Result<()> {
+    let state = AppState::default();
+    let players = ["Alice", "Bob"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| PlayerConfig {
+            id: PlayerId(i),
+            name: name.to_string(),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        })
+        .collect();
+    // With a big blind of 100 everyone below 1000 chips is short-stacked
+    let config = TableConfig {
+        players,
+        sb: 50,
+        bb: 100,
+        bounty: None,
+        betting_limit: BettingLimit::NoLimit,
+    };
+    let msg = Frontend2BackendMsg::CreateTable { config };
+    let table_id = match dispatch_client_message(&state, DEFAULT_TABLE_ID, msg).await {
+        Backend2FrontendMsg::TableJoined(id) => id,
+        other => bail!("unexpected response {other:?}"),
+    };
+    let Some(gs) = current_state_public(&state, table_id).await else {
+        bail!("table {table_id} has no game");
+    };
+    let folder = gs.to_act;
+
+    // Not while the hand is being played
+    let resp = dispatch_client_message(&state, table_id, rebuy(folder, 500)).await;
+    assert!(matches!(resp, Backend2FrontendMsg::Error(_)));
+
+    let mut rx = state.broadcaster.subscribe();
+    let fold = Frontend2BackendMsg::Action {
+        table_id,
+        player_id: folder,
+        action: PlayerAction::Fold,
+    };
+    dispatch_client_message(&state, table_id, fold).await;
+    let mut offers = Vec::new();
+    while let Ok((_, msg)) = rx.try_recv() {
+        if let Backend2FrontendMsg::RebuyAvailable { player_id, .. } = msg {
+            offers.push(player_id);
+        }
+    }
+    assert_eq!(offers, vec![folder]);
+
+    // Above the configured maximum
+    let resp = dispatch_client_message(&state, table_id, rebuy(folder, 5000)).await;
+    assert!(matches!(resp, Backend2FrontendMsg::Error(_)));
+
+    let resp = dispatch_client_message(&state, table_id, rebuy(folder, 500)).await;
+    let Backend2FrontendMsg::State(gs) = resp else {
+        bail!("expected state after rebuy, got {resp:?}");
+    };
+    assert_eq!(gs.stage, Stage::Showdown);
+    let stack_of = |id| gs.players.iter().find(|p| p.id == id).map(|p| p.stack);
+    assert_eq!(stack_of(folder), Some(1450));
+
+    // The winner is not short-stacked
+    let winner = gs.players.iter().find(|p| p.id != folder).map(|p| p.id);
+    let Some(winner) = winner else {
+        bail!("no winner seated");
+    };
+    let resp = dispatch_client_message(&state, table_id, rebuy(winner, 500)).await;
+    assert!(matches!(resp, Backend2FrontendMsg::Error(_)));
+
+    // The next hand is dealt with the extra chips in play
+    let next = Frontend2BackendMsg::NextHand {
+        player_id: PlayerId(0),
+    };
+    let resp = dispatch_client_message(&state, table_id, next).await;
+    let Backend2FrontendMsg::State(gs) = resp else {
+        bail!("expected state for the next hand, got {resp:?}");
+    };
+    let total: u32 = gs.players.iter().map(|p| p.stack).sum::<u32>() + gs.pot;
+    assert_eq!(total, 2500);
+    Ok(())
+}
+
+#[tokio::test]
+async fn rebuys_over_http_are_refused() {
+    use axum::extract::State;
+    use axum::Json;
+
+    let state = AppState::default();
+    let Json(resp) =
+        native_mcg::server::http::message_handler(State(state), Json(rebuy(PlayerId(0), 500)))
+            .await;
+    assert!(
+        matches!(&resp, Backend2FrontendMsg::Error(e) if e.contains("websocket")),
+        "{resp:?}"
+    );
+}
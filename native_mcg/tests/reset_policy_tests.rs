@@ -0,0 +1,199 @@
+//! Tests for the reset policies that decide what happens after each hand
+
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use mcg_shared::{
+    Backend2FrontendMsg, BettingLimit, Frontend2BackendMsg, GameResetPolicy, GameStatePublic,
+    PlayerAction, PlayerConfig, PlayerId, Stage, TableConfig, TableId, DEFAULT_TABLE_ID,
+};
+use native_mcg::config::Config;
+use native_mcg::server::{current_state_public, dispatch_client_message, AppState};
+
+const TOKEN: &str = "secret";
+const STARTING_STACK: u32 = 1000;
+
+fn admin_state() -> AppState {
+    let config = Config {
+        admin_token: Some(TOKEN.to_string()),
+        next_hand_delay_ms: 20,
+        ..Config::default()
+    };
+    AppState::new(config, None)
+}
+
+async fn open_table(state: &AppState) -> Result<TableId> {
+    let players = ["Alice", "Bob"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| PlayerConfig {
+            id: PlayerId(i),
+            name: name.to_string(),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        })
+        .collect();
+    let config = TableConfig {
+        players,
+        sb: 5,
+        bb: 10,
+        bounty: None,
+        betting_limit: BettingLimit::NoLimit,
+    };
+    let msg = Frontend2BackendMsg::CreateTable { config };
+    match dispatch_client_message(state, DEFAULT_TABLE_ID, msg).await {
+        Backend2FrontendMsg::TableJoined(id) => Ok(id),
+        other => bail!("unexpected response {other:?}"),
+    }
+}
+
+async fn set_policy(
+    state: &AppState,
+    policy: Option<GameResetPolicy>,
+    token: &str,
+) -> Backend2FrontendMsg {
+    let msg = Frontend2BackendMsg::SetResetPolicy {
+        policy,
+        token: token.to_string(),
+    };
+    dispatch_client_message(state, DEFAULT_TABLE_ID, msg).await
+}
+
+async fn state_of(state: &AppState, table_id: TableId) -> Result<GameStatePublic> {
+    match current_state_public(state, table_id).await {
+        Some(gs) => Ok(gs),
+        None => bail!("table {table_id} has no game"),
+    }
+}
+
+async fn act(state: &AppState, table_id: TableId, action: PlayerAction) -> Result<()> {
+    let gs = state_of(state, table_id).await?;
+    let msg = Frontend2BackendMsg::Action {
+        table_id,
+        player_id: gs.to_act,
+        action,
+    };
+    if let Backend2FrontendMsg::Error(e) = dispatch_client_message(state, table_id, msg).await {
+        bail!("action rejected: {e}");
+    }
+    Ok(())
+}
+
+/// Play hands with both players all in until one of them is busted.
+async fn play_until_bust(state: &AppState, table_id: TableId) -> Result<GameStatePublic> {
+    for _ in 0..50 {
+        act(state, table_id, PlayerAction::AllIn).await?;
+        act(state, table_id, PlayerAction::CheckCall).await?;
+        let gs = state_of(state, table_id).await?;
+        assert_eq!(gs.stage, Stage::Showdown);
+        if gs.players.iter().any(|p| p.stack == 0) {
+            return Ok(gs);
+        }
+        let next = Frontend2BackendMsg::NextHand {
+            player_id: PlayerId(0),
+        };
+        dispatch_client_message(state, table_id, next).await;
+    }
+    bail!("every all in was a split pot")
+}
+
+/// Poll the table until `done` holds for its state.
+async fn wait_for(
+    state: &AppState,
+    table_id: TableId,
+    done: impl Fn(&GameStatePublic) -> bool,
+) -> Result<GameStatePublic> {
+    for _ in 0..200 {
+        let gs = state_of(state, table_id).await?;
+        if done(&gs) {
+            return Ok(gs);
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    bail!("table {table_id} never reached the expected state")
+}
+
+#[tokio::test]
+async fn setting_the_policy_requires_the_admin_token() -> Result<()> {
+    let state = admin_state();
+    let policy = Some(GameResetPolicy::ContinueWithSameConfig);
+    let resp = set_policy(&state, policy, "wrong").await;
+    assert!(matches!(resp, Backend2FrontendMsg::Error(_)));
+    assert_eq!(*state.reset_policy.read().await, None);
+
+    let resp = set_policy(&state, policy, TOKEN).await;
+    assert!(matches!(resp, Backend2FrontendMsg::Tables(_)));
+    assert_eq!(*state.reset_policy.read().await, policy);
+    Ok(())
+}
+
+#[tokio::test]
+async fn without_a_policy_the_table_waits_at_showdown() -> Result<()> {
+    let state = admin_state();
+    let table_id = open_table(&state).await?;
+    act(&state, table_id, PlayerAction::Fold).await?;
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(state_of(&state, table_id).await?.stage, Stage::Showdown);
+    Ok(())
+}
+
+#[tokio::test]
+async fn continue_deals_the_next_hand_after_the_delay() -> Result<()> {
+    let state = admin_state();
+    set_policy(&state, Some(GameResetPolicy::ContinueWithSameConfig), TOKEN).await;
+    let table_id = open_table(&state).await?;
+
+    act(&state, table_id, PlayerAction::Fold).await?;
+    assert_eq!(state_of(&state, table_id).await?.stage, Stage::Showdown);
+    let gs = wait_for(&state, table_id, |gs| gs.stage == Stage::Preflop).await?;
+    // The blinds of the folded hand changed hands
+    assert_ne!(
+        gs.players[0].stack + gs.players[0].bet_this_round,
+        STARTING_STACK
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn busted_players_are_sent_back_to_the_lobby() -> Result<()> {
+    let state = admin_state();
+    set_policy(&state, Some(GameResetPolicy::ReturnToLobby), TOKEN).await;
+    let table_id = open_table(&state).await?;
+    let mut rx = state.broadcaster.subscribe();
+
+    let gs = play_until_bust(&state, table_id).await?;
+    let Some(busted) = gs.players.iter().find(|p| p.stack == 0).map(|p| p.id) else {
+        bail!("nobody busted");
+    };
+    let mut eliminated = Vec::new();
+    while let Ok((_, msg)) = rx.try_recv() {
+        if let Backend2FrontendMsg::PlayerEliminated {
+            player_id,
+            final_rank,
+        } = msg
+        {
+            eliminated.push((player_id, final_rank));
+        }
+    }
+    assert_eq!(eliminated, [(busted, 2)]);
+
+    // No hand is dealt without a `NextHand`
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(state_of(&state, table_id).await?.stage, Stage::Showdown);
+    Ok(())
+}
+
+#[tokio::test]
+async fn a_new_tournament_starts_once_a_single_player_is_left() -> Result<()> {
+    let state = admin_state();
+    set_policy(&state, Some(GameResetPolicy::StartNewTournament), TOKEN).await;
+    let table_id = open_table(&state).await?;
+
+    play_until_bust(&state, table_id).await?;
+    let gs = wait_for(&state, table_id, |gs| gs.stage == Stage::Preflop).await?;
+    for p in &gs.players {
+        assert_eq!(p.stack + p.bet_this_round, STARTING_STACK, "{}", p.name);
+    }
+    Ok(())
+}
@@ -0,0 +1,97 @@
+//! Tests for drawing tournament seats over several rounds
+
+use std::collections::HashMap;
+
+use mcg_shared::PlayerId;
+use native_mcg::server::lobby::{
+    generate_seat_draw, validate_schedule, ScheduleError, TableAssignment,
+};
+
+fn players(count: usize) -> Vec<PlayerId> {
+    (0..count).map(PlayerId).collect()
+}
+
+/// How often each two players shared a table
+fn pair_meetings(schedule: &[TableAssignment]) -> HashMap<(usize, usize), usize> {
+    let mut meetings = HashMap::new();
+    for table in schedule.iter().flatten() {
+        for (i, a) in table.iter().enumerate() {
+            for b in &table[i + 1..] {
+                *meetings.entry((a.0.min(b.0), a.0.max(b.0))).or_default() += 1;
+            }
+        }
+    }
+    meetings
+}
+
+#[test]
+fn nine_players_at_three_tables_share_a_table_at_most_once_in_four_rounds() {
+    let schedule = generate_seat_draw(&players(9), 3, 4);
+    assert_eq!(schedule.len(), 4);
+    assert_eq!(validate_schedule(&schedule), Ok(()));
+    for round in &schedule {
+        assert!(round.iter().all(|table| table.len() == 3));
+    }
+
+    let meetings = pair_meetings(&schedule);
+    assert!(meetings.values().all(|&n| n == 1), "{meetings:?}");
+    // Every pair of the 9 players met exactly once
+    assert_eq!(meetings.len(), 9 * 8 / 2);
+}
+
+#[test]
+fn uneven_fields_get_balanced_tables_and_rotating_seats() {
+    let schedule = generate_seat_draw(&players(10), 3, 3);
+    assert_eq!(validate_schedule(&schedule), Ok(()));
+    let mut sizes: Vec<usize> = schedule[0].iter().map(Vec::len).collect();
+    sizes.sort_unstable();
+    assert_eq!(sizes, [3, 3, 4]);
+
+    let first_seats: Vec<PlayerId> = schedule.iter().map(|round| round[0][0]).collect();
+    assert_ne!(first_seats[0], first_seats[1]);
+}
+
+#[test]
+fn unbalanced_schedules_are_rejected() {
+    let p = PlayerId;
+    let round = vec![vec![p(0), p(1)], vec![p(2), p(3)]];
+
+    let uneven = vec![round.clone(), vec![vec![p(0), p(1), p(2)], vec![p(3)]]];
+    assert_eq!(
+        validate_schedule(&uneven),
+        Err(ScheduleError::Unbalanced { round: 1 })
+    );
+
+    let merged = vec![round.clone(), vec![vec![p(0), p(1), p(2), p(3)]]];
+    assert_eq!(
+        validate_schedule(&merged),
+        Err(ScheduleError::TableCount { round: 1 })
+    );
+
+    let twice = vec![round.clone(), vec![vec![p(0), p(1)], vec![p(2), p(0)]]];
+    assert_eq!(
+        validate_schedule(&twice),
+        Err(ScheduleError::SeatedTwice {
+            round: 1,
+            player: p(0)
+        })
+    );
+
+    let unknown = vec![round.clone(), vec![vec![p(0), p(1)], vec![p(2), p(4)]]];
+    assert_eq!(
+        validate_schedule(&unknown),
+        Err(ScheduleError::Missing {
+            round: 1,
+            player: p(4)
+        })
+    );
+
+    let missing = vec![round, vec![vec![p(0), p(1)], vec![p(2)]]];
+    assert_eq!(
+        validate_schedule(&missing),
+        Err(ScheduleError::Missing {
+            round: 1,
+            player: p(3)
+        })
+    );
+}
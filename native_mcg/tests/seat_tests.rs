@@ -0,0 +1,125 @@
+//! Tests for reserving seats in the `NewGame` setup, taking seats between
+//! hands and posting to play
+
+use anyhow::{bail, Result};
+use mcg_shared::{
+    Backend2FrontendMsg, Frontend2BackendMsg, PlayerAction, PlayerConfig, PlayerId,
+    DEFAULT_TABLE_ID,
+};
+use native_mcg::server::{dispatch_client_message, AppState};
+
+fn players(seats: &[Option<u8>]) -> Vec<PlayerConfig> {
+    seats
+        .iter()
+        .enumerate()
+        .map(|(i, &preferred_seat)| PlayerConfig {
+            id: PlayerId(i),
+            name: format!("P{i}"),
+            is_bot: i > 0,
+            avatar_color: None,
+            preferred_seat,
+        })
+        .collect()
+}
+
+async fn new_game(state: &AppState, players: Vec<PlayerConfig>) -> Backend2FrontendMsg {
+    let msg = Frontend2BackendMsg::NewGame {
+        player_id: PlayerId(0),
+        players,
+    };
+    dispatch_client_message(state, DEFAULT_TABLE_ID, msg).await
+}
+
+#[tokio::test]
+async fn players_sit_in_their_preferred_seats() -> Result<()> {
+    let state = AppState::default();
+    let resp = new_game(&state, players(&[Some(3), None, Some(0), None, None])).await;
+    let Backend2FrontendMsg::State(gs) = resp else {
+        bail!("unexpected response {resp:?}");
+    };
+    let order: Vec<usize> = gs.players.iter().map(|p| p.id.0).collect();
+    // P0 and P2 keep their seats, the others fill the free ones in order
+    assert_eq!(order, vec![2, 1, 3, 0, 4]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn conflicting_or_missing_seats_are_refused() -> Result<()> {
+    let state = AppState::default();
+    let resp = new_game(&state, players(&[Some(1), Some(1), None])).await;
+    assert!(
+        matches!(resp, Backend2FrontendMsg::Error(ref e) if e.contains("both asked for seat 1")),
+        "got {resp:?}"
+    );
+
+    let resp = new_game(&state, players(&[Some(2), None])).await;
+    assert!(
+        matches!(resp, Backend2FrontendMsg::Error(ref e) if e.contains("no such seat")),
+        "got {resp:?}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn only_late_joiners_post_a_blind_to_play() -> Result<()> {
+    let state = AppState::default();
+    let resp = new_game(&state, players(&[None, None, None])).await;
+    let Backend2FrontendMsg::State(gs) = resp else {
+        bail!("unexpected response {resp:?}");
+    };
+    assert!(gs.players.iter().all(|p| !p.must_post_blind));
+
+    // Posted out of turn, so the game rather than the turn order refuses it
+    let waiting = gs.players.iter().find(|p| p.id != gs.to_act).map(|p| p.id);
+    let Some(player_id) = waiting else {
+        bail!("only one player seated");
+    };
+    let post = Frontend2BackendMsg::Action {
+        table_id: DEFAULT_TABLE_ID,
+        player_id,
+        action: PlayerAction::PostBlind,
+    };
+    let resp = dispatch_client_message(&state, DEFAULT_TABLE_ID, post).await;
+    assert!(
+        matches!(resp, Backend2FrontendMsg::Error(ref e) if e.contains("between hands")),
+        "got {resp:?}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn seats_are_taken_between_hands_only() -> Result<()> {
+    let state = AppState::default();
+    let resp = new_game(&state, players(&[None, None])).await;
+    let Backend2FrontendMsg::State(gs) = resp else {
+        bail!("unexpected response {resp:?}");
+    };
+    let take_seat = |id: usize| Frontend2BackendMsg::TakeSeat {
+        player: PlayerConfig {
+            id: PlayerId(id),
+            name: format!("P{id}"),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        },
+    };
+
+    let resp = dispatch_client_message(&state, DEFAULT_TABLE_ID, take_seat(2)).await;
+    assert!(
+        matches!(resp, Backend2FrontendMsg::Error(ref e) if e.contains("between hands")),
+        "got {resp:?}"
+    );
+
+    let fold = Frontend2BackendMsg::Action {
+        table_id: DEFAULT_TABLE_ID,
+        player_id: gs.to_act,
+        action: PlayerAction::Fold,
+    };
+    dispatch_client_message(&state, DEFAULT_TABLE_ID, fold).await;
+    let resp = dispatch_client_message(&state, DEFAULT_TABLE_ID, take_seat(0)).await;
+    assert!(
+        matches!(resp, Backend2FrontendMsg::Error(ref e) if e.contains("already seated")),
+        "got {resp:?}"
+    );
+    Ok(())
+}
@@ -0,0 +1,83 @@
+//! Tests for the seat check of websocket and iroh client sessions
+
+use mcg_shared::{Backend2FrontendMsg, Frontend2BackendMsg, PlayerAction, PlayerId};
+use native_mcg::server::session::ClientSession;
+
+const fn action(player_id: PlayerId) -> Frontend2BackendMsg {
+    Frontend2BackendMsg::Action {
+        table_id: 0,
+        player_id,
+        action: PlayerAction::CheckCall,
+    }
+}
+
+fn is_unauthorized(result: Result<(), Box<Backend2FrontendMsg>>) -> bool {
+    matches!(result.map_err(|e| *e), Err(Backend2FrontendMsg::Error(e)) if e == "Unauthorized player_id")
+}
+
+#[test]
+fn first_claimed_player_becomes_the_seat() {
+    let mut session = ClientSession::default();
+    assert_eq!(session.you_id(), None);
+    // Messages without a player do not register a seat
+    assert!(session.authorize(&Frontend2BackendMsg::Ping).is_ok());
+    assert_eq!(session.you_id(), None);
+
+    let next_hand = Frontend2BackendMsg::NextHand {
+        player_id: PlayerId(2),
+    };
+    assert!(session.authorize(&next_hand).is_ok());
+    assert_eq!(session.you_id(), Some(PlayerId(2)));
+    assert!(session.authorize(&action(PlayerId(2))).is_ok());
+}
+
+#[test]
+fn other_players_are_unauthorized() {
+    let mut session = ClientSession::default();
+    assert!(session.authorize(&action(PlayerId(0))).is_ok());
+    assert!(is_unauthorized(session.authorize(&action(PlayerId(1)))));
+    let request = Frontend2BackendMsg::RequestState {
+        player_id: PlayerId(1),
+    };
+    assert!(is_unauthorized(session.authorize(&request)));
+    let new_game = Frontend2BackendMsg::NewGame {
+        player_id: PlayerId(1),
+        players: Vec::new(),
+    };
+    assert!(is_unauthorized(session.authorize(&new_game)));
+    let rebuy = Frontend2BackendMsg::Rebuy {
+        player_id: PlayerId(1),
+        amount: 500,
+    };
+    assert!(is_unauthorized(session.authorize(&rebuy)));
+    assert_eq!(session.you_id(), Some(PlayerId(0)));
+
+    // Kicking names the target, not the sender
+    let kick = Frontend2BackendMsg::AdminKick {
+        player_id: PlayerId(1),
+        token: "secret".into(),
+    };
+    assert!(session.authorize(&kick).is_ok());
+}
+
+#[test]
+fn leaving_the_seat_allows_a_new_one() {
+    let mut session = ClientSession::default();
+    assert!(session.authorize(&action(PlayerId(0))).is_ok());
+    session.leave_seat();
+    assert!(session.authorize(&action(PlayerId(1))).is_ok());
+    assert_eq!(session.you_id(), Some(PlayerId(1)));
+}
+
+#[test]
+fn re_sent_messages_are_delivered_once() {
+    let mut session = ClientSession::default();
+    // Message 1 overtakes message 0, then both arrive again
+    assert!(session.first_delivery(1));
+    assert!(session.first_delivery(0));
+    assert!(!session.first_delivery(0));
+    assert!(!session.first_delivery(1));
+    assert!(session.first_delivery(3));
+    assert!(!session.first_delivery(3));
+    assert!(session.first_delivery(2));
+}
@@ -0,0 +1,105 @@
+//! Tests for sending game states as diffs over a connection
+
+use anyhow::Result;
+use mcg_shared::{Backend2FrontendMsg, Card, CardRank, CardSuit, PlayerAction, PlayerId, Stage};
+use native_mcg::game::{Game, Player};
+use native_mcg::transport::{StateDiffer, FULL_STATE_INTERVAL};
+
+fn create_test_players(count: usize) -> Vec<Player> {
+    (0..count)
+        .map(|i| Player {
+            id: mcg_shared::PlayerId(i),
+            name: format!("Player {i}"),
+            stack: 1000,
+            cards: [
+                Card::new(CardRank::Two, CardSuit::Clubs),
+                Card::new(CardRank::Three, CardSuit::Clubs),
+            ],
+            has_folded: false,
+            all_in: false,
+        })
+        .collect()
+}
+
+fn serialized_len(msg: &Backend2FrontendMsg) -> usize {
+    serde_json::to_string(msg).map_or(0, |s| s.len())
+}
+
+#[test]
+fn diffs_rebuild_the_state_and_are_smaller_than_full_states() -> Result<()> {
+    let mut game = Game::with_players(create_test_players(6))?;
+    let mut differ = StateDiffer::default();
+    let mut client_state = None;
+    let (mut diff_bytes, mut full_bytes, mut diffs) = (0, 0, 0);
+
+    // Everyone calls or checks until the hand reaches showdown
+    for _ in 0..40 {
+        if game.stage == Stage::Showdown {
+            break;
+        }
+        game.apply_player_action(game.to_act, PlayerAction::CheckCall)?;
+
+        let gs = game.public_for(PlayerId(0));
+        let full = Backend2FrontendMsg::State(gs.clone());
+        let sent = differ.encode(full.clone(), Some(PlayerId(0)));
+        match &sent {
+            Backend2FrontendMsg::State(full_state) => client_state = Some(full_state.clone()),
+            Backend2FrontendMsg::StateDiff(diff) => {
+                let base = client_state.as_mut().expect("diff before first full state");
+                diff.apply_to(base);
+                diff_bytes += serialized_len(&sent);
+                full_bytes += serialized_len(&full);
+                diffs += 1;
+            }
+            other => panic!("unexpected message {other:?}"),
+        }
+        assert_eq!(client_state.as_ref(), Some(&gs));
+    }
+
+    assert!(diffs > 0);
+    assert!(
+        diff_bytes * 2 < full_bytes,
+        "{diffs} diffs: {diff_bytes} bytes, as full states: {full_bytes} bytes"
+    );
+    Ok(())
+}
+
+#[test]
+fn full_state_is_resent_periodically_and_after_joining() -> Result<()> {
+    let game = Game::with_players(create_test_players(2))?;
+    let mut differ = StateDiffer::default();
+    let state = || Backend2FrontendMsg::State(game.public());
+
+    let kinds: Vec<bool> = (0..2 * FULL_STATE_INTERVAL)
+        .map(|_| matches!(differ.encode(state(), None), Backend2FrontendMsg::State(_)))
+        .collect();
+    let full_at: Vec<usize> = (0..kinds.len()).filter(|&i| kinds[i]).collect();
+    assert_eq!(full_at, vec![0, FULL_STATE_INTERVAL as usize]);
+
+    differ.encode(Backend2FrontendMsg::TableJoined(1), None);
+    assert!(matches!(
+        differ.encode(state(), None),
+        Backend2FrontendMsg::State(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn connections_only_see_their_own_hole_cards() -> Result<()> {
+    let game = Game::with_players(create_test_players(3))?;
+    let state = || Backend2FrontendMsg::State(game.public());
+
+    let Backend2FrontendMsg::State(seen) =
+        StateDiffer::default().encode(state(), Some(PlayerId(1)))
+    else {
+        panic!("first state is sent in full");
+    };
+    let shown: Vec<bool> = seen.players.iter().map(|p| p.cards.is_some()).collect();
+    assert_eq!(shown, vec![false, true, false]);
+
+    let Backend2FrontendMsg::State(spectated) = StateDiffer::default().encode(state(), None) else {
+        panic!("first state is sent in full");
+    };
+    assert!(spectated.players.iter().all(|p| p.cards.is_none()));
+    Ok(())
+}
@@ -0,0 +1,186 @@
+//! Tests for hosting several concurrent tables on one server
+
+use anyhow::{bail, Result};
+use mcg_shared::{
+    Backend2FrontendMsg, BettingLimit, Frontend2BackendMsg, PlayerAction, PlayerConfig, PlayerId,
+    TableConfig, TableId, DEFAULT_TABLE_ID,
+};
+use native_mcg::server::{dispatch_client_message, AppState};
+
+fn players(names: &[&str]) -> Vec<PlayerConfig> {
+    names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| PlayerConfig {
+            id: PlayerId(i),
+            name: name.to_string(),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        })
+        .collect()
+}
+
+async fn create_table(state: &AppState, config: TableConfig) -> Result<TableId> {
+    let msg = Frontend2BackendMsg::CreateTable { config };
+    match dispatch_client_message(state, DEFAULT_TABLE_ID, msg).await {
+        Backend2FrontendMsg::TableJoined(id) => Ok(id),
+        other => bail!("unexpected response {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn tables_are_listed_with_player_count_and_blinds() -> Result<()> {
+    let state = AppState::default();
+    let first = create_table(&state, TableConfig::new(players(&["Alice", "Bob"]))).await?;
+    let second = create_table(
+        &state,
+        TableConfig {
+            players: players(&["Carol", "Dave", "Erin"]),
+            sb: 25,
+            bb: 50,
+            bounty: None,
+            betting_limit: BettingLimit::NoLimit,
+        },
+    )
+    .await?;
+    assert_ne!(first, second);
+
+    let resp =
+        dispatch_client_message(&state, DEFAULT_TABLE_ID, Frontend2BackendMsg::ListTables).await;
+    let Backend2FrontendMsg::Tables(tables) = resp else {
+        bail!("expected table list, got {resp:?}");
+    };
+    // The default table has no game yet and is not listed
+    assert_eq!(tables.len(), 2);
+    assert_eq!((tables[0].table_id, tables[0].player_count), (first, 2));
+    assert_eq!((tables[0].sb, tables[0].bb), (5, 10));
+    assert_eq!((tables[1].table_id, tables[1].player_count), (second, 3));
+    assert_eq!((tables[1].sb, tables[1].bb), (25, 50));
+    Ok(())
+}
+
+#[tokio::test]
+async fn custom_blinds_are_posted_in_the_first_hand() -> Result<()> {
+    let state = AppState::default();
+    let table = create_table(
+        &state,
+        TableConfig {
+            players: players(&["Alice", "Bob", "Carol"]),
+            sb: 25,
+            bb: 50,
+            bounty: None,
+            betting_limit: BettingLimit::PotLimit,
+        },
+    )
+    .await?;
+
+    let Some(gs) = native_mcg::server::current_state_public(&state, table).await else {
+        bail!("table {table} has no game");
+    };
+    assert_eq!(gs.hand_number, 1);
+    assert_eq!(gs.betting_limit, BettingLimit::PotLimit);
+    assert_eq!(gs.pot, 25 + 50);
+    let chips: u32 = gs.players.iter().map(|p| p.stack).sum::<u32>() + gs.pot;
+    assert_eq!(chips, 3 * 1000);
+    Ok(())
+}
+
+#[tokio::test]
+async fn actions_and_broadcasts_are_scoped_to_their_table() -> Result<()> {
+    let state = AppState::default();
+    let first = create_table(&state, TableConfig::new(players(&["Alice", "Bob"]))).await?;
+    let second = create_table(&state, TableConfig::new(players(&["Carol", "Dave"]))).await?;
+    let mut rx = state.broadcaster.subscribe();
+
+    let before = native_mcg::server::current_state_public(&state, second).await;
+    let to_act = native_mcg::server::current_state_public(&state, first)
+        .await
+        .map(|gs| gs.to_act);
+    let Some(player_id) = to_act else {
+        bail!("table {first} has no game");
+    };
+
+    let resp = dispatch_client_message(
+        &state,
+        first,
+        Frontend2BackendMsg::Action {
+            table_id: first,
+            player_id,
+            action: PlayerAction::Fold,
+        },
+    )
+    .await;
+    assert!(matches!(resp, Backend2FrontendMsg::State(_)));
+
+    let (table_id, msg) = rx.try_recv()?;
+    assert_eq!(table_id, first);
+    assert!(matches!(msg, Backend2FrontendMsg::State(_)));
+
+    // The other table is untouched
+    let after = native_mcg::server::current_state_public(&state, second).await;
+    assert_eq!(
+        before.map(|gs| gs.action_log.len()),
+        after.map(|gs| gs.action_log.len())
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn joining_unknown_table_fails() {
+    let state = AppState::default();
+    let resp = dispatch_client_message(
+        &state,
+        DEFAULT_TABLE_ID,
+        Frontend2BackendMsg::JoinTable { table_id: 42 },
+    )
+    .await;
+    assert!(matches!(resp, Backend2FrontendMsg::Error(_)));
+}
+
+#[tokio::test]
+async fn avatar_colors_are_visible_in_public_state() -> Result<()> {
+    let state = AppState::default();
+    let mut seats = players(&["Alice", "Bob"]);
+    seats[0].avatar_color = Some([200, 40, 40]);
+    let table_id = create_table(&state, TableConfig::new(seats)).await?;
+
+    let Some(gs) = native_mcg::server::current_state_public(&state, table_id).await else {
+        bail!("table {table_id} has no game");
+    };
+    assert_eq!(gs.players[0].avatar_color, Some([200, 40, 40]));
+    assert_eq!(gs.players[1].avatar_color, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn knockout_tables_put_a_bounty_on_every_player() -> Result<()> {
+    let state = AppState::default();
+    let config = TableConfig {
+        bounty: Some(100),
+        ..TableConfig::new(players(&["Alice", "Bob"]))
+    };
+    let table_id = create_table(&state, config).await?;
+
+    let Some(gs) = native_mcg::server::current_state_public(&state, table_id).await else {
+        bail!("table {table_id} has no game");
+    };
+    assert!(gs.players.iter().all(|p| p.bounty == Some(100)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn server_ad_counts_players_across_tables() -> Result<()> {
+    let state = AppState::default();
+    create_table(&state, TableConfig::new(players(&["Alice", "Bob"]))).await?;
+    create_table(
+        &state,
+        TableConfig::new(players(&["Carol", "Dave", "Erin"])),
+    )
+    .await?;
+
+    let ad = native_mcg::server::discovery::server_ad(&state, 3000).await;
+    assert_eq!((ad.port, ad.players, ad.max), (3000, 5, 9));
+    assert_eq!(ad.name, "MCG Server");
+    Ok(())
+}
@@ -0,0 +1,97 @@
+//! Tests for the tournament bracket reported to clients
+
+use anyhow::{bail, Result};
+use mcg_shared::{
+    Backend2FrontendMsg, BettingLimit, Frontend2BackendMsg, PlayerAction, PlayerConfig, PlayerId,
+    TableConfig, TableId, DEFAULT_TABLE_ID,
+};
+use native_mcg::server::{current_state_public, dispatch_client_message, AppState};
+
+async fn open_table(state: &AppState, names: &[&str]) -> Result<TableId> {
+    let players = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| PlayerConfig {
+            id: PlayerId(i),
+            name: (*name).to_string(),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        })
+        .collect();
+    let config = TableConfig {
+        players,
+        sb: 5,
+        bb: 10,
+        bounty: None,
+        betting_limit: BettingLimit::NoLimit,
+    };
+    let msg = Frontend2BackendMsg::CreateTable { config };
+    match dispatch_client_message(state, DEFAULT_TABLE_ID, msg).await {
+        Backend2FrontendMsg::TableJoined(id) => Ok(id),
+        other => bail!("unexpected response {other:?}"),
+    }
+}
+
+/// Fold the player to act, ending the hand of a heads-up table.
+async fn fold_hand(state: &AppState, table_id: TableId) -> Result<()> {
+    let Some(gs) = current_state_public(state, table_id).await else {
+        bail!("table {table_id} has no game");
+    };
+    let fold = Frontend2BackendMsg::Action {
+        table_id,
+        player_id: gs.to_act,
+        action: PlayerAction::Fold,
+    };
+    dispatch_client_message(state, table_id, fold).await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn finished_hands_fill_the_bracket() -> Result<()> {
+    let state = AppState::default();
+    let first = open_table(&state, &["Alice", "Bob"]).await?;
+    let second = open_table(&state, &["Carol", "Dave"]).await?;
+    let mut receiver = state.broadcaster.subscribe();
+
+    fold_hand(&state, first).await?;
+
+    // Every table hears of the new bracket, including the default lobby
+    let mut told = Vec::new();
+    while let Ok((table_id, msg)) = receiver.try_recv() {
+        if let Backend2FrontendMsg::TournamentState(t) = msg {
+            assert_eq!(t.rounds.len(), 1);
+            told.push(table_id);
+        }
+    }
+    assert_eq!(told, vec![DEFAULT_TABLE_ID, first, second]);
+
+    let resp =
+        dispatch_client_message(&state, DEFAULT_TABLE_ID, Frontend2BackendMsg::RequestTournament)
+            .await;
+    let Backend2FrontendMsg::TournamentState(tournament) = resp else {
+        bail!("unexpected response {resp:?}");
+    };
+    let round = &tournament.rounds[0];
+    assert_eq!(round.round, 1);
+    let tables: Vec<TableId> = round.tables.iter().map(|t| t.table_id).collect();
+    assert_eq!(tables, vec![first, second]);
+    let stacks: u32 = round.tables[0].players.iter().map(|p| p.stack).sum();
+    assert_eq!(stacks, 2000);
+    assert_eq!(tournament.remaining_players(), 4);
+    Ok(())
+}
+
+#[tokio::test]
+async fn no_bracket_before_the_first_hand_ends() -> Result<()> {
+    let state = AppState::default();
+    open_table(&state, &["Alice", "Bob"]).await?;
+    let resp =
+        dispatch_client_message(&state, DEFAULT_TABLE_ID, Frontend2BackendMsg::RequestTournament)
+            .await;
+    let Backend2FrontendMsg::TournamentState(tournament) = resp else {
+        bail!("unexpected response {resp:?}");
+    };
+    assert!(tournament.rounds.is_empty());
+    Ok(())
+}
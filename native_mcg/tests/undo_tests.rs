@@ -0,0 +1,59 @@
+//! Tests for taking back player actions (`undo` feature)
+
+use anyhow::{bail, Result};
+use mcg_shared::{
+    Backend2FrontendMsg, BettingLimit, Frontend2BackendMsg, PlayerAction, PlayerConfig, PlayerId,
+    TableConfig, DEFAULT_TABLE_ID,
+};
+use native_mcg::server::{current_state_public, dispatch_client_message, AppState};
+
+#[tokio::test]
+async fn undo_action_restores_the_table() -> Result<()> {
+    let state = AppState::default();
+    let players = ["Alice", "Bob", "Carol"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| PlayerConfig {
+            id: PlayerId(i),
+            name: name.to_string(),
+            is_bot: false,
+            avatar_color: None,
+            preferred_seat: None,
+        })
+        .collect();
+    let config = TableConfig {
+        players,
+        sb: 5,
+        bb: 10,
+        bounty: None,
+        betting_limit: BettingLimit::NoLimit,
+    };
+    let msg = Frontend2BackendMsg::CreateTable { config };
+    let table_id = match dispatch_client_message(&state, DEFAULT_TABLE_ID, msg).await {
+        Backend2FrontendMsg::TableJoined(id) => id,
+        other => bail!("unexpected response {other:?}"),
+    };
+    let Some(before) = current_state_public(&state, table_id).await else {
+        bail!("table {table_id} has no game");
+    };
+
+    let call = Frontend2BackendMsg::Action {
+        table_id,
+        player_id: before.to_act,
+        action: PlayerAction::CheckCall,
+    };
+    dispatch_client_message(&state, table_id, call).await;
+
+    let resp = dispatch_client_message(&state, table_id, Frontend2BackendMsg::UndoAction).await;
+    let Backend2FrontendMsg::State(after) = resp else {
+        bail!("expected state after undo, got {resp:?}");
+    };
+    assert_eq!(
+        serde_json::to_value(&after)?,
+        serde_json::to_value(&before)?
+    );
+
+    let resp = dispatch_client_message(&state, table_id, Frontend2BackendMsg::UndoAction).await;
+    assert!(matches!(resp, Backend2FrontendMsg::Error(e) if e == "Nothing to undo"));
+    Ok(())
+}
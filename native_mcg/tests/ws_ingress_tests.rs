@@ -0,0 +1,52 @@
+//! Tests for the validation of client messages arriving over the websocket
+
+use mcg_shared::Frontend2BackendMsg;
+use native_mcg::server::ws::{parse_client_msg, parse_sequenced_client_msg};
+use proptest::prelude::*;
+
+#[test]
+fn well_formed_messages_are_decoded() {
+    assert!(matches!(
+        parse_client_msg(r#"{"type":"Ping"}"#),
+        Ok(Frontend2BackendMsg::Ping)
+    ));
+    assert!(matches!(
+        parse_client_msg(r#"{"type":"JoinTable","data":{"table_id":3}}"#),
+        Ok(Frontend2BackendMsg::JoinTable { table_id: 3 })
+    ));
+}
+
+#[test]
+fn malformed_messages_name_the_problem() {
+    let err = |txt: &str| parse_client_msg(txt).unwrap_err();
+    assert!(err("{not json").contains("invalid JSON"));
+    assert!(err("[1, 2]").contains("expected a JSON object"));
+    assert!(err(r#"{"data":{}}"#).contains("missing \"type\""));
+    assert!(err(r#"{"type":"JoinTable"}"#).contains("missing \"data\" field for JoinTable"));
+    assert!(err(r#"{"type":"JoinTable","data":{"table_id":"x"}}"#).contains("invalid JoinTable"));
+    assert!(err(r#"{"type":"Nope"}"#).contains("invalid Nope"));
+}
+
+#[test]
+fn sequence_numbers_are_read_even_from_malformed_messages() {
+    let (seq, msg) = parse_sequenced_client_msg(r#"{"seq":4,"type":"Ping"}"#);
+    assert_eq!(seq, Some(4));
+    assert!(matches!(msg, Ok(Frontend2BackendMsg::Ping)));
+    let (seq, msg) = parse_sequenced_client_msg(r#"{"seq":5,"type":"Nope"}"#);
+    assert_eq!(seq, Some(5));
+    assert!(msg.unwrap_err().contains("invalid Nope"));
+    assert_eq!(parse_sequenced_client_msg(r#"{"type":"Ping"}"#).0, None);
+}
+
+proptest! {
+    #[test]
+    fn random_bytes_never_panic(bytes in proptest::collection::vec(any::<u8>(), 0..512)) {
+        let _ = parse_client_msg(&String::from_utf8_lossy(&bytes));
+    }
+
+    #[test]
+    fn random_envelopes_never_panic(kind in "[A-Za-z]{0,16}", data in "\\PC{0,64}") {
+        let txt = format!(r#"{{"type":"{kind}","data":{data}}}"#);
+        let _ = parse_client_msg(&txt);
+    }
+}
@@ -0,0 +1,21 @@
+#!/usr/bin/env bash
+# Fail if the WASM bundle exceeds its size budget.
+# Usage: scripts/check-wasm-size.sh [WASM_FILE]
+# WASM_FILE defaults to pkg/frontend_bg.wasm; the budget can be changed with
+# WASM_SIZE_BUDGET (bytes, default 5 MiB).
+set -euo pipefail
+
+wasm="${1:-pkg/frontend_bg.wasm}"
+budget="${WASM_SIZE_BUDGET:-$((5 * 1024 * 1024))}"
+
+if [[ ! -f "$wasm" ]]; then
+  echo "error: $wasm not found, build it with \`just build\` or \`just minify-wasm\`" >&2
+  exit 2
+fi
+
+size=$(wc -c < "$wasm" | tr -d ' ')
+echo "$wasm: $size bytes (budget $budget bytes)"
+if (( size > budget )); then
+  echo "error: WASM bundle is $((size - budget)) bytes over budget" >&2
+  exit 1
+fi
@@ -0,0 +1,55 @@
+//! Rating of random 7-card hands, by table lookup and by analysis.
+//!
+//! Each iteration rates the same 1,000,000 hands. The lookup table is built
+//! before measuring, as a server builds it once for its whole lifetime.
+//!
+//! Usage:
+//!   cargo bench -p `mcg-shared` --bench `hand_evaluation`
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use mcg_shared::evaluation::{evaluate_best_hand, evaluate_by_analysis};
+use mcg_shared::Card;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+const HANDS: usize = 1_000_000;
+
+fn random_hands() -> Vec<([Card; 2], [Card; 5])> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut deck: Vec<Card> = (0..52).map(Card).collect();
+    (0..HANDS)
+        .map(|_| {
+            let (dealt, _) = deck.partial_shuffle(&mut rng, 7);
+            (
+                [dealt[0], dealt[1]],
+                [dealt[2], dealt[3], dealt[4], dealt[5], dealt[6]],
+            )
+        })
+        .collect()
+}
+
+fn hand_evaluation(c: &mut Criterion) {
+    let hands = random_hands();
+    let _ = evaluate_best_hand(hands[0].0, &hands[0].1);
+    let mut group = c.benchmark_group("hand_evaluation");
+    group.sample_size(10);
+    group.bench_function("lookup", |b| {
+        b.iter(|| {
+            for (hole, board) in &hands {
+                black_box(evaluate_best_hand(*hole, board));
+            }
+        });
+    });
+    group.bench_function("analysis", |b| {
+        b.iter(|| {
+            for (hole, board) in &hands {
+                black_box(evaluate_by_analysis(*hole, board));
+            }
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, hand_evaluation);
+criterion_main!(benches);
@@ -1,7 +1,9 @@
 //! Card-related types and constants for the Mental Card Game.
 
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::fmt;
+use std::str::FromStr;
 
 /// Card rank values (0=Ace, 1=2, ..., 12=King)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -22,30 +24,57 @@ pub enum CardRank {
 }
 
 impl CardRank {
-    /// Convert from u8 to CardRank. Panics if value > 12.
+    /// Convert from u8 to `CardRank`.
+    ///
+    /// # Panics
+    ///
+    /// If `value` is greater than 12.
+    #[must_use]
     pub fn from_u8(value: u8) -> Self {
         match value {
-            0 => CardRank::Ace,
-            1 => CardRank::Two,
-            2 => CardRank::Three,
-            3 => CardRank::Four,
-            4 => CardRank::Five,
-            5 => CardRank::Six,
-            6 => CardRank::Seven,
-            7 => CardRank::Eight,
-            8 => CardRank::Nine,
-            9 => CardRank::Ten,
-            10 => CardRank::Jack,
-            11 => CardRank::Queen,
-            12 => CardRank::King,
-            _ => panic!("Invalid card rank: {}", value),
+            0 => Self::Ace,
+            1 => Self::Two,
+            2 => Self::Three,
+            3 => Self::Four,
+            4 => Self::Five,
+            5 => Self::Six,
+            6 => Self::Seven,
+            7 => Self::Eight,
+            8 => Self::Nine,
+            9 => Self::Ten,
+            10 => Self::Jack,
+            11 => Self::Queen,
+            12 => Self::King,
+            _ => panic!("Invalid card rank: {value}"),
         }
     }
 
     /// Convert to usize for array indexing.
-    pub fn as_usize(self) -> usize {
+    #[must_use]
+    pub const fn as_usize(self) -> usize {
         self as usize
     }
+
+    /// Value in high hands, from Two=2 up to Ace=14.
+    #[must_use]
+    pub const fn high_card_value(self) -> u8 {
+        match self {
+            Self::Ace => 14,
+            other => other as u8 + 1,
+        }
+    }
+
+    /// Value in low hands, where the Ace plays low: Ace=1 up to King=13.
+    #[must_use]
+    pub const fn low_card_value(self) -> u8 {
+        self as u8 + 1
+    }
+
+    /// Whether the rank can be part of an eight-or-better low, Ace through Eight.
+    #[must_use]
+    pub const fn can_be_low(self) -> bool {
+        self.low_card_value() <= 8
+    }
 }
 
 /// Card suit values (0=Clubs, 1=Diamonds, 2=Hearts, 3=Spades)
@@ -58,19 +87,25 @@ pub enum CardSuit {
 }
 
 impl CardSuit {
-    /// Convert from u8 to CardSuit. Panics if value > 3.
+    /// Convert from u8 to `CardSuit`.
+    ///
+    /// # Panics
+    ///
+    /// If `value` is greater than 3.
+    #[must_use]
     pub fn from_u8(value: u8) -> Self {
         match value {
-            0 => CardSuit::Clubs,
-            1 => CardSuit::Diamonds,
-            2 => CardSuit::Hearts,
-            3 => CardSuit::Spades,
-            _ => panic!("Invalid card suit: {}", value),
+            0 => Self::Clubs,
+            1 => Self::Diamonds,
+            2 => Self::Hearts,
+            3 => Self::Spades,
+            _ => panic!("Invalid card suit: {value}"),
         }
     }
 
     /// Convert to usize for array indexing.
-    pub fn as_usize(self) -> usize {
+    #[must_use]
+    pub const fn as_usize(self) -> usize {
         self as usize
     }
 }
@@ -81,21 +116,25 @@ pub struct Card(pub u8);
 
 impl Card {
     /// Create a new card from rank and suit
-    pub fn new(rank: CardRank, suit: CardSuit) -> Self {
-        Card((suit as u8) * 13 + (rank as u8))
+    #[must_use]
+    pub const fn new(rank: CardRank, suit: CardSuit) -> Self {
+        Self((suit as u8) * 13 + (rank as u8))
     }
 
     /// Get the rank of this card
+    #[must_use]
     pub fn rank(self) -> CardRank {
         CardRank::from_u8(self.0 % 13)
     }
 
     /// Get the suit of this card
+    #[must_use]
     pub fn suit(self) -> CardSuit {
         CardSuit::from_u8(self.0 / 13)
     }
 
     /// Get the rank as a string (A, 2, 3, ..., K)
+    #[must_use]
     pub fn rank_str(self) -> &'static str {
         match self.rank() {
             CardRank::Ace => "A",
@@ -115,6 +154,7 @@ impl Card {
     }
 
     /// Get the suit as a character (♣, ♦, ♥, ♠)
+    #[must_use]
     pub fn suit_char(self) -> char {
         match self.suit() {
             CardSuit::Clubs => '♣',
@@ -124,21 +164,31 @@ impl Card {
         }
     }
 
-    /// Format the card as a short string like "A♣".
-    ///
-    /// Use `format!("{}", card)` (the `Display` impl) instead of calling
-    /// an inherent `to_string` method to satisfy clippy's `inherent_to_string` lint.
+    /// Get the suit as a lowercase letter (c, d, h, s)
+    #[must_use]
+    pub fn suit_letter(self) -> char {
+        match self.suit() {
+            CardSuit::Clubs => 'c',
+            CardSuit::Diamonds => 'd',
+            CardSuit::Hearts => 'h',
+            CardSuit::Spades => 's',
+        }
+    }
+
     /// Check if this is a red suit (hearts or diamonds)
+    #[must_use]
     pub fn is_red(self) -> bool {
         matches!(self.suit(), CardSuit::Hearts | CardSuit::Diamonds)
     }
 
     /// Check if this is a black suit (clubs or spades)
+    #[must_use]
     pub fn is_black(self) -> bool {
         matches!(self.suit(), CardSuit::Clubs | CardSuit::Spades)
     }
 
     /// Get the full name of the rank (Ace, Two, Three, ..., King)
+    #[must_use]
     pub fn rank_name(self) -> &'static str {
         match self.rank() {
             CardRank::Ace => "Ace",
@@ -158,6 +208,7 @@ impl Card {
     }
 
     /// Get the full name of the suit (Clubs, Diamonds, Hearts, Spades)
+    #[must_use]
     pub fn suit_name(self) -> &'static str {
         match self.suit() {
             CardSuit::Clubs => "Clubs",
@@ -168,6 +219,7 @@ impl Card {
     }
 
     /// Format the card with full details like "A♣ (Ace of Clubs)"
+    #[must_use]
     pub fn to_detailed_string(self) -> String {
         format!(
             "{}{} ({} of {})",
@@ -179,8 +231,195 @@ impl Card {
     }
 }
 
+impl Card {
+    /// Compare with the Ace as the lowest rank, below the Two.
+    ///
+    /// Ties between equal ranks are broken by suit like in the default
+    /// ace-high ordering.
+    #[must_use]
+    pub fn cmp_ace_low(&self, other: &Self) -> Ordering {
+        let key = |c: &Self| (c.rank().low_card_value(), c.suit() as u8);
+        key(self).cmp(&key(other))
+    }
+}
+
+/// Ace-high ordering: Two < Three < ... < King < Ace. Cards of equal rank are
+/// ordered by suit, Clubs < Diamonds < Hearts < Spades, so that sorting is
+/// deterministic.
+impl Ord for Card {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let key = |c: &Self| (c.rank().high_card_value(), c.suit() as u8);
+        key(self).cmp(&key(other))
+    }
+}
+
+impl PartialOrd for Card {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Cards by their index `suit * 13 + rank`, the encoding used on the wire.
+impl From<u8> for Card {
+    fn from(index: u8) -> Self {
+        Self(index)
+    }
+}
+
+impl From<Card> for u8 {
+    fn from(card: Card) -> Self {
+        card.0
+    }
+}
+
+/// Sort `cards` in place, highest card first, with the Ace high or low.
+pub fn sort_hand(cards: &mut [Card], ace_high: bool) {
+    if ace_high {
+        cards.sort_unstable_by(|a, b| b.cmp(a));
+    } else {
+        cards.sort_unstable_by(|a, b| b.cmp_ace_low(a));
+    }
+}
+
+/// Formats the card as a two-character string like "As" or "Th".
+///
+/// The alternate form (`{:#}`) uses suit symbols instead, like "A♠".
 impl fmt::Display for Card {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}{}", self.rank_str(), self.suit_char())
+        if f.alternate() {
+            write!(f, "{}{}", self.rank_str(), self.suit_char())
+        } else {
+            write!(f, "{}{}", self.rank_str(), self.suit_letter())
+        }
+    }
+}
+
+/// Error returned when a string is not a valid card
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidCardStr(pub String);
+
+impl fmt::Display for InvalidCardStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid card '{}'", self.0)
+    }
+}
+
+impl std::error::Error for InvalidCardStr {}
+
+/// Parses strings like "As", "Th" or "2c". Suit symbols ("A♠") are accepted
+/// as well so the alternate display form round-trips.
+impl FromStr for Card {
+    type Err = InvalidCardStr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidCardStr(s.to_string());
+        let mut chars = s.chars();
+        let rank = match chars.next().map(|c| c.to_ascii_uppercase()) {
+            Some('A') => CardRank::Ace,
+            Some('2') => CardRank::Two,
+            Some('3') => CardRank::Three,
+            Some('4') => CardRank::Four,
+            Some('5') => CardRank::Five,
+            Some('6') => CardRank::Six,
+            Some('7') => CardRank::Seven,
+            Some('8') => CardRank::Eight,
+            Some('9') => CardRank::Nine,
+            Some('T') => CardRank::Ten,
+            Some('J') => CardRank::Jack,
+            Some('Q') => CardRank::Queen,
+            Some('K') => CardRank::King,
+            _ => return Err(invalid()),
+        };
+        let suit = match chars.next().map(|c| c.to_ascii_lowercase()) {
+            Some('c' | '♣') => CardSuit::Clubs,
+            Some('d' | '♦') => CardSuit::Diamonds,
+            Some('h' | '♥') => CardSuit::Hearts,
+            Some('s' | '♠') => CardSuit::Spades,
+            _ => return Err(invalid()),
+        };
+        if chars.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(Self::new(rank, suit))
+    }
+}
+
+/// Parse a whitespace-separated list of cards like "Ah Kh Qh Jh Th".
+///
+/// # Errors
+///
+/// If any of the words is not a card.
+pub fn parse_cards(text: &str) -> Result<Vec<Card>, InvalidCardStr> {
+    text.split_whitespace().map(str::parse).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cards_convert_to_and_from_their_index() {
+        let card = Card::new(CardRank::Queen, CardSuit::Hearts);
+        assert_eq!(u8::from(card), 2 * 13 + 11);
+        assert_eq!(Card::from(u8::from(card)), card);
+    }
+
+    #[test]
+    fn all_cards_round_trip_through_strings() {
+        for i in 0..52 {
+            let card = Card(i);
+            assert_eq!(card.to_string().parse::<Card>(), Ok(card));
+            assert_eq!(format!("{card:#}").parse::<Card>(), Ok(card));
+        }
+    }
+
+    #[test]
+    fn cards_display_as_rank_and_suit_letter() {
+        assert_eq!(Card::new(CardRank::Ace, CardSuit::Spades).to_string(), "As");
+        assert_eq!(Card::new(CardRank::Ten, CardSuit::Hearts).to_string(), "Th");
+        assert_eq!(Card::new(CardRank::Two, CardSuit::Clubs).to_string(), "2c");
+    }
+
+    #[test]
+    fn cards_order_ace_high_with_suit_as_tiebreaker() {
+        let card = |s: &str| s.parse::<Card>().unwrap();
+        assert!(card("As") > card("Kc"));
+        assert!(card("3c") > card("2s"));
+        assert!(card("Ts") > card("Th"));
+        assert!(card("Th") > card("Td"));
+        assert!(card("Td") > card("Tc"));
+        assert_eq!(card("Ah").cmp_ace_low(&card("2c")), Ordering::Less);
+        assert_eq!(card("Ah").cmp_ace_low(&card("Ac")), Ordering::Greater);
+
+        let mut hand = ["5d", "Ah", "Kc", "2s", "5s"].map(card);
+        sort_hand(&mut hand, true);
+        assert_eq!(hand, ["Ah", "Kc", "5s", "5d", "2s"].map(card));
+        sort_hand(&mut hand, false);
+        assert_eq!(hand, ["Kc", "5s", "5d", "2s", "Ah"].map(card));
+    }
+
+    #[test]
+    fn ranks_value_the_ace_high_or_low() {
+        assert_eq!(CardRank::Ace.high_card_value(), 14);
+        assert_eq!(CardRank::King.high_card_value(), 13);
+        assert_eq!(CardRank::Two.high_card_value(), 2);
+        assert_eq!(CardRank::Ace.low_card_value(), 1);
+        assert_eq!(CardRank::King.low_card_value(), 13);
+        let lows: Vec<CardRank> = (0..13)
+            .map(CardRank::from_u8)
+            .filter(|r| r.can_be_low())
+            .collect();
+        assert_eq!(lows.first(), Some(&CardRank::Ace));
+        assert_eq!(lows.last(), Some(&CardRank::Eight));
+        assert_eq!(lows.len(), 8);
+    }
+
+    #[test]
+    fn invalid_card_strings_are_rejected() {
+        for s in ["", "A", "1s", "Ax", "Asd", "10h"] {
+            assert_eq!(s.parse::<Card>(), Err(InvalidCardStr(s.to_string())));
+        }
+        assert!(parse_cards("Ah Kh Zz").is_err());
+        assert_eq!(parse_cards("Ah Kh Qh Jh Th").map(|c| c.len()), Ok(5));
     }
 }
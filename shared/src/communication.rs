@@ -20,6 +20,7 @@ pub struct ModularElement {
 
 impl ModularElement {
     /// Create a new element in Z/pZ
+    #[must_use]
     pub fn new(value: BigUint, modulus: BigUint) -> Self {
         let normalized_value = value % &modulus;
         Self {
@@ -46,6 +47,11 @@ pub struct ElgamalCiphertext {
 
 impl ElgamalCiphertext {
     /// Create a new Elgamal ciphertext
+    ///
+    /// # Panics
+    ///
+    /// If the elements have different moduli.
+    #[must_use]
     pub fn new(c1: ModularElement, c2: ModularElement) -> Self {
         // Ensure both elements have the same modulus
         assert_eq!(
@@ -59,14 +65,19 @@ impl ElgamalCiphertext {
 /// A bitstring with maximum length of |p| bits
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct BitString {
-    /// The actual bits stored as a BigUint
+    /// The actual bits stored as a `BigUint`
     pub bits: BigUint,
     /// The length of the bitstring
     pub length: usize,
 }
 
 impl BitString {
-    /// Create a new bitstring from a BigUint and a length
+    /// Create a new bitstring from a `BigUint` and a length
+    ///
+    /// # Panics
+    ///
+    /// If `length` exceeds `MAX_PRIME_BIT_LENGTH`.
+    #[must_use]
     pub fn new(bits: BigUint, length: usize) -> Self {
         assert!(
             length <= MAX_PRIME_BIT_LENGTH,
@@ -76,6 +87,11 @@ impl BitString {
     }
 
     /// Check if a specific bit is set
+    ///
+    /// # Panics
+    ///
+    /// If `position` is not below the length.
+    #[must_use]
     pub fn get_bit(&self, position: usize) -> bool {
         assert!(position < self.length, "Bit position out of range");
         let mask = BigUint::from(1u32) << position;
@@ -94,7 +110,8 @@ pub struct CardDeck {
 
 impl CardDeck {
     /// Create a new card deck with the given modulus
-    pub fn new(modulus: BigUint) -> Self {
+    #[must_use]
+    pub const fn new(modulus: BigUint) -> Self {
         Self {
             cards: Vec::new(),
             modulus,
@@ -102,6 +119,10 @@ impl CardDeck {
     }
 
     /// Add a card to the deck
+    ///
+    /// # Panics
+    ///
+    /// If the card does not use the modulus of the deck.
     pub fn add_card(&mut self, card: ElgamalCiphertext) {
         assert_eq!(
             card.c1.modulus, self.modulus,
@@ -111,7 +132,8 @@ impl CardDeck {
     }
 
     /// Get the number of cards in the deck
-    pub fn size(&self) -> usize {
+    #[must_use]
+    pub const fn size(&self) -> usize {
         self.cards.len()
     }
 }
@@ -153,7 +175,8 @@ pub struct CommunicationPacket {
 
 impl CommunicationPacket {
     /// Create a new direct player-to-player communication
-    pub fn direct(sender: usize, recipient: usize, message: GameMessage) -> Self {
+    #[must_use]
+    pub const fn direct(sender: usize, recipient: usize, message: GameMessage) -> Self {
         Self {
             mode: CommunicationMode::DirectPlayerToPlayer,
             sender,
@@ -163,7 +186,8 @@ impl CommunicationPacket {
     }
 
     /// Create a new broadcast communication
-    pub fn broadcast(sender: usize, message: GameMessage) -> Self {
+    #[must_use]
+    pub const fn broadcast(sender: usize, message: GameMessage) -> Self {
         Self {
             mode: CommunicationMode::Broadcast,
             sender,
@@ -173,7 +197,8 @@ impl CommunicationPacket {
     }
 
     /// Create a new unicast communication
-    pub fn unicast(sender: usize, message: GameMessage) -> Self {
+    #[must_use]
+    pub const fn unicast(sender: usize, message: GameMessage) -> Self {
         Self {
             mode: CommunicationMode::Unicast,
             sender,
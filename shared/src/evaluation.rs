@@ -0,0 +1,787 @@
+//! Poker hand evaluation, shared so the server can settle showdowns and the
+//! client can rate its own hand.
+
+use crate::{sort_hand, Card, CardRank, CardSuit, HandRank, HandRankCategory, LowHandRank};
+
+mod equity;
+#[cfg(not(target_arch = "wasm32"))]
+mod lookup;
+
+pub use equity::{simulate_equity, EquityError, EquityResult};
+
+const NUM_SUITS: usize = 4;
+/// Rank values run from 2 to 14; 0 and 1 are unused
+const RANK_COUNT_ARRAY_SIZE: usize = 15;
+
+/// Evaluate the best 5-card hand from 2 hole + up to 5 community cards.
+/// Returns a `HandRank` with category and tiebreakers for comparison.
+///
+/// Complete 7-card hands are looked up in a table built on first use, except
+/// in the browser, which rates a few hands per frame and is better off
+/// without the table; fewer cards are analyzed directly.
+#[must_use]
+pub fn evaluate_best_hand(hole: [Card; 2], community: &[Card]) -> HandRank {
+    #[cfg(not(target_arch = "wasm32"))]
+    if let &[c1, c2, c3, c4, c5] = community {
+        if let Some(rank) = lookup::evaluate_seven([hole[0], hole[1], c1, c2, c3, c4, c5]) {
+            return rank;
+        }
+    }
+    evaluate_by_analysis(hole, community)
+}
+
+/// Evaluate like `evaluate_best_hand`, always analyzing the cards instead of
+/// looking them up. This is how the lookup table is filled.
+#[must_use]
+pub fn evaluate_by_analysis(hole: [Card; 2], community: &[Card]) -> HandRank {
+    let mut cards = Vec::with_capacity(7);
+    cards.push(hole[0]);
+    cards.push(hole[1]);
+    for &c in community {
+        cards.push(c);
+    }
+    best_rank_from_seven(&cards)
+}
+
+/// Compute and return the exact best 5-card combination for presentation.
+///
+/// Enumerates all 5-card combinations from the available cards (2 hole + up to 5 community),
+/// evaluates each with the same ranking logic, and returns the highest-ranked subset.
+/// If fewer than 5 cards are available (early streets), returns the highest-ranked available cards.
+#[must_use]
+pub fn pick_best_five(hole: [Card; 2], community: &[Card]) -> [Card; 5] {
+    // Build list of available cards (2 hole + up to 5 community)
+    let mut all = Vec::with_capacity(7);
+    all.push(hole[0]);
+    all.push(hole[1]);
+    for &c in community {
+        all.push(c);
+    }
+
+    // If fewer than 5 cards are available (pre-flop/early streets), just take the highest ones
+    if all.len() < 5 {
+        sort_hand(&mut all, true);
+        let mut out = [Card::new(CardRank::Ace, CardSuit::Clubs); 5];
+        let n = all.len().min(5);
+        out[..n].copy_from_slice(&all[..n]);
+        return out;
+    }
+
+    // Enumerate all 5-card combinations and select the one with the highest rank
+    let n = all.len();
+    let mut best_rank: Option<HandRank> = None;
+    let mut best_combo: [Card; 5] = [Card::new(CardRank::Ace, CardSuit::Clubs); 5];
+
+    for i in 0..(n - 4) {
+        for j in (i + 1)..(n - 3) {
+            for k in (j + 1)..(n - 2) {
+                for l in (k + 1)..(n - 1) {
+                    for m in (l + 1)..n {
+                        let subset = [all[i], all[j], all[k], all[l], all[m]];
+                        // Reuse the 7-card evaluator on exactly 5 cards
+                        let rank = best_rank_from_seven(subset.as_ref());
+                        match &best_rank {
+                            None => {
+                                best_rank = Some(rank);
+                                best_combo = subset;
+                            }
+                            Some(r) => {
+                                if rank > *r {
+                                    best_rank = Some(rank);
+                                    best_combo = subset;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    sort_hand(&mut best_combo, true);
+    best_combo
+}
+
+/// Evaluate the best qualifying Omaha low hand (eight-or-better).
+///
+/// Omaha rules apply: exactly two of the four hole cards and exactly three
+/// community cards must be used. Returns `None` if no combination yields five
+/// unpaired cards of rank 8 or lower (Ace counts low).
+#[must_use]
+pub fn evaluate_omaha_low(hole: [Card; 4], community: &[Card]) -> Option<LowHandRank> {
+    let mut best: Option<LowHandRank> = None;
+    let n = community.len();
+    if n < 3 {
+        return None;
+    }
+    for a in 0..4 {
+        for b in (a + 1)..4 {
+            for i in 0..(n - 2) {
+                for j in (i + 1)..(n - 1) {
+                    for k in (j + 1)..n {
+                        let five = [hole[a], hole[b], community[i], community[j], community[k]];
+                        if let Some(low) = low_rank_of_five(five) {
+                            if best.is_none_or(|b| low < b) {
+                                best = Some(low);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
+/// Evaluate the best qualifying low hand (eight-or-better) from 2 hole + up to
+/// 5 community cards, using any five of the available cards.
+#[must_use]
+pub fn evaluate_best_low(hole: [Card; 2], community: &[Card]) -> Option<LowHandRank> {
+    let mut values = Vec::with_capacity(7);
+    for c in hole.iter().chain(community) {
+        if c.rank().can_be_low() {
+            values.push(c.rank().low_card_value());
+        }
+    }
+    values.sort_unstable();
+    values.dedup();
+    if values.len() < 5 {
+        return None;
+    }
+    // The five lowest distinct ranks always form the best low.
+    Some(LowHandRank([
+        values[4], values[3], values[2], values[1], values[0],
+    ]))
+}
+
+// ===== Internal helpers =====
+
+/// Rank a single 5-card combination as a low hand, if it qualifies.
+fn low_rank_of_five(cards: [Card; 5]) -> Option<LowHandRank> {
+    let mut values = cards.map(|c| c.rank().low_card_value());
+    values.sort_unstable_by(|a, b| b.cmp(a));
+    let unpaired = values.windows(2).all(|w| w[0] != w[1]);
+    let qualifies = cards.iter().all(|c| c.rank().can_be_low());
+    (unpaired && qualifies).then_some(LowHandRank(values))
+}
+
+fn best_rank_from_seven(cards: &[Card]) -> HandRank {
+    let flush_suit = analyze_suits_for_flush(cards);
+
+    // Check for straight flush
+    if let Some(sflush) = check_straight_flush(cards, flush_suit) {
+        return sflush;
+    }
+
+    let (counts, all_values) = analyze_card_values(cards);
+
+    // Check hands in descending rank order
+    if let Some(four_kind) = check_four_of_a_kind(&counts, &all_values) {
+        return four_kind;
+    }
+
+    if let Some(full_house) = check_full_house(&counts) {
+        return full_house;
+    }
+
+    if let Some(flush) = check_flush(cards, flush_suit) {
+        return flush;
+    }
+
+    if let Some(straight) = check_straight(cards) {
+        return straight;
+    }
+
+    if let Some(three_kind) = check_three_of_a_kind(&counts, &all_values) {
+        return three_kind;
+    }
+
+    if let Some(two_pair) = check_two_pair(&counts, &all_values) {
+        return two_pair;
+    }
+
+    if let Some(pair) = check_one_pair(&counts, &all_values) {
+        return pair;
+    }
+
+    check_high_card(&all_values)
+}
+
+fn analyze_suits_for_flush(cards: &[Card]) -> Option<u8> {
+    // Group by suit
+    let mut suit_cards: [Vec<Card>; NUM_SUITS] = [vec![], vec![], vec![], vec![]];
+    for &c in cards {
+        suit_cards[c.suit().as_usize()].push(c);
+    }
+    // Suit presence >=5 indicates possible flush
+    (0..NUM_SUITS)
+        .find(|&s| suit_cards[s].len() >= 5)
+        .and_then(|s| u8::try_from(s).ok())
+}
+
+fn analyze_card_values(cards: &[Card]) -> ([u8; RANK_COUNT_ARRAY_SIZE], Vec<u8>) {
+    let mut counts = [0u8; RANK_COUNT_ARRAY_SIZE];
+    let mut all_values = Vec::with_capacity(cards.len());
+    for &c in cards {
+        let v = c.rank().high_card_value();
+        counts[v as usize] += 1;
+        all_values.push(v);
+    }
+    (counts, all_values)
+}
+
+fn check_straight_flush(cards: &[Card], flush_suit: Option<u8>) -> Option<HandRank> {
+    if let Some(fs) = flush_suit {
+        let mut suit_cards: [Vec<Card>; NUM_SUITS] = [vec![], vec![], vec![], vec![]];
+        for &c in cards {
+            suit_cards[c.suit().as_usize()].push(c);
+        }
+
+        let values = ranks_as_values_unique(&suit_cards[fs as usize]);
+        if let Some(high) = straight_high(&values) {
+            return Some(HandRank {
+                category: HandRankCategory::StraightFlush,
+                tiebreakers: vec![high],
+            });
+        }
+    }
+    None
+}
+
+fn check_four_of_a_kind(
+    counts: &[u8; RANK_COUNT_ARRAY_SIZE],
+    all_values: &[u8],
+) -> Option<HandRank> {
+    find_n_of_a_kind(counts, 4, all_values).map(|(quad, kicker)| HandRank {
+        category: HandRankCategory::FourKind,
+        tiebreakers: vec![quad, kicker],
+    })
+}
+
+fn check_full_house(counts: &[u8; RANK_COUNT_ARRAY_SIZE]) -> Option<HandRank> {
+    find_full_house(counts).map(|(trip, pair)| HandRank {
+        category: HandRankCategory::FullHouse,
+        tiebreakers: vec![trip, pair],
+    })
+}
+
+fn check_flush(cards: &[Card], flush_suit: Option<u8>) -> Option<HandRank> {
+    if let Some(fs) = flush_suit {
+        let mut suit_cards: [Vec<Card>; NUM_SUITS] = [vec![], vec![], vec![], vec![]];
+        for &c in cards {
+            suit_cards[c.suit().as_usize()].push(c);
+        }
+
+        let mut vs = suit_cards[fs as usize]
+            .iter()
+            .map(|&c| c.rank().high_card_value())
+            .collect::<Vec<u8>>();
+        vs.sort_unstable_by(|a, b| b.cmp(a));
+        vs.truncate(5);
+        return Some(HandRank {
+            category: HandRankCategory::Flush,
+            tiebreakers: vs,
+        });
+    }
+    None
+}
+
+fn check_straight(cards: &[Card]) -> Option<HandRank> {
+    let values = ranks_as_values_unique(cards);
+    straight_high(&values).map(|high| HandRank {
+        category: HandRankCategory::Straight,
+        tiebreakers: vec![high],
+    })
+}
+
+fn check_three_of_a_kind(
+    counts: &[u8; RANK_COUNT_ARRAY_SIZE],
+    all_values: &[u8],
+) -> Option<HandRank> {
+    find_n_kind_with_kickers(counts, all_values, 3, 2).map(|(trip, kickers)| {
+        let mut t = vec![trip];
+        t.extend(kickers);
+        HandRank {
+            category: HandRankCategory::ThreeKind,
+            tiebreakers: t,
+        }
+    })
+}
+
+fn check_two_pair(counts: &[u8; RANK_COUNT_ARRAY_SIZE], all_values: &[u8]) -> Option<HandRank> {
+    find_two_pair(counts, all_values).map(|(p_high, p_low, kicker)| HandRank {
+        category: HandRankCategory::TwoPair,
+        tiebreakers: vec![p_high, p_low, kicker],
+    })
+}
+
+fn check_one_pair(counts: &[u8; RANK_COUNT_ARRAY_SIZE], all_values: &[u8]) -> Option<HandRank> {
+    find_n_kind_with_kickers(counts, all_values, 2, 3).map(|(pair, kickers)| {
+        let mut t = vec![pair];
+        t.extend(kickers);
+        HandRank {
+            category: HandRankCategory::Pair,
+            tiebreakers: t,
+        }
+    })
+}
+
+fn check_high_card(all_values: &[u8]) -> HandRank {
+    let mut highs = all_values.to_vec();
+    highs.sort_unstable_by(|a, b| b.cmp(a));
+    highs.dedup();
+    highs.truncate(5);
+    HandRank {
+        category: HandRankCategory::HighCard,
+        tiebreakers: highs,
+    }
+}
+
+fn ranks_as_values_unique(cards: &[Card]) -> Vec<u8> {
+    let mut v = cards
+        .iter()
+        .map(|&c| c.rank().high_card_value())
+        .collect::<Vec<u8>>();
+    v.sort_unstable();
+    v.dedup();
+    v
+}
+
+fn straight_high(values_unique_sorted_asc: &Vec<u8>) -> Option<u8> {
+    if values_unique_sorted_asc.is_empty() {
+        return None;
+    }
+    // Build presence map for 2..14, also enable wheel (A as 1) if Ace present.
+    let mut present = [false; RANK_COUNT_ARRAY_SIZE];
+    for &v in values_unique_sorted_asc {
+        if (2..=14).contains(&v) {
+            present[v as usize] = true;
+        }
+    }
+    // wheel A-2-3-4-5: treat Ace as 1
+    if present[14] {
+        present[1] = true;
+    }
+
+    // scan runs from high to low to get highest straight
+    // We'll scan descending for convenience
+    let mut best: Option<u8> = None;
+    let mut run_len = 0usize;
+    let mut last_v = 0u8;
+
+    for v in (1..=14u8).rev() {
+        if present[usize::from(v)] {
+            if last_v == 0 || v + 1 == last_v {
+                run_len += 1;
+            } else {
+                run_len = 1;
+            }
+            if run_len >= 5 {
+                // v..v+4 is a straight; high card is last_v (or v+4), but since we're descending,
+                // when we hit run_len==5, the high is v+4; continue to keep the highest found.
+                let high = v + 4;
+                if best.is_none_or(|b| high > b) {
+                    best = Some(high);
+                }
+            }
+            last_v = v;
+        } else {
+            run_len = 0;
+            last_v = 0;
+        }
+    }
+
+    // normalize high=5 for wheel if detected (A-2-3-4-5)
+    if best == Some(5) {
+        return Some(5);
+    }
+    best
+}
+
+fn find_n_of_a_kind(
+    counts: &[u8; RANK_COUNT_ARRAY_SIZE],
+    n: u8,
+    all_values: &[u8],
+) -> Option<(u8, u8)> {
+    // (rank, top kicker) with rank in 2..14
+    let mut rank = None;
+    for v in (2..=14u8).rev() {
+        if counts[usize::from(v)] == n {
+            rank = Some(v);
+            break;
+        }
+    }
+    if let Some(rk) = rank {
+        let mut kickers = all_values
+            .iter()
+            .copied()
+            .filter(|&v| v != rk)
+            .collect::<Vec<u8>>();
+        kickers.sort_unstable_by(|a, b| b.cmp(a));
+        if let Some(&k) = kickers.first() {
+            return Some((rk, k));
+        }
+    }
+    None
+}
+
+fn find_full_house(counts: &[u8; RANK_COUNT_ARRAY_SIZE]) -> Option<(u8, u8)> {
+    let mut trips = vec![];
+    let mut pairs = vec![];
+    for v in (2..=14u8).rev() {
+        if counts[usize::from(v)] >= 3 {
+            trips.push(v);
+        } else if counts[usize::from(v)] >= 2 {
+            pairs.push(v);
+        }
+    }
+    if trips.is_empty() {
+        return None;
+    }
+    let trip = trips[0];
+    // Use second trip as pair if no pair exists
+    let pair = pairs.first().copied().or_else(|| trips.get(1).copied());
+    pair.map(|p| (trip, p))
+}
+
+fn find_n_kind_with_kickers(
+    counts: &[u8; RANK_COUNT_ARRAY_SIZE],
+    all_values: &[u8],
+    n: u8,
+    kicker_count: usize,
+) -> Option<(u8, Vec<u8>)> {
+    let mut kind_rank = None;
+    for v in (2..=14u8).rev() {
+        if counts[usize::from(v)] == n {
+            kind_rank = Some(v);
+            break;
+        }
+    }
+    if let Some(kr) = kind_rank {
+        let mut kickers = all_values
+            .iter()
+            .copied()
+            .filter(|&v| v != kr)
+            .collect::<Vec<u8>>();
+        kickers.sort_unstable_by(|a, b| b.cmp(a));
+        kickers.dedup();
+        kickers.truncate(kicker_count);
+        return Some((kr, kickers));
+    }
+    None
+}
+
+fn find_two_pair(counts: &[u8; RANK_COUNT_ARRAY_SIZE], all_values: &[u8]) -> Option<(u8, u8, u8)> {
+    let mut pairs = vec![];
+    for v in (2..=14u8).rev() {
+        if counts[usize::from(v)] >= 2 {
+            pairs.push(v);
+        }
+    }
+    if pairs.len() < 2 {
+        return None;
+    }
+    let p_high = pairs[0];
+    let p_low = pairs[1];
+
+    let mut kickers = all_values
+        .iter()
+        .copied()
+        .filter(|&v| v != p_high && v != p_low)
+        .collect::<Vec<u8>>();
+    kickers.sort_unstable_by(|a, b| b.cmp(a));
+    kickers.dedup();
+    let kicker = kickers.first().copied().unwrap_or(2);
+    Some((p_high, p_low, kicker))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_cards;
+
+    /// Split "Ah Kh Qh Jh Th" into the hole cards (first two) and the board.
+    fn hand(cards: &str) -> ([Card; 2], Vec<Card>) {
+        let cards = parse_cards(cards).unwrap();
+        ([cards[0], cards[1]], cards[2..].to_vec())
+    }
+
+    fn rank(cards: &str) -> HandRank {
+        let (hole, board) = hand(cards);
+        evaluate_best_hand(hole, &board)
+    }
+
+    fn assert_rank(cards: &str, category: HandRankCategory, tiebreakers: &[u8]) {
+        let hr = rank(cards);
+        assert_eq!(hr.category, category, "{cards}");
+        assert_eq!(hr.tiebreakers, tiebreakers, "{cards}");
+    }
+
+    // ===== Categories =====
+
+    #[test]
+    fn royal_flush() {
+        assert_rank("Ah Kh Qh Jh Th", HandRankCategory::StraightFlush, &[14]);
+    }
+
+    #[test]
+    fn straight_flush() {
+        assert_rank("9s 8s 7s 6s 5s", HandRankCategory::StraightFlush, &[9]);
+    }
+
+    #[test]
+    fn four_of_a_kind() {
+        assert_rank("Jc Jd Jh Js 4c", HandRankCategory::FourKind, &[11, 4]);
+    }
+
+    #[test]
+    fn full_house() {
+        assert_rank("Tc Td Th 7s 7c", HandRankCategory::FullHouse, &[10, 7]);
+    }
+
+    #[test]
+    fn flush() {
+        assert_rank(
+            "Ad Jd 8d 4d 2d",
+            HandRankCategory::Flush,
+            &[14, 11, 8, 4, 2],
+        );
+    }
+
+    #[test]
+    fn straight() {
+        assert_rank("9c 8d 7h 6s 5c", HandRankCategory::Straight, &[9]);
+    }
+
+    #[test]
+    fn three_of_a_kind() {
+        assert_rank("7c 7d 7h Ks 2c", HandRankCategory::ThreeKind, &[7, 13, 2]);
+    }
+
+    #[test]
+    fn two_pair() {
+        assert_rank("Ac Ad Qh Qs 7c", HandRankCategory::TwoPair, &[14, 12, 7]);
+    }
+
+    #[test]
+    fn one_pair() {
+        assert_rank("Kc Kd Ah 9s 4c", HandRankCategory::Pair, &[13, 14, 9, 4]);
+    }
+
+    #[test]
+    fn high_card() {
+        assert_rank(
+            "Ac Qd 9h 5s 3c",
+            HandRankCategory::HighCard,
+            &[14, 12, 9, 5, 3],
+        );
+    }
+
+    #[test]
+    fn categories_are_ordered() {
+        let ladder = [
+            "Ah Kh Qh Jh Th",
+            "9s 8s 7s 6s 5s",
+            "Jc Jd Jh Js 4c",
+            "Tc Td Th 7s 7c",
+            "Ad Jd 8d 4d 2d",
+            "9c 8d 7h 6s 5c",
+            "7c 7d 7h Ks 2c",
+            "Ac Ad Qh Qs 7c",
+            "Kc Kd Ah 9s 4c",
+            "Ac Qd 9h 5s 3c",
+        ];
+        for pair in ladder.windows(2) {
+            assert!(rank(pair[0]) > rank(pair[1]), "{} > {}", pair[0], pair[1]);
+        }
+    }
+
+    // ===== Straights =====
+
+    #[test]
+    fn wheel_straight_is_five_high() {
+        assert_rank("Ac 2d 3h 4s 5c", HandRankCategory::Straight, &[5]);
+    }
+
+    #[test]
+    fn broadway_straight_is_ace_high() {
+        assert_rank("Ac Kd Qh Js Tc", HandRankCategory::Straight, &[14]);
+    }
+
+    #[test]
+    fn steel_wheel_is_a_five_high_straight_flush() {
+        assert_rank("Ad 2d 3d 4d 5d", HandRankCategory::StraightFlush, &[5]);
+    }
+
+    #[test]
+    fn wheel_loses_to_six_high_straight() {
+        assert!(rank("2c 3d 4h 5s 6c") > rank("Ac 2d 3h 4s 5c"));
+    }
+
+    #[test]
+    fn steel_wheel_loses_to_six_high_straight_flush() {
+        assert!(rank("2d 3d 4d 5d 6d") > rank("Ad 2d 3d 4d 5d"));
+    }
+
+    #[test]
+    fn ace_does_not_wrap_around() {
+        assert_rank(
+            "Qc Kd Ah 2s 3c",
+            HandRankCategory::HighCard,
+            &[14, 13, 12, 3, 2],
+        );
+    }
+
+    #[test]
+    fn longest_run_uses_the_highest_five() {
+        assert_rank("4c 5d 6h 7s 8c 9d Th", HandRankCategory::Straight, &[10]);
+    }
+
+    #[test]
+    fn wheel_with_a_six_plays_six_high() {
+        assert_rank("Ac 2d 3h 4s 5c 6d Kh", HandRankCategory::Straight, &[6]);
+    }
+
+    // ===== Seven card hands =====
+
+    #[test]
+    fn hole_cards_combine_with_the_board() {
+        assert_rank(
+            "Ah 5h Kh 9h 2h 3c 7d",
+            HandRankCategory::Flush,
+            &[14, 13, 9, 5, 2],
+        );
+    }
+
+    #[test]
+    fn flush_uses_the_five_highest_suited_cards() {
+        assert_rank(
+            "2s 3s As Ks 9s 7s Jd",
+            HandRankCategory::Flush,
+            &[14, 13, 9, 7, 3],
+        );
+    }
+
+    #[test]
+    fn straight_flush_beats_a_higher_plain_straight() {
+        assert_rank(
+            "5h 6h 7h 8h 9h Tc 4d",
+            HandRankCategory::StraightFlush,
+            &[9],
+        );
+    }
+
+    #[test]
+    fn two_trips_make_a_full_house() {
+        assert_rank("8c 8d 8h 3s 3c 3d Kh", HandRankCategory::FullHouse, &[8, 3]);
+    }
+
+    #[test]
+    fn full_house_takes_the_highest_pair() {
+        assert_rank(
+            "4c 4d 4h Qs Qc 9d 9h",
+            HandRankCategory::FullHouse,
+            &[4, 12],
+        );
+    }
+
+    #[test]
+    fn quads_kicker_comes_from_the_board() {
+        assert_rank("5c 5d 5h 5s 2c 9d Kh", HandRankCategory::FourKind, &[5, 13]);
+    }
+
+    #[test]
+    fn three_pairs_play_the_top_two_with_best_kicker() {
+        assert_rank(
+            "Kc Kd 8h 8s 3c 3d 2h",
+            HandRankCategory::TwoPair,
+            &[13, 8, 3],
+        );
+    }
+
+    #[test]
+    fn pair_keeps_three_kickers() {
+        assert_rank(
+            "9c 9d Ah Qs 7c 4d 2h",
+            HandRankCategory::Pair,
+            &[9, 14, 12, 7],
+        );
+    }
+
+    #[test]
+    fn high_card_keeps_five_cards() {
+        assert_rank(
+            "Ac Jd 9h 7s 5c 3d 2h",
+            HandRankCategory::HighCard,
+            &[14, 11, 9, 7, 5],
+        );
+    }
+
+    #[test]
+    fn preflop_pocket_pair() {
+        assert_rank("Qc Qd", HandRankCategory::Pair, &[12]);
+    }
+
+    // ===== Tiebreakers =====
+
+    #[test]
+    fn higher_straight_flush_wins() {
+        assert!(rank("Kd Qd Jd Td 9d") > rank("Qc Jc Tc 9c 8c"));
+    }
+
+    #[test]
+    fn higher_quads_win() {
+        assert!(rank("Qc Qd Qh Qs 2c") > rank("Jc Jd Jh Js Ac"));
+    }
+
+    #[test]
+    fn quads_kicker_breaks_ties() {
+        assert!(rank("Qc Qd Qh Qs Ac") > rank("Qc Qd Qh Qs Kc"));
+    }
+
+    #[test]
+    fn full_house_trips_rank_first() {
+        assert!(rank("9c 9d 9h 2s 2c") > rank("8c 8d 8h As Ac"));
+        assert!(rank("9c 9d 9h As Ac") > rank("9c 9d 9h Ks Kc"));
+    }
+
+    #[test]
+    fn flush_compares_every_card() {
+        assert!(rank("Ad Kd 8d 4d 3d") > rank("Ah Kh 8h 4h 2h"));
+        assert!(rank("Ad 2d 3d 4d 6d") > rank("Kh Qh Jh 9h 8h"));
+    }
+
+    #[test]
+    fn higher_straight_wins() {
+        assert!(rank("Tc 9d 8h 7s 6c") > rank("9c 8d 7h 6s 5c"));
+    }
+
+    #[test]
+    fn trips_kickers_break_ties() {
+        assert!(rank("7c 7d 7h Ks 3c") > rank("7c 7d 7h Qs Jc"));
+    }
+
+    #[test]
+    fn two_pair_compares_high_pair_low_pair_then_kicker() {
+        assert!(rank("Ac Ad 2h 2s 3c") > rank("Kc Kd Qh Qs Jc"));
+        assert!(rank("Ac Ad 9h 9s 3c") > rank("Ac Ad 8h 8s Kc"));
+        assert!(rank("Ac Ad 9h 9s 4c") > rank("Ac Ad 9h 9s 3c"));
+    }
+
+    #[test]
+    fn pair_kickers_break_ties() {
+        assert!(rank("Kc Kd Ah 9s 4c") > rank("Kc Kd Ah 8s 7c"));
+        assert!(rank("Kc Kd Ah 9s 4c") > rank("Kc Kd Ah 9s 3c"));
+    }
+
+    #[test]
+    fn high_card_compares_down_to_the_last_card() {
+        assert!(rank("Ac Qd 9h 5s 4c") > rank("Ac Qd 9h 5s 3c"));
+    }
+
+    #[test]
+    fn identical_ranks_tie() {
+        assert_eq!(rank("Ac Kd 8h 8s 3c"), rank("Ad Kh 8c 8d 3s"));
+        assert_eq!(rank("Ah Kh Qh Jh Th"), rank("As Ks Qs Js Ts"));
+    }
+}
@@ -0,0 +1,236 @@
+//! Monte Carlo equity estimation for hold'em hands, used by the server's
+//! all-in equities and hand reviews as well as the client's strength meter.
+
+use std::fmt;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use super::evaluate_best_hand;
+use crate::Card;
+
+/// Outcome counts for one hand over a set of simulated runouts
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EquityResult {
+    pub wins: u32,
+    pub ties: u32,
+    pub losses: u32,
+    /// Sum of the pot fractions won; split pots count as `1 / winners`
+    pub pot_share: f64,
+}
+
+impl EquityResult {
+    #[must_use]
+    pub const fn runouts(&self) -> u32 {
+        self.wins + self.ties + self.losses
+    }
+
+    #[must_use]
+    pub fn win_pct(&self) -> f64 {
+        self.pct(f64::from(self.wins))
+    }
+
+    #[must_use]
+    pub fn tie_pct(&self) -> f64 {
+        self.pct(f64::from(self.ties))
+    }
+
+    #[must_use]
+    pub fn loss_pct(&self) -> f64 {
+        self.pct(f64::from(self.losses))
+    }
+
+    /// Expected share of the pot in percent
+    #[must_use]
+    pub fn equity_pct(&self) -> f64 {
+        self.pct(self.pot_share)
+    }
+
+    fn pct(&self, value: f64) -> f64 {
+        match self.runouts() {
+            0 => 0.0,
+            n => value * 100.0 / f64::from(n),
+        }
+    }
+}
+
+/// Why [`simulate_equity`] cannot deal the runouts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EquityError {
+    /// Fewer than two hands take part
+    TooFewHands,
+    /// The board has more than five cards
+    BoardTooLong(usize),
+    /// A card is in more than one place
+    DuplicateCard(Card),
+    /// The deck runs out of cards for the random hands
+    NotEnoughCards,
+}
+
+impl fmt::Display for EquityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFewHands => write!(f, "at least two hands are needed to compute equity"),
+            Self::BoardTooLong(n) => write!(f, "the board has at most 5 cards, got {n}"),
+            Self::DuplicateCard(card) => write!(f, "card {card} is used more than once"),
+            Self::NotEnoughCards => write!(f, "not enough cards left to deal every hand"),
+        }
+    }
+}
+
+impl std::error::Error for EquityError {}
+
+/// Deal `runouts` random completions of `board`, along with `random_hands`
+/// random hole cards per runout, and count how each of `hands` fares against
+/// the others. Returns one result per hand of `hands`.
+///
+/// Without random hands a complete board has only one possible outcome and is
+/// evaluated once.
+///
+/// # Errors
+///
+/// If there are fewer than two hands in total, more than five board cards, a
+/// card is used twice or the deck is too small for the random hands.
+pub fn simulate_equity<R: Rng + ?Sized>(
+    hands: &[[Card; 2]],
+    random_hands: usize,
+    board: &[Card],
+    runouts: u32,
+    rng: &mut R,
+) -> Result<Vec<EquityResult>, EquityError> {
+    if hands.len() + random_hands < 2 {
+        return Err(EquityError::TooFewHands);
+    }
+    if board.len() > 5 {
+        return Err(EquityError::BoardTooLong(board.len()));
+    }
+    let mut used: Vec<Card> = hands.iter().flatten().chain(board).copied().collect();
+    used.sort_by_key(|&c| u8::from(c));
+    if let Some([dup, _]) = used.windows(2).find(|w| w[0] == w[1]) {
+        return Err(EquityError::DuplicateCard(*dup));
+    }
+
+    let mut deck: Vec<Card> = (0..52)
+        .map(Card::from)
+        .filter(|c| !used.contains(c))
+        .collect();
+    let missing = 5 - board.len();
+    let needed = missing + 2 * random_hands;
+    if deck.len() < needed {
+        return Err(EquityError::NotEnoughCards);
+    }
+    let runouts = if needed == 0 { 1 } else { runouts };
+
+    let mut results = vec![EquityResult::default(); hands.len()];
+    let mut community = Vec::with_capacity(5);
+    let mut ranks = Vec::with_capacity(hands.len() + random_hands);
+    for _ in 0..runouts {
+        let (drawn, _) = deck.partial_shuffle(rng, needed);
+        let (drawn_board, drawn_hands) = drawn.split_at(missing);
+        community.clear();
+        community.extend_from_slice(board);
+        community.extend_from_slice(drawn_board);
+
+        ranks.clear();
+        let random = drawn_hands.chunks_exact(2).map(|c| [c[0], c[1]]);
+        ranks.extend(
+            hands
+                .iter()
+                .copied()
+                .chain(random)
+                .map(|hole| evaluate_best_hand(hole, &community)),
+        );
+        let Some(best) = ranks.iter().max() else {
+            unreachable!("at least two hands");
+        };
+        let winners = ranks.iter().filter(|r| *r == best).count();
+        // There are only a few hands, so the count is exact as f64
+        #[allow(clippy::cast_precision_loss)]
+        let share = 1.0 / winners as f64;
+        // The random hands come last and are not reported
+        for (result, rank) in results.iter_mut().zip(&ranks) {
+            if rank != best {
+                result.losses += 1;
+            } else if winners == 1 {
+                result.wins += 1;
+                result.pot_share += 1.0;
+            } else {
+                result.ties += 1;
+                result.pot_share += share;
+            }
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_cards;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn hand(text: &str) -> [Card; 2] {
+        let cards = parse_cards(text).unwrap();
+        [cards[0], cards[1]]
+    }
+
+    #[test]
+    // A single runout gives exact percentages
+    #[allow(clippy::float_cmp)]
+    fn complete_board_is_evaluated_exactly() {
+        let board = parse_cards("2c 7d Jh 9s 4c").unwrap();
+        let hands = [hand("Ah As"), hand("Kh Ks"), hand("Qd Qc")];
+        let results =
+            simulate_equity(&hands, 0, &board, 1000, &mut StdRng::seed_from_u64(1)).unwrap();
+        assert_eq!(results[0].runouts(), 1);
+        assert_eq!(results[0].win_pct(), 100.0);
+        assert_eq!(results[1].loss_pct(), 100.0);
+        assert_eq!(results[2].loss_pct(), 100.0);
+    }
+
+    #[test]
+    fn equivalent_hands_split_unless_a_flush_lands() {
+        let hands = [hand("Ah Kd"), hand("As Kc")];
+        let board = parse_cards("2h 7h 8h").unwrap();
+        let results =
+            simulate_equity(&hands, 0, &board, 500, &mut StdRng::seed_from_u64(7)).unwrap();
+        assert!(results.iter().all(|r| r.runouts() == 500));
+        // Hearts can only help the first hand; every other runout is a split
+        assert_eq!(results[1].wins, 0);
+        assert_eq!(results[0].losses, 0);
+        assert!(results[0].equity_pct() > 50.0);
+    }
+
+    #[test]
+    fn random_hands_are_dealt_on_a_complete_board() {
+        let board = parse_cards("Ac As Kd Kh 2c").unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+        // Quad aces always win
+        let results = simulate_equity(&[hand("Ah Ad")], 3, &board, 100, &mut rng).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].wins, 100);
+        // Any pair loses to a random king some of the time
+        let results = simulate_equity(&[hand("3h 3d")], 3, &board, 500, &mut rng).unwrap();
+        assert!(results[0].losses > 0);
+        assert!(results[0].equity_pct() < 100.0);
+    }
+
+    #[test]
+    fn invalid_input_is_rejected() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let hands = [hand("Ah Kh"), hand("Ah Qd")];
+        assert_eq!(
+            simulate_equity(&hands, 0, &[], 10, &mut rng),
+            Err(EquityError::DuplicateCard(hands[0][0]))
+        );
+        assert_eq!(
+            simulate_equity(&[hand("Ah Kh")], 0, &[], 10, &mut rng),
+            Err(EquityError::TooFewHands)
+        );
+        assert_eq!(
+            simulate_equity(&[hand("Ah Kh")], 30, &[], 10, &mut rng),
+            Err(EquityError::NotEnoughCards)
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! Table lookup of 7-card hands.
+//!
+//! Seven distinct cards with at most four of a suit rank by the multiset of
+//! their ranks alone. There are 49,205 such multisets, and a perfect hash
+//! numbers them densely. With five or more of a suit the hand is a flush or
+//! better: a full house or quads would need more than the two cards left.
+//! It then ranks by the ranks of that suit alone, which index a table of
+//! 8,192 entries. Both tables hold encoded ranks, filled once from the
+//! analysis in `evaluate_by_analysis` on first use; they take about 230 KB.
+
+use std::sync::OnceLock;
+
+use crate::{Card, HandRank, HandRankCategory};
+
+const RANKS: usize = 13;
+const HAND_SIZE: usize = 7;
+/// The flush table, indexed by the 13-bit set of ranks in the flush suit,
+/// comes first; the rank multisets follow.
+const FLUSH_ENTRIES: usize = 1 << RANKS;
+
+/// `WAYS[n][k]`: ways to deal `k` cards over `n` ranks, at most four each.
+const WAYS: [[usize; HAND_SIZE + 1]; RANKS + 1] = ways();
+
+/// `OFFSETS[rank][left][count]`: the multisets that share the counts of
+/// the ranks before `rank` and have fewer than `count` cards of `rank`,
+/// when `left` cards are still to be dealt.
+const OFFSETS: [[[usize; 5]; HAND_SIZE + 1]; RANKS] = offsets();
+
+const NON_FLUSH_ENTRIES: usize = WAYS[RANKS][HAND_SIZE];
+
+const CATEGORIES: [HandRankCategory; 9] = [
+    HandRankCategory::HighCard,
+    HandRankCategory::Pair,
+    HandRankCategory::TwoPair,
+    HandRankCategory::ThreeKind,
+    HandRankCategory::Straight,
+    HandRankCategory::Flush,
+    HandRankCategory::FullHouse,
+    HandRankCategory::FourKind,
+    HandRankCategory::StraightFlush,
+];
+
+static LOOKUP: OnceLock<Box<[u32]>> = OnceLock::new();
+
+const fn ways() -> [[usize; HAND_SIZE + 1]; RANKS + 1] {
+    let mut ways = [[0; HAND_SIZE + 1]; RANKS + 1];
+    ways[0][0] = 1;
+    let mut n = 1;
+    while n <= RANKS {
+        let mut k = 0;
+        while k <= HAND_SIZE {
+            let mut count = 0;
+            while count <= 4 && count <= k {
+                ways[n][k] += ways[n - 1][k - count];
+                count += 1;
+            }
+            k += 1;
+        }
+        n += 1;
+    }
+    ways
+}
+
+const fn offsets() -> [[[usize; 5]; HAND_SIZE + 1]; RANKS] {
+    let ways = ways();
+    let mut offsets = [[[0; 5]; HAND_SIZE + 1]; RANKS];
+    let mut rank = 0;
+    while rank < RANKS {
+        let mut left = 0;
+        while left <= HAND_SIZE {
+            let mut count = 1;
+            while count <= 4 && count <= left {
+                offsets[rank][left][count] =
+                    offsets[rank][left][count - 1] + ways[RANKS - rank - 1][left - (count - 1)];
+                count += 1;
+            }
+            left += 1;
+        }
+        rank += 1;
+    }
+    offsets
+}
+
+/// Rank seven cards with the lookup table, or `None` if some card is dealt
+/// twice.
+pub(super) fn evaluate_seven(cards: [Card; HAND_SIZE]) -> Option<HandRank> {
+    let table = LOOKUP.get_or_init(build_table);
+    let mut seen = 0u64;
+    let mut counts = [0u8; RANKS];
+    let mut suits = [0u16; 4];
+    for card in cards {
+        if seen & 1 << card.0 != 0 {
+            return None;
+        }
+        seen |= 1 << card.0;
+        let rank = card.rank() as usize;
+        counts[rank] += 1;
+        suits[card.suit().as_usize()] |= 1 << rank;
+    }
+    let code = match suits.iter().find(|ranks| ranks.count_ones() >= 5) {
+        Some(&flush) => table[usize::from(flush)],
+        None => table[FLUSH_ENTRIES + multiset_index(&counts)],
+    };
+    Some(decode(code))
+}
+
+/// Perfect hash of a multiset of seven ranks into `0..NON_FLUSH_ENTRIES`.
+fn multiset_index(counts: &[u8; RANKS]) -> usize {
+    let mut index = 0;
+    let mut left = HAND_SIZE;
+    for (rank, &count) in counts.iter().enumerate() {
+        index += OFFSETS[rank][left][usize::from(count)];
+        left -= usize::from(count);
+    }
+    index
+}
+
+// Ranks are below 13
+#[allow(clippy::cast_possible_truncation)]
+fn build_table() -> Box<[u32]> {
+    let mut table = vec![0; FLUSH_ENTRIES + NON_FLUSH_ENTRIES].into_boxed_slice();
+    for ranks in 0..FLUSH_ENTRIES {
+        if ranks.count_ones() >= 5 {
+            let cards: Vec<Card> = (0..RANKS)
+                .filter(|rank| ranks & 1 << rank != 0)
+                .map(|rank| Card(rank as u8))
+                .collect();
+            table[ranks] = encode(&super::best_rank_from_seven(&cards));
+        }
+    }
+    fill_multisets(&mut table[FLUSH_ENTRIES..], &mut [0; RANKS], 0, HAND_SIZE);
+    table
+}
+
+/// Rank every multiset that extends the counts of the ranks before `rank`.
+// A rank is dealt at most four times
+#[allow(clippy::cast_possible_truncation)]
+fn fill_multisets(table: &mut [u32], counts: &mut [u8; RANKS], rank: usize, left: usize) {
+    if rank == RANKS {
+        if left == 0 {
+            table[multiset_index(counts)] = encode(&super::best_rank_from_seven(&no_flush(counts)));
+        }
+        return;
+    }
+    for count in 0..=left.min(4) {
+        counts[rank] = count as u8;
+        fill_multisets(table, counts, rank + 1, left - count);
+    }
+    counts[rank] = 0;
+}
+
+/// Seven cards with the given ranks, dealt round the suits so that no suit
+/// gets more than two.
+// Card indices are below 52
+#[allow(clippy::cast_possible_truncation)]
+fn no_flush(counts: &[u8; RANKS]) -> Vec<Card> {
+    let ranks = (0..RANKS).flat_map(|rank| std::iter::repeat_n(rank, usize::from(counts[rank])));
+    ranks
+        .enumerate()
+        .map(|(i, rank)| Card(((i % 4) * RANKS + rank) as u8))
+        .collect()
+}
+
+/// Pack a rank into a `u32` that orders like the rank: the category, then
+/// up to five tiebreakers of four bits each. Tiebreakers are at least 1, so
+/// the zeros padding shorter lists decode unambiguously.
+fn encode(rank: &HandRank) -> u32 {
+    debug_assert!(rank.tiebreakers.len() <= 5);
+    (0..5).fold(rank.category as u32, |code, i| {
+        code << 4 | u32::from(rank.tiebreakers.get(i).copied().unwrap_or(0))
+    })
+}
+
+fn decode(code: u32) -> HandRank {
+    let tiebreakers = (0..5)
+        .map(|i| (code >> (16 - 4 * i) & 0xf) as u8)
+        .take_while(|&value| value != 0)
+        .collect();
+    HandRank {
+        category: CATEGORIES[(code >> 20) as usize],
+        tiebreakers,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // A rank is dealt at most four times
+    #[allow(clippy::cast_possible_truncation)]
+    fn the_perfect_hash_numbers_every_multiset_once() {
+        assert_eq!(NON_FLUSH_ENTRIES, 49_205);
+        let mut hit = vec![false; NON_FLUSH_ENTRIES];
+        let mut stack = vec![([0u8; RANKS], 0, HAND_SIZE)];
+        while let Some((counts, rank, left)) = stack.pop() {
+            if rank == RANKS {
+                if left == 0 {
+                    let index = multiset_index(&counts);
+                    assert!(!hit[index], "{counts:?} collides");
+                    hit[index] = true;
+                }
+                continue;
+            }
+            for count in 0..=left.min(4) {
+                let mut counts = counts;
+                counts[rank] = count as u8;
+                stack.push((counts, rank + 1, left - count));
+            }
+        }
+        assert!(hit.iter().all(|&hit| hit));
+    }
+
+    #[test]
+    fn lookup_agrees_with_the_analysis() {
+        // xorshift, so the test needs no random number crate
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..200_000 {
+            let mut deck: Vec<Card> = (0..52).map(Card).collect();
+            let mut cards = [Card(0); HAND_SIZE];
+            for card in &mut cards {
+                let pick = usize::try_from(next() % deck.len() as u64).unwrap_or(0);
+                *card = deck.swap_remove(pick);
+            }
+            assert_eq!(
+                evaluate_seven(cards),
+                Some(super::super::best_rank_from_seven(&cards)),
+                "{cards:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn repeated_cards_are_left_to_the_analysis() {
+        let cards = [0, 0, 1, 2, 3, 4, 5].map(Card);
+        assert_eq!(evaluate_seven(cards), None);
+    }
+}
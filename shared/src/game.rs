@@ -16,16 +16,59 @@ pub enum Stage {
     Showdown,
 }
 
+/// Betting structure that limits the size of bets and raises
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum BettingLimit {
+    /// Any bet up to the player's whole stack
+    #[default]
+    NoLimit,
+    /// Bets and raises up to the size of the pot
+    PotLimit,
+    /// Bets and raises of exactly `small_bet` preflop and on the flop and of
+    /// `big_bet` on the turn and river, with at most four raises per street
+    FixedLimit { small_bet: u32, big_bet: u32 },
+}
+
+impl std::fmt::Display for BettingLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoLimit => write!(f, "No Limit"),
+            Self::PotLimit => write!(f, "Pot Limit"),
+            Self::FixedLimit { small_bet, big_bet } => {
+                write!(f, "Fixed Limit {small_bet}/{big_bet}")
+            }
+        }
+    }
+}
+
+/// What the server does once a hand is over. Without a policy, the next hand
+/// is only dealt when a client sends `NextHand`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GameResetPolicy {
+    /// Deal the next hand after a short delay, while two players have chips
+    ContinueWithSameConfig,
+    /// Like `ContinueWithSameConfig`, but once a single player has chips left
+    /// the game starts over with the same seats and fresh stacks
+    StartNewTournament,
+    /// Players who lose their last chip are sent back to the lobby
+    ReturnToLobby,
+}
+
 /// Simple player action types that can be taken during a hand
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum PlayerAction {
     Fold,
     CheckCall,
     Bet(u32),
+    /// Put the whole remaining stack in, as a bet, raise or call
+    AllIn,
+    /// Post a big blind between hands to be dealt into the next one, for
+    /// players who joined a game in progress
+    PostBlind,
 }
 
 /// Player-side action kinds used in logs/history (keeps richer semantics for history)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ActionKind {
     Fold,
     Check,
@@ -33,22 +76,67 @@ pub enum ActionKind {
     Bet(u32),
     Raise { to: u32, by: u32 },
     PostBlind { kind: BlindKind, amount: u32 },
+    AllIn { amount: u32 },
 }
 
-/// Game-level actions/events (formerly folded into LogEvent)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Comparison of one action of a finished hand with the reference strategy
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GtoAnnotation {
+    pub player_id: PlayerId,
+    pub stage: Stage,
+    pub actual: ActionKind,
+    pub suggested: ActionKind,
+    /// Estimated chips given up by `actual` compared to `suggested`
+    pub ev_diff: f32,
+}
+
+impl GtoAnnotation {
+    /// Whether the actual action was the suggested one
+    #[must_use]
+    pub fn is_optimal(&self) -> bool {
+        self.actual == self.suggested
+    }
+}
+
+/// Game-level actions/events (formerly folded into `LogEvent`)
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum GameAction {
     StageChanged(Stage),
-    DealtHole { player_id: PlayerId },
-    DealtCommunity { cards: Vec<Card> },
-    Showdown { hand_results: Vec<HandResult> },
-    PotAwarded { winners: Vec<PlayerId>, amount: u32 },
+    DealtHole {
+        player_id: PlayerId,
+    },
+    DealtCommunity {
+        cards: Vec<Card>,
+    },
+    Showdown {
+        hand_results: Vec<HandResult>,
+    },
+    /// `amount` is split evenly among `winners`; chips that do not divide
+    /// evenly go one each to the first winners, who are listed in seat order
+    /// starting left of the dealer.
+    PotAwarded {
+        winners: Vec<PlayerId>,
+        amount: u32,
+    },
+    /// A player lost their last chip. In knockout games the bounty on their
+    /// head goes to the player who eliminated them.
+    PlayerEliminated {
+        player_id: PlayerId,
+        bounty_won_by: Option<PlayerId>,
+        #[serde(default)]
+        bounty: u32,
+    },
+    /// An admin removed the player; a bot plays their seat from now on.
+    PlayerKicked {
+        player_id: PlayerId,
+    },
 }
 
-/// A single recorded action/event in the game. This is now the canonical,
-/// typed source-of-truth for both UI and logs. Use ActionEvent::PlayerAction
-/// for player-initiated actions and ActionEvent::GameAction for dealer/stage/etc.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// A single recorded action/event in the game.
+///
+/// This is now the canonical, typed source-of-truth for both UI and logs. Use `ActionEvent::PlayerAction`
+/// for player-initiated actions and `ActionEvent::GameAction` for dealer/stage/etc.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ActionEvent {
     PlayerAction {
         player_id: PlayerId,
@@ -58,14 +146,16 @@ pub enum ActionEvent {
 }
 
 impl ActionEvent {
-    /// Helper to create a PlayerAction event from a player id + ActionKind
-    pub fn player(player_id: PlayerId, action: ActionKind) -> Self {
-        ActionEvent::PlayerAction { player_id, action }
+    /// Helper to create a `PlayerAction` event from a player id + `ActionKind`
+    #[must_use]
+    pub const fn player(player_id: PlayerId, action: ActionKind) -> Self {
+        Self::PlayerAction { player_id, action }
     }
 
-    /// Helper to create a GameAction event
-    pub fn game(action: GameAction) -> Self {
-        ActionEvent::GameAction(action)
+    /// Helper to create a `GameAction` event
+    #[must_use]
+    pub const fn game(action: GameAction) -> Self {
+        Self::GameAction(action)
     }
 }
 
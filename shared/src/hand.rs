@@ -1,5 +1,7 @@
 //! Hand evaluation types for poker hands in the Mental Card Game.
 
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
 use crate::cards::Card;
@@ -20,21 +22,28 @@ pub enum HandRankCategory {
 }
 
 impl HandRankCategory {
-    pub fn to_str(&self) -> &'static str {
+    #[must_use]
+    pub const fn to_str(&self) -> &'static str {
         match self {
-            HandRankCategory::HighCard => "High Card",
-            HandRankCategory::Pair => "Pair",
-            HandRankCategory::TwoPair => "Two Pair",
-            HandRankCategory::ThreeKind => "Three of a Kind",
-            HandRankCategory::Straight => "Straight",
-            HandRankCategory::Flush => "Flush",
-            HandRankCategory::FullHouse => "Full House",
-            HandRankCategory::FourKind => "Four of a Kind",
-            HandRankCategory::StraightFlush => "Straight Flush",
+            Self::HighCard => "High Card",
+            Self::Pair => "One Pair",
+            Self::TwoPair => "Two Pair",
+            Self::ThreeKind => "Three of a Kind",
+            Self::Straight => "Straight",
+            Self::Flush => "Flush",
+            Self::FullHouse => "Full House",
+            Self::FourKind => "Four of a Kind",
+            Self::StraightFlush => "Straight Flush",
         }
     }
 }
 
+impl fmt::Display for HandRankCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.to_str())
+    }
+}
+
 /// Complete hand ranking including category and tiebreakers
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct HandRank {
@@ -42,10 +51,146 @@ pub struct HandRank {
     pub tiebreakers: Vec<u8>,
 }
 
+/// Name of a tiebreaker rank value (2 to 14, with the Ace as 14 or 1).
+const fn rank_value_name(value: u8) -> &'static str {
+    match value {
+        1 | 14 => "Ace",
+        2 => "Two",
+        3 => "Three",
+        4 => "Four",
+        5 => "Five",
+        6 => "Six",
+        7 => "Seven",
+        8 => "Eight",
+        9 => "Nine",
+        10 => "Ten",
+        11 => "Jack",
+        12 => "Queen",
+        13 => "King",
+        _ => "?",
+    }
+}
+
+fn rank_value_plural(value: u8) -> String {
+    match value {
+        6 => "Sixes".to_string(),
+        v => format!("{}s", rank_value_name(v)),
+    }
+}
+
+/// Describes the hand using its tiebreakers, like "Pair of Kings",
+/// "Two Pair, Aces and Queens" or "Flush, Ace-high". Without tiebreakers only
+/// the category is shown.
+impl fmt::Display for HandRank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let t = &self.tiebreakers;
+        let Some(&first) = t.first() else {
+            return write!(f, "{}", self.category);
+        };
+        let second = t.get(1).copied();
+        match (self.category, second) {
+            (HandRankCategory::HighCard, _) => {
+                write!(f, "High Card, {}", rank_value_name(first))
+            }
+            (HandRankCategory::Pair, _) => write!(f, "Pair of {}", rank_value_plural(first)),
+            (HandRankCategory::TwoPair, Some(low)) => write!(
+                f,
+                "Two Pair, {} and {}",
+                rank_value_plural(first),
+                rank_value_plural(low)
+            ),
+            (HandRankCategory::ThreeKind, _) => {
+                write!(f, "Three of a Kind, {}", rank_value_plural(first))
+            }
+            (HandRankCategory::Straight, _) => {
+                write!(f, "Straight, {}-high", rank_value_name(first))
+            }
+            (HandRankCategory::Flush, _) => write!(f, "Flush, {}-high", rank_value_name(first)),
+            (HandRankCategory::FullHouse, Some(pair)) => write!(
+                f,
+                "Full House, {} full of {}",
+                rank_value_plural(first),
+                rank_value_plural(pair)
+            ),
+            (HandRankCategory::FourKind, _) => {
+                write!(f, "Four of a Kind, {}", rank_value_plural(first))
+            }
+            (HandRankCategory::StraightFlush, _) if first == 14 => write!(f, "Royal Flush"),
+            (HandRankCategory::StraightFlush, _) => {
+                write!(f, "Straight Flush, {}-high", rank_value_name(first))
+            }
+            (category, None) => write!(f, "{category}"),
+        }
+    }
+}
+
+/// Ranking of a qualifying low hand for hi-lo split games.
+///
+/// Holds the five unpaired card ranks (Ace = 1, all ranks 8 or lower) in
+/// descending order. Comparison is lexicographic, so a *smaller* value is the
+/// *better* low hand (e.g. `[5, 4, 3, 2, 1]` beats `[6, 4, 3, 2, 1]`).
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LowHandRank(pub [u8; 5]);
+
 /// Result of hand evaluation for a player at showdown
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct HandResult {
     pub player_id: PlayerId,
     pub rank: HandRank,
     pub best_five: [Card; 5],
+    /// Best qualifying low hand, only evaluated in hi-lo games.
+    #[serde(default)]
+    pub lo_rank: Option<LowHandRank>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rank(category: HandRankCategory, tiebreakers: &[u8]) -> String {
+        HandRank {
+            category,
+            tiebreakers: tiebreakers.to_vec(),
+        }
+        .to_string()
+    }
+
+    #[test]
+    fn categories_display_their_names() {
+        assert_eq!(HandRankCategory::HighCard.to_string(), "High Card");
+        assert_eq!(HandRankCategory::Pair.to_string(), "One Pair");
+        assert_eq!(HandRankCategory::TwoPair.to_string(), "Two Pair");
+        assert_eq!(HandRankCategory::ThreeKind.to_string(), "Three of a Kind");
+        assert_eq!(HandRankCategory::Straight.to_string(), "Straight");
+        assert_eq!(HandRankCategory::Flush.to_string(), "Flush");
+        assert_eq!(HandRankCategory::FullHouse.to_string(), "Full House");
+        assert_eq!(HandRankCategory::FourKind.to_string(), "Four of a Kind");
+        assert_eq!(
+            HandRankCategory::StraightFlush.to_string(),
+            "Straight Flush"
+        );
+    }
+
+    #[test]
+    fn hand_ranks_describe_their_tiebreakers() {
+        use HandRankCategory::*;
+        assert_eq!(rank(HighCard, &[14, 12, 9, 5, 3]), "High Card, Ace");
+        assert_eq!(rank(Pair, &[13, 14, 9, 4]), "Pair of Kings");
+        assert_eq!(rank(Pair, &[6, 14, 9, 4]), "Pair of Sixes");
+        assert_eq!(rank(TwoPair, &[14, 12, 7]), "Two Pair, Aces and Queens");
+        assert_eq!(rank(ThreeKind, &[7, 13, 2]), "Three of a Kind, Sevens");
+        assert_eq!(rank(Straight, &[9]), "Straight, Nine-high");
+        assert_eq!(rank(Straight, &[5]), "Straight, Five-high");
+        assert_eq!(rank(Flush, &[14, 11, 8, 4, 2]), "Flush, Ace-high");
+        assert_eq!(rank(FullHouse, &[10, 7]), "Full House, Tens full of Sevens");
+        assert_eq!(rank(FourKind, &[11, 3]), "Four of a Kind, Jacks");
+        assert_eq!(rank(StraightFlush, &[13]), "Straight Flush, King-high");
+        assert_eq!(rank(StraightFlush, &[14]), "Royal Flush");
+    }
+
+    #[test]
+    fn hand_ranks_without_tiebreakers_show_the_category() {
+        assert_eq!(rank(HandRankCategory::FullHouse, &[]), "Full House");
+        assert_eq!(rank(HandRankCategory::TwoPair, &[9]), "Two Pair");
+    }
 }
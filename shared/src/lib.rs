@@ -7,6 +7,7 @@
 // Module declarations
 pub mod cards;
 pub mod communication;
+pub mod evaluation;
 pub mod game;
 pub mod hand;
 pub mod messages;
@@ -4,11 +4,12 @@ use serde::{Deserialize, Serialize};
 
 use crate::cards::Card;
 use crate::game::PlayerAction;
-use crate::game::{ActionEvent, Stage};
+use crate::game::{ActionEvent, BettingLimit, GameResetPolicy, GtoAnnotation, Stage};
+use crate::hand::HandRankCategory;
 use crate::player::{PlayerConfig, PlayerId, PlayerPublic};
 
 /// Complete public view of the game state
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct GameStatePublic {
     pub players: Vec<PlayerPublic>,
     pub community: Vec<Card>,
@@ -27,6 +28,422 @@ pub struct GameStatePublic {
     pub current_bet: u32,
     #[serde(default)]
     pub min_raise: u32,
+    #[serde(default)]
+    pub betting_limit: BettingLimit,
+    /// Progress through the blind schedule, for games that have one
+    #[serde(default)]
+    pub blind_level: Option<BlindLevelInfo>,
+    /// Share of the pot each player in the hand wins on average, `0.0..=1.0`.
+    /// Only set once nobody can bet any more because the players left are
+    /// all-in, when their cards would be turned face up.
+    #[serde(default)]
+    pub pot_equities: Option<Vec<(PlayerId, f32)>>,
+    /// `pot` split into the main pot and side pots, e.g. `("Main Pot", 300)`
+    /// and `("Side Pot 1", 120)`, or just `("Pot", pot)` while everyone can
+    /// still win all of it. Empty when the pot is.
+    #[serde(default)]
+    pub pot_breakdown: Vec<(String, u32)>,
+    /// Player with the dealer button; `None` from servers that don't send it
+    #[serde(default)]
+    pub dealer: Option<PlayerId>,
+    /// Number of the current hand at the table, counting from 1; 0 from
+    /// servers that don't send it
+    #[serde(default)]
+    pub hand_number: u32,
+    /// Hands left to play including the current one, in games of a fixed
+    /// number of hands
+    #[serde(default)]
+    pub hands_remaining: Option<u32>,
+}
+
+/// Position of the current hand in a game's blind schedule
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BlindLevelInfo {
+    /// Current level, counting from 1
+    pub level: u32,
+    /// Hands left at this level including the current one; `None` on the
+    /// last level, which lasts until the game ends
+    pub hands_left: Option<u32>,
+}
+
+/// Change to the action log between two states. Within a hand the log only
+/// grows, so usually just the new entries are sent.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ActionLogDiff {
+    Append(Vec<ActionEvent>),
+    Replace(Vec<ActionEvent>),
+}
+
+/// Fields of a [`GameStatePublic`] that changed since the previous state sent
+/// to a client; `None` means unchanged.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct GameStateDiff {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub players: Option<Vec<PlayerPublic>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub community: Option<Vec<Card>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pot: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sb: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bb: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_act: Option<PlayerId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stage: Option<Stage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub winner_ids: Option<Vec<PlayerId>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action_log: Option<ActionLogDiff>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_bet: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_raise: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub betting_limit: Option<BettingLimit>,
+    /// `Some(None)` when the game no longer has a blind schedule
+    #[serde(
+        default,
+        deserialize_with = "present_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub blind_level: Option<Option<BlindLevelInfo>>,
+    /// `Some(None)` when the all-in equities are no longer shown
+    #[serde(
+        default,
+        deserialize_with = "present_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub pot_equities: Option<Option<Vec<(PlayerId, f32)>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pot_breakdown: Option<Vec<(String, u32)>>,
+    #[serde(
+        default,
+        deserialize_with = "present_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub dealer: Option<Option<PlayerId>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hand_number: Option<u32>,
+    #[serde(
+        default,
+        deserialize_with = "present_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub hands_remaining: Option<Option<u32>>,
+}
+
+/// Deserialize a present field, even `null`, as `Some`, so a changed
+/// optional field survives the round trip as `Some(None)`.
+// Absent and `null` mean different things here: unchanged and changed to `None`
+#[allow(clippy::option_option)]
+fn present_option<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// `Some(new)` when it differs from `old`
+fn changed<T: PartialEq + Clone>(old: &T, new: &T) -> Option<T> {
+    (old != new).then(|| new.clone())
+}
+
+impl GameStatePublic {
+    /// The state as `viewer` may see it, or a spectator for `None`: other
+    /// players' hole cards stay hidden until they are shown down.
+    #[must_use]
+    pub fn seen_by(mut self, viewer: Option<PlayerId>) -> Self {
+        let showdown = self.stage == Stage::Showdown;
+        for p in &mut self.players {
+            if Some(p.id) != viewer && (!showdown || p.has_folded) {
+                p.cards = None;
+            }
+        }
+        self
+    }
+
+    /// Fields of `self` that differ from `old`.
+    #[must_use]
+    pub fn diff_from(&self, old: &Self) -> GameStateDiff {
+        let action_log = if self.action_log == old.action_log {
+            None
+        } else if self.action_log.starts_with(&old.action_log) {
+            Some(ActionLogDiff::Append(
+                self.action_log[old.action_log.len()..].to_vec(),
+            ))
+        } else {
+            Some(ActionLogDiff::Replace(self.action_log.clone()))
+        };
+        GameStateDiff {
+            players: changed(&old.players, &self.players),
+            community: changed(&old.community, &self.community),
+            pot: changed(&old.pot, &self.pot),
+            sb: changed(&old.sb, &self.sb),
+            bb: changed(&old.bb, &self.bb),
+            to_act: changed(&old.to_act, &self.to_act),
+            stage: changed(&old.stage, &self.stage),
+            winner_ids: changed(&old.winner_ids, &self.winner_ids),
+            action_log,
+            current_bet: changed(&old.current_bet, &self.current_bet),
+            min_raise: changed(&old.min_raise, &self.min_raise),
+            betting_limit: changed(&old.betting_limit, &self.betting_limit),
+            blind_level: changed(&old.blind_level, &self.blind_level),
+            pot_equities: changed(&old.pot_equities, &self.pot_equities),
+            pot_breakdown: changed(&old.pot_breakdown, &self.pot_breakdown),
+            dealer: changed(&old.dealer, &self.dealer),
+            hand_number: changed(&old.hand_number, &self.hand_number),
+            hands_remaining: changed(&old.hands_remaining, &self.hands_remaining),
+        }
+    }
+}
+
+impl GameStateDiff {
+    /// Update `base` to the state the diff was computed for.
+    pub fn apply_to(&self, base: &mut GameStatePublic) {
+        fn set<T: Clone>(field: &mut T, value: Option<&T>) {
+            if let Some(value) = value {
+                field.clone_from(value);
+            }
+        }
+        set(&mut base.players, self.players.as_ref());
+        set(&mut base.community, self.community.as_ref());
+        set(&mut base.pot, self.pot.as_ref());
+        set(&mut base.sb, self.sb.as_ref());
+        set(&mut base.bb, self.bb.as_ref());
+        set(&mut base.to_act, self.to_act.as_ref());
+        set(&mut base.stage, self.stage.as_ref());
+        set(&mut base.winner_ids, self.winner_ids.as_ref());
+        match &self.action_log {
+            Some(ActionLogDiff::Append(entries)) => base.action_log.extend_from_slice(entries),
+            Some(ActionLogDiff::Replace(log)) => base.action_log.clone_from(log),
+            None => {}
+        }
+        set(&mut base.current_bet, self.current_bet.as_ref());
+        set(&mut base.min_raise, self.min_raise.as_ref());
+        set(&mut base.betting_limit, self.betting_limit.as_ref());
+        set(&mut base.blind_level, self.blind_level.as_ref());
+        set(&mut base.pot_equities, self.pot_equities.as_ref());
+        set(&mut base.pot_breakdown, self.pot_breakdown.as_ref());
+        set(&mut base.dealer, self.dealer.as_ref());
+        set(&mut base.hand_number, self.hand_number.as_ref());
+        set(&mut base.hands_remaining, self.hands_remaining.as_ref());
+    }
+}
+
+/// Identifier of a table hosted by the server
+pub type TableId = u32;
+
+/// The table that exists on every server and that clients use until they join another one
+pub const DEFAULT_TABLE_ID: TableId = 0;
+
+const fn default_small_blind() -> u32 {
+    5
+}
+
+const fn default_big_blind() -> u32 {
+    10
+}
+
+/// Configuration for a new table
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TableConfig {
+    pub players: Vec<PlayerConfig>,
+    #[serde(default = "default_small_blind")]
+    pub sb: u32,
+    #[serde(default = "default_big_blind")]
+    pub bb: u32,
+    /// Knockout bounty placed on every player, paid in chips to whoever eliminates them
+    #[serde(default)]
+    pub bounty: Option<u32>,
+    #[serde(default)]
+    pub betting_limit: BettingLimit,
+}
+
+impl TableConfig {
+    /// Table with the default blind levels
+    #[must_use]
+    pub const fn new(players: Vec<PlayerConfig>) -> Self {
+        Self {
+            players,
+            sb: default_small_blind(),
+            bb: default_big_blind(),
+            bounty: None,
+            betting_limit: BettingLimit::NoLimit,
+        }
+    }
+}
+
+/// Summary of an active table as listed in the lobby
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TableSummary {
+    pub table_id: TableId,
+    pub player_count: usize,
+    pub sb: u32,
+    pub bb: u32,
+}
+
+/// One player's result in a finished arena hand
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArenaPlayerResult {
+    pub player_id: PlayerId,
+    pub name: String,
+    /// Stack after the hand
+    pub stack: u32,
+    /// Chips won (or lost, if negative) in this hand
+    pub chips_won: i64,
+    pub won: bool,
+    /// Whether the player voluntarily put chips in preflop
+    pub vpip: bool,
+}
+
+/// Outcome of one hand played by bots in arena mode
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArenaHandSummary {
+    pub table_id: TableId,
+    pub hand_number: u32,
+    pub players: Vec<ArenaPlayerResult>,
+}
+
+/// A player in the tournament bracket
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BracketPlayer {
+    pub player_id: PlayerId,
+    pub name: String,
+    pub stack: u32,
+    /// Round the player busted out in; `None` while still in the tournament
+    pub eliminated_in_round: Option<u32>,
+}
+
+/// One table of a tournament round
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BracketTable {
+    pub table_id: TableId,
+    pub players: Vec<BracketPlayer>,
+}
+
+/// Tables playing in one round; rounds get fewer tables as they consolidate
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TournamentRound {
+    pub round: u32,
+    pub tables: Vec<BracketTable>,
+}
+
+/// Standings of a multi-table tournament, oldest round first
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TournamentState {
+    pub rounds: Vec<TournamentRound>,
+}
+
+impl TournamentState {
+    /// Players still in the tournament, counted at the latest round.
+    #[must_use]
+    pub fn remaining_players(&self) -> usize {
+        self.rounds.last().map_or(0, |r| {
+            r.tables
+                .iter()
+                .flat_map(|t| &t.players)
+                .filter(|p| p.eliminated_in_round.is_none())
+                .count()
+        })
+    }
+}
+
+/// Invitation to a table, passed between players as a QR code or text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PairingInvite {
+    /// HTTP/WebSocket address of the server, `host:port`
+    pub server_address: String,
+    /// Iroh node id of the server, for clients connecting over iroh
+    pub node_id: String,
+    pub table_id: TableId,
+}
+
+impl PairingInvite {
+    const SCHEME: &'static str = "mcg://";
+
+    /// Encode the invite as `mcg://<server_address>/<table_id>#<node_id>`.
+    #[must_use]
+    pub fn to_code(&self) -> String {
+        format!(
+            "{}{}/{}#{}",
+            Self::SCHEME,
+            self.server_address,
+            self.table_id,
+            self.node_id
+        )
+    }
+
+    /// Parse an invite code produced by [`PairingInvite::to_code`].
+    #[must_use]
+    pub fn parse(code: &str) -> Option<Self> {
+        let rest = code.trim().strip_prefix(Self::SCHEME)?;
+        let (location, node_id) = rest.split_once('#')?;
+        let (server_address, table_id) = location.rsplit_once('/')?;
+        if server_address.is_empty() || node_id.is_empty() {
+            return None;
+        }
+        Some(Self {
+            server_address: server_address.to_string(),
+            node_id: node_id.to_string(),
+            table_id: table_id.parse().ok()?,
+        })
+    }
+}
+
+/// Multicast group servers announce themselves on for LAN discovery
+pub const DISCOVERY_GROUP: std::net::Ipv4Addr = std::net::Ipv4Addr::new(239, 255, 0, 1);
+/// UDP port of the LAN discovery announcements
+pub const DISCOVERY_PORT: u16 = 7878;
+
+/// Presence announcement multicast by servers on the local network.
+/// The server's address is the source address of the packet.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ServerAd {
+    pub name: String,
+    /// HTTP/WebSocket port of the server
+    pub port: u16,
+    /// Players seated across all tables
+    pub players: usize,
+    pub max: usize,
+}
+
+/// An iroh node listed by the server's `/iroh/peers` endpoint: the server
+/// itself and the peers that connected to it
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PeerInfo {
+    /// Endpoint id (z-base-32), as used in `iroh://NODE_ID` addresses
+    pub node_id: String,
+    /// Unix time in milliseconds the server last heard of the node
+    pub last_seen_ms: u64,
+    pub relay_url: Option<String>,
+}
+
+/// Article listed by the server's `/articles` endpoint; the markdown body is
+/// fetched separately from `/articles/{id}`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArticleMeta {
+    pub id: String,
+    pub title: String,
+    pub summary: String,
+}
+
+/// One row of the server's `/leaderboard`, covering every hand played since
+/// the server started
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LeaderboardEntry {
+    /// Position on the board, starting at 1
+    pub rank: u32,
+    pub player_name: String,
+    pub hands_won: u32,
+    /// Chips won minus chips lost
+    pub total_chips_won: i64,
+    /// Games the player was seated in
+    pub sessions_played: u32,
+    /// Best hand the player showed down
+    pub best_hand: Option<HandRankCategory>,
 }
 
 /// Messages that the frontend sends to the backend
@@ -35,32 +452,173 @@ pub struct GameStatePublic {
 pub enum Frontend2BackendMsg {
     /// Player-initiated action: gets applied to the game
     Action {
+        #[serde(default)]
+        table_id: TableId,
         player_id: PlayerId,
         action: PlayerAction,
     },
     QrReq(String),
     Subscribe,
-    RequestState,
+    RequestState {
+        player_id: PlayerId,
+    },
     Ping,
-    NextHand,
+    NextHand {
+        player_id: PlayerId,
+    },
+    /// Start a game at the table; `player_id` is the sender's own seat
     NewGame {
+        player_id: PlayerId,
         players: Vec<PlayerConfig>,
     },
     /// Push a complete game state to the server (P2P state sync between backend nodes)
-    /// The state is a serialized Game struct from native_mcg
+    /// The state is a serialized Game struct from `native_mcg`
     PushState {
         state: serde_json::Value,
     },
+    /// Open a new table and join it
+    CreateTable {
+        config: TableConfig,
+    },
+    /// Join an existing table; subsequent state broadcasts are scoped to it
+    JoinTable {
+        table_id: TableId,
+    },
+    /// Sit down at the joined table between hands, in the player's preferred
+    /// seat or the first free one. The player posts a big blind to be dealt in.
+    TakeSeat {
+        player: PlayerConfig,
+    },
+    /// Request the list of active tables
+    ListTables,
+    /// Request the bracket of the tournament played on the server's tables
+    RequestTournament,
+    /// Add chips to a short stack between hands
+    Rebuy {
+        player_id: PlayerId,
+        amount: u32,
+    },
+    /// Replace a player by a bot; requires the server's admin token
+    AdminKick {
+        player_id: PlayerId,
+        token: String,
+    },
+    /// Choose what happens after each hand, on every table; requires the
+    /// server's admin token. `None` waits for `NextHand` again.
+    SetResetPolicy {
+        policy: Option<GameResetPolicy>,
+        token: String,
+    },
+    /// Compare every action of the finished hand with the reference strategy
+    ReviewHand,
+    /// Version of the client, sent right after connecting. The server answers
+    /// with `Error("Incompatible client")` if the major versions differ.
+    ClientVersion {
+        version: String,
+    },
+    /// Take back the last player action at the table (development builds only)
+    #[cfg(feature = "undo")]
+    UndoAction,
+}
+
+impl Frontend2BackendMsg {
+    /// The player a message is sent on behalf of. Transports check it against
+    /// the seat of the connection before dispatching the message.
+    #[must_use]
+    pub const fn player_id(&self) -> Option<PlayerId> {
+        match self {
+            Self::Action { player_id, .. }
+            | Self::RequestState { player_id }
+            | Self::NextHand { player_id }
+            | Self::NewGame { player_id, .. }
+            | Self::Rebuy { player_id, .. } => Some(*player_id),
+            Self::TakeSeat { player } => Some(player.id),
+            // `AdminKick` names the kicked player, not the sender
+            _ => None,
+        }
+    }
 }
 
 /// Messages that the backend sends to the frontend
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(tag = "type", content = "data")]
 pub enum Backend2FrontendMsg {
+    /// The message with sequence number `seq` was handled; sent on websockets
+    /// for every numbered client message
+    Ack {
+        seq: u64,
+    },
+    /// First message on a new websocket connection
+    Welcome {
+        /// Version of the server, e.g. `0.1.0`
+        server_version: String,
+    },
     State(GameStatePublic),
+    /// Changes to the last `State` (or `StateDiff`) sent on this connection
+    StateDiff(GameStateDiff),
     Error(String),
     Pong,
+    /// Sent periodically on websockets to keep the connection alive; clients
+    /// use the server's clock to display deadlines
+    Heartbeat {
+        /// Milliseconds since the Unix epoch on the server
+        server_time_ms: u64,
+    },
     QrRes(Box<[u8]>),
+    /// The client is now seated at the given table
+    TableJoined(TableId),
+    Tables(Vec<TableSummary>),
+    /// A player's stack fell below ten big blinds; they may rebuy before the next hand
+    RebuyAvailable {
+        player_id: PlayerId,
+        min_amount: u32,
+        max_amount: u32,
+    },
+    /// An admin replaced the player by a bot. Transports close the kicked
+    /// player's connection.
+    PlayerKicked(PlayerId),
+    /// Invite to a table, answered by `GET /pairing-code`
+    PairingCode {
+        node_id: String,
+        invite_code: String,
+    },
+    /// Answer to `ReviewHand`, one annotation per voluntary action in log order
+    HandReview(Vec<GtoAnnotation>),
+    /// An arena hand finished; sent on the `/arena` endpoint
+    ArenaHandComplete {
+        hand_summary: ArenaHandSummary,
+    },
+    /// Bracket of the running multi-table tournament
+    TournamentState(TournamentState),
+    /// A player lost their last chip under `GameResetPolicy::ReturnToLobby`;
+    /// their client goes back to the lobby
+    PlayerEliminated {
+        player_id: PlayerId,
+        /// Place the player finished in, 1 being the winner
+        final_rank: u32,
+    },
+}
+
+/// A message with its number on a websocket connection.
+///
+/// Each side numbers the messages it sends from 0, so the receiver can
+/// restore their order. The number is serialized as a `seq` field next to
+/// `type` and `data`; messages without one are accepted as well.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Sequenced<M> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u64>,
+    #[serde(flatten)]
+    pub msg: M,
+}
+
+/// Whether a client and server of the given semver versions understand each
+/// other, i.e. agree on the major version. Minor and patch releases keep the
+/// protocol backward compatible.
+#[must_use]
+pub fn versions_compatible(ours: &str, theirs: &str) -> bool {
+    let major = |version: &str| version.trim().split('.').next().map(str::to_owned);
+    major(ours) == major(theirs)
 }
 
 /// Messages that are send between two peers
@@ -71,3 +629,38 @@ pub enum Peer2PeerMsg {
     Pong,
     Payload(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequence_numbers_travel_next_to_the_message() {
+        let ping = Sequenced {
+            seq: Some(3),
+            msg: Frontend2BackendMsg::Ping,
+        };
+        let json = serde_json::to_string(&ping).unwrap();
+        assert_eq!(json, r#"{"seq":3,"type":"Ping"}"#);
+        let parsed: Sequenced<Frontend2BackendMsg> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.seq, Some(3));
+        assert!(matches!(parsed.msg, Frontend2BackendMsg::Ping));
+
+        // Unnumbered messages stay readable, and numbered ones by old peers
+        let plain: Sequenced<Backend2FrontendMsg> =
+            serde_json::from_str(r#"{"type":"Error","data":"oops"}"#).unwrap();
+        assert_eq!(plain.seq, None);
+        let old: Backend2FrontendMsg =
+            serde_json::from_str(r#"{"seq":1,"type":"Ack","data":{"seq":0}}"#).unwrap();
+        assert!(matches!(old, Backend2FrontendMsg::Ack { seq: 0 }));
+    }
+
+    #[test]
+    fn only_major_version_changes_are_incompatible() {
+        assert!(versions_compatible("1.2.3", "1.2.3"));
+        assert!(versions_compatible("1.2.3", "1.4.0"));
+        assert!(versions_compatible("1.2.3", "1.2.9"));
+        assert!(!versions_compatible("1.2.3", "2.0.0"));
+        assert!(!versions_compatible("0.1.0", "1.1.0"));
+    }
+}
@@ -10,7 +10,7 @@ pub struct PlayerId(pub usize);
 
 impl From<usize> for PlayerId {
     fn from(v: usize) -> Self {
-        PlayerId(v)
+        Self(v)
     }
 }
 
@@ -27,7 +27,7 @@ impl std::fmt::Display for PlayerId {
 }
 
 /// Public view of a player's state (what other players can see)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct PlayerPublic {
     pub id: PlayerId,
     pub name: String,
@@ -36,6 +36,20 @@ pub struct PlayerPublic {
     pub has_folded: bool,
     pub all_in: bool,
     pub bet_this_round: u32,
+    /// Avatar color chosen by the player, as RGB
+    #[serde(default)]
+    pub avatar_color: Option<[u8; 3]>,
+    /// Bounty paid for eliminating this player in knockout games
+    #[serde(default)]
+    pub bounty: Option<u32>,
+    /// Fixed seat at the table, counted from 0. Seats left empty are skipped
+    /// by the players list, so this can differ from the player's position.
+    #[serde(default)]
+    pub seat_number: u8,
+    /// Joined a game in progress and sits out until posting a big blind,
+    /// see `PlayerAction::PostBlind`
+    #[serde(default)]
+    pub must_post_blind: bool,
 }
 
 /// Configuration for setting up a player in a new game
@@ -44,16 +58,23 @@ pub struct PlayerConfig {
     pub id: PlayerId,
     pub name: String,
     pub is_bot: bool, // true if driven by bot mechanisms, false if waits for messages
+    /// Avatar color shown to other clients, as RGB
+    #[serde(default)]
+    pub avatar_color: Option<[u8; 3]>,
+    /// Seat reserved for this player, counted from 0. Players without one
+    /// fill the free seats in the order they are listed.
+    #[serde(default)]
+    pub preferred_seat: Option<u8>,
 }
 
 impl PlayerPublic {
     /// Get the name of a player by ID from a slice of players.
     /// Returns the player's name if found, or a default "Player {id}" format if not found.
-    pub fn name_of(players: &[PlayerPublic], id: PlayerId) -> String {
+    #[must_use]
+    pub fn name_of(players: &[Self], id: PlayerId) -> String {
         players
             .iter()
             .find(|p| p.id == id)
-            .map(|p| p.name.clone())
-            .unwrap_or_else(|| format!("Player {}", id))
+            .map_or_else(|| format!("Player {id}"), |p| p.name.clone())
     }
 }